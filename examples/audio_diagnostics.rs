@@ -49,7 +49,7 @@ use brdb::{
 };
 use heightmap::anim::bricks::{ARRAY_GET, ARRAY_VAR, CHANGE_DETECTOR};
 use heightmap::anim::chip::{finish, new_chip};
-use heightmap::anim::clock::{build_clock, gate};
+use heightmap::anim::clock::{Playback, build_clock, gate};
 use heightmap::anim::layout::{
     GATE_HALF, STAGE_PITCH, assert_bricks_dont_overlap, lattice_pos_staged,
 };
@@ -347,7 +347,8 @@ fn one_driven_port(
         &mut chip,
         RAMP_FPS,
         RAMP_FRAMES,
-        true,
+        Playback::default(),
+        false,
         service(0, 0),
     );
 
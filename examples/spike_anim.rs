@@ -9,7 +9,7 @@ use brdb::{
 };
 use heightmap::anim::{
     chip,
-    clock::{build_clock, gate},
+    clock::{Playback, build_clock, gate},
     layout::{GATE_HALF, lattice_pos},
 };
 
@@ -66,7 +66,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     );
     let service = |col: i32, row: i32| lattice_pos(col, row, 2, h, GATE_HALF);
 
-    let clock = build_clock(&mut world, &mut c, 2.0, frames.len(), true, service(0, -2));
+    let looping = Playback::default();
+    let clock = build_clock(&mut world, &mut c, 2.0, frames.len(), looping, false, service(0, -2));
     let detector = gate(
         &mut c,
         "B_1x1_Gate_Expr_ChangeDetectorExec",
@@ -52,12 +52,13 @@ use image::RgbaImage;
 
 use heightmap::anim::bricks::{ARRAY_GET, ARRAY_VAR, CHANGE_DETECTOR};
 use heightmap::anim::chip;
-use heightmap::anim::clock::{build_clock, gate};
+use heightmap::anim::clock::{Playback, build_clock, gate};
 use heightmap::anim::layout::{GATE_HALF, STAGE_PITCH, lattice_pos_staged};
 use heightmap::anim::pack::BANK_FRAMES;
 use heightmap::text::{
     FontPreset, MAX_COMPONENT_CHARS, TextBand, TextOptions, TextTile, add_text_tiles, encode_bands,
 };
+use heightmap::video::adjust::Adjustments;
 use heightmap::video::backend::{Backend, open_video};
 use heightmap::video::scale::{Filter, FitMode};
 use heightmap::video::source::{Source, decode, is_animated, is_video_path};
@@ -331,7 +332,15 @@ fn build_probe_world(source: &dyn FrameSource, opts: &TextOptions) -> Result<(Wo
         lattice_pos_staged(col, row, 2, n_bands, GATE_HALF, STAGE_PITCH)
     };
 
-    let clock = build_clock(&mut world, &mut chip, info.fps, frames, true, service(0, -2));
+    let clock = build_clock(
+        &mut world,
+        &mut chip,
+        info.fps,
+        frames,
+        Playback::default(),
+        false,
+        service(0, -2),
+    );
     let detector = gate(
         &mut chip,
         "B_1x1_Gate_Expr_ChangeDetectorExec",
@@ -532,6 +541,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         start_s: args.start,
         duration_s: args.duration,
         max_frames: BANK_FRAMES,
+        key: None,
+        adjust: Adjustments::default(),
     };
 
     eprintln!(
@@ -43,6 +43,7 @@
 //! `anim::pack`'s row-major hex chunks) would actually reuse.
 use heightmap::anim::pack::MAX_FRAMES;
 use heightmap::text::{FontPreset, PixelMode, TextOptions, encode_bands};
+use heightmap::video::adjust::Adjustments;
 use heightmap::video::backend::{self, Backend};
 use heightmap::video::ffmpeg::{DownloadConsent, ensure_ffmpeg};
 use heightmap::video::scale::{FitMode, Filter};
@@ -568,6 +569,8 @@ fn open_and_drain(path: &Path, size: Option<(u32, u32)>) -> Result<Vec<RgbaImage
             start_s: 0.0,
             duration_s: None,
             max_frames: MAX_FRAMES,
+            key: None,
+            adjust: Adjustments::default(),
         };
         drain_source(&adapted, MAX_FRAMES)
     } else {
@@ -591,6 +594,8 @@ fn open_and_drain(path: &Path, size: Option<(u32, u32)>) -> Result<Vec<RgbaImage
             start_s: 0.0,
             duration_s: None,
             max_frames: MAX_FRAMES,
+            key: None,
+            adjust: Adjustments::default(),
         };
         drain_source(&adapted, MAX_FRAMES)
     }
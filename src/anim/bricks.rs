@@ -9,6 +9,7 @@ use super::clock::{self, gate};
 use super::controls;
 use super::layout::{GATE_HALF, STAGE_PITCH, lattice_pos_staged, rotated_half};
 use super::pack::{self, BANK_FRAMES, HEX_STRIDE};
+use super::seek;
 use crate::progress::{FrameTotal, Progress};
 use crate::video::stream::FrameSource;
 use brdb::{
@@ -104,6 +105,143 @@ impl DisplayBrickStyle {
     }
 }
 
+/// Which way a screen of display bricks faces.
+///
+/// Brickadia's world axes are Unreal's: Z up and left-handed, so a viewer
+/// looking along -X has world -Y on their right, and one looking along -Y has
+/// world +X on their right. Each wall variant uses that to lay image column 0
+/// on the viewer's left, so the picture never reads mirrored. Pixel centres
+/// stay on non-negative coordinates along the wall, as on the floor, with the
+/// bottom row resting on `z = 0`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ScreenOrientation {
+    /// Flat on the ground, presenting its top face (+Z): image columns along
+    /// +X, rows along +Y. The default, and the only layout before walls.
+    #[default]
+    Floor,
+    /// An upright wall whose picture faces world +X, the way text mode's
+    /// glyph wall does.
+    WallXPositive,
+    /// An upright wall whose picture faces world -X.
+    WallXNegative,
+    /// An upright wall whose picture faces world +Y.
+    WallYPositive,
+    /// An upright wall whose picture faces world -Y.
+    WallYNegative,
+}
+
+impl ScreenOrientation {
+    /// Every spelling a user may type, for an error message.
+    pub const NAMES: &'static str = "floor, wall+x, wall-x, wall+y, wall-y";
+
+    /// Parse a CLI spelling. `None` for anything unrecognised -- the caller
+    /// owns the error message, as for [`super::AnimEncoding::parse`].
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "floor" => Some(Self::Floor),
+            "wall+x" | "wall" => Some(Self::WallXPositive),
+            "wall-x" => Some(Self::WallXNegative),
+            "wall+y" => Some(Self::WallYPositive),
+            "wall-y" => Some(Self::WallYNegative),
+            _ => None,
+        }
+    }
+
+    /// The canonical spelling.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Floor => "floor",
+            Self::WallXPositive => "wall+x",
+            Self::WallXNegative => "wall-x",
+            Self::WallYPositive => "wall+y",
+            Self::WallYNegative => "wall-y",
+        }
+    }
+
+    /// The `EBrickDirection` of the face the picture presents -- also the
+    /// anchor-cube face a subtitle over it draws on.
+    pub fn face(self) -> u8 {
+        match self {
+            Self::Floor => crate::text::FACE_Z_POSITIVE,
+            Self::WallXPositive => crate::text::FACE_X_POSITIVE,
+            Self::WallXNegative => crate::text::FACE_X_NEGATIVE,
+            Self::WallYPositive => crate::text::FACE_Y_POSITIVE,
+            Self::WallYNegative => crate::text::FACE_Y_NEGATIVE,
+        }
+    }
+}
+
+/// How many microchips a hex-mode screen is split across: `cols x rows`
+/// panels, each a rectangle of the picture with its own chip, arrays and
+/// pixel gates.
+///
+/// One chip holds a whole screen by default. The game starts dropping frames
+/// near 20 000 gates in one chip, which caps a single-chip hex screen at
+/// about 10 000 pixels; splitting spreads that load. Panel 0 (top left) owns
+/// the clock and the control pins, and hands its frame index to every other
+/// panel through a `Frame Index` output pin wired into each one's `Frame`
+/// input -- one timer, so the panels can never drift apart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PanelGrid {
+    pub cols: u32,
+    pub rows: u32,
+}
+
+impl Default for PanelGrid {
+    fn default() -> Self {
+        Self { cols: 1, rows: 1 }
+    }
+}
+
+/// One panel of a [`PanelGrid`]: a `width x height` rectangle of the picture
+/// whose top-left pixel is (`x`, `y`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Panel {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl PanelGrid {
+    /// Parse a `COLSxROWS` spelling (`2x2`, `4x3`). `None` for anything else,
+    /// including a zero on either axis -- the caller owns the error message.
+    pub fn parse(s: &str) -> Option<Self> {
+        let s = s.to_lowercase();
+        let (cols, rows) = s.split_once('x')?;
+        let (cols, rows) = (cols.trim().parse().ok()?, rows.trim().parse().ok()?);
+        (cols > 0 && rows > 0).then_some(Self { cols, rows })
+    }
+
+    /// The panels a `width x height` picture splits into, row-major from the
+    /// top left. Edges are spread as evenly as integer division allows, so
+    /// neighbouring panels differ by at most one pixel per axis.
+    ///
+    /// Never more panels along an axis than the picture has pixels: a panel
+    /// with no pixels would be a chip with nothing to drive, so a 3-pixel-wide
+    /// picture under `8x1` splits 3 ways. [`super::cost::estimate_chips`] and
+    /// the renderer both split through here, so they always agree.
+    pub fn split(self, width: u32, height: u32) -> Vec<Panel> {
+        let cols = self.cols.clamp(1, width.max(1));
+        let rows = self.rows.clamp(1, height.max(1));
+        let edge = |i: u32, n: u32, len: u32| (u64::from(i) * u64::from(len) / u64::from(n)) as u32;
+        let mut panels = Vec::with_capacity((cols * rows) as usize);
+        for r in 0..rows {
+            for c in 0..cols {
+                let (x, y) = (edge(c, cols, width), edge(r, rows, height));
+                panels.push(Panel {
+                    x,
+                    y,
+                    width: edge(c + 1, cols, width) - x,
+                    height: edge(r + 1, rows, height) - y,
+                });
+            }
+        }
+        panels
+    }
+}
+
+#[derive(Clone)]
 pub struct AnimOptions {
     pub alpha_threshold: u8,
     /// Half-extent of a display pixel, in game units, before the style's own
@@ -113,12 +251,33 @@ pub struct AnimOptions {
     /// pixels always tile flush at any style and extent.
     pub pixel_extent: u16,
     pub brick_style: DisplayBrickStyle,
+    /// Lay the screen on the floor (default) or stand it up as a wall. Read by
+    /// every brick encoding through [`add_display_bricks`]'s
+    /// [`ScreenGeometry`], which the chip shell, interaction plane and
+    /// subtitle anchor are all placed from; text mode always builds its own
+    /// +X-facing wall and ignores it. Changes no gate, wire or brick count.
+    pub orientation: ScreenOrientation,
+    /// Split the screen across this many chips ([`PanelGrid`]). Only
+    /// [`build_brick_world`] reads it; the other renderers build one chip and
+    /// the CLI refuses a split for them.
+    pub panels: PanelGrid,
     pub external_clock: bool,
     /// Repeat the clip forever (`true`, the default) or stop on its last
     /// frame (`false`). Sets `Timer.Limit` (see
     /// [`crate::anim::clock::stop_limit`]); changes no gate, wire or brick
     /// count. Inert under [`Self::external_clock`], which builds no timer.
     pub loop_playback: bool,
+    /// The order the clock plays the clip in, and how many times
+    /// ([`clock::Playback`]); [`Self::loop_playback`] off is one play of it.
+    /// First to last, forever, by default. Any other order adds
+    /// [`clock::Playback::clock_cost`] to the clock, and cannot take
+    /// [`Self::seek`], whose jumps assume first to last. Inert under
+    /// [`Self::external_clock`].
+    pub playback: clock::Playback,
+    /// Seek and skip buttons beside the control buttons ([`super::seek`]).
+    /// Empty by default, which builds the plain clock. Built whether or not
+    /// [`Self::control_buttons`] is on; inert under [`Self::external_clock`].
+    pub seek: super::seek::SeekControls,
     /// Pre-generate three physical labelled button bricks on the main grid,
     /// wired into the clock's `Pause`/`Restart`/`Resume` pins, so a fresh
     /// render is controllable out of the box (see [`super::controls`]).
@@ -141,10 +300,15 @@ pub struct AnimOptions {
     /// Defaults to `BANK_FRAMES`. Lowering it is how the multi-bank graph is
     /// tested without building a 65 536-frame clip.
     pub bank_size: usize,
-    /// Text mode only: quantize every frame to at most this many colours with
-    /// a median-cut palette ([`super::palette::Palette`]) before encoding.
-    /// `0` (default) means no quantization; both brick encodings ignore this
-    /// field, since they spend a fixed cost per pixel no palette can change.
+    /// Text mode: quantize every frame to at most this many colours with a
+    /// median-cut palette ([`super::palette::Palette`]) before encoding. `0`
+    /// (default) means no quantization.
+    ///
+    /// The palette encoding ([`super::palette_bricks`]) reads it as its
+    /// palette's size instead, `0` meaning
+    /// [`super::palette_bricks::DEFAULT_PALETTE_COLORS`]. The other brick
+    /// encodings ignore it, since they spend a fixed cost per pixel no palette
+    /// can change.
     pub colors: usize,
     /// Text mode only: the font, glyph and component geometry a text render
     /// uses. Lives on `AnimOptions` rather than as an extra parameter because
@@ -167,12 +331,12 @@ pub struct AnimOptions {
     /// How many world units the subtitle anchor is lifted "up the picture"
     /// from its bare bottom-centre baseline. Defaults to
     /// [`super::subtitle_display::DEFAULT_SUBTITLE_LIFT`], calibrated by eye
-    /// in text mode only. Applied along +z for text mode's upright wall
-    /// and -y for both brick encodings' flat, ground-facing screen (see
-    /// `subtitle_extent`'s doc); the brick-mode axis is unverified by eye.
+    /// in text mode only. Applied along +z for text mode's upright wall and
+    /// a brick-mode wall, and -y for a brick-mode floor screen (see
+    /// `subtitle_extent`'s doc); the floor axis is unverified by eye.
     /// Inert while [`Self::subtitles`] is `None`.
     ///
-    /// Both brick encodings accept any lift, including one that carries the
+    /// The brick encodings accept any lift, including one that carries the
     /// anchor to a negative `y` -- brdb round-trips negative main-grid
     /// coordinates fine. Text mode rejects a lift pushing its anchor below
     /// `z = 0`, since that mode keeps its whole main grid non-negative by
@@ -194,14 +358,18 @@ impl Default for AnimOptions {
             alpha_threshold: 128,
             pixel_extent: 1,
             brick_style: DisplayBrickStyle::Micro,
+            orientation: ScreenOrientation::Floor,
+            panels: PanelGrid::default(),
             external_clock: false,
             // Looping, which is what every render did before the flag
             // existed. The default has to stay this way or an unchanged
             // command line would quietly produce a different save.
             loop_playback: true,
+            playback: clock::Playback::default(),
             // ON: a fresh render ships pausable/restartable/resumable. Turned
             // off with `--no-control-buttons` (a default-on off-switch).
             control_buttons: true,
+            seek: super::seek::SeekControls::default(),
             glow: false,
             srgb_to_linear: false,
             bank_size: BANK_FRAMES,
@@ -220,16 +388,53 @@ impl Default for AnimOptions {
 /// disagree with the brick actually placed. Shared by both brick encodings
 /// ([`build_brick_world`] and [`super::color_bricks::build_color_array_world`]).
 pub struct ScreenGeometry {
-    /// A display brick's real on-ground half-extent on x and y, after the
-    /// style's own scale (unscaled for `Micro`, 5x for `SmoothTile`). Read off
-    /// the constructed `BrickType`, never re-derived.
+    /// A display brick's real half-extent across the picture (both of its
+    /// in-picture axes), after the style's own scale (unscaled for `Micro`,
+    /// 5x for `SmoothTile`). Read off the constructed `BrickType`, never
+    /// re-derived.
     pub footprint: i32,
-    /// A display brick's own half-height. Every style rests its underside on
-    /// z=0 by sitting at this z, so it is also the brick's centre height.
-    pub half_height: i32,
+    /// A display brick's own half-extent along the picture's normal -- its
+    /// half-height on the floor, its half-thickness in a wall. The screen's
+    /// back face sits at `out = 0` in [`Self::place`], so this is also the
+    /// pixel centres' `out`.
+    pub half_depth: i32,
     /// Distance between neighbouring pixels' centres: `2 * footprint`, the
     /// only pitch at which they touch without gapping or overlapping.
     pub pitch: i32,
+    /// Which way the picture faces; [`Self::place`] is the only reader.
+    pub orientation: ScreenOrientation,
+}
+
+impl ScreenGeometry {
+    /// The world position of a point given in the screen's own frame, for a
+    /// `cols x rows` picture: `right` from the picture's left edge, `up` from
+    /// its bottom edge, `out` from its back face toward the viewer.
+    ///
+    /// Every placement that must follow the screen (the pixels, the chip
+    /// shell, the interaction plane, a subtitle) goes through here, so a new
+    /// orientation is one more arm in this match. `Floor` reproduces the
+    /// layout the floor screen always had: pixel (col, row) centred at
+    /// `(col * pitch, row * pitch, half_depth)`.
+    pub fn place(&self, cols: i32, rows: i32, right: i32, up: i32, out: i32) -> Position {
+        let (fp, depth) = (self.footprint, 2 * self.half_depth);
+        let (width, height) = (cols * self.pitch, rows * self.pitch);
+        let (x, y, z) = match self.orientation {
+            ScreenOrientation::Floor => (right - fp, height - fp - up, out),
+            ScreenOrientation::WallXPositive => (out, width - fp - right, up),
+            ScreenOrientation::WallXNegative => (depth - out, right - fp, up),
+            ScreenOrientation::WallYPositive => (right - fp, out, up),
+            ScreenOrientation::WallYNegative => (width - fp - right, depth - out, up),
+        };
+        Position { x, y, z }
+    }
+
+    /// Pixel (`col`, `row`)'s brick centre, `row` counting down from the
+    /// picture's top as every packer does.
+    pub fn pixel(&self, cols: i32, rows: i32, col: i32, row: i32) -> Position {
+        let fp = self.footprint;
+        let (right, up) = (col * self.pitch + fp, (rows - 1 - row) * self.pitch + fp);
+        self.place(cols, rows, right, up, self.half_depth)
+    }
 }
 
 /// Where a subtitle sits relative to a screen of display bricks: laid across
@@ -237,23 +442,24 @@ pub struct ScreenGeometry {
 /// brick encodings, derived from the geometry [`add_display_bricks`] already
 /// returned.
 ///
-/// A display-brick screen lies flat: `x` is the image column, `y` the image
-/// row, `z` a constant. The anchor is centred on `x`, at the bottom of `y`
-/// (rows increase toward the picture's bottom, so `--subtitle-lift`'s axis
-/// here is `-y`, the opposite of text mode's `+z`), and one anchor-cube above
-/// the screen's top face on `z` so glyphs draw a full cube clear of the
-/// picture rather than z-fighting it or overlapping a display brick.
+/// Placed through [`ScreenGeometry::place`], so it follows the screen's
+/// orientation: centred across the picture, `--subtitle-lift` up from its
+/// bottom edge, and one anchor-cube out from its front face so glyphs draw a
+/// full cube clear of the picture rather than z-fighting it or overlapping a
+/// display brick. On the floor "up the picture" is world `-y` (rows increase
+/// toward the picture's bottom); on a wall it is `+z`, as in text mode.
 ///
-/// `face` is `FACE_Z_POSITIVE` because the screen presents its top (`+Z`) to
-/// the viewer; the default `X_Positive` face would stand the subtitle
-/// edge-on and unreadable. Which way "along the line" points within that
-/// plane is unverified by eye for this flat orientation -- only text mode's
-/// vertical wall has been checked.
+/// `face` is the face the picture presents ([`ScreenOrientation::face`]):
+/// `FACE_Z_POSITIVE` on the floor, where the default `X_Positive` face would
+/// stand the subtitle edge-on and unreadable, and the wall's own side face
+/// otherwise. Which way "along the line" points within the floor's plane is
+/// unverified by eye -- only upright walls have been checked.
 ///
-/// A negative `y` here (any picture 4 rows or shorter, with default options)
-/// is legal: brdb round-trips negative main-grid coordinates exactly, and the
-/// chip shell itself already sits at a negative main-grid `x` in every
-/// render (see [`new_screen_chip`]). Text mode still rejects its equivalent,
+/// A negative `y` on the floor (any picture 4 rows or shorter, with default
+/// options), or a negative wall coordinate from a negative lift, is legal:
+/// brdb round-trips negative main-grid coordinates exactly, and a floor
+/// screen's chip shell itself already sits at a negative main-grid `x` (see
+/// [`new_screen_chip`]). Text mode still rejects its equivalent,
 /// because that mode keeps its whole main grid non-negative by construction.
 pub fn subtitle_extent(
     geometry: &ScreenGeometry,
@@ -261,17 +467,17 @@ pub fn subtitle_extent(
     rows: i32,
     lift: f32,
 ) -> Result<super::subtitle_display::ScreenExtent, String> {
-    let baseline_y = (rows.max(1) - 1) * geometry.pitch + geometry.footprint;
-    let lift_units = super::subtitle_display::lift_units(lift);
-    let y = baseline_y - lift_units;
+    let (cols, rows) = (cols.max(1), rows.max(1));
     Ok(super::subtitle_display::ScreenExtent {
-        anchor: Position {
-            x: (cols.max(1) - 1) * geometry.pitch / 2,
-            y,
-            z: 2 * geometry.half_height + crate::text::ANCHOR_CUBE_HALF,
-        },
+        anchor: geometry.place(
+            cols,
+            rows,
+            cols * geometry.footprint,
+            super::subtitle_display::lift_units(lift),
+            2 * geometry.half_depth + crate::text::ANCHOR_CUBE_HALF,
+        ),
         row_height: geometry.pitch as f32,
-        face: crate::text::FACE_Z_POSITIVE,
+        face: geometry.orientation.face(),
     })
 }
 
@@ -292,17 +498,30 @@ pub fn add_display_bricks(
 ) -> (HashMap<usize, usize>, ScreenGeometry) {
     // .max(1): a 0 extent collapses the brick to a point.
     let extent = opts.pixel_extent.max(1);
-    let brick_type = opts.brick_style.brick_type(extent);
-    let BrickType::Procedural { size: brick_size, .. } = &brick_type else {
+    let mut brick_type = opts.brick_style.brick_type(extent);
+    let BrickType::Procedural { size: brick_size, .. } = &mut brick_type else {
         unreachable!("DisplayBrickStyle::brick_type always builds a Procedural brick")
     };
     let geometry = ScreenGeometry {
         footprint: brick_size.x as i32,
-        half_height: brick_size.z as i32,
+        half_depth: brick_size.z as i32,
         // Two adjacent pixels' bricks only touch -- neither gapped nor
         // overlapping -- at twice the real footprint.
         pitch: 2 * brick_size.x as i32,
+        orientation: opts.orientation,
     };
+    // A wall stands the brick up by swapping its size axes rather than by
+    // rotating it: `local_bounds` ignores rotation, so a rotated brick would
+    // overlap-check as if it still lay flat.
+    match opts.orientation {
+        ScreenOrientation::Floor => {}
+        ScreenOrientation::WallXPositive | ScreenOrientation::WallXNegative => {
+            std::mem::swap(&mut brick_size.x, &mut brick_size.z)
+        }
+        ScreenOrientation::WallYPositive | ScreenOrientation::WallYNegative => {
+            std::mem::swap(&mut brick_size.y, &mut brick_size.z)
+        }
+    }
 
     let mut brick_of: HashMap<usize, usize> = HashMap::new();
     for row in 0..h {
@@ -312,13 +531,10 @@ pub fn add_display_bricks(
             }
             let (brick, id) = Brick {
                 asset: brick_type.clone(),
-                // z is the brick's own half-height, so every style rests its
-                // underside on z=0 (a hardcoded 2 would float a micro brick).
-                position: Position {
-                    x: col * geometry.pitch,
-                    y: row * geometry.pitch,
-                    z: geometry.half_height,
-                },
+                // Centred half a depth out from the back face (on the floor,
+                // the brick's own half-height, so every style rests its
+                // underside on z=0 -- a hardcoded 2 would float a micro brick).
+                position: geometry.pixel(w, h, col, row),
                 // Intensity 0 is the dimmest glow, not "off". Non-glow arm
                 // keeps 5, matching the heightmap path's default.
                 material: if opts.glow { GLOW } else { PLASTIC },
@@ -336,11 +552,12 @@ pub fn add_display_bricks(
 
 /// Start the microchip that drives a screen of the given size and geometry.
 ///
-/// The shell sits beside the screen, on x, never stacked on top of it -- a
-/// stacked shell can overlap a display brick's z span, and the game silently
-/// drops one of the two bricks. `assert_bricks_dont_overlap` only needs one
-/// axis clear, and `chip::finish` still asserts main-grid non-overlap as the
-/// safety net either way.
+/// On the floor the shell sits beside the screen, on x, never stacked on top
+/// of it -- a stacked shell can overlap a display brick's z span, and the game
+/// silently drops one of the two bricks. A wall's shell sits on the ground
+/// directly behind the wall's back face, out of the picture's way.
+/// `assert_bricks_dont_overlap` only needs one axis clear, and `chip::finish`
+/// still asserts main-grid non-overlap as the safety net either way.
 ///
 /// Clearance is computed from the real half-extents in play, not a fixed
 /// guess: the shell's x half-extent comes from `local_bounds`, and the real
@@ -349,7 +566,8 @@ pub fn add_display_bricks(
 /// value would leave the shell overlapping the screen.
 ///
 /// The entity location computed here aims the interaction plane at the
-/// screen's middle but does not land there: `chip::finish` does not centre
+/// middle of the picture, a little out from its front face (above it, on the
+/// floor), but does not land there: `chip::finish` does not centre
 /// the chip's contents on the grid origin, and `chip::recompute_plane_extent`
 /// shifts `entity.location` again afterward. This is only the plane's
 /// starting point.
@@ -363,29 +581,143 @@ pub fn new_screen_chip(
     h: i32,
     geometry: &ScreenGeometry,
 ) -> chip::Chip {
-    let shell_half_x = {
+    let whole = Panel { x: 0, y: 0, width: w as u32, height: h as u32 };
+    new_panel_chip(world, w, h, geometry, &whole, 0)
+}
+
+/// [`new_screen_chip`] for one [`Panel`] of a `w x h` screen split across
+/// several chips.
+///
+/// `slot` is the panel's index: each shell sits one shell-width further
+/// along the row [`new_screen_chip`] starts (along y beside a floor, along
+/// the wall behind one), so no two shells collide, and the interaction plane
+/// aims at the panel's own middle rather than the whole screen's.
+pub fn new_panel_chip(
+    world: &mut World,
+    w: i32,
+    h: i32,
+    geometry: &ScreenGeometry,
+    panel: &Panel,
+    slot: i32,
+) -> chip::Chip {
+    let shell_half = {
         let (min, max) = Brick { asset: B_MICROCHIP, ..Default::default() }.local_bounds();
-        (max.x - min.x) / 2
+        (max - min) / 2
     };
-    let shell_x = -(shell_half_x + geometry.footprint);
-    let screen_span = |n: i32| (n - 1).max(0) * geometry.pitch;
-    let plane_anchor = Vector3f {
-        x: screen_span(w) as f32 / 2.0,
-        y: screen_span(h) as f32 / 2.0,
-        // Screen's top face is at 2*half_height (bricks rest their underside
-        // on z=0); rise clear of it from there.
-        z: (2 * geometry.half_height + PLANE_HEIGHT_ABOVE_SCREEN) as f32,
+    let fp = geometry.footprint;
+    let shell = match geometry.orientation {
+        ScreenOrientation::Floor => Position {
+            x: -(shell_half.x + fp),
+            y: slot * 2 * shell_half.y,
+            z: shell_half.z,
+        },
+        // Behind the back face (`out` negative), resting on the ground.
+        ScreenOrientation::WallXPositive | ScreenOrientation::WallXNegative => {
+            geometry.place(w, h, fp + slot * 2 * shell_half.y, shell_half.z, -shell_half.x)
+        }
+        ScreenOrientation::WallYPositive | ScreenOrientation::WallYNegative => {
+            geometry.place(w, h, fp + slot * 2 * shell_half.x, shell_half.z, -shell_half.y)
+        }
     };
-    chip::new_chip(
-        world,
-        Position { x: shell_x, y: 0, z: 2 },
-        plane_anchor,
-        IntVector { x: 5, y: 5, z: 5 },
-    )
+    // The panel's centre, clear of its front face: the back face is at
+    // `out = 0`, so the front is at 2*half_depth; rise clear of it from there.
+    let (x, y) = (panel.x as i32, panel.y as i32);
+    let (pw, ph) = (panel.width as i32, panel.height as i32);
+    let anchor = geometry.place(
+        w,
+        h,
+        (2 * x + pw) * fp,
+        (2 * (h - y) - ph) * fp,
+        2 * geometry.half_depth + PLANE_HEIGHT_ABOVE_SCREEN,
+    );
+    let plane_anchor = Vector3f { x: anchor.x as f32, y: anchor.y as f32, z: anchor.z as f32 };
+    chip::new_chip(world, shell, plane_anchor, IntVector { x: 5, y: 5, z: 5 })
+}
+
+/// The per-(chunk, bank) string arrays and gets for `chunks`, hung off
+/// `spine`, returning each chunk's current frame string (bank-selected).
+///
+/// Shared by the two string encodings ([`build_brick_world`] and
+/// [`super::palette_bricks::build_palette_world`]), which differ only in what
+/// a chunk's characters mean. `place` is the renderer's service-row placement,
+/// as for [`cascade::bank_cascade`]: arrays and gets go on row `-5`, selects
+/// on row `-9`.
+pub fn add_chunk_arrays(
+    world: &mut World,
+    chip: &mut chip::Chip,
+    chunks: &[pack::Chunk],
+    bank_size: usize,
+    spine: &cascade::BankCascade,
+    place: impl Fn(i32, i32) -> Position,
+) -> Vec<WirePort> {
+    let cascade::BankCascade { index_of_bank, ge, entry_of_bank } = spine;
+    let n_banks = index_of_bank.len();
+    // get_of[bank][chunk]
+    let mut get_of: Vec<Vec<usize>> = vec![Vec::with_capacity(chunks.len()); n_banks];
+    for (ci, chunk) in chunks.iter().enumerate() {
+        for (bi, frames) in pack::bank_frames(&chunk.frames, bank_size).iter().enumerate() {
+            let col = ((ci * n_banks + bi) * 2) as i32;
+            let array = gate(chip, "B_1x1_Gate_Variable_Array", ARRAY_VAR,
+                place(col, -5), vec![(
+                    "Value",
+                    Box::new(WireArrayVariant::StringArray(frames.to_vec()))
+                        as Box<dyn AsBrdbValue>,
+                )]);
+            let get = gate(chip, "B_1x1_Gate_Exec_ArrayVar_Get", ARRAY_GET,
+                place(col + 1, -5), vec![]);
+            world.add_wire_connection(
+                WirePort::new(array, ARRAY_VAR, "ArrayVarRef"),
+                WirePort::new(get, ARRAY_GET, "ArrayVarRef"),
+            );
+            world.add_wire_connection(
+                index_of_bank[bi].clone(),
+                WirePort::new(get, ARRAY_GET, "Index"),
+            );
+            get_of[bi].push(get);
+        }
+    }
+
+    // Exec: each bank's gets chain off that bank's entry. Per-chunk here rather
+    // than a fan-out, because at one Get per chunk the chain is a handful of
+    // gates deep, not thousands (see `color_bricks` for why the pixel-major
+    // encoding must fan out instead). Every Get's Exec still takes exactly one
+    // source, so no exec input ever gains a second.
+    for bi in 0..n_banks {
+        let mut prev = entry_of_bank[bi].clone();
+        for &get in &get_of[bi] {
+            world.add_wire_connection(prev, WirePort::new(get, ARRAY_GET, "Exec"));
+            prev = WirePort::new(get, ARRAY_GET, "ExecOut");
+        }
+    }
+
+    // Value: one select per chunk per boundary, cascading. For a frame in
+    // bank j, ge[0..j] are true so select j picks bank j, and every later
+    // select passes it through unchanged.
+    let mut value_of_chunk = Vec::with_capacity(chunks.len());
+    for ci in 0..chunks.len() {
+        let mut value = WirePort::new(get_of[0][ci], ARRAY_GET, "Value");
+        for bi in 1..n_banks {
+            let sel = gate(chip, "B_1x1_Gate_Expr_Select", SELECT,
+                place((ci * n_banks + bi) as i32, -9), vec![]);
+            world.add_wire_connection(ge[bi - 1].clone(), WirePort::new(sel, SELECT, "bSelectB"));
+            world.add_wire_connection(value, WirePort::new(sel, SELECT, "InputA"));
+            world.add_wire_connection(
+                WirePort::new(get_of[bi][ci], ARRAY_GET, "Value"),
+                WirePort::new(sel, SELECT, "InputB"),
+            );
+            value = WirePort::new(sel, SELECT, "Output");
+        }
+        value_of_chunk.push(value);
+    }
+    value_of_chunk
 }
 
 /// Streams `source` into a wired, animated display-brick [`World`].
 ///
+/// Split across one chip per [`AnimOptions::panels`] panel; the default
+/// single panel is the one-chip screen this has always built, brick for
+/// brick and wire for wire.
+///
 /// A cancelled `progress` (checked once per frame during decode, once per
 /// pixel during the build) returns an empty `World`, never a partial one --
 /// deliberately not an error, since cancellation is a normal outcome. It is
@@ -401,6 +733,7 @@ pub fn build_brick_world(
 ) -> Result<World, String> {
     let info = source.info();
     let (w, h) = (info.width as i32, info.height as i32);
+    let panels = opts.panels.split(info.width, info.height);
 
     // One fused streaming pass builds both the per-chunk frame strings and
     // the per-pixel visibility bitmap; no frame is ever retained (see
@@ -408,10 +741,18 @@ pub fn build_brick_world(
     // immediately -- treating it as end-of-clip would silently drop the tail.
     // `FrameTotal` falls back to the source's estimate when no exact count is
     // available, and says which it is.
+    //
+    // One packer per panel, each fed its own crop of the frame; an unsplit
+    // screen's single packer takes the frame as-is, with no copy.
     FrameTotal::new(info.frame_count_hint, source.frame_count_estimate())
         .begin(progress, "packing frames");
-    let mut packer = pack::Packer::new(info.width, info.height, opts.alpha_threshold, HEX_STRIDE)
-        .linearize(opts.srgb_to_linear);
+    let mut packers: Vec<pack::Packer> = panels
+        .iter()
+        .map(|p| {
+            pack::Packer::new(p.width, p.height, opts.alpha_threshold, HEX_STRIDE)
+                .linearize(opts.srgb_to_linear)
+        })
+        .collect();
     // Wrapped in a closure so `progress.finish()` below runs on every exit,
     // including a mid-stream error -- otherwise the bar is left "stuck".
     // Cancellation breaks this same loop rather than erroring, so `stream`
@@ -421,7 +762,26 @@ pub fn build_brick_world(
         let mut stream = source.open()?;
         let mut seen: u64 = 0;
         while let Some(frame) = stream.next()? {
-            packer.push_frame(&frame)?;
+            if let [packer] = packers.as_mut_slice() {
+                packer.push_frame(&frame)?;
+            } else {
+                // Checked here rather than left to each packer: a crop of a
+                // wrongly-sized frame is silently clipped to fit.
+                if frame.dimensions() != (info.width, info.height) {
+                    return Err(format!(
+                        "frame {seen} is {}x{}, but the source's SourceInfo reported {}x{} -- \
+                         every frame a FrameStream emits must match info()'s dimensions",
+                        frame.width(),
+                        frame.height(),
+                        info.width,
+                        info.height
+                    ));
+                }
+                for (packer, p) in packers.iter_mut().zip(&panels) {
+                    let crop = image::imageops::crop_imm(&frame, p.x, p.y, p.width, p.height);
+                    packer.push_frame(&crop.to_image())?;
+                }
+            }
             // Borrows the frame's buffer -- no copy. `Progress::frame`'s
             // default body is a no-op.
             progress.frame(frame.width(), frame.height(), frame.as_raw());
@@ -443,7 +803,8 @@ pub fn build_brick_world(
         return Ok(World::new());
     }
 
-    let (chunks, visible) = packer.finish();
+    let packed: Vec<(Vec<pack::Chunk>, Vec<bool>)> =
+        packers.into_iter().map(pack::Packer::finish).collect();
     let frame_count = seen as usize;
 
     // A zero-frame source (`--start`/`--duration` past the source's end)
@@ -458,199 +819,193 @@ pub fn build_brick_world(
         );
     }
 
+    // The whole screen's visibility, stitched back together from the panels'.
+    let mut visible = vec![false; (w * h) as usize];
+    for (p, (_, panel_visible)) in panels.iter().zip(&packed) {
+        for (i, &v) in panel_visible.iter().enumerate() {
+            let (col, row) = (p.x + i as u32 % p.width, p.y + i as u32 / p.width);
+            visible[(row * info.width + col) as usize] = v;
+        }
+    }
+
     let mut world = World::new();
     world.meta.bundle.description = "Animation generated from image frames".to_string();
 
     // --- 1. Display bricks on the main grid ---------------------------------
     let (brick_of, geometry) = add_display_bricks(&mut world, opts, w, h, &visible);
 
-    // --- 2. The chip --------------------------------------------------------
-    let mut chip = new_screen_chip(&mut world, w, h, &geometry);
-
-    // Service gates sit behind both pixel stages, so they must use the same
-    // stage pitch the (upright, 10-deep) pixel gates do -- with the flat
-    // `CELL` pitch they landed inside stage 1's depth and collided.
-    let service = |col: i32, row: i32| {
-        lattice_pos_staged(col, row, SERVICE_STAGE, h, GATE_HALF, STAGE_PITCH)
-    };
-
-    // --- 3. Frame index source ---------------------------------------------
-    // `control_pins` carries the clock's Pause/Restart/Resume pin ids for the
-    // control buttons below; `None` under `--external-clock`, which builds no
-    // timer and so has no pins to drive.
-    let (frame_index, control_pins) = if opts.external_clock {
-        let pin = chip::add_input_pin(&mut chip, "Frame", service(0, -1));
-        (chip::pin_source(pin, true), None)
-    } else {
-        let clock = clock::build_clock(
-            &mut world,
-            &mut chip,
-            info.fps,
-            frame_count,
-            opts.loop_playback,
-            service(0, -2),
-        );
-        let pins = (clock.pause_pin, clock.restart_pin, clock.resume_pin);
-        (clock.frame_index, Some(pins))
-    };
-
-    // --- 4. Exec source -----------------------------------------------------
-    let detector = gate(
-        &mut chip,
-        "B_1x1_Gate_Expr_ChangeDetectorExec",
-        CHANGE_DETECTOR,
-        service(0, -4),
-        vec![],
-    );
-    world.add_wire_connection(
-        frame_index.clone(),
-        WirePort::new(detector, CHANGE_DETECTOR, "Input"),
-    );
-
-    // --- 5. Arrays and gets, one per (chunk, bank) --------------------------
     let bank_size = opts.bank_size.max(1);
     let n_banks = frame_count.div_ceil(bank_size).max(1);
 
-    // The shared per-bank spine: per-bank index, boundary comparators, and the
-    // front-cascade of branches. `entry_of_bank` is kept so the subtitle
-    // display can fan out from it rather than being spliced into a chunk chain
-    // (see `cascade`). At n_banks == 1 this emits no gate at all and the chain
-    // is byte-identical to the pre-spillover wiring.
-    let cascade::BankCascade { index_of_bank, ge, entry_of_bank } = cascade::bank_cascade(
-        &mut world,
-        &mut chip,
-        &frame_index,
-        WirePort::new(detector, CHANGE_DETECTOR, "OnChanged"),
-        n_banks,
-        bank_size,
-        &service,
-    );
-
-    // get_of[bank][chunk]
-    let mut get_of: Vec<Vec<usize>> = vec![Vec::with_capacity(chunks.len()); n_banks];
-    for (ci, chunk) in chunks.iter().enumerate() {
-        for (bi, frames) in pack::bank_frames(&chunk.frames, bank_size).iter().enumerate() {
-            let col = ((ci * n_banks + bi) * 2) as i32;
-            let array = gate(&mut chip, "B_1x1_Gate_Variable_Array", ARRAY_VAR,
-                service(col, -5), vec![(
-                    "Value",
-                    Box::new(WireArrayVariant::StringArray(frames.to_vec()))
-                        as Box<dyn AsBrdbValue>,
-                )]);
-            let get = gate(&mut chip, "B_1x1_Gate_Exec_ArrayVar_Get", ARRAY_GET,
-                service(col + 1, -5), vec![]);
-            world.add_wire_connection(
-                WirePort::new(array, ARRAY_VAR, "ArrayVarRef"),
-                WirePort::new(get, ARRAY_GET, "ArrayVarRef"),
-            );
-            world.add_wire_connection(
-                index_of_bank[bi].clone(),
-                WirePort::new(get, ARRAY_GET, "Index"),
-            );
-            get_of[bi].push(get);
-        }
-    }
+    // Everything from here to step 6 is built once per panel. Panel 0 comes
+    // first and owns the frame index; `frame_out` is its `Frame Index`
+    // output pin once there is more than one panel to feed.
+    let mut chips = Vec::with_capacity(panels.len());
+    let mut frame_out: Option<WirePort> = None;
+    let mut master = None;
+    for (pi, (panel, (chunks, _))) in panels.iter().zip(&packed).enumerate() {
+        let (pw, ph) = (panel.width as i32, panel.height as i32);
+
+        // --- 2. The chip ----------------------------------------------------
+        let mut chip = new_panel_chip(&mut world, w, h, &geometry, panel, pi as i32);
+
+        // Service gates sit behind both pixel stages, so they must use the
+        // same stage pitch the (upright, 10-deep) pixel gates do -- with the
+        // flat `CELL` pitch they landed inside stage 1's depth and collided.
+        let service = |col: i32, row: i32| {
+            lattice_pos_staged(col, row, SERVICE_STAGE, ph, GATE_HALF, STAGE_PITCH)
+        };
+
+        // --- 3. Frame index source ------------------------------------------
+        // `control_pins` carries the clock's Pause/Restart/Resume pin ids for
+        // the control buttons below, and `seek_hook` what the seek graph
+        // hangs off; both `None` under `--external-clock`, which builds no
+        // timer and so has no pins to drive, and on every panel but the
+        // first, which reads the first panel's index through a `Frame` pin
+        // exactly as an external clock would be wired.
+        let (frame_index, control_pins, seek_hook) = if let Some(source) = &frame_out {
+            let pin = chip::add_input_pin(&mut chip, "Frame", service(0, -1));
+            world.add_wire_connection(source.clone(), chip::pin_target(pin, true));
+            (chip::pin_source(pin, true), None, None)
+        } else {
+            let (frame_index, control_pins, seek_hook) = if opts.external_clock {
+                let pin = chip::add_input_pin(&mut chip, "Frame", service(0, -1));
+                (chip::pin_source(pin, true), None, None)
+            } else {
+                let clock = clock::build_clock(
+                    &mut world,
+                    &mut chip,
+                    info.fps,
+                    frame_count,
+                    opts.playback.with_loop(opts.loop_playback),
+                    !opts.seek.is_empty(),
+                    service(0, -2),
+                );
+                let pins = (clock.pause_pin, clock.restart_pin, clock.resume_pin);
+                let hook = seek::SeekHook::of(&clock);
+                (clock.frame_index, Some(pins), hook)
+            };
+            if panels.len() > 1 {
+                let pin = chip::add_output_pin(&mut chip, "Frame Index", service(1, -1));
+                world.add_wire_connection(frame_index.clone(), chip::pin_target(pin, false));
+                frame_out = Some(chip::pin_source(pin, false));
+            }
+            (frame_index, control_pins, seek_hook)
+        };
 
-    // Exec: each bank's gets chain off that bank's entry. Per-chunk here rather
-    // than a fan-out, because at one Get per chunk the chain is a handful of
-    // gates deep, not thousands (see `color_bricks` for why the pixel-major
-    // encoding must fan out instead). Every Get's Exec still takes exactly one
-    // source, so no exec input ever gains a second.
-    for bi in 0..n_banks {
-        let mut prev = entry_of_bank[bi].clone();
-        for &get in &get_of[bi] {
-            world.add_wire_connection(prev, WirePort::new(get, ARRAY_GET, "Exec"));
-            prev = WirePort::new(get, ARRAY_GET, "ExecOut");
-        }
-    }
+        // --- 4. Exec source -------------------------------------------------
+        let detector = gate(
+            &mut chip,
+            "B_1x1_Gate_Expr_ChangeDetectorExec",
+            CHANGE_DETECTOR,
+            service(0, -4),
+            vec![],
+        );
+        world.add_wire_connection(
+            frame_index.clone(),
+            WirePort::new(detector, CHANGE_DETECTOR, "Input"),
+        );
 
-    // Value: one select per chunk per boundary, cascading. For a frame in
-    // bank j, ge[0..j] are true so select j picks bank j, and every later
-    // select passes it through unchanged.
-    let mut value_of_chunk = Vec::with_capacity(chunks.len());
-    for ci in 0..chunks.len() {
-        let mut value = WirePort::new(get_of[0][ci], ARRAY_GET, "Value");
-        for bi in 1..n_banks {
-            let sel = gate(&mut chip, "B_1x1_Gate_Expr_Select", SELECT,
-                service((ci * n_banks + bi) as i32, -9), vec![]);
-            world.add_wire_connection(ge[bi - 1].clone(), WirePort::new(sel, SELECT, "bSelectB"));
-            world.add_wire_connection(value, WirePort::new(sel, SELECT, "InputA"));
-            world.add_wire_connection(
-                WirePort::new(get_of[bi][ci], ARRAY_GET, "Value"),
-                WirePort::new(sel, SELECT, "InputB"),
-            );
-            value = WirePort::new(sel, SELECT, "Output");
-        }
-        value_of_chunk.push(value);
-    }
+        // --- 5. Arrays and gets, one per (chunk, bank) ----------------------
+        //
+        // The shared per-bank spine: per-bank index, boundary comparators, and
+        // the front-cascade of branches. `entry_of_bank` is kept so the
+        // subtitle display can fan out from it rather than being spliced into
+        // a chunk chain (see `cascade`). At n_banks == 1 this emits no gate at
+        // all and the chain is byte-identical to the pre-spillover wiring.
+        let spine = cascade::bank_cascade(
+            &mut world,
+            &mut chip,
+            &frame_index,
+            WirePort::new(detector, CHANGE_DETECTOR, "OnChanged"),
+            n_banks,
+            bank_size,
+            service,
+        );
+        let value_of_chunk =
+            add_chunk_arrays(&mut world, &mut chip, chunks, bank_size, &spine, service);
+
+        // --- 6. Two gates per surviving pixel -------------------------------
+        for (ci, chunk) in chunks.iter().enumerate() {
+            let value = value_of_chunk[ci].clone();
+            for local in 0..chunk.pixel_count {
+                // Polled per pixel, not per chunk: this is where nearly the
+                // whole gate count of a render is spent, and a
+                // chunk-granularity poll would still leave a cancel waiting
+                // through the rest of a chunk. The poll itself is far below
+                // the cost of a pixel's own gate allocations, so striding it
+                // buys nothing.
+                if progress.is_cancelled() {
+                    return Ok(World::new());
+                }
+                // `col`/`row` are panel-local: each chip's lattice is its own.
+                let idx = (chunk.first_pixel + local) as i32;
+                let (col, row) = (idx % pw, idx / pw);
+                let screen_idx = (panel.y as i32 + row) * w + panel.x as i32 + col;
+                let Some(&brick_id) = brick_of.get(&(screen_idx as usize)) else {
+                    continue; // culled: slot reserved in the string, no gates
+                };
+
+                // Stage 1 (above) = Substring, stage 0 (bottom) = MakeColorHex.
+                // Facing/roll are cosmetic; PIXEL_GATE_HALF is the real
+                // collision box.
+                let sub = clock::gate_oriented(
+                    &mut chip,
+                    "B_1x1_Gate_Expr_String_Substring",
+                    SUBSTRING,
+                    lattice_pos_staged(col, row, 1, ph, PIXEL_GATE_HALF, STAGE_PITCH),
+                    vec![
+                        // bare i64, not WireVariant::Int -- this gate's schema
+                        // fields are plain scalars; a WireVariant fails at
+                        // encode time with UnimplementedCast.
+                        (
+                            "Start",
+                            Box::new((local * HEX_STRIDE) as i64) as Box<dyn AsBrdbValue>,
+                        ),
+                        ("Length", Box::new(HEX_STRIDE as i64) as Box<dyn AsBrdbValue>),
+                    ],
+                    PIXEL_GATE_FACING,
+                    PIXEL_GATE_ROLL,
+                    PIXEL_GATE_HALF,
+                );
+                let mkcolor = clock::gate_oriented(
+                    &mut chip,
+                    "B_1x1_Gate_Expr_MakeColorHex",
+                    MAKE_COLOR_HEX,
+                    lattice_pos_staged(col, row, 0, ph, PIXEL_GATE_HALF, STAGE_PITCH),
+                    vec![],
+                    PIXEL_GATE_FACING,
+                    PIXEL_GATE_ROLL,
+                    PIXEL_GATE_HALF,
+                );
 
-    // --- 6. Two gates per surviving pixel -----------------------------------
-    for (ci, chunk) in chunks.iter().enumerate() {
-        let value = value_of_chunk[ci].clone();
-        for local in 0..chunk.pixel_count {
-            // Polled per pixel, not per chunk: this is where nearly the whole
-            // gate count of a render is spent, and a chunk-granularity poll
-            // would still leave a cancel waiting through the rest of a chunk.
-            // The poll itself is far below the cost of a pixel's own gate
-            // allocations, so striding it buys nothing.
-            if progress.is_cancelled() {
-                return Ok(World::new());
+                world.add_wire_connection(value.clone(), WirePort::new(sub, SUBSTRING, "Input"));
+                world.add_wire_connection(
+                    WirePort::new(sub, SUBSTRING, "Output"),
+                    WirePort::new(mkcolor, MAKE_COLOR_HEX, "Hex"),
+                );
+                world.add_wire_connection(
+                    WirePort::new(mkcolor, MAKE_COLOR_HEX, "Output"),
+                    WirePort::new(brick_id, PROP_CHANGER, "Color"),
+                );
             }
-            let idx = chunk.first_pixel + local;
-            let Some(&brick_id) = brick_of.get(&idx) else {
-                continue; // culled: slot reserved in the string, no gates
-            };
-            let (col, row) = ((idx as i32) % w, (idx as i32) / w);
-
-            // Stage 1 (above) = Substring, stage 0 (bottom) = MakeColorHex.
-            // Facing/roll are cosmetic; PIXEL_GATE_HALF is the real collision box.
-            let sub = clock::gate_oriented(
-                &mut chip,
-                "B_1x1_Gate_Expr_String_Substring",
-                SUBSTRING,
-                lattice_pos_staged(col, row, 1, h, PIXEL_GATE_HALF, STAGE_PITCH),
-                vec![
-                    // bare i64, not WireVariant::Int -- this gate's schema
-                    // fields are plain scalars; a WireVariant fails at encode
-                    // time with UnimplementedCast.
-                    (
-                        "Start",
-                        Box::new((local * HEX_STRIDE) as i64) as Box<dyn AsBrdbValue>,
-                    ),
-                    ("Length", Box::new(HEX_STRIDE as i64) as Box<dyn AsBrdbValue>),
-                ],
-                PIXEL_GATE_FACING,
-                PIXEL_GATE_ROLL,
-                PIXEL_GATE_HALF,
-            );
-            let mkcolor = clock::gate_oriented(
-                &mut chip,
-                "B_1x1_Gate_Expr_MakeColorHex",
-                MAKE_COLOR_HEX,
-                lattice_pos_staged(col, row, 0, h, PIXEL_GATE_HALF, STAGE_PITCH),
-                vec![],
-                PIXEL_GATE_FACING,
-                PIXEL_GATE_ROLL,
-                PIXEL_GATE_HALF,
-            );
+        }
 
-            world.add_wire_connection(value.clone(), WirePort::new(sub, SUBSTRING, "Input"));
-            world.add_wire_connection(
-                WirePort::new(sub, SUBSTRING, "Output"),
-                WirePort::new(mkcolor, MAKE_COLOR_HEX, "Hex"),
-            );
-            world.add_wire_connection(
-                WirePort::new(mkcolor, MAKE_COLOR_HEX, "Output"),
-                WirePort::new(brick_id, PROP_CHANGER, "Color"),
-            );
+        if pi == 0 {
+            master = Some((spine, control_pins, seek_hook));
         }
+        chips.push(chip);
     }
+    let (spine, control_pins, seek_hook) =
+        master.expect("PanelGrid::split always yields a panel");
+    let cascade::BankCascade { index_of_bank, ge, entry_of_bank } = spine;
 
     // --- 6b. Subtitles, if any ----------------------------------------------
     //
     // Last, after every pixel gate exists -- see `add_subtitle_display`'s doc
     // for why the ordering matters -- and gated on `opts.subtitles`, so a
-    // render without a track is exactly the graph it is today.
+    // render without a track is exactly the graph it is today. On the first
+    // panel's chip, whose spine it reads, and spanning the whole screen.
     if let Some(subs) = &opts.subtitles {
         // `opts.source_start_s`, not 0.0: a subtitle file is in source time,
         // and frame 0 of what this renderer receives is at source time
@@ -659,7 +1014,7 @@ pub fn build_brick_world(
         let per_frame = subs.per_frame(opts.source_start_s, info.fps as f64, frame_count)?;
         super::subtitle_display::add_subtitle_display(
             &mut world,
-            &mut chip,
+            &mut chips[0],
             super::subtitle_display::FrameIndex {
                 index_of_bank: &index_of_bank,
                 entry_of_bank: &entry_of_bank,
@@ -685,6 +1040,18 @@ pub fn build_brick_world(
         controls::add_control_buttons(&mut world, pause, restart, resume, anchor);
     }
 
+    // --- 6d. Seek controls --------------------------------------------------
+    //
+    // Only when asked for (`seek_hook` is `None` otherwise). The graph goes in
+    // the clock's chip beyond everything else there, subtitles included; its
+    // buttons beside the control buttons, so this comes after both.
+    if let Some(hook) = &seek_hook {
+        let plan = opts.seek.plan(info.fps, frame_count, opts.source_start_s);
+        let buttons = seek::add_seek_graph(&mut world, &mut chips[0], hook, &plan);
+        let anchor = controls::control_anchor(&world);
+        controls::add_seek_buttons(&mut world, &buttons, anchor);
+    }
+
     // --- 7. Publish -------------------------------------------------------
     //
     // One last poll before publish: the per-pixel loop only polls at the top
@@ -694,7 +1061,9 @@ pub fn build_brick_world(
     if progress.is_cancelled() {
         return Ok(World::new());
     }
-    chip::finish(&mut world, chip)?;
+    for chip in chips {
+        chip::finish(&mut world, chip)?;
+    }
     // Must be last: it registers every component type and port name actually
     // used, and it must see all bricks, grids and wires first.
     world.register_used_components();
@@ -756,6 +1125,166 @@ mod tests {
         }
     }
 
+    const WALLS: [ScreenOrientation; 4] = [
+        ScreenOrientation::WallXPositive,
+        ScreenOrientation::WallXNegative,
+        ScreenOrientation::WallYPositive,
+        ScreenOrientation::WallYNegative,
+    ];
+
+    /// `place` reproduces the floor layout every render had before walls
+    /// existed, pixel for pixel, and the floor subtitle anchor with it.
+    #[test]
+    fn the_floor_places_every_pixel_where_it_always_was() {
+        let geometry = ScreenGeometry {
+            footprint: 5,
+            half_depth: 2,
+            pitch: 10,
+            orientation: ScreenOrientation::Floor,
+        };
+        for row in 0..4 {
+            for col in 0..7 {
+                assert_eq!(
+                    geometry.pixel(7, 4, col, row),
+                    Position { x: col * 10, y: row * 10, z: 2 }
+                );
+            }
+        }
+        let extent = subtitle_extent(&geometry, 7, 4, 0.0).expect("a zero lift is legal");
+        assert_eq!(extent.anchor, Position { x: 30, y: 35, z: 4 + crate::text::ANCHOR_CUBE_HALF });
+        assert_eq!(extent.face, crate::text::FACE_Z_POSITIVE);
+    }
+
+    /// Every wall stands its picture up: the bottom row rests on `z = 0`, the
+    /// top row is highest, column 0 is on the viewer's left in a left-handed,
+    /// Z-up world, the bricks are stood on edge, and the screen's thickness is
+    /// on the wall's normal axis, in `[0, 2 * half_depth]`.
+    #[test]
+    fn every_wall_stands_upright_and_reads_left_to_right() {
+        for orientation in WALLS {
+            let opts = AnimOptions {
+                orientation,
+                brick_style: DisplayBrickStyle::SmoothTile,
+                ..AnimOptions::default()
+            };
+            let (w, h) = (3, 2);
+            let mut world = World::new();
+            let (brick_of, geometry) = add_display_bricks(&mut world, &opts, w, h, &[true; 6]);
+            let at = |col: i32, row: i32| {
+                let id = brick_of[&((row * w + col) as usize)];
+                world.bricks.iter().find(|b| b.id == Some(id)).expect("placed")
+            };
+            let (bottom, top) = (at(0, h - 1), at(0, 0));
+            assert_eq!(bottom.local_bounds().0.z, 0, "{orientation:?}: the bottom row on z=0");
+            assert!(top.position.z > bottom.position.z, "{orientation:?}: row 0 is the top");
+
+            // The picture's right, per the left-handed world: +X faces have
+            // -Y on their right, +Y faces have +X.
+            let step = at(1, 0).position - at(0, 0).position;
+            let right = match orientation {
+                ScreenOrientation::WallXPositive => Position { x: 0, y: -10, z: 0 },
+                ScreenOrientation::WallXNegative => Position { x: 0, y: 10, z: 0 },
+                ScreenOrientation::WallYPositive => Position { x: 10, y: 0, z: 0 },
+                _ => Position { x: -10, y: 0, z: 0 },
+            };
+            assert_eq!(step, right, "{orientation:?}: column 0 is on the viewer's left");
+
+            let BrickType::Procedural { size, .. } = &bottom.asset else { unreachable!() };
+            let (normal, thickness): (fn(Position) -> i32, _) = match orientation {
+                ScreenOrientation::WallXPositive | ScreenOrientation::WallXNegative => {
+                    (|p: Position| p.x, size.x)
+                }
+                _ => (|p: Position| p.y, size.y),
+            };
+            assert_eq!((thickness, size.z), (2, 5), "{orientation:?}: a tile stood on edge");
+            for brick in &world.bricks {
+                let (min, max) = brick.local_bounds();
+                let centre = brick.position;
+                assert!(centre.x >= 0 && centre.y >= 0, "{orientation:?}: {centre:?} is negative");
+                assert_eq!((normal(min), normal(max)), (0, 2 * geometry.half_depth));
+            }
+
+            // The subtitle stands a cube out from the front face, on it.
+            let extent = subtitle_extent(&geometry, w, h, 0.0).expect("a zero lift is legal");
+            assert_eq!(extent.face, orientation.face());
+            let front = geometry.place(w, h, 0, 0, 2 * geometry.half_depth);
+            let out_of = |p: Position| match orientation {
+                ScreenOrientation::WallXPositive => p.x,
+                ScreenOrientation::WallXNegative => -p.x,
+                ScreenOrientation::WallYPositive => p.y,
+                _ => -p.y,
+            };
+            assert_eq!(out_of(extent.anchor) - out_of(front), crate::text::ANCHOR_CUBE_HALF);
+        }
+    }
+
+    /// Both brick encodings build every orientation -- `chip::finish` asserts
+    /// the shell clears the screen -- with the same bricks, gates and wires
+    /// as the floor: orientation is placement only.
+    #[test]
+    fn both_encodings_build_every_orientation_with_the_floors_counts() {
+        let clip = solid_clip_sized(3, 4, 3);
+        let counts = |w: &World| (w.bricks.len(), w.grids[0].1.len(), w.wires.len());
+        let floor = AnimOptions::default();
+        let hex =
+            counts(&build_brick_world(&clip, &floor, &mut crate::progress::NoProgress).unwrap());
+        let color = counts(
+            &super::super::color_bricks::build_color_array_world(
+                &clip,
+                &floor,
+                &mut crate::progress::NoProgress,
+            )
+            .unwrap(),
+        );
+        for orientation in WALLS {
+            let opts = AnimOptions { orientation, ..AnimOptions::default() };
+            let world = build_brick_world(&clip, &opts, &mut crate::progress::NoProgress)
+                .unwrap_or_else(|e| panic!("{orientation:?}: {e}"));
+            assert_eq!(counts(&world), hex, "{orientation:?}: hex");
+            let world = super::super::color_bricks::build_color_array_world(
+                &clip,
+                &opts,
+                &mut crate::progress::NoProgress,
+            )
+            .unwrap_or_else(|e| panic!("{orientation:?}: {e}"));
+            assert_eq!(counts(&world), color, "{orientation:?}: colour array");
+        }
+    }
+
+    /// Panels tile the picture exactly -- no pixel twice, none missed -- and
+    /// differ by at most a pixel per axis.
+    #[test]
+    fn a_panel_grid_tiles_the_picture_exactly() {
+        let grid = PanelGrid { cols: 3, rows: 2 };
+        let panels = grid.split(10, 5);
+        assert_eq!(panels.len(), 6);
+        let mut covered = vec![0; 50];
+        for p in &panels {
+            for y in p.y..p.y + p.height {
+                for x in p.x..p.x + p.width {
+                    covered[(y * 10 + x) as usize] += 1;
+                }
+            }
+        }
+        assert!(covered.iter().all(|&n| n == 1), "{covered:?}");
+        let widths: Vec<u32> = panels[..3].iter().map(|p| p.width).collect();
+        assert_eq!(widths, [3, 3, 4]);
+        assert_eq!(panels[3], Panel { x: 0, y: 2, width: 3, height: 3 });
+
+        assert_eq!(PanelGrid::default().split(7, 3), [Panel { x: 0, y: 0, width: 7, height: 3 }]);
+        assert_eq!(PanelGrid::parse(" 4X2 "), Some(PanelGrid { cols: 4, rows: 2 }));
+        assert_eq!(PanelGrid::parse("0x2"), None);
+    }
+
+    #[test]
+    fn every_orientation_round_trips_its_name() {
+        for o in [ScreenOrientation::Floor].into_iter().chain(WALLS) {
+            assert_eq!(ScreenOrientation::parse(o.name()), Some(o));
+            assert!(ScreenOrientation::NAMES.contains(o.name()));
+        }
+        assert_eq!(ScreenOrientation::parse("ceiling"), None);
+    }
+
     /// The extent the pixel gates actually occupy, measured rather than
     /// asserted from the number the renderer recorded. Re-derives the box
     /// from the authored [`GATE_HALF`] and the facing/roll the bricks are
@@ -0,0 +1,389 @@
+//! Fitting a render to a budget: the best picture whose estimate stays under
+//! a gate count, a save size and a host memory figure.
+//!
+//! The search never asks for more than the caller's options already do. They
+//! are the top of every range -- the screen size, the frame rate, the palette
+//! -- and the search walks down from there, so a budget the render already
+//! meets changes nothing. What it can trade is:
+//!
+//! - the screen's width, down to one pixel, its height following at the same
+//!   aspect;
+//! - the frame rate, down [`RATES`];
+//! - the encoding, among the [`Space::modes`] the caller allows;
+//! - the palette size, between a large palette's two characters per pixel
+//!   and [`DEFAULT_PALETTE_COLORS`]' one.
+//!
+//! "Best" is the most pixels shown per second of clip ([`Fit::pixel_rate`]):
+//! a frame more per second is worth what the same share more pixels per frame
+//! is. Ties go to whichever candidate the caller listed first, so the encoding
+//! it asked for wins over one it merely allowed.
+//!
+//! Each figure is an upper bound, for the same reason [`super::cost`]'s are:
+//! a budget that is met on paper must be met by the render.
+use super::AnimMode;
+use super::bricks::AnimOptions;
+use super::cost::Cost;
+use super::palette_bricks::DEFAULT_PALETTE_COLORS;
+use super::{AnimEncoding, text_layout};
+
+/// Frame rates the search steps down through, below the caller's own.
+pub const RATES: [f32; 14] =
+    [30.0, 25.0, 24.0, 20.0, 15.0, 12.0, 10.0, 8.0, 6.0, 5.0, 4.0, 3.0, 2.0, 1.0];
+
+/// What every save carries whatever its size: the bundle metadata and the
+/// component and wire schemas. Measured at under 8 KiB for a 4x3 screen.
+const SAVE_BASE_BYTES: usize = 8 * 1024;
+
+/// Allowance per gate, wire and brick in a written save. Measured at well
+/// under this for every encoding once the data is taken out.
+const SAVE_ENTITY_BYTES: usize = 64;
+
+/// Allowance per gate, wire and brick for the world held in memory while it
+/// is built, components and all.
+const HOST_ENTITY_BYTES: usize = 1024;
+
+/// The limits a render must stay under; `None` is no limit.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Budget {
+    /// Wire-graph gates, the render's total -- see [`Cost::gates`].
+    pub gates: Option<usize>,
+    /// Bytes of the written save.
+    pub save_bytes: Option<usize>,
+    /// Bytes of host memory the build holds at its peak.
+    pub host_bytes: Option<usize>,
+}
+
+impl Budget {
+    pub fn is_empty(&self) -> bool {
+        *self == Budget::default()
+    }
+
+    /// Whether `gates`, `save_bytes` and `host_bytes` are all within the
+    /// limits set.
+    pub fn admits(&self, gates: usize, save_bytes: usize, host_bytes: usize) -> bool {
+        self.gates.is_none_or(|g| gates <= g)
+            && self.save_bytes.is_none_or(|s| save_bytes <= s)
+            && self.host_bytes.is_none_or(|h| host_bytes <= h)
+    }
+}
+
+/// Parse a byte size: a whole number with an optional `K`, `M` or `G`
+/// (binary multiples, an optional trailing `B` or `iB`). `None` for anything
+/// else -- the caller names the flag.
+pub fn parse_bytes(s: &str) -> Option<usize> {
+    let s = s.trim().to_ascii_uppercase();
+    let s = s.strip_suffix("IB").or_else(|| s.strip_suffix('B')).unwrap_or(&s);
+    let (digits, shift) = match s.char_indices().last()? {
+        (i, 'K') => (&s[..i], 10),
+        (i, 'M') => (&s[..i], 20),
+        (i, 'G') => (&s[..i], 30),
+        _ => (s, 0),
+    };
+    digits.trim().parse::<usize>().ok()?.checked_mul(1 << shift)
+}
+
+/// The bytes of frame data a render writes into its arrays, uncompressed:
+/// hex and palette mode's characters, colour-array mode's 16-byte colours,
+/// delta mode's worst case of a colour and a start frame for every pixel in
+/// every frame, and text mode's [`text_layout::band_char_bound`] for every
+/// band.
+fn data_bytes(mode: AnimMode, width: u32, height: u32, cost: &Cost, opts: &AnimOptions) -> usize {
+    match mode {
+        AnimMode::Brick(AnimEncoding::Hex | AnimEncoding::Palette) => cost.chars,
+        AnimMode::Brick(AnimEncoding::ColorArray) => {
+            super::color_bricks::accumulator_bytes(cost.pixels, cost.frames)
+        }
+        AnimMode::Brick(AnimEncoding::Delta) => cost.pixels * cost.frames * 24,
+        AnimMode::Text => {
+            let (w, h) = (width as usize, height as usize);
+            text_layout::plan_text_bands(w, h, &opts.text)
+                .unwrap_or_default()
+                .iter()
+                .map(|band| text_layout::band_char_bound(w, band.rows, &opts.text))
+                .sum::<usize>()
+                * cost.frames
+        }
+    }
+}
+
+fn entities(cost: &Cost) -> usize {
+    cost.gates + cost.wires + cost.bricks
+}
+
+/// An upper bound on the written save's size: [`data_bytes`] as though the
+/// save were not compressed, plus a fixed allowance per gate, wire and brick.
+pub fn save_bytes(
+    mode: AnimMode,
+    width: u32,
+    height: u32,
+    cost: &Cost,
+    opts: &AnimOptions,
+) -> usize {
+    SAVE_BASE_BYTES
+        + SAVE_ENTITY_BYTES * entities(cost)
+        + data_bytes(mode, width, height, cost, opts)
+}
+
+/// An upper bound on host memory at the build's peak, which is the moment the
+/// save is encoded: the packer's frame data, the world, and the save itself.
+///
+/// The frame data is what each packer documents. Delta mode's change lists
+/// grow one push at a time, so they are counted at the doubled capacity a
+/// `Vec` can be left at (see [`super::color_bricks::unreserved_accumulator_bytes`]).
+pub fn host_bytes(
+    mode: AnimMode,
+    width: u32,
+    height: u32,
+    cost: &Cost,
+    opts: &AnimOptions,
+) -> usize {
+    let data = match mode {
+        AnimMode::Brick(AnimEncoding::Delta) => {
+            cost.pixels * (cost.frames + 1).next_power_of_two().max(4) * 24
+        }
+        _ => data_bytes(mode, width, height, cost, opts),
+    };
+    data + HOST_ENTITY_BYTES * entities(cost) + save_bytes(mode, width, height, cost, opts)
+}
+
+/// What the search may change, and where each range starts.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Space {
+    /// The largest screen. Smaller ones keep its aspect.
+    pub size: (u32, u32),
+    /// The highest frame rate; lower ones come from [`RATES`].
+    pub fps: f32,
+    /// Whether the screen may shrink and the rate drop, or either is held
+    /// where the caller set it.
+    pub vary_size: bool,
+    pub vary_fps: bool,
+    /// The modes to choose between, preferred first.
+    pub modes: Vec<AnimMode>,
+    /// Whether a palette render may drop to [`DEFAULT_PALETTE_COLORS`] from a
+    /// larger `opts.colors`.
+    pub vary_colors: bool,
+}
+
+/// The configuration a search picked, and what it costs.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Fit {
+    pub mode: AnimMode,
+    pub width: u32,
+    pub height: u32,
+    pub fps: f32,
+    /// `opts.colors` for the render.
+    pub colors: usize,
+    pub cost: Cost,
+    pub save_bytes: usize,
+    pub host_bytes: usize,
+}
+
+impl Fit {
+    /// Pixels shown per second of clip: what the search maximises.
+    pub fn pixel_rate(&self) -> f64 {
+        self.width as f64 * self.height as f64 * self.fps as f64
+    }
+}
+
+/// The height a `width`-wide screen has at `size`'s aspect.
+fn height_at(size: (u32, u32), width: u32) -> u32 {
+    let h = (size.1 as f64 * width as f64 / size.0.max(1) as f64).round();
+    (h as u32).clamp(1, size.1.max(1))
+}
+
+/// The best [`Fit`] in `space` under `budget`, with `frames_at` giving the
+/// clip's frame count at each rate tried.
+///
+/// `Err` when nothing fits, naming what the smallest candidate still costs --
+/// a one-pixel screen has a clock and buttons, and a budget below them is no
+/// render at all.
+pub fn fit(
+    space: &Space,
+    opts: &AnimOptions,
+    budget: &Budget,
+    frames_at: &dyn Fn(f32) -> usize,
+) -> Result<Fit, String> {
+    let mut rates = vec![space.fps];
+    if space.vary_fps {
+        rates.extend(RATES.iter().copied().filter(|&r| r < space.fps));
+    }
+    let widths = if space.vary_size {
+        1..=space.size.0.max(1)
+    } else {
+        space.size.0..=space.size.0
+    };
+    let mut best: Option<Fit> = None;
+    let mut smallest: Option<Fit> = None;
+    for &fps in &rates {
+        let frames = frames_at(fps);
+        for &mode in &space.modes {
+            let mut palettes = vec![opts.colors];
+            if mode == AnimMode::Brick(AnimEncoding::Palette)
+                && space.vary_colors
+                && opts.colors > DEFAULT_PALETTE_COLORS
+            {
+                palettes.push(DEFAULT_PALETTE_COLORS);
+            }
+            for colors in palettes {
+                let opts = AnimOptions { colors, ..opts.clone() };
+                // Widest first: the first that fits is this candidate's best.
+                for width in widths.clone().rev() {
+                    let height =
+                        if space.vary_size { height_at(space.size, width) } else { space.size.1 };
+                    let Ok(cost) = mode.estimate(width, height, frames, &opts) else {
+                        continue;
+                    };
+                    let candidate = Fit {
+                        mode,
+                        width,
+                        height,
+                        fps,
+                        colors,
+                        cost,
+                        save_bytes: save_bytes(mode, width, height, &cost, &opts),
+                        host_bytes: host_bytes(mode, width, height, &cost, &opts),
+                    };
+                    if budget.admits(cost.gates, candidate.save_bytes, candidate.host_bytes) {
+                        if best.is_none_or(|b| candidate.pixel_rate() > b.pixel_rate()) {
+                            best = Some(candidate);
+                        }
+                        break;
+                    }
+                    if smallest.is_none_or(|s| candidate.cost.gates < s.cost.gates) {
+                        smallest = Some(candidate);
+                    }
+                }
+            }
+        }
+    }
+    best.ok_or_else(|| match smallest {
+        Some(s) => format!(
+            "nothing fits the budget: the smallest render tried, {}x{} at {} fps, needs {} \
+             gate(s), a save of up to {} and {} of memory",
+            s.width,
+            s.height,
+            s.fps,
+            s.cost.gates,
+            show_bytes(s.save_bytes),
+            show_bytes(s.host_bytes)
+        ),
+        None => "nothing fits the budget: no size tried can be laid out".to_string(),
+    })
+}
+
+/// `bytes` in the largest binary unit that keeps it above 1, for a log line.
+pub fn show_bytes(bytes: usize) -> String {
+    match bytes {
+        b if b >= 1 << 30 => format!("{:.1} GiB", b as f64 / (1u64 << 30) as f64),
+        b if b >= 1 << 20 => format!("{:.1} MiB", b as f64 / (1u64 << 20) as f64),
+        b if b >= 1 << 10 => format!("{:.1} KiB", b as f64 / 1024.0),
+        b => format!("{b} B"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn space(modes: Vec<AnimMode>) -> Space {
+        Space {
+            size: (64, 36),
+            fps: 10.0,
+            vary_size: true,
+            vary_fps: true,
+            modes,
+            vary_colors: true,
+        }
+    }
+
+    const HEX: AnimMode = AnimMode::Brick(AnimEncoding::Hex);
+
+    #[test]
+    fn byte_sizes_parse_with_binary_suffixes() {
+        assert_eq!(parse_bytes("512"), Some(512));
+        assert_eq!(parse_bytes("4k"), Some(4096));
+        assert_eq!(parse_bytes("200M"), Some(200 << 20));
+        assert_eq!(parse_bytes("2 GiB"), Some(2 << 30));
+        assert_eq!(parse_bytes("1.5G"), None);
+        assert_eq!(parse_bytes("lots"), None);
+        assert_eq!(parse_bytes(""), None);
+    }
+
+    /// A budget the render already meets leaves every setting where it was.
+    #[test]
+    fn a_met_budget_changes_nothing() {
+        let budget = Budget { gates: Some(1_000_000), ..Budget::default() };
+        let fit = fit(&space(vec![HEX]), &AnimOptions::default(), &budget, &|fps| {
+            (fps * 30.0) as usize
+        })
+        .unwrap();
+        assert_eq!((fit.width, fit.height, fit.fps, fit.mode), (64, 36, 10.0, HEX));
+    }
+
+    /// Gates do not depend on the rate, so a gate budget shrinks the screen
+    /// and keeps the rate; the fit is the widest screen under it.
+    #[test]
+    fn a_gate_budget_shrinks_the_screen_not_the_rate() {
+        let opts = AnimOptions::default();
+        let budget = Budget { gates: Some(2000), ..Budget::default() };
+        let fit = fit(&space(vec![HEX]), &opts, &budget, &|fps| (fps * 30.0) as usize).unwrap();
+        assert_eq!(fit.fps, 10.0);
+        assert!(fit.cost.gates <= 2000);
+        let wider = HEX.estimate(fit.width + 1, height_at((64, 36), fit.width + 1), 300, &opts);
+        assert!(wider.unwrap().gates > 2000, "one pixel wider must break the budget");
+    }
+
+    /// The memory a colour-array render holds grows with its frames, so a
+    /// host budget can be met by a lower rate as well as a smaller screen,
+    /// and the search takes whichever shows more pixels per second.
+    #[test]
+    fn a_memory_budget_trades_rate_against_size() {
+        let modes = vec![AnimMode::Brick(AnimEncoding::ColorArray)];
+        let budget = Budget { host_bytes: Some(8 << 20), ..Budget::default() };
+        let frames_at = |fps: f32| (fps * 60.0) as usize;
+        let fit = fit(&space(modes.clone()), &AnimOptions::default(), &budget, &frames_at).unwrap();
+        assert!(fit.host_bytes <= 8 << 20);
+        let held = Space { vary_fps: false, ..space(modes) };
+        let at_rate = super::fit(&held, &AnimOptions::default(), &budget, &frames_at).unwrap();
+        assert!(fit.pixel_rate() >= at_rate.pixel_rate());
+    }
+
+    /// Palette mode at a large palette pays two characters per pixel; under a
+    /// save budget the search may drop it to one before it shrinks the screen.
+    #[test]
+    fn a_save_budget_can_drop_a_large_palette() {
+        let palette = AnimMode::Brick(AnimEncoding::Palette);
+        let opts = AnimOptions { colors: 1000, ..AnimOptions::default() };
+        let frames_at = |_: f32| 2000;
+        let two = estimate_save(palette, &opts, 2000);
+        let one = estimate_save(palette, &AnimOptions { colors: 64, ..opts.clone() }, 2000);
+        let budget = Budget { save_bytes: Some((one + two) / 2), ..Budget::default() };
+        let held = Space { vary_fps: false, ..space(vec![palette]) };
+        let fit = fit(&held, &opts, &budget, &frames_at).unwrap();
+        assert_eq!((fit.width, fit.colors), (64, 64));
+    }
+
+    fn estimate_save(mode: AnimMode, opts: &AnimOptions, frames: usize) -> usize {
+        let cost = mode.estimate(64, 36, frames, opts).unwrap();
+        save_bytes(mode, 64, 36, &cost, opts)
+    }
+
+    /// Ties go to the mode listed first, so the encoding the caller asked for
+    /// is kept whenever another only matches it.
+    #[test]
+    fn the_first_listed_mode_wins_a_tie() {
+        let color = AnimMode::Brick(AnimEncoding::ColorArray);
+        for modes in [vec![HEX, color], vec![color, HEX]] {
+            let first = modes[0];
+            let fit = fit(&space(modes), &AnimOptions::default(), &Budget::default(), &|_| 10);
+            assert_eq!(fit.unwrap().mode, first);
+        }
+    }
+
+    #[test]
+    fn a_budget_below_the_clock_fits_nothing_and_says_why() {
+        let budget = Budget { gates: Some(3), ..Budget::default() };
+        let err = fit(&space(vec![HEX]), &AnimOptions::default(), &budget, &|_| 10).unwrap_err();
+        assert!(err.contains("1x1") && err.contains("gate(s)"), "{err}");
+    }
+}
@@ -1,5 +1,7 @@
 //! Timer -> frame index. Six gates, all shared by the whole screen (four for
-//! the index chain, two for the length and progress status taps).
+//! the index chain, two for the length and progress status taps), a seventh
+//! on a seekable clock (the offset [`super::seek`] writes), and two or four
+//! more for a [`Playback`] order other than first-to-last.
 use super::chip::{Chip, pin_source, pin_target};
 use super::layout::{CELL, GATE_HALF};
 use brdb::{
@@ -14,10 +16,22 @@ pub const TIMER: &str = "BrickComponentType_WireGraphPseudo_Timer";
 pub const MULTIPLY: &str = "BrickComponentType_WireGraph_Expr_MathMultiply";
 pub const BITWISE_OR: &str = "BrickComponentType_WireGraph_Expr_BitwiseOR";
 pub const MODULO: &str = "BrickComponentType_WireGraph_Expr_MathModuloFloored";
+pub const ADD: &str = "BrickComponentType_WireGraph_Expr_MathAdd";
+pub const MIN: &str = "BrickComponentType_WireGraph_Expr_MathMin";
+pub const MAX: &str = "BrickComponentType_WireGraph_Expr_MathMax";
 
 pub struct Clock {
     /// Source port carrying the wrapped integer frame index.
     pub frame_index: WirePort,
+    /// Source port carrying where the index is in one pass of a ping-pong
+    /// before its way back is folded down: `0..2 * frame_count - 2`, stepping
+    /// forward to the wrap where [`Self::frame_index`] turns round. The same
+    /// port as `frame_index` under every other order.
+    ///
+    /// What an event playhead reads instead of the index, over events laid
+    /// out there and back ([`Playback::unfold`]), so the one backward step it
+    /// ever sees is the wrap -- see [`super::delta_bricks`].
+    pub phase: WirePort,
     /// The timer's raw `Time` output: continuous seconds, advancing every tick
     /// while the clock runs and frozen while it is paused.
     ///
@@ -53,6 +67,11 @@ pub struct Clock {
     /// how fast the clip plays, not how long the content is. Always wired,
     /// inert until a builder taps it.
     pub length_pin: usize,
+    /// Target port adding a whole number of frames to the index before it
+    /// wraps, on a clock built `seekable`; `None` otherwise. Whatever drives
+    /// it moves the picture, and the progress tap with it, without touching
+    /// the timer -- see [`super::seek`].
+    pub offset: Option<WirePort>,
 }
 
 /// The `Timer.Limit`, in seconds, for a clock that must stop at the end of
@@ -94,6 +113,177 @@ pub fn stop_limit(fps: f32, frame_count: usize) -> f64 {
 /// explicitly. See `build_clock`.
 pub const FREE_RUNNING_LIMIT: f64 = 0.0;
 
+/// The order a clock walks its frames in ([`Playback::order`]).
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum PlayOrder {
+    /// First frame to last, then from the first again.
+    #[default]
+    Forward,
+    /// First to last and back down again, showing neither end twice in a row.
+    PingPong,
+    /// Everything before `start_s` once, as an intro, then `start_s..end_s`
+    /// over and over. Seconds from the clip's first frame; an end past the
+    /// clip is its end.
+    Range { start_s: f64, end_s: f64 },
+}
+
+/// How a clock plays its frames: in what order, and how many times before it
+/// stops.
+///
+/// Both are frame-index math inside [`build_clock`], so every renderer that
+/// reads the index -- every video encoding, and both audio modes -- plays
+/// them alike. A stopped clock holds the frame it stopped on: the last one
+/// going forward, the first after a ping-pong's way back, the range's last
+/// inside a range.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Playback {
+    pub order: PlayOrder,
+    /// Passes through [`Self::order`] before the clock stops, or `None` to
+    /// repeat forever. A ping-pong pass is there and back; a range counts its
+    /// looped section only, after the one intro.
+    pub plays: Option<u32>,
+}
+
+/// Where [`Playback::order`] lands on a clip of a given length: the numbers
+/// the graph inlines.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Wrap {
+    Forward { frames: usize },
+    /// `period` is `2 * frames - 2`: down the way back, then the first frame.
+    PingPong { period: usize },
+    Range { start: usize, len: usize },
+}
+
+impl Playback {
+    /// First to last, once, then hold the last: what `loop_playback` off
+    /// always meant.
+    pub const ONCE: Playback = Playback { order: PlayOrder::Forward, plays: Some(1) };
+
+    /// `self` under an options struct's `loop_playback` flag: off stops after
+    /// one pass, unless [`Self::plays`] already names how many.
+    pub fn with_loop(self, loop_playback: bool) -> Playback {
+        match self.plays {
+            None if !loop_playback => Playback { plays: Some(1), ..self },
+            _ => self,
+        }
+    }
+
+    /// Refuse what no clip could play: zero plays, or a range that is not a
+    /// span of time from the clip's start onward.
+    pub fn check(&self) -> Result<(), String> {
+        if self.plays == Some(0) {
+            return Err("a clip must play at least once".to_string());
+        }
+        if let PlayOrder::Range { start_s, end_s } = self.order
+            && !(start_s.is_finite() && end_s.is_finite() && 0.0 <= start_s && start_s < end_s)
+        {
+            return Err(format!(
+                "a loop range must start at 0 s or later and end after it starts \
+                 (got {start_s} s to {end_s} s)"
+            ));
+        }
+        Ok(())
+    }
+
+    /// What [`Self::order`] adds to [`build_clock`]'s graph, in `(gates,
+    /// wires)`. [`Self::plays`] only changes the inlined `Timer.Limit`.
+    pub fn clock_cost(&self) -> (usize, usize) {
+        match self.order {
+            PlayOrder::Forward => (0, 0),
+            PlayOrder::PingPong => (2, 3),
+            PlayOrder::Range { .. } => (4, 5),
+        }
+    }
+
+    /// The order in frames of a `frame_count`-frame clip at `fps`. A range is
+    /// rounded to whole frames and kept inside the clip, at least a frame
+    /// long.
+    fn wrap(&self, fps: f32, frame_count: usize) -> Wrap {
+        let frames = frame_count.max(1);
+        match self.order {
+            PlayOrder::Forward => Wrap::Forward { frames },
+            PlayOrder::PingPong => Wrap::PingPong { period: (2 * frames).saturating_sub(2).max(1) },
+            PlayOrder::Range { start_s, end_s } => {
+                let at = |s: f64| (s * fps as f64).round().max(0.0) as usize;
+                let start = at(start_s).min(frames - 1);
+                let end = at(end_s).clamp(start + 1, frames);
+                Wrap::Range { start, len: end - start }
+            }
+        }
+    }
+
+    /// The frame on screen `t` whole frames into playback -- the index
+    /// [`build_clock`]'s graph computes, written out in Rust.
+    pub fn frame_at(&self, t: i64, fps: f32, frame_count: usize) -> usize {
+        let index = match self.wrap(fps, frame_count) {
+            Wrap::Forward { frames } => t.rem_euclid(frames as i64),
+            Wrap::PingPong { period } => {
+                let u = t.rem_euclid(period as i64);
+                u.min(period as i64 - u)
+            }
+            Wrap::Range { start, len } => {
+                let start = start as i64;
+                t.min(start) + (t - start).max(0).rem_euclid(len as i64)
+            }
+        };
+        index as usize
+    }
+
+    /// The `Timer.Limit` this playback inlines: [`FREE_RUNNING_LIMIT`] with no
+    /// [`Self::plays`], otherwise [`stop_limit`] of however many frames the
+    /// plays take end to end -- so the timer expires inside the frame each
+    /// order ends on.
+    pub fn limit(&self, fps: f32, frame_count: usize) -> f64 {
+        match self.played_frames(fps, frame_count) {
+            Some(frames) => stop_limit(fps, frames),
+            None => FREE_RUNNING_LIMIT,
+        }
+    }
+
+    /// The frame [`Clock::phase`] stands for at each of its values: one
+    /// ping-pong pass, there and back, or the clip first to last under any
+    /// other order, whose phase is the index itself.
+    pub fn unfold(&self, fps: f32, frame_count: usize) -> Vec<usize> {
+        match self.wrap(fps, frame_count) {
+            Wrap::PingPong { period } => {
+                (0..period as i64).map(|t| self.frame_at(t, fps, frame_count)).collect()
+            }
+            _ => (0..frame_count.max(1)).collect(),
+        }
+    }
+
+    /// The frame a loop range's wrap steps back to, when that is not frame 0
+    /// -- the one backward step of any order that lands somewhere other than
+    /// where a restart does. `None` for every other order.
+    pub fn loop_start(&self, fps: f32, frame_count: usize) -> Option<usize> {
+        match self.wrap(fps, frame_count) {
+            Wrap::Range { start, .. } if start > 0 => Some(start),
+            _ => None,
+        }
+    }
+
+    /// Frames one pass through [`Self::order`] shows, end to end: a range's
+    /// intro and one time round its loop.
+    pub fn pass_frames(&self, fps: f32, frame_count: usize) -> usize {
+        match self.wrap(fps, frame_count) {
+            Wrap::Forward { frames } => frames,
+            Wrap::PingPong { period } => period,
+            Wrap::Range { start, len } => start + len,
+        }
+    }
+
+    /// Frames shown before the clock stops, or `None` when it never does.
+    pub fn played_frames(&self, fps: f32, frame_count: usize) -> Option<usize> {
+        let plays = self.plays?.max(1) as usize;
+        Some(match self.wrap(fps, frame_count) {
+            Wrap::Forward { frames } => plays * frames,
+            // One frame past the last pass: back on the first frame.
+            Wrap::PingPong { period } => plays * period + 1,
+            Wrap::Range { start, len } => start + plays * len,
+        })
+    }
+}
+
 /// Add a gate brick carrying `class`, with `data` inlined on its ports.
 /// Inlining a constant as component data costs no extra brick -- this is what
 /// keeps the per-pixel gate count at 2 instead of 4. Only valid for ports
@@ -173,37 +363,63 @@ pub fn gate_oriented(
 /// `ModuloFloored` needs no such barrier because it never feeds back into the
 /// timer.
 ///
-/// # `loop_playback`
+/// # `playback`
 ///
-/// The only thing this flag changes is the value inlined on `Timer.Limit`;
-/// the graph is the same four gates and eight wires either way.
+/// [`Playback::plays`] changes only the value inlined on `Timer.Limit`
+/// ([`Playback::limit`]):
 ///
-/// * `true` (the default) writes [`FREE_RUNNING_LIMIT`]. The timer never
+/// * `None` (the default) writes [`FREE_RUNNING_LIMIT`]. The timer never
 ///   expires, `ModuloFloored` wraps the frame index, and playback repeats.
-/// * `false` writes [`stop_limit`], which expires halfway through the last
-///   frame's slot so the index sits at `frame_count - 1` rather than
-///   wrapping to 0. Whether the picture really holds there is unverified in
-///   game -- see that function's doc.
+/// * `Some(n)` writes [`stop_limit`] of the `n` passes' frames, which expires
+///   halfway through the slot of the frame the last pass ends on, rather than
+///   wrapping past it. Whether the picture really holds there is unverified
+///   in game -- see that function's doc.
 ///
 /// Either way `Limit` is written explicitly: it falls back to the schema's
 /// registered struct default (`1.0`, not `0`) when omitted, which would
 /// silently cap the timer at one second.
+///
+/// [`Playback::order`] reshapes the wrap, on gates past the status taps:
+///
+/// * `Forward` is the chain above, `index = frames % frame_count`.
+/// * `PingPong` wraps at the there-and-back period `P = 2 * frame_count - 2`
+///   and folds the way back down: `index = min(u, P - u)` for the wrapped
+///   `u`. A `Subtract` and a `Min`.
+/// * `Range` keeps the intro and wraps only what is past the range's start
+///   `A`, at its length `L`: `index = min(frames, A) + max(frames - A, 0) %
+///   L`. Two `Add`s, a `Max` and a `Min`, the `ModuloFloored` wrapping at `L`.
+///
+/// [`Playback::frame_at`] is the same arithmetic in Rust.
+///
+/// # `seekable`
+///
+/// Adds one `Add` gate between `BitwiseOR` and `ModuloFloored`, its `InputB`
+/// returned as [`Clock::offset`]: the index becomes `(frames + offset) %
+/// frame_count`. One gate and one wire more, so it is a flag rather than
+/// always on -- every estimate that does not ask for seeking keeps its
+/// number. The stop limit is still in seconds of timer time, so a play-once
+/// clock seeked forward stops that much later in the clip (past its end,
+/// wrapping) rather than at its last frame. The offset is added before the
+/// order's wrap, so only a `Forward` clock seeks where [`super::seek`]
+/// expects.
 pub fn build_clock(
     world: &mut World,
     chip: &mut Chip,
     fps: f32,
     frame_count: usize,
-    loop_playback: bool,
+    playback: Playback,
+    seekable: bool,
     origin: Position,
 ) -> Clock {
     let at = |i: i32| Position { x: origin.x, y: origin.y + i * CELL, z: origin.z };
 
     // See this function's doc for both values and for why neither may be
     // left to the struct default.
-    let limit = if loop_playback {
-        FREE_RUNNING_LIMIT
-    } else {
-        stop_limit(fps, frame_count)
+    let limit = playback.limit(fps, frame_count);
+    let wrap_at = match playback.wrap(fps, frame_count) {
+        Wrap::Forward { frames } => frames,
+        Wrap::PingPong { period } => period,
+        Wrap::Range { len, .. } => len,
     };
     let timer = gate(chip, "B_1x1_Gate_Pseudo_Timer", TIMER, at(0), vec![(
         "Limit",
@@ -226,7 +442,7 @@ pub fn build_clock(
     // not Number, since the wrap point is an index, not a measurement.
     let wrap = gate(chip, "B_1x1_Gate_Expr_MathModuloFloored", MODULO, at(3), vec![(
         "InputB",
-        Box::new(WireVariant::Int(frame_count as i64)) as Box<dyn AsBrdbValue>,
+        Box::new(WireVariant::Int(wrap_at as i64)) as Box<dyn AsBrdbValue>,
     )]);
 
     world.add_wire_connection(
@@ -237,10 +453,27 @@ pub fn build_clock(
         WirePort::new(mul, MULTIPLY, "Output"),
         WirePort::new(trunc, BITWISE_OR, "InputA"),
     );
-    world.add_wire_connection(
-        WirePort::new(trunc, BITWISE_OR, "Output"),
-        WirePort::new(wrap, MODULO, "InputA"),
-    );
+    // The offset sits past the status taps (`at(4)`, `at(5)`), so a seekable
+    // clock only grows the chain's column by one.
+    let (frames, offset) = if seekable {
+        let add = gate(chip, "B_1x1_Gate_Expr_MathAdd", ADD, at(6), vec![(
+            "InputB",
+            Box::new(WireVariant::Int(0)) as Box<dyn AsBrdbValue>,
+        )]);
+        world.add_wire_connection(
+            WirePort::new(trunc, BITWISE_OR, "Output"),
+            WirePort::new(add, ADD, "InputA"),
+        );
+        (WirePort::new(add, ADD, "Output"), Some(WirePort::new(add, ADD, "InputB")))
+    } else {
+        (WirePort::new(trunc, BITWISE_OR, "Output"), None)
+    };
+    let order = playback.wrap(fps, frame_count);
+    let frame_index = wrap_in_order(world, chip, order, frames, wrap, &at);
+    let phase = match order {
+        Wrap::PingPong { .. } => WirePort::new(wrap, MODULO, "Output"),
+        _ => frame_index.clone(),
+    };
 
     // Control pins, placed in their own row clear of the clock chain.
     let pin_at = |i: i32| Position { x: origin.x + CELL, y: origin.y + i * CELL, z: origin.z };
@@ -300,8 +533,8 @@ pub fn build_clock(
 
     // Progress is the wrapped frame index scaled to a fraction: `frame_index /
     // (frame_count - 1)`, so frame 0 reads 0 and the last frame reads 1.
-    // Reading `wrap.Output` again is dataflow fan-out (the caller also reads it
-    // as the frame index), which the graph allows freely.
+    // Reading the index again is dataflow fan-out (the caller also reads it),
+    // which the graph allows freely.
     // `(frame_count - 1).max(1)` guards a single-frame clip, which then reads
     // a constant 0.
     let progress_scale = 1.0 / frame_count.saturating_sub(1).max(1) as f64;
@@ -310,7 +543,7 @@ pub fn build_clock(
         Box::new(WireVariant::Number(progress_scale)) as Box<dyn AsBrdbValue>,
     )]);
     world.add_wire_connection(
-        WirePort::new(wrap, MODULO, "Output"),
+        frame_index.clone(),
         WirePort::new(progress_gate, MULTIPLY, "InputA"),
     );
     let progress_pin = super::chip::add_output_pin(chip, "Progress", pin_at(6));
@@ -320,7 +553,8 @@ pub fn build_clock(
     );
 
     Clock {
-        frame_index: WirePort::new(wrap, MODULO, "Output"),
+        frame_index,
+        phase,
         // The timer's raw stopwatch, exposed for tapping. Not a new wire:
         // the `Timer.Time -> Multiply.InputA` connection above is the only
         // wire off this port `build_clock` itself emits.
@@ -332,6 +566,70 @@ pub fn build_clock(
         done_pin,
         progress_pin,
         length_pin,
+        offset,
+    }
+}
+
+/// Wire `frames` (the truncated, offset frame count) into the `wrap` modulo
+/// as `order` asks, returning the port carrying the frame index. The extra
+/// gates go below the seek offset's `at(6)`, whether or not it was built.
+fn wrap_in_order(
+    world: &mut World,
+    chip: &mut Chip,
+    order: Wrap,
+    frames: WirePort,
+    wrap: usize,
+    at: &dyn Fn(i32) -> Position,
+) -> WirePort {
+    use super::bricks::SUBTRACT;
+    let int = |v: usize| Box::new(WireVariant::Int(v as i64)) as Box<dyn AsBrdbValue>;
+    let wrapped = WirePort::new(wrap, MODULO, "Output");
+    match order {
+        Wrap::Forward { .. } => {
+            world.add_wire_connection(frames, WirePort::new(wrap, MODULO, "InputA"));
+            wrapped
+        }
+        Wrap::PingPong { period } => {
+            world.add_wire_connection(frames, WirePort::new(wrap, MODULO, "InputA"));
+            let back = gate(chip, "B_1x1_Gate_Expr_MathSubtract", SUBTRACT, at(7), vec![(
+                "InputA",
+                int(period),
+            )]);
+            world.add_wire_connection(wrapped.clone(), WirePort::new(back, SUBTRACT, "InputB"));
+            let fold = gate(chip, "B_1x1_Gate_Expr_MathMin", MIN, at(8), vec![]);
+            world.add_wire_connection(wrapped, WirePort::new(fold, MIN, "InputA"));
+            world.add_wire_connection(
+                WirePort::new(back, SUBTRACT, "Output"),
+                WirePort::new(fold, MIN, "InputB"),
+            );
+            WirePort::new(fold, MIN, "Output")
+        }
+        Wrap::Range { start, .. } => {
+            let shift = gate(chip, "B_1x1_Gate_Expr_MathAdd", ADD, at(7), vec![(
+                "InputB",
+                Box::new(WireVariant::Int(-(start as i64))) as Box<dyn AsBrdbValue>,
+            )]);
+            world.add_wire_connection(frames.clone(), WirePort::new(shift, ADD, "InputA"));
+            let past = gate(chip, "B_1x1_Gate_Expr_MathMax", MAX, at(8), vec![("InputB", int(0))]);
+            world.add_wire_connection(
+                WirePort::new(shift, ADD, "Output"),
+                WirePort::new(past, MAX, "InputA"),
+            );
+            world.add_wire_connection(
+                WirePort::new(past, MAX, "Output"),
+                WirePort::new(wrap, MODULO, "InputA"),
+            );
+            let intro =
+                gate(chip, "B_1x1_Gate_Expr_MathMin", MIN, at(9), vec![("InputB", int(start))]);
+            world.add_wire_connection(frames, WirePort::new(intro, MIN, "InputA"));
+            let index = gate(chip, "B_1x1_Gate_Expr_MathAdd", ADD, at(10), vec![]);
+            world.add_wire_connection(wrapped, WirePort::new(index, ADD, "InputA"));
+            world.add_wire_connection(
+                WirePort::new(intro, MIN, "Output"),
+                WirePort::new(index, ADD, "InputB"),
+            );
+            WirePort::new(index, ADD, "Output")
+        }
     }
 }
 
@@ -459,7 +757,8 @@ mod tests {
     #[test]
     fn build_clock_emits_exactly_eleven_wires_and_they_are_these_eleven() {
         let (mut world, mut chip) = a_chip();
-        build_clock(&mut world, &mut chip, 10.0, 90, true, Position { x: 0, y: 0, z: 6 });
+        let origin = Position { x: 0, y: 0, z: 6 };
+        build_clock(&mut world, &mut chip, 10.0, 90, Playback::default(), false, origin);
 
         let mut got = wire_shapes(&world);
         got.sort();
@@ -520,7 +819,9 @@ mod tests {
     #[test]
     fn build_clock_emits_six_gates_seven_pins_and_a_wrapped_frame_index() {
         let (mut world, mut chip) = a_chip();
-        let clock = build_clock(&mut world, &mut chip, 30.0, 90, true, Position { x: 0, y: 0, z: 6 });
+        let origin = Position { x: 0, y: 0, z: 6 };
+        let clock =
+            build_clock(&mut world, &mut chip, 30.0, 90, Playback::default(), false, origin);
 
         assert_eq!(
             chip.placed().len(),
@@ -550,26 +851,29 @@ mod tests {
         assert_eq!(unique.len(), 7, "seven pins must be seven bricks");
     }
 
-    /// The loop toggle changes one inlined value and nothing else. Every
-    /// cost estimate counts a flat 6 clock gates and 11 clock wires without
-    /// seeing this flag, so that stays true only while both settings emit
+    /// The loop toggle -- any count of plays -- changes one inlined value and
+    /// nothing else. Every cost estimate counts the clock's gates and wires
+    /// from its order alone, so that stays true only while every count emits
     /// identical structure. (What the flag writes into `Timer.Limit` is
     /// checked against a real save in
     /// `tests/anim_world.rs::a_non_looping_clock_writes_a_limit_landing_on_the_last_frame`.)
     #[test]
     fn the_loop_toggle_changes_no_gate_no_pin_and_no_wire() {
-        let build = |looping: bool| {
+        let build = |playback: Playback| {
             let (mut world, mut chip) = a_chip();
-            build_clock(&mut world, &mut chip, 15.0, 90, looping, Position { x: 0, y: 0, z: 6 });
+            let origin = Position { x: 0, y: 0, z: 6 };
+            build_clock(&mut world, &mut chip, 15.0, 90, playback, false, origin);
             let placed: Vec<_> = chip.placed().to_vec();
             let mut wires = wire_shapes(&world);
             wires.sort();
             (placed, wires)
         };
-        let (loop_placed, loop_wires) = build(true);
-        let (stop_placed, stop_wires) = build(false);
-        assert_eq!(loop_placed, stop_placed, "same gates and pins, in the same places");
-        assert_eq!(loop_wires, stop_wires, "same wiring");
+        let (loop_placed, loop_wires) = build(Playback::default());
+        for stop in [Playback::ONCE, Playback { plays: Some(3), ..Playback::default() }] {
+            let (stop_placed, stop_wires) = build(stop);
+            assert_eq!(loop_placed, stop_placed, "same gates and pins, in the same places");
+            assert_eq!(loop_wires, stop_wires, "same wiring");
+        }
         // And the two limits really do differ, or this test is comparing a
         // change that never happened.
         assert_ne!(stop_limit(15.0, 90), FREE_RUNNING_LIMIT);
@@ -581,10 +885,141 @@ mod tests {
     #[test]
     fn the_clocks_own_bricks_never_overlap() {
         for origin in [Position { x: 0, y: 0, z: 6 }, Position { x: 40, y: 20, z: 6 }] {
+            for seekable in [false, true] {
+                for order in ORDERS {
+                    let (mut world, mut chip) = a_chip();
+                    let playback = Playback { order, plays: None };
+                    build_clock(&mut world, &mut chip, 10.0, 5, playback, seekable, origin);
+                    super::super::layout::assert_no_overlap(chip.placed())
+                        .unwrap_or_else(|e| panic!("clock at {origin:?}: {e}"));
+                }
+            }
+        }
+    }
+
+    /// A seekable clock is the plain one with the chain's `BitwiseOR ->
+    /// ModuloFloored` link routed through an `Add`: one gate and one wire
+    /// more, and the offset port is that gate's other input.
+    #[test]
+    fn a_seekable_clock_adds_its_offset_before_the_wrap() {
+        let origin = Position { x: 0, y: 0, z: 6 };
+        let (mut plain_world, mut plain_chip) = a_chip();
+        let looping = Playback::default();
+        let plain =
+            build_clock(&mut plain_world, &mut plain_chip, 10.0, 90, looping, false, origin);
+        let (mut world, mut chip) = a_chip();
+        let clock = build_clock(&mut world, &mut chip, 10.0, 90, looping, true, origin);
+
+        assert!(plain.offset.is_none());
+        let offset = clock.offset.expect("a seekable clock has an offset port");
+        assert_eq!(offset.component_type.to_string(), ADD);
+        assert_eq!(offset.port_name.to_string(), "InputB");
+        assert_eq!(chip.placed().len(), plain_chip.placed().len() + 1);
+        assert_eq!(world.wires.len(), plain_world.wires.len() + 1);
+
+        let mut got = wire_shapes(&world);
+        got.sort();
+        assert!(got.contains(&(format!("{BITWISE_OR}.Output"), format!("{ADD}.InputA"))));
+        assert!(got.contains(&(format!("{ADD}.Output"), format!("{MODULO}.InputA"))));
+        assert!(
+            !got.contains(&(format!("{BITWISE_OR}.Output"), format!("{MODULO}.InputA"))),
+            "the offset must not be bypassed"
+        );
+    }
+
+    // --- Playback orders -----------------------------------------------------
+
+    const RANGE: PlayOrder = PlayOrder::Range { start_s: 0.2, end_s: 0.5 };
+    const ORDERS: [PlayOrder; 3] = [PlayOrder::Forward, PlayOrder::PingPong, RANGE];
+
+    /// The first `n` frames a `frames`-frame clip at 10 fps shows.
+    fn shown(order: PlayOrder, frames: usize, n: i64) -> Vec<usize> {
+        let playback = Playback { order, plays: None };
+        (0..n).map(|t| playback.frame_at(t, 10.0, frames)).collect()
+    }
+
+    #[test]
+    fn each_order_shows_its_frames_in_its_own_order() {
+        assert_eq!(shown(PlayOrder::Forward, 4, 9), vec![0, 1, 2, 3, 0, 1, 2, 3, 0]);
+        assert_eq!(shown(PlayOrder::PingPong, 4, 9), vec![0, 1, 2, 3, 2, 1, 0, 1, 2]);
+        // 0.2 s..0.5 s is frames 2..5: the intro 0 and 1 once, then 2, 3, 4.
+        assert_eq!(shown(RANGE, 6, 10), vec![0, 1, 2, 3, 4, 2, 3, 4, 2, 3]);
+        // The clips too short to have a way back, or a range inside them.
+        assert_eq!(shown(PlayOrder::PingPong, 1, 3), vec![0, 0, 0]);
+        assert_eq!(shown(PlayOrder::PingPong, 2, 4), vec![0, 1, 0, 1]);
+        assert_eq!(shown(RANGE, 2, 4), vec![0, 1, 1, 1], "a range past the clip is its end");
+    }
+
+    /// A counted playback's limit must expire on the frame its last pass ends
+    /// on: the last frame forward, the first after a ping-pong's way back, the
+    /// range's own last frame.
+    #[test]
+    fn a_counted_playback_stops_on_the_frame_its_last_pass_ends_on() {
+        for &fps in &[10.0f32, 23.976, 30.0] {
+            for &frames in &[1usize, 2, 7, 90] {
+                for order in ORDERS {
+                    for plays in [1, 2, 5] {
+                        let playback = Playback { order, plays: Some(plays) };
+                        let t = (playback.limit(fps, frames) * fps as f64).floor() as i64;
+                        let end = match playback.wrap(fps, frames) {
+                            Wrap::Forward { frames } => frames - 1,
+                            Wrap::PingPong { .. } => 0,
+                            Wrap::Range { start, len } => start + len - 1,
+                        };
+                        assert_eq!(
+                            playback.frame_at(t, fps, frames),
+                            end,
+                            "{order:?} x{plays} over {frames} frames at {fps} fps"
+                        );
+                        // And it is the last pass's end, not the first's.
+                        let once = Playback { plays: Some(1), ..playback }.limit(fps, frames);
+                        assert_eq!(playback.limit(fps, frames) > once, plays > 1);
+                    }
+                }
+            }
+        }
+        assert_eq!(Playback::ONCE.limit(15.0, 90), stop_limit(15.0, 90));
+        assert_eq!(Playback::default().limit(15.0, 90), FREE_RUNNING_LIMIT);
+    }
+
+    #[test]
+    fn loop_playback_off_plays_once_unless_a_count_is_given() {
+        let three = Playback { plays: Some(3), ..Playback::default() };
+        assert_eq!(Playback::default().with_loop(true), Playback::default());
+        assert_eq!(Playback::default().with_loop(false), Playback::ONCE);
+        assert_eq!(three.with_loop(false), three);
+        assert_eq!(three.with_loop(true), three);
+    }
+
+    #[test]
+    fn zero_plays_and_backward_ranges_are_refused() {
+        assert!(Playback { plays: Some(0), ..Playback::default() }.check().is_err());
+        for (start_s, end_s) in [(2.0, 1.0), (1.0, 1.0), (-1.0, 1.0), (0.0, f64::NAN)] {
+            let order = PlayOrder::Range { start_s, end_s };
+            assert!(Playback { order, plays: None }.check().is_err(), "{start_s}..{end_s}");
+        }
+        assert!(Playback { order: RANGE, plays: Some(2) }.check().is_ok());
+    }
+
+    /// Each order adds exactly what [`Playback::clock_cost`] says, and the
+    /// index the render reads is the last gate of its arithmetic.
+    #[test]
+    fn each_order_adds_what_its_clock_cost_counts() {
+        let origin = Position { x: 0, y: 0, z: 6 };
+        let (mut plain_world, mut plain_chip) = a_chip();
+        build_clock(&mut plain_world, &mut plain_chip, 10.0, 9, Playback::default(), true, origin);
+        for (order, last) in [(PlayOrder::PingPong, MIN), (RANGE, ADD)] {
+            let playback = Playback { order, plays: None };
             let (mut world, mut chip) = a_chip();
-            build_clock(&mut world, &mut chip, 10.0, 5, true, origin);
-            super::super::layout::assert_no_overlap(chip.placed())
-                .unwrap_or_else(|e| panic!("clock at {origin:?}: {e}"));
+            let clock = build_clock(&mut world, &mut chip, 10.0, 9, playback, true, origin);
+            let (gates, wires) = playback.clock_cost();
+            assert_eq!(chip.placed().len(), plain_chip.placed().len() + gates, "{order:?}");
+            assert_eq!(world.wires.len(), plain_world.wires.len() + wires, "{order:?}");
+            assert_eq!(clock.frame_index.component_type.to_string(), last, "{order:?}");
+            // The progress tap reads the folded index, not the raw wrap.
+            let got = wire_shapes(&world);
+            assert!(got.contains(&(format!("{last}.Output"), format!("{MULTIPLY}.InputA"))));
+            assert!(!got.contains(&(format!("{MODULO}.Output"), format!("{MULTIPLY}.InputA"))));
         }
     }
 }
@@ -42,6 +42,7 @@ use super::color_pack::{ColorPacker, LinearColor};
 use super::controls;
 use super::layout::{GATE_HALF, STAGE_PITCH, lattice_pos_staged};
 use super::pack;
+use super::seek;
 use super::subtitle_display;
 // Gate/component names are shared with the hex renderer -- reused, never
 // redefined, so a rename can only ever happen in one place.
@@ -164,20 +165,22 @@ pub fn build_color_array_world(
     // --- 3. Frame index source ---------------------------------------------
     // `control_pins` carries the clock's Pause/Restart/Resume pin ids for the
     // control buttons below; `None` under `--external-clock` (no timer, no pins).
-    let (frame_index, control_pins) = if opts.external_clock {
+    let (frame_index, control_pins, seek_hook) = if opts.external_clock {
         let pin = chip::add_input_pin(&mut chip, "Frame", service(0, -1));
-        (chip::pin_source(pin, true), None)
+        (chip::pin_source(pin, true), None, None)
     } else {
         let clock = clock::build_clock(
             &mut world,
             &mut chip,
             info.fps,
             frame_count,
-            opts.loop_playback,
+            opts.playback.with_loop(opts.loop_playback),
+            !opts.seek.is_empty(),
             service(0, -2),
         );
         let pins = (clock.pause_pin, clock.restart_pin, clock.resume_pin);
-        (clock.frame_index, Some(pins))
+        let hook = seek::SeekHook::of(&clock);
+        (clock.frame_index, Some(pins), hook)
     };
 
     // --- 4. Exec source -----------------------------------------------------
@@ -355,6 +358,14 @@ pub fn build_color_array_world(
         let anchor = controls::control_anchor(&world);
         controls::add_control_buttons(&mut world, pause, restart, resume, anchor);
     }
+    // Seek controls, if asked for: the graph beyond everything else in the
+    // chip, the buttons beside the control buttons. Same step as hex mode's.
+    if let Some(hook) = &seek_hook {
+        let plan = opts.seek.plan(info.fps, frame_count, opts.source_start_s);
+        let buttons = seek::add_seek_graph(&mut world, &mut chip, hook, &plan);
+        let anchor = controls::control_anchor(&world);
+        controls::add_seek_buttons(&mut world, &buttons, anchor);
+    }
 
     // --- 8. Publish ---------------------------------------------------------
     //
@@ -61,6 +61,10 @@ pub const CONTROL_BRICKS: usize = CONTROLS.len();
 /// Wires it adds: one per control, `bHeld -> pin`.
 pub const CONTROL_WIRES: usize = CONTROLS.len();
 
+/// Every seek button's colour ([`add_seek_buttons`]): one colour for the whole
+/// row, so it reads as one control apart from the three above.
+pub const SEEK_COLOR: Color = Color { r: 255, g: 200, b: 0 };
+
 /// The label's component `LineHeight` (font size).
 const LABEL_LINE_HEIGHT: f32 = 1.0;
 /// Clearance past the farthest main-grid face before the button row starts.
@@ -130,6 +134,27 @@ pub fn add_control_buttons(
     resume_pin: usize,
     anchor: Position,
 ) {
+    let pins = [pause_pin, restart_pin, resume_pin];
+    let row: Vec<(&str, Color, usize)> =
+        CONTROLS.iter().zip(pins).map(|(&(name, color), pin)| (name, color, pin)).collect();
+    add_button_row(world, &row, anchor);
+}
+
+/// Build one button per seek control, in the order given, each wired into its
+/// pin -- the `(label, pin)` pairs [`super::seek::add_seek_graph`] returns.
+///
+/// The same assembly as [`add_control_buttons`], all in [`SEEK_COLOR`]. Pass a
+/// fresh [`control_anchor`] read after the control buttons are placed, and the
+/// row lands beside theirs.
+pub fn add_seek_buttons(world: &mut World, buttons: &[(String, usize)], anchor: Position) {
+    let row: Vec<(&str, Color, usize)> =
+        buttons.iter().map(|(label, pin)| (label.as_str(), SEEK_COLOR, *pin)).collect();
+    add_button_row(world, &row, anchor);
+}
+
+/// A row of labelled buttons along +y from `anchor`, each `bHeld` wired into
+/// its pin's external input. See [`add_control_buttons`] for the assembly.
+fn add_button_row(world: &mut World, buttons: &[(&str, Color, usize)], anchor: Position) {
     debug_assert!(
         anchor.x >= 0 && anchor.y >= 0 && anchor.z >= 0,
         "control-button anchor must be non-negative, got {anchor:?}"
@@ -142,8 +167,7 @@ pub fn add_control_buttons(
     // width if that is wider, so they never overlap whatever `brdb` reports.
     let slot_y = (2 * half.y).max(10);
 
-    let pins = [pause_pin, restart_pin, resume_pin];
-    for (i, (&(name, color), &pin)) in CONTROLS.iter().zip(&pins).enumerate() {
+    for (i, &(name, color, pin)) in buttons.iter().enumerate() {
         let base_y = anchor.y + i as i32 * slot_y;
         let button_pos = Position {
             x: anchor.x + half.x,
@@ -7,6 +7,7 @@
 //! express a difference (e.g. `char_repeat`, or whether a subtitle track is
 //! set) cannot get it wrong the way a hand-picked subset can.
 use super::bricks::AnimOptions;
+use super::clock::PlayOrder;
 use super::pack::{HEX_STRIDE, PIXELS_PER_CHUNK};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -21,6 +22,23 @@ pub struct Cost {
     pub chars: usize,
 }
 
+impl Cost {
+    /// Two chips' costs as one render's: every count adds, while `banks` and
+    /// `frames` -- the same clip on every chip -- carry through.
+    fn plus(self, other: Cost) -> Cost {
+        Cost {
+            pixels: self.pixels + other.pixels,
+            gates: self.gates + other.gates,
+            wires: self.wires + other.wires,
+            bricks: self.bricks + other.bricks,
+            chunks: self.chunks + other.chunks,
+            banks: self.banks,
+            frames: self.frames,
+            chars: self.chars + other.chars,
+        }
+    }
+}
+
 /// What a subtitle track adds to a render, in `(gates, wires, bricks)`.
 ///
 /// Exactly what [`crate::anim::subtitle_display::add_subtitle_display`]
@@ -75,6 +93,68 @@ fn control_button_cost(opts: &AnimOptions) -> (usize, usize, usize) {
     }
 }
 
+/// What the seek controls add, in `(gates, wires, bricks)`: the graph
+/// [`crate::anim::seek::graph_cost`] counts in the clock's chip -- the clock's
+/// own extra `Add` included -- plus one main-grid button and one `bHeld` wire
+/// per seek button.
+///
+/// `(0, 0, 0)` without [`AnimOptions::seek`], and under
+/// [`AnimOptions::external_clock`], which has no clock to seek. An upper
+/// bound when chapters are listed: the render drops any outside the clip.
+fn seek_cost(opts: &AnimOptions) -> (usize, usize, usize) {
+    if opts.external_clock {
+        return (0, 0, 0);
+    }
+    let (gates, wires) = super::seek::graph_cost(&opts.seek);
+    let buttons = super::seek::button_count(&opts.seek);
+    (gates, wires + buttons, buttons)
+}
+
+/// [`seek_cost`] for a renderer whose playheads need the skips on marks
+/// ([`super::seek::SeekPlan::on_marks`]).
+fn marked_seek_cost(opts: &AnimOptions) -> (usize, usize, usize) {
+    if opts.external_clock {
+        return (0, 0, 0);
+    }
+    let (gates, wires) = super::seek::marked_graph_cost(&opts.seek);
+    let buttons = super::seek::button_count(&opts.seek);
+    (gates, wires + buttons, buttons)
+}
+
+/// What [`AnimOptions::playback`]'s order adds to the clock, in `(gates,
+/// wires)` -- see [`super::clock::Playback::clock_cost`]. Nothing under
+/// [`AnimOptions::external_clock`], whose frame index arrives from outside.
+fn playback_cost(opts: &AnimOptions) -> (usize, usize) {
+    if opts.external_clock {
+        return (0, 0);
+    }
+    opts.playback.clock_cost()
+}
+
+/// How [`crate::anim::delta_bricks`]' playheads resync after a backward step,
+/// in `(gates, wires)` for `pixels` of them: the shared `BufferTicks`/
+/// `CompareLess` reset pair, or on a clock with landings past frame 0
+/// ([`super::landing`]) the landing graph and each pixel's keys. With no fps
+/// to round it by, a loop range starting after 0 s is taken to start after
+/// frame 0, and every listed chapter to be inside the clip -- the bound side.
+fn delta_resync_cost(opts: &AnimOptions, pixels: usize) -> (usize, usize) {
+    use crate::anim::delta_bricks::{LANDING_GATES_PER_PIXEL, LANDING_WIRES_PER_PIXEL};
+    use crate::anim::landing::Landings;
+    if opts.external_clock {
+        return (2, 3);
+    }
+    let seek = &opts.seek;
+    let clips = seek.playlist.as_ref().map_or(0, |p| p.clips.len());
+    let (gates, wires) = match opts.playback.order {
+        PlayOrder::Range { start_s, .. } if start_s > 0.0 => Landings::cost(1, false),
+        _ if !seek.is_empty() => {
+            Landings::cost(seek.chapters.len() + clips + seek.bar, seek.skip_s > 0.0)
+        }
+        _ => return (2, 3),
+    };
+    (gates + pixels * LANDING_GATES_PER_PIXEL, wires + pixels * LANDING_WIRES_PER_PIXEL)
+}
+
 /// Estimate the build cost of a `width * height` screen over `frames` frames,
 /// spilling across arrays of at most `opts.bank_size` frames each.
 ///
@@ -94,22 +174,75 @@ fn control_button_cost(opts: &AnimOptions) -> (usize, usize, usize) {
 /// `opts.subtitles` adds [`subtitle_cost`] on top; the whole `AnimOptions` is
 /// taken rather than a `bank_size` so it cannot be read from a different
 /// value than the render uses (see the module doc).
+///
+/// Under [`AnimOptions::panels`] this is the whole screen's total: the sum of
+/// [`estimate_chips`], whose entries are what each chip carries.
 pub fn estimate(width: u32, height: u32, frames: usize, opts: &AnimOptions) -> Cost {
+    let chips = estimate_chips(width, height, frames, opts);
+    chips.into_iter().reduce(Cost::plus).expect("PanelGrid::split always yields a panel")
+}
+
+/// [`estimate`], one [`Cost`] per microchip of a screen split by
+/// [`AnimOptions::panels`], in the renderer's panel order (row-major from
+/// the top left). Each chip's `gates` is its own load -- the figure to hold
+/// against the game's ~20 000-gate frame-drop point -- and `bricks` counts
+/// its display bricks and shell.
+///
+/// The first chip carries the clock, the control buttons and any subtitle,
+/// plus one wire into its `Frame Index` output pin when there are others.
+/// Every other chip swaps the clock for a `Frame` input pin (a pin, not a
+/// gate) and its one cross-chip wire, counted on the receiving chip.
+pub fn estimate_chips(width: u32, height: u32, frames: usize, opts: &AnimOptions) -> Vec<Cost> {
+    let panels = opts.panels.split(width, height);
+    let shared = panels.len() > 1;
+    panels
+        .iter()
+        .enumerate()
+        .map(|(i, p)| estimate_chip(p.width, p.height, frames, opts, i == 0, shared))
+        .collect()
+}
+
+/// One hex-mode chip driving a `width * height` panel; `first` is whether
+/// it owns the clock, `shared` whether any other chip reads it.
+fn estimate_chip(
+    width: u32,
+    height: u32,
+    frames: usize,
+    opts: &AnimOptions,
+    first: bool,
+    shared: bool,
+) -> Cost {
     let pixels = width as usize * height as usize;
     let chunks = pixels.div_ceil(PIXELS_PER_CHUNK).max(1);
     let banks = frames.div_ceil(opts.bank_size.max(1)).max(1);
     let boundaries = banks - 1;
-    let (sub_gates, sub_wires, sub_bricks) = subtitle_cost(opts, banks);
-    let (cb_gates, cb_wires, cb_bricks) = control_button_cost(opts);
+    let ((sub_gates, sub_wires, sub_bricks), (cb_gates, cb_wires, cb_bricks)) = if first {
+        (subtitle_cost(opts, banks), control_button_cost(opts))
+    } else {
+        ((0, 0, 0), (0, 0, 0))
+    };
+    let (seek_gates, seek_wires, seek_bricks) = if first { seek_cost(opts) } else { (0, 0, 0) };
+    let (order_gates, order_wires) = if first { playback_cost(opts) } else { (0, 0) };
+    // The first chip's 6 clock gates and 11 wires (see below); any other
+    // chip's one cross-chip wire into its `Frame` pin. A shared first chip
+    // also writes its frame index into its `Frame Index` pin.
+    let (clock_gates, clock_wires) = match (first, shared) {
+        (true, false) => (6, 11),
+        (true, true) => (6, 12),
+        (false, _) => (0, 1),
+    };
     Cost {
         pixels,
         // 2 per pixel + 2 per chunk per bank (ArrayVar, Get) + 6 clock
         // + 1 detector, plus per boundary: comparator, branch, index subtract,
         // and one value select per chunk. The 6 clock gates are the 4 index
         // gates plus the length and progress status taps.
-        gates: 2 * pixels + 2 * chunks * banks + 7 + boundaries * 3 + boundaries * chunks
+        gates: 2 * pixels + 2 * chunks * banks + clock_gates + 1 + boundaries * 3
+            + boundaries * chunks
             + sub_gates
-            + cb_gates,
+            + cb_gates
+            + seek_gates
+            + order_gates,
         // 3 per pixel + 2 per chunk per bank + exec chain + detector feed
         // + 11 clock (3 chain + 3 control pins + Rate + Done + the length and
         // progress taps: 2 pin writes + 1 shared frame-index read), plus per
@@ -125,12 +258,14 @@ pub fn estimate(width: u32, height: u32, frames: usize, opts: &AnimOptions) -> C
             + 2 * chunks * banks
             + chunks * banks
             + 1
-            + 11
+            + clock_wires
             + boundaries * (3 * chunks + 4)
             + sub_wires
-            + cb_wires,
+            + cb_wires
+            + seek_wires
+            + order_wires,
         // one display brick per pixel + the microchip shell + control buttons
-        bricks: pixels + 1 + sub_bricks + cb_bricks,
+        bricks: pixels + 1 + sub_bricks + cb_bricks + seek_bricks,
         chunks,
         banks,
         frames,
@@ -169,13 +304,17 @@ pub fn estimate_color_array(width: u32, height: u32, frames: usize, opts: &AnimO
     let boundaries = banks - 1;
     let (sub_gates, sub_wires, sub_bricks) = subtitle_cost(opts, banks);
     let (cb_gates, cb_wires, cb_bricks) = control_button_cost(opts);
+    let (seek_gates, seek_wires, seek_bricks) = seek_cost(opts);
+    let (order_gates, order_wires) = playback_cost(opts);
     Cost {
         pixels,
         // per pixel: one ArrayVar + one Get per bank, plus one Select per
         // boundary; plus 6 clock + 1 detector; plus per boundary a comparator,
         // a branch and an index subtract. The 6 clock gates are the 4 index
         // gates plus the length and progress status taps.
-        gates: pixels * (2 * banks + boundaries) + 7 + boundaries * 3 + sub_gates + cb_gates,
+        gates: pixels * (2 * banks + boundaries) + 7 + boundaries * 3 + sub_gates + cb_gates
+            + seek_gates
+            + order_gates,
         // per pixel: ArrayVarRef + Index + Exec per bank (3), plus
         // bSelectB/InputA/InputB per boundary (3), plus the one wire into the
         // display brick's Color; plus the detector feed (1), the clock's own
@@ -183,9 +322,11 @@ pub fn estimate_color_array(width: u32, height: u32, frames: usize, opts: &AnimO
         // plus per boundary the comparator's InputA, the subtract's InputA and
         // the branch's bCond/Exec.
         wires: pixels * (3 * banks + 3 * boundaries + 1) + 1 + 11 + boundaries * 4 + sub_wires
-            + cb_wires,
+            + cb_wires
+            + seek_wires
+            + order_wires,
         // one display brick per pixel + the microchip shell + control buttons
-        bricks: pixels + 1 + sub_bricks + cb_bricks,
+        bricks: pixels + 1 + sub_bricks + cb_bricks + seek_bricks,
         // Genuinely zero, not unknown: see the doc comment.
         chunks: 0,
         banks,
@@ -194,6 +335,108 @@ pub fn estimate_color_array(width: u32, height: u32, frames: usize, opts: &AnimO
     }
 }
 
+/// Estimate the build cost of the same screen rendered in delta mode
+/// ([`crate::anim::delta_bricks`]).
+///
+/// Unlike the other two brick encodings nothing per pixel depends on the
+/// bank count: each pixel's playhead is
+/// [`GATES_PER_PIXEL`](crate::anim::delta_bricks::GATES_PER_PIXEL) gates and
+/// [`WIRES_PER_PIXEL`](crate::anim::delta_bricks::WIRES_PER_PIXEL) wires
+/// whatever the clip's length. The bank spine is still built, for a subtitle
+/// track, so `banks` and its per-boundary gates are counted as in
+/// [`estimate_color_array`].
+///
+/// The same upper bound on `gates`, `wires` and `bricks` as the other two
+/// (every pixel assumed to survive), and the same `chunks`/`chars` of 0. What
+/// the render stores is content-dependent -- how often each pixel changes --
+/// so no element count is estimated; the renderer logs the real one.
+pub fn estimate_delta(width: u32, height: u32, frames: usize, opts: &AnimOptions) -> Cost {
+    use crate::anim::delta_bricks::{GATES_PER_PIXEL, WIRES_PER_PIXEL};
+    let pixels = width as usize * height as usize;
+    let banks = frames.div_ceil(opts.bank_size.max(1)).max(1);
+    let boundaries = banks - 1;
+    let (sub_gates, sub_wires, sub_bricks) = subtitle_cost(opts, banks);
+    let (cb_gates, cb_wires, cb_bricks) = control_button_cost(opts);
+    let (seek_gates, seek_wires, seek_bricks) = marked_seek_cost(opts);
+    let (order_gates, order_wires) = playback_cost(opts);
+    let (resync_gates, resync_wires) = delta_resync_cost(opts, pixels);
+    Cost {
+        pixels,
+        // per pixel: the playhead; plus 6 clock + 1 detector + the shared
+        // resync; plus the spine's 3 per boundary.
+        gates: pixels * GATES_PER_PIXEL + 7 + resync_gates + boundaries * 3 + sub_gates + cb_gates
+            + seek_gates
+            + order_gates,
+        // per pixel: the playhead's own; plus the detector feed (1), the
+        // clock's 11, the resync's; plus the spine's 4 per boundary.
+        wires: pixels * WIRES_PER_PIXEL + 1 + 11 + resync_wires + boundaries * 4 + sub_wires
+            + cb_wires
+            + seek_wires
+            + order_wires,
+        bricks: pixels + 1 + sub_bricks + cb_bricks + seek_bricks,
+        chunks: 0,
+        banks,
+        frames,
+        chars: 0,
+    }
+}
+
+/// Estimate the build cost of the same screen rendered in palette mode
+/// ([`crate::anim::palette_bricks`]).
+///
+/// [`estimate`]'s shape, with three differences: chunks are
+/// `MAX_COMPONENT_CHARS / stride` pixels rather than [`PIXELS_PER_CHUNK`],
+/// each pixel is [`GATES_PER_PIXEL`](crate::anim::palette_bricks::GATES_PER_PIXEL)
+/// gates behind a change detector per chunk, and one palette array is shared
+/// by all of them. The stride comes from
+/// [`palette_size`](crate::anim::palette_bricks::palette_size) -- the size
+/// asked for, which is what the render's stride is decided from too, even
+/// when median cut finds fewer colours.
+///
+/// The same upper bound on `gates`, `wires` and `bricks` as [`estimate`], and
+/// `chars` is exact for the same reason: a culled pixel still reserves its
+/// slot.
+pub fn estimate_palette(width: u32, height: u32, frames: usize, opts: &AnimOptions) -> Cost {
+    use crate::anim::palette::index_stride;
+    use crate::anim::palette_bricks::{GATES_PER_PIXEL, WIRES_PER_PIXEL, palette_size};
+    let stride = index_stride(palette_size(opts));
+    let pixels = width as usize * height as usize;
+    let chunks = pixels.div_ceil(crate::text::MAX_COMPONENT_CHARS / stride).max(1);
+    let banks = frames.div_ceil(opts.bank_size.max(1)).max(1);
+    let boundaries = banks - 1;
+    let (sub_gates, sub_wires, sub_bricks) = subtitle_cost(opts, banks);
+    let (cb_gates, cb_wires, cb_bricks) = control_button_cost(opts);
+    let (seek_gates, seek_wires, seek_bricks) = seek_cost(opts);
+    let (order_gates, order_wires) = playback_cost(opts);
+    Cost {
+        pixels,
+        // per pixel: the lookup; per chunk per bank: ArrayVar + Get; per
+        // chunk: its detector; plus 6 clock + 1 frame detector + the palette
+        // array; plus per boundary the spine's 3 and a select per chunk.
+        gates: pixels * GATES_PER_PIXEL + 2 * chunks * banks + chunks + 8 + boundaries * 3
+            + boundaries * chunks
+            + sub_gates
+            + cb_gates
+            + seek_gates
+            + order_gates,
+        // per pixel: the lookup's own; per chunk per bank: ArrayVarRef,
+        // Index and the exec chain link; per chunk: its detector's input;
+        // plus the detector feed (1) and the clock's 11; plus per boundary
+        // the spine's 4 and a select's 3 per chunk.
+        wires: pixels * WIRES_PER_PIXEL + 3 * chunks * banks + chunks + 1 + 11
+            + boundaries * (3 * chunks + 4)
+            + sub_wires
+            + cb_wires
+            + seek_wires
+            + order_wires,
+        bricks: pixels + 1 + sub_bricks + cb_bricks + seek_bricks,
+        chunks,
+        banks,
+        frames,
+        chars: pixels * frames * stride,
+    }
+}
+
 /// Estimate the build cost of a `width * height` text-mode screen
 /// ([`crate::anim::text_bricks`]) over `frames` frames, spilling across
 /// arrays of at most `opts.bank_size` frames each.
@@ -207,11 +450,12 @@ pub fn estimate_color_array(width: u32, height: u32, frames: usize, opts: &AnimO
 /// brick encoding at typical screen sizes: a 192x108 screen is 2304 pixels
 /// but only 54 bands.
 ///
-/// The band layout depends on `opts.text.char_repeat`
-/// ([`crate::text::TextOptions`]); reading it off the same `AnimOptions` the
-/// render is built from (rather than a separate parameter) is what keeps a
-/// `--font` choosing a different-width glyph from silently disagreeing with
-/// the render it describes -- see the module doc.
+/// The band layout depends on `opts.text.char_repeat` and `opts.text.mode`
+/// ([`crate::text::TextOptions`]); reading them off the same `AnimOptions`
+/// the render is built from (rather than separate parameters) is what keeps
+/// a `--font` choosing a different-width glyph, or `--braille` packing eight
+/// pixels a character, from silently disagreeing with the render it
+/// describes -- see the module doc.
 ///
 /// This is not the same tradeoff [`estimate`] and [`estimate_color_array`] make
 /// by assuming the built-in clock. That assumption is off by a fixed handful of
@@ -228,8 +472,9 @@ pub fn estimate_color_array(width: u32, height: u32, frames: usize, opts: &AnimO
 /// length depends on the clip's actual content -- something this function has
 /// no way to know before a render. A number that looks like a measurement but
 /// is a guess is worse than no number: the CLI instead prints
-/// [`crate::anim::text_layout::worst_case_row_chars`]'s bound separately,
-/// clearly labelled as a bound rather than an estimate.
+/// [`crate::anim::text_layout::band_char_bound`] separately, clearly
+/// labelled as a bound rather than an estimate. (A glyph mode's length is
+/// in fact fixed, but it is reported the same way rather than special-cased.)
 ///
 /// `pixels` is still `width * height`, for display purposes (it is not the
 /// unit gates/bricks are counted in here). `chunks` is genuinely 0: text
@@ -253,28 +498,29 @@ pub fn estimate_text(
 ) -> Result<Cost, String> {
     // The band count depends on `char_repeat`: a single-glyph font (Orbitron,
     // `char_repeat` 1) fits more rows per component than a double-glyph one,
-    // so 192x108 bands 36 ways rather than 54. Reading the option off the
-    // same struct the render is built from, rather than taking it as a
-    // separate parameter, is what keeps this in sync with `--font`.
+    // so 192x108 bands 36 ways rather than 54 -- and on the pixel mode, since
+    // braille fits 108 rows in one. Reading both off the same struct the
+    // render is built from, rather than taking them as separate parameters,
+    // is what keeps this in sync with `--font` and `--braille`.
     //
     // Propagated, not swallowed: see this function's doc.
-    let bands = super::text_layout::plan_bands(
-        width as usize,
-        height as usize,
-        opts.text.char_repeat.max(1),
-    )?
-    .len();
+    let bands =
+        super::text_layout::plan_text_bands(width as usize, height as usize, &opts.text)?.len();
     let banks = frames.div_ceil(opts.bank_size.max(1)).max(1);
     let boundaries = banks - 1;
     let (sub_gates, sub_wires, sub_bricks) = subtitle_cost(opts, banks);
     let (cb_gates, cb_wires, cb_bricks) = control_button_cost(opts);
+    let (seek_gates, seek_wires, seek_bricks) = seek_cost(opts);
+    let (order_gates, order_wires) = playback_cost(opts);
     Ok(Cost {
         pixels: width as usize * height as usize,
         // 2 per band per bank (ArrayVar, Get) + 6 clock + 1 detector, plus
         // per boundary: comparator, branch, index subtract, and one select
         // per band. The 6 clock gates are the 4 index gates plus the length
         // and progress status taps.
-        gates: 2 * bands * banks + 7 + boundaries * 3 + boundaries * bands + sub_gates + cb_gates,
+        gates: 2 * bands * banks + 7 + boundaries * 3 + boundaries * bands + sub_gates + cb_gates
+            + seek_gates
+            + order_gates,
         // 3 per band per bank (ArrayVarRef, Index, Exec) + 1 per band (the
         // wire into the TextDisplay's Text port) + detector feed (1) + 3
         // clock chain + 3 control pins + Rate + Done + length/progress taps
@@ -282,9 +528,11 @@ pub fn estimate_text(
         // + branch bCond/Exec (2) + one select's bSelectB/InputA/InputB (3)
         // per band.
         wires: bands * (3 * banks + 3 * boundaries + 1) + 1 + 11 + boundaries * 4 + sub_wires
-            + cb_wires,
+            + cb_wires
+            + seek_wires
+            + order_wires,
         // one TextDisplay anchor per band + the microchip shell + control buttons
-        bricks: bands + 1 + sub_bricks + cb_bricks,
+        bricks: bands + 1 + sub_bricks + cb_bricks + seek_bricks,
         // Genuinely zero, not unknown: text mode tiles nothing into chunks.
         chunks: 0,
         banks,
@@ -366,6 +614,30 @@ mod tests {
         );
     }
 
+    /// Splitting moves pixel gates between chips but keeps a single clock: the
+    /// only added cost is one wire per extra chip plus the master's pin write,
+    /// and one shell brick per extra chip.
+    #[test]
+    fn a_split_screen_costs_its_pins_and_shells_and_nothing_else() {
+        use crate::anim::bricks::PanelGrid;
+        let one = opts(BANK_FRAMES);
+        assert_eq!(estimate_chips(64, 36, 300, &one).len(), 1);
+        let split = AnimOptions { panels: PanelGrid { cols: 4, rows: 2 }, ..opts(BANK_FRAMES) };
+        let chips = estimate_chips(64, 36, 300, &split);
+        assert_eq!(chips.len(), 8);
+        assert_eq!(chips.iter().map(|c| c.pixels).sum::<usize>(), 64 * 36);
+        // Every panel here is 16x18, so the chips differ only by the clock.
+        for c in &chips[1..] {
+            assert_eq!(chips[0].gates - c.gates, 6, "only the first chip has a clock");
+            assert_eq!(chips[0].wires - c.wires, 11, "12 clock wires against one pin feed");
+        }
+
+        let (whole, parts) = (estimate(64, 36, 300, &one), estimate(64, 36, 300, &split));
+        assert_eq!(parts.bricks, whole.bricks + 7, "one shell per extra chip");
+        assert_eq!(parts.pixels, whole.pixels);
+        assert_eq!(parts.frames, whole.frames);
+    }
+
     #[test]
     fn matches_the_spec_formula() {
         // 64x36 = 2304 px; ceil(2304/1666) = 2 chunks
@@ -543,6 +815,16 @@ mod tests {
         assert_eq!(c.bricks, 54 + 1);
     }
 
+    #[test]
+    fn a_glyph_mode_bands_by_glyph_line() {
+        // 192x108 braille: 96 glyphs a line, all 27 lines in one band
+        let mut o = opts(BANK_FRAMES);
+        o.text.mode = crate::text::PixelMode::Braille;
+        let c = estimate_text(192, 108, 300, &o).expect("a legal geometry must estimate");
+        assert_eq!(c.gates, 2 + 7);
+        assert_eq!(c.bricks, 1 + 1);
+    }
+
     #[test]
     fn text_mode_is_orders_of_magnitude_under_brick_mode() {
         let text = estimate_text(192, 108, 300, &opts(BANK_FRAMES)).expect("a legal geometry must estimate");
@@ -0,0 +1,707 @@
+//! Delta mode: the same screen of display bricks [`super::bricks`] builds,
+//! driven by per-pixel change lists instead of a colour per pixel per frame.
+//!
+//! The third sibling of [`super::bricks::build_brick_world`] and
+//! [`super::color_bricks::build_color_array_world`]. Everything above the
+//! per-pixel layer -- display bricks, chip shell, clock or external `Frame`
+//! pin, change detector, control buttons, subtitles -- is built from the same
+//! helpers they use.
+//!
+//! # The per-pixel playhead
+//!
+//! Each pixel stores a [`PixelTrack`]: an `Int64Array` of the frames its
+//! colour changes on and a `LinearColorArray` of the colours, plus a
+//! persistent `Var` index into them. It is the stateful-`Var` playhead
+//! `audio::speakers::build_midi_event_world` plays notes with, with one
+//! difference: a note may start a tick late, a pixel may not, so the read
+//! LOOKS AHEAD instead of showing the stale colour until the index catches
+//! up. On every frame change:
+//!
+//! ```text
+//! next    = starts[idx]                       (Get, exec from the detector)
+//! reached = frame >= next
+//! colour  = colors[decreased ? 0 : reached ? idx + 1 : idx]
+//! if decreased  { idx = 0 }                   (a restart or a loop wrap)
+//! elif reached  { idx += 1 }
+//! ```
+//!
+//! Thirteen gates per pixel: the two arrays, the `Var`, two `Get`s, the
+//! compare, an `Add`, two `Select`s, two `Branch`es, `Var_Set` and
+//! `Var_Increment`. The exec runs as ONE chain per pixel (`Get` -> `Get` ->
+//! `Branch` -> `Branch`), the colour read ahead of both writes so it sees the
+//! index it was computed from, and every exec input has exactly one source --
+//! the same no-fan-in rule [`super::color_bricks`] keeps.
+//!
+//! `decreased` is shared: one `BufferTicks` holding last tick's frame index
+//! and one `CompareLess` against it, fanned out to every pixel.
+//!
+//! # What it trades
+//!
+//! Six and a half times [`super::color_bricks`]' two gates per pixel, for
+//! arrays that only grow where the picture moves. A long, mostly static clip
+//! stores a small fraction of the colours; footage where every pixel changes
+//! every frame stores slightly more than colour-array mode (the start list)
+//! and pays the gates on top, so it is the wrong encoding for it.
+//!
+//! The index advances one change per frame change. A clip that skips frames
+//! (a game tick slower than the clip's fps, or an external `Frame` pin that
+//! jumps forward) therefore lags a pixel that changed on a skipped frame by
+//! one change, and catches up on the following frames. A backward jump always
+//! resets to the start; on a restart or a loop wrap that is frame 0 and
+//! exact, and an external pin jumping back to the middle catches up the same
+//! way.
+//!
+//! # Other orders
+//!
+//! Every other backward step lands somewhere the reset gets wrong, so the
+//! two other orders of the chip's own clock each keep the steps the reset
+//! handles:
+//!
+//! * A ping-pong's way back steps back on every frame. The playheads read
+//!   the clock's [`phase`](clock::Clock::phase) instead of its index --
+//!   where it is in one pass, there and back, counting up -- over tracks
+//!   re-packed along that pass ([`PixelTrack::along`]). The pass's one
+//!   backward step is its wrap to frame 0, and a pixel stores the colours of
+//!   the way back again: up to twice the changes.
+//! * A loop range wraps back to its start. The clock gets a second
+//!   [landing](super::landing) there, and each pixel two more gates: a
+//!   `keys` array holding the run it is in on each landing, and the `Get`
+//!   that reads it at the head of the exec chain. On a landing the read and
+//!   the `Var_Set` take that key in place of 0; the reset pair is not built.
+//!
+//! # Seek buttons
+//!
+//! A seek moves the clock's frame offset, not the pixels' indices, so every
+//! frame a button jumps to is a landing as well: each chapter start and bar
+//! button's frame, and the skips' marks, which is why the skips go to a mark
+//! here rather than by their time ([`seek::SeekPlan::on_marks`]). The same two
+//! gates a pixel, and the same resync on arriving. A playlist is still
+//! refused: its clips' loop points send the clock back to a clip's start
+//! from a frame no button pressed.
+use super::cascade;
+use super::chip;
+use super::clock::{self, PlayOrder, Playback, gate};
+use super::controls;
+use super::delta_pack::{DeltaPacker, PixelTrack, stored_changes};
+use super::landing::{self, Landings};
+use super::layout::{GATE_HALF, STAGE_PITCH, lattice_pos_staged};
+use super::seek;
+use super::subtitle_display;
+use super::bricks::{
+    ARRAY_GET, ARRAY_VAR, AnimOptions, BRANCH, CHANGE_DETECTOR, COMPARE_GE, PROP_CHANGER, SELECT,
+};
+use crate::audio::speakers::BUFFER_TICKS;
+use crate::progress::{FrameTotal, Progress};
+use crate::video::stream::FrameSource;
+use brdb::{
+    AsBrdbValue, WirePort, World,
+    schema::{WireArrayVariant, WireVariant},
+};
+
+pub const VAR: &str = "BrickComponentType_WireGraphPseudo_Var";
+pub const VAR_SET: &str = "BrickComponentType_WireGraph_Exec_Var_Set";
+pub const VAR_INCREMENT: &str = "BrickComponentType_WireGraph_Exec_Var_Increment";
+pub const ADD: &str = "BrickComponentType_WireGraph_Expr_MathAdd";
+pub const COMPARE_LESS: &str = "BrickComponentType_WireGraph_Expr_CompareLess";
+
+/// Gates, and lattice stages, one pixel's playhead occupies. See the module
+/// doc for the list.
+pub const GATES_PER_PIXEL: usize = 13;
+
+/// Wires one pixel's playhead adds, the one into its display brick's `Color`
+/// included.
+pub const WIRES_PER_PIXEL: usize = 23;
+
+/// Gates, and lattice stages, a pixel's playhead adds on a clock with
+/// landings past frame 0 ([`super::landing`]): its `keys` array and the `Get`
+/// that reads them.
+pub const LANDING_GATES_PER_PIXEL: usize = 2;
+
+/// Wires those two add, with the landed index wired into the read and the
+/// `Var_Set` where the plain playhead inlines 0.
+pub const LANDING_WIRES_PER_PIXEL: usize = 5;
+
+/// Refuse the options the playhead cannot follow (see the module doc): a
+/// playlist. Run before a frame is decoded, and by the `--max-gates` search
+/// to drop delta from the encodings it tries.
+pub fn check_options(opts: &AnimOptions) -> Result<(), String> {
+    if opts.seek.playlist.is_some() {
+        return Err(
+            "--anim-encoding delta cannot take --playlist: its clip buttons and loop points \
+             jump the clock, not each pixel's playhead; use hex, color-array or palette"
+                .to_string(),
+        );
+    }
+    Ok(())
+}
+
+/// Streams `source` into a wired, animated display-brick [`World`] using the
+/// delta encoding.
+///
+/// Signature, streaming contract and cancellation semantics are identical to
+/// [`super::bricks::build_brick_world`].
+///
+/// Fails on any options [`check_options`] refuses, before decoding.
+///
+/// Fails if any pixel changes more often than one array may hold
+/// (`opts.bank_size` elements) -- the change lists are not banked, since a
+/// pixel's changes do not fall on bank boundaries the way its frames do. A
+/// clip that busy is one the other encodings render better anyway.
+pub fn build_delta_world(
+    source: &dyn FrameSource,
+    opts: &AnimOptions,
+    progress: &mut dyn Progress,
+) -> Result<World, String> {
+    check_options(opts)?;
+    let info = source.info();
+    let (w, h) = (info.width as i32, info.height as i32);
+
+    // The same fused streaming pass as the other two renderers; only the
+    // accumulator differs. See `build_brick_world` for the loop's reasoning.
+    FrameTotal::new(info.frame_count_hint, source.frame_count_estimate())
+        .begin(progress, "packing frames");
+    let mut packer = DeltaPacker::new(info.width, info.height, opts.alpha_threshold);
+    let seen: Result<u64, String> = (|| {
+        let mut stream = source.open()?;
+        let mut seen: u64 = 0;
+        while let Some(frame) = stream.next()? {
+            packer.push_frame(&frame)?;
+            progress.frame(frame.width(), frame.height(), frame.as_raw());
+            seen += 1;
+            progress.tick(seen);
+            if progress.is_cancelled() {
+                break;
+            }
+        }
+        Ok(seen)
+    })();
+    progress.finish();
+    let seen = seen?;
+
+    if progress.is_cancelled() {
+        return Ok(World::new());
+    }
+
+    let (tracks, visible) = packer.finish();
+    let frame_count = seen as usize;
+
+    if frame_count == 0 {
+        return Err(
+            "clip has 0 frames -- nothing to render (check --start/--duration, or the GUI's \
+             Start/Duration, against the source's length)"
+                .to_string(),
+        );
+    }
+
+    // A ping-pong's playhead reads the clock's phase, over tracks laid out
+    // there and back; a loop range's resyncs on its landings, and so does a
+    // seekable clock's. An external pin has no order and no seek buttons, so
+    // it keeps the plain reset either way.
+    let playback = opts.playback.with_loop(opts.loop_playback);
+    let plan = opts.seek.plan(info.fps, frame_count, opts.source_start_s).on_marks();
+    let (tracks, landings) = if opts.external_clock {
+        (tracks, Landings::of(&Playback::default(), info.fps, frame_count))
+    } else {
+        let tracks = match playback.order {
+            PlayOrder::PingPong => {
+                let pass = playback.unfold(info.fps, frame_count);
+                tracks.iter().map(|t| t.along(&pass)).collect()
+            }
+            _ => tracks,
+        };
+        let landings = Landings::of(&playback, info.fps, frame_count);
+        (tracks, if opts.seek.is_empty() { landings } else { landings.with_seeks(&plan, frame_count) })
+    };
+
+    let bank_size = opts.bank_size.max(1);
+    if let Some(idx) = tracks.iter().position(|t| t.colors.len() > bank_size) {
+        return Err(format!(
+            "pixel ({}, {}) changes colour {} times{}, more than one {bank_size}-element array \
+             holds -- the delta encoding keeps each pixel's changes in a single array; use \
+             --anim-encoding hex or color-array for a clip this busy",
+            idx as i32 % w,
+            idx as i32 / w,
+            tracks[idx].colors.len(),
+            if playback.order == PlayOrder::PingPong && !opts.external_clock {
+                " there and back"
+            } else {
+                ""
+            },
+        ));
+    }
+
+    let stored = stored_changes(&tracks);
+    let full = tracks.len() * frame_count;
+    log::info!(
+        "Delta encoding: {stored} stored colour(s) instead of {full} ({:.1}% fewer)",
+        100.0 * (1.0 - stored as f64 / full.max(1) as f64)
+    );
+
+    let mut world = World::new();
+    world.meta.bundle.description = "Animation generated from image frames".to_string();
+
+    // --- 1. Display bricks, 2. the chip: shared verbatim with hex mode. -----
+    let (brick_of, geometry) = super::bricks::add_display_bricks(&mut world, opts, w, h, &visible);
+    let mut chip = super::bricks::new_screen_chip(&mut world, w, h, &geometry);
+
+    let n_banks = frame_count.div_ceil(bank_size).max(1);
+    let service = |col: i32, row: i32| {
+        lattice_pos_staged(col, row, GATES_PER_PIXEL as i32, h, GATE_HALF, STAGE_PITCH)
+    };
+
+    // --- 3. Frame index source ---------------------------------------------
+    // The playheads read `phase`; the subtitle spine reads the index.
+    let (frame_index, phase, control_pins, seek_hook) = if opts.external_clock {
+        let pin = chip::add_input_pin(&mut chip, "Frame", service(0, -1));
+        let index = chip::pin_source(pin, true);
+        (index.clone(), index, None, None)
+    } else {
+        let clock = clock::build_clock(
+            &mut world,
+            &mut chip,
+            info.fps,
+            frame_count,
+            playback,
+            !opts.seek.is_empty(),
+            service(0, -2),
+        );
+        let pins = (clock.pause_pin, clock.restart_pin, clock.resume_pin);
+        let hook = seek::SeekHook::of(&clock);
+        (clock.frame_index, clock.phase, Some(pins), hook)
+    };
+
+    // --- 4. Exec source and the shared resync signal ------------------------
+    let detector = gate(
+        &mut chip,
+        "B_1x1_Gate_Expr_ChangeDetectorExec",
+        CHANGE_DETECTOR,
+        service(0, -4),
+        vec![],
+    );
+    world.add_wire_connection(phase.clone(), WirePort::new(detector, CHANGE_DETECTOR, "Input"));
+    let landed;
+    let resync = if landings.is_plain() {
+        let prev = gate(&mut chip, "B_1x1_Gate_Pseudo_BufferTicks", BUFFER_TICKS, service(1, -4), vec![(
+            "TicksToWait",
+            Box::new(1i32) as Box<dyn AsBrdbValue>,
+        )]);
+        world.add_wire_connection(phase.clone(), WirePort::new(prev, BUFFER_TICKS, "Input"));
+        let decreased =
+            gate(&mut chip, "B_1x1_Gate_Expr_CompareLess", COMPARE_LESS, service(2, -4), vec![]);
+        world.add_wire_connection(phase.clone(), WirePort::new(decreased, COMPARE_LESS, "InputA"));
+        world.add_wire_connection(
+            WirePort::new(prev, BUFFER_TICKS, "Output"),
+            WirePort::new(decreased, COMPARE_LESS, "InputB"),
+        );
+        Resync::Reset(WirePort::new(decreased, COMPARE_LESS, "bOutput"))
+    } else {
+        landed = landing::add_landing_graph(&mut world, &mut chip, &phase, &landings, |col| {
+            service(1 + col, -4)
+        });
+        Resync::Land(&landed, &landings)
+    };
+
+    // --- 5. The bank spine, for the subtitle track only ----------------------
+    // Pixels index their own change lists and never read a bank; the spine is
+    // built anyway so a subtitle track gets the same `FrameIndex` every other
+    // renderer hands it. Below one bank's worth of frames it emits nothing.
+    let cascade::BankCascade { index_of_bank, ge, entry_of_bank } = cascade::bank_cascade(
+        &mut world,
+        &mut chip,
+        &frame_index,
+        WirePort::new(detector, CHANGE_DETECTOR, "OnChanged"),
+        n_banks,
+        bank_size,
+        service,
+    );
+
+    // --- 6. One playhead per surviving pixel ---------------------------------
+    // Row-major, never `brick_of`'s iteration order -- see `color_bricks`.
+    for (idx, track) in tracks.iter().enumerate() {
+        if progress.is_cancelled() {
+            return Ok(World::new());
+        }
+        let Some(&brick_id) = brick_of.get(&idx) else {
+            continue; // culled in every frame: no display brick, so no gates
+        };
+        let (col, row) = ((idx as i32) % w, (idx as i32) / w);
+        add_pixel_playhead(
+            &mut world,
+            &mut chip,
+            track,
+            |stage| lattice_pos_staged(col, row, stage, h, GATE_HALF, STAGE_PITCH),
+            &phase,
+            &resync,
+            WirePort::new(detector, CHANGE_DETECTOR, "OnChanged"),
+            WirePort::new(brick_id, PROP_CHANGER, "Color"),
+        );
+    }
+
+    // --- 6b. Subtitles, if any: last, as in the other renderers. -------------
+    if let Some(subs) = &opts.subtitles {
+        let per_frame = subs.per_frame(opts.source_start_s, info.fps as f64, frame_count)?;
+        subtitle_display::add_subtitle_display(
+            &mut world,
+            &mut chip,
+            subtitle_display::FrameIndex {
+                index_of_bank: &index_of_bank,
+                entry_of_bank: &entry_of_bank,
+                ge: &ge,
+            },
+            &per_frame,
+            info.fps,
+            opts,
+            super::bricks::subtitle_extent(&geometry, w, h, opts.subtitle_lift)?,
+        )?;
+    }
+
+    // --- 6c. Control buttons -------------------------------------------------
+    if let (true, Some((pause, restart, resume))) = (opts.control_buttons, control_pins) {
+        let anchor = controls::control_anchor(&world);
+        controls::add_control_buttons(&mut world, pause, restart, resume, anchor);
+    }
+    // Seek controls, if asked for: the graph beyond everything else in the
+    // chip, the buttons beside the control buttons. Same step as hex mode's,
+    // on the plan the landings were laid out from.
+    if let Some(hook) = &seek_hook {
+        let buttons = seek::add_seek_graph(&mut world, &mut chip, hook, &plan);
+        let anchor = controls::control_anchor(&world);
+        controls::add_seek_buttons(&mut world, &buttons, anchor);
+    }
+
+    // --- 7. Publish ----------------------------------------------------------
+    if progress.is_cancelled() {
+        return Ok(World::new());
+    }
+    chip::finish(&mut world, chip)?;
+    world.register_used_components();
+    Ok(world)
+}
+
+/// How a pixel's playhead finds its place again when the index steps back.
+enum Resync<'a> {
+    /// Back to run 0 whenever the index decreases: this port's `bOutput`.
+    Reset(WirePort),
+    /// To its key for the landing the index is on, whenever it is on one.
+    Land(&'a landing::Landed, &'a Landings),
+}
+
+/// Build one pixel's playhead (see the module doc) on stages
+/// `0..GATES_PER_PIXEL` of its lattice column, one gate per stage, and wire
+/// its colour into `color`. A [`Resync::Land`] adds its keys on the two
+/// stages after those.
+#[allow(clippy::too_many_arguments)]
+fn add_pixel_playhead(
+    world: &mut World,
+    chip: &mut chip::Chip,
+    track: &PixelTrack,
+    at: impl Fn(i32) -> brdb::Position,
+    frame_index: &WirePort,
+    resync: &Resync,
+    exec: WirePort,
+    color: WirePort,
+) {
+    let starts = gate(chip, "B_1x1_Gate_Variable_Array", ARRAY_VAR, at(0), vec![(
+        "Value",
+        Box::new(WireArrayVariant::Int64Array(track.starts.clone())) as Box<dyn AsBrdbValue>,
+    )]);
+    let colors = gate(chip, "B_1x1_Gate_Variable_Array", ARRAY_VAR, at(1), vec![(
+        "Value",
+        Box::new(WireArrayVariant::LinearColorArray(track.colors.clone())) as Box<dyn AsBrdbValue>,
+    )]);
+    let idx = gate(chip, "B_1x1_Gate_Variable", VAR, at(2), vec![(
+        "Value",
+        Box::new(WireVariant::Int(0)) as Box<dyn AsBrdbValue>,
+    )]);
+
+    // The run to jump to, read first on the exec chain: `keys[slot]` on a
+    // clock with landings, a constant 0 inlined below on one without.
+    let (cond, key, exec) = match resync {
+        Resync::Reset(decreased) => (decreased.clone(), None, exec),
+        Resync::Land(landed, landings) => {
+            let base = GATES_PER_PIXEL as i32;
+            let keys = gate(chip, "B_1x1_Gate_Variable_Array", ARRAY_VAR, at(base), vec![(
+                "Value",
+                Box::new(WireArrayVariant::Int64Array(
+                    landings.keys(|frame| track.run_at(frame) as i64),
+                )) as Box<dyn AsBrdbValue>,
+            )]);
+            let get = gate(chip, "B_1x1_Gate_Exec_ArrayVar_Get", ARRAY_GET, at(base + 1), vec![]);
+            world.add_wire_connection(
+                WirePort::new(keys, ARRAY_VAR, "ArrayVarRef"),
+                WirePort::new(get, ARRAY_GET, "ArrayVarRef"),
+            );
+            world.add_wire_connection(landed.slot.clone(), WirePort::new(get, ARRAY_GET, "Index"));
+            world.add_wire_connection(exec, WirePort::new(get, ARRAY_GET, "Exec"));
+            (
+                landed.landed.clone(),
+                Some(WirePort::new(get, ARRAY_GET, "Value")),
+                WirePort::new(get, ARRAY_GET, "ExecOut"),
+            )
+        }
+    };
+    let zero = || Box::new(WireVariant::Int(0)) as Box<dyn AsBrdbValue>;
+
+    // Look ahead: the frame the next colour starts on.
+    let next = gate(chip, "B_1x1_Gate_Exec_ArrayVar_Get", ARRAY_GET, at(3), vec![]);
+    world.add_wire_connection(
+        WirePort::new(starts, ARRAY_VAR, "ArrayVarRef"),
+        WirePort::new(next, ARRAY_GET, "ArrayVarRef"),
+    );
+    world.add_wire_connection(WirePort::new(idx, VAR, "Value"), WirePort::new(next, ARRAY_GET, "Index"));
+    world.add_wire_connection(exec, WirePort::new(next, ARRAY_GET, "Exec"));
+    let reached = gate(chip, "B_1x1_Gate_Expr_CompareGreaterOrEqual", COMPARE_GE, at(4), vec![]);
+    world.add_wire_connection(frame_index.clone(), WirePort::new(reached, COMPARE_GE, "InputA"));
+    world.add_wire_connection(
+        WirePort::new(next, ARRAY_GET, "Value"),
+        WirePort::new(reached, COMPARE_GE, "InputB"),
+    );
+    let reached = WirePort::new(reached, COMPARE_GE, "bOutput");
+
+    // colour index = resync ? key : (reached ? idx + 1 : idx)
+    let ahead = gate(chip, "B_1x1_Gate_Expr_MathAdd", ADD, at(5), vec![(
+        "InputB",
+        Box::new(WireVariant::Int(1)) as Box<dyn AsBrdbValue>,
+    )]);
+    world.add_wire_connection(WirePort::new(idx, VAR, "Value"), WirePort::new(ahead, ADD, "InputA"));
+    let pick = gate(chip, "B_1x1_Gate_Expr_Select", SELECT, at(6), vec![]);
+    world.add_wire_connection(reached.clone(), WirePort::new(pick, SELECT, "bSelectB"));
+    world.add_wire_connection(WirePort::new(idx, VAR, "Value"), WirePort::new(pick, SELECT, "InputA"));
+    world.add_wire_connection(WirePort::new(ahead, ADD, "Output"), WirePort::new(pick, SELECT, "InputB"));
+    let read = gate(chip, "B_1x1_Gate_Expr_Select", SELECT, at(7), match key {
+        None => vec![("InputB", zero())],
+        Some(_) => vec![],
+    });
+    world.add_wire_connection(cond.clone(), WirePort::new(read, SELECT, "bSelectB"));
+    if let Some(key) = &key {
+        world.add_wire_connection(key.clone(), WirePort::new(read, SELECT, "InputB"));
+    }
+    world.add_wire_connection(WirePort::new(pick, SELECT, "Output"), WirePort::new(read, SELECT, "InputA"));
+
+    // The colour, read before either write below can move the index.
+    let get = gate(chip, "B_1x1_Gate_Exec_ArrayVar_Get", ARRAY_GET, at(8), vec![]);
+    world.add_wire_connection(
+        WirePort::new(colors, ARRAY_VAR, "ArrayVarRef"),
+        WirePort::new(get, ARRAY_GET, "ArrayVarRef"),
+    );
+    world.add_wire_connection(WirePort::new(read, SELECT, "Output"), WirePort::new(get, ARRAY_GET, "Index"));
+    world.add_wire_connection(
+        WirePort::new(next, ARRAY_GET, "ExecOut"),
+        WirePort::new(get, ARRAY_GET, "Exec"),
+    );
+    world.add_wire_connection(WirePort::new(get, ARRAY_GET, "Value"), color);
+
+    // Resync after a backward jump, otherwise advance once the next change is
+    // due.
+    let br_reset = gate(chip, "B_1x1_Gate_Exec_Branch", BRANCH, at(9), vec![]);
+    world.add_wire_connection(cond, WirePort::new(br_reset, BRANCH, "bCond"));
+    world.add_wire_connection(WirePort::new(get, ARRAY_GET, "ExecOut"), WirePort::new(br_reset, BRANCH, "Exec"));
+    let set = gate(chip, "B_1x1_Gate_Exec_Var_Set", VAR_SET, at(10), match key {
+        None => vec![("Value", zero())],
+        Some(_) => vec![],
+    });
+    world.add_wire_connection(WirePort::new(idx, VAR, "VarRef"), WirePort::new(set, VAR_SET, "VarRef"));
+    if let Some(key) = key {
+        world.add_wire_connection(key, WirePort::new(set, VAR_SET, "Value"));
+    }
+    world.add_wire_connection(WirePort::new(br_reset, BRANCH, "ExecOutA"), WirePort::new(set, VAR_SET, "Exec"));
+    let br_reached = gate(chip, "B_1x1_Gate_Exec_Branch", BRANCH, at(11), vec![]);
+    world.add_wire_connection(reached, WirePort::new(br_reached, BRANCH, "bCond"));
+    world.add_wire_connection(
+        WirePort::new(br_reset, BRANCH, "ExecOutB"),
+        WirePort::new(br_reached, BRANCH, "Exec"),
+    );
+    let inc = gate(chip, "B_1x1_Gate_Exec_Var_Increment", VAR_INCREMENT, at(12), vec![(
+        "Value",
+        Box::new(WireVariant::Int(1)) as Box<dyn AsBrdbValue>,
+    )]);
+    world.add_wire_connection(WirePort::new(idx, VAR, "VarRef"), WirePort::new(inc, VAR_INCREMENT, "VarRef"));
+    world.add_wire_connection(
+        WirePort::new(br_reached, BRANCH, "ExecOutA"),
+        WirePort::new(inc, VAR_INCREMENT, "Exec"),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::anim::clock::Playback;
+    use crate::anim::color_pack::LinearColor;
+    use crate::video::Clip;
+
+    /// A clip whose left half never changes and whose right half flips every
+    /// `period` frames.
+    fn half_moving_clip(frames: usize, period: usize) -> Clip {
+        Clip {
+            width: 4,
+            height: 2,
+            fps: 10.0,
+            frames: (0..frames)
+                .map(|f| {
+                    image::RgbaImage::from_fn(4, 2, |x, _| {
+                        let v = if x < 2 { 90 } else { ((f / period) % 2 * 200) as u8 };
+                        image::Rgba([v, v, v, 255])
+                    })
+                })
+                .collect(),
+        }
+    }
+
+    /// The graph is the documented thirteen gates per pixel -- the constant the
+    /// cost estimate multiplies by is counted off a real render, not assumed.
+    #[test]
+    fn every_pixel_gets_the_documented_playhead() {
+        let clip = half_moving_clip(6, 2);
+        let opts = AnimOptions { control_buttons: false, ..AnimOptions::default() };
+        let world = build_delta_world(&clip, &opts, &mut crate::progress::NoProgress).unwrap();
+        // 8 pixels, 6 clock + detector + BufferTicks + CompareLess, 7 pins.
+        assert_eq!(world.grids[0].1.len(), 8 * GATES_PER_PIXEL + 9 + 7);
+    }
+
+    /// Pixel (2, 0) of a half-moving clip: three colour runs over six frames.
+    fn moving_track() -> PixelTrack {
+        let clip = half_moving_clip(6, 2);
+        let mut packer = DeltaPacker::new(4, 2, 0);
+        for frame in &clip.frames {
+            packer.push_frame(frame).unwrap();
+        }
+        packer.finish().0.swap_remove(2)
+    }
+
+    fn exact(track: &PixelTrack, frames: &[usize]) -> Vec<LinearColor> {
+        frames.iter().map(|&f| track.color_at(f)).collect()
+    }
+
+    /// First to last, wrapped three times: every backward step is onto frame
+    /// 0, which is the one place the reset is exact.
+    #[test]
+    fn forward_playback_shows_every_frame_exactly() {
+        let track = moving_track();
+        let forward = Playback::default();
+        let frames: Vec<usize> = (0..18).map(|t| forward.frame_at(t, 10.0, 6)).collect();
+        assert_eq!(track.play(frames.iter().copied()), exact(&track, &frames));
+    }
+
+    /// A ping-pong's way back steps 5 -> 4 -> 3, never onto frame 0: a
+    /// playhead reading the index would reset on each step and show frame 0's
+    /// colour, which frame 3 does not have. Why it reads the phase instead.
+    #[test]
+    fn a_reverse_step_shows_frame_0s_colour() {
+        let track = moving_track();
+        let ping_pong = Playback { order: PlayOrder::PingPong, plays: None };
+        let frames: Vec<usize> = (0..10).map(|t| ping_pong.frame_at(t, 10.0, 6)).collect();
+        assert_eq!(frames, [0, 1, 2, 3, 4, 5, 4, 3, 2, 1]);
+        let (got, want) = (track.play(frames.iter().copied()), exact(&track, &frames));
+        assert_eq!(got[..6], want[..6], "the way out is exact");
+        assert_eq!(got[7], got[0], "frame 3 on the way back shows frame 0's colour");
+        assert_ne!(got[7], want[7]);
+    }
+
+    /// The same ping-pong, three times round, read off the phase over the
+    /// track laid out there and back: its only backward step is the wrap.
+    #[test]
+    fn ping_pong_plays_every_frame_exactly_along_its_phase() {
+        let track = moving_track();
+        let ping_pong = Playback { order: PlayOrder::PingPong, plays: None };
+        let pass = ping_pong.unfold(10.0, 6);
+        assert_eq!(pass, [0, 1, 2, 3, 4, 5, 4, 3, 2, 1]);
+        let along = track.along(&pass);
+        let phases = (0..30).map(|t| t % pass.len());
+        let frames: Vec<usize> = (0..30).map(|t| ping_pong.frame_at(t, 10.0, 6)).collect();
+        assert_eq!(along.play(phases), exact(&track, &frames));
+    }
+
+    /// A loop range wraps from its end back to its start, which the plain
+    /// reset gets wrong and the range's landing gets right -- and a restart
+    /// back to frame 0 mid-loop is a landing too.
+    #[test]
+    fn a_loop_range_plays_every_frame_exactly_off_its_landings() {
+        let track = moving_track();
+        let range = Playback { order: PlayOrder::Range { start_s: 0.3, end_s: 0.6 }, plays: None };
+        let mut frames: Vec<usize> = (0..12).map(|t| range.frame_at(t, 10.0, 6)).collect();
+        assert_eq!(frames[..9], [0, 1, 2, 3, 4, 5, 3, 4, 5]);
+        frames.extend((0..6).map(|t| range.frame_at(t, 10.0, 6)));
+        let landings = Landings::of(&range, 10.0, 6);
+        let want = exact(&track, &frames);
+        assert_eq!(track.play_landing(frames.iter().copied(), &landings), want);
+        assert_ne!(track.play(frames.iter().copied()), want, "the plain reset goes to frame 0");
+    }
+
+    /// Each order's real render is the gate count the module doc and the
+    /// cost estimate give it. An external pin has no order of its own, so it
+    /// builds the plain playhead whatever the options say.
+    #[test]
+    fn every_order_builds_its_documented_gates() {
+        let clip = half_moving_clip(6, 2);
+        let count = |playback: Playback, external_clock: bool| {
+            let opts = AnimOptions {
+                playback,
+                external_clock,
+                control_buttons: false,
+                ..AnimOptions::default()
+            };
+            let world = build_delta_world(&clip, &opts, &mut crate::progress::NoProgress)
+                .expect("built");
+            world.grids[0].1.len()
+        };
+        let ping_pong = Playback { order: PlayOrder::PingPong, plays: None };
+        let range = Playback { order: PlayOrder::Range { start_s: 0.2, end_s: 0.4 }, plays: None };
+        // Two more clock gates; the playheads as they were.
+        assert_eq!(count(ping_pong, false), 8 * GATES_PER_PIXEL + 9 + 2 + 7);
+        // Four more clock gates, the landing chain in place of the reset
+        // pair, and each pixel's keys.
+        let (chain, _) = Landings::of(&range, 10.0, 6).graph_cost();
+        assert_eq!(
+            count(range, false),
+            8 * (GATES_PER_PIXEL + LANDING_GATES_PER_PIXEL) + 7 + chain + 4 + 7
+        );
+        // A pin, and no clock.
+        for playback in [ping_pong, range] {
+            assert_eq!(count(playback, true), 8 * GATES_PER_PIXEL + 3 + 1);
+        }
+    }
+
+    /// A seek forward from frame 1 to frame 4 passes two of the pixel's
+    /// changes at once; the playhead takes one, and shows the colour frame 4
+    /// does not have.
+    #[test]
+    fn a_forward_jump_leaves_the_playhead_behind() {
+        let track = moving_track();
+        let frames = [0, 1, 4, 5];
+        let (got, want) = (track.play(frames), exact(&track, &frames));
+        assert_eq!(got[..2], want[..2]);
+        assert_ne!(got[2], want[2], "one change taken of the two passed");
+    }
+
+    /// Seek buttons jumping forward onto a mark, back onto a chapter and
+    /// back to the start: every jump lands on a landing, where the playhead
+    /// takes its key, so every frame is exact -- and the same jumps without
+    /// the seek landings are not.
+    #[test]
+    fn every_seek_lands_the_playhead_where_the_frame_is() {
+        let track = moving_track();
+        let seek = seek::SeekControls { skip_s: 0.2, chapters: vec![0.3], bar: 2, playlist: None };
+        let plan = seek.plan(10.0, 6, 0.0).on_marks();
+        let landings = Landings::of(&Playback::default(), 10.0, 6).with_seeks(&plan, 6);
+        let frames = [0, 1, 4, 5, 3, 4, 5, 0, 1, 2, 3];
+        assert!([4, 3, 0].iter().all(|&f| landings.contains(f)), "each jump's target");
+        let want = exact(&track, &frames);
+        assert_eq!(track.play_landing(frames, &landings), want);
+        assert_ne!(track.play(frames), want, "the plain reset loses the jumps");
+    }
+
+    #[test]
+    fn a_pixel_busier_than_one_array_is_refused_by_name() {
+        let clip = half_moving_clip(6, 1);
+        let opts = AnimOptions { bank_size: 4, ..AnimOptions::default() };
+        let err = build_delta_world(&clip, &opts, &mut crate::progress::NoProgress)
+            .err()
+            .expect("six changes cannot fit a four-element array");
+        assert!(err.contains("(2, 0)") && err.contains("6 times"), "{err}");
+        assert!(err.contains("--anim-encoding"), "names the way out: {err}");
+
+        // The still half alone would have fitted, and a slower flip fits too.
+        let ok = half_moving_clip(6, 2);
+        build_delta_world(&ok, &opts, &mut crate::progress::NoProgress).expect("three changes fit");
+    }
+}
@@ -0,0 +1,297 @@
+//! Change-only packing: turns a stream of frames into the per-pixel change
+//! lists a Brickadia microchip reads in delta mode.
+//!
+//! The third sibling of [`super::pack`] and [`super::color_pack`], and
+//! pixel-major like the latter -- but where a colour array holds a pixel's
+//! colour for EVERY frame, a [`PixelTrack`] holds only the frames where that
+//! colour changes. A pixel that never changes is one colour; a static
+//! background behind a moving subject costs almost nothing.
+//!
+//! Colours are converted exactly as [`super::color_pack`] converts them (the
+//! same sRGB -> linear table, the same [`CULLED`] for a pixel below the alpha
+//! threshold), so "changed" means the linear colour the display brick would
+//! be told differs -- two sRGB bytes that map to the same `f32` are not a
+//! change.
+//!
+//! ## Memory
+//!
+//! 24 bytes per stored change (a 16-byte colour plus its 8-byte start
+//! frame), and nothing for a frame in which the pixel held still. The worst
+//! case -- every pixel changing every frame -- is 1.5x
+//! [`super::color_pack`]'s 16 bytes per pixel per frame; any clip with a
+//! static region comes in under it, usually by a long way.
+use super::color_pack::{CULLED, LinearColor};
+use super::landing::Landings;
+use image::RgbaImage;
+
+/// One pixel's whole clip, as change events.
+///
+/// `colors[i]` is shown from frame `starts[i - 1]` (frame 0 for `i == 0`) up
+/// to, but not including, frame `starts[i]`. The two vectors are always the
+/// same length: the last start is a sentinel equal to the clip's frame count,
+/// which the in-game frame index never reaches, so the playhead's look-ahead
+/// read is always in bounds and never needs an out-of-bounds branch. See
+/// [`super::delta_bricks`].
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PixelTrack {
+    /// The frame each following colour starts on, then the sentinel.
+    pub starts: Vec<i64>,
+    /// Every distinct run's colour, in order.
+    pub colors: Vec<LinearColor>,
+}
+
+impl PixelTrack {
+    /// The colour this track shows on `frame`. What the in-game playhead
+    /// produces when stepped one frame at a time; used by tests and by
+    /// anything that wants to check a track against its source.
+    pub fn color_at(&self, frame: usize) -> LinearColor {
+        self.colors.get(self.run_at(frame)).copied().unwrap_or(CULLED)
+    }
+
+    /// The index into [`Self::colors`] of the run `frame` falls in: where the
+    /// playhead stands on that frame, once it has kept up.
+    pub fn run_at(&self, frame: usize) -> usize {
+        self.starts.partition_point(|&s| s <= frame as i64)
+    }
+
+    /// This track re-packed along `frames`: the colour `frames[i]` shows,
+    /// on frame `i`. How a ping-pong's pass is laid out there and back
+    /// ([`super::clock::Playback::unfold`]), so its playhead only ever steps
+    /// forward.
+    pub fn along(&self, frames: &[usize]) -> PixelTrack {
+        let mut track = PixelTrack::default();
+        for (at, &frame) in frames.iter().enumerate() {
+            let color = self.color_at(frame);
+            if track.colors.last() != Some(&color) {
+                if !track.colors.is_empty() {
+                    track.starts.push(at as i64);
+                }
+                track.colors.push(color);
+            }
+        }
+        if !track.colors.is_empty() {
+            track.starts.push(frames.len() as i64);
+        }
+        track
+    }
+
+    /// The colours the in-game playhead shows over a sequence of frame
+    /// indices, whatever order they come in: [`super::delta_bricks`]' step,
+    /// gate for gate. `prev` starts at 0 as the `BufferTicks` does. Agrees
+    /// with [`Self::color_at`] wherever the frames only step forward by one
+    /// or back to frame 0.
+    pub fn play(&self, frames: impl IntoIterator<Item = usize>) -> Vec<LinearColor> {
+        let (mut idx, mut prev) = (0usize, 0usize);
+        let at = |i: usize| self.colors.get(i).copied().unwrap_or(CULLED);
+        frames
+            .into_iter()
+            .map(|frame| {
+                let decreased = frame < prev;
+                let reached = frame as i64 >= self.starts[idx];
+                let colour = at(if decreased { 0 } else if reached { idx + 1 } else { idx });
+                if decreased {
+                    idx = 0;
+                } else if reached {
+                    idx += 1;
+                }
+                prev = frame;
+                colour
+            })
+            .collect()
+    }
+
+    /// [`Self::play`] for a playhead built with `landings` past frame 0: on a
+    /// landing it jumps to that frame's run instead of stepping, and it never
+    /// resets. Agrees with [`Self::color_at`] wherever the frames only step
+    /// forward by one or back onto a landing.
+    pub fn play_landing(
+        &self,
+        frames: impl IntoIterator<Item = usize>,
+        landings: &Landings,
+    ) -> Vec<LinearColor> {
+        let mut idx = 0usize;
+        let at = |i: usize| self.colors.get(i).copied().unwrap_or(CULLED);
+        frames
+            .into_iter()
+            .map(|frame| {
+                if landings.contains(frame) {
+                    idx = self.run_at(frame);
+                    return at(idx);
+                }
+                let reached = frame as i64 >= self.starts[idx];
+                let colour = at(if reached { idx + 1 } else { idx });
+                if reached {
+                    idx += 1;
+                }
+                colour
+            })
+            .collect()
+    }
+}
+
+/// Builds the per-pixel change lists and the per-pixel visibility bitmap in
+/// ONE traversal of the frames, retaining no frame -- the same contract
+/// [`super::color_pack::ColorPacker`] holds, so the delta renderer shares the
+/// other two's single streaming pull loop.
+///
+/// Pixels are indexed row-major, the same order every other packer uses.
+pub struct DeltaPacker {
+    width: usize,
+    height: usize,
+    alpha_threshold: u8,
+    tracks: Vec<PixelTrack>,
+    visible: Vec<bool>,
+    frames_pushed: usize,
+}
+
+impl DeltaPacker {
+    /// No frame-count hint, unlike [`super::color_pack::ColorPacker::new`]:
+    /// how many changes a pixel will store is the thing nobody knows ahead of
+    /// decode, so there is nothing to reserve.
+    pub fn new(width: u32, height: u32, alpha_threshold: u8) -> Self {
+        let total_pixels = width as usize * height as usize;
+        Self {
+            width: width as usize,
+            height: height as usize,
+            alpha_threshold,
+            tracks: vec![PixelTrack::default(); total_pixels],
+            visible: vec![false; total_pixels],
+            frames_pushed: 0,
+        }
+    }
+
+    /// Push one frame, recording a change for every pixel whose colour differs
+    /// from the one it showed last frame.
+    ///
+    /// Same frame-limit and dimension checks as
+    /// [`super::color_pack::ColorPacker::push_frame`], with the same messages.
+    pub fn push_frame(&mut self, frame: &RgbaImage) -> Result<(), String> {
+        if self.frames_pushed >= super::pack::MAX_FRAMES {
+            return Err(format!(
+                "clip exceeds the {}-frame limit ({} banks of {})",
+                super::pack::MAX_FRAMES,
+                super::pack::MAX_BANKS,
+                super::pack::BANK_FRAMES
+            ));
+        }
+        let (frame_w, frame_h) = (frame.width() as usize, frame.height() as usize);
+        if frame_w != self.width || frame_h != self.height {
+            return Err(format!(
+                "frame {} is {frame_w}x{frame_h}, but the source's SourceInfo reported \
+                 {}x{} -- every frame a FrameStream emits must match info()'s dimensions",
+                self.frames_pushed, self.width, self.height
+            ));
+        }
+        let lut = crate::util::srgb_to_linear_f32_table();
+        let at = self.frames_pushed as i64;
+        for ((track, vis), p) in
+            self.tracks.iter_mut().zip(self.visible.iter_mut()).zip(frame.as_raw().chunks_exact(4))
+        {
+            let color = if p[3] < self.alpha_threshold {
+                CULLED
+            } else {
+                *vis = true;
+                // Identical to `color_pack::push_pixels`: alpha is a plain
+                // rescale, never transferred.
+                (lut[p[0] as usize], lut[p[1] as usize], lut[p[2] as usize], p[3] as f32 / 255.0)
+            };
+            if track.colors.last() != Some(&color) {
+                if !track.colors.is_empty() {
+                    track.starts.push(at);
+                }
+                track.colors.push(color);
+            }
+        }
+        self.frames_pushed += 1;
+        Ok(())
+    }
+
+    /// The per-pixel tracks (row-major, each closed with its sentinel start),
+    /// plus row-major per-pixel visibility.
+    pub fn finish(self) -> (Vec<PixelTrack>, Vec<bool>) {
+        let end = self.frames_pushed as i64;
+        let tracks = self
+            .tracks
+            .into_iter()
+            .map(|mut t| {
+                if !t.colors.is_empty() {
+                    t.starts.push(end);
+                }
+                t
+            })
+            .collect();
+        (tracks, self.visible)
+    }
+}
+
+/// The number of colours a set of tracks stores -- the figure to hold against
+/// [`super::color_bricks::array_elements`]'s one per pixel per frame.
+pub fn stored_changes(tracks: &[PixelTrack]) -> usize {
+    tracks.iter().map(|t| t.colors.len()).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgba;
+
+    fn frame(w: u32, h: u32, f: impl Fn(u32, u32) -> [u8; 4]) -> RgbaImage {
+        RgbaImage::from_fn(w, h, |x, y| Rgba(f(x, y)))
+    }
+
+    /// A still pixel stores one colour however long the clip; a moving one
+    /// stores a change per frame it moved, and either replays to exactly the
+    /// colour the full colour array would have held.
+    #[test]
+    fn only_changes_are_stored_and_every_frame_replays() {
+        let frames: Vec<RgbaImage> = (0..9u32)
+            .map(|f| {
+                frame(2, 1, |x, _| {
+                    if x == 0 { [40, 80, 120, 255] } else { [(f / 3 * 50) as u8, 0, 0, 255] }
+                })
+            })
+            .collect();
+        let mut p = DeltaPacker::new(2, 1, 128);
+        let mut full = super::super::color_pack::ColorPacker::new(2, 1, 128, None);
+        for f in &frames {
+            p.push_frame(f).unwrap();
+            full.push_frame(f).unwrap();
+        }
+        let (tracks, _) = p.finish();
+        let (colors, _) = full.finish();
+
+        assert_eq!(tracks[0].colors.len(), 1, "a still pixel is one colour");
+        assert_eq!(tracks[0].starts, vec![9], "and only the sentinel");
+        assert_eq!(tracks[1].colors.len(), 3, "three runs of three frames");
+        assert_eq!(tracks[1].starts, vec![3, 6, 9]);
+        assert_eq!(stored_changes(&tracks), 4);
+        for (i, track) in tracks.iter().enumerate() {
+            assert_eq!(track.starts.len(), track.colors.len(), "pixel {i}: one start per colour");
+            for (f, want) in colors[i].iter().enumerate() {
+                assert_eq!(track.color_at(f), *want, "pixel {i} frame {f}");
+            }
+        }
+    }
+
+    /// A pixel dropping below the alpha threshold is a change to `CULLED`, and
+    /// coming back is another -- visibility is still "opaque in any frame".
+    #[test]
+    fn culling_is_a_change_like_any_other() {
+        let alphas = [255u8, 0, 0, 255];
+        let mut p = DeltaPacker::new(1, 1, 128);
+        for a in alphas {
+            p.push_frame(&frame(1, 1, |_, _| [255, 255, 255, a])).unwrap();
+        }
+        let (tracks, visible) = p.finish();
+        assert!(visible[0]);
+        assert_eq!(tracks[0].colors, vec![(1.0, 1.0, 1.0, 1.0), CULLED, (1.0, 1.0, 1.0, 1.0)]);
+        assert_eq!(tracks[0].starts, vec![1, 3, 4]);
+    }
+
+    #[test]
+    fn a_frame_of_the_wrong_size_is_a_descriptive_error() {
+        let mut p = DeltaPacker::new(3, 2, 128);
+        let err = p.push_frame(&frame(2, 2, |_, _| [0, 0, 0, 255])).unwrap_err();
+        assert!(err.contains("2x2") && err.contains("3x2"), "{err}");
+    }
+}
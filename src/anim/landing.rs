@@ -0,0 +1,313 @@
+//! Landing points: the frames a clock's index can step BACK onto, for the
+//! playheads that follow it one event at a time.
+//!
+//! A stateful-`Var` playhead -- a delta pixel's ([`super::delta_bricks`]), a
+//! MIDI voice's or percussion lane's (`audio::speakers::build_midi_event_world`)
+//! -- only ever steps forward through its events. A backward step needs the
+//! index put back where the new frame is, and with nothing but frame 0 to go
+//! back to that is a reset to 0 on any decrease. A loop range's wrap goes back
+//! to the range start instead, so such a clock has two landings, and a
+//! playhead stores where it stands at each: its `keys`, one per landing.
+//!
+//! The graph here is shared by every playhead in the chip. Per landing past
+//! frame 0, a `CompareGreaterOrEqual` against it and two `Select`s carry the
+//! last landing at or before the index down a chain -- its slot in `keys`
+//! and its frame -- and one `CompareEqual` at the end says whether the index
+//! is ON it. A playhead reads `keys[slot]` and, when `landed`, takes it as its
+//! index instead of stepping.
+//!
+//! Landing on a point going forward resyncs too, to where the playhead
+//! already is when it kept up. What it cannot do is catch a jump onto
+//! anything but a landing; every order the chip's own clock plays only ever
+//! takes those, and so does every seek button ([`super::seek`]) of a plan
+//! built [`on marks`](super::seek::SeekPlan::on_marks).
+//!
+//! # Marks
+//!
+//! Such a plan's skip buttons land on every `step`th frame, too many to chain
+//! one by one on a long clip. Those marks take the first `keys` slots, one
+//! per mark, and five more shared gates work out which one the index is on:
+//! `index * (1 / step)` truncated by a `BitwiseOR` is the mark at or before
+//! it -- and its slot -- and `index % step == 0` says whether it is on it.
+//! Two `Select`s put that ahead of the chain's pick. The same truncation as
+//! the clock's, with half a frame added first, so a float a hair under a
+//! whole mark never truncates to the one before.
+use super::bricks::{COMPARE_GE, SELECT};
+use super::chip::Chip;
+use super::clock::{ADD, BITWISE_OR, MODULO, MULTIPLY, Playback, gate};
+use super::seek::SeekPlan;
+use brdb::{AsBrdbValue, Position, WirePort, World, schema::WireVariant};
+
+pub const COMPARE_EQ: &str = "BrickComponentType_WireGraph_Expr_CompareEqual";
+
+/// The frames a clock's index can land on: frame 0, the rest ascending
+/// after it, and every mark.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Landings {
+    frames: Vec<usize>,
+    marks: Option<Marks>,
+}
+
+/// Every `step`th frame, `count` of them from frame 0. See the module doc.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Marks {
+    step: usize,
+    count: usize,
+}
+
+impl Landings {
+    /// Where `playback` steps back to on a `frame_count`-frame clip at
+    /// `fps`: frame 0, on a restart or a wrap, and a loop range's start.
+    pub fn of(playback: &Playback, fps: f32, frame_count: usize) -> Landings {
+        let mut frames = vec![0];
+        frames.extend(playback.loop_start(fps, frame_count));
+        Landings { frames, marks: None }
+    }
+
+    /// These landings and every frame `plan`'s buttons jump to on a
+    /// `frame_count`-frame clip. Two buttons landing on one frame keep a
+    /// slot each, so the cost is the same wherever they land.
+    pub fn with_seeks(mut self, plan: &SeekPlan, frame_count: usize) -> Landings {
+        self.frames.extend(plan.landings());
+        self.frames.sort_unstable();
+        self.marks = plan
+            .mark_step()
+            .map(|step| Marks { step, count: frame_count.div_ceil(step).max(1) });
+        self
+    }
+
+    /// Frame 0 only: a playhead resets on any backward step and needs no
+    /// graph from here.
+    pub fn is_plain(&self) -> bool {
+        self.frames.len() == 1 && self.marks.is_none()
+    }
+
+    /// Whether `frame` is a landing, i.e. whether the graph's `landed` is true
+    /// on it.
+    pub fn contains(&self, frame: usize) -> bool {
+        self.slot_of(frame).is_some()
+    }
+
+    /// The slot the graph reads `keys` at on `frame`, when it is a landing:
+    /// its mark's, or else the last of its chained slots.
+    pub fn slot_of(&self, frame: usize) -> Option<usize> {
+        if let Some(m) = self.marks
+            && frame.is_multiple_of(m.step)
+        {
+            return Some(frame / m.step);
+        }
+        let at = self.frames.partition_point(|&f| f <= frame) - 1;
+        (self.frames[at] == frame).then(|| match at {
+            0 => 0,
+            at => self.chain_base() + at - 1,
+        })
+    }
+
+    /// One playhead's `keys`: `index_at` of every landing, in slot order --
+    /// the marks (or frame 0 alone), then the chained landings past it.
+    pub fn keys(&self, index_at: impl Fn(usize) -> i64) -> Vec<i64> {
+        let marks = self.marks.map_or(vec![0], |m| (0..m.count).map(|k| k * m.step).collect());
+        marks.into_iter().chain(self.frames[1..].iter().copied()).map(index_at).collect()
+    }
+
+    /// What [`add_landing_graph`] builds, in `(gates, wires)`: nothing for a
+    /// plain set.
+    pub fn graph_cost(&self) -> (usize, usize) {
+        Self::cost(self.frames.len() - 1, self.marks.is_some())
+    }
+
+    /// [`Self::graph_cost`] of a set with `past` chained landings after
+    /// frame 0, and marks or not.
+    pub fn cost(past: usize, marks: bool) -> (usize, usize) {
+        let chain = match past {
+            0 => (0, 0),
+            past => (3 * past + 1, 5 * past),
+        };
+        match (marks, past) {
+            (false, _) => chain,
+            (true, 0) => (5, 5),
+            (true, _) => (chain.0 + 7, chain.1 + 11),
+        }
+    }
+
+    /// The first chained slot past frame 0: after the marks, or after frame
+    /// 0's own slot.
+    fn chain_base(&self) -> usize {
+        self.marks.map_or(1, |m| m.count)
+    }
+}
+
+/// The shared signals every playhead reads. See the module doc.
+pub struct Landed {
+    /// Source port carrying the slot, in `keys`, of the last landing at or
+    /// before the index.
+    pub slot: WirePort,
+    /// Source port carrying whether the index is on that landing.
+    pub landed: WirePort,
+}
+
+/// Build the landing graph for `index` (see the module doc), one gate per
+/// `at` column. Call only with a set that is not [`Landings::is_plain`].
+pub fn add_landing_graph(
+    world: &mut World,
+    chip: &mut Chip,
+    index: &WirePort,
+    landings: &Landings,
+    at: impl Fn(i32) -> Position,
+) -> Landed {
+    let int = |v: usize| Box::new(WireVariant::Int(v as i64)) as Box<dyn AsBrdbValue>;
+    let mut col = 0;
+    let mut next = || {
+        col += 1;
+        at(col - 1)
+    };
+    let marks = landings.marks.map(|m| {
+        let mark = add_mark(world, chip, index, m.step, 0.0, &mut next);
+        let rem = gate(chip, "B_1x1_Gate_Expr_MathModuloFloored", MODULO, next(), vec![(
+            "InputB",
+            int(m.step),
+        )]);
+        world.add_wire_connection(index.clone(), WirePort::new(rem, MODULO, "InputA"));
+        let on = gate(chip, "B_1x1_Gate_Expr_CompareEqual", COMPARE_EQ, next(), vec![(
+            "InputB",
+            int(0),
+        )]);
+        world.add_wire_connection(WirePort::new(rem, MODULO, "Output"), WirePort::new(on, COMPARE_EQ, "InputA"));
+        (mark, WirePort::new(on, COMPARE_EQ, "bOutput"))
+    });
+    if landings.frames.len() == 1 {
+        let (slot, landed) = marks.expect("a set that is not plain");
+        return Landed { slot, landed };
+    }
+    let base = landings.chain_base();
+    let (mut slot, mut value) = (None::<WirePort>, None::<WirePort>);
+    for (i, &frame) in landings.frames.iter().enumerate().skip(1) {
+        let ge = gate(chip, "B_1x1_Gate_Expr_CompareGreaterOrEqual", COMPARE_GE, next(), vec![(
+            "InputB",
+            int(frame),
+        )]);
+        world.add_wire_connection(index.clone(), WirePort::new(ge, COMPARE_GE, "InputA"));
+        let ge = WirePort::new(ge, COMPARE_GE, "bOutput");
+        // Past the first landing, the chain carries the previous pick on
+        // `InputA`; the first inlines landing 0's slot and frame there.
+        let mut pick = |prev: &Option<WirePort>, b: usize| {
+            let inline = vec![("InputA", int(0)), ("InputB", int(b))];
+            let sel = gate(chip, "B_1x1_Gate_Expr_Select", SELECT, next(), match prev {
+                None => inline,
+                Some(_) => inline.into_iter().skip(1).collect(),
+            });
+            world.add_wire_connection(ge.clone(), WirePort::new(sel, SELECT, "bSelectB"));
+            if let Some(prev) = prev {
+                world.add_wire_connection(prev.clone(), WirePort::new(sel, SELECT, "InputA"));
+            }
+            Some(WirePort::new(sel, SELECT, "Output"))
+        };
+        slot = pick(&slot, base + i - 1);
+        value = pick(&value, frame);
+    }
+    let (mut slot, mut value) = (slot.expect("a landing past frame 0"), value.expect("and its frame"));
+    // On a mark, its slot; and the index itself as the frame, so the compare
+    // below holds.
+    if let Some((mark, on)) = marks {
+        let mut ahead = |chained: WirePort, marked: WirePort| {
+            let sel = gate(chip, "B_1x1_Gate_Expr_Select", SELECT, next(), vec![]);
+            world.add_wire_connection(on.clone(), WirePort::new(sel, SELECT, "bSelectB"));
+            world.add_wire_connection(chained, WirePort::new(sel, SELECT, "InputA"));
+            world.add_wire_connection(marked, WirePort::new(sel, SELECT, "InputB"));
+            WirePort::new(sel, SELECT, "Output")
+        };
+        slot = ahead(slot, mark);
+        value = ahead(value, index.clone());
+    }
+    let eq = gate(chip, "B_1x1_Gate_Expr_CompareEqual", COMPARE_EQ, next(), vec![]);
+    world.add_wire_connection(index.clone(), WirePort::new(eq, COMPARE_EQ, "InputA"));
+    world.add_wire_connection(value, WirePort::new(eq, COMPARE_EQ, "InputB"));
+    Landed { slot, landed: WirePort::new(eq, COMPARE_EQ, "bOutput") }
+}
+
+/// `x * (1 / step) + bias`, truncated to an integer by a `BitwiseOR`, on the
+/// next three `at` columns: `x / step` rounded down for a `bias` of 0, with
+/// half a frame added so a whole mark never truncates to the one before. A
+/// `bias` of `k / step` adds `k` frames to `x` first. Shared with the seek
+/// graph's skips to a mark.
+pub fn add_mark(
+    world: &mut World,
+    chip: &mut Chip,
+    x: &WirePort,
+    step: usize,
+    bias: f64,
+    mut at: impl FnMut() -> Position,
+) -> WirePort {
+    let number = |v: f64| Box::new(WireVariant::Number(v)) as Box<dyn AsBrdbValue>;
+    let step = step as f64;
+    let scale = gate(chip, "B_1x1_Gate_Expr_MathMultiply", MULTIPLY, at(), vec![(
+        "InputB",
+        number(1.0 / step),
+    )]);
+    world.add_wire_connection(x.clone(), WirePort::new(scale, MULTIPLY, "InputA"));
+    let shift = gate(chip, "B_1x1_Gate_Expr_MathAdd", ADD, at(), vec![(
+        "InputB",
+        number(bias + 0.5 / step),
+    )]);
+    world.add_wire_connection(WirePort::new(scale, MULTIPLY, "Output"), WirePort::new(shift, ADD, "InputA"));
+    // A bare `i64`, as the clock's own truncation takes it.
+    let trunc = gate(chip, "B_1x1_Gate_Expr_BitwiseOR", BITWISE_OR, at(), vec![(
+        "InputB",
+        Box::new(0i64) as Box<dyn AsBrdbValue>,
+    )]);
+    world.add_wire_connection(WirePort::new(shift, ADD, "Output"), WirePort::new(trunc, BITWISE_OR, "InputA"));
+    WirePort::new(trunc, BITWISE_OR, "Output")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::anim::clock::PlayOrder;
+
+    #[test]
+    fn a_loop_range_lands_on_its_start_and_nothing_else_adds_a_landing() {
+        let range = Playback { order: PlayOrder::Range { start_s: 0.5, end_s: 0.8 }, plays: None };
+        let landings = Landings::of(&range, 10.0, 10);
+        assert_eq!(landings.keys(|f| f as i64), [0, 5]);
+        assert!(landings.contains(5) && !landings.contains(6));
+        assert_eq!(landings.graph_cost(), (4, 5));
+
+        let from_zero = Playback { order: PlayOrder::Range { start_s: 0.0, end_s: 0.8 }, plays: None };
+        for playback in [Playback::default(), Playback { order: PlayOrder::PingPong, plays: None }, from_zero] {
+            let landings = Landings::of(&playback, 10.0, 10);
+            assert!(landings.is_plain(), "{playback:?}");
+            assert_eq!(landings.graph_cost(), (0, 0));
+        }
+    }
+
+    /// A skip every 3 frames marks 0, 3, 6 and 9, slots 0 to 3; the chapter
+    /// at 4 and the bar's 0 and 5 chain on after them, a repeat of frame 0
+    /// included. Every landing reads its own key.
+    #[test]
+    fn marks_take_the_first_slots_and_seek_landings_chain_after_them() {
+        use crate::anim::seek::SeekControls;
+        let seek = SeekControls { skip_s: 0.3, chapters: vec![0.4], bar: 2, playlist: None };
+        let plan = seek.plan(10.0, 10, 0.0).on_marks();
+        let landings = Landings::of(&Playback::default(), 10.0, 10).with_seeks(&plan, 10);
+        let keys = landings.keys(|f| f as i64);
+        assert_eq!(keys, [0, 3, 6, 9, 0, 4, 5]);
+        for frame in 0..10 {
+            let slot = landings.slot_of(frame);
+            let landing = [0, 3, 4, 5, 6, 9].contains(&frame);
+            assert_eq!(slot.is_some(), landing, "frame {frame}");
+            if let Some(slot) = slot {
+                assert_eq!(keys[slot], frame as i64, "frame {frame}");
+            }
+        }
+        assert_eq!(landings.graph_cost(), Landings::cost(3, true));
+
+        // The same buttons off marks add no mark slots, and a skip alone adds
+        // nothing to chain.
+        let plain = Landings::of(&Playback::default(), 10.0, 10).with_seeks(&seek.plan(10.0, 10, 0.0), 10);
+        assert_eq!(plain.keys(|f| f as i64), [0, 0, 4, 5]);
+        let skip = SeekControls { skip_s: 0.3, ..SeekControls::default() };
+        let marks = Landings::of(&Playback::default(), 10.0, 10).with_seeks(&skip.plan(10.0, 10, 0.0).on_marks(), 10);
+        assert!(!marks.is_plain());
+        assert_eq!(marks.graph_cost(), (5, 5));
+    }
+}
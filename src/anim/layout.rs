@@ -47,6 +47,10 @@ pub const GATE_HALF: IntVector = IntVector { x: 5, y: 5, z: 2 };
 /// x/y -- so the convention is unobservable for every use here, which is what
 /// makes deriving the extent safe without an in-game measurement.
 /// `a_rotated_gate_is_square_in_the_two_non_direction_axes` pins that.
+///
+/// The one brick that does observe it is a wall's sloped piece:
+/// `opt::stand_upright` follows this convention in its `basis`, so the two agree
+/// (`opt_wall_frames_agree_with_the_derived_extent`).
 pub const fn rotated_half(half: IntVector, direction: Direction, rotation: Rotation) -> IntVector {
     // The two authored extents that are NOT on the brick's own z axis, in the
     // order they fill the two world axes the direction did not claim. A
@@ -667,6 +671,27 @@ mod tests {
         );
     }
 
+    /// `opt::stand_upright` turns sloped pieces by the world axes their own axes
+    /// point along; reading a brick's extent off that frame must give what
+    /// [`rotated_half`] does, for every orientation and a brick whose three
+    /// extents differ.
+    #[test]
+    fn opt_wall_frames_agree_with_the_derived_extent() {
+        use crate::opt::{DIRECTIONS, ROTATIONS, basis};
+        let authored = [3, 7, 2];
+        for dir in DIRECTIONS {
+            for rot in ROTATIONS {
+                let mut world = [0; 3];
+                for (axis, extent) in basis(dir, rot).into_iter().zip(authored) {
+                    let on = [axis.0, axis.1, axis.2];
+                    world[on.iter().position(|&c| c != 0).unwrap()] = extent;
+                }
+                let got = rotated_half(IntVector { x: 3, y: 7, z: 2 }, dir, rot);
+                assert_eq!(world, [got.x, got.y, got.z], "{dir:?}/{rot:?}");
+            }
+        }
+    }
+
     /// [`STAGE_PITCH`] against the REAL depth of the brick it has to clear,
     /// rather than against a remembered 15. An upright gate is
     /// `2 * rotated_half.z` units through; the pitch has to exceed that or two
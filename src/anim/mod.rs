@@ -1,4 +1,5 @@
 pub mod bricks;
+pub mod budget;
 pub mod cascade;
 pub mod chip;
 pub mod clock;
@@ -6,9 +7,17 @@ pub mod color_bricks;
 pub mod color_pack;
 pub mod controls;
 pub mod cost;
+pub mod delta_bricks;
+pub mod delta_pack;
+pub mod landing;
 pub mod layout;
 pub mod pack;
 pub mod palette;
+pub mod palette_bricks;
+pub mod playlist;
+pub mod replay;
+pub mod seek;
+pub mod soundtrack;
 pub mod subtitle_display;
 pub mod text_bricks;
 pub mod text_layout;
@@ -20,9 +29,9 @@ use brdb::World;
 
 /// How a render encodes a pixel's colour for the wire graph.
 ///
-/// The two encodings produce the same *picture* from the same source; they
+/// The encodings produce the same *picture* from the same source; they
 /// differ in what the microchip does per frame to produce it, and the whole
-/// point of having both is to measure that difference in game.
+/// point of having several is to measure that difference in game.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum AnimEncoding {
     /// Frame-major hex strings (see [`bricks`] and [`pack`]). One `ArrayVar`
@@ -44,6 +53,23 @@ pub enum AnimEncoding {
     /// [`color_pack`]) and far more gates at a bank boundary (see
     /// [`color_bricks`]).
     ColorArray,
+    /// Per-pixel change lists (see [`delta_bricks`] and [`delta_pack`]). Each
+    /// pixel stores only the frames its colour changes on, read by a stateful
+    /// `Var` playhead of its own.
+    ///
+    /// Thirteen components per pixel against two, for arrays that shrink with
+    /// the motion in the clip: on mostly static footage a small fraction of
+    /// [`ColorArray`](Self::ColorArray)'s colours, and less host memory to
+    /// build. The wrong choice for footage where everything moves.
+    Delta,
+    /// Frame-major palette indices (see [`palette_bricks`] and [`palette`]).
+    /// Hex mode's chunked strings with one or two characters per pixel
+    /// instead of six, each naming an entry in one shared colour array.
+    ///
+    /// A third to a sixth of [`Hex`](Self::Hex)'s characters for one more
+    /// component per pixel, and the picture snapped to at most `--colors`
+    /// colours (64 by default).
+    Palette,
 }
 
 impl AnimEncoding {
@@ -53,14 +79,26 @@ impl AnimEncoding {
         match s.to_lowercase().as_str() {
             "hex" => Some(Self::Hex),
             "color-array" | "colour-array" | "color" | "colour" => Some(Self::ColorArray),
+            "delta" => Some(Self::Delta),
+            "palette" => Some(Self::Palette),
             _ => None,
         }
     }
 
     /// Every spelling a user may type, for an error message.
-    pub const NAMES: &'static str = "hex, color-array";
+    pub const NAMES: &'static str = "hex, color-array, delta, palette";
+
+    /// The canonical spelling, as `--anim-encoding` takes it.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Hex => "hex",
+            Self::ColorArray => "color-array",
+            Self::Delta => "delta",
+            Self::Palette => "palette",
+        }
+    }
 
-    /// Render `source` with this encoding. The two renderers have identical
+    /// Render `source` with this encoding. The renderers have identical
     /// signatures on purpose, so every caller (CLI, GUI, tests) can pick an
     /// encoding without branching over anything else.
     pub fn build(
@@ -72,13 +110,17 @@ impl AnimEncoding {
         match self {
             Self::Hex => bricks::build_brick_world(source, opts, progress),
             Self::ColorArray => color_bricks::build_color_array_world(source, opts, progress),
+            Self::Delta => delta_bricks::build_delta_world(source, opts, progress),
+            Self::Palette => palette_bricks::build_palette_world(source, opts, progress),
         }
     }
 
-    /// The build-cost estimate for this encoding. The two formulas are
-    /// genuinely different -- colour-array mode has no chunks, no characters,
-    /// and a per-pixel select at every bank boundary -- so this dispatches
-    /// rather than sharing one approximate number between them.
+    /// The build-cost estimate for this encoding. The formulas are genuinely
+    /// different -- colour-array mode has no chunks, no characters, and a
+    /// per-pixel select at every bank boundary; delta mode has no banks per
+    /// pixel at all; palette mode's chunks hold more pixels than hex mode's --
+    /// so this dispatches rather than sharing one approximate number between
+    /// them.
     ///
     /// Takes the whole [`bricks::AnimOptions`] for the same reason
     /// [`AnimMode::estimate`] does: every option that changes the graph --
@@ -95,6 +137,8 @@ impl AnimEncoding {
         match self {
             Self::Hex => cost::estimate(width, height, frames, opts),
             Self::ColorArray => cost::estimate_color_array(width, height, frames, opts),
+            Self::Delta => cost::estimate_delta(width, height, frames, opts),
+            Self::Palette => cost::estimate_palette(width, height, frames, opts),
         }
     }
 }
@@ -105,13 +149,14 @@ impl AnimEncoding {
 /// (`Component_TextDisplay` instead of a display brick per pixel), different
 /// components, and a completely different cost shape (per-band rather than
 /// per-pixel or per-chunk) -- see [`text_bricks`] and [`cost::estimate_text`].
-/// `AnimEncoding` only has a meaningful choice between [`Hex`](AnimEncoding::Hex)
-/// and [`ColorArray`](AnimEncoding::ColorArray) *within* [`Brick`](Self::Brick);
+/// `AnimEncoding` only has a meaningful choice between [`Hex`](AnimEncoding::Hex),
+/// [`ColorArray`](AnimEncoding::ColorArray), [`Delta`](AnimEncoding::Delta)
+/// and [`Palette`](AnimEncoding::Palette) *within* [`Brick`](Self::Brick);
 /// `--anim-encoding` is therefore ignored -- and rejected, if the caller passed
 /// one explicitly -- under `--anim-mode text`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum AnimMode {
-    /// One display brick per pixel, in either [`AnimEncoding`].
+    /// One display brick per pixel, in any [`AnimEncoding`].
     Brick(AnimEncoding),
     /// A stack of animated `Component_TextDisplay` bricks, one per band of
     /// image rows (see [`text_layout::plan_bands`] and [`text_bricks`]).
@@ -153,15 +198,26 @@ impl AnimMode {
     }
 
     /// Render `source` in this mode. Identical signature to
-    /// [`AnimEncoding::build`] and each of the three renderers it and
+    /// [`AnimEncoding::build`] and each of the five renderers it and
     /// [`text_bricks::build_text_world`] wrap, so dispatch never branches on
     /// anything but the mode itself.
+    ///
+    /// Refuses a playback no clock can build ([`clock::Playback::check`]), and
+    /// seek buttons on an order other than first to last: a jump lands on
+    /// its frame by where the index is, which only holds going forward.
     pub fn build(
         &self,
         source: &dyn FrameSource,
         opts: &bricks::AnimOptions,
         progress: &mut dyn Progress,
     ) -> Result<World, String> {
+        opts.playback.check()?;
+        if opts.playback.order != clock::PlayOrder::Forward
+            && !opts.seek.is_empty()
+            && !opts.external_clock
+        {
+            return Err("seek buttons cannot take a ping-pong or loop-range playback".to_string());
+        }
         match self {
             Self::Brick(enc) => enc.build(source, opts, progress),
             Self::Text => text_bricks::build_text_world(source, opts, progress),
@@ -179,7 +235,7 @@ impl AnimMode {
     ///
     /// `Err` only from text mode, and only for a geometry
     /// [`text_layout::plan_bands`] cannot lay out at all (too wide for the
-    /// component character limit). Both brick encodings are total. The error
+    /// component character limit). Every brick encoding is total. The error
     /// is the one [`Self::build`] would fail with, so a caller that shows
     /// this in place of a cost tells the user exactly what pressing Generate
     /// would tell them. See [`cost::estimate_text`].
@@ -213,6 +269,19 @@ mod tests {
         assert_eq!(AnimMode::parse("brick", None).unwrap(), AnimMode::Brick(AnimEncoding::Hex));
     }
 
+    #[test]
+    fn every_encoding_parses_back_from_its_name() {
+        for enc in [
+            AnimEncoding::Hex,
+            AnimEncoding::ColorArray,
+            AnimEncoding::Delta,
+            AnimEncoding::Palette,
+        ] {
+            assert_eq!(AnimEncoding::parse(enc.name()), Some(enc));
+            assert!(AnimEncoding::NAMES.contains(enc.name()), "{}", enc.name());
+        }
+    }
+
     #[test]
     fn text_mode_parses() {
         assert_eq!(AnimMode::parse("text", None).unwrap(), AnimMode::Text);
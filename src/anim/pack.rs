@@ -204,6 +204,9 @@ pub struct Packer {
     culled: String,
     chunks: Vec<Chunk>,
     visible: Vec<bool>,
+    /// Row-major, `true` where the pixel was culled in at least one frame:
+    /// with [`Self::visible`], what [`Self::partly_culled`] reads.
+    hidden: Vec<bool>,
     frames_pushed: usize,
     /// The sRGB -> linear table to run each pixel through before hex-encoding
     /// it, or `None` to encode the source bytes untouched.
@@ -223,6 +226,11 @@ pub struct Packer {
     /// [`crate::util::linear_gamma_table`]); the table's entries are pinned
     /// against the function, so the colours are unchanged.
     linearize: Option<&'static [u8; 256]>,
+    /// The palette to write each pixel's index into instead of its hex, or
+    /// `None` for hex. Set by [`Self::indexed`] for
+    /// [`super::palette_bricks`]; the stride `new` was given must then be
+    /// [`super::palette::index_stride`] of this palette's size.
+    indexed: Option<super::palette::Palette>,
 }
 
 impl Packer {
@@ -248,8 +256,10 @@ impl Packer {
             culled: "0".repeat(stride),
             chunks,
             visible: vec![false; total_pixels],
+            hidden: vec![false; total_pixels],
             frames_pushed: 0,
             linearize: None,
+            indexed: None,
         }
     }
 
@@ -262,6 +272,15 @@ impl Packer {
         self
     }
 
+    /// Write each pixel as its nearest entry's code in `palette` (see
+    /// [`super::palette::push_index_code`]) rather than as hex. The index is
+    /// taken from the source bytes; [`Self::linearize`] has no effect, since
+    /// the palette's own entries are what get converted.
+    pub fn indexed(mut self, palette: super::palette::Palette) -> Self {
+        self.indexed = Some(palette);
+        self
+    }
+
     /// Push one frame's contribution to every chunk's per-frame string.
     ///
     /// `frame` must be exactly `width x height` (the dimensions `new` was
@@ -303,9 +322,12 @@ impl Packer {
         );
 
         {
-            let Self { chunks, visible, per_chunk, alpha_threshold, culled, linearize, .. } = self;
+            let Self {
+                chunks, visible, hidden, per_chunk, alpha_threshold, culled, linearize, indexed, ..
+            } = self;
             let (alpha_threshold, linearize, culled) = (*alpha_threshold, *linearize, &**culled);
-            encode_frame(chunks, visible, *per_chunk, |chunk, vis| {
+            let indexed = indexed.as_ref();
+            encode_frame(chunks, visible, hidden, *per_chunk, |chunk, vis, hid| {
                 debug_assert_eq!(
                     vis.len(),
                     chunk.pixel_count,
@@ -313,7 +335,14 @@ impl Packer {
                 );
                 let start = chunk.first_pixel * 4;
                 let end = start + chunk.pixel_count * 4;
-                let s = encode_chunk(&raw[start..end], culled, alpha_threshold, linearize, vis);
+                let s = match indexed {
+                    Some(palette) => {
+                        index_chunk(&raw[start..end], culled, alpha_threshold, palette, vis, hid)
+                    }
+                    None => {
+                        encode_chunk(&raw[start..end], culled, alpha_threshold, linearize, vis, hid)
+                    }
+                };
                 chunk.frames.push(s);
             });
         }
@@ -323,6 +352,13 @@ impl Packer {
 
     /// The chunks, plus row-major per-pixel visibility (`true` where the
     /// pixel was opaque enough in at least one frame).
+    /// Whether some pixel was culled in one frame and kept in another: one
+    /// with a brick, whose culled slot therefore shows. Read before
+    /// [`Self::finish`].
+    pub fn partly_culled(&self) -> bool {
+        self.visible.iter().zip(&self.hidden).any(|(&v, &h)| v && h)
+    }
+
     pub fn finish(self) -> (Vec<Chunk>, Vec<bool>) {
         (self.chunks, self.visible)
     }
@@ -336,17 +372,20 @@ impl Packer {
 /// bitmap -- both indexed by pixel-within-chunk, so this function never needs
 /// the screen width, a division, or a global pixel index. `vis` is only ever
 /// set to `true` (a pixel visible in an earlier frame stays visible), which
-/// is what lets the same slice accumulate across every frame.
+/// is what lets the same slice accumulate across every frame; `hid`, the
+/// same slice of the culled-somewhere bitmap, accumulates the same way.
 fn encode_chunk(
     px: &[u8],
     culled: &str,
     alpha_threshold: u8,
     linearize: Option<&[u8; 256]>,
     vis: &mut [bool],
+    hid: &mut [bool],
 ) -> String {
     let mut s = String::with_capacity(vis.len() * culled.len());
     for (local, p) in px.chunks_exact(4).enumerate() {
         if p[3] < alpha_threshold {
+            hid[local] = true;
             s.push_str(culled);
         } else {
             vis[local] = true;
@@ -363,6 +402,30 @@ fn encode_chunk(
     s
 }
 
+/// [`encode_chunk`]'s indexed twin: each kept pixel is its nearest palette
+/// entry's `culled.len()`-character code.
+fn index_chunk(
+    px: &[u8],
+    culled: &str,
+    alpha_threshold: u8,
+    palette: &super::palette::Palette,
+    vis: &mut [bool],
+    hid: &mut [bool],
+) -> String {
+    let mut s = String::with_capacity(vis.len() * culled.len());
+    for (local, p) in px.chunks_exact(4).enumerate() {
+        if p[3] < alpha_threshold {
+            hid[local] = true;
+            s.push_str(culled);
+        } else {
+            vis[local] = true;
+            let index = palette.nearest_index([p[0], p[1], p[2]]);
+            super::palette::push_index_code(&mut s, index, culled.len());
+        }
+    }
+    s
+}
+
 /// Fewest chunks rayon may put in one job.
 ///
 /// Measured, not guessed: without it, `par_iter_mut` splits down to a single
@@ -379,15 +442,16 @@ fn encode_chunk(
 #[cfg(not(target_arch = "wasm32"))]
 const MIN_CHUNKS_PER_JOB: usize = 8;
 
-/// Run `encode` over every chunk paired with its own slice of the visibility
-/// bitmap.
+/// Run `encode` over every chunk paired with its own slices of the visibility
+/// and culled-somewhere bitmaps.
 ///
 /// Native builds fan this out across rayon's pool. There is no shared mutable
 /// state to race on: each chunk owns its `String`, and `chunks_mut(per_chunk)`
 /// splits `visible` on exactly the seams `Packer::new` cut the chunks on, so
 /// each closure invocation writes only its own disjoint slice. That is a
 /// split borrow the compiler checks, not a lock and not an atomic -- there is
-/// no way for two jobs to reach the same `visible[i]`.
+/// no way for two jobs to reach the same `visible[i]`. `hidden` is split the
+/// same way.
 ///
 /// `rayon` is a native-only dependency (see `Cargo.toml`'s target blocks --
 /// it needs threads, which `wasm32-unknown-unknown` does not have without a
@@ -398,15 +462,17 @@ const MIN_CHUNKS_PER_JOB: usize = 8;
 fn encode_frame(
     chunks: &mut [Chunk],
     visible: &mut [bool],
+    hidden: &mut [bool],
     per_chunk: usize,
-    encode: impl Fn(&mut Chunk, &mut [bool]) + Send + Sync,
+    encode: impl Fn(&mut Chunk, &mut [bool], &mut [bool]) + Send + Sync,
 ) {
     use rayon::prelude::*;
     chunks
         .par_iter_mut()
         .zip(visible.par_chunks_mut(per_chunk))
+        .zip(hidden.par_chunks_mut(per_chunk))
         .with_min_len(MIN_CHUNKS_PER_JOB)
-        .for_each(|(chunk, vis)| encode(chunk, vis));
+        .for_each(|((chunk, vis), hid)| encode(chunk, vis, hid));
 }
 
 /// See the native sibling above.
@@ -414,10 +480,13 @@ fn encode_frame(
 fn encode_frame(
     chunks: &mut [Chunk],
     visible: &mut [bool],
+    hidden: &mut [bool],
     per_chunk: usize,
-    encode: impl Fn(&mut Chunk, &mut [bool]) + Send + Sync,
+    encode: impl Fn(&mut Chunk, &mut [bool], &mut [bool]) + Send + Sync,
 ) {
-    for (chunk, vis) in chunks.iter_mut().zip(visible.chunks_mut(per_chunk)) {
-        encode(chunk, vis);
+    for ((chunk, vis), hid) in
+        chunks.iter_mut().zip(visible.chunks_mut(per_chunk)).zip(hidden.chunks_mut(per_chunk))
+    {
+        encode(chunk, vis, hid);
     }
 }
@@ -15,9 +15,70 @@
 //!
 //! No dithering, deliberately: it would improve appearance while destroying the
 //! run lengths this exists to create.
+//!
+//! Brick mode's palette encoding ([`super::palette_bricks`]) uses the same
+//! palette for a different saving: every pixel is stored as one or two
+//! [`INDEX_ALPHABET`] characters naming an entry, instead of six hex digits.
+//! There the palette IS an in-game table, so its size is what decides the
+//! stride -- see [`index_stride`].
+use crate::text::MAX_COMPONENT_CHARS;
 use image::RgbaImage;
 use std::collections::HashMap;
 
+/// The characters a palette index is written in, most significant first.
+/// `'0'` is index 0, so a culled pixel's all-`'0'` slot (see
+/// [`super::pack::Packer`]) decodes to a real entry rather than to nothing --
+/// black, which sorts first, whenever the palette has it.
+/// ASCII only; `A` and `a` are distinct codes, which is why the decoding
+/// `String_Find` is set case-sensitive.
+pub const INDEX_ALPHABET: &str =
+    "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz-_";
+
+/// Separates two-character codes in an [`index_table`]. Not in
+/// [`INDEX_ALPHABET`], so no two-character window straddling a record
+/// boundary can ever match a code.
+const INDEX_SEPARATOR: char = '|';
+
+/// Most entries an indexed palette may have: the two-character lookup table
+/// ([`index_table`]) has to fit one component's string.
+pub const MAX_INDEXED_COLORS: usize = MAX_COMPONENT_CHARS / 3;
+
+/// Characters per pixel for a palette of `size` entries: one while every
+/// index is a single [`INDEX_ALPHABET`] character, two past that.
+pub fn index_stride(size: usize) -> usize {
+    if size <= INDEX_ALPHABET.len() { 1 } else { 2 }
+}
+
+/// Characters one entry occupies in [`index_table`], and so the factor the
+/// table's `Find` offsets come out multiplied by: a bare character at stride
+/// 1, a code plus its [`INDEX_SEPARATOR`] at stride 2.
+pub fn index_record(stride: usize) -> usize {
+    if stride == 1 { 1 } else { stride + 1 }
+}
+
+/// Append `index`'s `stride`-character code to `out`.
+pub fn push_index_code(out: &mut String, index: usize, stride: usize) {
+    let alphabet = INDEX_ALPHABET.as_bytes();
+    let base = alphabet.len();
+    for place in (0..stride).rev() {
+        out.push(alphabet[index / base.pow(place as u32) % base] as char);
+    }
+}
+
+/// The string a `String_Find` searches for a pixel's code: every code from
+/// 0 to `len - 1`, in order, each [`index_record`] characters wide. The match
+/// offset is therefore `index * index_record(stride)`.
+pub fn index_table(len: usize, stride: usize) -> String {
+    let mut s = String::with_capacity(len * index_record(stride));
+    for i in 0..len {
+        push_index_code(&mut s, i, stride);
+        if stride > 1 {
+            s.push(INDEX_SEPARATOR);
+        }
+    }
+    s
+}
+
 /// A fixed set of colours, plus a memoized nearest-entry lookup.
 #[derive(Debug, Clone, Default)]
 pub struct Palette {
@@ -166,6 +227,16 @@ impl Palette {
         Self { entries, cache: HashMap::new() }
     }
 
+    /// A fixed palette, such as one read from a palette file. Duplicates are
+    /// dropped and the order is normalized, exactly as [`Palette::build`]
+    /// leaves its entries.
+    pub fn from_entries(entries: impl IntoIterator<Item = [u8; 3]>) -> Self {
+        let mut entries: Vec<[u8; 3]> = entries.into_iter().collect();
+        entries.sort_unstable();
+        entries.dedup();
+        Self { entries, cache: HashMap::new() }
+    }
+
     pub fn len(&self) -> usize {
         self.entries.len()
     }
@@ -187,19 +258,27 @@ impl Palette {
         if let Some(hit) = self.cache.get(&rgb) {
             return *hit;
         }
-        let mut best = self.entries[0];
+        let best = self.entries[self.nearest_index(rgb)];
+        self.cache.insert(rgb, best);
+        best
+    }
+
+    /// The position in [`Self::entries`] of the entry [`Self::map`] would
+    /// pick, or 0 for an empty palette. Uncached and `&self`, so the packer's
+    /// per-chunk jobs can share one palette across threads.
+    pub fn nearest_index(&self, rgb: [u8; 3]) -> usize {
+        let mut best = 0;
         let mut best_d = u32::MAX;
-        for e in &self.entries {
+        for (i, e) in self.entries.iter().enumerate() {
             let dr = e[0] as i32 - rgb[0] as i32;
             let dg = e[1] as i32 - rgb[1] as i32;
             let db = e[2] as i32 - rgb[2] as i32;
             let d = (dr * dr + dg * dg + db * db) as u32;
             if d < best_d {
                 best_d = d;
-                best = *e;
+                best = i;
             }
         }
-        self.cache.insert(rgb, best);
         best
     }
 }
@@ -281,6 +360,13 @@ mod tests {
         assert_eq!(p.entries(), &[[0xFF, 0, 0]]);
     }
 
+    #[test]
+    fn a_fixed_palette_is_deduplicated_and_maps_to_its_own_entries() {
+        let mut p = Palette::from_entries([[9, 9, 9], [0, 0, 0], [9, 9, 9]]);
+        assert_eq!(p.entries(), &[[0, 0, 0], [9, 9, 9]]);
+        assert_eq!(p.map([8, 7, 9]), [9, 9, 9]);
+    }
+
     #[test]
     fn building_is_deterministic() {
         let f = img(&[RED, GREEN, BLUE, [0x80, 0x80, 0x80, 0xFF]]);
@@ -378,4 +464,34 @@ mod tests {
              {thirty_two} vs {three}"
         );
     }
+
+    /// What the in-game lookup does, done here: `Find` a code in the table,
+    /// divide by the record width, and land on the index that was encoded --
+    /// at both strides, including past the one-character alphabet.
+    #[test]
+    fn every_code_is_found_at_its_own_record_in_the_table() {
+        for (len, stride) in [(64usize, 1usize), (65, 2), (MAX_INDEXED_COLORS, 2)] {
+            assert_eq!(index_stride(len), stride, "{len} entries");
+            let table = index_table(len, stride);
+            assert!(table.len() <= MAX_COMPONENT_CHARS, "{len} entries fit one component");
+            for i in 0..len {
+                let mut code = String::new();
+                push_index_code(&mut code, i, stride);
+                assert_eq!(code.len(), stride);
+                assert_eq!(table.find(&code), Some(i * index_record(stride)), "index {i}");
+            }
+        }
+    }
+
+    #[test]
+    fn nearest_index_points_at_the_entry_map_returns() {
+        let mut p = Palette::from_entries([[0, 0, 0], [200, 10, 10], [10, 10, 200]]);
+        for rgb in [[190, 0, 30], [0, 0, 255], [20, 20, 20]] {
+            let nearest = p.entries()[p.nearest_index(rgb)];
+            assert_eq!(nearest, p.map(rgb));
+        }
+        let mut zero = String::new();
+        push_index_code(&mut zero, 0, 2);
+        assert_eq!(zero, "00", "a culled slot's zeros decode to entry 0");
+    }
 }
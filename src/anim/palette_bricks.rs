@@ -0,0 +1,487 @@
+//! Palette mode: the same screen of display bricks [`super::bricks`] builds,
+//! driven by frame strings of palette indices instead of hex.
+//!
+//! The fourth brick encoding, and hex mode's closest sibling: the same
+//! per-chunk string arrays, bank spine and selects (see
+//! [`super::bricks::add_chunk_arrays`]), but each pixel occupies one or two
+//! [`INDEX_ALPHABET`](super::palette::INDEX_ALPHABET) characters rather than
+//! [`HEX_STRIDE`](super::pack::HEX_STRIDE)'s six. The colours themselves live
+//! once, in a single `LinearColorArray` shared by every pixel.
+//!
+//! # The per-pixel lookup
+//!
+//! ```text
+//! code   = Substring(chunk, local * stride, stride)
+//! offset = Find(table, code)             (table = every code, in order)
+//! colour = palette[offset]               (Get, exec from the chunk's detector)
+//! ```
+//!
+//! `Find` returns where the code sits in [`index_table`], which is the index
+//! times [`index_record`] -- so the palette array is laid out at that same
+//! spacing and no division gate is needed. At stride 1 the record is one
+//! character and the array is dense.
+//!
+//! The `Get` needs an exec, and it has to run after its chunk's string has
+//! changed. Each chunk gets its own `ChangeDetectorExec` on that string,
+//! fanned out to the chunk's pixels: one source per exec input, the same
+//! no-fan-in rule [`super::color_bricks`] keeps, and a chunk whose string did
+//! not change (a still region) executes nothing at all.
+//!
+//! Neither `String_Find`'s output port nor a `ChangeDetectorExec` watching a
+//! string has been checked in game; both follow the schema's own field
+//! types.
+//!
+//! # Culled pixels
+//!
+//! A pixel culled in every frame gets no brick and no gates, as in hex mode.
+//! One culled in only some frames shows black there, as hex mode's
+//! `"000000"` does: its slot holds the packer's all-`'0'` code, which `Find`s
+//! to entry 0, and a clip that culls anything keeps black as that entry
+//! ([`render_palette`]). Black sorts first, so it is entry 0 whenever it is
+//! in the palette at all; the picture gives up a colour for it only when its
+//! own palette has no black.
+//!
+//! Whether a clip culls is read off the frames sampled for the palette. One
+//! that culls only between the samples is caught by the packer
+//! ([`needs_black`]) and packed again with black kept -- a third pass over
+//! the source, for a case the samples almost always see first.
+//!
+//! # What it trades
+//!
+//! Three gates per pixel against hex mode's two, for a sixth (64 colours or
+//! fewer) or a third (up to [`MAX_INDEXED_COLORS`]) of its stored characters,
+//! and a picture snapped to the palette. No dithering, for the same reason
+//! [`super::palette`] gives: nothing here is worth a noisier picture.
+use super::bricks::{
+    ARRAY_GET, ARRAY_VAR, AnimOptions, CHANGE_DETECTOR, PROP_CHANGER, SUBSTRING,
+    add_chunk_arrays,
+};
+use super::cascade;
+use super::chip;
+use super::clock::{self, gate};
+use super::color_pack::{CULLED, LinearColor};
+use super::controls;
+use super::layout::{GATE_HALF, STAGE_PITCH, lattice_pos_staged};
+use super::pack::{self, HEX_STRIDE};
+use super::palette::{
+    MAX_INDEXED_COLORS, Palette, index_record, index_stride, index_table,
+};
+use super::seek;
+use super::subtitle_display;
+use super::text_bricks::{PALETTE_SAMPLE_FRAMES, sample_frames};
+use crate::progress::{FrameTotal, Progress};
+use crate::video::stream::FrameSource;
+use brdb::{AsBrdbValue, WirePort, World, schema::WireArrayVariant};
+use image::RgbaImage;
+
+pub const STRING_FIND: &str = "BrickComponentType_WireGraph_Expr_String_Find";
+
+/// Palette size when `opts.colors` is 0: the most that still fits one
+/// character per pixel.
+pub const DEFAULT_PALETTE_COLORS: usize = 64;
+
+/// Gates one pixel's lookup occupies: `Substring`, `Find`, `Get`.
+pub const GATES_PER_PIXEL: usize = 3;
+
+/// Wires one pixel's lookup adds: the chunk string in, code to `Find`,
+/// offset to `Index`, the palette's `ArrayVarRef`, the exec, and the colour
+/// into its display brick.
+pub const WIRES_PER_PIXEL: usize = 6;
+
+/// Lattice stage holding clock, array and detector gates: behind all three
+/// pixel stages.
+const SERVICE_STAGE: i32 = GATES_PER_PIXEL as i32;
+
+/// The palette size a render with these options uses: `opts.colors`, or
+/// [`DEFAULT_PALETTE_COLORS`] when that is 0. The estimate and the render both
+/// read it here, so they agree on the stride.
+pub fn palette_size(opts: &AnimOptions) -> usize {
+    if opts.colors == 0 { DEFAULT_PALETTE_COLORS } else { opts.colors }
+}
+
+/// Whether any pixel of `sampled` is culled, so a render keeps black in its
+/// palette from the start.
+pub fn culls_any(sampled: &[RgbaImage], alpha_threshold: u8) -> bool {
+    sampled.iter().any(|f| f.pixels().any(|p| p.0[3] < alpha_threshold))
+}
+
+/// The palette a render with these options packs against: the `sampled`
+/// frames' median cut to [`palette_size`] entries, or a single black entry
+/// when nothing in them is opaque. With `keep_black`, black is entry 0 --
+/// the one a culled slot shows -- taking one of the picture's entries if its
+/// own palette has no black (but leaving it at least one).
+pub fn render_palette(sampled: &[RgbaImage], opts: &AnimOptions, keep_black: bool) -> Palette {
+    let colors = palette_size(opts);
+    let palette = Palette::build(sampled, colors, opts.alpha_threshold);
+    if palette.is_empty() {
+        // Nothing opaque in any sample. Pixels that turn opaque between
+        // samples still need an entry to resolve to.
+        return Palette::from_entries([[0, 0, 0]]);
+    }
+    if !keep_black || has_black(&palette) {
+        return palette;
+    }
+    let picture = Palette::build(sampled, colors.saturating_sub(1).max(1), opts.alpha_threshold);
+    Palette::from_entries(picture.entries().iter().copied().chain([[0, 0, 0]]))
+}
+
+/// Whether a finished pack has a pixel culled in some frames but not others
+/// and `palette` has no black for its culled slots: it has to be packed
+/// again against [`render_palette`] with `keep_black`.
+pub fn needs_black(palette: &Palette, packer: &pack::Packer) -> bool {
+    packer.partly_culled() && !has_black(palette)
+}
+
+/// Black sorts first, so it is in the palette exactly when it is entry 0.
+fn has_black(palette: &Palette) -> bool {
+    palette.entries().first() == Some(&[0, 0, 0])
+}
+
+/// The `LinearColorArray` the pixels' `Get`s read: entry `i` at
+/// `i * index_record(stride)`, to match where `Find` lands, and
+/// [`CULLED`] in the gaps between (never read).
+pub fn palette_array(palette: &Palette, stride: usize) -> Vec<LinearColor> {
+    let lut = crate::util::srgb_to_linear_f32_table();
+    let record = index_record(stride);
+    let mut out = vec![CULLED; (palette.len().max(1) - 1) * record + 1];
+    for (i, e) in palette.entries().iter().enumerate() {
+        out[i * record] = (lut[e[0] as usize], lut[e[1] as usize], lut[e[2] as usize], 1.0);
+    }
+    out
+}
+
+/// Streams `source` into a wired, animated display-brick [`World`] using the
+/// palette encoding.
+///
+/// Signature, streaming contract and cancellation semantics are identical to
+/// [`super::bricks::build_brick_world`]. Like
+/// [`super::text_bricks::build_text_world`] with `--colors`, the source is
+/// traversed twice -- once to sample frames for the palette, once to encode
+/// -- and a cancel is honoured between the two.
+///
+/// Fails if [`palette_size`] is over [`MAX_INDEXED_COLORS`].
+pub fn build_palette_world(
+    source: &dyn FrameSource,
+    opts: &AnimOptions,
+    progress: &mut dyn Progress,
+) -> Result<World, String> {
+    let info = source.info();
+    let (w, h) = (info.width as i32, info.height as i32);
+
+    let colors = palette_size(opts);
+    if colors > MAX_INDEXED_COLORS {
+        return Err(format!(
+            "--colors {colors} is over the palette encoding's {MAX_INDEXED_COLORS}-colour limit \
+             (its lookup table has to fit one {}-character string)",
+            crate::text::MAX_COMPONENT_CHARS
+        ));
+    }
+    let stride = index_stride(colors);
+
+    // --- The palette: a sampling pass, as text mode's `--colors` does -------
+    FrameTotal::new(
+        info.frame_count_hint.map(|n| n.min(PALETTE_SAMPLE_FRAMES)),
+        source.frame_count_estimate().map(|n| n.min(PALETTE_SAMPLE_FRAMES)),
+    )
+    .begin(progress, "sampling colours");
+    let sampled = sample_frames(source, PALETTE_SAMPLE_FRAMES, progress);
+    progress.finish();
+    let sampled = sampled?;
+    if progress.is_cancelled() {
+        return Ok(World::new());
+    }
+
+    // --- The encode pass: `build_brick_world`'s loop, writing indices -------
+    // Packed again, once, if a pixel culls only where no sample looked.
+    let mut keep_black = culls_any(&sampled, opts.alpha_threshold);
+    let (palette, chunks, visible, seen) = loop {
+        let palette = render_palette(&sampled, opts, keep_black);
+        FrameTotal::new(info.frame_count_hint, source.frame_count_estimate())
+            .begin(progress, "packing frames");
+        let mut packer = pack::Packer::new(info.width, info.height, opts.alpha_threshold, stride)
+            .indexed(palette.clone());
+        let seen: Result<u64, String> = (|| {
+            let mut stream = source.open()?;
+            let mut seen: u64 = 0;
+            while let Some(frame) = stream.next()? {
+                packer.push_frame(&frame)?;
+                progress.frame(frame.width(), frame.height(), frame.as_raw());
+                seen += 1;
+                progress.tick(seen);
+                if progress.is_cancelled() {
+                    break;
+                }
+            }
+            Ok(seen)
+        })();
+        progress.finish();
+        let seen = seen?;
+
+        if progress.is_cancelled() {
+            return Ok(World::new());
+        }
+        if needs_black(&palette, &packer) {
+            log::info!(
+                "Palette encoding: a pixel is culled in frames the palette sample missed; \
+                 packing again with black kept for it"
+            );
+            keep_black = true;
+            continue;
+        }
+        let (chunks, visible) = packer.finish();
+        break (palette, chunks, visible, seen);
+    };
+    let frame_count = seen as usize;
+
+    if frame_count == 0 {
+        return Err(
+            "clip has 0 frames -- nothing to render (check --start/--duration, or the GUI's \
+             Start/Duration, against the source's length)"
+                .to_string(),
+        );
+    }
+
+    let chars = visible.len() * frame_count * stride;
+    let hex_chars = visible.len() * frame_count * HEX_STRIDE;
+    log::info!(
+        "Palette encoding: {} colour(s), {stride} character(s) per pixel -- {chars} character(s) \
+         instead of hex mode's {hex_chars} ({:.1}x fewer)",
+        palette.len(),
+        hex_chars as f64 / chars.max(1) as f64
+    );
+
+    let mut world = World::new();
+    world.meta.bundle.description = "Animation generated from image frames".to_string();
+
+    // --- 1. Display bricks, 2. the chip: shared verbatim with hex mode. -----
+    let (brick_of, geometry) = super::bricks::add_display_bricks(&mut world, opts, w, h, &visible);
+    let mut chip = super::bricks::new_screen_chip(&mut world, w, h, &geometry);
+
+    let service = |col: i32, row: i32| {
+        lattice_pos_staged(col, row, SERVICE_STAGE, h, GATE_HALF, STAGE_PITCH)
+    };
+
+    // --- 3. Frame index source ---------------------------------------------
+    let (frame_index, control_pins, seek_hook) = if opts.external_clock {
+        let pin = chip::add_input_pin(&mut chip, "Frame", service(0, -1));
+        (chip::pin_source(pin, true), None, None)
+    } else {
+        let clock = clock::build_clock(
+            &mut world,
+            &mut chip,
+            info.fps,
+            frame_count,
+            opts.playback.with_loop(opts.loop_playback),
+            !opts.seek.is_empty(),
+            service(0, -2),
+        );
+        let pins = (clock.pause_pin, clock.restart_pin, clock.resume_pin);
+        let hook = seek::SeekHook::of(&clock);
+        (clock.frame_index, Some(pins), hook)
+    };
+
+    // --- 4. Exec source, and the one palette every pixel reads ---------------
+    let detector = gate(
+        &mut chip,
+        "B_1x1_Gate_Expr_ChangeDetectorExec",
+        CHANGE_DETECTOR,
+        service(0, -4),
+        vec![],
+    );
+    world.add_wire_connection(
+        frame_index.clone(),
+        WirePort::new(detector, CHANGE_DETECTOR, "Input"),
+    );
+    let palette_var = gate(&mut chip, "B_1x1_Gate_Variable_Array", ARRAY_VAR, service(1, -4), vec![(
+        "Value",
+        Box::new(WireArrayVariant::LinearColorArray(palette_array(&palette, stride)))
+            as Box<dyn AsBrdbValue>,
+    )]);
+    let table = index_table(palette.len(), stride);
+
+    // --- 5. Chunk strings: hex mode's arrays, gets and selects ---------------
+    let bank_size = opts.bank_size.max(1);
+    let n_banks = frame_count.div_ceil(bank_size).max(1);
+    let spine = cascade::bank_cascade(
+        &mut world,
+        &mut chip,
+        &frame_index,
+        WirePort::new(detector, CHANGE_DETECTOR, "OnChanged"),
+        n_banks,
+        bank_size,
+        service,
+    );
+    let value_of_chunk = add_chunk_arrays(&mut world, &mut chip, &chunks, bank_size, &spine, service);
+    let cascade::BankCascade { index_of_bank, ge, entry_of_bank } = spine;
+
+    // --- 6. One detector per chunk, three gates per surviving pixel ----------
+    for (ci, chunk) in chunks.iter().enumerate() {
+        let value = value_of_chunk[ci].clone();
+        let changed = gate(
+            &mut chip,
+            "B_1x1_Gate_Expr_ChangeDetectorExec",
+            CHANGE_DETECTOR,
+            service(ci as i32, -10),
+            vec![],
+        );
+        world.add_wire_connection(value.clone(), WirePort::new(changed, CHANGE_DETECTOR, "Input"));
+        for local in 0..chunk.pixel_count {
+            if progress.is_cancelled() {
+                return Ok(World::new());
+            }
+            let idx = chunk.first_pixel + local;
+            let Some(&brick_id) = brick_of.get(&idx) else {
+                continue; // culled throughout: slot reserved in the string, no gates
+            };
+            let (col, row) = ((idx as i32) % w, (idx as i32) / w);
+            let at = |stage| lattice_pos_staged(col, row, stage, h, GATE_HALF, STAGE_PITCH);
+
+            // bare i64s, as in hex mode's `Substring`.
+            let sub = gate(&mut chip, "B_1x1_Gate_Expr_String_Substring", SUBSTRING, at(2), vec![
+                ("Start", Box::new((local * stride) as i64) as Box<dyn AsBrdbValue>),
+                ("Length", Box::new(stride as i64) as Box<dyn AsBrdbValue>),
+            ]);
+            let find = gate(&mut chip, "B_1x1_Gate_Expr_String_Find", STRING_FIND, at(1), vec![
+                ("Input", Box::new(table.clone()) as Box<dyn AsBrdbValue>),
+                ("bCaseSensitive", Box::new(true) as Box<dyn AsBrdbValue>),
+            ]);
+            let get = gate(&mut chip, "B_1x1_Gate_Exec_ArrayVar_Get", ARRAY_GET, at(0), vec![]);
+
+            world.add_wire_connection(value.clone(), WirePort::new(sub, SUBSTRING, "Input"));
+            world.add_wire_connection(
+                WirePort::new(sub, SUBSTRING, "Output"),
+                WirePort::new(find, STRING_FIND, "Search"),
+            );
+            world.add_wire_connection(
+                WirePort::new(find, STRING_FIND, "Output"),
+                WirePort::new(get, ARRAY_GET, "Index"),
+            );
+            world.add_wire_connection(
+                WirePort::new(palette_var, ARRAY_VAR, "ArrayVarRef"),
+                WirePort::new(get, ARRAY_GET, "ArrayVarRef"),
+            );
+            world.add_wire_connection(
+                WirePort::new(changed, CHANGE_DETECTOR, "OnChanged"),
+                WirePort::new(get, ARRAY_GET, "Exec"),
+            );
+            world.add_wire_connection(
+                WirePort::new(get, ARRAY_GET, "Value"),
+                WirePort::new(brick_id, PROP_CHANGER, "Color"),
+            );
+        }
+    }
+
+    // --- 6b. Subtitles, if any: last, as in the other renderers. -------------
+    if let Some(subs) = &opts.subtitles {
+        let per_frame = subs.per_frame(opts.source_start_s, info.fps as f64, frame_count)?;
+        subtitle_display::add_subtitle_display(
+            &mut world,
+            &mut chip,
+            subtitle_display::FrameIndex {
+                index_of_bank: &index_of_bank,
+                entry_of_bank: &entry_of_bank,
+                ge: &ge,
+            },
+            &per_frame,
+            info.fps,
+            opts,
+            super::bricks::subtitle_extent(&geometry, w, h, opts.subtitle_lift)?,
+        )?;
+    }
+
+    // --- 6c. Control buttons -------------------------------------------------
+    if let (true, Some((pause, restart, resume))) = (opts.control_buttons, control_pins) {
+        let anchor = controls::control_anchor(&world);
+        controls::add_control_buttons(&mut world, pause, restart, resume, anchor);
+    }
+    // Seek controls, if asked for: the graph beyond everything else in the
+    // chip, the buttons beside the control buttons. Same step as hex mode's.
+    if let Some(hook) = &seek_hook {
+        let plan = opts.seek.plan(info.fps, frame_count, opts.source_start_s);
+        let buttons = seek::add_seek_graph(&mut world, &mut chip, hook, &plan);
+        let anchor = controls::control_anchor(&world);
+        controls::add_seek_buttons(&mut world, &buttons, anchor);
+    }
+
+    // --- 7. Publish ----------------------------------------------------------
+    if progress.is_cancelled() {
+        return Ok(World::new());
+    }
+    chip::finish(&mut world, chip)?;
+    world.register_used_components();
+    Ok(world)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::video::Clip;
+
+    /// Eight pixels of four colours, cycling through them.
+    fn four_colour_clip(frames: usize) -> Clip {
+        let colours = [[255, 0, 0], [0, 255, 0], [0, 0, 255], [250, 250, 250]];
+        Clip {
+            width: 4,
+            height: 2,
+            fps: 10.0,
+            frames: (0..frames)
+                .map(|f| {
+                    image::RgbaImage::from_fn(4, 2, |x, y| {
+                        let [r, g, b] = colours[(x as usize + y as usize + f) % 4];
+                        image::Rgba([r, g, b, 255])
+                    })
+                })
+                .collect(),
+        }
+    }
+
+    /// The palette array puts each entry where `Find` will land for its code,
+    /// at both strides.
+    #[test]
+    fn the_palette_array_is_spaced_like_the_lookup_table() {
+        let palette = Palette::from_entries([[0, 0, 0], [255, 255, 255]]);
+        assert_eq!(palette_array(&palette, 1), vec![(0.0, 0.0, 0.0, 1.0), (1.0, 1.0, 1.0, 1.0)]);
+        let wide = palette_array(&palette, 2);
+        assert_eq!(wide.len(), 4);
+        assert_eq!(wide[3], (1.0, 1.0, 1.0, 1.0));
+    }
+
+    /// The graph is the documented three gates per pixel plus a detector per
+    /// chunk -- counted off a real render, not assumed.
+    #[test]
+    fn every_pixel_gets_the_documented_lookup() {
+        let clip = four_colour_clip(5);
+        let opts = AnimOptions { control_buttons: false, ..AnimOptions::default() };
+        let world = build_palette_world(&clip, &opts, &mut crate::progress::NoProgress).unwrap();
+        // 8 pixels; 6 clock + detector + palette array + ArrayVar/Get + chunk
+        // detector; 7 pins.
+        assert_eq!(world.grids[0].1.len(), 8 * GATES_PER_PIXEL + 11 + 7);
+    }
+
+    /// Kept black is entry 0, and costs the picture a colour only when the
+    /// picture has no black of its own.
+    #[test]
+    fn keeping_black_costs_a_colour_only_when_the_picture_has_none() {
+        let frames = four_colour_clip(1).frames;
+        let opts = AnimOptions { colors: 4, ..AnimOptions::default() };
+        let kept = render_palette(&frames, &opts, true);
+        assert_eq!((kept.entries()[0], kept.len()), ([0, 0, 0], 4));
+        assert!(!render_palette(&frames, &opts, false).entries().contains(&[0, 0, 0]));
+
+        let mut dark = frames.clone();
+        dark[0].put_pixel(0, 0, image::Rgba([0, 0, 0, 255]));
+        let opts = AnimOptions { colors: 8, ..AnimOptions::default() };
+        let kept = render_palette(&dark, &opts, true);
+        assert_eq!(kept.entries(), render_palette(&dark, &opts, false).entries());
+        assert_eq!(kept.len(), 5, "the four colours and the picture's own black");
+    }
+
+    #[test]
+    fn a_palette_past_the_table_limit_is_refused() {
+        let opts = AnimOptions { colors: MAX_INDEXED_COLORS + 1, ..AnimOptions::default() };
+        let err = build_palette_world(&four_colour_clip(2), &opts, &mut crate::progress::NoProgress)
+            .err()
+            .expect("too many colours");
+        assert!(err.contains("--colors") && err.contains(&MAX_INDEXED_COLORS.to_string()), "{err}");
+    }
+}
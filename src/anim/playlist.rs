@@ -0,0 +1,284 @@
+//! Several clips in one world: one timeline, with buttons to move between
+//! them.
+//!
+//! The clips are played back to back as one long clip, so every renderer
+//! builds a playlist exactly as it builds a single clip -- one screen, one
+//! clock, and a clip's frames held in whichever banks its place on the
+//! timeline falls in, selected by the same [`super::cascade`] that selects any
+//! bank. What makes it a playlist is [`super::seek`]: Previous/Next Clip and a
+//! button per clip are jumps to a clip's first frame.
+//!
+//! # Auto-advance
+//!
+//! On by default: a clip runs into the next one, and the last into the first
+//! (or stops, under a play-once clock). Off, each clip loops on itself. That
+//! takes one extra frame per clip on the timeline, a copy of the clip's last
+//! frame: the seek graph turns the offset back by the clip's length whenever
+//! the index lands on it ([`Playlist::loop_points`]). Reading where the index
+//! IS rather than where it came from keeps the check stateless, so a button
+//! jumping to a clip's start is never mistaken for a clip running off its end.
+//!
+//! Every clip must share the first one's size and fps -- they fill one screen
+//! at one rate.
+//!
+//! # Songs and the GUI
+//!
+//! `--audio-mode` songs make the same [`Playlist`], on the speakers' clock:
+//! `audio::playlist` joins their analyses into one track. The GUI's video tab
+//! builds a [`PlaylistSource`] from its clip list, as the CLI does.
+//!
+//! # What it does not cover
+//!
+//! MIDI and delta mode, whose playheads can only land back on a few known
+//! frames, not on any clip's (see [`super::delta_bricks::check_options`]).
+use crate::video::stream::{FrameSource, FrameStream, SourceInfo};
+use image::RgbaImage;
+
+/// One clip on a playlist's timeline.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlaylistClip {
+    /// What its button says.
+    pub name: String,
+    /// Its own frames, not counting the looped copy [`Playlist::auto_advance`]
+    /// off adds.
+    pub frames: usize,
+}
+
+/// A playlist's clips, in play order, and what happens at the end of each.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Playlist {
+    pub clips: Vec<PlaylistClip>,
+    /// Run each clip into the next (`true`) or loop it on itself.
+    pub auto_advance: bool,
+}
+
+impl Playlist {
+    /// Frames a clip takes on the timeline: its own, plus the copy of its last
+    /// frame a looping clip ends on.
+    fn span(&self, clip: &PlaylistClip) -> usize {
+        clip.frames + usize::from(!self.auto_advance)
+    }
+
+    /// Each clip's first frame on the timeline.
+    pub fn starts(&self) -> Vec<usize> {
+        self.clips
+            .iter()
+            .scan(0, |at, clip| {
+                let start = *at;
+                *at += self.span(clip);
+                Some(start)
+            })
+            .collect()
+    }
+
+    /// The whole timeline's length.
+    pub fn frame_count(&self) -> usize {
+        self.clips.iter().map(|c| self.span(c)).sum()
+    }
+
+    /// With auto-advance off, `(copy, start)` per clip: the frame holding the
+    /// copy of its last frame, and the start the offset turns back to from it.
+    /// Empty with auto-advance on.
+    pub fn loop_points(&self) -> Vec<(usize, usize)> {
+        if self.auto_advance {
+            return Vec::new();
+        }
+        self.starts().into_iter().zip(&self.clips).map(|(s, c)| (s + c.frames, s)).collect()
+    }
+}
+
+/// Several [`FrameSource`]s played back to back as one, laid out as
+/// [`Playlist`] describes.
+///
+/// Every clip's length is counted when the playlist is built -- from its hint
+/// where it has one, otherwise by decoding it once -- because the buttons are
+/// wired to where each clip starts. A stream that then disagrees with its
+/// count is an error rather than a playlist whose buttons miss.
+pub struct PlaylistSource<'a> {
+    clips: Vec<&'a dyn FrameSource>,
+    playlist: Playlist,
+}
+
+impl<'a> PlaylistSource<'a> {
+    pub fn new(
+        clips: Vec<(String, &'a dyn FrameSource)>,
+        auto_advance: bool,
+    ) -> Result<Self, String> {
+        let Some((first_name, first)) = clips.first() else {
+            return Err("a playlist needs at least one clip".to_string());
+        };
+        let want = first.info();
+        let mut counted = Vec::with_capacity(clips.len());
+        for (name, clip) in &clips {
+            let info = clip.info();
+            if (info.width, info.height) != (want.width, want.height) {
+                return Err(format!(
+                    "playlist clip '{name}' is {}x{} but '{first_name}' is {}x{}; every clip \
+                     fills the same screen",
+                    info.width, info.height, want.width, want.height
+                ));
+            }
+            if (info.fps - want.fps).abs() > f32::EPSILON * want.fps.max(1.0) {
+                return Err(format!(
+                    "playlist clip '{name}' plays at {} fps but '{first_name}' at {}; every clip \
+                     runs on the same clock",
+                    info.fps, want.fps
+                ));
+            }
+            let frames = match info.frame_count_hint {
+                Some(n) => n,
+                None => clip.open()?.advance(usize::MAX)?.0,
+            };
+            if frames == 0 {
+                return Err(format!("playlist clip '{name}' has no frames"));
+            }
+            counted.push(PlaylistClip { name: name.clone(), frames });
+        }
+        Ok(PlaylistSource {
+            clips: clips.into_iter().map(|(_, c)| c).collect(),
+            playlist: Playlist { clips: counted, auto_advance },
+        })
+    }
+
+    /// The timeline this source streams.
+    pub fn playlist(&self) -> &Playlist {
+        &self.playlist
+    }
+}
+
+impl FrameSource for PlaylistSource<'_> {
+    fn info(&self) -> SourceInfo {
+        SourceInfo {
+            frame_count_hint: Some(self.playlist.frame_count()),
+            ..self.clips[0].info()
+        }
+    }
+
+    fn open(&self) -> Result<Box<dyn FrameStream + '_>, String> {
+        Ok(Box::new(PlaylistStream {
+            source: self,
+            clip: 0,
+            stream: None,
+            seen: 0,
+            last: None,
+        }))
+    }
+}
+
+struct PlaylistStream<'a> {
+    source: &'a PlaylistSource<'a>,
+    /// The clip being streamed; `clips.len()` once all have been.
+    clip: usize,
+    stream: Option<Box<dyn FrameStream + 'a>>,
+    /// Frames `clip` has given so far.
+    seen: usize,
+    /// Its latest, for the copy a looping clip ends on.
+    last: Option<RgbaImage>,
+}
+
+impl FrameStream for PlaylistStream<'_> {
+    fn next(&mut self) -> Result<Option<RgbaImage>, String> {
+        let playlist = &self.source.playlist;
+        while self.clip < playlist.clips.len() {
+            let expected = &playlist.clips[self.clip];
+            let stream = match &mut self.stream {
+                Some(s) => s,
+                None => self.stream.insert(self.source.clips[self.clip].open()?),
+            };
+            if let Some(frame) = stream.next()? {
+                self.seen += 1;
+                if self.seen > expected.frames {
+                    return Err(format!(
+                        "playlist clip '{}' gave more than the {} frames counted for it",
+                        expected.name, expected.frames
+                    ));
+                }
+                if !playlist.auto_advance {
+                    self.last = Some(frame.clone());
+                }
+                return Ok(Some(frame));
+            }
+            if self.seen != expected.frames {
+                return Err(format!(
+                    "playlist clip '{}' gave {} frames where {} were counted",
+                    expected.name, self.seen, expected.frames
+                ));
+            }
+            self.clip += 1;
+            self.stream = None;
+            self.seen = 0;
+            if let Some(copy) = self.last.take() {
+                return Ok(Some(copy));
+            }
+        }
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::video::Clip;
+    use image::Rgba;
+
+    fn flat(shade: u8, n: usize) -> Clip {
+        let frames = (0..n)
+            .map(|f| RgbaImage::from_pixel(2, 1, Rgba([shade, f as u8, 0, 255])))
+            .collect();
+        Clip { width: 2, height: 1, fps: 10.0, frames }
+    }
+
+    fn drain(source: &dyn FrameSource) -> Vec<[u8; 2]> {
+        let mut stream = source.open().unwrap();
+        let mut out = Vec::new();
+        while let Some(f) = stream.next().unwrap() {
+            out.push([f.get_pixel(0, 0)[0], f.get_pixel(0, 0)[1]]);
+        }
+        out
+    }
+
+    #[test]
+    fn clips_play_back_to_back() {
+        let (a, b) = (flat(1, 2), flat(2, 3));
+        let source =
+            PlaylistSource::new(vec![("a".into(), &a), ("b".into(), &b)], true).unwrap();
+        assert_eq!(drain(&source), vec![[1, 0], [1, 1], [2, 0], [2, 1], [2, 2]]);
+        assert_eq!(source.info().frame_count_hint, Some(5));
+        assert_eq!(source.playlist().starts(), vec![0, 2]);
+        assert!(source.playlist().loop_points().is_empty());
+    }
+
+    /// Each looping clip ends on a copy of its last frame, which is where the
+    /// seek graph turns it back.
+    #[test]
+    fn a_looping_clip_ends_on_a_copy_of_its_last_frame() {
+        let (a, b) = (flat(1, 2), flat(2, 3));
+        let source =
+            PlaylistSource::new(vec![("a".into(), &a), ("b".into(), &b)], false).unwrap();
+        let frames = drain(&source);
+        assert_eq!(frames, vec![[1, 0], [1, 1], [1, 1], [2, 0], [2, 1], [2, 2], [2, 2]]);
+        let playlist = source.playlist();
+        assert_eq!(playlist.starts(), vec![0, 3]);
+        assert_eq!(playlist.frame_count(), frames.len());
+        assert_eq!(playlist.loop_points(), vec![(2, 0), (6, 3)]);
+    }
+
+    fn refusal(clips: Vec<(String, &dyn FrameSource)>) -> String {
+        match PlaylistSource::new(clips, true) {
+            Ok(_) => panic!("the playlist must be refused"),
+            Err(e) => e,
+        }
+    }
+
+    #[test]
+    fn clips_of_another_size_or_rate_are_refused() {
+        let a = flat(1, 2);
+        let wide = Clip { width: 3, ..flat(2, 2) };
+        let err = refusal(vec![("a".into(), &a), ("wide".into(), &wide)]);
+        assert!(err.contains("'wide' is 3x1"), "{err}");
+        let fast = Clip { fps: 20.0, ..flat(2, 2) };
+        let err = refusal(vec![("a".into(), &a), ("fast".into(), &fast)]);
+        assert!(err.contains("20 fps"), "{err}");
+        assert!(refusal(vec![]).contains("at least one clip"));
+    }
+}
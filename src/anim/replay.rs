@@ -0,0 +1,740 @@
+//! Simulated playback: a render's encoded arrays decoded back into the
+//! frames its screen will show, and written out as a GIF, an APNG or a
+//! directory of PNGs -- so a render can be checked before its save is
+//! loaded.
+//!
+//! Nothing here re-derives a picture from the source. [`replay`] streams the
+//! source through the packer each renderer uses, configured exactly as that
+//! renderer configures it, and then reads every frame back out of what the
+//! packer produced, the way the chip's gates would: hex slices parsed as
+//! `MakeColorHex` parses them, palette codes looked up with the same `Find`
+//! over [`index_table`], colour arrays and change lists indexed by frame, and
+//! text bands parsed glyph by glyph. Whatever the encoding did to the
+//! picture -- alpha culling, `--colors`, `--srgb-to-linear`, text mode's
+//! colour runs and glyph cells -- is therefore in the replay, and so is the
+//! source adapter's frame resampling, since the replay pulls from the same
+//! [`FrameSource`].
+//!
+//! # What the screen is assumed to do
+//!
+//! - A pixel that is never visible gets no display brick, and replays
+//!   transparent. A hex or palette pixel culled in only some frames shows
+//!   whatever its slot says there -- black for hex's `"000000"`, and black
+//!   for a palette's all-zero code too, since a palette that needs it keeps
+//!   black as entry 0 -- and a colour-array or delta pixel replays
+//!   [`CULLED`]'s zero alpha as transparent.
+//! - A delta pixel is read straight off its change list
+//!   ([`PixelTrack::color_at`]). The chip's playhead takes one change per
+//!   frame change instead, and the two agree on every order the replay plays:
+//!   the clock's ticks are whole frames, so it never skips one, and every
+//!   step back the playhead sees is onto frame 0 or a loop range's start,
+//!   where it resyncs exactly (see [`super::delta_bricks`]). The seeks that
+//!   would part them are refused here as the renderer refuses them. An
+//!   external `Frame` pin is not replayed at all, so its lag on a forward
+//!   jump is not shown.
+//! - Hex is shown as the sRGB bytes it spells. `--srgb-to-linear` rewrites
+//!   those bytes, so its effect is visible as written; whether the game's
+//!   `MakeColorHex` wants them that way is exactly what the preview is for.
+//! - Linear colours go back to sRGB through the inverse of the table the
+//!   packers converted with, so an unquantised colour array replays its
+//!   source bytes exactly.
+//! - Text glyphs are one pixel per `char_repeat` glyphs (colour mode) or one
+//!   sprite cell per glyph (the monochrome modes). A drawn glyph fills its
+//!   pixels solidly; the font's real shape is not modelled. Glyphs are white
+//!   until a colour tag says otherwise, as the component's own colour is.
+//! - Subtitles are drawn with a built-in 5x7 pixel font, white with a black
+//!   outline, centred near the bottom. The timing is the track's own per-frame
+//!   text; the lettering is a stand-in for the game's font.
+//!
+//! The picture is enlarged by a whole factor before the subtitle is drawn
+//! (see [`auto_scale`]), so a 64-pixel-wide render still has room for a line
+//! of text.
+use super::bricks::AnimOptions;
+use super::color_pack::{CULLED, ColorPacker, LinearColor};
+use super::delta_pack::{DeltaPacker, PixelTrack};
+use super::pack::{self, HEX_STRIDE};
+use super::palette::{MAX_INDEXED_COLORS, Palette, index_record, index_stride, index_table};
+use super::palette_bricks::{culls_any, needs_black, palette_array, palette_size, render_palette};
+use super::text_bricks::{PALETTE_SAMPLE_FRAMES, sample_frames};
+use super::text_layout::{BandPlan, plan_text_bands};
+use super::text_pack::TextPacker;
+use super::{AnimEncoding, AnimMode};
+use crate::progress::{FrameTotal, Progress};
+use crate::text::{COLOR_TAG_CLOSE, COLOR_TAG_OPEN, PixelMode, TextOptions};
+use crate::video::stream::FrameSource;
+use image::{Rgba, RgbaImage};
+use std::path::Path;
+
+/// Width [`auto_scale`] enlarges a replay to at least.
+pub const PREVIEW_MIN_WIDTH: u32 = 480;
+
+/// The largest factor [`auto_scale`] picks.
+pub const MAX_AUTO_SCALE: u32 = 16;
+
+/// What a screen shows with nothing drawn on it.
+const CLEAR: Rgba<u8> = Rgba([0, 0, 0, 0]);
+
+/// Glyph colour before any `<color>` tag: the component's own white.
+const GLYPH_WHITE: [u8; 3] = [255, 255, 255];
+
+/// What each encoding's packer left behind, kept whole so any frame can be
+/// decoded in any order.
+enum Screen {
+    Hex {
+        chunks: Vec<pack::Chunk>,
+        visible: Vec<bool>,
+    },
+    Colors {
+        pixels: Vec<Vec<LinearColor>>,
+        visible: Vec<bool>,
+    },
+    Delta {
+        tracks: Vec<PixelTrack>,
+        visible: Vec<bool>,
+    },
+    Palette {
+        chunks: Vec<pack::Chunk>,
+        visible: Vec<bool>,
+        stride: usize,
+        /// The string each pixel's `Find` searches.
+        table: String,
+        /// The `LinearColorArray` its `Get` reads.
+        colors: Vec<LinearColor>,
+    },
+    Text {
+        plan: Vec<BandPlan>,
+        /// `bands[band][frame]`, as [`TextPacker::finish`] returns them.
+        bands: Vec<Vec<String>>,
+        text: TextOptions,
+    },
+}
+
+/// A render's frames, decoded on demand.
+pub struct Replay {
+    pub width: u32,
+    pub height: u32,
+    pub fps: f32,
+    /// Frames in the clip, before any playback order repeats them.
+    pub frames: usize,
+    screen: Screen,
+    /// The subtitle on each frame; empty without a track.
+    subtitles: Vec<String>,
+    /// The order the clock would walk, for [`Replay::play_order`].
+    playback: Option<super::clock::Playback>,
+}
+
+/// Pack `source` as `mode` would, keeping the encoded arrays for
+/// [`Replay::frame`] to decode.
+///
+/// Fails where the renderer would: a layout text mode cannot band, a palette
+/// over its limit, a delta seek its playhead cannot follow, a
+/// zero-frame clip, a subtitle track that cannot be timed.
+/// A cancelled `progress` is an error here, since there is nothing partial
+/// worth returning.
+pub fn replay(
+    mode: AnimMode,
+    source: &dyn FrameSource,
+    opts: &AnimOptions,
+    progress: &mut dyn Progress,
+) -> Result<Replay, String> {
+    let info = source.info();
+    let (w, h) = (info.width, info.height);
+    let (screen, frames) = match mode {
+        AnimMode::Brick(AnimEncoding::Hex) => {
+            // One packer for the whole screen: panels cut the same pixels
+            // on different seams, and the replay has no seams to draw.
+            let mut packer = pack::Packer::new(w, h, opts.alpha_threshold, HEX_STRIDE)
+                .linearize(opts.srgb_to_linear);
+            let frames = pull(source, progress, |f| packer.push_frame(f))?;
+            let (chunks, visible) = packer.finish();
+            (Screen::Hex { chunks, visible }, frames)
+        }
+        AnimMode::Brick(AnimEncoding::ColorArray) => {
+            let mut packer = ColorPacker::new(w, h, opts.alpha_threshold, info.frame_count_hint);
+            let frames = pull(source, progress, |f| packer.push_frame(f))?;
+            let (pixels, visible) = packer.finish();
+            (Screen::Colors { pixels, visible }, frames)
+        }
+        AnimMode::Brick(AnimEncoding::Delta) => {
+            super::delta_bricks::check_options(opts)?;
+            let mut packer = DeltaPacker::new(w, h, opts.alpha_threshold);
+            let frames = pull(source, progress, |f| packer.push_frame(f))?;
+            let (tracks, visible) = packer.finish();
+            (Screen::Delta { tracks, visible }, frames)
+        }
+        AnimMode::Brick(AnimEncoding::Palette) => {
+            let size = palette_size(opts);
+            if size > MAX_INDEXED_COLORS {
+                return Err(format!(
+                    "--colors {size} is over the palette encoding's {MAX_INDEXED_COLORS}-colour \
+                     limit"
+                ));
+            }
+            let stride = index_stride(size);
+            let sampled = sampled_frames(source, progress)?;
+            // As `build_palette_world` packs: again, with black kept, if a
+            // pixel culls only where no sample looked.
+            let mut keep_black = culls_any(&sampled, opts.alpha_threshold);
+            loop {
+                let palette = render_palette(&sampled, opts, keep_black);
+                let mut packer = pack::Packer::new(w, h, opts.alpha_threshold, stride)
+                    .indexed(palette.clone());
+                let frames = pull(source, progress, |f| packer.push_frame(f))?;
+                if needs_black(&palette, &packer) {
+                    keep_black = true;
+                    continue;
+                }
+                let table = index_table(palette.len(), stride);
+                let colors = palette_array(&palette, stride);
+                let (chunks, visible) = packer.finish();
+                break (Screen::Palette { chunks, visible, stride, table, colors }, frames);
+            }
+        }
+        AnimMode::Text => {
+            let plan = plan_text_bands(w as usize, h as usize, &opts.text)?;
+            let palette = if opts.colors > 0 && opts.text.mode == PixelMode::Color {
+                let sampled = sampled_frames(source, progress)?;
+                Palette::build(&sampled, opts.colors, opts.text.alpha_threshold)
+            } else {
+                Palette::default()
+            };
+            let mut packer = TextPacker::new(w, h, plan.clone(), opts.text.clone(), palette);
+            let frames = pull(source, progress, |f| packer.push_frame(f))?;
+            let bands = packer.finish();
+            (Screen::Text { plan, bands, text: opts.text.clone() }, frames)
+        }
+    };
+    if frames == 0 {
+        return Err("clip has 0 frames -- nothing to replay".to_string());
+    }
+    let subtitles = match &opts.subtitles {
+        Some(subs) => subs.per_frame(opts.source_start_s, info.fps as f64, frames)?,
+        None => Vec::new(),
+    };
+    // An external clock is driven from outside, in no order of its own.
+    let playback = (!opts.external_clock).then(|| opts.playback.with_loop(opts.loop_playback));
+    Ok(Replay { width: w, height: h, fps: info.fps, frames, screen, subtitles, playback })
+}
+
+/// `build_text_world`'s and `build_palette_world`'s sampling pass.
+fn sampled_frames(
+    source: &dyn FrameSource,
+    progress: &mut dyn Progress,
+) -> Result<Vec<RgbaImage>, String> {
+    let info = source.info();
+    FrameTotal::new(
+        info.frame_count_hint.map(|n| n.min(PALETTE_SAMPLE_FRAMES)),
+        source.frame_count_estimate().map(|n| n.min(PALETTE_SAMPLE_FRAMES)),
+    )
+    .begin(progress, "sampling colours");
+    let sampled = sample_frames(source, PALETTE_SAMPLE_FRAMES, progress);
+    progress.finish();
+    if progress.is_cancelled() {
+        return Err("replay cancelled".to_string());
+    }
+    sampled
+}
+
+/// The renderers' streaming pull loop, feeding each frame to `push`.
+fn pull(
+    source: &dyn FrameSource,
+    progress: &mut dyn Progress,
+    mut push: impl FnMut(&RgbaImage) -> Result<(), String>,
+) -> Result<usize, String> {
+    FrameTotal::new(source.info().frame_count_hint, source.frame_count_estimate())
+        .begin(progress, "packing frames");
+    let seen: Result<u64, String> = (|| {
+        let mut stream = source.open()?;
+        let mut seen: u64 = 0;
+        while let Some(frame) = stream.next()? {
+            push(&frame)?;
+            progress.frame(frame.width(), frame.height(), frame.as_raw());
+            seen += 1;
+            progress.tick(seen);
+            if progress.is_cancelled() {
+                break;
+            }
+        }
+        Ok(seen)
+    })();
+    progress.finish();
+    if progress.is_cancelled() {
+        return Err("replay cancelled".to_string());
+    }
+    Ok(seen? as usize)
+}
+
+impl Replay {
+    /// Frame `frame` of the clip as the screen shows it, at one image pixel
+    /// per screen pixel and without the subtitle.
+    pub fn screen(&self, frame: usize) -> RgbaImage {
+        let (w, h) = (self.width, self.height);
+        let mut img = RgbaImage::from_pixel(w, h, CLEAR);
+        let put = |img: &mut RgbaImage, i: usize, c: Rgba<u8>| {
+            img.put_pixel(i as u32 % w, i as u32 / w, c);
+        };
+        match &self.screen {
+            Screen::Hex { chunks, visible } => {
+                for chunk in chunks {
+                    for local in 0..chunk.pixel_count {
+                        let i = chunk.first_pixel + local;
+                        if visible[i] {
+                            put(&mut img, i, parse_hex(pack::slice_of(chunk, frame, local)));
+                        }
+                    }
+                }
+            }
+            Screen::Colors { pixels, visible } => {
+                for (i, colors) in pixels.iter().enumerate() {
+                    if visible[i] {
+                        put(&mut img, i, to_srgb(colors[frame]));
+                    }
+                }
+            }
+            Screen::Delta { tracks, visible } => {
+                for (i, track) in tracks.iter().enumerate() {
+                    if visible[i] {
+                        put(&mut img, i, to_srgb(track.color_at(frame)));
+                    }
+                }
+            }
+            Screen::Palette { chunks, visible, stride, table, colors } => {
+                let record = index_record(*stride);
+                for chunk in chunks {
+                    let codes = chunk.frames[frame].as_bytes();
+                    for (local, code) in codes.chunks_exact(*stride).enumerate() {
+                        let i = chunk.first_pixel + local;
+                        if !visible[i] {
+                            continue;
+                        }
+                        // `Find` lands on a record boundary or nowhere; the
+                        // `Get` of a miss reads nothing.
+                        let code = std::str::from_utf8(code).unwrap_or_default();
+                        let color = table
+                            .find(code)
+                            .filter(|at| at % record == 0)
+                            .and_then(|at| colors.get(at))
+                            .copied()
+                            .unwrap_or(CULLED);
+                        put(&mut img, i, to_srgb(color));
+                    }
+                }
+            }
+            Screen::Text { plan, bands, text } => {
+                for (band, texts) in plan.iter().zip(bands) {
+                    draw_band(&mut img, band, &texts[frame], text);
+                }
+            }
+        }
+        img
+    }
+
+    /// The subtitle on frame `frame`, or `""`.
+    pub fn subtitle(&self, frame: usize) -> &str {
+        self.subtitles.get(frame).map_or("", String::as_str)
+    }
+
+    /// Frame `frame` enlarged `scale` times, with its subtitle drawn on top.
+    pub fn frame(&self, frame: usize, scale: u32) -> RgbaImage {
+        let screen = self.screen(frame);
+        let scale = scale.max(1);
+        let mut img = if scale == 1 {
+            screen
+        } else {
+            let (w, h) = (self.width * scale, self.height * scale);
+            RgbaImage::from_fn(w, h, |x, y| *screen.get_pixel(x / scale, y / scale))
+        };
+        draw_subtitle(&mut img, self.subtitle(frame));
+        img
+    }
+
+    /// The clip frame shown at each tick of playback, in order: one pass
+    /// through the clock's order, or every pass a counted playback makes.
+    /// `true` alongside when playback repeats forever.
+    pub fn play_order(&self) -> (Vec<usize>, bool) {
+        let Some(playback) = self.playback else {
+            return ((0..self.frames).collect(), true);
+        };
+        let (ticks, forever) = match playback.played_frames(self.fps, self.frames) {
+            Some(n) => (n, false),
+            None => (playback.pass_frames(self.fps, self.frames), true),
+        };
+        let order = (0..ticks as i64).map(|t| playback.frame_at(t, self.fps, self.frames));
+        (order.collect(), forever)
+    }
+}
+
+/// A whole enlargement factor that brings `width` up to
+/// [`PREVIEW_MIN_WIDTH`], capped at [`MAX_AUTO_SCALE`].
+pub fn auto_scale(width: u32) -> u32 {
+    PREVIEW_MIN_WIDTH.div_ceil(width.max(1)).clamp(1, MAX_AUTO_SCALE)
+}
+
+/// Write `replay` to `path` in play order, enlarged `scale` times: an
+/// animated GIF for `.gif`, an APNG for `.png`/`.apng`, and a directory of
+/// numbered PNGs for a path with no extension. Returns the frames written.
+///
+/// A GIF holds 256 colours a frame, so it re-quantises the picture; the APNG
+/// and the PNG directory are exact.
+pub fn write(replay: &Replay, path: &Path, scale: u32) -> Result<usize, String> {
+    let (order, forever) = replay.play_order();
+    let ext = path.extension().map(|e| e.to_string_lossy().to_lowercase());
+    match ext.as_deref() {
+        Some("gif") => write_gif(replay, &order, forever, path, scale)?,
+        Some("png" | "apng") => write_apng(replay, &order, forever, path, scale)?,
+        None => write_png_dir(replay, &order, path, scale)?,
+        Some(other) => {
+            return Err(format!(
+                "cannot write a replay to a .{other} file -- use .gif, .png (APNG), or a \
+                 directory path with no extension for numbered PNG frames"
+            ));
+        }
+    }
+    Ok(order.len())
+}
+
+fn write_gif(
+    replay: &Replay,
+    order: &[usize],
+    forever: bool,
+    path: &Path,
+    scale: u32,
+) -> Result<(), String> {
+    use image::codecs::gif::{GifEncoder, Repeat};
+    let file = std::fs::File::create(path)
+        .map_err(|e| format!("could not create {}: {e}", path.display()))?;
+    let mut gif = GifEncoder::new(std::io::BufWriter::new(file));
+    let repeat = if forever { Repeat::Infinite } else { Repeat::Finite(0) };
+    gif.set_repeat(repeat).map_err(|e| format!("GIF: {e}"))?;
+    let delay =
+        image::Delay::from_numer_denom_ms(100_000, (replay.fps * 100.0).round().max(1.0) as u32);
+    for &f in order {
+        let frame = image::Frame::from_parts(replay.frame(f, scale), 0, 0, delay);
+        gif.encode_frame(frame).map_err(|e| format!("GIF: {e}"))?;
+    }
+    Ok(())
+}
+
+fn write_apng(
+    replay: &Replay,
+    order: &[usize],
+    forever: bool,
+    path: &Path,
+    scale: u32,
+) -> Result<(), String> {
+    let file = std::fs::File::create(path)
+        .map_err(|e| format!("could not create {}: {e}", path.display()))?;
+    let (w, h) = (replay.width * scale.max(1), replay.height * scale.max(1));
+    let mut png = png::Encoder::new(std::io::BufWriter::new(file), w, h);
+    png.set_color(png::ColorType::Rgba);
+    png.set_depth(png::BitDepth::Eight);
+    let apng = |e: png::EncodingError| format!("APNG: {e}");
+    // 0 plays is the format's "forever".
+    png.set_animated(order.len() as u32, if forever { 0 } else { 1 }).map_err(apng)?;
+    // A hundredth of a second is as fine as a delay needs to be, and keeps
+    // both halves of the fraction in range at any sane rate.
+    png.set_frame_delay(100, (replay.fps * 100.0).round().clamp(1.0, u16::MAX as f32) as u16)
+        .map_err(apng)?;
+    let mut writer = png.write_header().map_err(apng)?;
+    for &f in order {
+        writer.write_image_data(replay.frame(f, scale).as_raw()).map_err(apng)?;
+    }
+    writer.finish().map_err(apng)
+}
+
+fn write_png_dir(replay: &Replay, order: &[usize], dir: &Path, scale: u32) -> Result<(), String> {
+    std::fs::create_dir_all(dir)
+        .map_err(|e| format!("could not create {}: {e}", dir.display()))?;
+    for (t, &f) in order.iter().enumerate() {
+        let path = dir.join(format!("frame_{t:05}.png"));
+        replay
+            .frame(f, scale)
+            .save(&path)
+            .map_err(|e| format!("could not write {}: {e}", path.display()))?;
+    }
+    Ok(())
+}
+
+/// What `MakeColorHex` makes of a six-character slice: opaque, and black for
+/// anything that is not hex.
+fn parse_hex(s: &str) -> Rgba<u8> {
+    let byte = |i: usize| u8::from_str_radix(s.get(i..i + 2).unwrap_or(""), 16).unwrap_or(0);
+    Rgba([byte(0), byte(2), byte(4), 255])
+}
+
+/// A linear colour back to sRGB bytes, through the nearest entry of the
+/// table the packers converted with.
+fn to_srgb((r, g, b, a): LinearColor) -> Rgba<u8> {
+    let lut = crate::util::srgb_to_linear_f32_table();
+    let byte = |v: f32| {
+        let hi = lut.partition_point(|&x| x < v).min(255);
+        if hi > 0 && (v - lut[hi - 1]) <= (lut[hi] - v) { hi as u8 - 1 } else { hi as u8 }
+    };
+    Rgba([byte(r), byte(g), byte(b), (a.clamp(0.0, 1.0) * 255.0).round() as u8])
+}
+
+/// Draw one band's string for one frame at its rows of `img`.
+fn draw_band(img: &mut RgbaImage, band: &BandPlan, s: &str, text: &TextOptions) {
+    if text.mode == PixelMode::Color {
+        draw_color_runs(img, band, s, text);
+    } else {
+        draw_glyph_cells(img, band, s, text.mode);
+    }
+}
+
+/// Colour mode: `char_repeat` glyphs a pixel, a `<color>` tag wherever the
+/// colour changes, the colour carried from row to row within the band.
+fn draw_color_runs(img: &mut RgbaImage, band: &BandPlan, s: &str, text: &TextOptions) {
+    let repeat = text.char_repeat.max(1);
+    let mut color = GLYPH_WHITE;
+    for (r, line) in s.split('\n').enumerate().take(band.rows) {
+        let y = (band.start_row + r) as u32;
+        let (mut glyphs, mut rest) = (0usize, line);
+        while let Some(c) = rest.chars().next() {
+            if let Some(tag) = rest.strip_prefix(COLOR_TAG_OPEN)
+                && let Some((hex, after)) = tag.split_once(COLOR_TAG_CLOSE)
+            {
+                let Rgba([r, g, b, _]) = parse_hex(hex);
+                color = [r, g, b];
+                rest = after;
+                continue;
+            }
+            // A pixel's first glyph says which it is; its repeats follow.
+            let x = (glyphs / repeat) as u32;
+            if glyphs % repeat == 0 && c == text.fill_char && x < img.width() && y < img.height()
+            {
+                img.put_pixel(x, y, Rgba([color[0], color[1], color[2], 255]));
+            }
+            glyphs += 1;
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+}
+
+/// A monochrome mode: one sprite per cell, its dots white.
+fn draw_glyph_cells(img: &mut RgbaImage, band: &BandPlan, s: &str, mode: PixelMode) {
+    let (cw, ch) = mode.cell();
+    let end = (band.start_row + band.rows) as u32;
+    for (line, glyphs) in s.split('\n').enumerate() {
+        for (cx, glyph) in glyphs.chars().enumerate() {
+            let Some(bits) = mode.sprite_bits(glyph) else {
+                continue;
+            };
+            for dy in 0..ch {
+                for dx in 0..cw {
+                    let x = cx as u32 * cw + dx;
+                    let y = band.start_row as u32 + line as u32 * ch + dy;
+                    if bits & (1 << (dy * cw + dx)) != 0 && x < img.width() && y < end {
+                        img.put_pixel(x, y, Rgba([255, 255, 255, 255]));
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Draw `text` centred near the bottom of `img`, word-wrapped to its width.
+fn draw_subtitle(img: &mut RgbaImage, text: &str) {
+    if text.trim().is_empty() {
+        return;
+    }
+    let (w, h) = img.dimensions();
+    // Glyph pixels per font pixel: about twenty lines of text fit the height.
+    let px = (h / (20 * LINE_PITCH)).max(1);
+    let per_line = (w / (GLYPH_PITCH * px)).saturating_sub(1).max(1) as usize;
+    let lines = wrap(text, per_line);
+    let line_h = LINE_PITCH * px;
+    let bottom = h.saturating_sub(line_h / 2);
+    let top = bottom as i64 - (lines.len() as u32 * line_h) as i64;
+    for (n, line) in lines.iter().enumerate() {
+        let width = line.chars().count() as u32 * GLYPH_PITCH * px;
+        let x0 = (w as i64 - width as i64) / 2;
+        let y0 = top + (n as u32 * line_h) as i64;
+        // The outline first, as every glyph's dots pushed one font pixel
+        // each way, then the glyphs over it.
+        for (ox, oy, color) in OUTLINE_PASSES {
+            let dx = ox * px as i64;
+            let dy = oy * px as i64;
+            draw_line(img, line, x0 + dx, y0 + dy, px, color);
+        }
+    }
+}
+
+const OUTLINE_PASSES: [(i64, i64, Rgba<u8>); 5] = [
+    (-1, 0, Rgba([0, 0, 0, 255])),
+    (1, 0, Rgba([0, 0, 0, 255])),
+    (0, -1, Rgba([0, 0, 0, 255])),
+    (0, 1, Rgba([0, 0, 0, 255])),
+    (0, 0, Rgba([255, 255, 255, 255])),
+];
+
+fn draw_line(img: &mut RgbaImage, line: &str, x0: i64, y0: i64, px: u32, color: Rgba<u8>) {
+    let (w, h) = (img.width() as i64, img.height() as i64);
+    for (i, c) in line.chars().enumerate() {
+        let columns = glyph(c);
+        let gx = x0 + (i as u32 * GLYPH_PITCH * px) as i64;
+        for (col, bits) in columns.iter().enumerate() {
+            for row in 0..7 {
+                if bits & (1 << row) == 0 {
+                    continue;
+                }
+                for sy in 0..px as i64 {
+                    for sx in 0..px as i64 {
+                        let x = gx + col as i64 * px as i64 + sx;
+                        let y = y0 + row as i64 * px as i64 + sy;
+                        if (0..w).contains(&x) && (0..h).contains(&y) {
+                            img.put_pixel(x as u32, y as u32, color);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Greedy word wrap at `width` characters, breaking a word longer than a
+/// line wherever it overflows.
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    for para in text.lines() {
+        let mut line = String::new();
+        for word in para.split_whitespace() {
+            let mut word: Vec<char> = word.chars().collect();
+            let len = line.chars().count();
+            if len > 0 && len + 1 + word.len() > width {
+                lines.push(std::mem::take(&mut line));
+            }
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            while line.chars().count() + word.len() > width {
+                let room = width - line.chars().count();
+                line.extend(word.drain(..room));
+                lines.push(std::mem::take(&mut line));
+            }
+            line.extend(word);
+        }
+        if !line.is_empty() {
+            lines.push(line);
+        }
+    }
+    lines
+}
+
+/// Font pixels from one glyph's left edge to the next's.
+const GLYPH_PITCH: u32 = 6;
+
+/// Font pixels from one line's top to the next's.
+const LINE_PITCH: u32 = 9;
+
+/// `c`'s five columns, bit 0 the top row. Anything outside printable ASCII
+/// is drawn as `?`.
+fn glyph(c: char) -> &'static [u8; 5] {
+    let i = match c {
+        ' '..='~' => c as usize - ' ' as usize,
+        _ => '?' as usize - ' ' as usize,
+    };
+    &FONT[i]
+}
+
+/// The classic 5x7 character-LCD font, printable ASCII from the space.
+#[rustfmt::skip]
+const FONT: [[u8; 5]; 95] = [
+    [0x00, 0x00, 0x00, 0x00, 0x00], [0x00, 0x00, 0x5F, 0x00, 0x00], // space !
+    [0x00, 0x07, 0x00, 0x07, 0x00], [0x14, 0x7F, 0x14, 0x7F, 0x14], // " #
+    [0x24, 0x2A, 0x7F, 0x2A, 0x12], [0x23, 0x13, 0x08, 0x64, 0x62], // $ %
+    [0x36, 0x49, 0x55, 0x22, 0x50], [0x00, 0x05, 0x03, 0x00, 0x00], // & '
+    [0x00, 0x1C, 0x22, 0x41, 0x00], [0x00, 0x41, 0x22, 0x1C, 0x00], // ( )
+    [0x08, 0x2A, 0x1C, 0x2A, 0x08], [0x08, 0x08, 0x3E, 0x08, 0x08], // * +
+    [0x00, 0x50, 0x30, 0x00, 0x00], [0x08, 0x08, 0x08, 0x08, 0x08], // , -
+    [0x00, 0x60, 0x60, 0x00, 0x00], [0x20, 0x10, 0x08, 0x04, 0x02], // . /
+    [0x3E, 0x51, 0x49, 0x45, 0x3E], [0x00, 0x42, 0x7F, 0x40, 0x00], // 0 1
+    [0x42, 0x61, 0x51, 0x49, 0x46], [0x21, 0x41, 0x45, 0x4B, 0x31], // 2 3
+    [0x18, 0x14, 0x12, 0x7F, 0x10], [0x27, 0x45, 0x45, 0x45, 0x39], // 4 5
+    [0x3C, 0x4A, 0x49, 0x49, 0x30], [0x01, 0x71, 0x09, 0x05, 0x03], // 6 7
+    [0x36, 0x49, 0x49, 0x49, 0x36], [0x06, 0x49, 0x49, 0x29, 0x1E], // 8 9
+    [0x00, 0x36, 0x36, 0x00, 0x00], [0x00, 0x56, 0x36, 0x00, 0x00], // : ;
+    [0x08, 0x14, 0x22, 0x41, 0x00], [0x14, 0x14, 0x14, 0x14, 0x14], // < =
+    [0x00, 0x41, 0x22, 0x14, 0x08], [0x02, 0x01, 0x51, 0x09, 0x06], // > ?
+    [0x32, 0x49, 0x79, 0x41, 0x3E], [0x7E, 0x11, 0x11, 0x11, 0x7E], // @ A
+    [0x7F, 0x49, 0x49, 0x49, 0x36], [0x3E, 0x41, 0x41, 0x41, 0x22], // B C
+    [0x7F, 0x41, 0x41, 0x22, 0x1C], [0x7F, 0x49, 0x49, 0x49, 0x41], // D E
+    [0x7F, 0x09, 0x09, 0x01, 0x01], [0x3E, 0x41, 0x41, 0x51, 0x32], // F G
+    [0x7F, 0x08, 0x08, 0x08, 0x7F], [0x00, 0x41, 0x7F, 0x41, 0x00], // H I
+    [0x20, 0x40, 0x41, 0x3F, 0x01], [0x7F, 0x08, 0x14, 0x22, 0x41], // J K
+    [0x7F, 0x40, 0x40, 0x40, 0x40], [0x7F, 0x02, 0x04, 0x02, 0x7F], // L M
+    [0x7F, 0x04, 0x08, 0x10, 0x7F], [0x3E, 0x41, 0x41, 0x41, 0x3E], // N O
+    [0x7F, 0x09, 0x09, 0x09, 0x06], [0x3E, 0x41, 0x51, 0x21, 0x5E], // P Q
+    [0x7F, 0x09, 0x19, 0x29, 0x46], [0x46, 0x49, 0x49, 0x49, 0x31], // R S
+    [0x01, 0x01, 0x7F, 0x01, 0x01], [0x3F, 0x40, 0x40, 0x40, 0x3F], // T U
+    [0x1F, 0x20, 0x40, 0x20, 0x1F], [0x7F, 0x20, 0x18, 0x20, 0x7F], // V W
+    [0x63, 0x14, 0x08, 0x14, 0x63], [0x03, 0x04, 0x78, 0x04, 0x03], // X Y
+    [0x61, 0x51, 0x49, 0x45, 0x43], [0x00, 0x7F, 0x41, 0x41, 0x00], // Z [
+    [0x02, 0x04, 0x08, 0x10, 0x20], [0x00, 0x41, 0x41, 0x7F, 0x00], // \ ]
+    [0x04, 0x02, 0x01, 0x02, 0x04], [0x40, 0x40, 0x40, 0x40, 0x40], // ^ _
+    [0x00, 0x01, 0x02, 0x04, 0x00], [0x20, 0x54, 0x54, 0x54, 0x78], // ` a
+    [0x7F, 0x48, 0x44, 0x44, 0x38], [0x38, 0x44, 0x44, 0x44, 0x20], // b c
+    [0x38, 0x44, 0x44, 0x48, 0x7F], [0x38, 0x54, 0x54, 0x54, 0x18], // d e
+    [0x08, 0x7E, 0x09, 0x01, 0x02], [0x0C, 0x52, 0x52, 0x52, 0x3E], // f g
+    [0x7F, 0x08, 0x04, 0x04, 0x78], [0x00, 0x44, 0x7D, 0x40, 0x00], // h i
+    [0x20, 0x40, 0x44, 0x3D, 0x00], [0x7F, 0x10, 0x28, 0x44, 0x00], // j k
+    [0x00, 0x41, 0x7F, 0x40, 0x00], [0x7C, 0x04, 0x18, 0x04, 0x78], // l m
+    [0x7C, 0x08, 0x04, 0x04, 0x78], [0x38, 0x44, 0x44, 0x44, 0x38], // n o
+    [0x7C, 0x14, 0x14, 0x14, 0x08], [0x08, 0x14, 0x14, 0x18, 0x7C], // p q
+    [0x7C, 0x08, 0x04, 0x04, 0x08], [0x48, 0x54, 0x54, 0x54, 0x20], // r s
+    [0x04, 0x3F, 0x44, 0x40, 0x20], [0x3C, 0x40, 0x40, 0x20, 0x7C], // t u
+    [0x1C, 0x20, 0x40, 0x20, 0x1C], [0x3C, 0x40, 0x30, 0x40, 0x3C], // v w
+    [0x44, 0x28, 0x10, 0x28, 0x44], [0x0C, 0x50, 0x50, 0x50, 0x3C], // x y
+    [0x44, 0x64, 0x54, 0x4C, 0x44], [0x00, 0x08, 0x36, 0x41, 0x00], // z {
+    [0x00, 0x00, 0x7F, 0x00, 0x00], [0x00, 0x41, 0x36, 0x08, 0x00], // | }
+    [0x02, 0x01, 0x02, 0x04, 0x02],                                 // ~
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn linear_colours_go_back_to_the_bytes_they_came_from() {
+        let lut = crate::util::srgb_to_linear_f32_table();
+        for b in 0..=255u8 {
+            let v = lut[b as usize];
+            assert_eq!(to_srgb((v, v, v, 1.0)), Rgba([b, b, b, 255]));
+        }
+        assert_eq!(to_srgb(CULLED), CLEAR);
+    }
+
+    #[test]
+    fn colour_runs_carry_their_colour_and_skip_empties() {
+        let text = TextOptions { char_repeat: 2, ..AnimOptions::default().text };
+        let fill: String = std::iter::repeat_n(text.fill_char, 2).collect();
+        let empty: String = std::iter::repeat_n(text.empty_char, 2).collect();
+        let s = format!("<color=\"FF0000\">{fill}{empty}{fill}\n{fill}");
+        let mut img = RgbaImage::from_pixel(3, 2, CLEAR);
+        draw_color_runs(&mut img, &BandPlan { start_row: 0, rows: 2 }, &s, &text);
+        let red = Rgba([255, 0, 0, 255]);
+        assert_eq!(img.get_pixel(0, 0), &red);
+        assert_eq!(img.get_pixel(1, 0), &CLEAR);
+        assert_eq!(img.get_pixel(2, 0), &red);
+        assert_eq!(img.get_pixel(0, 1), &red, "the colour carries to the next row");
+    }
+
+    #[test]
+    fn glyph_cells_draw_the_sprite_their_bits_name() {
+        let mode = PixelMode::Blocks;
+        // Bits 0 and 3: the 2x2 cell's top left and bottom right.
+        assert_eq!(mode.sprite_bits('\u{259A}'), Some(0b1001));
+        assert_eq!(mode.sprite_bits('x'), None);
+        let mut img = RgbaImage::from_pixel(2, 2, CLEAR);
+        draw_glyph_cells(&mut img, &BandPlan { start_row: 0, rows: 2 }, "\u{259A}", mode);
+        let on: Vec<bool> = img.pixels().map(|p| p.0[3] == 255).collect();
+        assert_eq!(on, vec![true, false, false, true]);
+    }
+
+    #[test]
+    fn subtitles_wrap_at_words_and_break_long_ones() {
+        assert_eq!(wrap("one two three", 7), vec!["one two", "three"]);
+        assert_eq!(wrap("abcdefghij", 4), vec!["abcd", "efgh", "ij"]);
+        assert_eq!(wrap("top\nbottom", 20), vec!["top", "bottom"]);
+    }
+}
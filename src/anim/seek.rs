@@ -0,0 +1,748 @@
+//! Seek and skip controls: buttons that move the picture to another point in
+//! the clip, beside the Pause/Restart/Resume row [`super::controls`] builds.
+//!
+//! Four kinds, each optional ([`SeekControls`]): skip back and forward by a
+//! fixed time, jump to the previous or next chapter, a progress bar of buttons
+//! each jumping to its own fraction of the clip, and a playlist's
+//! Previous/Next Clip and per-clip buttons ([`super::playlist`]).
+//!
+//! # How a seek moves the clock
+//!
+//! The timer cannot be set to a time, only paused, resumed and restarted. So
+//! nothing here touches it: a seekable clock ([`super::clock::build_clock`])
+//! adds a frame offset to its index before the wrap, and every button changes
+//! that offset. The offset is one `Var`, and every button ends in a
+//! `Var_Increment` of it:
+//!
+//! * a skip increments it by a constant, `±skip_frames`;
+//! * a jump to frame `T` increments it by `T - index`. Since `index` is
+//!   already `(frames + offset) % frame_count`, the new index is `T` whatever
+//!   the offset was.
+//!
+//! Restart also clears the offset (a `Var_Set` to 0 off the clock's own
+//! `Restart` pin), or a restart after a seek would start from wherever the
+//! last seek left the picture.
+//!
+//! A button fires on its press only: a `ChangeDetectorExec` pulses on both
+//! edges of the button's `bHeld`, and a `Branch` on `bHeld` itself passes the
+//! pulse where it became true.
+//!
+//! # Chapters
+//!
+//! "Next" is the first chapter after the current frame, wrapping to the first
+//! chapter; "previous" is the last one starting at least a second before the
+//! current frame, wrapping to the last. The second is the usual player's
+//! grace, so a press just after a chapter starts goes to the one before it
+//! instead of back to its own start. Each is a chain of one compare and one
+//! `Select` per chapter, read whenever the button fires.
+//!
+//! A playlist's clips are chapters too, for Previous/Next Clip. With its
+//! auto-advance off, one more chain turns the offset back whenever the index
+//! lands on a clip's loop point ([`super::playlist::Playlist::loop_points`]):
+//! a `CompareNotEqual` and a `Select` per clip, picking 0 everywhere else and
+//! feeding a `Var_Increment` that fires on every frame change.
+//!
+//! # Marks
+//!
+//! A delta screen's pixels and a MIDI piece's voices follow the clock with a
+//! playhead each, which can only find its place again on a frame known when
+//! the chip is built -- a [landing](super::landing). Chapters and the bar jump
+//! to such frames already; a skip from wherever the picture is does not. So
+//! a plan [built on marks](SeekPlan::on_marks) snaps its skips to every
+//! `skip_frames`th frame instead: forward to the next mark, wrapping to frame
+//! 0 past the last, and back to the last mark at least a second behind, like
+//! "previous chapter", stopping at frame 0. Each works its mark out with the
+//! same truncation as the landing graph ([`super::landing::add_mark`]) and
+//! feeds it to the button as a jump to a frame.
+//!
+//! # What is unverified in game
+//!
+//! That `Var_Increment.Value` can be wired (every other increment in this
+//! crate inlines it), and that a `Var` read by the clock's `Add` on the tick
+//! it is incremented shows the new frame then or one tick later -- either is
+//! fine for a button.
+use super::bricks::{BRANCH, CHANGE_DETECTOR, COMPARE_GE, SELECT, SUBTRACT};
+use super::chip::{Chip, add_input_pin, pin_source};
+use super::clock::{Clock, gate};
+use super::delta_bricks::{COMPARE_LESS, VAR, VAR_INCREMENT, VAR_SET};
+use super::clock::{MAX, MULTIPLY};
+use super::landing::add_mark;
+use super::layout::{CELL, GATE_HALF, STAGE_BASE_Z};
+use super::playlist::Playlist;
+use crate::audio::speakers::COMPARE_NE;
+use brdb::{AsBrdbValue, Position, WirePort, World, schema::WireVariant};
+
+/// Which seek buttons a render gets. All off by default, which builds the
+/// plain clock and no seek graph at all.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SeekControls {
+    /// Seconds a skip button moves the picture; `0` builds no skip buttons.
+    pub skip_s: f32,
+    /// Chapter starts in SOURCE seconds, like a subtitle track's cues, so
+    /// `--start` is honoured ([`SeekControls::plan`] moves them into the
+    /// clip). Empty builds no chapter buttons.
+    pub chapters: Vec<f64>,
+    /// Buttons in the progress bar; `0` builds none.
+    pub bar: usize,
+    /// The clips of a playlist render, for its buttons; `None` for one clip.
+    pub playlist: Option<Playlist>,
+}
+
+impl SeekControls {
+    /// No button at all: the clock is built without its offset.
+    pub fn is_empty(&self) -> bool {
+        self.skip_s <= 0.0 && self.chapters.is_empty() && self.bar == 0 && self.playlist.is_none()
+    }
+
+    /// Resolve to frames for a clip of `frame_count` frames at `fps` whose
+    /// frame 0 is at source time `source_start_s`.
+    ///
+    /// Chapters outside the clip are dropped and two landing on one frame are
+    /// one, so a render can have fewer chapters than were asked for -- or
+    /// none, and then no chapter buttons. A skip is at least one frame.
+    pub fn plan(&self, fps: f32, frame_count: usize, source_start_s: f64) -> SeekPlan {
+        let fps = fps as f64;
+        let skip_frames =
+            if self.skip_s > 0.0 { ((self.skip_s as f64 * fps).round() as i64).max(1) } else { 0 };
+        let mut chapters: Vec<i64> = self
+            .chapters
+            .iter()
+            .map(|&t| ((t - source_start_s) * fps).round() as i64)
+            .filter(|&f| (0..frame_count as i64).contains(&f))
+            .collect();
+        chapters.sort_unstable();
+        chapters.dedup();
+        let bar = (0..self.bar).map(|k| (k * frame_count / self.bar) as i64).collect();
+        // A playlist's timeline is the clip, so there is no start to shift by;
+        // a clip cut off by the frame cap loses its button like a chapter.
+        let inside = |frame: &usize| *frame < frame_count;
+        let (clips, loops) = match &self.playlist {
+            None => (Vec::new(), Vec::new()),
+            Some(p) => (
+                p.clips
+                    .iter()
+                    .zip(p.starts())
+                    .filter(|(_, s)| inside(s))
+                    .map(|(c, s)| (c.name.clone(), s as i64))
+                    .collect(),
+                p.loop_points()
+                    .into_iter()
+                    .filter(|(copy, _)| inside(copy))
+                    .map(|(copy, start)| (copy as i64, start as i64 - copy as i64))
+                    .collect(),
+            ),
+        };
+        SeekPlan {
+            skip_frames,
+            chapters,
+            bar,
+            clips,
+            loops,
+            grace: (fps.round() as i64).max(1),
+            fps,
+            frame_count,
+            marks: false,
+        }
+    }
+}
+
+/// [`SeekControls`] in frames, for one clip. See [`SeekControls::plan`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct SeekPlan {
+    /// Frames a skip moves; `0` for no skip buttons.
+    pub skip_frames: i64,
+    /// Chapter starts, ascending, each inside the clip.
+    pub chapters: Vec<i64>,
+    /// The frame each progress-bar button jumps to, in bar order.
+    pub bar: Vec<i64>,
+    /// Each playlist clip's name and first frame, in play order.
+    pub clips: Vec<(String, i64)>,
+    /// Each looping playlist clip's loop point and how far the offset moves
+    /// from it, back to the clip's start.
+    pub loops: Vec<(i64, i64)>,
+    /// How far into a chapter "previous" goes back to that chapter's own
+    /// start rather than the one before: one second of frames.
+    pub grace: i64,
+    /// The clip's fps, for the bar buttons' time labels.
+    fps: f64,
+    frame_count: usize,
+    /// Skips go to a mark rather than by `skip_frames` (see the module doc).
+    marks: bool,
+}
+
+impl SeekPlan {
+    /// This plan with its skips snapped to marks, for a clock a playhead
+    /// follows. See the module doc.
+    pub fn on_marks(self) -> SeekPlan {
+        SeekPlan { marks: true, ..self }
+    }
+
+    /// The frames between marks, when the skips go to them.
+    pub fn mark_step(&self) -> Option<usize> {
+        (self.marks && self.skip_frames > 0).then_some(self.skip_frames as usize)
+    }
+
+    /// Every frame a chapter, clip or bar button jumps to, in that order,
+    /// repeats kept. A skip's land on the marks, or anywhere.
+    pub fn landings(&self) -> Vec<usize> {
+        let clips = self.clips.iter().map(|&(_, start)| start);
+        self.chapters.iter().copied().chain(clips).chain(self.bar.iter().copied()).map(|f| f as usize).collect()
+    }
+}
+
+/// What the seek graph hangs off: the seekable clock's wrapped index, its
+/// offset port ([`super::clock::Clock::offset`]) and its `Restart` pin.
+pub struct SeekHook {
+    pub frame_index: WirePort,
+    pub offset: WirePort,
+    pub restart_pin: usize,
+}
+
+impl SeekHook {
+    /// The hook on `clock`, or `None` if it was not built seekable.
+    pub fn of(clock: &Clock) -> Option<SeekHook> {
+        Some(SeekHook {
+            frame_index: clock.frame_index.clone(),
+            offset: clock.offset.clone()?,
+            restart_pin: clock.restart_pin,
+        })
+    }
+}
+
+/// What a seek graph for `controls` adds to its chip, in `(gates, wires)`,
+/// counting the clock's own `Add` and its extra wire. Pins are not gates.
+///
+/// Takes the controls rather than a [`SeekPlan`] so an estimate can use it
+/// before the clip is decoded: every listed chapter is counted, so it is an
+/// upper bound once chapters outside the clip are dropped.
+pub fn graph_cost(controls: &SeekControls) -> (usize, usize) {
+    cost_of(controls, false)
+}
+
+/// [`graph_cost`] for a plan built [`SeekPlan::on_marks`]: each skip works
+/// its mark out first and jumps to it, like a bar button.
+pub fn marked_graph_cost(controls: &SeekControls) -> (usize, usize) {
+    cost_of(controls, true)
+}
+
+fn cost_of(controls: &SeekControls, marks: bool) -> (usize, usize) {
+    if controls.is_empty() {
+        return (0, 0);
+    }
+    // Two buttons of 4 gates and 8 wires, and two chains of a compare and a
+    // select per point, the first select's fallback inlined.
+    let previous_and_next = |k: usize| match k {
+        0 => (0, 0),
+        k => (2 * 4 + 2 * 2 * k, 2 * 8 + 2 * (3 * k - 1)),
+    };
+    // The clock's Add (1 gate, 1 wire), the offset Var and its read (1, 1),
+    // and the restart reset (3, 5).
+    let (mut gates, mut wires) = (5, 7);
+    if controls.skip_s > 0.0 && marks {
+        // Back: the mark (3, 3), scaled and held at 0 (2, 2); ahead: the
+        // mark, scaled and wrapped (3, 4); and two jump buttons.
+        (gates, wires) = (gates + 5 + 6 + 2 * 4, wires + 5 + 7 + 2 * 8);
+    } else if controls.skip_s > 0.0 {
+        (gates, wires) = (gates + 2 * 3, wires + 2 * 5);
+    }
+    let (g, w) = previous_and_next(controls.chapters.len());
+    (gates, wires) = (gates + g, wires + w);
+    let mut jumps = controls.bar;
+    if let Some(playlist) = &controls.playlist {
+        let n = playlist.clips.len();
+        let (g, w) = previous_and_next(n);
+        (gates, wires) = (gates + g, wires + w);
+        jumps += n;
+        if !playlist.auto_advance {
+            // The detector and the increment (4 wires between them), and a
+            // compare and a select per clip, the first select's fallback
+            // inlined.
+            (gates, wires) = (gates + 2 + 2 * n, wires + 4 + 3 * n - 1);
+        }
+    }
+    (gates + 4 * jumps, wires + 7 * jumps)
+}
+
+/// The seek buttons `controls` asks for, an upper bound in the same sense as
+/// [`graph_cost`]: one main-grid brick and one wire each.
+pub fn button_count(controls: &SeekControls) -> usize {
+    let skip = if controls.skip_s > 0.0 { 2 } else { 0 };
+    let chapters = if controls.chapters.is_empty() { 0 } else { 2 };
+    let clips = controls.playlist.as_ref().map_or(0, |p| 2 + p.clips.len());
+    skip + chapters + clips + controls.bar
+}
+
+/// Build the seek graph for `plan` into `chip`, returning each button's label
+/// and the input pin its `bHeld` must drive -- hand them to
+/// [`super::controls::add_seek_buttons`].
+///
+/// Placed like the subtitle gates: clear rows beyond everything already in
+/// the chip, one per button, so it works over every renderer's lattice. Call
+/// after everything else in the chip is placed, subtitles included.
+pub fn add_seek_graph(
+    world: &mut World,
+    chip: &mut Chip,
+    hook: &SeekHook,
+    plan: &SeekPlan,
+) -> Vec<(String, usize)> {
+    let rows = Rows {
+        base_x: chip.placed().iter().map(|(p, h)| p.x + h.x).max().unwrap_or(0) + CELL,
+    };
+
+    // Row 0: the offset itself, and Restart clearing it.
+    let var = gate(chip, "B_1x1_Gate_Variable", VAR, rows.at(0, 0), vec![("Value", int(0))]);
+    world.add_wire_connection(WirePort::new(var, VAR, "Value"), hook.offset.clone());
+    let on_restart = pressed(world, chip, hook.restart_pin, &rows, 0);
+    let clear =
+        gate(chip, "B_1x1_Gate_Exec_Var_Set", VAR_SET, rows.at(0, 3), vec![("Value", int(0))]);
+    world.add_wire_connection(
+        WirePort::new(var, VAR, "VarRef"),
+        WirePort::new(clear, VAR_SET, "VarRef"),
+    );
+    world.add_wire_connection(on_restart, WirePort::new(clear, VAR_SET, "Exec"));
+
+    // Chains and the loop check go on rows of their own below the buttons.
+    let mut free_row = 1 + plan.button_count() as i32;
+    let mut jumps = Vec::new();
+    if plan.skip_frames > 0 {
+        let secs = plan.skip_frames as f64 / plan.fps;
+        let (back, ahead) = match plan.mark_step() {
+            None => (Jump::By(-plan.skip_frames), Jump::By(plan.skip_frames)),
+            Some(step) => {
+                let back = mark_back(world, chip, hook, &rows, free_row, step, plan.grace);
+                let ahead = mark_ahead(world, chip, hook, &rows, free_row + 1, step, plan.frame_count);
+                free_row += 2;
+                (Jump::ToPort(back), Jump::ToPort(ahead))
+            }
+        };
+        jumps.push((format!("-{}s", short(secs)), back));
+        jumps.push((format!("+{}s", short(secs)), ahead));
+    }
+    let clip_starts: Vec<i64> = plan.clips.iter().map(|&(_, start)| start).collect();
+    for (points, what) in [(&plan.chapters, "Chapter"), (&clip_starts, "Clip")] {
+        if points.is_empty() {
+            continue;
+        }
+        let prev = chapter_chain(world, chip, hook, &rows, free_row, points, plan.grace, true);
+        let next = chapter_chain(world, chip, hook, &rows, free_row + 1, points, 0, false);
+        free_row += 2;
+        jumps.push((format!("Prev {what}"), Jump::ToPort(prev)));
+        jumps.push((format!("Next {what}"), Jump::ToPort(next)));
+    }
+    for (name, start) in &plan.clips {
+        jumps.push((clip_label(name), Jump::ToFrame(*start)));
+    }
+    for &frame in &plan.bar {
+        jumps.push((clock_label(frame as f64 / plan.fps), Jump::ToFrame(frame)));
+    }
+    debug_assert_eq!(jumps.len(), plan.button_count());
+    if !plan.loops.is_empty() {
+        loop_clips(world, chip, hook, var, &rows, free_row, &plan.loops);
+    }
+
+    let mut buttons = Vec::with_capacity(jumps.len());
+    for (i, (label, jump)) in jumps.into_iter().enumerate() {
+        let row = i as i32 + 1;
+        let pin = add_input_pin(chip, &label, rows.at(row, 0));
+        let fire = pressed(world, chip, pin, &rows, row);
+        let by = if let Jump::By(n) = jump { n } else { 0 };
+        let inc = gate(
+            chip,
+            "B_1x1_Gate_Exec_Var_Increment",
+            VAR_INCREMENT,
+            rows.at(row, 4),
+            vec![("Value", int(by))],
+        );
+        world.add_wire_connection(
+            WirePort::new(var, VAR, "VarRef"),
+            WirePort::new(inc, VAR_INCREMENT, "VarRef"),
+        );
+        world.add_wire_connection(fire, WirePort::new(inc, VAR_INCREMENT, "Exec"));
+        // A jump to a frame is an increment by how far away it is.
+        let target = match jump {
+            Jump::By(_) => None,
+            Jump::ToFrame(frame) => Some((vec![("InputA", int(frame))], None)),
+            Jump::ToPort(port) => Some((vec![], Some(port))),
+        };
+        if let Some((data, port)) = target {
+            let to = gate(chip, "B_1x1_Gate_Expr_MathSubtract", SUBTRACT, rows.at(row, 3), data);
+            if let Some(port) = port {
+                world.add_wire_connection(port, WirePort::new(to, SUBTRACT, "InputA"));
+            }
+            world.add_wire_connection(
+                hook.frame_index.clone(),
+                WirePort::new(to, SUBTRACT, "InputB"),
+            );
+            world.add_wire_connection(
+                WirePort::new(to, SUBTRACT, "Output"),
+                WirePort::new(inc, VAR_INCREMENT, "Value"),
+            );
+        }
+        buttons.push((label, pin));
+    }
+    buttons
+}
+
+impl SeekPlan {
+    /// The buttons this plan builds; [`button_count`] before chapters and
+    /// clips outside the clip are dropped.
+    fn button_count(&self) -> usize {
+        let pair = |present: bool| if present { 2 } else { 0 };
+        pair(self.skip_frames > 0)
+            + pair(!self.chapters.is_empty())
+            + pair(!self.clips.is_empty())
+            + self.clips.len()
+            + self.bar.len()
+    }
+}
+
+/// What one button does to the offset.
+enum Jump {
+    By(i64),
+    ToFrame(i64),
+    ToPort(WirePort),
+}
+
+/// The seek graph's rows: along x past the rest of the chip, slots along y.
+struct Rows {
+    base_x: i32,
+}
+
+impl Rows {
+    fn at(&self, row: i32, slot: i32) -> Position {
+        Position {
+            x: self.base_x + row * CELL + GATE_HALF.x,
+            y: slot * CELL + GATE_HALF.y,
+            z: STAGE_BASE_Z + GATE_HALF.z,
+        }
+    }
+}
+
+fn int(v: i64) -> Box<dyn AsBrdbValue> {
+    Box::new(WireVariant::Int(v))
+}
+
+/// An exec pulse when the input pin `pin` goes true -- the press, not the
+/// release -- from a detector and a branch in slots 1 and 2 of `row`.
+fn pressed(world: &mut World, chip: &mut Chip, pin: usize, rows: &Rows, row: i32) -> WirePort {
+    let detector =
+        gate(chip, "B_1x1_Gate_Expr_ChangeDetectorExec", CHANGE_DETECTOR, rows.at(row, 1), vec![]);
+    let branch = gate(chip, "B_1x1_Gate_Exec_Branch", BRANCH, rows.at(row, 2), vec![]);
+    world.add_wire_connection(
+        pin_source(pin, true),
+        WirePort::new(detector, CHANGE_DETECTOR, "Input"),
+    );
+    world.add_wire_connection(pin_source(pin, true), WirePort::new(branch, BRANCH, "bCond"));
+    world.add_wire_connection(
+        WirePort::new(detector, CHANGE_DETECTOR, "OnChanged"),
+        WirePort::new(branch, BRANCH, "Exec"),
+    );
+    WirePort::new(branch, BRANCH, "ExecOutA")
+}
+
+/// One chain on `row` over `points` (chapters or clip starts, ascending),
+/// returning the port carrying its choice. Each link replaces the running
+/// choice with its point when its compare holds; the first link's fallback is
+/// the wrap-around point, inlined.
+///
+/// `back` builds "previous": `index >= point + grace` picks the point,
+/// ascending, so the last one far enough behind wins; the fallback is the
+/// last point. Otherwise "next": `index < point`, descending, so the first one
+/// ahead wins; the fallback is the first.
+#[allow(clippy::too_many_arguments)]
+fn chapter_chain(
+    world: &mut World,
+    chip: &mut Chip,
+    hook: &SeekHook,
+    rows: &Rows,
+    row: i32,
+    points: &[i64],
+    grace: i64,
+    back: bool,
+) -> WirePort {
+    let (order, fallback): (Vec<i64>, i64) = if back {
+        (points.to_vec(), points[points.len() - 1])
+    } else {
+        (points.iter().rev().copied().collect(), points[0])
+    };
+    let mut choice: Option<WirePort> = None;
+    for (i, &chapter) in order.iter().enumerate() {
+        let slot = 2 * i as i32;
+        let (holds, port) = if back {
+            let ge = gate(
+                chip,
+                "B_1x1_Gate_Expr_CompareGreaterOrEqual",
+                COMPARE_GE,
+                rows.at(row, slot),
+                vec![("InputB", int(chapter + grace))],
+            );
+            (ge, WirePort::new(ge, COMPARE_GE, "bOutput"))
+        } else {
+            let lt = gate(
+                chip,
+                "B_1x1_Gate_Expr_CompareLess",
+                COMPARE_LESS,
+                rows.at(row, slot),
+                vec![("InputB", int(chapter))],
+            );
+            (lt, WirePort::new(lt, COMPARE_LESS, "bOutput"))
+        };
+        let class = if back { COMPARE_GE } else { COMPARE_LESS };
+        world.add_wire_connection(hook.frame_index.clone(), WirePort::new(holds, class, "InputA"));
+        let mut data = vec![("InputB", int(chapter))];
+        if choice.is_none() {
+            data.push(("InputA", int(fallback)));
+        }
+        let select = gate(chip, "B_1x1_Gate_Expr_Select", SELECT, rows.at(row, slot + 1), data);
+        world.add_wire_connection(port, WirePort::new(select, SELECT, "bSelectB"));
+        if let Some(previous) = choice {
+            world.add_wire_connection(previous, WirePort::new(select, SELECT, "InputA"));
+        }
+        choice = Some(WirePort::new(select, SELECT, "Output"));
+    }
+    choice.expect("a chain is only built over at least one point")
+}
+
+/// On `row`, the last mark at least `grace` frames behind the index, or
+/// frame 0 if there is none.
+fn mark_back(
+    world: &mut World,
+    chip: &mut Chip,
+    hook: &SeekHook,
+    rows: &Rows,
+    row: i32,
+    step: usize,
+    grace: i64,
+) -> WirePort {
+    let mut slot = 0;
+    let mut next = || {
+        slot += 1;
+        rows.at(row, slot - 1)
+    };
+    let bias = -(grace as f64) / step as f64;
+    let mark = add_mark(world, chip, &hook.frame_index, step, bias, &mut next);
+    let frame = gate(chip, "B_1x1_Gate_Expr_MathMultiply", MULTIPLY, next(), vec![(
+        "InputB",
+        int(step as i64),
+    )]);
+    world.add_wire_connection(mark, WirePort::new(frame, MULTIPLY, "InputA"));
+    let held = gate(chip, "B_1x1_Gate_Expr_MathMax", MAX, next(), vec![("InputB", int(0))]);
+    world.add_wire_connection(WirePort::new(frame, MULTIPLY, "Output"), WirePort::new(held, MAX, "InputA"));
+    WirePort::new(held, MAX, "Output")
+}
+
+/// On `row`, the first mark after the index, or frame 0 past the last one.
+fn mark_ahead(
+    world: &mut World,
+    chip: &mut Chip,
+    hook: &SeekHook,
+    rows: &Rows,
+    row: i32,
+    step: usize,
+    frame_count: usize,
+) -> WirePort {
+    let mut slot = 0;
+    let mut next = || {
+        slot += 1;
+        rows.at(row, slot - 1)
+    };
+    let mark = add_mark(world, chip, &hook.frame_index, step, 1.0, &mut next);
+    let frame = gate(chip, "B_1x1_Gate_Expr_MathMultiply", MULTIPLY, next(), vec![(
+        "InputB",
+        int(step as i64),
+    )]);
+    world.add_wire_connection(mark, WirePort::new(frame, MULTIPLY, "InputA"));
+    let frame = WirePort::new(frame, MULTIPLY, "Output");
+    let inside = gate(chip, "B_1x1_Gate_Expr_CompareLess", COMPARE_LESS, next(), vec![(
+        "InputB",
+        int(frame_count as i64),
+    )]);
+    world.add_wire_connection(frame.clone(), WirePort::new(inside, COMPARE_LESS, "InputA"));
+    let wrapped = gate(chip, "B_1x1_Gate_Expr_Select", SELECT, next(), vec![("InputA", int(0))]);
+    world.add_wire_connection(
+        WirePort::new(inside, COMPARE_LESS, "bOutput"),
+        WirePort::new(wrapped, SELECT, "bSelectB"),
+    );
+    world.add_wire_connection(frame, WirePort::new(wrapped, SELECT, "InputB"));
+    WirePort::new(wrapped, SELECT, "Output")
+}
+
+/// The loop check on `row`: on every frame change, increment the offset by
+/// the move of whichever loop point the index is on, or by 0.
+///
+/// A `CompareNotEqual` per point, since the index is on at most one: while it
+/// holds, the point's `Select` passes the running choice on.
+fn loop_clips(
+    world: &mut World,
+    chip: &mut Chip,
+    hook: &SeekHook,
+    var: usize,
+    rows: &Rows,
+    row: i32,
+    loops: &[(i64, i64)],
+) {
+    let detector =
+        gate(chip, "B_1x1_Gate_Expr_ChangeDetectorExec", CHANGE_DETECTOR, rows.at(row, 0), vec![]);
+    world.add_wire_connection(
+        hook.frame_index.clone(),
+        WirePort::new(detector, CHANGE_DETECTOR, "Input"),
+    );
+    let mut choice: Option<WirePort> = None;
+    for (i, &(point, by)) in loops.iter().enumerate() {
+        let slot = 1 + 2 * i as i32;
+        let away = gate(
+            chip,
+            "B_1x1_Gate_Expr_CompareNotEqual",
+            COMPARE_NE,
+            rows.at(row, slot),
+            vec![("InputB", int(point))],
+        );
+        world.add_wire_connection(
+            hook.frame_index.clone(),
+            WirePort::new(away, COMPARE_NE, "InputA"),
+        );
+        let mut data = vec![("InputA", int(by))];
+        if choice.is_none() {
+            data.push(("InputB", int(0)));
+        }
+        let select = gate(chip, "B_1x1_Gate_Expr_Select", SELECT, rows.at(row, slot + 1), data);
+        world.add_wire_connection(
+            WirePort::new(away, COMPARE_NE, "bOutput"),
+            WirePort::new(select, SELECT, "bSelectB"),
+        );
+        if let Some(previous) = choice {
+            world.add_wire_connection(previous, WirePort::new(select, SELECT, "InputB"));
+        }
+        choice = Some(WirePort::new(select, SELECT, "Output"));
+    }
+    let inc = gate(
+        chip,
+        "B_1x1_Gate_Exec_Var_Increment",
+        VAR_INCREMENT,
+        rows.at(row, 1 + 2 * loops.len() as i32),
+        vec![],
+    );
+    world.add_wire_connection(
+        WirePort::new(var, VAR, "VarRef"),
+        WirePort::new(inc, VAR_INCREMENT, "VarRef"),
+    );
+    world.add_wire_connection(
+        WirePort::new(detector, CHANGE_DETECTOR, "OnChanged"),
+        WirePort::new(inc, VAR_INCREMENT, "Exec"),
+    );
+    world.add_wire_connection(
+        choice.expect("the check is only built over at least one loop point"),
+        WirePort::new(inc, VAR_INCREMENT, "Value"),
+    );
+}
+
+/// A clip's name as its button says it: at most 20 characters.
+fn clip_label(name: &str) -> String {
+    if name.chars().count() <= 20 {
+        return name.to_string();
+    }
+    let kept: String = name.chars().take(19).collect();
+    format!("{}~", kept.trim_end())
+}
+
+/// `secs` without a trailing `.0`: "10", "2.5".
+fn short(secs: f64) -> String {
+    let rounded = (secs * 10.0).round() / 10.0;
+    if rounded.fract() == 0.0 { format!("{rounded:.0}") } else { format!("{rounded:.1}") }
+}
+
+/// `secs` as a player shows it: "0:00", "1:30", "1:02:05".
+fn clock_label(secs: f64) -> String {
+    let total = secs.floor() as u64;
+    let (h, m, s) = (total / 3600, total / 60 % 60, total % 60);
+    if h > 0 { format!("{h}:{m:02}:{s:02}") } else { format!("{m}:{s:02}") }
+}
+
+/// Parse a `--chapters` list: comma-separated times, each seconds (`90`,
+/// `90.5`) or `m:ss` / `h:mm:ss` with an optional fraction (`1:30`,
+/// `1:02:05.5`).
+pub fn parse_times(list: &str) -> Result<Vec<f64>, String> {
+    list.split(',')
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .map(|t| {
+            let mut secs = 0.0;
+            for part in t.split(':') {
+                let v: f64 = part.parse().map_err(|_| format!("'{t}' is not a time"))?;
+                if !v.is_finite() || v < 0.0 {
+                    return Err(format!("'{t}' is not a time"));
+                }
+                secs = secs * 60.0 + v;
+            }
+            Ok(secs)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plan_moves_chapters_into_the_clip_and_drops_the_rest() {
+        let chapters = vec![9.0, 1.0, 1.04, 2.5, 3.0];
+        let controls = SeekControls { skip_s: 10.0, chapters, bar: 4, playlist: None };
+        // A 20-frame clip at 10 fps starting 1 s into the source.
+        let plan = controls.plan(10.0, 20, 1.0);
+        assert_eq!(plan.skip_frames, 100);
+        // 9 s and 3 s are past its end, and 1.04 s lands on 1 s's frame.
+        assert_eq!(plan.chapters, vec![0, 15]);
+        assert_eq!(plan.bar, vec![0, 5, 10, 15]);
+        assert_eq!(plan.grace, 10);
+    }
+
+    /// A playlist's clips are placed where the playlist says they start, and
+    /// a looping clip's loop point moves the offset back to that start.
+    #[test]
+    fn plan_places_a_playlists_clips_and_loop_points() {
+        use crate::anim::playlist::{Playlist, PlaylistClip};
+        let clip = |name: &str, frames| PlaylistClip { name: name.to_string(), frames };
+        let playlist = Playlist {
+            clips: vec![clip("intro", 3), clip("a much longer clip name", 4)],
+            auto_advance: false,
+        };
+        let controls = SeekControls { playlist: Some(playlist), ..SeekControls::default() };
+        let plan = controls.plan(10.0, 9, 0.0);
+        assert_eq!(
+            plan.clips,
+            vec![("intro".to_string(), 0), ("a much longer clip name".to_string(), 4)]
+        );
+        assert_eq!(plan.loops, vec![(3, -3), (8, -4)]);
+        assert_eq!(plan.button_count(), button_count(&controls));
+        assert_eq!(clip_label(&plan.clips[1].0), "a much longer clip~");
+
+        // Cut short by the frame cap, the second clip and its loop are gone.
+        let plan = controls.plan(10.0, 4, 0.0);
+        assert_eq!(plan.clips.len(), 1);
+        assert_eq!(plan.loops, vec![(3, -3)]);
+    }
+
+    #[test]
+    fn a_skip_is_at_least_one_frame() {
+        let plan = SeekControls { skip_s: 0.01, ..SeekControls::default() }.plan(10.0, 5, 0.0);
+        assert_eq!(plan.skip_frames, 1);
+        assert!(SeekControls::default().is_empty());
+    }
+
+    #[test]
+    fn parse_times_reads_seconds_and_clock_times() {
+        assert_eq!(parse_times("0, 90.5,1:30, 1:02:05").unwrap(), vec![0.0, 90.5, 90.0, 3725.0]);
+        assert_eq!(parse_times("").unwrap(), Vec::<f64>::new());
+        assert!(parse_times("1:xx").is_err());
+        assert!(parse_times("-5").is_err());
+    }
+
+    #[test]
+    fn labels_read_like_a_player() {
+        assert_eq!(short(10.0), "10");
+        assert_eq!(short(2.5), "2.5");
+        assert_eq!(clock_label(0.0), "0:00");
+        assert_eq!(clock_label(90.4), "1:30");
+        assert_eq!(clock_label(3725.0), "1:02:05");
+    }
+}
@@ -0,0 +1,115 @@
+//! A screen and its soundtrack in one world, on one clock.
+//!
+//! Built as two chips rather than one: the screen is rendered exactly as
+//! [`AnimMode::build`] renders it under `external_clock`, and
+//! [`speakers::add_soundtrack`] puts the speakers, their chip and the one
+//! clock beside it, feeding the screen's `Frame` pin. Nothing in either
+//! renderer learns about the other -- the pin is the whole interface, the same
+//! one a user wiring their own clock would use.
+//!
+//! Both halves read one frame index, so the track is analysed at the screen's
+//! fps and cut or padded to its frame count. The count is only known once the
+//! stream has been drained, which is what [`Counted`] is for.
+use super::AnimMode;
+use super::bricks::AnimOptions;
+use crate::audio::speakers::{self, Soundtrack};
+use crate::audio::track::AudioOptions;
+use crate::progress::Progress;
+use crate::video::stream::{FrameSource, FrameStream, SourceInfo};
+use brdb::World;
+use image::RgbaImage;
+use std::cell::Cell;
+
+/// Render `source` in `mode` with `soundtrack` playing alongside it.
+///
+/// `soundtrack` must have been analysed at `source.info().fps`; it is fitted
+/// to the rendered frame count here. `opts.external_clock` is refused, since
+/// the speakers' clock is what drives the screen. `opts.loop_playback` and
+/// `opts.control_buttons` decide both halves -- the matching fields of
+/// `audio_opts` are not read.
+pub fn build_with_soundtrack(
+    mode: &AnimMode,
+    source: &dyn FrameSource,
+    opts: &AnimOptions,
+    mut soundtrack: Soundtrack,
+    audio_opts: &AudioOptions,
+    progress: &mut dyn Progress,
+) -> Result<World, String> {
+    if opts.external_clock {
+        return Err(
+            "a render with a soundtrack cannot take an external clock: the speakers' clock \
+             drives the screen"
+                .to_string(),
+        );
+    }
+    let fps = source.info().fps;
+    if (soundtrack.fps() - fps).abs() > f32::EPSILON * fps.max(1.0) {
+        return Err(format!(
+            "the soundtrack was analysed at {} fps but the screen plays at {fps}; both read \
+             one frame index, so they must match",
+            soundtrack.fps()
+        ));
+    }
+
+    let counted = Counted { inner: source, frames: Cell::new(0) };
+    let screen_opts =
+        AnimOptions { external_clock: true, control_buttons: false, ..opts.clone() };
+    let mut world = mode.build(&counted, &screen_opts, progress)?;
+    // A cancelled render comes back empty; there is no screen to add to.
+    if progress.is_cancelled() {
+        return Ok(world);
+    }
+
+    soundtrack.fit_to(counted.frames.get());
+    let audio_opts = AudioOptions {
+        loop_playback: opts.loop_playback,
+        playback: opts.playback,
+        control_buttons: opts.control_buttons,
+        ..*audio_opts
+    };
+    speakers::add_soundtrack(&mut world, &soundtrack, &audio_opts)?;
+    world.meta.bundle.description = "Animation with audio generated from a video".to_string();
+    world.register_used_components();
+    Ok(world)
+}
+
+/// A [`FrameSource`] that remembers how many frames its longest stream gave.
+///
+/// The longest, not the last or the sum: a renderer may traverse the source
+/// twice (the palette encoding does), and a pass it abandons early must not
+/// shorten the count.
+struct Counted<'a> {
+    inner: &'a dyn FrameSource,
+    frames: Cell<usize>,
+}
+
+impl FrameSource for Counted<'_> {
+    fn info(&self) -> SourceInfo {
+        self.inner.info()
+    }
+
+    fn open(&self) -> Result<Box<dyn FrameStream + '_>, String> {
+        Ok(Box::new(CountedStream { inner: self.inner.open()?, seen: 0, frames: &self.frames }))
+    }
+
+    fn frame_count_estimate(&self) -> Option<usize> {
+        self.inner.frame_count_estimate()
+    }
+}
+
+struct CountedStream<'a> {
+    inner: Box<dyn FrameStream + 'a>,
+    seen: usize,
+    frames: &'a Cell<usize>,
+}
+
+impl FrameStream for CountedStream<'_> {
+    fn next(&mut self) -> Result<Option<RgbaImage>, String> {
+        let frame = self.inner.next()?;
+        if frame.is_some() {
+            self.seen += 1;
+            self.frames.set(self.frames.get().max(self.seen));
+        }
+        Ok(frame)
+    }
+}
@@ -119,9 +119,10 @@ pub struct ScreenExtent {
     /// surface because a block drawn in the picture's own plane would
     /// z-fight with it -- the cube rests flush on the picture's front face,
     /// one [`crate::text::ANCHOR_CUBE_HALF`] past it. Which axis is "in
-    /// front" differs per renderer (world +X for text mode's upright wall,
-    /// +Z for a brick mode's ground screen), which is why this is a whole
-    /// position and not an offset.
+    /// front" differs per renderer and orientation (world +X for text mode's
+    /// upright wall, +Z for a brick mode's floor screen, the wall's own
+    /// normal for a brick-mode wall), which is why this is a whole position
+    /// and not an offset.
     ///
     /// May be negative: brdb round-trips negative main-grid coordinates
     /// exactly (see `bricks::subtitle_extent`'s doc for the concrete case
@@ -140,15 +141,16 @@ pub struct ScreenExtent {
     /// `subtitle_scale` mean the same thing in every mode.
     pub row_height: f32,
     /// Which face of the anchor cube the glyphs are drawn on
-    /// ([`crate::text::FACE_X_POSITIVE`] / [`crate::text::FACE_Z_POSITIVE`]),
-    /// i.e. which plane the subtitle lies in.
+    /// (one of the `crate::text::FACE_*` constants), i.e. which plane the
+    /// subtitle lies in.
     ///
-    /// A per-renderer input because the three renderers do not build the
-    /// same kind of screen: text mode's is a vertical wall facing world +X,
-    /// so its subtitle goes on the same upright face every glyph band uses.
-    /// Both brick encodings lay their screen flat and present its top to the
-    /// viewer, so a subtitle on the +X face would stand edge-on and
-    /// unreadable; those two pass [`crate::text::FACE_Z_POSITIVE`] instead.
+    /// A per-renderer input because the renderers do not build the same kind
+    /// of screen: text mode's is a vertical wall facing world +X, so its
+    /// subtitle goes on the same upright face every glyph band uses. A brick
+    /// encoding's screen faces wherever its
+    /// [`super::bricks::ScreenOrientation`] says -- on the floor it presents
+    /// its top, where a subtitle on the +X face would stand edge-on and
+    /// unreadable, so it passes [`crate::text::FACE_Z_POSITIVE`] instead.
     pub face: u8,
 }
 
@@ -280,8 +282,8 @@ pub fn add_subtitle_display(
             z: opts.text.offset_z,
         },
         false,
-        // The plane the glyphs are drawn in -- upright over text mode's wall,
-        // flat over a brick mode's ground screen. See `ScreenExtent::face`.
+        // The plane the glyphs are drawn in -- upright over a wall, flat over
+        // a brick mode's floor screen. See `ScreenExtent::face`.
         screen.face,
         // Centred on the anchor and growing upward from it, with an outline so
         // the cue reads over whatever the picture is showing.
@@ -406,7 +408,8 @@ fn glyph_scale(opts: &AnimOptions, row_height: f32) -> f32 {
 mod tests {
     use super::*;
     use crate::anim::bricks::{
-        BRANCH, CHANGE_DETECTOR, COMPARE_GE, SUBTRACT, ScreenGeometry, build_brick_world,
+        BRANCH, CHANGE_DETECTOR, COMPARE_GE, SUBTRACT, ScreenGeometry, ScreenOrientation,
+        build_brick_world,
     };
     use crate::anim::chip;
     use crate::anim::color_bricks::build_color_array_world;
@@ -1061,8 +1064,9 @@ mod tests {
     fn a_brick_screens_subtitle_anchors_bottom_centre_in_front_of_the_screen() {
         let geometry = ScreenGeometry {
             footprint: 2,
-            half_height: 2,
+            half_depth: 2,
             pitch: 4,
+            orientation: ScreenOrientation::Floor,
         };
         // No lift here -- this pins the bare baseline anchor; the lift's own
         // effect on this same geometry is `subtitle_lift_moves_the_brick_
@@ -1074,12 +1078,12 @@ mod tests {
         // 1-half-extent cube rests on it at 5.
         assert_eq!(extent.anchor, Position { x: 16, y: 18, z: 5 });
         assert!(
-            extent.anchor.z > 2 * geometry.half_height,
+            extent.anchor.z > 2 * geometry.half_depth,
             "the cube must clear the screen's top face, not sit inside it"
         );
     }
 
-    /// Brick mode's up-the-picture axis is -y, not +z. A display-brick screen
+    /// A floor screen's up-the-picture axis is -y, not +z. A floor screen
     /// lies flat: the image's rows run along y, increasing toward the
     /// picture's bottom (see `subtitle_extent`'s doc), so lifting the anchor
     /// toward the picture's top moves it toward smaller y -- the opposite of
@@ -1090,8 +1094,9 @@ mod tests {
     fn subtitle_lift_moves_the_brick_anchor_up_the_picture_along_minus_y() {
         let geometry = ScreenGeometry {
             footprint: 2,
-            half_height: 2,
+            half_depth: 2,
             pitch: 4,
+            orientation: ScreenOrientation::Floor,
         };
         let base = crate::anim::bricks::subtitle_extent(&geometry, 9, 5, 0.0)
             .expect("a zero lift must always be legal")
@@ -1158,8 +1163,9 @@ mod tests {
     fn a_brick_mode_lift_bigger_than_the_picture_is_applied_not_refused() {
         let geometry = ScreenGeometry {
             footprint: 2,
-            half_height: 2,
+            half_depth: 2,
             pitch: 4,
+            orientation: ScreenOrientation::Floor,
         };
         // A single row's baseline y is just the footprint, 2; a lift of 3
         // lands at y = -1.
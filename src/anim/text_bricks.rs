@@ -51,11 +51,12 @@ use super::controls;
 use super::layout::{GATE_HALF, STAGE_PITCH, lattice_pos_staged};
 use super::pack;
 use super::palette::Palette;
+use super::seek;
 use super::subtitle_display;
-use super::text_layout::plan_bands;
+use super::text_layout::plan_text_bands;
 use super::text_pack::TextPacker;
 use crate::progress::{FrameTotal, Progress};
-use crate::text::{TextBand, TextOptions, TextTile, add_text_tiles};
+use crate::text::{PixelMode, TextBand, TextOptions, TextTile, add_text_tiles};
 use crate::video::stream::FrameSource;
 use brdb::{
     AsBrdbValue, IntVector, Position, Vector3f, WirePort, World,
@@ -125,12 +126,11 @@ pub fn build_text_world(
 
     // --- 1. The band layout, decided before a single frame is looked at -----
     //
-    // Closed-form from the width alone (see `text_layout`), which is exactly
-    // what lets the encode pass stream: the row range each component draws is
-    // fixed for the whole clip, so frames can be encoded and dropped one at a
-    // time.
-    let char_repeat = opts.text.char_repeat.max(1);
-    let plan = plan_bands(info.width as usize, info.height as usize, char_repeat)?;
+    // Closed-form from the width and the pixel mode alone (see
+    // `text_layout`), which is exactly what lets the encode pass stream: the
+    // row range each component draws is fixed for the whole clip, so frames
+    // can be encoded and dropped one at a time.
+    let plan = plan_text_bands(info.width as usize, info.height as usize, &opts.text)?;
     if plan.is_empty() {
         return Err(format!(
             "source is {}x{} -- a zero-height clip has no rows to band",
@@ -139,7 +139,10 @@ pub fn build_text_world(
     }
 
     // --- 2. The palette (optional, and a second traversal) ------------------
-    let palette = if opts.colors > 0 {
+    //
+    // Colour runs only: a glyph mode draws dots in one colour, so there is
+    // nothing for a palette to do and no reason to pay for the extra pass.
+    let palette = if opts.colors > 0 && opts.text.mode == PixelMode::Color {
         // The total is the number of frames that will actually be sampled, not
         // the cap: a bar that stops at 30/120 on a 30-frame clip reads as a
         // stall. When the source cannot give an exact length, its estimate is
@@ -375,20 +378,22 @@ pub fn build_text_world(
     // --- 6. Frame index source ---------------------------------------------
     // `control_pins` carries the clock's Pause/Restart/Resume pin ids for the
     // control buttons below; `None` under `--external-clock` (no timer, no pins).
-    let (frame_index, control_pins) = if opts.external_clock {
+    let (frame_index, control_pins, seek_hook) = if opts.external_clock {
         let pin = chip::add_input_pin(&mut chip, "Frame", service(0, -1));
-        (chip::pin_source(pin, true), None)
+        (chip::pin_source(pin, true), None, None)
     } else {
         let clock = clock::build_clock(
             &mut world,
             &mut chip,
             info.fps,
             frame_count,
-            opts.loop_playback,
+            opts.playback.with_loop(opts.loop_playback),
+            !opts.seek.is_empty(),
             service(0, -2),
         );
         let pins = (clock.pause_pin, clock.restart_pin, clock.resume_pin);
-        (clock.frame_index, Some(pins))
+        let hook = seek::SeekHook::of(&clock);
+        (clock.frame_index, Some(pins), hook)
     };
 
     // --- 7. Exec source -----------------------------------------------------
@@ -630,6 +635,14 @@ pub fn build_text_world(
         let anchor = controls::control_anchor(&world);
         controls::add_control_buttons(&mut world, pause, restart, resume, anchor);
     }
+    // Seek controls, if asked for: the graph beyond everything else in the
+    // chip, the buttons beside the control buttons. Same step as hex mode's.
+    if let Some(hook) = &seek_hook {
+        let plan = opts.seek.plan(info.fps, frame_count, opts.source_start_s);
+        let buttons = seek::add_seek_graph(&mut world, &mut chip, hook, &plan);
+        let anchor = controls::control_anchor(&world);
+        controls::add_seek_buttons(&mut world, &buttons, anchor);
+    }
 
     // --- 11. Publish --------------------------------------------------------
     //
@@ -721,7 +734,10 @@ pub fn subtitle_centre_shift(opts: &AnimOptions, width_px: usize) -> i32 {
 /// is still up rather than one whole traversal later. A cancelled sample
 /// returns the frames it did gather -- `build_text_world` throws them away at
 /// its own check immediately afterwards, so they are never built from.
-fn sample_frames(
+///
+/// Brick mode's palette encoding samples through this too (see
+/// [`super::palette_bricks`]), under the same contract.
+pub(super) fn sample_frames(
     source: &dyn FrameSource,
     n: usize,
     progress: &mut dyn Progress,
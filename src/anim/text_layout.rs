@@ -19,9 +19,15 @@
 //! integer division rounds correctly). That constant is 2 rows at width 192,
 //! 5 at width 96, and 8 at width 64 -- the values this module's tests pin.
 //!
+//! The monochrome glyph modes ([`PixelMode::Braille`] and friends) need no
+//! bound at all: they emit no tags, exactly one glyph per cell, so a line of
+//! glyphs costs `ceil(width / cell_width)` characters whatever the frame
+//! holds. Their bands hold whole glyph lines -- a multiple of the cell height
+//! in image rows -- so no cell straddles two components ([`plan_mono_bands`]).
+//!
 //! This module is pure arithmetic over `width` / `height` / `char_repeat`; it
 //! knows nothing about images, bricks, or `World`.
-use crate::text::MAX_COMPONENT_CHARS;
+use crate::text::{MAX_COMPONENT_CHARS, PixelMode, TextOptions};
 
 /// Characters one `<color="RRGGBB">` tag costs: `<color="` (8) + 6 hex + `">`
 /// (2). The worst-case row bound assumes every pixel emits one, which is what
@@ -76,6 +82,67 @@ pub fn plan_bands(width: usize, height: usize, char_repeat: usize) -> Result<Vec
         .collect())
 }
 
+/// Characters one line of `mode`'s glyphs costs across a `width`-pixel row:
+/// one glyph per cell, no tags. Exact, not a bound.
+pub fn mono_line_chars(width: usize, mode: PixelMode) -> usize {
+    width.div_ceil(mode.cell().0 as usize)
+}
+
+/// The band layout for a monochrome glyph mode: as many whole glyph lines
+/// per band as fit one component, each line [`mono_line_chars`] plus the
+/// newline joining it to the next. `rows` counts image rows, so it is a
+/// multiple of the cell height (only the last band may be short, and its
+/// partial cells pad with off pixels like a static render's edge).
+pub fn plan_mono_bands(
+    width: usize,
+    height: usize,
+    mode: PixelMode,
+) -> Result<Vec<BandPlan>, String> {
+    let line = mono_line_chars(width, mode);
+    if line > MAX_COMPONENT_CHARS {
+        return Err(format!(
+            "a {width}-pixel row is {line} {} glyphs per line, over the \
+             {MAX_COMPONENT_CHARS}-char TextDisplay limit -- render narrower",
+            mode.name()
+        ));
+    }
+    let cell_h = mode.cell().1 as usize;
+    let rows_per_band = ((MAX_COMPONENT_CHARS + 1) / (line + 1)).max(1) * cell_h;
+    Ok((0..height)
+        .step_by(rows_per_band)
+        .map(|start_row| BandPlan {
+            start_row,
+            rows: rows_per_band.min(height - start_row),
+        })
+        .collect())
+}
+
+/// The band layout for `text`'s pixel mode: [`plan_bands`]' worst-case
+/// colour layout, or [`plan_mono_bands`]' exact glyph one.
+pub fn plan_text_bands(
+    width: usize,
+    height: usize,
+    text: &TextOptions,
+) -> Result<Vec<BandPlan>, String> {
+    match text.mode {
+        PixelMode::Color => plan_bands(width, height, text.char_repeat.max(1)),
+        mode => plan_mono_bands(width, height, mode),
+    }
+}
+
+/// The most characters a band of `rows` image rows can cost per frame in
+/// `text`'s pixel mode: the worst case for colour runs, exact for glyphs.
+pub fn band_char_bound(width: usize, rows: usize, text: &TextOptions) -> usize {
+    let (lines, line) = match text.mode {
+        PixelMode::Color => (rows, worst_case_row_chars(width, text.char_repeat.max(1))),
+        mode => (
+            rows.div_ceil(mode.cell().1 as usize),
+            mono_line_chars(width, mode),
+        ),
+    };
+    lines * line + lines.saturating_sub(1)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -148,4 +215,28 @@ mod tests {
     fn a_zero_height_screen_plans_no_bands() {
         assert!(plan_bands(64, 0, 2).unwrap().is_empty());
     }
+
+    #[test]
+    fn mono_bands_hold_whole_glyph_lines_and_fit_exactly() {
+        // 192 px of braille is 96 glyphs a line: 103 lines of 4 rows a band
+        let plan = plan_mono_bands(192, 1080, PixelMode::Braille).unwrap();
+        assert_eq!(plan[0].rows, 103 * 4);
+        let text = TextOptions {
+            mode: PixelMode::Braille,
+            ..TextOptions::default()
+        };
+        assert!(band_char_bound(192, plan[0].rows, &text) <= MAX_COMPONENT_CHARS);
+        assert_eq!(plan_text_bands(192, 1080, &text).unwrap(), plan);
+        assert!(plan.iter().all(|b| b.start_row % 4 == 0));
+        assert_eq!(plan.iter().map(|b| b.rows).sum::<usize>(), 1080);
+        // far more rows per component than colour's worst case at any width
+        assert!(plan[0].rows > 50 * plan_bands(192, 1080, 2).unwrap()[0].rows);
+    }
+
+    #[test]
+    fn a_mono_row_too_wide_for_one_component_is_an_error() {
+        assert!(plan_mono_bands(20_000, 4, PixelMode::Blocks).is_ok());
+        let err = plan_mono_bands(20_002, 4, PixelMode::Blocks).unwrap_err();
+        assert!(err.contains("20002"), "{err}");
+    }
 }
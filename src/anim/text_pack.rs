@@ -41,9 +41,19 @@
 //! that behaviour for free -- fresh colour state per band, per frame -- which
 //! is also what [`super::text_layout::worst_case_row_chars`]'s bound assumes.
 //! Colour state is never threaded between bands.
+//!
+//! # Monochrome glyph modes
+//!
+//! With `opts.mode` a glyph mode there is no colour state and no palette:
+//! each frame is cut to dots once, whole ([`crate::text::mono_mask`]), so a
+//! dithered frame diffuses its error across band seams exactly as a static
+//! render does across tile seams, and every band then reads its own rows of
+//! that one mask.
 use crate::anim::palette::Palette;
 use crate::anim::text_layout::BandPlan;
-use crate::text::{MAX_COMPONENT_CHARS, TextOptions, encode_bands};
+use crate::text::{
+    MAX_COMPONENT_CHARS, PixelMode, TextOptions, encode_bands, encode_mono_tile, mono_mask,
+};
 use image::RgbaImage;
 
 /// Builds one string per frame per band, in one traversal of the frames, so
@@ -73,9 +83,10 @@ impl TextPacker {
     /// `width`/`height` are the source's reported dimensions; every pushed
     /// frame must match them exactly. `plan` is normally
     /// [`super::text_layout::plan_bands`]'s own output, unmodified, and must
-    /// have been computed for these same dimensions. `palette` may be
+    /// have been computed for these same dimensions (and `opts.mode` --
+    /// [`super::text_layout::plan_text_bands`]). `palette` may be
     /// [`Palette::default()`] (empty), which leaves every pixel's colour
-    /// untouched.
+    /// untouched; a glyph mode ignores it.
     pub fn new(
         width: u32,
         height: u32,
@@ -139,11 +150,15 @@ impl TextPacker {
             ));
         }
 
+        // A glyph mode cuts the whole frame to dots once; see the module
+        // doc's "Monochrome glyph modes" section.
+        let mask = (self.opts.mode != PixelMode::Color).then(|| mono_mask(frame, &self.opts));
+
         // Quantize once, up front, so every band crops from the same
         // post-palette pixels. Skipped entirely for an empty palette so the
         // unquantized path allocates nothing extra.
         let quantized;
-        let src: &RgbaImage = if self.palette.is_empty() {
+        let src: &RgbaImage = if self.palette.is_empty() || mask.is_some() {
             frame
         } else {
             let mut q = frame.clone();
@@ -161,7 +176,7 @@ impl TextPacker {
         // an error in band 7 cannot leave bands 0..7 one frame longer than the
         // rest -- the ragged accumulator the previous push-as-you-go loop left
         // behind on its (bug-only) error paths.
-        let encoded = encode_all_bands(&self.plan, src, frame_w, &self.opts)?;
+        let encoded = encode_all_bands(&self.plan, src, mask.as_deref(), frame_w, &self.opts)?;
         for (band, text) in self.bands.iter_mut().zip(encoded) {
             band.push(text);
         }
@@ -177,7 +192,8 @@ impl TextPacker {
 }
 
 /// One band's string for one frame: crop its rows out of `src` and encode them
-/// as a single `TextDisplay` component's worth of text.
+/// as a single `TextDisplay` component's worth of text -- or, in a glyph mode,
+/// read them out of the frame's `mask`.
 ///
 /// Takes the band's own index only to name it in the two "this is a bug in
 /// this codebase" errors below.
@@ -185,9 +201,23 @@ fn encode_band(
     bi: usize,
     band: &BandPlan,
     src: &RgbaImage,
+    mask: Option<&[bool]>,
     frame_w: usize,
     opts: &TextOptions,
 ) -> Result<String, String> {
+    if let Some(mask) = mask {
+        let rect = (0, band.start_row as u32, frame_w as u32, band.rows as u32);
+        let (text, chars, _) = encode_mono_tile(mask, frame_w as u32, rect, opts);
+        if chars > MAX_COMPONENT_CHARS {
+            return Err(format!(
+                "band {bi} (row {}): {chars} glyph chars exceeds the {MAX_COMPONENT_CHARS}-char \
+                 TextDisplay limit -- the glyph layout in text_layout and the encoder in text.rs \
+                 disagree, which is a bug in this codebase, not a user error",
+                band.start_row
+            ));
+        }
+        return Ok(text);
+    }
     let sub = image::imageops::crop_imm(
         src,
         0,
@@ -253,6 +283,7 @@ const MIN_BANDS_PER_JOB: usize = 4;
 fn encode_all_bands(
     plan: &[BandPlan],
     src: &RgbaImage,
+    mask: Option<&[bool]>,
     frame_w: usize,
     opts: &TextOptions,
 ) -> Result<Vec<String>, String> {
@@ -261,7 +292,7 @@ fn encode_all_bands(
     plan.par_iter()
         .enumerate()
         .with_min_len(MIN_BANDS_PER_JOB)
-        .map(|(bi, band)| encode_band(bi, band, src, frame_w, opts))
+        .map(|(bi, band)| encode_band(bi, band, src, mask, frame_w, opts))
         .collect_into_vec(&mut out);
     out.into_iter().collect()
 }
@@ -271,12 +302,13 @@ fn encode_all_bands(
 fn encode_all_bands(
     plan: &[BandPlan],
     src: &RgbaImage,
+    mask: Option<&[bool]>,
     frame_w: usize,
     opts: &TextOptions,
 ) -> Result<Vec<String>, String> {
     plan.iter()
         .enumerate()
-        .map(|(bi, band)| encode_band(bi, band, src, frame_w, opts))
+        .map(|(bi, band)| encode_band(bi, band, src, mask, frame_w, opts))
         .collect()
 }
 
@@ -378,6 +410,25 @@ mod tests {
         assert!(p.finish()[0][0].contains("123456"), "exact source colour");
     }
 
+    #[test]
+    fn a_glyph_mode_packs_cells_and_ignores_the_palette() {
+        // 4x8 braille is two lines of two cells, in one band; a palette
+        // that would turn everything red changes nothing
+        let text = TextOptions {
+            mode: PixelMode::Braille,
+            ..opts()
+        };
+        let plan = crate::anim::text_layout::plan_text_bands(4, 8, &text).unwrap();
+        let pal = Palette::build(&[solid(4, 8, [0xFF, 0, 0])], 1, 1);
+        let mut p = TextPacker::new(4, 8, plan, text, pal);
+        p.push_frame(&solid(4, 8, [0xFF, 0xFF, 0xFF])).unwrap();
+        p.push_frame(&solid(4, 8, [0, 0, 0])).unwrap();
+        let out = p.finish();
+        assert_eq!(out.len(), 1, "eight rows of braille is one band");
+        assert_eq!(out[0][0], "⣿⣿\n⣿⣿");
+        assert_eq!(out[0][1], "⠀⠀\n⠀⠀");
+    }
+
     #[test]
     fn pushing_past_the_frame_limit_errors_rather_than_truncating() {
         let plan = plan_bands(1, 1, 2).unwrap();
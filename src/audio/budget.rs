@@ -0,0 +1,170 @@
+//! Fitting an audio render to a budget: [`crate::anim::budget`] for the
+//! speaker bank.
+//!
+//! Audio has two settings worth trading, and the search walks both down from
+//! where the caller set them: the analysis rate, down [`RATES`], and the
+//! speaker count -- `--bands` in bank mode, `--max-voices` in voice mode.
+//! "Best" is the most speakers updated per second, the audio counterpart of
+//! a picture's pixels per second. The clip's length is fixed, so the frame
+//! count at each rate is [`frames_for_duration`]'s, the same the analysis
+//! will produce.
+use super::AudioMode;
+use super::cost::{AudioCost, estimate, frames_for_duration};
+use super::track::{AudioOptions, band_plan};
+use crate::anim::budget::{Budget, show_bytes};
+
+/// Analysis rates the search steps down through, below the caller's own.
+pub const RATES: [f32; 8] = [60.0, 50.0, 40.0, 30.0, 25.0, 20.0, 15.0, 10.0];
+
+/// See [`crate::anim::budget`]'s allowances of the same names; an audio save
+/// carries the same schemas and the same kinds of component.
+const SAVE_BASE_BYTES: usize = 8 * 1024;
+const SAVE_ENTITY_BYTES: usize = 64;
+const HOST_ENTITY_BYTES: usize = 1024;
+
+/// An upper bound on the written save's size: every element as an
+/// uncompressed `f64`, plus the per-entity allowance.
+pub fn save_bytes(cost: &AudioCost) -> usize {
+    SAVE_BASE_BYTES
+        + SAVE_ENTITY_BYTES * (cost.gates + cost.wires + cost.bricks)
+        + cost.elements * 8
+}
+
+/// An upper bound on host memory at the build's peak: the analysis's `f32`
+/// levels and the `f64` arrays made from them (12 bytes an element), the
+/// world, and the save.
+pub fn host_bytes(cost: &AudioCost) -> usize {
+    cost.elements * 12
+        + HOST_ENTITY_BYTES * (cost.gates + cost.wires + cost.bricks)
+        + save_bytes(cost)
+}
+
+/// The options a search picked, and what they cost.
+#[derive(Clone, Copy, Debug)]
+pub struct AudioFit {
+    /// The caller's options with `fps` and `bands` or `max_voices` replaced.
+    pub opts: AudioOptions,
+    pub cost: AudioCost,
+    pub save_bytes: usize,
+    pub host_bytes: usize,
+}
+
+impl AudioFit {
+    /// Speakers updated per second: what the search maximises.
+    pub fn speaker_rate(&self) -> f64 {
+        self.cost.speakers as f64 * self.opts.fps as f64
+    }
+}
+
+/// The best [`AudioFit`] under `budget` for `duration_s` seconds of audio at
+/// `sample_rate`.
+///
+/// `Err` when nothing fits, naming the smallest candidate's cost, or with the
+/// renderer's refusal when `opts` cannot be rendered at all.
+pub fn fit(
+    mode: AudioMode,
+    opts: &AudioOptions,
+    budget: &Budget,
+    duration_s: f64,
+    sample_rate: u32,
+) -> Result<AudioFit, String> {
+    super::cost::check(mode, opts)?;
+    // Bank mode's count includes its noise bands, and a plan needs two tonal
+    // ones; voice mode can go down to a single voice.
+    let (top, floor) = match mode {
+        AudioMode::Bank => (band_plan(opts)?.len(), opts.noise_bands + 2),
+        AudioMode::Voice => (opts.max_voices, 1),
+    };
+    let mut rates = vec![opts.fps];
+    rates.extend(RATES.iter().copied().filter(|&r| r < opts.fps));
+
+    let mut best: Option<AudioFit> = None;
+    let mut smallest: Option<AudioFit> = None;
+    for fps in rates {
+        for speakers in (floor..=top.max(floor)).rev() {
+            let opts = match mode {
+                AudioMode::Bank => AudioOptions { fps, bands: Some(speakers), ..*opts },
+                AudioMode::Voice => AudioOptions { fps, max_voices: speakers, ..*opts },
+            };
+            let Some(frames) = frames_for_duration(duration_s, sample_rate, &opts) else {
+                continue;
+            };
+            let Ok(cost) = estimate(mode, frames, &opts) else {
+                continue;
+            };
+            let candidate = AudioFit {
+                opts,
+                cost,
+                save_bytes: save_bytes(&cost),
+                host_bytes: host_bytes(&cost),
+            };
+            if budget.admits(cost.gates, candidate.save_bytes, candidate.host_bytes) {
+                if best.is_none_or(|b| candidate.speaker_rate() > b.speaker_rate()) {
+                    best = Some(candidate);
+                }
+                break;
+            }
+            if smallest.is_none_or(|s| candidate.host_bytes < s.host_bytes) {
+                smallest = Some(candidate);
+            }
+        }
+    }
+    best.ok_or_else(|| match smallest {
+        Some(s) => format!(
+            "nothing fits the budget: the smallest render tried, {} speaker(s) at {} fps, needs \
+             {} gate(s), a save of up to {} and {} of memory",
+            s.cost.speakers,
+            s.opts.fps,
+            s.cost.gates,
+            show_bytes(s.save_bytes),
+            show_bytes(s.host_bytes)
+        ),
+        None => "nothing fits the budget: no rate tried can analyse this audio".to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MINUTE: f64 = 60.0;
+
+    #[test]
+    fn a_met_budget_changes_nothing() {
+        let opts = AudioOptions::default();
+        let fit = fit(AudioMode::Bank, &opts, &Budget::default(), MINUTE, 44_100).unwrap();
+        assert_eq!(fit.opts.fps, opts.fps);
+        assert_eq!(fit.cost.speakers, band_plan(&opts).unwrap().len());
+    }
+
+    /// A voice-mode gate budget drops voices, never the rate: the gates do
+    /// not depend on it until the track spills into another bank.
+    #[test]
+    fn a_gate_budget_drops_voices() {
+        let opts = AudioOptions { max_voices: 12, ..AudioOptions::default() };
+        let full = fit(AudioMode::Voice, &opts, &Budget::default(), MINUTE, 44_100).unwrap();
+        let budget = Budget { gates: Some(full.cost.gates - 1), ..Budget::default() };
+        let fit = fit(AudioMode::Voice, &opts, &budget, MINUTE, 44_100).unwrap();
+        assert!(fit.opts.max_voices < 12 && fit.cost.gates < full.cost.gates);
+        assert_eq!(fit.opts.fps, opts.fps);
+    }
+
+    /// The save grows with frames times speakers, so a save budget can be met
+    /// at a lower rate and the search weighs that against fewer bands.
+    #[test]
+    fn a_save_budget_fits_under_its_limit() {
+        let opts = AudioOptions::default();
+        let budget = Budget { save_bytes: Some(256 << 10), ..Budget::default() };
+        let fit = fit(AudioMode::Bank, &opts, &budget, 10.0 * MINUTE, 44_100).unwrap();
+        assert!(fit.save_bytes <= 256 << 10);
+        assert!(fit.opts.fps < opts.fps || fit.cost.speakers < 79, "something must give");
+    }
+
+    #[test]
+    fn a_budget_below_the_clock_fits_nothing() {
+        let budget = Budget { gates: Some(5), ..Budget::default() };
+        let err =
+            fit(AudioMode::Bank, &AudioOptions::default(), &budget, MINUTE, 44_100).unwrap_err();
+        assert!(err.contains("nothing fits"), "{err}");
+    }
+}
@@ -122,6 +122,7 @@ pub fn estimate(
     // non-positive pair, so a cost line for one would describe a build that
     // cannot happen, next to a Generate button that (rightly) refuses it.
     super::speakers::check_attenuation(opts)?;
+    opts.playback.check()?;
     let (speakers, streams_per_speaker) = match mode {
         AudioMode::Bank => (band_plan(opts)?.len(), 1),
         AudioMode::Voice => {
@@ -150,6 +151,9 @@ pub fn estimate(
     } else {
         (0, 0)
     };
+    // A ping-pong or loop-range order's extra clock gates; nothing for the
+    // default first-to-last, whatever the count of plays.
+    let (order_gates, order_wires) = opts.playback.clock_cost();
 
     Ok(AudioCost {
         speakers,
@@ -157,6 +161,7 @@ pub fn estimate(
         frames,
         banks,
         gates: CLOCK_GATES
+            + order_gates
             + PAUSE_MUTE_GATES
             + 1
             + speakers
@@ -164,6 +169,7 @@ pub fn estimate(
             + 3 * boundaries
             + streams * boundaries,
         wires: CLOCK_WIRES
+            + order_wires
             + PAUSE_MUTE_WIRES
             + 3 * speakers
             + 2 * speakers
@@ -211,6 +217,7 @@ pub fn frames_for_duration(
 /// from the renderer.
 pub fn check(mode: AudioMode, opts: &AudioOptions) -> Result<(), String> {
     super::speakers::check_attenuation(opts)?;
+    opts.playback.check()?;
     match mode {
         AudioMode::Bank => {
             check_subdiv(opts.subdiv)?;
@@ -223,6 +230,7 @@ pub fn check(mode: AudioMode, opts: &AudioOptions) -> Result<(), String> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::anim::clock::{PlayOrder, Playback};
     use crate::audio::bands::BandPlan;
     use crate::audio::speakers::{build_speaker_world, build_voice_world};
     use crate::audio::track::VoiceTrack;
@@ -302,6 +310,26 @@ mod tests {
         }
     }
 
+    /// A ping-pong or loop-range order is a few gates on the shared clock,
+    /// whichever builder owns it; a count of plays is only the timer's limit.
+    #[test]
+    fn the_estimate_matches_a_real_build_in_every_playback_order() {
+        let range = PlayOrder::Range { start_s: 0.5, end_s: 1.0 };
+        for order in [PlayOrder::Forward, PlayOrder::PingPong, range] {
+            for plays in [None, Some(2)] {
+                let playback = Playback { order, plays };
+                let o = AudioOptions { bands: Some(6), max_voices: 5, playback, ..opts(64) };
+                let bank = build_speaker_world(&track(&o, 40), &o).expect("build");
+                let voice = build_voice_world(&streams(5, 40, o.fps), &o).expect("build");
+                for (mode, world) in [(AudioMode::Bank, bank), (AudioMode::Voice, voice)] {
+                    let c = estimate(mode, 40, &o).expect("estimate");
+                    assert_eq!(c.gates, built_gates(&world), "{mode:?} {playback:?}: gates");
+                    assert_eq!(c.wires, world.wires.len(), "{mode:?} {playback:?}: wires");
+                }
+            }
+        }
+    }
+
     /// Bank mode's speaker count follows `--bands`/`--noise-bands`/`--subdiv`
     /// through the renderer's own [`band_plan`], so a default (unset `--bands`)
     /// estimate must describe the full span rather than some second default.
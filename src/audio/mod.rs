@@ -1,10 +1,12 @@
 pub mod backend;
 pub mod bands;
+pub mod budget;
 pub mod cost;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod ffmpeg_src;
 pub mod oneshot_sounds;
 pub mod percussion;
+pub mod playlist;
 pub mod presets;
 pub mod source;
 pub mod speakers;
@@ -0,0 +1,177 @@
+//! Several songs in one world: their analyses played back to back as one
+//! track, with the clip buttons [`crate::anim::playlist`] gives a video.
+//!
+//! Every song is analysed with the same options, so every song drives the
+//! same speakers -- one band plan, or one voice count -- and a playlist is
+//! each speaker's rows laid end to end. Nothing else changes: one cluster, one
+//! chip, one clock, and the seek graph on that clock
+//! ([`super::speakers::build_speaker_world_with_seek`]) jumping between songs.
+//! With auto-advance off, each song ends on a copy of its last frame, the
+//! loop point a video clip ends on too ([`Playlist::loop_points`]).
+use super::track::VoiceTrack;
+use super::voices::{VoiceStats, VoiceStreams};
+use crate::anim::playlist::{Playlist, PlaylistClip};
+
+/// `songs`' bank analyses back to back as one track, and the [`Playlist`] its
+/// buttons are built from. Every song must have the first one's bands and fps.
+pub fn join_tracks(
+    songs: Vec<(String, VoiceTrack)>,
+    auto_advance: bool,
+) -> Result<(VoiceTrack, Playlist), String> {
+    let Some((first_name, first)) = songs.first() else {
+        return Err("a playlist needs at least one song".to_string());
+    };
+    for (name, track) in &songs {
+        if track.plan.pitches != first.plan.pitches || track.plan.kinds != first.plan.kinds {
+            return Err(format!(
+                "playlist song '{name}' has other bands than '{first_name}'; every song plays \
+                 through the same speakers"
+            ));
+        }
+        check_song(name, track.fps, track.frame_count, first_name, first.fps)?;
+    }
+    let playlist = lay_out(&songs, |t| t.frame_count, auto_advance);
+    let mut songs = songs.into_iter().map(|(_, t)| t);
+    let mut joined = songs.next().expect("checked above");
+    joined.volumes = joined.volumes.into_iter().map(|row| end(row, auto_advance)).collect();
+    for track in songs {
+        append(&mut joined.volumes, track.volumes, auto_advance);
+    }
+    joined.frame_count = playlist.frame_count();
+    Ok((joined, playlist))
+}
+
+/// [`join_tracks`] for voice analyses. Every song must have the first one's
+/// voice count and fps. Each song's [`VoiceStats`] describe that song alone,
+/// so the joined streams carry none: read them before joining.
+pub fn join_streams(
+    songs: Vec<(String, VoiceStreams)>,
+    auto_advance: bool,
+) -> Result<(VoiceStreams, Playlist), String> {
+    let Some((first_name, first)) = songs.first() else {
+        return Err("a playlist needs at least one song".to_string());
+    };
+    for (name, streams) in &songs {
+        if streams.voice_count() != first.voice_count() {
+            return Err(format!(
+                "playlist song '{name}' has {} voices but '{first_name}' has {}; every song \
+                 plays through the same speakers",
+                streams.voice_count(),
+                first.voice_count()
+            ));
+        }
+        check_song(name, streams.fps, streams.frame_count, first_name, first.fps)?;
+    }
+    let playlist = lay_out(&songs, |s| s.frame_count, auto_advance);
+    let mut songs = songs.into_iter().map(|(_, s)| s);
+    let mut joined = songs.next().expect("checked above");
+    joined.pitches = joined.pitches.into_iter().map(|row| end(row, auto_advance)).collect();
+    joined.volumes = joined.volumes.into_iter().map(|row| end(row, auto_advance)).collect();
+    for streams in songs {
+        append(&mut joined.pitches, streams.pitches, auto_advance);
+        append(&mut joined.volumes, streams.volumes, auto_advance);
+    }
+    joined.frame_count = playlist.frame_count();
+    joined.stats = VoiceStats::default();
+    Ok((joined, playlist))
+}
+
+/// One song against the first: the same clock rate, and something to play.
+fn check_song(
+    name: &str,
+    fps: f32,
+    frames: usize,
+    first_name: &str,
+    first_fps: f32,
+) -> Result<(), String> {
+    if (fps - first_fps).abs() > f32::EPSILON * first_fps.max(1.0) {
+        return Err(format!(
+            "playlist song '{name}' was analysed at {fps} fps but '{first_name}' at \
+             {first_fps}; every song runs on the same clock"
+        ));
+    }
+    if frames == 0 {
+        return Err(format!("playlist song '{name}' has no frames"));
+    }
+    Ok(())
+}
+
+/// The [`Playlist`] `songs` make, each named for its button.
+fn lay_out<T>(
+    songs: &[(String, T)],
+    frames: impl Fn(&T) -> usize,
+    auto_advance: bool,
+) -> Playlist {
+    let clips = songs
+        .iter()
+        .map(|(name, song)| PlaylistClip { name: name.clone(), frames: frames(song) })
+        .collect();
+    Playlist { clips, auto_advance }
+}
+
+/// `row` as it sits on the timeline: followed by a copy of its last frame
+/// when the song loops on itself.
+fn end(mut row: Vec<f64>, auto_advance: bool) -> Vec<f64> {
+    if !auto_advance && let Some(&last) = row.last() {
+        row.push(last);
+    }
+    row
+}
+
+/// Lay each of `rows` after the same speaker's row in `joined`.
+fn append(joined: &mut [Vec<f64>], rows: Vec<Vec<f64>>, auto_advance: bool) {
+    for (into, row) in joined.iter_mut().zip(rows) {
+        into.extend(end(row, auto_advance));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio::bands::BandPlan;
+
+    fn track(bands: usize, volumes: &[f64]) -> VoiceTrack {
+        VoiceTrack {
+            plan: BandPlan::new(bands, 0).unwrap(),
+            volumes: vec![volumes.to_vec(); bands],
+            fps: 30.0,
+            frame_count: volumes.len(),
+        }
+    }
+
+    #[test]
+    fn songs_play_back_to_back_and_a_looping_song_ends_on_a_copy() {
+        for (auto_advance, want) in [
+            (true, vec![0.1, 0.2, 0.5, 0.6, 0.7]),
+            (false, vec![0.1, 0.2, 0.2, 0.5, 0.6, 0.7, 0.7]),
+        ] {
+            let songs = vec![
+                ("a".to_string(), track(3, &[0.1, 0.2])),
+                ("b".to_string(), track(3, &[0.5, 0.6, 0.7])),
+            ];
+            let (joined, playlist) = join_tracks(songs, auto_advance).unwrap();
+            assert_eq!(joined.frame_count, want.len());
+            assert_eq!(playlist.frame_count(), want.len());
+            assert!(joined.volumes.iter().all(|row| *row == want), "{auto_advance}");
+            let names: Vec<_> = playlist.clips.iter().map(|c| c.name.as_str()).collect();
+            assert_eq!(names, ["a", "b"]);
+        }
+    }
+
+    #[test]
+    fn songs_on_other_speakers_or_clocks_are_refused() {
+        let other_bands = vec![
+            ("a".to_string(), track(3, &[0.1])),
+            ("b".to_string(), track(4, &[0.1])),
+        ];
+        let err = join_tracks(other_bands, true).err().unwrap();
+        assert!(err.contains("'b' has other bands"), "{err}");
+        let other_fps = vec![
+            ("a".to_string(), track(3, &[0.1])),
+            ("b".to_string(), VoiceTrack { fps: 60.0, ..track(3, &[0.1]) }),
+        ];
+        let err = join_tracks(other_fps, true).err().unwrap();
+        assert!(err.contains("60 fps"), "{err}");
+        assert!(join_tracks(Vec::new(), true).is_err());
+    }
+}
@@ -15,9 +15,12 @@ use super::voices::VoiceStreams;
 use crate::anim::bricks::{
     ARRAY_GET, ARRAY_VAR, BRANCH, CHANGE_DETECTOR, COMPARE_GE, SELECT, SUBTRACT,
 };
-use crate::anim::chip::{Chip, add_input_pin, finish, new_chip, pin_source};
-use crate::anim::clock::{MULTIPLY, build_clock, gate};
+use crate::anim::chip::{
+    Chip, MICROCHIP_INPUT, add_input_pin, add_output_pin, finish, new_chip, pin_source, pin_target,
+};
+use crate::anim::clock::{MULTIPLY, PlayOrder, Playback, build_clock, gate};
 use crate::anim::layout::{GATE_HALF, STAGE_BASE_Z, STAGE_PITCH, lattice_pos_staged};
+use crate::anim::seek::{self, SeekControls, SeekHook, SeekPlan};
 use brdb::{
     AsBrdbValue, Brick, BrickType, IntVector, Position, Vector3f, WirePort, World,
     assets::{
@@ -271,6 +274,21 @@ fn add_emitter(
     Ok(id)
 }
 
+/// A screen the speakers are added beside rather than built alone: see
+/// [`add_soundtrack`].
+struct Host {
+    /// Main-grid x offset for the cluster and its chip, past every brick the
+    /// screen put there.
+    shift: i32,
+    /// The screen's `Frame` input pins, each fed the speakers' frame index.
+    frame_pins: Vec<usize>,
+}
+
+/// `position` moved past `host`'s screen, or left alone without one.
+fn beside(host: Option<&Host>, position: Position) -> Position {
+    Position { x: position.x + host.map_or(0, |h| h.shift), ..position }
+}
+
 /// The chip, its clock, and the four attenuation/volume input pins -- the
 /// scaffolding both render modes put around their speakers.
 struct Scaffold {
@@ -281,15 +299,17 @@ struct Scaffold {
     control_pins: (usize, usize, usize),
     /// Source port carrying the wrapped integer frame index.
     frame_index: WirePort,
-    /// The timer's raw `Time` (continuous seconds), for a consumer that reads
-    /// the runtime directly rather than the wrapped frame index -- the MIDI
-    /// event playhead compares it against note start/end times.
-    time: WirePort,
+    /// The clock's [`phase`](crate::anim::clock::Clock::phase): what the MIDI
+    /// event playheads read their time off.
+    phase: WirePort,
     /// The gated master-volume source every per-speaker multiply reads: the
     /// pause-mute `Select`'s output, not the raw `Volume` pin. Passes the
     /// master volume through while the clock advances, emits 0 while frozen
     /// (paused, an ended no-loop track, or a stalled external clock).
     master_volume: WirePort,
+    /// What the seek graph hangs off, when the clock was built seekable for
+    /// a non-empty [`SeekControls`]; `None` otherwise.
+    seek_hook: Option<SeekHook>,
 }
 
 /// Build the microchip, its clock, and the four input pins, and fan three of
@@ -299,18 +319,28 @@ struct Scaffold {
 /// contract as clock.rs's rate_pin). So each of the four pins keeps a real
 /// baked value underneath it; the inverse (unwired pin forcing 0) would
 /// silence the build in game with a save that looks perfect.
+///
+/// With a `host`, the clock's frame index also leaves through a `Frame Index`
+/// output pin into every one of the host's `Frame` pins, so the screen and
+/// the speakers pause, restart and change rate together.
+///
+/// The clock is built seekable only when `seek` asks for a button, so a
+/// render without seek controls keeps the plain clock.
+#[allow(clippy::too_many_arguments)]
 fn scaffold(
     world: &mut World,
     speaker_ids: &[usize],
     fps: f32,
     frame_count: usize,
-    loop_playback: bool,
+    playback: Playback,
+    seek: &SeekControls,
+    host: Option<&Host>,
 ) -> Scaffold {
     // Beside the cluster on x, never inside it: an overlap on the main grid
     // silently drops one of the two bricks. The cluster grows from the origin
     // along positive x/y/z, so any chip_x that keeps the shell negative clears
     // it for every speaker count.
-    let chip_x = -(speaker_half().x * 4);
+    let chip_x = beside(host, Position { x: -(speaker_half().x * 4), y: 0, z: 0 }).x;
     let mut chip = new_chip(
         world,
         Position {
@@ -331,9 +361,10 @@ fn scaffold(
     );
 
     // Timer -> multiply by fps -> truncate -> modulo frame count, plus
-    // Pause/Restart/Resume/Rate pins. `loop_playback` rides along so a track
-    // and a clip stop the same way.
-    let clock = build_clock(world, &mut chip, fps, frame_count, loop_playback, service(0, 0));
+    // Pause/Restart/Resume/Rate pins. `playback` rides along so a track and a
+    // clip play and stop the same way.
+    let clock =
+        build_clock(world, &mut chip, fps, frame_count, playback, !seek.is_empty(), service(0, 0));
     let frame_index = clock.frame_index.clone();
     // The clock's control pins, for the pre-wired control buttons.
     let control_pins = (clock.pause_pin, clock.restart_pin, clock.resume_pin);
@@ -349,6 +380,13 @@ fn scaffold(
     let max_distance_pin = add_input_pin(&mut chip, "Max Distance", service(1, -2));
     let directional_pin = add_input_pin(&mut chip, "Directional", service(2, -2));
     let volume_pin = add_input_pin(&mut chip, "Volume", service(3, -2));
+    if let Some(host) = host {
+        let out = add_output_pin(&mut chip, "Frame Index", service(4, -2));
+        world.add_wire_connection(frame_index.clone(), pin_target(out, false));
+        for &pin in &host.frame_pins {
+            world.add_wire_connection(pin_source(out, false), pin_target(pin, true));
+        }
+    }
 
     // Three fan straight out to every speaker's own emitter port -- one pin,
     // `speaker_ids.len()` wires -- so a pin wired to only some of the cluster
@@ -436,8 +474,36 @@ fn scaffold(
         chip,
         control_pins,
         frame_index,
-        time: clock.time.clone(),
+        phase: clock.phase.clone(),
         master_volume: WirePort::new(gated, SELECT, "Output"),
+        seek_hook: SeekHook::of(&clock),
+    }
+}
+
+/// Refuse `seek` under an order other than first to last: a jump lands on a
+/// frame of the track, not on a place in the order it is being played in.
+/// The CLI names the flags before the file is read; this guards any other
+/// caller.
+fn check_seek(seek: &SeekControls, playback: Playback) -> Result<(), String> {
+    if !seek.is_empty() && playback.order != PlayOrder::Forward {
+        return Err(
+            "seek buttons need a track played first to last, not a ping-pong or loop-range \
+             order"
+                .to_string(),
+        );
+    }
+    Ok(())
+}
+
+/// The seek graph and its buttons for `plan` on `sc`'s clock, when it was
+/// built seekable; nothing otherwise. Same placement as a video render's: the
+/// graph beyond everything else in the chip, the buttons beside the control
+/// buttons, so this comes after both and before `finish`.
+fn add_seek_controls(world: &mut World, sc: &mut Scaffold, plan: &SeekPlan) {
+    if let Some(hook) = &sc.seek_hook {
+        let buttons = seek::add_seek_graph(world, &mut sc.chip, hook, plan);
+        let anchor = crate::anim::controls::control_anchor(world);
+        crate::anim::controls::add_seek_buttons(world, &buttons, anchor);
     }
 }
 
@@ -652,6 +718,38 @@ fn build_stream_cascade(
 
 /// Build a world whose speaker cluster plays `track` back as a fixed band bank.
 pub fn build_speaker_world(track: &VoiceTrack, opts: &AudioOptions) -> Result<World, String> {
+    build_speaker_world_with_seek(track, opts, &SeekControls::default())
+}
+
+/// [`build_speaker_world`] with seek and skip buttons beside the control
+/// buttons ([`crate::anim::seek`]), moving the speakers' clock as they move a
+/// video's. Needs a first-to-last order; an empty `seek` is exactly
+/// [`build_speaker_world`].
+pub fn build_speaker_world_with_seek(
+    track: &VoiceTrack,
+    opts: &AudioOptions,
+    seek: &SeekControls,
+) -> Result<World, String> {
+    let mut world = World::new();
+    world.meta.bundle.description =
+        "Audio spectrum playback generated from an audio file".to_string();
+    add_band_bank(&mut world, track, opts, seek, None)?;
+    // Must be last: register_used_components only sees registered gates after
+    // finish publishes the chip's inner grid, else to_brz_vec fails with
+    // UnregisteredComponentType.
+    world.register_used_components();
+    Ok(world)
+}
+
+/// [`build_speaker_world`]'s cluster, chip and cascade, added to `world`; with
+/// a `host`, beside its screen and driving its `Frame` pins.
+fn add_band_bank(
+    world: &mut World,
+    track: &VoiceTrack,
+    opts: &AudioOptions,
+    seek: &SeekControls,
+    host: Option<&Host>,
+) -> Result<(), String> {
     if track.frame_count == 0 {
         return Err("audio track has 0 frames -- nothing to render".to_string());
     }
@@ -663,10 +761,8 @@ pub fn build_speaker_world(track: &VoiceTrack, opts: &AudioOptions) -> Result<Wo
         ));
     }
     check_attenuation(opts)?;
-
-    let mut world = World::new();
-    world.meta.bundle.description =
-        "Audio spectrum playback generated from an audio file".to_string();
+    opts.playback.check()?;
+    check_seek(seek, opts.playback)?;
 
     // --- 1. The speaker cluster on the main grid ----------------------------
     // Position is audible: bSpatialization=false stops panning, not distance
@@ -689,10 +785,10 @@ pub fn build_speaker_world(track: &VoiceTrack, opts: &AudioOptions) -> Result<Wo
         let position = if in_chip {
             speaker_inner_position(b, n_speakers)
         } else {
-            speaker_position(b, n_speakers)
+            beside(host, speaker_position(b, n_speakers))
         };
         speaker_ids.push(add_emitter(
-            &mut world,
+            world,
             asset_name.as_ref(),
             track.plan.pitches[b],
             position,
@@ -703,11 +799,13 @@ pub fn build_speaker_world(track: &VoiceTrack, opts: &AudioOptions) -> Result<Wo
 
     // --- 2. Chip, clock and the four input pins -----------------------------
     let mut sc = scaffold(
-        &mut world,
+        world,
         &speaker_ids,
         track.fps,
         track.frame_count,
-        opts.loop_playback,
+        opts.playback.with_loop(opts.loop_playback),
+        seek,
+        host,
     );
 
     // With --speakers-in-chip, place the deferred speakers on the chip's inner
@@ -722,7 +820,7 @@ pub fn build_speaker_world(track: &VoiceTrack, opts: &AudioOptions) -> Result<Wo
         .enumerate()
         .map(|(b, &speaker)| {
             // Below the audio select cascade (rows -4..-9), per the service doc.
-            volume_multiply(&mut world, &mut sc.chip, b, speaker, &sc.master_volume, -10)
+            volume_multiply(world, &mut sc.chip, b, speaker, &sc.master_volume, -10)
         })
         .collect();
 
@@ -736,7 +834,7 @@ pub fn build_speaker_world(track: &VoiceTrack, opts: &AudioOptions) -> Result<Wo
         .map(|(values, target)| FrameStream { values, target })
         .collect();
     build_stream_cascade(
-        &mut world,
+        world,
         &mut sc.chip,
         &sc.frame_index,
         track.frame_count,
@@ -750,16 +848,15 @@ pub fn build_speaker_world(track: &VoiceTrack, opts: &AudioOptions) -> Result<Wo
     // overlap check sees them.
     if opts.control_buttons {
         let (pause, restart, resume) = sc.control_pins;
-        let anchor = crate::anim::controls::control_anchor(&world);
-        crate::anim::controls::add_control_buttons(&mut world, pause, restart, resume, anchor);
+        let anchor = crate::anim::controls::control_anchor(world);
+        crate::anim::controls::add_control_buttons(world, pause, restart, resume, anchor);
     }
 
-    finish(&mut world, sc.chip)?;
-    // Must be last: register_used_components only sees registered gates after
-    // finish publishes the chip's inner grid, else to_brz_vec fails with
-    // UnregisteredComponentType.
-    world.register_used_components();
-    Ok(world)
+    // --- 6. Seek controls ---------------------------------------------------
+    // Audio never trims its source, so the track starts at source time 0.
+    add_seek_controls(world, &mut sc, &seek.plan(track.fps, track.frame_count, 0.0));
+
+    finish(world, sc.chip)
 }
 
 /// Build a world whose speakers track spectral peaks: both pitch and volume
@@ -774,6 +871,16 @@ pub fn build_speaker_world(track: &VoiceTrack, opts: &AudioOptions) -> Result<Wo
 /// settles it). The baked value is frame 0's, so a paused chip holds the
 /// first note rather than an arbitrary one.
 pub fn build_voice_world(streams: &VoiceStreams, opts: &AudioOptions) -> Result<World, String> {
+    build_voice_world_with_seek(streams, opts, &SeekControls::default())
+}
+
+/// [`build_voice_world`] with seek and skip buttons, as
+/// [`build_speaker_world_with_seek`] adds them to a bank.
+pub fn build_voice_world_with_seek(
+    streams: &VoiceStreams,
+    opts: &AudioOptions,
+    seek: &SeekControls,
+) -> Result<World, String> {
     if streams.voice_count() == 0 {
         return Err("a voice-mode track needs at least one voice".to_string());
     }
@@ -790,10 +897,125 @@ pub fn build_voice_world(streams: &VoiceStreams, opts: &AudioOptions) -> Result<
         streams.fps,
         streams.frame_count,
         opts,
+        seek,
         "Audio peak-tracking playback generated from an audio file",
     )
 }
 
+/// An analysed track in either render mode, for [`add_soundtrack`].
+pub enum Soundtrack {
+    Bank(VoiceTrack),
+    Voice(VoiceStreams),
+}
+
+impl Soundtrack {
+    pub fn fps(&self) -> f32 {
+        match self {
+            Soundtrack::Bank(t) => t.fps,
+            Soundtrack::Voice(s) => s.fps,
+        }
+    }
+
+    pub fn frame_count(&self) -> usize {
+        match self {
+            Soundtrack::Bank(t) => t.frame_count,
+            Soundtrack::Voice(s) => s.frame_count,
+        }
+    }
+
+    /// Cut or pad the track to exactly `frames` frames, so a clock sized for
+    /// the picture never reads past its arrays. Padding is silence; a padded
+    /// voice holds its last pitch, for the reason [`VoiceStreams::pitches`]
+    /// never drops to 0.
+    pub fn fit_to(&mut self, frames: usize) {
+        let fit = |row: &mut Vec<f64>, fill: f64| row.resize(frames, fill);
+        match self {
+            Soundtrack::Bank(t) => {
+                t.volumes.iter_mut().for_each(|v| fit(v, 0.0));
+                t.frame_count = frames;
+            }
+            Soundtrack::Voice(s) => {
+                for p in &mut s.pitches {
+                    let last = p.last().copied().unwrap_or(1.0);
+                    fit(p, last);
+                }
+                s.volumes.iter_mut().for_each(|v| fit(v, 0.0));
+                s.frame_count = frames;
+            }
+        }
+    }
+}
+
+/// Add `soundtrack`'s speakers to `world`, a screen already built with
+/// `external_clock`, and drive that screen from the speakers' clock.
+///
+/// The speakers, their chip and its clock are built exactly as
+/// [`build_speaker_world`] or [`build_voice_world`] would, moved along x past
+/// every brick on the main grid. The screen's `Frame` pins -- every chip input
+/// pin nothing writes to yet -- are then fed the clock's frame index, so one
+/// `Pause`, `Restart`, `Resume` or `Rate` reaches picture and sound alike.
+/// That index only lines the two up if the track was analysed at the
+/// screen's fps and fitted ([`Soundtrack::fit_to`]) to its frame count; the
+/// caller owns both.
+///
+/// `opts.control_buttons` wires the buttons to the speakers' clock, so the
+/// screen must have been built without its own.
+pub fn add_soundtrack(
+    world: &mut World,
+    soundtrack: &Soundtrack,
+    opts: &AudioOptions,
+) -> Result<(), String> {
+    let fed: std::collections::HashSet<usize> =
+        world.wires.iter().map(|w| w.target.brick_id).collect();
+    let frame_pins: Vec<usize> = world
+        .grids
+        .iter()
+        .flat_map(|(_, bricks)| bricks)
+        .filter(|b| {
+            b.components
+                .iter()
+                .any(|c| c.component_type().is_some_and(|t| t.to_string() == MICROCHIP_INPUT))
+        })
+        .filter_map(|b| b.id)
+        .filter(|id| !fed.contains(id))
+        .collect();
+    if frame_pins.is_empty() {
+        return Err(
+            "the screen has no unwired Frame pin for the soundtrack's clock to drive -- \
+             build it with an external clock"
+                .to_string(),
+        );
+    }
+    // Six speaker half-widths: the chip shell sits four back from the
+    // cluster's origin (see `scaffold`), and two more clear its own half.
+    let max_x = world.bricks.iter().map(|b| b.local_bounds().1.x).max().unwrap_or(0);
+    let host = Host { shift: max_x + speaker_half().x * 6, frame_pins };
+    match soundtrack {
+        Soundtrack::Bank(track) => {
+            add_band_bank(world, track, opts, &SeekControls::default(), Some(&host))
+        }
+        Soundtrack::Voice(streams) => {
+            if streams.voice_count() == 0 {
+                return Err("a voice-mode track needs at least one voice".to_string());
+            }
+            let synths = vec![opts.tonal_synth; streams.voice_count()];
+            let pitches: Vec<&[f64]> = streams.pitches.iter().map(Vec::as_slice).collect();
+            let volumes: Vec<&[f64]> = streams.volumes.iter().map(Vec::as_slice).collect();
+            add_pitch_volume_bank(
+                world,
+                &pitches,
+                &volumes,
+                &synths,
+                streams.fps,
+                streams.frame_count,
+                opts,
+                &SeekControls::default(),
+                Some(&host),
+            )
+        }
+    }
+}
+
 /// Build a world that plays a MIDI file with an EVENT-BASED playback circuit.
 ///
 /// Each speaker stores its notes as ONE quat array -- each element
@@ -808,8 +1030,17 @@ pub fn build_voice_world(streams: &VoiceStreams, opts: &AudioOptions) -> Result<
 /// Reuses the audio [`scaffold`] for the chip, clock, spatialization pins,
 /// pause-mute master volume (a frozen clock is silent) and control buttons; the
 /// per-speaker playhead is new and sits on service rows below the scaffold's.
-/// Looping feeds the playhead `Time mod duration`; playing once feeds raw `Time`
-/// and the index caps at the last event.
+/// The playback time is the clock's phase in seconds of the piece, so the
+/// clock's order, plays and stop all reach the playheads; the events are laid
+/// out along it by [`crate::midi::playhead::EventLayout`], which is where a
+/// ping-pong's way back and a loop range's second landing come from. On a
+/// clock with that landing, each playhead reads its `keys` at the head of its
+/// exec chain and a percussion lane strikes off a counter of its own, so a
+/// resync to a key is never a strike.
+///
+/// `opts.seek` moves the clock's frame offset as it does an `--audio-mode`
+/// song's, in seconds of the piece, and every frame a button jumps to is a
+/// landing too -- the skips to marks ([`SeekPlan::on_marks`]).
 pub fn build_midi_event_world(
     score: &crate::midi::MidiScore,
     opts: &crate::midi::MidiOptions,
@@ -821,7 +1052,8 @@ pub fn build_midi_event_world(
     const COMPARE_GREATER: &str = "BrickComponentType_WireGraph_Expr_CompareGreater";
     const COMPARE_LE: &str = "BrickComponentType_WireGraph_Expr_CompareLessOrEqual";
     const COMPARE_LESS: &str = "BrickComponentType_WireGraph_Expr_CompareLess";
-    use crate::anim::clock::MODULO;
+    use crate::anim::landing;
+    use crate::midi::playhead::{EventLayout, TICKS_PER_SECOND};
 
     if score.voices.is_empty() && score.percussion_lanes.is_empty() {
         return Err(
@@ -853,6 +1085,16 @@ pub fn build_midi_event_world(
         ..AudioOptions::default()
     };
     check_attenuation(&audio_opts)?;
+    let playback = opts.playback.with_loop(opts.loop_playback);
+    playback.check()?;
+    check_seek(&opts.seek, playback)?;
+    if opts.seek.playlist.is_some() {
+        return Err(
+            "a MIDI piece cannot take a playlist: its clips' loop points send the clock back \
+             to frames no note playhead can land on"
+                .to_string(),
+        );
+    }
 
     let n_speakers = score.voices.len();
     let mut world = World::new();
@@ -879,26 +1121,40 @@ pub fn build_midi_event_world(
     }
 
     // --- 2. Chip, clock, pins, pause-mute (shared scaffold) ------------------
-    // Feed the clock a real frame count at 60 fps times the playback rate, so
-    // its `Progress` (0..1) and `Length` (seconds) status output pins track this
-    // piece at the chosen speed -- the frame-index chain is otherwise unused
-    // here (the playhead reads `Time` directly below). A looping render frees
-    // the timer (Time counts up, the frame index wraps); a play-once render
-    // stops it at the end.
+    // Feed the clock a real frame count at 60 fps times the playback rate: a
+    // frame is a 60th of a second of the piece whatever the rate, so its
+    // `Progress` (0..1) and `Length` (seconds) status output pins track this
+    // piece at the chosen speed and the playhead reads its time off the phase
+    // below. A looping render frees the timer; a play-once or counted render
+    // stops it at the end of its last pass. A loop range is in seconds of the
+    // piece, so it is divided by the rate the clock's fps carries; the seek
+    // plan is too, and a tick is a frame of it at any rate. The skips go to
+    // marks, where the playheads can land.
     let rate = (opts.playback_rate as f64).max(0.01);
-    let clock_fps = 60.0f32 * rate as f32;
-    let frame_count = (score.duration_s * 60.0).round().max(1.0) as usize;
-    let mut sc = scaffold(&mut world, &speaker_ids, clock_fps, frame_count, opts.loop_playback);
+    let clock_fps = (TICKS_PER_SECOND * rate) as f32;
+    let frame_count = (score.duration_s * TICKS_PER_SECOND).round().max(1.0) as usize;
+    let playback = match playback.order {
+        PlayOrder::Range { start_s, end_s } => Playback {
+            order: PlayOrder::Range { start_s: start_s / rate, end_s: end_s / rate },
+            ..playback
+        },
+        _ => playback,
+    };
+    let plan = opts.seek.plan(TICKS_PER_SECOND as f32, frame_count, 0.0).on_marks();
+    let layout = EventLayout::of(&playback, clock_fps, frame_count).with_seeks(&plan, frame_count);
+    let mut sc =
+        scaffold(&mut world, &speaker_ids, clock_fps, frame_count, playback, &opts.seek, None);
     for brick in in_chip_speakers {
         sc.chip.add_brick(brick, speaker_half());
     }
 
     // Layout below the scaffold (clock 0, pins -2, pause-mute -3) and the
     // per-voice master-volume multiplies (row -4): the SHARED playhead gates
-    // (time scaling, the loop wrap and the backward-jump detector) fill one row,
-    // then every voice gets its OWN row below. So each voice reads as the same
-    // left-to-right strip, stacked one per row -- a clean repeating pattern
-    // rather than every voice's gates splooted end-to-end down a serpentine.
+    // (the time, and the backward-jump detector or the landing chain) fill one
+    // row, then every voice gets its OWN row below. So each voice reads as the
+    // same left-to-right strip, stacked one per row -- a clean repeating
+    // pattern rather than every voice's gates splooted end-to-end down a
+    // serpentine.
     const SHARED_ROW: i32 = -5;
     let mut shared_col = 0i32;
     let mut shared_pos = || {
@@ -907,41 +1163,63 @@ pub fn build_midi_event_world(
         p
     };
 
-    // Playback time in seconds: scale the clock's `Time` by the baked rate
-    // (identity at 1.0), then -- for a looping render -- wrap it at the
-    // duration. The clock's fps carries the same rate, so its Progress/Length
-    // stay in step with these note comparisons.
-    let scaled_time = if (rate - 1.0).abs() < 1e-9 {
-        sc.time.clone()
-    } else {
-        let m = gate(&mut sc.chip, "B_1x1_Gate_Expr_MathMultiply", MULTIPLY, shared_pos(), vec![(
-            "InputB",
-            Box::new(WireVariant::Number(rate)) as Box<dyn AsBrdbValue>,
+    // Playback time in seconds of the piece: the clock's phase, a frame per
+    // 60th of a second. Its order, plays, rate and stop are the clock's.
+    let seconds = gate(&mut sc.chip, "B_1x1_Gate_Expr_MathMultiply", MULTIPLY, shared_pos(), vec![(
+        "InputB",
+        Box::new(WireVariant::Number(1.0 / TICKS_PER_SECOND)) as Box<dyn AsBrdbValue>,
+    )]);
+    world.add_wire_connection(sc.phase.clone(), WirePort::new(seconds, MULTIPLY, "InputA"));
+    let play_time = WirePort::new(seconds, MULTIPLY, "Output");
+
+    // Shared resync signal, fanned out to every playhead: whether the time
+    // jumped backward (a restart or a loop wrap), against last tick's; or, on
+    // a clock with landings past the start, whether it is on one, and which.
+    let (resync, slot) = if layout.landings().is_plain() {
+        let prev = gate(&mut sc.chip, "B_1x1_Gate_Pseudo_BufferTicks", BUFFER_TICKS, shared_pos(), vec![(
+            "TicksToWait",
+            Box::new(1i32) as Box<dyn AsBrdbValue>,
         )]);
-        world.add_wire_connection(sc.time.clone(), WirePort::new(m, MULTIPLY, "InputA"));
-        WirePort::new(m, MULTIPLY, "Output")
+        world.add_wire_connection(play_time.clone(), WirePort::new(prev, BUFFER_TICKS, "Input"));
+        let decreased = gate(&mut sc.chip, "B_1x1_Gate_Expr_CompareLess", COMPARE_LESS, shared_pos(), vec![]);
+        world.add_wire_connection(play_time.clone(), WirePort::new(decreased, COMPARE_LESS, "InputA"));
+        world.add_wire_connection(WirePort::new(prev, BUFFER_TICKS, "Output"), WirePort::new(decreased, COMPARE_LESS, "InputB"));
+        (WirePort::new(decreased, COMPARE_LESS, "bOutput"), None)
+    } else {
+        let first = shared_col;
+        let landed = landing::add_landing_graph(&mut world, &mut sc.chip, &sc.phase, layout.landings(), |col| {
+            service(first + col, SHARED_ROW)
+        });
+        (landed.landed, Some(landed.slot))
     };
-    let play_time = if opts.loop_playback {
-        let m = gate(&mut sc.chip, "B_1x1_Gate_Expr_MathModuloFloored", MODULO, shared_pos(), vec![(
-            "InputB",
-            Box::new(WireVariant::Number(score.duration_s)) as Box<dyn AsBrdbValue>,
+    // `keys[slot]`, read at the head of a playhead's exec chain: the `Get`'s
+    // `Value` and `ExecOut`, or the chain's own start where there is no slot.
+    let read_key = |world: &mut World, chip: &mut Chip, keys: Vec<i64>, exec: WirePort, pos: &mut dyn FnMut() -> Position| {
+        let Some(slot) = &slot else {
+            return (None, exec);
+        };
+        let arr = gate(chip, "B_1x1_Gate_Variable_Array", ARRAY_VAR, pos(), vec![(
+            "Value",
+            Box::new(WireArrayVariant::Int64Array(keys)) as Box<dyn AsBrdbValue>,
         )]);
-        world.add_wire_connection(scaled_time.clone(), WirePort::new(m, MODULO, "InputA"));
-        WirePort::new(m, MODULO, "Output")
-    } else {
-        scaled_time
+        let get = gate(chip, "B_1x1_Gate_Exec_ArrayVar_Get", ARRAY_GET, pos(), vec![]);
+        world.add_wire_connection(WirePort::new(arr, ARRAY_VAR, "ArrayVarRef"), WirePort::new(get, ARRAY_GET, "ArrayVarRef"));
+        world.add_wire_connection(slot.clone(), WirePort::new(get, ARRAY_GET, "Index"));
+        world.add_wire_connection(exec, WirePort::new(get, ARRAY_GET, "Exec"));
+        (Some(WirePort::new(get, ARRAY_GET, "Value")), WirePort::new(get, ARRAY_GET, "ExecOut"))
+    };
+    // The resync's `Var_Set`: to the key where there is one, else to 0.
+    let set_to = |world: &mut World, chip: &mut Chip, var: usize, key: Option<WirePort>, at: Position| {
+        let set = gate(chip, "B_1x1_Gate_Exec_Var_Set", VAR_SET, at, match key {
+            Some(_) => vec![],
+            None => vec![("Value", Box::new(WireVariant::Int(0)) as Box<dyn AsBrdbValue>)],
+        });
+        world.add_wire_connection(WirePort::new(var, VAR, "VarRef"), WirePort::new(set, VAR_SET, "VarRef"));
+        if let Some(key) = key {
+            world.add_wire_connection(key, WirePort::new(set, VAR_SET, "Value"));
+        }
+        set
     };
-
-    // Shared reset signal: last tick's playback time, and whether it jumped
-    // backward (a restart or a loop wrap). Fanned out to every speaker's reset.
-    let prev = gate(&mut sc.chip, "B_1x1_Gate_Pseudo_BufferTicks", BUFFER_TICKS, shared_pos(), vec![(
-        "TicksToWait",
-        Box::new(1i32) as Box<dyn AsBrdbValue>,
-    )]);
-    world.add_wire_connection(play_time.clone(), WirePort::new(prev, BUFFER_TICKS, "Input"));
-    let decreased = gate(&mut sc.chip, "B_1x1_Gate_Expr_CompareLess", COMPARE_LESS, shared_pos(), vec![]);
-    world.add_wire_connection(play_time.clone(), WirePort::new(decreased, COMPARE_LESS, "InputA"));
-    world.add_wire_connection(WirePort::new(prev, BUFFER_TICKS, "Output"), WirePort::new(decreased, COMPARE_LESS, "InputB"));
 
     // --- 3. Per-speaker playhead ---------------------------------------------
     for (v, voice) in score.voices.iter().enumerate() {
@@ -956,12 +1234,12 @@ pub fn build_midi_event_world(
             col += 1;
             p
         };
-        // The events, packed one quat each: (start, end, pitch, vol) = (X,Y,Z,W).
-        let events: Vec<(f64, f64, f64, f64)> = voice
-            .notes
-            .iter()
-            .map(|n| (n.start_s, n.end_s, n.pitch, n.volume))
-            .collect();
+        // The events, laid out along the phase and packed one quat each:
+        // (start, end, pitch, vol) = (X,Y,Z,W).
+        let notes = layout.notes(&voice.notes);
+        let keys = layout.note_keys(&notes);
+        let events: Vec<(f64, f64, f64, f64)> =
+            notes.iter().map(|n| (n.start_s, n.end_s, n.pitch, n.volume)).collect();
         let events_arr = gate(&mut sc.chip, "B_1x1_Gate_Variable_Array", ARRAY_VAR, vpos(), vec![(
             "Value",
             Box::new(WireArrayVariant::QuatArray(events)) as Box<dyn AsBrdbValue>,
@@ -975,12 +1253,19 @@ pub fn build_midi_event_world(
         // chaining -- the reset/advance branches would break a shared chain).
         let detector = gate(&mut sc.chip, "B_1x1_Gate_Expr_ChangeDetectorExec", CHANGE_DETECTOR, vpos(), vec![]);
         world.add_wire_connection(play_time.clone(), WirePort::new(detector, CHANGE_DETECTOR, "Input"));
+        let (key, exec) = read_key(
+            &mut world,
+            &mut sc.chip,
+            keys,
+            WirePort::new(detector, CHANGE_DETECTOR, "OnChanged"),
+            &mut vpos,
+        );
 
         // Read the current event and break it into its four floats.
         let get = gate(&mut sc.chip, "B_1x1_Gate_Exec_ArrayVar_Get", ARRAY_GET, vpos(), vec![]);
         world.add_wire_connection(WirePort::new(events_arr, ARRAY_VAR, "ArrayVarRef"), WirePort::new(get, ARRAY_GET, "ArrayVarRef"));
         world.add_wire_connection(WirePort::new(idx_var, VAR, "Value"), WirePort::new(get, ARRAY_GET, "Index"));
-        world.add_wire_connection(WirePort::new(detector, CHANGE_DETECTOR, "OnChanged"), WirePort::new(get, ARRAY_GET, "Exec"));
+        world.add_wire_connection(exec, WirePort::new(get, ARRAY_GET, "Exec"));
         let split = gate(&mut sc.chip, "B_1x1_Gate_Expr_SplitQuaternion", SPLIT_QUAT, vpos(), vec![]);
         world.add_wire_connection(WirePort::new(get, ARRAY_GET, "Value"), WirePort::new(split, SPLIT_QUAT, "Input"));
         let start = WirePort::new(split, SPLIT_QUAT, "X");
@@ -988,15 +1273,12 @@ pub fn build_midi_event_world(
         let pitch = WirePort::new(split, SPLIT_QUAT, "Z");
         let vol = WirePort::new(split, SPLIT_QUAT, "W");
 
-        // Reset first: if playback time jumped backward, set the index to 0.
+        // Resync first: if playback time jumped backward, set the index to 0;
+        // on a landing, to this voice's key for it.
         let br_reset = gate(&mut sc.chip, "B_1x1_Gate_Exec_Branch", BRANCH, vpos(), vec![]);
-        world.add_wire_connection(WirePort::new(decreased, COMPARE_LESS, "bOutput"), WirePort::new(br_reset, BRANCH, "bCond"));
+        world.add_wire_connection(resync.clone(), WirePort::new(br_reset, BRANCH, "bCond"));
         world.add_wire_connection(WirePort::new(get, ARRAY_GET, "ExecOut"), WirePort::new(br_reset, BRANCH, "Exec"));
-        let var_set = gate(&mut sc.chip, "B_1x1_Gate_Exec_Var_Set", VAR_SET, vpos(), vec![(
-            "Value",
-            Box::new(WireVariant::Int(0)) as Box<dyn AsBrdbValue>,
-        )]);
-        world.add_wire_connection(WirePort::new(idx_var, VAR, "VarRef"), WirePort::new(var_set, VAR_SET, "VarRef"));
+        let var_set = set_to(&mut world, &mut sc.chip, idx_var, key, vpos());
         world.add_wire_connection(WirePort::new(br_reset, BRANCH, "ExecOutA"), WirePort::new(var_set, VAR_SET, "Exec"));
 
         // Advance: on the no-reset exec, if Time > end and in bounds, idx += 1.
@@ -1074,10 +1356,13 @@ pub fn build_midi_event_world(
             p
         };
 
-        // Strike times, and the playhead index into them (starts 0).
+        // Strike times, laid out along the phase, and the playhead index into
+        // them (starts 0).
+        let hits = layout.hits(&lane.hits);
+        let keys = layout.hit_keys(&hits);
         let hits_arr = gate(&mut sc.chip, "B_1x1_Gate_Variable_Array", ARRAY_VAR, vpos(), vec![(
             "Value",
-            Box::new(WireArrayVariant::DoubleArray(lane.hits.clone())) as Box<dyn AsBrdbValue>,
+            Box::new(WireArrayVariant::DoubleArray(hits)) as Box<dyn AsBrdbValue>,
         )]);
         let idx_var = gate(&mut sc.chip, "B_1x1_Gate_Variable", VAR, vpos(), vec![(
             "Value",
@@ -1085,23 +1370,29 @@ pub fn build_midi_event_world(
         )]);
         let detector = gate(&mut sc.chip, "B_1x1_Gate_Expr_ChangeDetectorExec", CHANGE_DETECTOR, vpos(), vec![]);
         world.add_wire_connection(play_time.clone(), WirePort::new(detector, CHANGE_DETECTOR, "Input"));
+        let (key, exec) = read_key(
+            &mut world,
+            &mut sc.chip,
+            keys,
+            WirePort::new(detector, CHANGE_DETECTOR, "OnChanged"),
+            &mut vpos,
+        );
 
         // Read the current strike time (bOutOfBounds once the index passes the end).
         let get = gate(&mut sc.chip, "B_1x1_Gate_Exec_ArrayVar_Get", ARRAY_GET, vpos(), vec![]);
         world.add_wire_connection(WirePort::new(hits_arr, ARRAY_VAR, "ArrayVarRef"), WirePort::new(get, ARRAY_GET, "ArrayVarRef"));
         world.add_wire_connection(WirePort::new(idx_var, VAR, "Value"), WirePort::new(get, ARRAY_GET, "Index"));
-        world.add_wire_connection(WirePort::new(detector, CHANGE_DETECTOR, "OnChanged"), WirePort::new(get, ARRAY_GET, "Exec"));
+        world.add_wire_connection(exec, WirePort::new(get, ARRAY_GET, "Exec"));
 
-        // Reset first: if playback time jumped backward (a loop wrap), index = 0.
-        // Setting it to 0 does not fire Play (0 is not non-zero).
+        // Resync first: if playback time jumped backward (a loop wrap), index =
+        // 0, which does not fire Play (0 is not non-zero); on a landing, index
+        // = this lane's key, which would -- so there the strikes are counted
+        // apart (below).
+        let striking_off_index = key.is_none();
         let br_reset = gate(&mut sc.chip, "B_1x1_Gate_Exec_Branch", BRANCH, vpos(), vec![]);
-        world.add_wire_connection(WirePort::new(decreased, COMPARE_LESS, "bOutput"), WirePort::new(br_reset, BRANCH, "bCond"));
+        world.add_wire_connection(resync.clone(), WirePort::new(br_reset, BRANCH, "bCond"));
         world.add_wire_connection(WirePort::new(get, ARRAY_GET, "ExecOut"), WirePort::new(br_reset, BRANCH, "Exec"));
-        let var_set = gate(&mut sc.chip, "B_1x1_Gate_Exec_Var_Set", VAR_SET, vpos(), vec![(
-            "Value",
-            Box::new(WireVariant::Int(0)) as Box<dyn AsBrdbValue>,
-        )]);
-        world.add_wire_connection(WirePort::new(idx_var, VAR, "VarRef"), WirePort::new(var_set, VAR_SET, "VarRef"));
+        let var_set = set_to(&mut world, &mut sc.chip, idx_var, key, vpos());
         world.add_wire_connection(WirePort::new(br_reset, BRANCH, "ExecOutA"), WirePort::new(var_set, VAR_SET, "Exec"));
 
         // Advance: on the no-reset exec, if playback time has reached the strike
@@ -1123,8 +1414,25 @@ pub fn build_midi_event_world(
         world.add_wire_connection(WirePort::new(br_oob, BRANCH, "ExecOutB"), WirePort::new(inc, VAR_INCREMENT, "Exec"));
 
         // The incrementing index fires the oneshot: one strike per advance.
+        // Where a resync can set it to a key instead, a second `Var` counts
+        // the advances alone, stepped off the same exec.
+        let strikes = if striking_off_index {
+            idx_var
+        } else {
+            let strikes = gate(&mut sc.chip, "B_1x1_Gate_Variable", VAR, vpos(), vec![(
+                "Value",
+                Box::new(WireVariant::Int(0)) as Box<dyn AsBrdbValue>,
+            )]);
+            let count = gate(&mut sc.chip, "B_1x1_Gate_Exec_Var_Increment", VAR_INCREMENT, vpos(), vec![(
+                "Value",
+                Box::new(WireVariant::Int(1)) as Box<dyn AsBrdbValue>,
+            )]);
+            world.add_wire_connection(WirePort::new(strikes, VAR, "VarRef"), WirePort::new(count, VAR_INCREMENT, "VarRef"));
+            world.add_wire_connection(WirePort::new(br_oob, BRANCH, "ExecOutB"), WirePort::new(count, VAR_INCREMENT, "Exec"));
+            strikes
+        };
         world.add_wire_connection(
-            WirePort::new(idx_var, VAR, "Value"),
+            WirePort::new(strikes, VAR, "Value"),
             WirePort::new(emitter, crate::audio::percussion::ONESHOT_EMITTER, crate::audio::percussion::PLAY_PORT),
         );
     }
@@ -1135,6 +1443,7 @@ pub fn build_midi_event_world(
         let anchor = crate::anim::controls::control_anchor(&world);
         crate::anim::controls::add_control_buttons(&mut world, pause, restart, resume, anchor);
     }
+    add_seek_controls(&mut world, &mut sc, &plan);
 
     finish(&mut world, sc.chip)?;
     world.register_used_components();
@@ -1206,7 +1515,8 @@ pub fn build_playhead_probe_world() -> Result<World, String> {
         &mut chip,
         1.0,
         notes.len().max(1),
-        true,
+        Playback::default(),
+        false,
         Position { x: GATE_HALF.x, y: 200, z: GATE_HALF.z },
     );
     let time = || clock.time.clone();
@@ -1378,6 +1688,7 @@ pub fn build_playhead_probe_world() -> Result<World, String> {
 /// multiply, all banked by the shared frame cascade. `opts` supplies only the
 /// spatialization/playback fields (inner/max radius, speakers-in-chip, loop,
 /// control buttons, bank size); `fps` and `frame_count` come from the streams.
+#[allow(clippy::too_many_arguments)]
 fn build_pitch_volume_world(
     pitches: &[&[f64]],
     volumes: &[&[f64]],
@@ -1385,8 +1696,42 @@ fn build_pitch_volume_world(
     fps: f32,
     frame_count: usize,
     opts: &AudioOptions,
+    seek: &SeekControls,
     description: &str,
 ) -> Result<World, String> {
+    let mut world = World::new();
+    world.meta.bundle.description = description.to_string();
+    add_pitch_volume_bank(
+        &mut world,
+        pitches,
+        volumes,
+        synths,
+        fps,
+        frame_count,
+        opts,
+        seek,
+        None,
+    )?;
+    // Must be last, after finish publishes the chip's inner grid -- see
+    // [`build_speaker_world`].
+    world.register_used_components();
+    Ok(world)
+}
+
+/// [`build_pitch_volume_world`]'s speakers, chip and cascade, added to
+/// `world`; with a `host`, beside its screen and driving its `Frame` pins.
+#[allow(clippy::too_many_arguments)]
+fn add_pitch_volume_bank(
+    world: &mut World,
+    pitches: &[&[f64]],
+    volumes: &[&[f64]],
+    synths: &[SynthWave],
+    fps: f32,
+    frame_count: usize,
+    opts: &AudioOptions,
+    seek: &SeekControls,
+    host: Option<&Host>,
+) -> Result<(), String> {
     if frame_count == 0 {
         return Err("audio track has 0 frames -- nothing to render".to_string());
     }
@@ -1428,9 +1773,8 @@ fn build_pitch_volume_world(
         }
     }
     check_attenuation(opts)?;
-
-    let mut world = World::new();
-    world.meta.bundle.description = description.to_string();
+    opts.playback.check()?;
+    check_seek(seek, opts.playback)?;
 
     // --- 1. The speaker cluster (main grid, or the chip's inner grid) -------
     let in_chip = opts.speakers_in_chip;
@@ -1442,10 +1786,10 @@ fn build_pitch_volume_world(
         let position = if in_chip {
             speaker_inner_position(v, n)
         } else {
-            speaker_position(v, n)
+            beside(host, speaker_position(v, n))
         };
         speaker_ids.push(add_emitter(
-            &mut world,
+            world,
             synths[v].asset().as_ref(),
             pitches[v][0] as f32,
             position,
@@ -1455,7 +1799,8 @@ fn build_pitch_volume_world(
     }
 
     // --- 2. Chip, clock and the four input pins -----------------------------
-    let mut sc = scaffold(&mut world, &speaker_ids, fps, frame_count, opts.loop_playback);
+    let playback = opts.playback.with_loop(opts.loop_playback);
+    let mut sc = scaffold(world, &speaker_ids, fps, frame_count, playback, seek, host);
 
     // Place the deferred in-chip speakers now the chip exists (a no-op for the
     // default beside-the-chip layout). See `build_speaker_world`.
@@ -1468,7 +1813,7 @@ fn build_pitch_volume_world(
         .iter()
         .enumerate()
         .map(|(v, &speaker)| {
-            volume_multiply(&mut world, &mut sc.chip, v, speaker, &sc.master_volume, -10)
+            volume_multiply(world, &mut sc.chip, v, speaker, &sc.master_volume, -10)
         })
         .collect();
 
@@ -1488,7 +1833,7 @@ fn build_pitch_volume_world(
         });
     }
     build_stream_cascade(
-        &mut world,
+        world,
         &mut sc.chip,
         &sc.frame_index,
         frame_count,
@@ -1501,15 +1846,14 @@ fn build_pitch_volume_world(
     // control pins, before finish so its overlap check sees them.
     if opts.control_buttons {
         let (pause, restart, resume) = sc.control_pins;
-        let anchor = crate::anim::controls::control_anchor(&world);
-        crate::anim::controls::add_control_buttons(&mut world, pause, restart, resume, anchor);
+        let anchor = crate::anim::controls::control_anchor(world);
+        crate::anim::controls::add_control_buttons(world, pause, restart, resume, anchor);
     }
 
-    finish(&mut world, sc.chip)?;
-    // Must be last, after finish publishes the chip's inner grid -- see
-    // [`build_speaker_world`].
-    world.register_used_components();
-    Ok(world)
+    // --- 6. Seek controls ---------------------------------------------------
+    add_seek_controls(world, &mut sc, &seek.plan(fps, frame_count, 0.0));
+
+    finish(world, sc.chip)
 }
 
 /// Resolve a synth asset name to the value `AudioDescriptor` expects.
@@ -1719,4 +2063,36 @@ mod tests {
         let opts = crate::midi::MidiOptions { speakers_in_chip: false, ..Default::default() };
         build_midi_event_world(&score, &opts).expect("the beside-the-chip layout must build");
     }
+
+    /// Fitting cuts a long track and pads a short one with silence; a padded
+    /// voice keeps its last pitch rather than dropping out of the legal range.
+    #[test]
+    fn fitting_a_soundtrack_cuts_or_pads_every_row() {
+        let o = AudioOptions::default();
+        let mut bank = Soundtrack::Bank(bank_track(&o, 10));
+        bank.fit_to(4);
+        let Soundtrack::Bank(t) = &bank else { unreachable!() };
+        assert_eq!(t.frame_count, 4);
+        assert!(t.volumes.iter().all(|v| v.len() == 4));
+
+        let mut streams = voice_streams(2, 3, 30.0);
+        streams.pitches[1][2] = 2.0;
+        let mut voice = Soundtrack::Voice(streams);
+        voice.fit_to(5);
+        let Soundtrack::Voice(s) = &voice else { unreachable!() };
+        assert_eq!(voice.frame_count(), 5);
+        assert_eq!(s.pitches[1], [1.0, 1.0, 2.0, 2.0, 2.0]);
+        assert_eq!(s.volumes[0], [0.5, 0.5, 0.5, 0.0, 0.0]);
+    }
+
+    /// Without an unwired input pin there is nothing for the speakers' clock
+    /// to drive, and the soundtrack is refused rather than left free-running.
+    #[test]
+    fn a_soundtrack_needs_a_frame_pin_to_drive() {
+        let o = AudioOptions::default();
+        let mut world = World::new();
+        let err = add_soundtrack(&mut world, &Soundtrack::Bank(bank_track(&o, 4)), &o)
+            .expect_err("no screen, no pin");
+        assert!(err.contains("Frame pin"), "{err}");
+    }
 }
@@ -3,6 +3,7 @@ use super::bands::{BandKind, BandPlan};
 use super::source::AudioSource;
 use super::speakers::{DEFAULT_INNER_RADIUS, DEFAULT_MAX_DISTANCE};
 use super::stft::{frame_count_for, hop_for, StftStream};
+use crate::anim::clock::Playback;
 use crate::anim::pack::BANK_FRAMES;
 use crate::progress::{FrameTotal, Progress};
 use brdb::BString;
@@ -156,6 +157,10 @@ pub struct AudioOptions {
     /// which the audio path refuses outright, this one is read by
     /// `speakers::build_speaker_world`.
     pub loop_playback: bool,
+    /// The order the track plays in and how many times, on the same clock
+    /// ([`crate::anim::clock::Playback`]); [`Self::loop_playback`] off is one
+    /// play of it. Spectral frames play backward on a ping-pong's way back.
+    pub playback: Playback,
     /// Place the speaker cluster inside the microchip's own inner grid rather
     /// than beside it on the world's main grid (`false`, the default). On,
     /// the cross-grid remote wires to each band become same-grid internal
@@ -203,6 +208,7 @@ impl Default for AudioOptions {
             max_frames: BANK_FRAMES * 16,
             external_clock: false,
             loop_playback: true,
+            playback: Playback::default(),
             speakers_in_chip: false,
             control_buttons: true,
             tonal_synth: SynthWave::Sine,
@@ -0,0 +1,444 @@
+//! Palette reduction for still images, with optional dithering.
+//!
+//! `--img` puts every pixel's exact colour into a brick, and the quadtree can
+//! only merge pixels that match exactly -- so a photograph, where almost no
+//! two neighbours do, comes out as nearly one brick per pixel. Snapping the
+//! picture to a small palette first gives the merger long runs of one colour
+//! to work with.
+//!
+//! The palette is either a median-cut [`Palette`] of the picture itself
+//! (`--colors N`, the same quantizer the animated text mode uses) or a fixed
+//! set of colours read from a file (`--palette`). Dithering trades some of
+//! the merge back for a picture that reads as more colours than it has:
+//! Floyd–Steinberg scatters the error as noise, which costs the most bricks;
+//! ordered (Bayer) dithering lays it out as a regular pattern, which costs
+//! fewer and suits the blocky look. Without dithering the runs are longest.
+//!
+//! The same patterns serve the monochrome text modes, which cut a picture to
+//! on/off dots: [`threshold`] is the two-level case, measured against a
+//! brightness cut instead of a palette.
+
+use crate::anim::palette::Palette;
+use image::RgbaImage;
+use std::path::Path;
+
+/// The most colours a palette file may hold. A palette is a short list; an
+/// "image palette" with thousands of colours is a photograph given by mistake,
+/// and mapping every pixel against it would reduce nothing.
+pub const MAX_PALETTE_FILE_COLORS: usize = 256;
+
+/// How the quantization error is spread.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum Dither {
+    /// Every pixel to its nearest entry: the longest runs, and banding.
+    #[default]
+    None,
+    /// Error diffusion to the unvisited neighbours, serpentine.
+    FloydSteinberg,
+    /// An 8x8 Bayer threshold pattern added before the nearest-entry lookup.
+    Ordered,
+    /// Atkinson's error diffusion: only three quarters of the error, spread
+    /// wider. Lighter noise, and highlights and shadows clip clean.
+    Atkinson,
+}
+
+impl Dither {
+    pub const ALL: [Dither; 4] = [
+        Dither::None,
+        Dither::Ordered,
+        Dither::FloydSteinberg,
+        Dither::Atkinson,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Dither::None => "none",
+            Dither::Ordered => "ordered",
+            Dither::FloydSteinberg => "floyd-steinberg",
+            Dither::Atkinson => "atkinson",
+        }
+    }
+
+    /// Parse a `--dither` value: a [`Dither::name`], or `bayer`/`fs`.
+    pub fn from_name(s: &str) -> Option<Dither> {
+        match s {
+            "bayer" => Some(Dither::Ordered),
+            "fs" => Some(Dither::FloydSteinberg),
+            _ => Dither::ALL.into_iter().find(|d| d.name() == s),
+        }
+    }
+
+    /// The error-diffusion kernel as (ahead, down, share of the error), with
+    /// `ahead` mirrored on the right-to-left rows. Empty for the others.
+    fn kernel(&self) -> &'static [(i64, i64, f32)] {
+        match self {
+            Dither::FloydSteinberg => &FLOYD_STEINBERG,
+            Dither::Atkinson => &ATKINSON,
+            Dither::None | Dither::Ordered => &[],
+        }
+    }
+}
+
+const FLOYD_STEINBERG: [(i64, i64, f32); 4] = [
+    (1, 0, 7.0 / 16.0),
+    (-1, 1, 3.0 / 16.0),
+    (0, 1, 5.0 / 16.0),
+    (1, 1, 1.0 / 16.0),
+];
+
+const ATKINSON: [(i64, i64, f32); 6] = [
+    (1, 0, 1.0 / 8.0),
+    (2, 0, 1.0 / 8.0),
+    (-1, 1, 1.0 / 8.0),
+    (0, 1, 1.0 / 8.0),
+    (1, 1, 1.0 / 8.0),
+    (0, 2, 1.0 / 8.0),
+];
+
+/// The 8x8 Bayer matrix, values 0..64.
+const BAYER_8: [[u8; 8]; 8] = [
+    [0, 32, 8, 40, 2, 34, 10, 42],
+    [48, 16, 56, 24, 50, 18, 58, 26],
+    [12, 44, 4, 36, 14, 46, 6, 38],
+    [60, 28, 52, 20, 62, 30, 54, 22],
+    [3, 35, 11, 43, 1, 33, 9, 41],
+    [51, 19, 59, 27, 49, 17, 57, 25],
+    [15, 47, 7, 39, 13, 45, 5, 37],
+    [63, 31, 55, 23, 61, 29, 53, 21],
+];
+
+/// Snap every visible pixel of `image` to `palette`, spreading the error as
+/// `dither` says. Fully transparent pixels are left alone -- they are never
+/// drawn, and `--cull` drops them -- and neither give nor take error. Alpha is
+/// kept as it was. An empty palette returns the image unchanged.
+pub fn quantize(image: &RgbaImage, palette: &mut Palette, dither: Dither) -> RgbaImage {
+    let mut out = image.clone();
+    if palette.is_empty() {
+        return out;
+    }
+    let (w, h) = image.dimensions();
+    match dither {
+        Dither::None => {
+            for p in out.pixels_mut() {
+                if p.0[3] > 0 {
+                    let [r, g, b] = palette.map([p.0[0], p.0[1], p.0[2]]);
+                    p.0[..3].copy_from_slice(&[r, g, b]);
+                }
+            }
+        }
+        Dither::Ordered => {
+            // The pattern's amplitude is about one palette step. A median-cut
+            // palette clusters where the picture spends its pixels, so the
+            // step is measured -- the mean distance from each entry to its
+            // nearest neighbour, per channel -- rather than assumed from an
+            // even spread through the RGB cube, which overshoots badly.
+            let spread = palette_step(palette.entries());
+            for (x, y, p) in out.enumerate_pixels_mut() {
+                if p.0[3] == 0 {
+                    continue;
+                }
+                let t = (BAYER_8[(y % 8) as usize][(x % 8) as usize] as f32 + 0.5) / 64.0 - 0.5;
+                let rgb = [0, 1, 2]
+                    .map(|c| (p.0[c] as f32 + t * spread).round().clamp(0.0, 255.0) as u8);
+                let [r, g, b] = palette.map(rgb);
+                p.0[..3].copy_from_slice(&[r, g, b]);
+            }
+        }
+        Dither::FloydSteinberg | Dither::Atkinson => {
+            // Serpentine: every other row runs right to left, so the error
+            // does not drift in one direction and streak.
+            let mut error = vec![[0f32; 3]; (w * h) as usize];
+            for y in 0..h {
+                let reverse = y % 2 == 1;
+                for i in 0..w {
+                    let x = if reverse { w - 1 - i } else { i };
+                    let at = (x + y * w) as usize;
+                    let p = out.get_pixel_mut(x, y);
+                    if p.0[3] == 0 {
+                        continue;
+                    }
+                    let want =
+                        [0, 1, 2].map(|c| (p.0[c] as f32 + error[at][c]).clamp(0.0, 255.0));
+                    let got = palette.map(want.map(|v| v.round() as u8));
+                    p.0[..3].copy_from_slice(&got);
+                    let diff = [0, 1, 2].map(|c| want[c] - got[c] as f32);
+
+                    let ahead: i64 = if reverse { -1 } else { 1 };
+                    for &(dx, dy, share) in dither.kernel() {
+                        let (nx, ny) = (x as i64 + dx * ahead, y as i64 + dy);
+                        if nx < 0 || nx >= w as i64 || ny >= h as i64 {
+                            continue;
+                        }
+                        if image.get_pixel(nx as u32, ny as u32).0[3] == 0 {
+                            continue;
+                        }
+                        let n = (nx + ny * w as i64) as usize;
+                        for c in 0..3 {
+                            error[n][c] += diff[c] * share;
+                        }
+                    }
+                }
+            }
+        }
+    }
+    out
+}
+
+/// Cut a greyscale picture to on/off dots: a level at least `cut` is on.
+/// `levels` is row-major, `width` pixels to a row, with `None` for pixels
+/// that are never drawn -- they are off, and neither give nor take error.
+///
+/// Dithered, the dots keep the picture's shading: error diffusion carries
+/// each dot's rounding to its neighbours, and ordered dithering moves the
+/// cut up and down by a Bayer pattern a full 0..255 step tall, so a flat grey
+/// comes out as that share of dots either way.
+pub fn threshold(levels: &[Option<u8>], width: usize, cut: u8, dither: Dither) -> Vec<bool> {
+    let cut = cut as f32;
+    match dither {
+        Dither::None => levels
+            .iter()
+            .map(|l| l.is_some_and(|v| v as f32 >= cut))
+            .collect(),
+        Dither::Ordered => levels
+            .iter()
+            .enumerate()
+            .map(|(i, l)| {
+                let (x, y) = (i % width, i / width);
+                let t = (BAYER_8[y % 8][x % 8] as f32 + 0.5) / 64.0 - 0.5;
+                l.is_some_and(|v| v as f32 + t * 255.0 >= cut)
+            })
+            .collect(),
+        Dither::FloydSteinberg | Dither::Atkinson => {
+            let height = levels.len().checked_div(width).unwrap_or(0);
+            let mut on = vec![false; levels.len()];
+            let mut error = vec![0f32; levels.len()];
+            for y in 0..height {
+                let reverse = y % 2 == 1;
+                for i in 0..width {
+                    let x = if reverse { width - 1 - i } else { i };
+                    let at = x + y * width;
+                    let Some(v) = levels[at] else {
+                        continue;
+                    };
+                    let want = v as f32 + error[at];
+                    on[at] = want >= cut;
+                    let diff = want - if on[at] { 255.0 } else { 0.0 };
+
+                    let ahead: i64 = if reverse { -1 } else { 1 };
+                    for &(dx, dy, share) in dither.kernel() {
+                        let (nx, ny) = (x as i64 + dx * ahead, (y as i64 + dy) as usize);
+                        if nx < 0 || nx >= width as i64 || ny >= height {
+                            continue;
+                        }
+                        let n = nx as usize + ny * width;
+                        if levels[n].is_some() {
+                            error[n] += diff * share;
+                        }
+                    }
+                }
+            }
+            on
+        }
+    }
+}
+
+/// The mean per-channel distance from each entry to its nearest neighbour.
+fn palette_step(entries: &[[u8; 3]]) -> f32 {
+    if entries.len() < 2 {
+        return 0.0;
+    }
+    let total: f32 = entries
+        .iter()
+        .enumerate()
+        .map(|(i, a)| {
+            entries
+                .iter()
+                .enumerate()
+                .filter(|&(j, _)| j != i)
+                .map(|(_, b)| {
+                    (0..3)
+                        .map(|c| (a[c] as f32 - b[c] as f32).powi(2))
+                        .sum::<f32>()
+                })
+                .fold(f32::INFINITY, f32::min)
+                .sqrt()
+        })
+        .sum();
+    total / entries.len() as f32 / 3f32.sqrt()
+}
+
+/// The colours of a palette file, as a [`Palette`].
+///
+/// An image (PNG/JPG) contributes its distinct opaque colours, so a swatch
+/// strip or a screenshot of the in-game colour set works as-is. Anything else
+/// is read as text: see [`parse_palette`].
+pub fn read_palette_file(file: &Path) -> Result<Palette, String> {
+    let ext = file
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase());
+    let colors = match ext.as_deref() {
+        Some("png") | Some("jpg") | Some("jpeg") => {
+            let image = image::open(file)
+                .map_err(|e| format!("Could not read palette {}: {e}", file.display()))?
+                .to_rgba8();
+            let mut colors: Vec<[u8; 3]> = image
+                .pixels()
+                .filter(|p| p.0[3] > 0)
+                .map(|p| [p.0[0], p.0[1], p.0[2]])
+                .collect();
+            colors.sort_unstable();
+            colors.dedup();
+            colors
+        }
+        _ => {
+            let text = std::fs::read_to_string(file)
+                .map_err(|e| format!("Could not read palette {}: {e}", file.display()))?;
+            parse_palette(&text).map_err(|e| format!("{}: {e}", file.display()))?
+        }
+    };
+    if colors.is_empty() {
+        return Err(format!("the palette {} has no colours in it", file.display()));
+    }
+    if colors.len() > MAX_PALETTE_FILE_COLORS {
+        return Err(format!(
+            "the palette {} has {} colours, more than the {MAX_PALETTE_FILE_COLORS} a palette \
+             file may hold. To reduce a picture to its own N most important colours, use \
+             --colors N instead",
+            file.display(),
+            colors.len()
+        ));
+    }
+    Ok(Palette::from_entries(colors))
+}
+
+/// Parse a text palette: one colour per line, as `RRGGBB` or `#RRGGBB` hex
+/// (the `.hex` lists palette sites export) or as `r g b` / `r,g,b` decimal
+/// with an optional name after it (GIMP's `.gpl`, whose header lines are
+/// skipped). Other lines starting with `#` are comments.
+pub fn parse_palette(text: &str) -> Result<Vec<[u8; 3]>, String> {
+    let mut colors = vec![];
+    for (n, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty()
+            || line == "GIMP Palette"
+            || line.starts_with("Name:")
+            || line.starts_with("Columns:")
+        {
+            continue;
+        }
+        let first = line.split_whitespace().next().unwrap_or("");
+        let hex = first.strip_prefix('#').unwrap_or(first);
+        if hex.len() == 6 && hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            let v = u32::from_str_radix(hex, 16).unwrap();
+            colors.push([(v >> 16) as u8, (v >> 8) as u8, v as u8]);
+            continue;
+        }
+        if line.starts_with('#') || line.starts_with("//") {
+            continue;
+        }
+        let fields: Vec<&str> = line
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|s| !s.is_empty())
+            .take(3)
+            .collect();
+        match fields
+            .iter()
+            .map(|f| f.parse::<u8>())
+            .collect::<Result<Vec<u8>, _>>()
+        {
+            Ok(rgb) if rgb.len() == 3 => colors.push([rgb[0], rgb[1], rgb[2]]),
+            _ => {
+                return Err(format!(
+                    "line {}: '{line}' is not a colour (RRGGBB hex, or r g b from 0 to 255)",
+                    n + 1
+                ));
+            }
+        }
+    }
+    Ok(colors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgba;
+
+    fn bw() -> Palette {
+        Palette::from_entries([[0, 0, 0], [255, 255, 255]])
+    }
+
+    fn grey(w: u32, h: u32, v: u8) -> RgbaImage {
+        RgbaImage::from_pixel(w, h, Rgba([v, v, v, 255]))
+    }
+
+    fn white_share(image: &RgbaImage) -> f32 {
+        image.pixels().filter(|p| p.0[0] == 255).count() as f32 / image.pixels().count() as f32
+    }
+
+    #[test]
+    fn without_dithering_every_pixel_takes_its_nearest_entry() {
+        let out = quantize(&grey(4, 4, 100), &mut bw(), Dither::None);
+        assert!(out.pixels().all(|p| p.0 == [0, 0, 0, 255]));
+    }
+
+    #[test]
+    fn both_ditherings_keep_the_average_brightness() {
+        // 25% grey through a black/white palette: a quarter of the pixels
+        // white, give or take the edges.
+        // (Not Atkinson: it drops a quarter of the error by design, so a
+        // quarter grey comes out darker.)
+        for dither in [Dither::FloydSteinberg, Dither::Ordered] {
+            let out = quantize(&grey(32, 32, 64), &mut bw(), dither);
+            let share = white_share(&out);
+            assert!((share - 0.25).abs() < 0.05, "{dither:?}: {share}");
+            assert!(out.pixels().all(|p| p.0[0] == 0 || p.0[0] == 255));
+        }
+    }
+
+    #[test]
+    fn transparent_pixels_are_left_alone_and_alpha_is_kept() {
+        let mut image = grey(2, 1, 200);
+        image.put_pixel(0, 0, Rgba([200, 10, 10, 0]));
+        image.put_pixel(1, 0, Rgba([200, 200, 200, 128]));
+        for dither in [Dither::None, Dither::FloydSteinberg, Dither::Ordered] {
+            let out = quantize(&image, &mut bw(), dither);
+            assert_eq!(out.get_pixel(0, 0).0, [200, 10, 10, 0], "{dither:?}");
+            assert_eq!(out.get_pixel(1, 0).0[3], 128, "{dither:?}");
+        }
+    }
+
+    #[test]
+    fn a_dithered_threshold_keeps_the_share_of_grey() {
+        // 40% grey: no dots at all with a plain cut at 128, about 40% dotted
+        // either way with dithering; undrawn pixels stay off.
+        let mut levels = vec![Some(102u8); 32 * 32];
+        levels[0] = None;
+        assert!(!threshold(&levels, 32, 128, Dither::None).contains(&true));
+        for dither in [Dither::Ordered, Dither::FloydSteinberg, Dither::Atkinson] {
+            let on = threshold(&levels, 32, 128, dither);
+            let share = on.iter().filter(|&&b| b).count() as f32 / on.len() as f32;
+            assert!((share - 0.4).abs() < 0.05, "{dither:?}: {share}");
+            assert!(!on[0], "{dither:?}");
+        }
+    }
+
+    #[test]
+    fn an_empty_palette_changes_nothing() {
+        let image = grey(3, 3, 77);
+        let out = quantize(&image, &mut Palette::default(), Dither::FloydSteinberg);
+        assert_eq!(out, image);
+    }
+
+    #[test]
+    fn text_palettes_read_hex_decimal_and_gpl() {
+        let colors = parse_palette(
+            "GIMP Palette\nName: test\nColumns: 4\n# a comment\n#FF8000\n00ff00\n\
+             1 2 3\tnamed\n4,5,6\n",
+        )
+        .unwrap();
+        assert_eq!(colors, vec![[255, 128, 0], [0, 255, 0], [1, 2, 3], [4, 5, 6]]);
+        assert!(parse_palette("red\n").unwrap_err().contains("line 1"));
+        assert!(parse_palette("1 2 300\n").is_err());
+    }
+}
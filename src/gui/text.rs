@@ -1,14 +1,18 @@
 use crate::{
+    anim::palette::Palette,
+    dither::{Dither, quantize},
     gui::{
         SharedOptions,
         util::{
-            PickedImage, deliver_save, deliver_world, out_file_warning_row, pick_images,
-            refuse_bad_out_file, save_destination_row, thumb,
+            PickedBytes, PickedImage, deliver_save, deliver_world, out_file_warning_row,
+            pick_font_presets_bytes, pick_images, refuse_bad_out_file, save_destination_row,
+            thumb,
         },
     },
     text::{
-        FontPreset, PixelMode, TILE_PX, TextMaterial, TextOptions, TextShading, add_text_tiles,
-        build_calibration_world, encode_tiles, make_text_prefab, mono_geometry,
+        CustomFontPreset, FontChoice, FontPreset, PixelMode, TILE_PX, TextMaterial, TextOptions,
+        TextShading, add_text_tiles, build_calibration_world, encode_tiles, make_text_prefab,
+        mono_geometry, parse_font_presets, tiles_cost,
     },
 };
 use crate::gui::theme::{icons, widgets};
@@ -20,7 +24,10 @@ use poll_promise::Promise;
 pub struct TextApp {
     image: Option<PickedImage>,
     pending_pick: Option<Promise<Vec<PickedImage>>>,
-    preset: FontPreset,
+    preset: FontChoice,
+    /// presets loaded from a font presets file, listed after the built-ins
+    custom_fonts: Vec<CustomFontPreset>,
+    pending_font_file: Option<PickedBytes>,
     fill_char: String,
     empty_char: String,
     char_repeat: usize,
@@ -38,6 +45,9 @@ pub struct TextApp {
     mode: PixelMode,
     luma_threshold: u8,
     invert: bool,
+    dither: Dither,
+    /// colour mode: median-cut palette size (0 = exact colours)
+    colors: usize,
     /// world units between calibration tile anchors (tiny = tiny displays)
     cube_spacing: u32,
     // material; not reseeded by presets -- a user choice, not calibration
@@ -54,12 +64,14 @@ pub struct TextApp {
 
 impl Default for TextApp {
     fn default() -> Self {
-        let preset = FontPreset::MonaspaceArgon;
+        let preset = FontChoice::Builtin(FontPreset::MonaspaceArgon);
         let d = preset.options(1.0);
         Self {
             image: None,
             pending_pick: None,
             preset,
+            custom_fonts: Vec::new(),
+            pending_font_file: None,
             fill_char: d.fill_char.to_string(),
             empty_char: d.empty_char.to_string(),
             char_repeat: d.char_repeat,
@@ -76,6 +88,8 @@ impl Default for TextApp {
             mode: d.mode,
             luma_threshold: d.luma_threshold,
             invert: d.invert,
+            dither: d.dither,
+            colors: 0,
             cube_spacing: 30,
             material: d.material,
             material_intensity: d.material_intensity,
@@ -109,6 +123,7 @@ impl TextApp {
             mode: self.mode,
             luma_threshold: self.luma_threshold,
             invert: self.invert,
+            dither: self.dither,
             material: self.material,
             material_intensity: self.material_intensity,
             scuff: self.scuff,
@@ -190,10 +205,24 @@ impl TextApp {
         let Some(picked) = &self.image else { return };
         let img = (*picked.image).clone();
         let opts = self.options();
+        let img = if opts.mode == PixelMode::Color && self.colors > 0 {
+            let mut palette =
+                Palette::build(std::slice::from_ref(&img), self.colors, opts.alpha_threshold);
+            if let Ok(full) = encode_tiles(&img, &opts) {
+                let (chars, components) = tiles_cost(&full);
+                info!("Exact colours: {chars} characters in {components} TextDisplay(s)");
+            }
+            info!("Reducing the picture to {} colours", palette.len());
+            quantize(&img, &mut palette, opts.dither)
+        } else {
+            img
+        };
         let tiles = match encode_tiles(&img, &opts) {
             Ok(t) => t,
             Err(e) => return error!("{e}"),
         };
+        let (chars, components) = tiles_cost(&tiles);
+        info!("{chars} characters in {components} TextDisplay(s)");
         info!(
             "Encoded {} tile(s), {} text band(s)",
             tiles.len(),
@@ -233,13 +262,35 @@ impl TextApp {
                 |ui| {
                     ui.horizontal(|ui| {
                         let mut changed = false;
-                        widgets::combo(ui, "text_font_preset", self.preset.name(), 160.0, |ui| {
+                        let selected = self.preset.name().to_string();
+                        widgets::combo(ui, "text_font_preset", selected, 160.0, |ui| {
                             for p in FontPreset::ALL {
-                                changed |= widgets::combo_item(ui, &mut self.preset, p, p.name())
-                                    .changed();
+                                changed |= widgets::combo_item(
+                                    ui,
+                                    &mut self.preset,
+                                    FontChoice::Builtin(p),
+                                    p.name(),
+                                )
+                                .changed();
+                            }
+                            for c in &self.custom_fonts {
+                                changed |= widgets::combo_item(
+                                    ui,
+                                    &mut self.preset,
+                                    FontChoice::Custom(c.clone()),
+                                    c.name.as_str(),
+                                )
+                                .changed();
                             }
                         });
-                        if self.preset == FontPreset::Orbitron {
+                        if widgets::info(ui, format!("{}  Load presets", icons::FOLDER_OPEN))
+                            .on_hover_text("Add font presets from a file ([[font]] tables)")
+                            .clicked()
+                            && self.pending_font_file.is_none()
+                        {
+                            self.pending_font_file = Some(pick_font_presets_bytes());
+                        }
+                        if self.preset == FontChoice::Builtin(FontPreset::Orbitron) {
                             ui.colored_label(
                                 Color32::from_rgb(255, 200, 100),
                                 "1 glyph/px; transparency does not align (proportional font)",
@@ -275,7 +326,7 @@ impl TextApp {
             t.row_hover(
                 ui,
                 "Mode",
-                Some("Color: one colored glyph run per pixel. Braille: monochrome, 8 pixels per character. Blocks: monochrome quadrants, 4 per character"),
+                Some("Color: one colored glyph run per pixel. Braille: monochrome, 8 pixels per character. Blocks: monochrome quadrants, 4 per character. Sextants: monochrome 2x3 cells, 6 per character. Octants: monochrome solid 2x4 cells, 8 per character (Unicode 16 font needed). Sextant and octant spacing is provisional: scaled from Blocks, not yet measured in game"),
                 |ui| {
                     ui.horizontal(|ui| {
                         let mut mode_changed = false;
@@ -293,6 +344,31 @@ impl TextApp {
                                 .on_hover_text("Pixels at least this bright are drawn");
                             widgets::toggle(ui, &mut self.invert, "Invert")
                                 .on_hover_text("Draw dark pixels instead of bright ones");
+                        } else {
+                            ui.label("Colors");
+                            widgets::slider(ui, egui::Slider::new(&mut self.colors, 0..=64))
+                                .on_hover_text(
+                                    "Snap the picture to this many colours first (0 = exact). \
+                                     Every colour change costs a tag, so fewer colours means \
+                                     fewer TextDisplay bricks",
+                                );
+                        }
+                        if self.mode != PixelMode::Color || self.colors > 0 {
+                            ui.label("Dither");
+                            egui::ComboBox::from_id_salt("text_dither")
+                                .selected_text(self.dither.name())
+                                .show_ui(ui, |ui| {
+                                    for d in Dither::ALL {
+                                        ui.selectable_value(&mut self.dither, d, d.name());
+                                    }
+                                })
+                                .response
+                                .on_hover_text(
+                                    "Keep the shading the luma cut or the palette throws away: \
+                                     ordered is a regular Bayer pattern, floyd-steinberg and \
+                                     atkinson diffuse the error (atkinson lighter, with clean \
+                                     highlights and shadows)",
+                                );
                         }
                     });
                 },
@@ -519,7 +595,34 @@ impl TextApp {
         }
     }
 
+    /// Replace the loaded custom presets with a picked file's. A preset that
+    /// is still selected is swapped for the file's version of the same name,
+    /// or falls back to the first built-in when the file no longer has it.
+    fn load_font_file(&mut self, name: &str, bytes: &[u8]) {
+        let presets = match std::str::from_utf8(bytes)
+            .map_err(|e| e.to_string())
+            .and_then(parse_font_presets)
+        {
+            Ok(p) => p,
+            Err(e) => return error!("{name}: {e}"),
+        };
+        info!("Loaded {} font preset(s) from {name}", presets.len());
+        self.custom_fonts = presets;
+        if let FontChoice::Custom(c) = &self.preset {
+            self.preset = FontChoice::resolve(&c.name, &self.custom_fonts)
+                .unwrap_or(FontChoice::Builtin(FontPreset::MonaspaceArgon));
+            self.load_preset();
+        }
+    }
+
     pub fn draw(&mut self, ui: &mut Ui, shared: &mut SharedOptions) {
+        if let Some(promise) = self.pending_font_file.take() {
+            match promise.try_take() {
+                Ok(Some((name, bytes))) => self.load_font_file(&name, &bytes),
+                Ok(None) => {}
+                Err(promise) => self.pending_font_file = Some(promise),
+            }
+        }
         if let Some(promise) = self.pending_pick.take() {
             match promise.try_take() {
                 Ok(images) => {
@@ -290,10 +290,14 @@ pub fn pick_images(multiple: bool) -> Promise<Vec<PickedImage>> {
     }
 }
 
+/// A file pick still in flight: the picked file's name and raw bytes, `None`
+/// if the user cancels.
+pub type PickedBytes = Promise<Option<(String, Vec<u8>)>>;
+
 /// Pick a single file's raw bytes, without any image decode -- unlike
 /// `pick_images`, which decodes eagerly and keeps only the first frame of an
 /// animated image. `None` if the user cancels.
-pub fn pick_animated_bytes() -> Promise<Option<(String, Vec<u8>)>> {
+pub fn pick_animated_bytes() -> PickedBytes {
     let dialog = rfd::AsyncFileDialog::new()
         .add_filter("Animated Images", &["gif", "png", "webp", "jpg", "jpeg"]);
     let pick = async move {
@@ -315,7 +319,7 @@ pub fn pick_animated_bytes() -> Promise<Option<(String, Vec<u8>)>> {
 /// Pick a subtitle file's raw bytes and name -- bytes rather than a path so it
 /// works on the web too; the name comes along since its extension picks the
 /// parser (`subs::parse_auto`). `None` if the user cancels.
-pub fn pick_subtitle_bytes() -> Promise<Option<(String, Vec<u8>)>> {
+pub fn pick_subtitle_bytes() -> PickedBytes {
     let dialog =
         rfd::AsyncFileDialog::new().add_filter("Subtitle Files", &["srt", "ass", "ssa", "vtt"]);
     let pick = async move {
@@ -334,6 +338,27 @@ pub fn pick_subtitle_bytes() -> Promise<Option<(String, Vec<u8>)>> {
     }
 }
 
+/// Pick a font presets file's raw bytes and name (see
+/// `text::parse_font_presets`) -- bytes so it works on the web too. `None` if
+/// the user cancels.
+pub fn pick_font_presets_bytes() -> PickedBytes {
+    let dialog = rfd::AsyncFileDialog::new().add_filter("Font Presets", &["toml", "txt"]);
+    let pick = async move {
+        let handle = dialog.pick_file().await?;
+        let name = handle.file_name();
+        let bytes = handle.read().await;
+        Some((name, bytes))
+    };
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        Promise::spawn_thread("pick_font_presets_bytes", move || pollster::block_on(pick))
+    }
+    #[cfg(target_arch = "wasm32")]
+    {
+        Promise::spawn_async(pick)
+    }
+}
+
 /// Pick a video file, returning its path rather than its bytes: the decode
 /// backends stream from a path instead of reading a multi-hundred-MB file
 /// into memory up front. Native only, since a browser file handle has no path.
@@ -371,7 +396,7 @@ pub fn pick_audio_path() -> Promise<Option<std::path::PathBuf>> {
 /// replaces the native re-openable path. Web only: the native pane keeps the
 /// path so it can also reach the ffmpeg backend and per-container track pick.
 #[cfg(target_arch = "wasm32")]
-pub fn pick_audio_bytes() -> Promise<Option<(String, Vec<u8>)>> {
+pub fn pick_audio_bytes() -> PickedBytes {
     let dialog = rfd::AsyncFileDialog::new()
         .add_filter("Audio Files", &crate::audio::source::AUDIO_EXTENSIONS)
         .add_filter("Video Files", &crate::video::source::VIDEO_EXTENSIONS);
@@ -388,7 +413,7 @@ pub fn pick_audio_bytes() -> Promise<Option<(String, Vec<u8>)>> {
 /// [`pick_video_path`]. Bytes because a browser upload has no path, decoded by
 /// the pure-Rust builtin backend (there is no ffmpeg on wasm). Web only.
 #[cfg(target_arch = "wasm32")]
-pub fn pick_video_bytes() -> Promise<Option<(String, Vec<u8>)>> {
+pub fn pick_video_bytes() -> PickedBytes {
     let dialog = rfd::AsyncFileDialog::new()
         .add_filter("Video Files", &crate::video::source::VIDEO_EXTENSIONS);
     let pick = async move {
@@ -408,7 +433,7 @@ pub fn pick_video_bytes() -> Promise<Option<(String, Vec<u8>)>> {
 /// native the dialog defaults into the user's Music folder, where a `.mid` most
 /// often lives; if [`dirs::audio_dir`] cannot name one the dialog opens wherever
 /// rfd defaults. `None` if the user cancels.
-pub fn pick_midi_bytes() -> Promise<Option<(String, Vec<u8>)>> {
+pub fn pick_midi_bytes() -> PickedBytes {
     #[allow(unused_mut)]
     let mut dialog = rfd::AsyncFileDialog::new().add_filter("MIDI Files", &["mid", "midi"]);
     #[cfg(not(target_arch = "wasm32"))]
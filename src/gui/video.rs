@@ -13,21 +13,25 @@ use crate::{
         bricks::{AnimOptions, DisplayBrickStyle},
         cost::Cost,
         pack::{BANK_FRAMES, MAX_FRAMES},
+        playlist::PlaylistSource,
         text_layout,
     },
+    dither::Dither,
     gui::{
         SharedOptions,
         util::{
-            self, PickedImage, RenderMsg, bound_pane_width, deliver_world_unless_cancelled,
-            draw_progress_bar, note, out_file_warning_row, pick_animated_bytes, pick_images,
-            pick_subtitle_bytes, refuse_bad_out_file, save_destination_row, section, thumb,
+            self, PickedBytes, PickedImage, RenderMsg, bound_pane_width,
+            deliver_world_unless_cancelled, draw_progress_bar, note, out_file_warning_row,
+            pick_animated_bytes, pick_images, pick_subtitle_bytes, refuse_bad_out_file,
+            save_destination_row, section, thumb,
         },
     },
     progress::Progress,
     subs::{self, Subtitles},
-    text::{FontPreset, TextOptions},
+    text::{FontPreset, PixelMode, TextOptions, mono_geometry},
     video::{
         Clip,
+        adjust::{Adjustments, Crop, Flip, Grade, Rotation, detect_letterbox},
         scale::{Filter, FitMode, estimated_frame_count, max_frames_error},
         source::{Source, decode},
         stream::{AdaptedSource, FrameSource},
@@ -189,6 +193,33 @@ enum Input {
     },
 }
 
+/// A video added to the playlist after the picked one. Held undecoded and
+/// opened by the worker, exactly as `Input::Video` is.
+struct PickedVideo {
+    #[cfg(not(target_arch = "wasm32"))]
+    path: std::path::PathBuf,
+    #[cfg(target_arch = "wasm32")]
+    bytes: std::sync::Arc<[u8]>,
+    name: String,
+}
+
+impl PickedVideo {
+    /// What the video picker returned, named by its file name.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn new(path: std::path::PathBuf) -> Self {
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| path.display().to_string());
+        Self { path, name }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn new((name, bytes): (String, Vec<u8>)) -> Self {
+        Self { bytes: bytes.into(), name }
+    }
+}
+
 /// The picked input, captured just before a render is handed to the worker.
 ///
 /// `Animated`'s `Clip` is already decoded, so it crosses as a real deep copy
@@ -211,6 +242,11 @@ enum GenSource {
         #[cfg(target_arch = "wasm32")]
         name: String,
     },
+    /// The picked video and every [`VideoApp::playlist`] clip after it, each
+    /// a `Video` named for its button. Each is opened and adapted in the
+    /// worker as a lone `Video` is, then played back to back by
+    /// [`PlaylistSource`].
+    Playlist(Vec<(String, GenSource)>),
 }
 
 /// The result of [`VideoApp::check_ffmpeg_consent`]: whether resolving a
@@ -231,14 +267,28 @@ enum FfmpegCheck {
 
 pub struct VideoApp {
     input: Input,
-    pending_pick_animated: Option<Promise<Option<(String, Vec<u8>)>>>,
+    pending_pick_animated: Option<PickedBytes>,
     pending_pick_sequence: Option<Promise<Vec<PickedImage>>>,
     /// The in-flight video picker: a path dialog on native, a byte upload on
     /// the web.
     #[cfg(not(target_arch = "wasm32"))]
     pending_pick_video: Option<Promise<Option<std::path::PathBuf>>>,
     #[cfg(target_arch = "wasm32")]
-    pending_pick_video: Option<Promise<Option<(String, Vec<u8>)>>>,
+    pending_pick_video: Option<PickedBytes>,
+    /// Videos played after the picked one, each its own clip on one timeline
+    /// with Previous/Next Clip and a button per clip (see
+    /// [`crate::anim::playlist`]). Offered only while the input is a video, as
+    /// the CLI's `--playlist` takes video files only; empty renders one clip.
+    playlist: Vec<PickedVideo>,
+    /// The in-flight picker adding a clip to [`Self::playlist`].
+    #[cfg(not(target_arch = "wasm32"))]
+    pending_pick_clip: Option<Promise<Option<std::path::PathBuf>>>,
+    #[cfg(target_arch = "wasm32")]
+    pending_pick_clip: Option<PickedBytes>,
+    /// Run each clip into the next, or loop it on itself
+    /// ([`crate::anim::playlist::Playlist::auto_advance`]). Inert while
+    /// [`Self::playlist`] is empty.
+    auto_advance: bool,
     /// Which decode backend `generate` uses for an `Input::Video` source.
     #[cfg(not(target_arch = "wasm32"))]
     backend: Backend,
@@ -281,6 +331,17 @@ pub struct VideoApp {
     fit: FitMode,
     filter: Filter,
 
+    /// Keep only `crop` of the source, in source pixels. Ignored while off,
+    /// so toggling it doesn't lose the rectangle.
+    crop_enabled: bool,
+    crop: Crop,
+    /// Find the black bars at render time and crop them instead, as
+    /// `--auto-crop` does. Exclusive with `crop_enabled`.
+    auto_crop: bool,
+    rotation: Rotation,
+    flip: Flip,
+    grade: Grade,
+
     fps: f32,
     start: f32,
     limit_duration: bool,
@@ -321,8 +382,19 @@ pub struct VideoApp {
     /// ([`text_layout::plan_bands`]) -- see [`Self::anim_opts`].
     text_char_repeat: usize,
     /// TEXT MODE ONLY. The component `LineHeight` (font size); the Image2Text
-    /// pane calls this control "Font Size".
+    /// pane calls this control "Font Size". Colour runs only: a glyph mode
+    /// uses its own calibrated geometry ([`mono_geometry`]).
     text_line_height: f32,
+    /// TEXT MODE ONLY. Colour runs, or a monochrome glyph mode packing
+    /// several pixels into each character; also drives the band layout
+    /// ([`text_layout::plan_text_bands`]).
+    text_pixel_mode: PixelMode,
+    /// TEXT MODE ONLY, glyph modes: pixels at least this bright are drawn.
+    text_luma_threshold: u8,
+    /// TEXT MODE ONLY, glyph modes: draw dark pixels instead of bright ones.
+    text_invert: bool,
+    /// TEXT MODE ONLY, glyph modes: how the dots keep the frame's shading.
+    text_dither: Dither,
     /// TEXT MODE ONLY. Median-cut palette size passed through to
     /// [`AnimOptions::colors`]; `0` means full 24-bit colour. Shown only when
     /// [`Self::shows_colours_control`] is true.
@@ -336,7 +408,7 @@ pub struct VideoApp {
     /// The in-flight subtitle picker. Bytes, not a path -- see
     /// [`crate::gui::util::pick_subtitle_bytes`], which is why this works on
     /// the web as well.
-    pending_pick_subtitles: Option<Promise<Option<(String, Vec<u8>)>>>,
+    pending_pick_subtitles: Option<PickedBytes>,
     /// How much bigger a subtitle line is than one row of the screen
     /// ([`AnimOptions::subtitle_scale`]). Inert while [`Self::subtitles`] is
     /// `None`, and the control is hidden then for the same reason the Colours
@@ -362,6 +434,9 @@ impl Default for VideoApp {
             pending_pick_animated: None,
             pending_pick_sequence: None,
             pending_pick_video: None,
+            playlist: Vec::new(),
+            pending_pick_clip: None,
+            auto_advance: true,
             #[cfg(not(target_arch = "wasm32"))]
             backend: Backend::Auto,
             #[cfg(not(target_arch = "wasm32"))]
@@ -382,6 +457,14 @@ impl Default for VideoApp {
             height: 64,
             fit: FitMode::Contain,
             filter: Filter::Lanczos,
+            crop_enabled: false,
+            // Zero-sized until first enabled, when it takes the source's size
+            // (see `draw_adjust_rows`).
+            crop: Crop { x: 0, y: 0, width: 0, height: 0 },
+            auto_crop: false,
+            rotation: Rotation::None,
+            flip: Flip::default(),
+            grade: Grade::default(),
             fps: 10.0,
             start: 0.0,
             limit_duration: false,
@@ -406,6 +489,10 @@ impl Default for VideoApp {
             text_empty_char: text_default.empty_char.to_string(),
             text_char_repeat: text_default.char_repeat,
             text_line_height: text_default.line_height,
+            text_pixel_mode: text_default.mode,
+            text_luma_threshold: text_default.luma_threshold,
+            text_invert: text_default.invert,
+            text_dither: text_default.dither,
             colors: d.colors,
             subtitles: None,
             pending_pick_subtitles: None,
@@ -446,6 +533,13 @@ fn backend_name(b: Backend) -> &'static str {
     }
 }
 
+/// A playlist clip's button label: its file name without the extension, as
+/// the CLI labels a `--playlist` clip.
+fn clip_name(file_name: &str) -> String {
+    let path = std::path::Path::new(file_name);
+    path.file_stem().map_or_else(|| file_name.to_string(), |s| s.to_string_lossy().into_owned())
+}
+
 impl VideoApp {
     /// `(width, height, frame_count, source_fps)` of the picked input at its
     /// natural resolution/frame count, before any resize/resample. `None`
@@ -475,15 +569,28 @@ impl VideoApp {
     /// video source (see that method's doc). Shared by `live_cost` and
     /// `draw_text_char_bound` so the two can't quietly compute this
     /// differently from each other.
+    ///
+    /// Without Resize that is the source's size after crop and rotation; an
+    /// Auto Bars crop isn't known until render time, so it reads uncropped.
     fn output_dims(&self) -> Option<(u32, u32)> {
         let (src_w, src_h, ..) = self.source_info()?;
         Some(if self.resize {
             (self.width.max(1), self.height.max(1))
         } else {
-            (src_w, src_h)
+            self.adjustments().size(src_w, src_h)
         })
     }
 
+    /// The crop, rotation, mirror and grade for the current UI state.
+    fn adjustments(&self) -> Adjustments {
+        Adjustments {
+            crop: self.crop_enabled.then_some(self.crop),
+            rotation: self.rotation,
+            flip: self.flip,
+            grade: self.grade,
+        }
+    }
+
     /// The render options for the current UI state.
     ///
     /// The single source of these for both `generate` and `live_cost`, so
@@ -506,8 +613,28 @@ impl VideoApp {
             // (see `anim_options` in main.rs for why both fields must match).
             alpha_threshold: self.alpha_threshold,
             line_height: self.text_line_height,
+            mode: self.text_pixel_mode,
+            luma_threshold: self.text_luma_threshold,
+            invert: self.text_invert,
+            dither: self.text_dither,
             ..self.text_preset.options(1.0)
         };
+        // The glyph modes' own component geometry, exactly as `text_options`
+        // in main.rs applies it for `--braille` and friends.
+        let text = if text.mode == PixelMode::Color {
+            text
+        } else {
+            let (line_height, kerning, line_offset, pitch_x, pitch_y) =
+                mono_geometry(text.mode, 1.0);
+            TextOptions {
+                line_height,
+                kerning,
+                line_offset,
+                pitch_x: pitch_x.unwrap_or(text.pitch_x),
+                pitch_y,
+                ..text
+            }
+        };
         AnimOptions {
             alpha_threshold: self.alpha_threshold,
             pixel_extent: self.pixel_extent,
@@ -538,7 +665,7 @@ impl VideoApp {
     /// `AnimOptions::colors` is simply ignored there, so showing the slider
     /// under `AnimMode::Brick` would offer a control that does nothing.
     fn shows_colours_control(&self) -> bool {
-        matches!(self.mode, AnimMode::Text)
+        matches!(self.mode, AnimMode::Text) && self.text_pixel_mode == PixelMode::Color
     }
 
     /// A live approximation of `AnimMode::estimate` for the current options,
@@ -621,6 +748,7 @@ impl VideoApp {
                                     .unwrap_or_else(|| RgbaImage::new(1, 1));
                                 self.width = clip.width;
                                 self.height = clip.height;
+                                self.playlist.clear();
                                 self.input = Input::Animated {
                                     preview: PickedImage {
                                         name: name.clone(),
@@ -687,6 +815,7 @@ impl VideoApp {
                             self.width = first.image.width();
                             self.height = first.image.height();
                         }
+                        self.playlist.clear();
                         self.input = Input::Sequence(images);
                     }
                 }
@@ -719,6 +848,19 @@ impl VideoApp {
                 Err(promise) => self.pending_pick_video = Some(promise),
             }
         }
+
+        if let Some(promise) = self.pending_pick_clip.take() {
+            match promise.try_take() {
+                Ok(result) => {
+                    if let Some(picked) = result {
+                        let clip = PickedVideo::new(picked);
+                        info!("Added playlist clip: {}", clip.name);
+                        self.playlist.push(clip);
+                    }
+                }
+                Err(promise) => self.pending_pick_clip = Some(promise),
+            }
+        }
     }
 
     /// Poll the in-flight render, if any, same `try_take`/put-back idiom as
@@ -836,6 +978,33 @@ impl VideoApp {
         }
     }
 
+    /// Whether `generate` renders a playlist: a picked video with at least one
+    /// clip added after it.
+    fn is_playlist(&self) -> bool {
+        matches!(self.input, Input::Video { .. }) && !self.playlist.is_empty()
+    }
+
+    /// A playlist's buttons jump the clock, so it refuses what the CLI's
+    /// `--playlist` refuses: an external clock has no clock to jump, and a
+    /// subtitle track is timed in one clip's seconds.
+    fn check_playlist(&self) -> Result<(), &'static str> {
+        if !self.is_playlist() {
+            return Ok(());
+        }
+        if self.external_clock {
+            return Err("a playlist cannot take an external clock: its clip buttons jump the \
+                        built-in one");
+        }
+        if self.subtitles.is_some() {
+            return Err("a playlist cannot take subtitles: they are timed in one clip's seconds");
+        }
+        if !self.auto_advance && !self.loop_playback {
+            return Err("a playlist with auto-advance off cannot play once: each clip loops on \
+                        itself, so it never reaches an end to stop at");
+        }
+        Ok(())
+    }
+
     /// The always-visible half of the pane: destination, render mode, screen
     /// size and frame rate.
     ///
@@ -978,6 +1147,11 @@ impl VideoApp {
             },
         );
 
+        let (chips, open) = (self.adjust_chips(), self.adjust_is_tuned());
+        section(ui, "video_adjust_section", "Crop & colour", &chips, open, |ui| {
+            widgets::settings_table(ui, |ui, t| self.draw_adjust_rows(t, ui));
+        });
+
         let (chips, open) = (self.picture_chips(), self.picture_is_tuned());
         section(ui, "video_picture_section", "Picture", &chips, open, |ui| {
             widgets::settings_table(ui, |ui, t| self.draw_picture_rows(t, ui));
@@ -994,9 +1168,10 @@ impl VideoApp {
             self.text_preset.name().to_string(),
             format!("x{} repeat", self.text_char_repeat),
             format!("line height {:.2}", self.text_line_height),
-            match self.colors {
-                0 => "full colour".to_string(),
-                n => format!("{n} colours"),
+            match (self.text_pixel_mode, self.colors) {
+                (PixelMode::Color, 0) => "full colour".to_string(),
+                (PixelMode::Color, n) => format!("{n} colours"),
+                (mode, _) => mode.name().to_string(),
             },
         ]
     }
@@ -1012,6 +1187,7 @@ impl VideoApp {
                 || self.text_empty_char != d.text.empty_char.to_string()
                 || self.text_char_repeat != d.text.char_repeat
                 || self.text_line_height != d.text.line_height
+                || self.text_pixel_mode != d.text.mode
                 || self.colors != d.colors)
     }
 
@@ -1049,6 +1225,35 @@ impl VideoApp {
             || self.max_frames != BANK_FRAMES as u32
     }
 
+    fn adjust_chips(&self) -> Vec<String> {
+        let mut chips = Vec::new();
+        if self.auto_crop {
+            chips.push("crop bars".to_string());
+        } else if self.crop_enabled {
+            chips.push(format!("crop {}", self.crop));
+        }
+        if self.rotation != Rotation::None {
+            chips.push(format!("rotate {}°", self.rotation.degrees()));
+        }
+        match (self.flip.horizontal, self.flip.vertical) {
+            (true, true) => chips.push("mirror both".to_string()),
+            (true, false) => chips.push("mirror h".to_string()),
+            (false, true) => chips.push("mirror v".to_string()),
+            (false, false) => {}
+        }
+        if !self.grade.is_identity() {
+            chips.push("graded".to_string());
+        }
+        if chips.is_empty() {
+            chips.push("as decoded".to_string());
+        }
+        chips
+    }
+
+    fn adjust_is_tuned(&self) -> bool {
+        self.auto_crop || self.adjustments() != Adjustments::default()
+    }
+
     fn picture_chips(&self) -> Vec<String> {
         let mut chips = vec![
             format!("alpha {}", self.alpha_threshold),
@@ -1082,6 +1287,86 @@ impl VideoApp {
             || self.glow != d.glow
     }
 
+    /// Crop, rotation, mirror and grade, applied before the resize on every
+    /// input kind and backend (see `video::adjust`).
+    fn draw_adjust_rows(&mut self, t: &mut widgets::SettingsTable, ui: &mut Ui) {
+        t.row_hover(
+            ui,
+            "Crop",
+            Some(
+                "Keep only a rectangle of the source, in source pixels, before resizing. Auto \
+                 Bars finds the black letterbox or pillarbox bars in the first dozen seconds \
+                 from Start and crops those instead; the size readout can't see them until \
+                 the render runs.",
+            ),
+            |ui| {
+                ui.horizontal(|ui| {
+                    if widgets::toggle(ui, &mut self.crop_enabled, "Crop").changed() {
+                        self.auto_crop &= !self.crop_enabled;
+                        if self.crop.width == 0 || self.crop.height == 0 {
+                            let (w, h, ..) = self.source_info().unwrap_or((64, 64, 0, 0.0));
+                            self.crop = Crop { x: 0, y: 0, width: w, height: h };
+                        }
+                    }
+                    if widgets::toggle(ui, &mut self.auto_crop, "Auto Bars").changed() {
+                        self.crop_enabled &= !self.auto_crop;
+                    }
+                });
+            },
+        );
+
+        // Origin and size on rows of their own, for the width reason the
+        // Size sliders above give.
+        t.row(ui, "Crop Origin", |ui| {
+            ui.add_enabled_ui(self.crop_enabled, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("X");
+                    ui.add(egui::DragValue::new(&mut self.crop.x).range(0..=16384));
+                    ui.label("Y");
+                    ui.add(egui::DragValue::new(&mut self.crop.y).range(0..=16384));
+                });
+            });
+        });
+        t.row(ui, "Crop Size", |ui| {
+            ui.add_enabled_ui(self.crop_enabled, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("W");
+                    ui.add(egui::DragValue::new(&mut self.crop.width).range(1..=16384));
+                    ui.label("H");
+                    ui.add(egui::DragValue::new(&mut self.crop.height).range(1..=16384));
+                });
+            });
+        });
+
+        t.row_hover(ui, "Rotate", Some("Turn the source clockwise, after the crop"), |ui| {
+            ui.horizontal(|ui| {
+                for r in [Rotation::None, Rotation::Cw90, Rotation::Half, Rotation::Ccw90] {
+                    widgets::radio(ui, &mut self.rotation, r, format!("{}°", r.degrees()));
+                }
+            });
+        });
+
+        t.row_hover(ui, "Mirror", Some("Flip the source, after the rotation"), |ui| {
+            ui.horizontal(|ui| {
+                widgets::toggle(ui, &mut self.flip.horizontal, "Left-right");
+                widgets::toggle(ui, &mut self.flip.vertical, "Top-bottom");
+            });
+        });
+
+        t.row_hover(ui, "Brightness", Some("Added to every channel (0 = unchanged)"), |ui| {
+            widgets::slider(ui, egui::Slider::new(&mut self.grade.brightness, -1.0..=1.0));
+        });
+        t.row_hover(ui, "Contrast", Some("Stretch about mid-grey (1 = unchanged)"), |ui| {
+            widgets::slider(ui, egui::Slider::new(&mut self.grade.contrast, 0.0..=3.0));
+        });
+        t.row_hover(ui, "Saturation", Some("0 = greyscale, 1 = unchanged"), |ui| {
+            widgets::slider(ui, egui::Slider::new(&mut self.grade.saturation, 0.0..=3.0));
+        });
+        t.row_hover(ui, "Gamma", Some("Above 1 lifts the shadows (1 = unchanged)"), |ui| {
+            widgets::slider(ui, egui::Slider::new(&mut self.grade.gamma, 0.1..=3.0));
+        });
+    }
+
     /// Text mode's font, glyph and geometry controls.
     ///
     /// **Three grid rows, not one.** These five controls used to share a single
@@ -1147,9 +1432,55 @@ impl VideoApp {
             t.row_hover(
                 ui,
                 "Line Height",
-                Some("Component LineHeight (font size)"),
+                Some(
+                    "Component LineHeight (font size). Colour runs only: the glyph modes use \
+                     their own calibrated size",
+                ),
                 |ui| {
-                    ui.add(egui::DragValue::new(&mut self.text_line_height).speed(0.01));
+                    ui.add_enabled(
+                        self.text_pixel_mode == PixelMode::Color,
+                        egui::DragValue::new(&mut self.text_line_height).speed(0.01),
+                    );
+                },
+            );
+
+            t.row_hover(
+                ui,
+                "Pixels",
+                Some(
+                    "Color: one coloured glyph run per pixel. Braille, Blocks, Sextants and \
+                     Octants: monochrome, 8, 4, 6 and 8 pixels per character -- no colour tags, \
+                     so each TextDisplay holds many times more of the picture. Sextant and octant \
+                     spacing is provisional: scaled from Blocks, not yet measured in game",
+                ),
+                |ui| {
+                    ui.horizontal_wrapped(|ui| {
+                        for m in PixelMode::ALL {
+                            ui.radio_value(&mut self.text_pixel_mode, m, m.name());
+                        }
+                        if self.text_pixel_mode != PixelMode::Color {
+                            ui.label("Luma");
+                            widgets::slider(
+                                ui,
+                                egui::Slider::new(&mut self.text_luma_threshold, 0..=255),
+                            )
+                            .on_hover_text("Pixels at least this bright are drawn");
+                            widgets::toggle(ui, &mut self.text_invert, "Invert")
+                                .on_hover_text("Draw dark pixels instead of bright ones");
+                            ui.label("Dither");
+                            widgets::combo(
+                                ui,
+                                "video_text_dither",
+                                self.text_dither.name(),
+                                120.0,
+                                |ui| {
+                                    for d in Dither::ALL {
+                                        widgets::combo_item(ui, &mut self.text_dither, d, d.name());
+                                    }
+                                },
+                            );
+                        }
+                    });
                 },
             );
         }
@@ -1552,7 +1883,53 @@ impl VideoApp {
         }
         if clear_input {
             self.input = Input::None;
+            self.playlist.clear();
         }
+        if matches!(self.input, Input::Video { .. }) {
+            self.draw_playlist(ui);
+        }
+    }
+
+    /// The clips played after the picked video. One or more makes the render
+    /// a playlist, as `--playlist` does for the CLI: every clip fitted to the
+    /// first one's size and played at the same fps, with Previous/Next Clip
+    /// and a button per clip beside the control buttons.
+    fn draw_playlist(&mut self, ui: &mut Ui) {
+        let mut remove = None;
+        for (i, clip) in self.playlist.iter().enumerate() {
+            ui.horizontal_wrapped(|ui| {
+                if widgets::danger_icon(ui, icons::XMARK).clicked() {
+                    remove = Some(i);
+                }
+                ui.label(format!("then {} -- playlist clip {}", clip.name, i + 2));
+            });
+        }
+        if let Some(i) = remove {
+            self.playlist.remove(i);
+        }
+        ui.horizontal_wrapped(|ui| {
+            let add = widgets::info(ui, format!("{}  Add clip to playlist", icons::FILM))
+                .on_hover_text(
+                    "Play another video after this one in the same world, with Previous/Next \
+                     Clip and a button per clip. Not with an external clock or subtitles.",
+                );
+            if add.clicked() && self.pending_pick_clip.is_none() {
+                #[cfg(not(target_arch = "wasm32"))]
+                {
+                    self.pending_pick_clip = Some(pick_video_path());
+                }
+                #[cfg(target_arch = "wasm32")]
+                {
+                    self.pending_pick_clip = Some(pick_video_bytes());
+                }
+            }
+            if !self.playlist.is_empty() {
+                widgets::toggle(ui, &mut self.auto_advance, "Auto-advance").on_hover_text(
+                    "On: each clip runs into the next. Off: each clip loops on itself until a \
+                     button moves on, costing one frame and two gates per clip.",
+                );
+            }
+        });
     }
 
     fn draw_cost(&self, ui: &mut Ui) {
@@ -1562,9 +1939,11 @@ impl VideoApp {
         // folding into `live_cost`'s own `None` case below, which reads
         // as "nothing picked yet" and would be misleading here.
         if matches!(self.input, Input::Video { .. }) {
-            ui.label(
-                "Video source selected -- cost is measured once Generate opens the file.",
-            );
+            ui.label(if self.playlist.is_empty() {
+                "Video source selected -- cost is measured once Generate opens the file."
+            } else {
+                "Playlist selected -- cost is measured once Generate opens the files."
+            });
             return;
         }
 
@@ -1613,9 +1992,27 @@ impl VideoApp {
     /// estimate. Computed the same way `main.rs`'s `log_cost` computes it for
     /// `--anim-mode text`, from `text_layout::plan_bands` and
     /// `text_layout::worst_case_row_chars` over the SAME resized
-    /// width/height `live_cost` used, so the two can't disagree either.
+    /// width/height `live_cost` used, so the two can't disagree either -- or,
+    /// in a glyph mode, from `plan_text_bands` and `band_char_bound`.
     fn draw_text_char_bound(&self, ui: &mut Ui) {
         let Some((w, h)) = self.output_dims() else { return };
+        if self.text_pixel_mode != PixelMode::Color {
+            let text = self.anim_opts().text;
+            let Ok(plan) = text_layout::plan_text_bands(w as usize, h as usize, &text) else {
+                return;
+            };
+            let Some(first) = plan.first() else { return };
+            let rows = first.rows;
+            ui.label(format!(
+                "{} band(s) of {rows} row(s); at most {} character(s) per band per frame \
+                 ({} {} glyph(s) per line, no colour tags)",
+                plan.len(),
+                text_layout::band_char_bound(w as usize, rows, &text),
+                text_layout::mono_line_chars(w as usize, text.mode),
+                text.mode.name().to_lowercase(),
+            ));
+            return;
+        }
         let repeat = self.text_char_repeat.max(1);
         let Ok(plan) = text_layout::plan_bands(w as usize, h as usize, repeat) else {
             // A layout this geometry cannot support -- Generate will fail
@@ -1731,17 +2128,25 @@ impl VideoApp {
         }
         #[cfg(not(target_arch = "wasm32"))]
         if let Input::Video { path, .. } = &self.input {
-            match self.check_ffmpeg_consent(path) {
-                FfmpegCheck::Ready => {}
-                FfmpegCheck::NeedsConsent => {
-                    self.modal.request(path.clone());
-                    return;
+            let clips = self.playlist.iter().map(|c| &c.path);
+            for path in std::iter::once(path).chain(clips) {
+                match self.check_ffmpeg_consent(path) {
+                    FfmpegCheck::Ready => {}
+                    FfmpegCheck::NeedsConsent => {
+                        self.modal.request(path.clone());
+                        return;
+                    }
+                    // Already logged by `check_ffmpeg_consent`.
+                    FfmpegCheck::Failed => return,
                 }
-                // Already logged by `check_ffmpeg_consent`.
-                FfmpegCheck::Failed => return,
             }
         }
 
+        if let Err(e) = self.check_playlist() {
+            return error!("{e}");
+        }
+        let playlist = self.is_playlist();
+
         let source = match &self.input {
             Input::None => return error!("pick an animated file, frame sequence, or video first"),
             Input::Animated { clip, .. } => GenSource::Clip(clip.clone()),
@@ -1766,6 +2171,26 @@ impl VideoApp {
                 name: name.clone(),
             },
         };
+        // Named after the file without its extension, as the CLI names a
+        // `--playlist` clip's button.
+        let source = if playlist {
+            let Input::Video { name, .. } = &self.input else { unreachable!() };
+            let clips = self.playlist.iter().map(|c| {
+                let source = GenSource::Video {
+                    #[cfg(not(target_arch = "wasm32"))]
+                    path: c.path.clone(),
+                    #[cfg(target_arch = "wasm32")]
+                    bytes: std::sync::Arc::clone(&c.bytes),
+                    #[cfg(target_arch = "wasm32")]
+                    name: c.name.clone(),
+                };
+                (clip_name(&c.name), source)
+            });
+            GenSource::Playlist(std::iter::once((clip_name(name), source)).chain(clips).collect())
+        } else {
+            source
+        };
+        let auto_advance = self.auto_advance;
 
         // Everything below is plain data or an owned clone -- nothing here
         // borrows `self` or `shared`, so it's all free to move into `work`.
@@ -1774,6 +2199,8 @@ impl VideoApp {
         let height = self.height.max(1);
         let fit = self.fit;
         let filter = self.filter;
+        let adjust = self.adjustments();
+        let auto_crop = self.auto_crop;
         let fps = self.fps;
         #[cfg(not(target_arch = "wasm32"))]
         let backend = self.backend;
@@ -1836,47 +2263,61 @@ impl VideoApp {
             // which is what makes the pane's width/height/fit/filter/fps
             // controls behave identically for a picked video and for a
             // decoded `Clip`.
-            let raw: Box<dyn FrameSource> = match source {
-                GenSource::Clip(c) => Box::new(c),
-                GenSource::Sequence(named) => {
-                    // `decode` wants owned `RgbaImage`s, so the pixel copy
-                    // has to happen somewhere -- here, on the worker, rather
-                    // than in the click handler that captured these `Arc`s.
-                    let named: Vec<(String, RgbaImage)> = named
-                        .into_iter()
-                        .map(|(name, image)| (name, (*image).clone()))
-                        .collect();
-                    Box::new(decode(Source::Sequence(named), fps)?)
-                }
-                #[cfg(not(target_arch = "wasm32"))]
-                GenSource::Video { path } => {
-                    // `DownloadConsent::Never` is safe here, not silent:
-                    // `check_ffmpeg_consent` already ran this exact call on
-                    // the UI thread before this worker was ever spawned, so
-                    // either ffmpeg was not needed for this file/backend, or
-                    // it is already installed (the consent modal's Download
-                    // button ran `ensure_ffmpeg(DownloadConsent::Always)`
-                    // and this worker only starts once that succeeded) --
-                    // `ensure_ffmpeg`'s doc: an already-installed ffmpeg
-                    // short-circuits every consent variant to `Ok`, so
-                    // `Never` here can't newly refuse anything `Ready`
-                    // didn't already clear.
-                    backend::open_video_ensuring(
-                        &path,
-                        backend,
-                        None,
-                        fit,
-                        filter,
-                        None,
-                        &mut || ensure_ffmpeg(DownloadConsent::Never),
-                    )?
-                }
-                // The web build decodes the uploaded bytes with the builtin
-                // backend (no ffmpeg); the guards inside `open_video_bytes`
-                // refuse anything it cannot decode correctly.
-                #[cfg(target_arch = "wasm32")]
-                GenSource::Video { bytes, name } => backend::open_video_bytes(&name, bytes)?,
+            let open = |source: GenSource| -> Result<Box<dyn FrameSource>, String> {
+                Ok(match source {
+                    GenSource::Clip(c) => Box::new(c),
+                    GenSource::Sequence(named) => {
+                        // `decode` wants owned `RgbaImage`s, so the pixel copy
+                        // has to happen somewhere -- here, on the worker, rather
+                        // than in the click handler that captured these `Arc`s.
+                        let named: Vec<(String, RgbaImage)> = named
+                            .into_iter()
+                            .map(|(name, image)| (name, (*image).clone()))
+                            .collect();
+                        Box::new(decode(Source::Sequence(named), fps)?)
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    GenSource::Video { path } => {
+                        // `DownloadConsent::Never` is safe here, not silent:
+                        // `check_ffmpeg_consent` already ran this exact call on
+                        // the UI thread before this worker was ever spawned, so
+                        // either ffmpeg was not needed for this file/backend, or
+                        // it is already installed (the consent modal's Download
+                        // button ran `ensure_ffmpeg(DownloadConsent::Always)`
+                        // and this worker only starts once that succeeded) --
+                        // `ensure_ffmpeg`'s doc: an already-installed ffmpeg
+                        // short-circuits every consent variant to `Ok`, so
+                        // `Never` here can't newly refuse anything `Ready`
+                        // didn't already clear.
+                        backend::open_video_ensuring(
+                            &path,
+                            backend,
+                            None,
+                            fit,
+                            filter,
+                            None,
+                            &mut || ensure_ffmpeg(DownloadConsent::Never),
+                        )?
+                    }
+                    // The web build decodes the uploaded bytes with the builtin
+                    // backend (no ffmpeg); the guards inside `open_video_bytes`
+                    // refuse anything it cannot decode correctly.
+                    #[cfg(target_arch = "wasm32")]
+                    GenSource::Video { bytes, name } => backend::open_video_bytes(&name, bytes)?,
+                    GenSource::Playlist(_) => {
+                        return Err("a playlist clip cannot be another playlist".to_string());
+                    }
+                })
             };
+            // A lone source is a playlist of one, unnamed: it has no buttons.
+            let clips = match source {
+                GenSource::Playlist(clips) => clips,
+                single => vec![(String::new(), single)],
+            };
+            let mut raws = Vec::with_capacity(clips.len());
+            for (name, source) in clips {
+                raws.push((name, open(source)?));
+            }
 
             // Omitted resize means "use the source's own dimensions" -- skip
             // the resize entirely rather than resampling to an identical
@@ -1888,25 +2329,79 @@ impl VideoApp {
             // open_path` takes none), so this is the one place that ever
             // resizes or resamples regardless of which backend produced
             // `raw`.
-            let size = if resize { Some((width, height)) } else { None };
-            let adapted = AdaptedSource {
-                inner: raw.as_ref(),
-                size,
-                fit,
-                filter,
-                target_fps: fps,
-                start_s: start,
-                duration_s: duration,
-                max_frames,
+            // Bars found on the raw frames, as `--auto-crop` finds them --
+            // clip by clip, so one clip's letterbox can't leave bars on the
+            // next.
+            let mut adjusts = Vec::with_capacity(raws.len());
+            for (_, raw) in &raws {
+                adjusts.push(if auto_crop {
+                    match detect_letterbox(raw.as_ref(), start)? {
+                        Some(crop) => {
+                            info!("Cropping bars: keeping {crop}");
+                            Adjustments { crop: Some(crop), ..adjust }
+                        }
+                        None => adjust,
+                    }
+                } else {
+                    adjust
+                });
+            }
+            // Every playlist clip fills the first one's screen, as the CLI
+            // fits them.
+            let size = if resize {
+                Some((width, height))
+            } else if raws.len() > 1 {
+                let native = raws[0].1.info();
+                Some(adjusts[0].size(native.width, native.height))
+            } else {
+                None
             };
+            let adapted: Vec<AdaptedSource> = raws
+                .iter()
+                .zip(adjusts)
+                .map(|((_, raw), adjust)| AdaptedSource {
+                    inner: raw.as_ref(),
+                    size,
+                    fit,
+                    filter,
+                    target_fps: fps,
+                    start_s: start,
+                    duration_s: duration,
+                    max_frames,
+                    key: None,
+                    adjust,
+                })
+                .collect();
 
-            // The resampled frame count isn't knowable up front without
-            // running the stream (see `AdaptedSource::info`), so this logs
-            // only what's known before the render starts.
-            let info = adapted.info();
-            info!("Building frames at {}x{} ({} fps)...", info.width, info.height, info.fps);
             let mut progress = ChannelProgress::new(progress_tx, cancel_flag);
-            let world = mode.build(&adapted, &anim_opts, &mut progress)?;
+            let world = if let [adapted] = adapted.as_slice() {
+                // The resampled frame count isn't knowable up front without
+                // running the stream (see `AdaptedSource::info`), so this
+                // logs only what's known before the render starts.
+                let info = adapted.info();
+                info!("Building frames at {}x{} ({} fps)...", info.width, info.height, info.fps);
+                mode.build(adapted, &anim_opts, &mut progress)?
+            } else {
+                // Every clip is counted here, as `PlaylistSource::new` must
+                // to place the buttons, so the whole length is known up front.
+                let clips = raws
+                    .iter()
+                    .zip(&adapted)
+                    .map(|((name, _), clip)| (name.clone(), clip as &dyn FrameSource))
+                    .collect();
+                let source = PlaylistSource::new(clips, auto_advance)?;
+                let frames = source.playlist().frame_count();
+                if frames > max_frames {
+                    return Err(max_frames_error(max_frames));
+                }
+                info!(
+                    "Playlist of {} clip(s), {frames} frame(s) in all",
+                    source.playlist().clips.len()
+                );
+                let mut anim_opts = anim_opts;
+                anim_opts.seek.playlist = Some(source.playlist().clone());
+                mode.build(&source, &anim_opts, &mut progress)?
+            };
             deliver_world_unless_cancelled(world, &progress, &out_file, out_clipboard)
         };
 
@@ -2266,4 +2761,42 @@ mod tests {
             );
         }
     }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn a_playlist_refuses_what_the_cli_playlist_refuses() {
+        let video = || Input::Video { path: "a.mp4".into(), name: "a.mp4".to_string() };
+        let clip = || PickedVideo::new("b.mp4".into());
+        let app = VideoApp { input: video(), playlist: vec![clip()], ..VideoApp::default() };
+        assert_eq!(app.check_playlist(), Ok(()));
+        assert_eq!(clip_name("b.mp4"), "b");
+
+        let clocked = VideoApp {
+            external_clock: true,
+            input: video(),
+            playlist: vec![clip()],
+            ..VideoApp::default()
+        };
+        assert!(clocked.check_playlist().unwrap_err().contains("external clock"));
+        let subtitled = VideoApp {
+            subtitles: Some(("a.srt".to_string(), picked_track())),
+            input: video(),
+            playlist: vec![clip()],
+            ..VideoApp::default()
+        };
+        assert!(subtitled.check_playlist().unwrap_err().contains("subtitles"));
+        let stuck = VideoApp {
+            auto_advance: false,
+            loop_playback: false,
+            input: video(),
+            playlist: vec![clip()],
+            ..VideoApp::default()
+        };
+        assert!(stuck.check_playlist().unwrap_err().contains("auto-advance off"));
+
+        // One clip is not a playlist, so none of that is refused.
+        let single = VideoApp { external_clock: true, input: video(), ..VideoApp::default() };
+        assert!(!single.is_playlist());
+        assert_eq!(single.check_playlist(), Ok(()));
+    }
 }
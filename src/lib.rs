@@ -1,13 +1,18 @@
 pub mod anim;
 pub mod audio;
+pub mod dither;
 pub mod map;
+pub mod mesh;
 pub mod midi;
 pub mod opt;
+pub mod pointcloud;
 pub mod progress;
+pub mod sign;
 pub mod subs;
 pub mod text;
 pub mod util;
 pub mod video;
+pub mod voxel;
 
 #[cfg(feature = "gui")]
 pub mod gui;
@@ -1,6 +1,13 @@
+// `clap_app!` expands one level per argument, and the flag list has outgrown
+// the default limit of 128.
+#![recursion_limit = "256"]
+
 pub mod map;
+pub mod mesh;
 pub mod opt;
+pub mod pointcloud;
 pub mod util;
+pub mod voxel;
 // NOT `pub mod text;`. `src/text.rs` is a LIBRARY module (`heightmap::text`),
 // and declaring it here as well would compile a second, distinct copy of every
 // type in it into this binary -- at which point the `TextOptions` the `--text`
@@ -13,7 +20,7 @@ pub mod util;
 mod progress_cli;
 
 #[cfg(not(target_arch = "wasm32"))]
-use crate::{map::*, opt::*, util::*};
+use crate::{map::*, opt::*, pointcloud::*, util::*};
 #[cfg(not(target_arch = "wasm32"))]
 use brdb::World;
 #[cfg(not(target_arch = "wasm32"))]
@@ -29,26 +36,42 @@ use env_logger::Builder;
 use heightmap::{
     anim::{
         AnimEncoding, AnimMode,
-        bricks::{AnimOptions, DisplayBrickStyle},
-        color_bricks, cost,
-        pack::MAX_FRAMES,
+        budget::{self, Budget},
+        bricks::{AnimOptions, DisplayBrickStyle, PanelGrid, ScreenOrientation},
+        clock::{PlayOrder, Playback},
+        color_bricks, cost, delta_bricks, layout,
+        soundtrack::build_with_soundtrack,
+        pack::{HEX_STRIDE, MAX_FRAMES},
+        palette::Palette,
+        playlist::{Playlist, PlaylistSource},
+        replay,
+        seek::{self, SeekControls},
         subtitle_display::{DEFAULT_SUBTITLE_LIFT, DEFAULT_SUBTITLE_SCALE},
         text_layout,
     },
     audio::{
         AudioMode,
         backend::{AudioBackend, open_audio_track},
+        budget as audio_budget,
         cost as audio_cost,
-        speakers::{build_midi_event_world, build_speaker_world, build_voice_world},
+        playlist as audio_playlist,
+        speakers::{
+            Soundtrack, build_midi_event_world, build_speaker_world_with_seek,
+            build_voice_world_with_seek,
+        },
         track::{AudioOptions, SynthWave, analyze},
         voices::{MAX_PITCH_SNAP_CENTS, analyze_voices},
     },
+    dither::{Dither, quantize, read_palette_file},
     midi::{MidiOptions, ToneAssignment, analyze_midi, discover},
+    sign::{DEFAULT_SIGN_LINE, SignAlign, SignOptions, build_sign, parse_hex_color},
     subs::{self, Subtitles},
     text::*,
     video::{
         backend::{self, Backend},
+        adjust::{Adjustments, Crop, Flip, Grade, Rotation, detect_letterbox},
         ffmpeg::{DownloadConsent, ensure_ffmpeg},
+        key::{ChromaKey, Keying},
         scale::{Filter, FitMode, estimated_frame_count, max_frames_error},
         source::{Source, decode, is_animated, is_video_path},
         stream::{AdaptedSource, FrameSource},
@@ -82,7 +105,7 @@ fn cli() -> clap::App<'static, 'static> {
         (version: env!("CARGO_PKG_VERSION"))
         (author: "github.com/Meshiest")
         (about: "Converts heightmap images (PNG/JPG) to Brickadia save files")
-        (@arg INPUT: +required +multiple "Input heightmap image files (PNG/JPG)")
+        (@arg INPUT: required_unless[signtext] +multiple "Input heightmap image files (PNG/JPG), or point clouds (.xyz/.csv/uncompressed .las), or text files for --sign")
         (@arg output: -o --output +takes_value "Output file (BRDB, BRZ)")
         (@arg colormap: -c --colormap +takes_value "Input colormap image (PNG/JPG)")
         (@arg vertical: -v --vertical +takes_value "Vertical scale multiplier (default 1)")
@@ -95,27 +118,52 @@ fn cli() -> clap::App<'static, 'static> {
         (@arg terrain: --terrain "Render the terrain as SMOOTH micro bricks instead of flat-topped tiles: every pixel gets a sloped top chosen from Brickadia's micro wedge family (ramp, wedge corner, inner corner, and the stacked diagonal corner+triangle), fitted to the four shared vertex heights around it. Heights are sampled on a shared (w+1)x(h+1) vertex grid so neighbouring cells MEET rather than step. Replaces --tile/--smooth/--micro/--stud and the optimizers, which have no meaning once the top face is not flat")
         (@arg rampify: --rampify "Rampify the terrain with Wrapperup's rampifier: fit full-size ramps, wedges and ramp corners onto the height column surface and fill the rest with plain bricks. Coarser than --terrain (one plate of vertical resolution, runs of at most 4 studs) but uses ordinary bricks rather than micro pieces. Replaces --tile/--smooth/--micro/--stud and the optimizers")
         (@arg wedge: --wedge "Build TERRACED wedge terrain: tops stay flat, every height is a whole terrace step, and the outlines of the terraces are cut at 45 degrees by vertical side wedges (PB_DefaultSideWedge) -- convex corners chamfered, concave corners filled, collinear staircases merged into single large wedges, flat tops greedy-merged into boxes. Unbuildable configurations (diagonal crossings, spikes) are eroded first. Unlike --terrain and --rampify, slopes are not approximated: this is the terraced 'brick terrain' look of hand-built Brickadia maps. Replaces --tile/--smooth/--micro/--stud and the optimizers")
-        (@arg prefab: --prefab "Heightmap/image renders: write a PREFAB bundle instead of a world, so the save can be dropped in Brickadia's Prefabs folder and spawned from the prefab browser rather than loaded as a level")
+        (@arg layers: --layers "Layered heightmap: read the input files as bottom/top PAIRS (bottom1 top1 bottom2 top2 ...) instead of stacking them, and fill every column only between each pair's two values -- arches, cave ceilings, floating islands. A column whose top is not above its bottom is left empty. Works with the default blocks renderer and --greedy; refused with --terrain/--rampify/--wedge and --img")
+        (@arg cellsize: --("cell-size") +takes_value "Point cloud (.xyz/.csv/.las input): the width of one grid cell in the points' own units, usually metres (default 1). Every cell becomes one pixel of the heightmap, --size studs wide. Heights keep their true proportions at --vertical equal to the stud size of a pixel (--vertical 1 per --size 1)")
+        (@arg cellz: --("cell-z") +takes_value "Point cloud: how the points in one cell become its height -- 'max' (default; rooftops and canopy), 'mean', or 'min' (closest to bare ground)")
+        (@arg gapfill: --("gap-fill") +takes_value "Point cloud: how many cells an empty cell looks outward for points to interpolate from, by inverse distance (default 8). 0 leaves empty cells empty; they are transparent, so --cull drops them")
+        (@arg relief: --relief "Relief: carve the input PICTURE into a heightmap by its brightness -- bright pixels stand proud, dark ones sink -- and colour it from the same picture, so one image makes a sculpted floor without a hand-made greyscale copy. Works with every surface renderer (--terrain/--rampify/--wedge, --greedy)")
+        (@arg reliefdepth: --("relief-depth") +takes_value "Relief: how many height levels separate black from white (default 16); each level is --vertical units")
+        (@arg depthmap: --("depth-map") +takes_value "Relief: take the heights from this depth map (a greyscale PNG/JPG the size of the picture, near = bright, as MiDaS writes it) instead of the picture's own brightness")
+        (@arg wall: --wall +takes_value "Heightmap/image renders: stand the build upright as a wall along the 'x' or 'y' axis instead of lying on the ground -- a mural, billboard or relief wall. The picture's top row is the top of the wall and heights stand out of its face. Works with every surface renderer")
+        (@arg prefab: --prefab "Heightmap/image/voxel renders: write a PREFAB bundle instead of a world, so the save can be dropped in Brickadia's Prefabs folder and spawned from the prefab browser rather than loaded as a level")
         (@arg snap: --snap "Snap bricks to the brick grid")
         (@arg lrgb: --lrgb "DEPRECATED and ignored. Colormap pixels always go into the bricks without conversion, because a save file stores brick colours in the same encoding as an image")
         (@arg img: -i --img "Make the heightmap flat and render an image")
+        (@arg palette: --palette +takes_value "Image or colour --text: snap the picture to a FIXED palette file before building it -- a list of RRGGBB hex or 'r g b' lines (GIMP .gpl works), or a swatch image whose distinct colours are the palette. Like --colors, this gives the merger (or the text encoder) long same-colour runs on a photo")
+        (@arg dither: --dither +takes_value "Image or colour --text with --colors/--palette, or text with a monochrome glyph mode: how to spread the rounding error -- 'none' (default; longest runs, fewest bricks, a hard luma cut), 'ordered' (a regular 8x8 Bayer pattern), 'floyd-steinberg' (error diffusion; the best picture and the most bricks) or 'atkinson' (lighter error diffusion; clean highlights and shadows)")
         (@arg glow: --glow "Make the heightmap (or animation display) glow at 0 intensity")
         (@arg srgb2lin: --("srgb-to-linear") "Animation: convert sRGB frame colors to linear before encoding (use if the render looks too bright)")
         (@arg hdmap: --hdmap "Using a high detail rgb color encoded heightmap")
         (@arg nocollide: --nocollide "Disable brick collision")
         (@arg greedy: --greedy "Use greedy optimization")
         (@arg text: --text "Render the input image as TextDisplay component bricks")
+        (@arg mesh: --mesh "Mesh: voxelize a triangle mesh (.obj with its .mtl colours and map_Kd textures, or binary/ASCII .stl) at --resolution and build the voxels from bricks exactly as --vox does. Honours the same flags as --vox")
+        (@arg meshresolution: --resolution +takes_value "Mesh: voxels along the mesh's LONGEST axis (default 64); the other two axes follow its proportions")
+        (@arg meshfill: --fill +takes_value "Mesh: 'solid' (default) fills everything the surface encloses, which the mesher merges into a few large boxes; 'shell' keeps the one-voxel surface only, for open meshes or a hollow build")
+        (@arg meshup: --up +takes_value "Mesh: which axis of the file points up, y or z (default y for .obj as Blender exports it, z for .stl)")
+        (@arg vox: --vox "Voxel model: read the input as a MagicaVoxel .vox file and build it from bricks, one cube --size studs wide per voxel (micro units with --micro). Every layer is greedy meshed per colour and identical rectangles in consecutive layers stack into one brick, so a solid block of one colour is one brick. Multi-model scenes are placed as the editor shows them. Honours --size, --micro, --tile/--smooth/--stud, --glow, --nocollide and --prefab")
         (@arg fillchar: --("fill-char") +takes_value "Text mode: glyph for opaque pixels (default █)")
         (@arg emptychar: --("empty-char") +takes_value "Text mode: glyph for transparent pixels (default space)")
         (@arg charrepeat: --("char-repeat") +takes_value "Text mode: glyphs emitted per pixel (default 2)")
         (@arg alphathreshold: --("alpha-threshold") +takes_value "Text mode: alpha below this is transparent (default 128)")
-        (@arg lineheight: --("line-height-world") +takes_value "Text mode: world units per pixel row / pixel size (default 1)")
-        (@arg font: --font +takes_value "Text mode: font preset (monaspace, iosevka, orbitron; default monaspace)")
+        (@arg lineheight: --("line-height-world") +takes_value "Text mode: world units per pixel row / pixel size (default 1). --sign: world units per line of text (default 10)")
+        (@arg font: --font +takes_value "Text mode: font preset (monaspace, iosevka, orbitron, or a name from --font-file; default monaspace)")
+        (@arg fontfile: --("font-file") +takes_value "Text mode: load extra font presets from this file ([[font]] tables with name, asset and optional glyph/geometry keys) so --font can select them by name")
         (@arg braille: --braille "Text mode: monochrome braille glyphs (8 pixels per character)")
         (@arg blocks: --blocks "Text mode: monochrome quadrant-block glyphs (4 pixels per character)")
-        (@arg lumathreshold: --("luma-threshold") +takes_value "Braille/blocks: pixels at least this bright are drawn (default 128)")
-        (@arg invert: --invert "Braille/blocks: draw dark pixels instead of bright ones")
+        (@arg sextants: --sextants "Text mode: monochrome sextant glyphs (6 pixels per character, 2x3). Provisional: its glyph spacing is scaled from --blocks, not measured in game, so cells may not line up exactly")
+        (@arg octants: --octants "Text mode: monochrome octant glyphs (8 pixels per character, 2x4, solid cells; needs a font with Unicode 16 octants). Provisional: its glyph spacing is scaled from --blocks, not measured in game, so cells may not line up exactly")
+        (@arg lumathreshold: --("luma-threshold") +takes_value "Braille/blocks/sextants/octants: pixels at least this bright are drawn (default 128)")
+        (@arg invert: --invert "Braille/blocks/sextants/octants: draw dark pixels instead of bright ones. --relief: raise dark pixels instead of bright ones")
         (@arg material: --material +takes_value "Text mode: material (unlit, graffiti, plastic, metallic, glow, translucent, glass; default unlit)")
+        (@arg sign: --sign "Sign maker: lay out the INPUT text file(s) as one TextDisplay sign instead of rendering an image. Takes --font/--font-file, --line-height-world and --material from text mode")
+        (@arg signtext: --("sign-text") +takes_value "Sign maker: the sign's text itself, instead of an input file (implies --sign). Inline <color=\"RRGGBB\">...</> runs are kept")
+        (@arg signcolor: --("sign-color") +takes_value "Sign: text colour outside inline <color> runs, RRGGBB (default FFFFFF)")
+        (@arg align: --align +takes_value "Sign: line alignment, left, centre or right (default left)")
+        (@arg wrap: --wrap +takes_value "Sign: word-wrap lines longer than this many characters (default: only the text's own line breaks)")
+        (@arg plate: --plate +takes_value "Sign: put a backing plate of this RRGGBB colour behind the text")
+        (@arg outline: --outline "Sign: draw a black outline around the glyphs")
         (@arg audiomode: --("audio-mode") +takes_value "Audio output mode: 'bank' = Pitch-Per-Speaker (a fixed bank of ~79 speakers, each owning one pitch, only their volumes written -- best for speech and broadband material) or 'voice' = Pitch Switching (--max-voices speakers that TRACK spectral peaks, re-pitching every frame -- no band grid, so no tuning error; best for tonal material such as piano)")
         (@arg audiotrack: --("audio-track") +takes_value "Audio: which audio stream to decode, 0 = first (default 0). Dual-audio releases commonly carry the original language first and the dub second, so which is 'first' is a container-ordering accident. Needs the ffmpeg backend")
         (@arg audiobands: --bands +takes_value "Audio: total speakers including noise bands. Tonal bands sit on exact equal-tempered steps, so this SELECTS THE SPAN (the steps nearest A440), not the spacing. Default: every step the emitter's pitch range holds -- 79 tonal at --subdiv 12 (F#1..C8), 159 at 24")
@@ -135,30 +183,59 @@ fn cli() -> clap::App<'static, 'static> {
         (@arg audiomaxdist: --("max-distance") +takes_value "Audio: speaker audible range in units (default 400; 10 units = 1 brick). Raise it (e.g. 4000) to be heard across a big build")
         (@arg audiospeakersinchip: --("speakers-in-chip") "Audio: place the speaker cluster INSIDE the microchip's own inner grid instead of beside it on the main grid, so the whole audio device is one portable microchip. The speakers play from the chip's ORIGIN regardless of their inner-grid layout (an AudioEmitter on a microchip inner grid emits from the chip's world position), so the layout is physical placement only, not spatial audio. Default off (speakers beside the chip)")
         (@arg audiosynth: --synth +takes_value "Audio: the synth waveform every TONAL band plays through -- sine (default), square, triangle or sawtooth. Applies to tonal bands in BOTH modes; white/pink --noise-bands keep their own noise assets and are unaffected. Default sine renders exactly as before this flag existed")
-        (@arg midi: --midi "MIDI (midi2brick): read the input as a Standard MIDI File and build an EVENT-BASED speaker world -- each track's notes are stored as spans and played by a runtime playhead. The input is the .mid; -o is the .brz. Tones: a waveform is picked per instrument from its General MIDI program (bass/electric guitar sawtooth, lead square, piano/vocals sine, ...); --synth forces one wave for the whole file. Per-track manual tones are a GUI feature. Reuses --inner-radius, --max-distance, --gain, --no-loop, --no-control-buttons, --speakers-in-chip and --polyphony-cap")
+        (@arg midi: --midi "MIDI (midi2brick): read the input as a Standard MIDI File and build an EVENT-BASED speaker world -- each track's notes are stored as spans and played by a runtime playhead. The input is the .mid; -o is the .brz. Tones: a waveform is picked per instrument from its General MIDI program (bass/electric guitar sawtooth, lead square, piano/vocals sine, ...); --synth forces one wave for the whole file. Per-track manual tones are a GUI feature. Reuses --inner-radius, --max-distance, --gain, --no-loop, --repeat, --no-control-buttons, --speakers-in-chip and --polyphony-cap")
         (@arg midilist: --("midi-list") "MIDI: with --midi, print the discovered instruments (name, channel, note count, max polyphony) and the file's format/duration/tempo, then exit without building")
         (@arg midipolyphony: --("polyphony-cap") +takes_value "MIDI: maximum speakers per instrument, however many notes it plays at once (default 8). A busier instrument steals its oldest sounding note")
         (@arg midirate: --("playback-rate") +takes_value "MIDI: playback speed multiplier baked into the clock (default 1.0; 2.0 = double speed, 0.5 = half). The generated Rate pin still overrides it at runtime")
         (@arg nopercussion: --("no-percussion") "MIDI: skip the percussion channel (10). By default each drum note plays a oneshot sample, mapped from its General MIDI drum note through a fold table; this builds only the pitched instruments")
         (@arg animmode: --("anim-mode") +takes_value "Animation output mode (brick, text). 'brick' builds one display brick per pixel, driven by the encoding --anim-encoding selects. 'text' builds one animated Component_TextDisplay per BAND of image rows instead -- roughly two orders of magnitude fewer gates (a 192x108 clip is 113 gates against 4613), at the cost of glyph-grid rendering rather than real bricks. Text mode reuses --font, --char-repeat, --fill-char, --empty-char, --alpha-threshold and --line-height-world, and adds --colors")
-        (@arg animcolors: --("colors") +takes_value "Text mode: quantize to at most N colours with a median-cut palette (default 0 = full 24-bit colour). Fewer colours means longer same-colour runs and a smaller save; useful values are 16 to 64")
-        (@arg animencoding: --("anim-encoding") +takes_value "Animation pixel encoding (hex, color-array; default hex). 'hex' packs each frame into a shared RRGGBB string per chunk; 'color-array' gives each pixel its own colour array -- fewer gate evaluations and no string work, at the cost of more host RAM to build")
+        (@arg animcolors: --("colors") +takes_value "Text renders (still and animated) and --img: quantize to at most N colours with a median-cut palette (default 0 = full 24-bit colour). Fewer colours means longer same-colour runs and a smaller save; useful values are 16 to 64. Also sizes --anim-encoding palette's palette (default 64; 64 or fewer is one character per pixel)")
+        (@arg animencoding: --("anim-encoding") +takes_value "Animation pixel encoding (hex, color-array, delta, palette; default hex). 'hex' packs each frame into a shared RRGGBB string per chunk; 'color-array' gives each pixel its own colour array -- fewer gate evaluations and no string work, at the cost of more host RAM to build; 'delta' stores only the frames where each pixel changes, read by a per-pixel playhead -- far smaller saves on mostly static footage, at 13 gates per pixel (15 with --loop-range or seek buttons, whose skips then go to the next or previous mark every --skip-seconds); 'palette' is hex with one or two characters per pixel indexing a shared --colors palette (default 64) -- 3 to 6x fewer stored characters, at 3 gates per pixel")
         (@arg animfps: --fps +takes_value "Animation output frame rate (default 10)")
         (@arg animstart: --start +takes_value "Start offset into the source, seconds")
         (@arg animduration: --duration +takes_value "Duration taken from the source, seconds")
         (@arg animmaxframes: --("max-frames") +takes_value "Cap on emitted frames (default 1048560; frames past 65535 spill into extra arrays)")
+        (@arg maxgates: --("max-gates") +takes_value "Fit to a budget: pick the best width, frame rate, encoding and palette whose estimated gate count (all chips together) stays under N. The flags set the top of every range -- --width, --fps, --colors, and --anim-encoding if given, which then stays fixed -- and the search only lowers them. Video, images and audio (where it lowers --audio-fps and the speaker count); not --playlist or --with-audio")
+        (@arg maxsave: --("max-save-size") +takes_value "Fit to a budget, as --max-gates: the save must stay under this size (e.g. 200M; K, M and G are binary). Measured against an upper bound, so the real save is usually well under it")
+        (@arg maxram: --("max-ram") +takes_value "Fit to a budget, as --max-gates: the build must stay under this much host memory at its peak (e.g. 4G). An upper bound on what the renderer holds, not counting decode")
+        (@arg animpreview: --preview +takes_value "Animation: instead of a save, write what its screen would show -- the encoded frames decoded back, with culling, --colors, --srgb-to-linear, text runs, --fps resampling, playback order and subtitles -- as an animated .gif, an APNG .png, or numbered PNGs in a directory (a path with no extension). GIF re-quantises to 256 colours; the other two are exact")
+        (@arg animpreviewscale: --("preview-scale") +takes_value "Animation: with --preview, enlarge each pixel N times (default: enough for a 480-pixel-wide picture, at most 16)")
         (@arg animwidth: --width +takes_value "Target width in pixels")
         (@arg animheight: --height +takes_value "Target height in pixels")
         (@arg animfit: --fit +takes_value "Fit mode (exact, contain, cover; default contain)")
         (@arg animfilter: --filter +takes_value "Resample filter (lanczos, nearest; default lanczos)")
+        (@arg crop: --crop +takes_value "Animation: keep only this rectangle of the source, WxH+X+Y in source pixels (e.g. 1280x536+0+92), before --width/--height. Refused if it runs off the frame. Not with --auto-crop")
+        (@arg autocrop: --("auto-crop") "Animation: find the source's black letterbox/pillarbox bars, from a dozen frames a second apart from --start, and crop them off. Logs the rectangle it chose, for --crop next time")
+        (@arg rotate: --rotate +takes_value "Animation: turn the source clockwise by 90, 180 or 270 degrees, after --crop and before --width/--height")
+        (@arg flip: --flip +takes_value "Animation: mirror the source -- h (left-right), v (top-bottom) or hv (both), after --rotate")
+        (@arg brightness: --brightness +takes_value "Animation: add to every colour channel, -1 to 1 of full scale (default 0)")
+        (@arg contrast: --contrast +takes_value "Animation: stretch colours about mid-grey (default 1; 0 = flat grey, 2 = twice the contrast)")
+        (@arg saturation: --saturation +takes_value "Animation: scale how colourful each pixel is (default 1; 0 = greyscale, 1.5 = half as vivid again)")
+        (@arg gamma: --gamma +takes_value "Animation: gamma-correct the colours (default 1; above 1 lifts the shadows, below 1 darkens them)")
+        (@arg chromakey: --("chroma-key") +takes_value "Animation: make pixels near this colour transparent, so a green-screen video renders as a see-through, culled display. RRGGBB, or green/blue for the pure primaries; a real screen is usually better given by its own colour (e.g. 00B140). Applied after --width/--height, on either backend")
+        (@arg keytolerance: --("key-tolerance") +takes_value "Animation: with --chroma-key, how far a pixel's colour may be from the key and still be cut, in chroma units (default 48; 0 to about 180)")
+        (@arg keysoftness: --("key-softness") +takes_value "Animation: with --chroma-key, the band past --key-tolerance over which a pixel fades back in, for soft edges (default 32; 0 = hard edge)")
+        (@arg keyspill: --("key-spill") +takes_value "Animation: with --chroma-key, how much of the key's colour cast to remove from the pixels that stay, 0 to 1 (default 1). Lower it if the foreground itself is greenish")
+        (@arg mask: --mask +takes_value "Animation: a still image stretched over every frame as a transparency mask -- white keeps, black cuts, grey fades, and the mask's own alpha cuts too. Combines with --chroma-key")
         (@arg subtitles: --subtitles +takes_value "Animation: render this subtitle file (.srt, .ass/.ssa) as a single wired TextDisplay overlaying the bottom of the screen -- 2 gates for the whole track, centred and outlined, at vector-glyph size rather than the screen's pixel grid. The file is read in SOURCE time, so --start is honoured. Mutually exclusive with --subtitle-track")
         (@arg subtitletrack: --("subtitle-track") +takes_value "Animation: extract subtitles from the input container instead, 0 = the first SUBTITLE stream (not the first stream). Text tracks only -- a PGS/DVD/DVB track is an image sequence with no text in it, and is refused by name rather than rendered as an empty track. Needs ffmpeg. Mutually exclusive with --subtitles")
         (@arg subtitlescale: --("subtitle-scale") +takes_value "Animation: how much bigger a subtitle line is than one row of the screen (default 6). At 192 px wide the screen is hundreds of glyph cells across while a subtitle line is 40-60 characters, so at equal size the text would occupy a seventh of the width; 6 covers about half. Ignored without --subtitles/--subtitle-track")
-        (@arg subtitlelift: --("subtitle-lift") +takes_value "Animation: world units to lift the subtitle anchor toward the top of the picture (default 8, measured by eye against --anim-mode text at 192x108 with --subtitle-scale 6). --anim-mode brick/color-array lay their screen flat, so the lift there moves the OPPOSITE horizontal axis and is unverified by eye. Refused, not clamped, if it would push the anchor to a negative coordinate on a picture shorter than the lift. Ignored without --subtitles/--subtitle-track")
+        (@arg subtitlelift: --("subtitle-lift") +takes_value "Animation: world units to lift the subtitle anchor toward the top of the picture (default 8, measured by eye against --anim-mode text at 192x108 with --subtitle-scale 6). A brick-mode floor screen (the default --screen-orientation) lies flat, so the lift there moves the OPPOSITE horizontal axis and is unverified by eye. Refused, not clamped, if it would push the anchor to a negative coordinate on a picture shorter than the lift. Ignored without --subtitles/--subtitle-track")
         (@arg externalclock: --("external-clock") "Expose Frame as a chip input instead of running a timer")
         (@arg noloop: --("no-loop") "Play through once and stop on the last frame, instead of looping forever (the default). Applies to video and audio alike. Inert with --external-clock, which builds no timer at all")
+        (@arg pingpong: --("ping-pong") "Play first to last and back again instead of from the first again. Video, audio and MIDI alike; not with seek buttons or --playlist. Inert with --external-clock")
+        (@arg looprange: --("loop-range") +takes_value "Play everything before START once, then loop START..END (e.g. 0:05,0:20): an intro, then a section over and over. Times as --chapters takes them, counted from the start of the RENDERED clip rather than the source; an END past the clip is its end. Video, audio and MIDI alike (a MIDI range in seconds of the piece); not with seek buttons, --playlist or --ping-pong. Inert with --external-clock")
+        (@arg repeat: --repeat +takes_value "Play this many times (or, with --ping-pong, there and back this many times; with --loop-range, the section this many times) and then stop, instead of looping forever. Video, audio and MIDI alike. Not with --no-loop; inert with --external-clock")
         (@arg nocontrolbuttons: --("no-control-buttons") "Do NOT pre-generate the three physical Pause/Restart/Resume BUTTON bricks on the main grid, wired into the clock's control pins (default: buttons ON, so a fresh render is pausable/restartable/resumable with no manual wiring). Video and audio alike. Adds 9 main-grid bricks and 6 wires per render; no extra microchip gate. Inert with --external-clock, which builds no timer and so exposes no control pins")
+        (@arg animskip: --("skip-seconds") +takes_value "Animation, --audio-mode and --midi: add two buttons beside Pause/Restart/Resume that skip back and forward this many seconds (e.g. 10). The picture wraps around the clip's ends. With --midi or --anim-encoding delta they go to marks this many seconds apart instead: forward to the next, back to the last one at least a second behind. Not with --external-clock or --with-audio")
+        (@arg animchapters: --chapters +takes_value "Animation, --audio-mode and --midi: add Previous/Next Chapter buttons beside Pause/Restart/Resume. Chapter starts are a comma-separated list of source times (90, 1:30, 1:02:05), 'subtitles' for every cue start of --subtitles/--subtitle-track, or 'container' for the video file's own chapters (needs ffmpeg). Chapters outside the rendered clip are dropped. With --midi, times in the piece. Not with --external-clock or --with-audio")
+        (@arg animseekbar: --("seek-bar") +takes_value "Animation, --audio-mode and --midi: add a progress bar of this many buttons beside Pause/Restart/Resume, each jumping to its own fraction of the clip. Not with --external-clock or --with-audio")
+        (@arg animplaylist: --playlist "Animation and --audio-mode: play several input videos or songs back to back in one world, each its own clip, with Previous/Next Clip and a button per clip beside Pause/Restart/Resume. Every video clip is fitted to the first one's size (or --width/--height) and played at --fps; --start/--duration window each clip. Songs are each analysed with the same flags and play through the same speakers. Not with --midi, --external-clock, --with-audio, --chapters, subtitles, the --max-* budget flags or --anim-encoding delta")
+        (@arg animnoautoadvance: --("no-auto-advance") "Animation and --audio-mode: with --playlist, loop each clip on itself instead of running into the next one. Costs one frame and two gates per clip. Not with --no-loop")
         (@arg animbrickstyle: --("brick-style") +takes_value "Animation display-brick style (micro, tile; default micro)")
+        (@arg animpanels: --panels +takes_value "Animation: split a brick-mode screen across COLSxROWS microchips (e.g. 2x2; default 1x1). Each panel gets its own chip and arrays, all driven by the first panel's clock, so a screen past the ~20000-gate frame-drop point of a single chip stays playable. --anim-encoding hex only")
+        (@arg animwithaudio: --("with-audio") +takes_value "Animation: also render the video's own audio track (--audio-track picks it) as speakers beside the screen -- 'bank' or 'voice', as --audio-mode. The speakers' clock drives the screen, so Pause/Restart/Resume and Rate move picture and sound together. Analysed at --fps rather than --audio-fps, since both halves read one frame index; takes the other audio flags. Video files only, and not with --external-clock or --start")
+        (@arg animorientation: --("screen-orientation") +takes_value "Animation: which way a brick-mode screen faces -- floor (default: flat on the ground, read from above) or an upright wall facing wall+x, wall-x, wall+y or wall-y. The chip, its interaction plane and any subtitle follow the screen. --anim-mode text always builds its own wall facing +X and ignores it")
         (@arg animpixelextent: --("pixel-extent") +takes_value "Animation display-brick half-extent in units (default 1; 1 = smallest, 2 units wide; tile style is always 4 units tall)")
         (@arg yesdownload: --yes "Consent to downloading ffmpeg if it is missing and a video backend needs it")
         (@arg nodownload: --("no-download") "Never download ffmpeg; error instead if it is missing")
@@ -176,15 +253,15 @@ fn main() {
     let matches = cli().get_matches();
 
     // get files from matches
+    // Absent only for `--sign-text`, which clap lets through without one.
     let heightmap_files = matches
         .values_of("INPUT")
-        .unwrap()
-        .map(|s| PathBuf::from(s))
-        .collect::<Vec<_>>();
+        .map(|v| v.map(|s| PathBuf::from(s)).collect::<Vec<_>>())
+        .unwrap_or_default();
     let colormap_file = matches
         .value_of("colormap")
         .map(PathBuf::from)
-        .unwrap_or(heightmap_files[0].clone());
+        .unwrap_or_else(|| heightmap_files.first().cloned().unwrap_or_default());
     let out_file = matches
         .value_of("output")
         .unwrap_or("./out.brz")
@@ -333,11 +410,11 @@ fn main() {
     // selection below, the code tells the user. This is the rule that
     // `--subtitles` above uses: a user who gives `--terrain --text` and gets a
     // text export would think that the terrain renderer is defective.
-    if ["midi", "audiomode", "animmode", "text"]
+    if ["midi", "audiomode", "animmode", "text", "sign", "signtext"]
         .iter()
         .any(|m| matches.is_present(m))
     {
-        for flag in ["terrain", "rampify", "wedge", "prefab"] {
+        for flag in ["terrain", "rampify", "wedge", "layers", "relief", "wall", "prefab"] {
             if matches.is_present(flag) {
                 warn!(
                     "--{flag} applies to heightmap and --img renders only; this render is \
@@ -345,10 +422,33 @@ fn main() {
                 );
             }
         }
+        for flag in ["vox", "mesh"] {
+            if matches.is_present(flag) {
+                warn!(
+                    "--{flag} is overridden by another output mode on this command line; the \
+                     input is not read as a voxel model or mesh. Pass one output mode"
+                );
+            }
+        }
+    }
+
+    if matches.is_present("sign") || matches.is_present("signtext") {
+        run_sign(&matches, &heightmap_files, &out_file);
+        return;
+    }
+
+    // A playlist jumps the clock between clips (`anim::playlist`), and the
+    // MIDI player's note playheads cannot follow a jump, so a list of MIDI
+    // files is refused rather than cut down to its first file.
+    if matches.is_present("animplaylist") && matches.is_present("midi") {
+        fail(
+            "--playlist cannot take --midi: its clip buttons jump the clock, and the note \
+             playheads only land back on the piece's own loop points. Render one save per file",
+        );
     }
 
     if matches.is_present("midi") {
-        run_midi(&matches, &heightmap_files, &out_file);
+        run_midi(&matches, &heightmap_files, consent, &out_file);
         return;
     }
 
@@ -367,6 +467,11 @@ fn main() {
         return;
     }
 
+    if matches.is_present("vox") || matches.is_present("mesh") {
+        run_voxel(&matches, &heightmap_files, &out_file);
+        return;
+    }
+
     run_heightmap(&matches, &heightmap_files, &colormap_file, &out_file);
 }
 
@@ -400,11 +505,40 @@ fn run_audio(
     // named out loud rather than quietly dropped: a render that silently
     // ignored `--width` would look like it worked and sound identical to
     // one that never got the flag at all.
-    if heightmap_files.len() > 1 {
-        warn!(
-            "--audio-mode uses only the first input file; ignoring {} extra input(s)",
-            heightmap_files.len() - 1
-        );
+    let playlist = matches.is_present("animplaylist");
+    if playlist {
+        if heightmap_files.len() < 2 {
+            fail("--playlist needs at least two input songs");
+        }
+        // Chapters are one song's seconds; the rest are refused for a video
+        // playlist too (see `run_anim`).
+        for (flag, name) in [
+            ("--chapters", "animchapters"),
+            ("--ping-pong", "pingpong"),
+            ("--loop-range", "looprange"),
+        ] {
+            if matches.is_present(name) {
+                fail!("--playlist cannot take {flag}");
+            }
+        }
+        for (flag, name) in [("--no-loop", "noloop"), ("--repeat", "repeat")] {
+            if matches.is_present("animnoautoadvance") && matches.is_present(name) {
+                fail!(
+                    "--no-auto-advance cannot take {flag}: each song loops on itself, so the \
+                     playlist never reaches an end to stop at"
+                );
+            }
+        }
+    } else {
+        if heightmap_files.len() > 1 {
+            warn!(
+                "--audio-mode uses only the first input file; ignoring {} extra input(s)",
+                heightmap_files.len() - 1
+            );
+        }
+        if matches.is_present("animnoautoadvance") {
+            warn!("--no-auto-advance is ignored without --playlist");
+        }
     }
     if matches.is_present("colormap") {
         warn!("--audio-mode ignores --colormap");
@@ -514,19 +648,38 @@ fn run_audio(
     // `--inner-radius`/`--max-distance` pair used to survive the entire
     // analysis and then kill `build_speaker_world`, printing the analysis
     // summary first and the refusal after it.
-    if let Err(e) = audio_cost::check(
-        if voice_mode {
-            AudioMode::Voice
-        } else {
-            AudioMode::Bank
-        },
-        &audio_opts,
-    ) {
+    let audio_mode = if voice_mode { AudioMode::Voice } else { AudioMode::Bank };
+    if let Err(e) = audio_cost::check(audio_mode, &audio_opts) {
         fail!("{e}");
     }
-
+    let budget = match budget_options(matches) {
+        Ok(b) => b,
+        Err(e) => fail(e),
+    };
     let input = &heightmap_files[0];
-    info!("Opening audio {}", input.display());
+    // The speakers' clock takes the same seek graph as a video's. No
+    // subtitles on this path, so `--chapters subtitles` has no cues to read.
+    let seek = match load_seek_controls(matches, input, None, consent) {
+        Ok(s) => s,
+        Err(e) => fail(e),
+    };
+    if !seek.is_empty() && (matches.is_present("pingpong") || matches.is_present("looprange")) {
+        fail(
+            "--skip-seconds, --chapters and --seek-bar cannot take --ping-pong or --loop-range: \
+             a jump lands on a frame of the track, not on a place in the order it is being \
+             played in",
+        );
+    }
+    // The audio estimate has no seek graph in it, so a fit would come out
+    // over the budget by however many gates the buttons add; and a budget
+    // sizes one song.
+    if budget.is_some() && (!seek.is_empty() || playlist) {
+        fail(
+            "--max-gates, --max-save-size and --max-ram cannot take --skip-seconds, \
+             --chapters, --seek-bar or --playlist in audio mode yet",
+        );
+    }
+
     let audio_track = match matches.value_of("audiotrack") {
         Some(s) => match s.parse::<usize>() {
             Ok(v) => v,
@@ -534,146 +687,220 @@ fn run_audio(
         },
         None => 0,
     };
-    let source = match open_audio_track(input, audio_backend, consent, audio_track) {
-        Ok(s) => s,
-        Err(e) => fail!("{e}"),
-    };
-
-    let world = if voice_mode {
-        let streams = match analyze_voices(
-            source.as_ref(),
-            &audio_opts,
-            &mut progress_cli::CliProgress::new(),
-        ) {
+    // One song, or with --playlist every input in turn, each analysed with the
+    // same options so they all play through the same speakers.
+    let inputs = if playlist { heightmap_files } else { &heightmap_files[..1] };
+    let mut audio_opts = audio_opts;
+    let mut bank_songs = Vec::with_capacity(inputs.len());
+    let mut voice_songs = Vec::with_capacity(inputs.len());
+    for input in inputs {
+        info!("Opening audio {}", input.display());
+        let source = match open_audio_track(input, audio_backend, consent, audio_track) {
             Ok(s) => s,
             Err(e) => fail!("{e}"),
         };
-        let st = &streams.stats;
-        info!(
-            "Analyzed {} frame(s) at {} fps across {} voice(s), {} bank(s)",
-            streams.frame_count,
-            streams.fps,
-            streams.voice_count(),
-            streams
-                .frame_count
-                .div_ceil(audio_opts.bank_size.max(1))
-                .max(1),
-        );
-        // Reported every render, not hidden behind a probe. Each of these
-        // answers a question that decides whether the mode worked, and
-        // none is visible in the save: a mean lifetime near 1 frame is
-        // chimes however right the pitches are, and the REAL voices per
-        // frame is the number to compare between renders -- the flag value
-        // is not, and a run where a gate rather than the flag was setting
-        // the count has shipped before.
-        info!(
-            "  {:.2} voices sounding per frame (--max-voices {}), mean voice lifetime \
-             {:.1} frames",
-            st.mean_voices_per_frame(streams.frame_count),
-            streams.voice_count(),
-            st.mean_lifetime(),
-        );
-        info!(
-            "  {} voice-frames tracked, mean {:.1} cents from equal temperament; {:.2}% \
-             of sounding frame pairs jump more than a semitone",
-            st.peak_count,
-            st.mean_abs_cents,
-            st.pitch_jump_fraction() * 100.0,
-        );
-        // The wobble metric, measured before and after smoothing over the
-        // SAME frames -- two runs with different settings would not
-        // continue the same voices, so their jitter would not be
-        // comparable.
-        info!(
-            "  pitch jitter {:.1} cents rms as tracked -> {:.1} written \
-             (--pitch-snap {})",
-            st.raw_jitter_rms_cents(),
-            st.jitter_rms_cents(),
-            audio_opts.pitch_snap_cents,
-        );
-        // **The bleed.** A voice that is sounding while nothing in the
-        // spectrum matches it is playing a note the source has stopped
-        // playing, and the tail is how long it takes to reach exactly
-        // zero. Reported every render because none of the metrics above
-        // can see it -- a voice droning through a whole phrase scores
-        // BETTER on lifetime, on jitter and on jumps alike, which is how
-        // "it bleeds together" and "disembodied" got shipped.
-        let (tail_mean, tail_p95) = st.tail_ms(streams.fps);
-        info!(
-            "  {:.1}% of sounding voice-frames are unmatched (a note that has ended); \
-             time from a partial's end to zero: mean {:.0} ms, p95 {:.0} ms \
-             (--voice-release {})",
-            st.unmatched_fraction() * 100.0,
-            tail_mean,
-            tail_p95,
-            audio_opts.voice_release_ms,
-        );
-        // The voice BUDGET: how much of the build goes on one note's
-        // overtones rather than on separate notes.
+        // Fitted once the source is open, since the frame count at each rate
+        // comes from its length. A source that cannot say how long it is has
+        // nothing to cost, and is refused rather than guessed at.
+        if let Some(budget) = &budget {
+            let info = source.info();
+            let Some(duration) = info.duration_hint else {
+                fail(
+                    "--max-gates, --max-save-size and --max-ram need the audio's length up \
+                     front, and this source does not report one before decoding",
+                );
+            };
+            let rate = info.sample_rate;
+            let fitted = match audio_budget::fit(audio_mode, &audio_opts, budget, duration, rate)
+            {
+                Ok(f) => f,
+                Err(e) => fail(e),
+            };
+            info!(
+                "Fitted to the budget: {} {} at {} fps; {} gate(s), a save of up to {}, up to \
+                 {} of memory",
+                fitted.cost.speakers,
+                if voice_mode { "voice(s)" } else { "band(s)" },
+                fitted.opts.fps,
+                fitted.cost.gates,
+                budget::show_bytes(fitted.save_bytes),
+                budget::show_bytes(fitted.host_bytes),
+            );
+            audio_opts = fitted.opts;
+        }
+
+        // Named after the file, for its button on a playlist.
+        let name = input.file_stem().map(|s| s.to_string_lossy().into_owned());
+        let name = name.unwrap_or_default();
+        if voice_mode {
+            let streams = match analyze_voices(
+                source.as_ref(),
+                &audio_opts,
+                &mut progress_cli::CliProgress::new(),
+            ) {
+                Ok(s) => s,
+                Err(e) => fail!("{e}"),
+            };
+            let st = &streams.stats;
+            info!(
+                "Analyzed {} frame(s) at {} fps across {} voice(s), {} bank(s)",
+                streams.frame_count,
+                streams.fps,
+                streams.voice_count(),
+                streams
+                    .frame_count
+                    .div_ceil(audio_opts.bank_size.max(1))
+                    .max(1),
+            );
+            // Reported every render, not hidden behind a probe. Each of these
+            // answers a question that decides whether the mode worked, and
+            // none is visible in the save: a mean lifetime near 1 frame is
+            // chimes however right the pitches are, and the REAL voices per
+            // frame is the number to compare between renders -- the flag value
+            // is not, and a run where a gate rather than the flag was setting
+            // the count has shipped before.
+            info!(
+                "  {:.2} voices sounding per frame (--max-voices {}), mean voice lifetime \
+                 {:.1} frames",
+                st.mean_voices_per_frame(streams.frame_count),
+                streams.voice_count(),
+                st.mean_lifetime(),
+            );
+            info!(
+                "  {} voice-frames tracked, mean {:.1} cents from equal temperament; {:.2}% \
+                 of sounding frame pairs jump more than a semitone",
+                st.peak_count,
+                st.mean_abs_cents,
+                st.pitch_jump_fraction() * 100.0,
+            );
+            // The wobble metric, measured before and after smoothing over the
+            // SAME frames -- two runs with different settings would not
+            // continue the same voices, so their jitter would not be
+            // comparable.
+            info!(
+                "  pitch jitter {:.1} cents rms as tracked -> {:.1} written \
+                 (--pitch-snap {})",
+                st.raw_jitter_rms_cents(),
+                st.jitter_rms_cents(),
+                audio_opts.pitch_snap_cents,
+            );
+            // **The bleed.** A voice that is sounding while nothing in the
+            // spectrum matches it is playing a note the source has stopped
+            // playing, and the tail is how long it takes to reach exactly
+            // zero. Reported every render because none of the metrics above
+            // can see it -- a voice droning through a whole phrase scores
+            // BETTER on lifetime, on jitter and on jumps alike, which is how
+            // "it bleeds together" and "disembodied" got shipped.
+            let (tail_mean, tail_p95) = st.tail_ms(streams.fps);
+            info!(
+                "  {:.1}% of sounding voice-frames are unmatched (a note that has ended); \
+                 time from a partial's end to zero: mean {:.0} ms, p95 {:.0} ms \
+                 (--voice-release {})",
+                st.unmatched_fraction() * 100.0,
+                tail_mean,
+                tail_p95,
+                audio_opts.voice_release_ms,
+            );
+            // The voice BUDGET: how much of the build goes on one note's
+            // overtones rather than on separate notes.
+            info!(
+                "  {:.1}% of sounding voices sit on a harmonic of another; {:.1} distinct \
+                 fundamentals per frame",
+                st.harmonic_fraction() * 100.0,
+                st.mean_fundamentals(),
+            );
+            voice_songs.push((name, streams));
+        } else {
+            let track = match analyze(
+                source.as_ref(),
+                &audio_opts,
+                &mut progress_cli::CliProgress::new(),
+            ) {
+                Ok(t) => t,
+                Err(e) => fail!("{e}"),
+            };
+            let sounding: usize = track
+                .volumes
+                .iter()
+                .flat_map(|b| b.iter())
+                .filter(|v| **v > 0.0)
+                .count();
+            info!(
+                "Analyzed {} frame(s) at {} fps across {} band(s), {} bank(s)",
+                track.frame_count,
+                track.fps,
+                track.plan.len(),
+                track.frame_count.div_ceil(audio_opts.bank_size.max(1)).max(1),
+            );
+            // The same "what did the flag actually do" report as voice mode's.
+            // `--max-voices` is an upper bound that `--peak-gate` usually
+            // binds first, so the flag value says nothing about the render.
+            // Mean length of a run of one band being continuously non-zero:
+            // THE BEEPING METRIC. A run of one or two frames is a 33-66 ms
+            // blip, heard as a beep rather than as a note, and it is what
+            // `--attack`/`--release` exist to lengthen.
+            let mut runs = 0usize;
+            for band in &track.volumes {
+                let mut on = false;
+                for &v in band {
+                    if v > 0.0 && !on {
+                        runs += 1;
+                    }
+                    on = v > 0.0;
+                }
+            }
+            info!(
+                "  {:.2} bands sounding per frame (--max-voices {}, --peak-gate {}); mean run \
+                 {:.1} frames (--attack {} --release {})",
+                sounding as f64 / track.frame_count as f64,
+                audio_opts.max_voices,
+                audio_opts.peak_gate,
+                if runs > 0 {
+                    sounding as f64 / runs as f64
+                } else {
+                    0.0
+                },
+                audio_opts.attack_ms,
+                audio_opts.release_ms,
+            );
+            bank_songs.push((name, track));
+        }
+    }
+
+    // A playlist's songs back to back on one track (`audio::playlist`), its
+    // clip buttons on the speakers' clock like any other seek button.
+    let auto_advance = !matches.is_present("animnoautoadvance");
+    let with_playlist = |playlist: Playlist| {
         info!(
-            "  {:.1}% of sounding voices sit on a harmonic of another; {:.1} distinct \
-             fundamentals per frame",
-            st.harmonic_fraction() * 100.0,
-            st.mean_fundamentals(),
+            "Playlist of {} song(s), {} frame(s) in all",
+            playlist.clips.len(),
+            playlist.frame_count()
         );
-        match build_voice_world(&streams, &audio_opts) {
+        SeekControls { playlist: Some(playlist), ..seek.clone() }
+    };
+    let world = if voice_mode {
+        let (streams, seek) = if playlist {
+            match audio_playlist::join_streams(voice_songs, auto_advance) {
+                Ok((streams, playlist)) => (streams, with_playlist(playlist)),
+                Err(e) => fail(e),
+            }
+        } else {
+            (voice_songs.remove(0).1, seek.clone())
+        };
+        match build_voice_world_with_seek(&streams, &audio_opts, &seek) {
             Ok(w) => w,
             Err(e) => fail!("{e}"),
         }
     } else {
-        let track = match analyze(
-            source.as_ref(),
-            &audio_opts,
-            &mut progress_cli::CliProgress::new(),
-        ) {
-            Ok(t) => t,
-            Err(e) => fail!("{e}"),
-        };
-        let sounding: usize = track
-            .volumes
-            .iter()
-            .flat_map(|b| b.iter())
-            .filter(|v| **v > 0.0)
-            .count();
-        info!(
-            "Analyzed {} frame(s) at {} fps across {} band(s), {} bank(s)",
-            track.frame_count,
-            track.fps,
-            track.plan.len(),
-            track.frame_count.div_ceil(audio_opts.bank_size.max(1)).max(1),
-        );
-        // The same "what did the flag actually do" report as voice mode's.
-        // `--max-voices` is an upper bound that `--peak-gate` usually
-        // binds first, so the flag value says nothing about the render.
-        // Mean length of a run of one band being continuously non-zero:
-        // THE BEEPING METRIC. A run of one or two frames is a 33-66 ms
-        // blip, heard as a beep rather than as a note, and it is what
-        // `--attack`/`--release` exist to lengthen.
-        let mut runs = 0usize;
-        for band in &track.volumes {
-            let mut on = false;
-            for &v in band {
-                if v > 0.0 && !on {
-                    runs += 1;
-                }
-                on = v > 0.0;
+        let (track, seek) = if playlist {
+            match audio_playlist::join_tracks(bank_songs, auto_advance) {
+                Ok((track, playlist)) => (track, with_playlist(playlist)),
+                Err(e) => fail(e),
             }
-        }
-        info!(
-            "  {:.2} bands sounding per frame (--max-voices {}, --peak-gate {}); mean run \
-             {:.1} frames (--attack {} --release {})",
-            sounding as f64 / track.frame_count as f64,
-            audio_opts.max_voices,
-            audio_opts.peak_gate,
-            if runs > 0 {
-                sounding as f64 / runs as f64
-            } else {
-                0.0
-            },
-            audio_opts.attack_ms,
-            audio_opts.release_ms,
-        );
-        match build_speaker_world(&track, &audio_opts) {
+        } else {
+            (bank_songs.remove(0).1, seek.clone())
+        };
+        match build_speaker_world_with_seek(&track, &audio_opts, &seek) {
             Ok(w) => w,
             Err(e) => fail!("{e}"),
         }
@@ -691,11 +918,30 @@ fn run_audio(
 /// it. Lite CLI: one `--synth` tone for the whole file; per-track tones are a
 /// GUI feature.
 #[cfg(not(target_arch = "wasm32"))]
-fn run_midi(matches: &clap::ArgMatches, heightmap_files: &[PathBuf], out_file: &str) {
+fn run_midi(
+    matches: &clap::ArgMatches,
+    heightmap_files: &[PathBuf],
+    consent: DownloadConsent,
+    out_file: &str,
+) {
     let input = &heightmap_files[0];
     if heightmap_files.len() > 1 {
         warn!("--midi reads a single MIDI file; ignoring the extra inputs");
     }
+    // The same seek graph as an --audio-mode song's, in seconds of the piece,
+    // refused from the flags before the file is read. No subtitles here, so
+    // `--chapters subtitles` has no cues to read.
+    let seek = match load_seek_controls(matches, input, None, consent) {
+        Ok(s) => s,
+        Err(e) => fail(e),
+    };
+    if !seek.is_empty() && (matches.is_present("pingpong") || matches.is_present("looprange")) {
+        fail(
+            "--skip-seconds, --chapters and --seek-bar cannot take --ping-pong or --loop-range: \
+             a jump lands on a place in the piece, not on a place in the order it is being \
+             played in",
+        );
+    }
     let bytes = match std::fs::read(input) {
         Ok(b) => b,
         Err(e) => fail!("could not read {}: {e}", input.display()),
@@ -709,10 +955,11 @@ fn run_midi(matches: &clap::ArgMatches, heightmap_files: &[PathBuf], out_file: &
         return;
     }
 
-    let opts = match midi_options(matches) {
+    let mut opts = match midi_options(matches) {
         Ok(o) => o,
         Err(e) => fail!("{e}"),
     };
+    opts.seek = seek;
     let score = match analyze_midi(&bytes, &opts) {
         Ok(s) => s,
         Err(e) => fail!("{e}"),
@@ -721,7 +968,11 @@ fn run_midi(matches: &clap::ArgMatches, heightmap_files: &[PathBuf], out_file: &
         "midi2brick: {} speaker(s), {:.1}s{}",
         score.voices.len(),
         score.duration_s,
-        if opts.loop_playback { ", looping" } else { "" }
+        match opts.playback.with_loop(opts.loop_playback).plays {
+            None => ", looping".to_string(),
+            Some(1) => String::new(),
+            Some(n) => format!(", playing {n} times"),
+        }
     );
     let world = match build_midi_event_world(&score, &opts) {
         Ok(w) => w,
@@ -759,6 +1010,10 @@ fn midi_options(matches: &clap::ArgMatches) -> Result<MidiOptions, String> {
             d.polyphony_cap,
         )?,
         loop_playback: !matches.is_present("noloop"),
+        playback: playback_options(matches)?,
+        // Read by `run_midi`, which has the input and the download consent
+        // `--chapters container` needs.
+        seek: SeekControls::default(),
         control_buttons: !matches.is_present("nocontrolbuttons"),
         speakers_in_chip: matches.is_present("audiospeakersinchip"),
         // Lite CLI path: uniform tone, and no per-instrument volume (that is a
@@ -844,7 +1099,7 @@ fn run_anim(
         warn!("--anim-mode ignores --colormap");
     }
 
-    // Median-cut palette size, text mode only. Through `parse_arg` rather
+    // Median-cut palette size, for text mode and the palette encoding. Through `parse_arg` rather
     // than `.expect(...)`: a mistyped `--colors many` must be a CLI error
     // naming the flag, not a Rust panic trace (see `parse_arg`'s doc).
     let colors = match parse_arg(&matches, "animcolors", "--colors", "an integer", 0usize) {
@@ -856,14 +1111,54 @@ fn run_anim(
     // can change, so the flag is meaningless there -- but it is named out
     // loud, because a user who passed it and got a byte-for-byte
     // equivalent save would reasonably conclude quantization was broken.
-    if colors > 0 && mode != AnimMode::Text {
+    // The palette encoding is the one brick encoding with a palette of its
+    // own, and reads the flag as its size.
+    if colors > 0 && !matches!(mode, AnimMode::Text | AnimMode::Brick(AnimEncoding::Palette)) {
         warn!(
-            "--colors applies to --anim-mode text only (it lengthens colour RUNS in the \
-             encoded strings); brick mode's cost is fixed per pixel, so the flag is ignored \
-             here"
+            "--colors applies to --anim-mode text and --anim-encoding palette only (it \
+             lengthens colour RUNS in text mode's strings, and sizes the palette encoding's \
+             palette); the other brick encodings' cost is fixed per pixel, so the flag is \
+             ignored here"
         );
     }
 
+    // A playlist's buttons are seek buttons, so they share the seek refusals
+    // below, plus the two things only one clip can have: a chapter list and
+    // a subtitle track, both timed in one source's seconds. Ahead of the
+    // subtitles, so a playlist never reads a track it would refuse.
+    let playlist = matches.is_present("animplaylist");
+    if playlist {
+        for (flag, name) in [
+            ("--external-clock", "externalclock"),
+            ("--with-audio", "animwithaudio"),
+            ("--chapters", "animchapters"),
+            ("--subtitles", "subtitles"),
+            ("--subtitle-track", "subtitletrack"),
+            ("--ping-pong", "pingpong"),
+            ("--loop-range", "looprange"),
+        ] {
+            if matches.is_present(name) {
+                fail!("--playlist cannot take {flag}");
+            }
+        }
+        if mode == AnimMode::Brick(AnimEncoding::Delta) {
+            fail(
+                "--playlist cannot take --anim-encoding delta: its clip buttons jump the clock, \
+                 not each pixel's playhead; use hex, color-array or palette",
+            );
+        }
+        for (flag, name) in [("--no-loop", "noloop"), ("--repeat", "repeat")] {
+            if matches.is_present("animnoautoadvance") && matches.is_present(name) {
+                fail!(
+                    "--no-auto-advance cannot take {flag}: each clip loops on itself, so the \
+                     playlist never reaches an end to stop at"
+                );
+            }
+        }
+    } else if matches.is_present("animnoautoadvance") {
+        warn!("--no-auto-advance is ignored without --playlist");
+    }
+
     // Resolved HERE, before a single frame is decoded, so a typo'd
     // subtitle path or a bitmap track fails in a second rather than after
     // a long render. `heightmap_files[0]` is the pre-expansion input --
@@ -898,22 +1193,114 @@ fn run_anim(
         Ok(v) => v,
         Err(e) => fail(e),
     };
+    // An unknown spelling is `anim_options`' error to report; here it only
+    // decides which warnings apply.
+    let orientation = matches
+        .value_of("animorientation")
+        .and_then(ScreenOrientation::parse)
+        .unwrap_or_default();
+    // Only hex mode splits; the others would build one chip regardless, so
+    // taking the flag silently would report a load the render doesn't have.
+    if matches.is_present("animpanels") && mode != AnimMode::Brick(AnimEncoding::Hex) {
+        fail("--panels is only supported by --anim-mode brick with --anim-encoding hex");
+    }
+    // Parsed here so a typo fails before any decode. The two refusals are the
+    // audio branch's own: the speakers' clock is the one driving the screen,
+    // and nothing on the audio decode path can seek to match `--start`.
+    let with_audio = match matches.value_of("animwithaudio").map(AudioMode::parse) {
+        Some(Ok(m)) => Some(m),
+        Some(Err(e)) => fail!("--with-audio: {e}"),
+        None => None,
+    };
+    if with_audio.is_some() {
+        if matches.is_present("externalclock") {
+            fail(
+                "--with-audio cannot take --external-clock: the speakers' clock is what drives \
+                 the screen",
+            );
+        }
+        if matches.is_present("animstart") {
+            fail(
+                "--with-audio cannot take --start: the audio decode path cannot seek, so the \
+                 sound would start at 0 under a picture that does not",
+            );
+        }
+        if matches.is_present("audiofps") {
+            warn!("--with-audio ignores --audio-fps; the track is analysed at --fps");
+        }
+    }
+    // A budget sizes one picture. A playlist is several, fitted to the first
+    // one's size, and a soundtrack's speakers are costed by a different model
+    // that this search does not weigh against the screen's.
+    let budget = match budget_options(matches) {
+        Ok(b) => b,
+        Err(e) => fail(e),
+    };
+    if budget.is_some() {
+        if playlist {
+            fail("--max-gates, --max-save-size and --max-ram cannot take --playlist yet");
+        }
+        if with_audio.is_some() {
+            fail("--max-gates, --max-save-size and --max-ram cannot take --with-audio yet");
+        }
+    }
+    // Resolved after the subtitles, which `--chapters subtitles` reads. Every
+    // seek button moves the clock's own frame offset, so a render without
+    // that clock -- or one whose picture follows the speakers' -- has nothing
+    // for them to move.
+    let seek = match load_seek_controls(
+        matches,
+        &heightmap_files[0],
+        subtitles.as_deref(),
+        consent,
+    ) {
+        Ok(s) => s,
+        Err(e) => fail(e),
+    };
+    if !seek.is_empty() {
+        if matches.is_present("externalclock") {
+            fail(
+                "--skip-seconds, --chapters and --seek-bar cannot take --external-clock: \
+                 they move the chip's own clock, and --external-clock builds none",
+            );
+        }
+        if with_audio.is_some() {
+            fail(
+                "--skip-seconds, --chapters and --seek-bar cannot take --with-audio yet: \
+                 they would move the picture away from the speakers' sound",
+            );
+        }
+        if matches.is_present("pingpong") || matches.is_present("looprange") {
+            fail(
+                "--skip-seconds, --chapters and --seek-bar cannot take --ping-pong or \
+                 --loop-range: a jump lands on a frame of the clip, not on a place in the \
+                 order it is being played in",
+            );
+        }
+    }
+    if mode == AnimMode::Text && matches.is_present("animorientation") {
+        warn!(
+            "--anim-mode text ignores --screen-orientation: its screen is always an \
+             upright wall facing +X"
+        );
+    }
     // **Said out loud rather than left to be discovered in game.** A
     // `TextDisplay` draws in the plane of the anchor face it is given, and
-    // a brick-mode screen lies FLAT -- so the subtitle is placed on the
+    // a brick-mode floor screen lies FLAT -- so the subtitle is placed on the
     // screen's upward face (`bricks::subtitle_extent`) rather than the
-    // upright one text mode uses. That much is read off the save schema's
-    // own `EBrickDirection`; which way the line RUNS within that plane is
-    // a property of the component nothing here can know, and has not been
-    // checked by eye.
-    if subtitles.is_some() && mode != AnimMode::Text {
+    // upright one text mode and a brick-mode wall use. That much is read off
+    // the save schema's own `EBrickDirection`; which way the line RUNS within
+    // that plane is a property of the component nothing here can know, and
+    // has not been checked by eye.
+    if subtitles.is_some() && mode != AnimMode::Text && orientation == ScreenOrientation::Floor {
         warn!(
             "subtitles over --anim-mode brick lie FLAT in the screen's own plane (the \
              screen is on the ground, so they are read from above, not from the front), \
              just above its surface and centred on the image's last row. Which \
              horizontal axis the line runs along -- and so whether that row reads as \
-             the BOTTOM of the picture -- is unverified in game; --anim-mode text, \
-             whose screen is an upright wall, is the mode this was designed against"
+             the BOTTOM of the picture -- is unverified in game; an upright wall \
+             (--screen-orientation wall+x and friends, or --anim-mode text) is what \
+             this was designed against"
         );
     }
 
@@ -926,36 +1313,29 @@ fn run_anim(
         Ok(o) => o,
         Err(e) => fail(e),
     };
-    // Named out loud for the same reason as `--colors` above: the band
-    // layout is a closed-form bound on the COLOUR encoder's row width (16
-    // characters of colour tag plus `char_repeat` glyph characters per
-    // pixel), and a monochrome glyph mode packs several pixels into one
-    // character, so it does not obey that bound or that row geometry.
-    // REFUSED, not warned about. This used to warn and then build the save
-    // anyway, which shipped a render that is WRONG rather than merely
-    // unsupported: `text_options` above has already replaced `line_height`,
-    // `kerning`, `line_offset`, `pitch_x` and `pitch_y` with
-    // `mono_geometry(Braille, ..)`, so the world is laid out on braille
-    // geometry -- while `text::encode_bands`/`encode_row`, the anim text
-    // encoder, never consults `opts.mode` at all and emits colour tags plus
-    // `char_repeat` glyphs regardless. The cost readout describes the colour
-    // render too. A save the tool itself knows to be wrong must not be
-    // written.
-    if mode == AnimMode::Text && (matches.is_present("braille") || matches.is_present("blocks"))
-    {
-        fail!(
-            "--braille/--blocks are not supported under --anim-mode text: the band layout \
-             assumes the colour encoder's row geometry and the animated text encoder emits \
-             colour runs whatever the mode says, so the save would be laid out for one \
-             encoder and filled by the other. Use --text for a still monochrome export"
+    // A glyph mode (`--braille` and friends) carries through the whole
+    // pipeline -- band layout, encoder and cost readout all read
+    // `text_opts.mode` -- but it draws dots in one colour, so a palette has
+    // nothing to reduce. Named out loud for the same reason as above.
+    if mode == AnimMode::Text && text_opts.mode != PixelMode::Color && colors > 0 {
+        warn!(
+            "--colors does not apply to --{}: the glyphs are one colour, so the palette pass \
+             is skipped",
+            text_opts.mode.name().to_lowercase()
         );
     }
-    // Colour-array mode always converts sRGB -> linear itself, unlike hex
-    // mode where `MakeColorHex` makes `--srgb-to-linear` a real question.
-    if mode == AnimMode::Brick(AnimEncoding::ColorArray) && matches.is_present("srgb2lin") {
+    // Colour-array, delta and palette modes always convert sRGB -> linear
+    // themselves, unlike hex mode where `MakeColorHex` makes
+    // `--srgb-to-linear` a real question.
+    if let AnimMode::Brick(
+        enc @ (AnimEncoding::ColorArray | AnimEncoding::Delta | AnimEncoding::Palette),
+    ) = mode
+        && matches.is_present("srgb2lin")
+    {
         warn!(
-            "--srgb-to-linear is implied by --anim-encoding color-array (that encoding \
-             stores linear colors, so it always converts) -- the flag changes nothing here"
+            "--srgb-to-linear is implied by --anim-encoding {} (that encoding \
+             stores linear colors, so it always converts) -- the flag changes nothing here",
+            enc.name()
         );
     }
     // Third and last inert case: text mode writes colour tags straight
@@ -1025,6 +1405,15 @@ fn run_anim(
             fail!("unknown filter '{other}' (lanczos, nearest)");
         }
     };
+    let keying = match keying(matches) {
+        Ok(k) => k,
+        Err(e) => fail(e),
+    };
+    let adjust = match adjustments(matches) {
+        Ok(a) => a,
+        Err(e) => fail(e),
+    };
+    let auto_crop = matches.is_present("autocrop");
 
     // A frame sequence outruns the OS argument limit long before it
     // outruns anything else this renderer cares about. At ~11 characters
@@ -1076,6 +1465,118 @@ fn run_anim(
         heightmap_files
     };
 
+    // A playlist: every input its own clip, back to back on one timeline (see
+    // `anim::playlist`). Each is opened and adapted as the single-video
+    // branch below opens and adapts one, and fitted to the first clip's size
+    // so they all fill one screen.
+    if playlist {
+        if heightmap_files.len() < 2 {
+            fail("--playlist needs at least two input videos");
+        }
+        if let Some(input) = heightmap_files.iter().find(|f| !is_video_path(f)) {
+            fail!("--playlist takes video files only, and {} is not one", input.display());
+        }
+        let mut raws = Vec::with_capacity(heightmap_files.len());
+        for input in &heightmap_files {
+            info!("Opening video {}", input.display());
+            match backend::open_video_ensuring(
+                input,
+                backend_choice,
+                None,
+                fit,
+                filter,
+                None,
+                &mut || ensure_ffmpeg(consent),
+            ) {
+                Ok(s) => raws.push(s),
+                Err(e) => fail!("{e}"),
+            }
+        }
+        // Bars are found clip by clip: each is fitted to `size` after its own
+        // crop, so one clip's letterbox can't leave bars on the next.
+        let mut adjusts = Vec::with_capacity(raws.len());
+        for raw in &raws {
+            match adjust_source(adjust, auto_crop, raw.as_ref(), start) {
+                Ok(a) => adjusts.push(a),
+                Err(e) => fail!("{e}"),
+            }
+        }
+        let native = raws[0].info();
+        let (native_w, native_h) = adjusts[0].size(native.width, native.height);
+        let size = match target_size(matches, native_w, native_h) {
+            Ok(s) => s.unwrap_or((native_w, native_h)),
+            Err(e) => fail(e),
+        };
+        let adapted: Vec<AdaptedSource> = raws
+            .iter()
+            .zip(&adjusts)
+            .map(|(raw, &adjust)| AdaptedSource {
+                inner: raw.as_ref(),
+                size: Some(size),
+                fit,
+                filter,
+                target_fps: fps,
+                start_s: start,
+                duration_s: duration,
+                max_frames,
+                key: keying.as_ref(),
+                adjust,
+            })
+            .collect();
+        // Named after the file, for its button.
+        let clips = heightmap_files
+            .iter()
+            .zip(&adapted)
+            .map(|(input, clip)| {
+                let name = input.file_stem().map(|s| s.to_string_lossy().into_owned());
+                (name.unwrap_or_default(), clip as &dyn FrameSource)
+            })
+            .collect();
+        let source = match PlaylistSource::new(clips, !matches.is_present("animnoautoadvance")) {
+            Ok(s) => s,
+            Err(e) => fail(e),
+        };
+        let info = source.info();
+        let frames = source.playlist().frame_count();
+        if frames > max_frames {
+            fail!("{}", max_frames_error(max_frames));
+        }
+        info!("Playlist of {} clip(s), {frames} frame(s) in all", source.playlist().clips.len());
+
+        let anim_opts = match anim_options(
+            matches,
+            colors,
+            &text_opts,
+            None,
+            subtitle_scale,
+            subtitle_lift,
+            start,
+        ) {
+            Ok(o) => AnimOptions {
+                seek: SeekControls { playlist: Some(source.playlist().clone()), ..seek },
+                ..o
+            },
+            Err(e) => fail(e),
+        };
+        let cost = match mode.estimate(info.width, info.height, frames, &anim_opts) {
+            Ok(c) => c,
+            Err(e) => fail!("{e}"),
+        };
+        log_cost(mode, &cost, info.width, info.height, &anim_opts);
+        if write_preview(matches, mode, &source, &anim_opts) {
+            return info!("Done!");
+        }
+        let world = match mode.build(&source, &anim_opts, &mut progress_cli::CliProgress::new()) {
+            Ok(w) => w,
+            Err(e) => fail!("{e}"),
+        };
+        info!("Writing Save to {}", out_file);
+        if let Err(e) = write_world(&world, out_file) {
+            fail!("{e}");
+        }
+        return info!("Done!");
+    }
+
     // A video file goes to a decode backend, which pushes fps and
     // scaling into its own filters rather than materialising a Clip --
     // the whole point of this path. The directory and animated-image
@@ -1120,7 +1621,12 @@ fn run_anim(
         // fps stays `None` deliberately. Pushing THAT into ffmpeg changes
         // which frames are selected, not merely how they are scaled, and
         // needs a cross-backend selection test first.
+        //
+        // Nor when the frame is cropped or turned first: a crop rectangle is
+        // in source pixels, and `AdaptedSource` reshapes before it resizes.
+        let reshaped = adjust.reshapes() || auto_crop;
         let preset_size = match (matches.value_of("animwidth"), matches.value_of("animheight")) {
+            _ if reshaped => None,
             (Some(w), Some(h)) => match (w.parse::<u32>(), h.parse::<u32>()) {
                 (Ok(w), Ok(h)) => Some((w, h)),
                 // Leave the error itself to the parse below, which already
@@ -1155,23 +1661,17 @@ fn run_anim(
         // for size/fps so the raw source stays native and untouched on
         // EITHER backend -- there is exactly one place, this
         // `AdaptedSource`, that ever resizes or resamples.
+        let adjust = match adjust_source(adjust, auto_crop, raw.as_ref(), start) {
+            Ok(a) => a,
+            Err(e) => fail!("{e}"),
+        };
         let native = raw.info();
-        let size = match target_size(&matches, native.width, native.height) {
+        let (native_w, native_h) = adjust.size(native.width, native.height);
+        let size = match target_size(&matches, native_w, native_h) {
             Ok(s) => s,
             Err(e) => fail(e),
         };
 
-        let adapted = AdaptedSource {
-            inner: raw.as_ref(),
-            size,
-            fit,
-            filter,
-            target_fps: fps,
-            start_s: start,
-            duration_s: duration,
-            max_frames,
-        };
-
         // Same builder the image/sequence path below uses; see `anim_options`.
         let anim_opts = match anim_options(
             &matches,
@@ -1182,10 +1682,55 @@ fn run_anim(
             subtitle_lift,
             start,
         ) {
-            Ok(o) => o,
+            Ok(o) => AnimOptions { seek: seek.clone(), ..o },
             Err(e) => fail(e),
         };
 
+        // Fitted before the source is adapted, so the screen and rate the
+        // search settles on are the ones decoded. Each rate's frame count is
+        // the one the adapted source below would report at it.
+        let (mode, fps, size, anim_opts) = match &budget {
+            Some(budget) => {
+                let frames_at = |rate: f32| {
+                    let probe = AdaptedSource {
+                        inner: raw.as_ref(),
+                        size,
+                        fit,
+                        filter,
+                        target_fps: rate,
+                        start_s: start,
+                        duration_s: duration,
+                        max_frames,
+                        key: None,
+                        adjust,
+                    };
+                    probe.info().frame_count_hint.or(probe.frame_count_estimate()).unwrap_or(0)
+                };
+                let top = size.unwrap_or((native_w, native_h));
+                let fitted =
+                    match fit_anim(matches, budget, mode, top, fps, &anim_opts, &frames_at) {
+                        Ok(f) => f,
+                        Err(e) => fail(e),
+                    };
+                let opts = AnimOptions { colors: fitted.colors, ..anim_opts };
+                (fitted.mode, fitted.fps, Some((fitted.width, fitted.height)), opts)
+            }
+            None => (mode, fps, size, anim_opts),
+        };
+
+        let adapted = AdaptedSource {
+            inner: raw.as_ref(),
+            size,
+            fit,
+            filter,
+            target_fps: fps,
+            start_s: start,
+            duration_s: duration,
+            max_frames,
+            key: keying.as_ref(),
+            adjust,
+        };
+
         // `AdaptedSource::info` folds the resample/window math into the
         // hint whenever the raw source can say its own frame count up
         // front (see its doc) -- a video probed from a real container
@@ -1234,15 +1779,48 @@ fn run_anim(
             Ok(c) => c,
             Err(e) => fail!("{e}"),
         };
-        log_cost(mode, &cost, info.width, info.height, text_opts.char_repeat);
+        log_cost(mode, &cost, info.width, info.height, &anim_opts);
 
-        let world = match mode.build(
-            &adapted,
-            &anim_opts,
-            &mut progress_cli::CliProgress::new(),
-        ) {
-            Ok(w) => w,
-            Err(e) => fail!("{e}"),
+        if with_audio.is_some() && matches.is_present("animpreview") {
+            warn!("--preview shows the picture only; the --with-audio track is not in it");
+        }
+        if write_preview(matches, mode, &adapted, &anim_opts) {
+            return info!("Done!");
+        }
+        let built = match with_audio {
+            Some(audio_mode) => {
+                // Bounded by the picture's own count when it is exact: the
+                // track is cut to it anyway, so analysing past it is waste.
+                let frames = info.frame_count_hint.unwrap_or(max_frames);
+                let soundtrack = match analyze_soundtrack(
+                    matches,
+                    &heightmap_files[0],
+                    audio_mode,
+                    info.fps,
+                    frames,
+                    consent,
+                ) {
+                    Ok(s) => s,
+                    Err(e) => fail(e),
+                };
+                let audio_opts = match audio_options(matches) {
+                    Ok(o) => o,
+                    Err(e) => fail(e),
+                };
+                build_with_soundtrack(
+                    &mode,
+                    &adapted,
+                    &anim_opts,
+                    soundtrack,
+                    &audio_opts,
+                    &mut progress_cli::CliProgress::new(),
+                )
+            }
+            None => mode.build(&adapted, &anim_opts, &mut progress_cli::CliProgress::new()),
+        };
+        let world = match built {
+            Ok(w) => w,
+            Err(e) => fail!("{e}"),
         };
 
         info!("Writing Save to {}", out_file);
@@ -1252,6 +1830,10 @@ fn run_anim(
         return info!("Done!");
     }
 
+    if with_audio.is_some() {
+        fail("--with-audio needs a video file: an image or frame sequence has no audio track");
+    }
+
     info!("Reading image file(s)");
     let source = if heightmap_files.len() == 1 {
         let input = &heightmap_files[0];
@@ -1284,27 +1866,17 @@ fn run_anim(
         Err(e) => fail!("{e}"),
     };
 
+    let adjust = match adjust_source(adjust, auto_crop, &clip, start) {
+        Ok(a) => a,
+        Err(e) => fail!("{e}"),
+    };
+    let (clip_w, clip_h) = adjust.size(clip.width, clip.height);
     // The SAME parser the video branch above uses -- see `target_size`.
-    let size = match target_size(&matches, clip.width, clip.height) {
+    let size = match target_size(&matches, clip_w, clip_h) {
         Ok(s) => s,
         Err(e) => fail(e),
     };
 
-    // Resize (if requested) then resample, streamed rather than
-    // materialized twice: `AdaptedSource` layers `ResizeStream` under
-    // `FpsStream` so frames are scaled before selection, never the other
-    // way around.
-    let adapted = AdaptedSource {
-        inner: &clip,
-        size,
-        fit,
-        filter,
-        target_fps: fps,
-        start_s: start,
-        duration_s: duration,
-        max_frames,
-    };
-
     // Built before the estimate so the readout costs the options the
     // render will actually consume -- `bank_size` in particular decides
     // how many arrays the frames spill across. Same builder the video
@@ -1318,10 +1890,46 @@ fn run_anim(
         subtitle_lift,
         start,
     ) {
-        Ok(o) => o,
+        Ok(o) => AnimOptions { seek, ..o },
         Err(e) => fail(e),
     };
 
+    // As in the video branch, ahead of the adapter; the frame counts are
+    // `estimated_frame_count`'s, as below.
+    let (mode, fps, size, anim_opts) = match &budget {
+        Some(budget) => {
+            let frames_at = |rate: f32| {
+                let frames = clip.frames.len();
+                estimated_frame_count(frames, clip.fps, rate, start, duration, max_frames)
+            };
+            let top = size.unwrap_or((clip_w, clip_h));
+            let fitted = match fit_anim(matches, budget, mode, top, fps, &anim_opts, &frames_at) {
+                Ok(f) => f,
+                Err(e) => fail(e),
+            };
+            let opts = AnimOptions { colors: fitted.colors, ..anim_opts };
+            (fitted.mode, fitted.fps, Some((fitted.width, fitted.height)), opts)
+        }
+        None => (mode, fps, size, anim_opts),
+    };
+
+    // Resize (if requested) then resample, streamed rather than
+    // materialized twice: `AdaptedSource` layers `ResizeStream` under
+    // `FpsStream` so frames are scaled before selection, never the other
+    // way around.
+    let adapted = AdaptedSource {
+        inner: &clip,
+        size,
+        fit,
+        filter,
+        target_fps: fps,
+        start_s: start,
+        duration_s: duration,
+        max_frames,
+        key: keying.as_ref(),
+        adjust,
+    };
+
     // The resampled frame count isn't knowable from the *stream* (see
     // `AdaptedSource::info`, whose hint is `None` once the rate changes),
     // but it is computable from the source's length plus the scalars --
@@ -1353,8 +1961,11 @@ fn run_anim(
         Ok(c) => c,
         Err(e) => fail!("{e}"),
     };
-    log_cost(mode, &cost, info.width, info.height, text_opts.char_repeat);
+    log_cost(mode, &cost, info.width, info.height, &anim_opts);
 
+    if write_preview(matches, mode, &adapted, &anim_opts) {
+        return info!("Done!");
+    }
     let world =
         match mode.build(&adapted, &anim_opts, &mut progress_cli::CliProgress::new()) {
             Ok(w) => w,
@@ -1390,16 +2001,105 @@ fn run_text(matches: &clap::ArgMatches, heightmap_files: &[PathBuf], out_file: &
         Err(e) => fail!("{e}"),
     };
 
+    // Palette reduction, colour mode only: every colour change is a tag,
+    // so a photo snapped to a few colours runs long and packs into fewer
+    // TextDisplays. Settled before the picture is read, as for `--img`.
+    let colors = match parse_arg(matches, "animcolors", "--colors", "an integer", 0usize) {
+        Ok(v) => v,
+        Err(e) => fail(e),
+    };
+    let reduce = colors > 0 || matches.is_present("palette");
+    if text_opts.mode != PixelMode::Color {
+        if reduce {
+            warn!(
+                "--colors/--palette apply to colour text renders; the {} glyphs have no colour \
+                 to reduce, so this render ignores them",
+                text_opts.mode.name()
+            );
+        }
+    } else if colors > 0 && matches.is_present("palette") {
+        fail!(
+            "--colors and --palette are two ways to pick the palette and cannot be combined: \
+             --colors builds N colours from the picture, --palette reads a fixed set. Pass one"
+        );
+    } else if !reduce && matches.is_present("dither") {
+        warn!(
+            "--dither ignored: a colour text render dithers only to a --colors or --palette \
+             palette (the monochrome glyph modes dither their dots)"
+        );
+    }
+    let reduce = reduce && text_opts.mode == PixelMode::Color;
+    let fixed_palette = match matches.value_of("palette") {
+        Some(file) if reduce => match read_palette_file(file.as_ref()) {
+            Ok(palette) => Some(palette),
+            Err(e) => fail!("{e}"),
+        },
+        _ => None,
+    };
+
     let input = &heightmap_files[0];
     info!("Reading image file {}", input.display());
     let img = match image::open(input) {
         Ok(i) => i.to_rgba8(),
         Err(e) => fail!("Error reading image: {e:?}"),
     };
+    // The reduced picture is what gets encoded; the original is kept only
+    // to count what the exact colours would have cost.
+    let (img, unreduced) = if reduce {
+        let (mut palette, source) = match fixed_palette {
+            Some(palette) => (palette, "the --palette file"),
+            None => (
+                Palette::build(std::slice::from_ref(&img), colors, text_opts.alpha_threshold),
+                "median cut",
+            ),
+        };
+        info!(
+            "Reducing the picture to {} colours ({source}, {} dithering)",
+            palette.len(),
+            text_opts.dither.name()
+        );
+        (quantize(&img, &mut palette, text_opts.dither), Some(img))
+    } else {
+        (img, None)
+    };
     let tiles = match encode_tiles(&img, &text_opts) {
         Ok(t) => t,
         Err(e) => fail!("{e}"),
     };
+    if let Some(original) = &unreduced {
+        let (chars, components) = tiles_cost(&tiles);
+        match encode_tiles(original, &text_opts) {
+            // Dithering breaks runs up, so the reduction can cost more than
+            // the exact colours did; the log says so.
+            Ok(full) => {
+                let (full_chars, full_components) = tiles_cost(&full);
+                let report = format!(
+                    "Palette reduction: {} characters in {} TextDisplay(s) instead of {} in {}",
+                    commas(chars as u64),
+                    commas(components as u64),
+                    commas(full_chars as u64),
+                    commas(full_components as u64)
+                );
+                if chars <= full_chars {
+                    info!(
+                        "{report} ({}% fewer characters)",
+                        (100. - chars as f64 / full_chars.max(1) as f64 * 100.).floor()
+                    );
+                } else {
+                    warn!(
+                        "{report} -- MORE than the exact colours cost. Dithering breaks up runs; \
+                         try --dither none"
+                    );
+                }
+            }
+            Err(e) => info!(
+                "Palette reduction: {} characters in {} TextDisplay(s); the exact colours \
+                 would not have encoded at all ({e})",
+                commas(chars as u64),
+                commas(components as u64)
+            ),
+        }
+    }
     info!(
         "Encoded {} tile(s), {} text band(s)",
         tiles.len(),
@@ -1421,6 +2121,97 @@ fn run_text(matches: &clap::ArgMatches, heightmap_files: &[PathBuf], out_file: &
     return info!("Done!");
 }
 
+/// The `--sign`/`--sign-text` branch: lays the text out as one `TextDisplay`
+/// with [`build_sign`], on the same `TextOptions` as `--text` but sized per
+/// line of text rather than per pixel.
+#[cfg(not(target_arch = "wasm32"))]
+fn run_sign(matches: &clap::ArgMatches, heightmap_files: &[PathBuf], out_file: &str) {
+    let text = match matches.value_of("signtext") {
+        Some(t) => {
+            if !heightmap_files.is_empty() {
+                warn!(
+                    "--sign-text is the sign's text; ignoring {} input file(s)",
+                    heightmap_files.len()
+                );
+            }
+            t.to_string()
+        }
+        None => {
+            let mut text = String::new();
+            for file in heightmap_files {
+                info!("Reading text file {}", file.display());
+                match std::fs::read_to_string(file) {
+                    Ok(t) => text.push_str(&t),
+                    Err(e) => fail!("Error reading {}: {e}", file.display()),
+                }
+                if !text.ends_with('\n') {
+                    text.push('\n');
+                }
+            }
+            text
+        }
+    };
+    for flag in ["braille", "blocks", "sextants", "octants", "dither", "colormap"] {
+        if matches.is_present(flag) {
+            warn!("--{flag} does not apply to --sign");
+        }
+    }
+    let opts = match sign_options(matches) {
+        Ok(o) => o,
+        Err(e) => fail!("{e}"),
+    };
+
+    let mut world = World::new();
+    let (cols, lines) = match build_sign(&mut world, &text, &opts) {
+        Ok(size) => size,
+        Err(e) => fail!("{e}"),
+    };
+    info!("Laid out {lines} line(s), up to {cols} character(s) wide");
+    world.meta.bundle.description = "Sign generated from text".to_string();
+
+    info!("Writing Save to {}", out_file);
+    if let Err(e) = write_world(&world, out_file) {
+        fail!("{e}");
+    }
+    info!("Done!");
+}
+
+/// `--sign`'s options: [`text_options_sized`] at the sign's default line
+/// size, plus `--sign-color`, `--align`, `--wrap`, `--plate` and `--outline`.
+#[cfg(not(target_arch = "wasm32"))]
+fn sign_options(matches: &clap::ArgMatches) -> Result<SignOptions, String> {
+    let color_arg = |name: &str, flag: &str| -> Result<Option<[u8; 3]>, String> {
+        matches
+            .value_of(name)
+            .map(|s| {
+                parse_hex_color(s)
+                    .ok_or_else(|| format!("{flag} takes an RRGGBB hex colour, got '{s}'"))
+            })
+            .transpose()
+    };
+    let align = match matches.value_of("align").map(|s| s.to_lowercase()).as_deref() {
+        None | Some("left") => SignAlign::Left,
+        Some("centre") | Some("center") => SignAlign::Centre,
+        Some("right") => SignAlign::Right,
+        Some(other) => return Err(format!("unknown --align '{other}' (left, centre, right)")),
+    };
+    let wrap = match matches.value_of("wrap") {
+        Some(_) => Some(parse_arg(matches, "wrap", "--wrap", "an integer", 0usize)?),
+        None => None,
+    };
+    if wrap == Some(0) {
+        return Err("--wrap must be at least 1".to_string());
+    }
+    Ok(SignOptions {
+        text: text_options_sized(matches, DEFAULT_SIGN_LINE)?,
+        color: color_arg("signcolor", "--sign-color")?.unwrap_or([255; 3]),
+        align,
+        wrap,
+        plate: color_arg("plate", "--plate")?,
+        outline: matches.is_present("outline"),
+    })
+}
+
 /// The default (no `--audio-mode`/`--anim-mode`/`--text`) render branch:
 /// parses `--size`/`--vertical` into `GenOptions`, reads the colormap and
 /// heightmap image(s), generates bricks, and writes the result. Split out of
@@ -1510,6 +2301,181 @@ fn run_heightmap(
     }
     let blocks = surface == SurfaceMode::Blocks;
 
+    // A layered column has a gap in it, which only the flat-topped renderers
+    // can leave. Checked before any file is read, like the surface conflict
+    // above.
+    let layered = matches.is_present("layers");
+    if layered {
+        if !blocks {
+            let mode = match surface {
+                SurfaceMode::Terrain => "--terrain",
+                SurfaceMode::Rampify => "--rampify",
+                _ => "--wedge",
+            };
+            fail!(
+                "--layers cannot be combined with {mode}: the sloped renderers shape the top of \
+                 ONE surface per column, and a layered column has gaps between its layers. \
+                 Layers build with the flat-topped blocks (optionally --greedy)"
+            );
+        }
+        if matches.is_present("img") {
+            fail!("--layers cannot be combined with --img: a flat image has no layers to fill");
+        }
+        if !heightmap_files.len().is_multiple_of(2) {
+            fail!(
+                "--layers reads the inputs as bottom/top PAIRS (floor then ceiling, for each \
+                 layer), but {} input file(s) were given",
+                heightmap_files.len()
+            );
+        }
+    }
+
+    // A point cloud is gridded into the heightmap here rather than read as
+    // an image. Mixing the two in one stack has no meaning -- the cloud's grid
+    // is sized by its cells, not by any image -- so it is refused up front.
+    let clouds = heightmap_files.iter().filter(|f| is_point_cloud(f)).count();
+    let cloud_options = if clouds > 0 {
+        if clouds < heightmap_files.len() {
+            fail!(
+                "the inputs mix point clouds (.xyz/.csv/.las) with heightmap images; a cloud is \
+                 gridded at --cell-size and cannot be stacked with an image. Pass one kind"
+            );
+        }
+        if layered {
+            fail!(
+                "--layers reads bottom/top heightmap IMAGES; a point cloud has one surface per \
+                 cell, not a floor and a ceiling"
+            );
+        }
+        if matches.is_present("hdmap") {
+            warn!("a point cloud ignores --hdmap: its heights are not encoded in an image");
+        }
+        let cell_size = match parse_arg(matches, "cellsize", "--cell-size", "a number", 1.0f64) {
+            Ok(v) if v > 0.0 && v.is_finite() => v,
+            Ok(v) => fail!("--cell-size must be a positive number (got {v})"),
+            Err(e) => fail(e),
+        };
+        let reduce = match matches.value_of("cellz") {
+            None | Some("max") => CellReduce::Max,
+            Some("mean") => CellReduce::Mean,
+            Some("min") => CellReduce::Min,
+            Some(other) => fail!("unknown --cell-z '{other}' (max, mean, min)"),
+        };
+        let fill_radius = match parse_arg(matches, "gapfill", "--gap-fill", "an integer", 8u32) {
+            Ok(v) => v,
+            Err(e) => fail(e),
+        };
+        Some(PointCloudOptions {
+            cell_size,
+            reduce,
+            fill_radius,
+        })
+    } else {
+        for (flag, name) in [
+            ("--cell-size", "cellsize"),
+            ("--cell-z", "cellz"),
+            ("--gap-fill", "gapfill"),
+        ] {
+            if matches.is_present(name) {
+                warn!("{flag} applies to point cloud inputs (.xyz/.csv/.las) only; ignoring it");
+            }
+        }
+        None
+    };
+
+    // `--relief` carves the picture itself, so it has no use for a second
+    // heightmap source. Every conflict is refused before anything is read.
+    let relief = matches.is_present("relief");
+    let relief_depth = if relief {
+        if matches.is_present("img") {
+            fail!(
+                "--relief cannot be combined with --img: --img lays the picture flat, --relief \
+                 carves it by brightness. Pass one"
+            );
+        }
+        if layered {
+            fail!("--relief cannot be combined with --layers: a relief is one surface");
+        }
+        if cloud_options.is_some() {
+            fail!("--relief carves a PICTURE; a point cloud already has its own heights");
+        }
+        if heightmap_files.len() > 1 {
+            warn!(
+                "--relief carves only the first input picture; ignoring {} extra input(s)",
+                heightmap_files.len() - 1
+            );
+        }
+        match parse_arg(matches, "reliefdepth", "--relief-depth", "an integer", 16u32) {
+            Ok(0) => fail!("--relief-depth must be at least 1 (it is a count of height levels)"),
+            Ok(v) if v > HeightmapRelief::MAX_DEPTH => fail!(
+                "--relief-depth must be at most {} (a picture has only 256 shades to spread \
+                 over it), got {v}",
+                HeightmapRelief::MAX_DEPTH
+            ),
+            Ok(v) => v,
+            Err(e) => fail(e),
+        }
+    } else {
+        for (flag, name) in [
+            ("--relief-depth", "reliefdepth"),
+            ("--depth-map", "depthmap"),
+            ("--invert", "invert"),
+        ] {
+            if matches.is_present(name) {
+                warn!("{flag} applies to --relief renders only; ignoring it");
+            }
+        }
+        0
+    };
+
+    // Palette reduction for `--img`. Settled here, before the picture is
+    // read, so a bad flag or an unreadable palette file fails fast.
+    let colors = match parse_arg(matches, "animcolors", "--colors", "an integer", 0usize) {
+        Ok(v) => v,
+        Err(e) => fail(e),
+    };
+    let dither = match dither_arg(matches) {
+        Ok(d) => d,
+        Err(e) => fail(e),
+    };
+    let reduce = colors > 0 || matches.is_present("palette");
+    let flat_image = matches.is_present("img") && blocks;
+    if !flat_image {
+        for (flag, present) in [
+            ("--colors", colors > 0),
+            ("--palette", matches.is_present("palette")),
+            ("--dither", matches.is_present("dither")),
+        ] {
+            if present {
+                warn!(
+                    "{flag} applies to --img renders only (it lengthens the same-colour runs \
+                     of a flat picture); this render ignores it"
+                );
+            }
+        }
+    } else if colors > 0 && matches.is_present("palette") {
+        fail!(
+            "--colors and --palette are two ways to pick the palette and cannot be combined: \
+             --colors builds N colours from the picture, --palette reads a fixed set. Pass one"
+        );
+    } else if !reduce && matches.is_present("dither") {
+        warn!("--dither ignored: there is no --colors or --palette to dither to");
+    }
+    let fixed_palette = match matches.value_of("palette") {
+        Some(file) if flat_image => match read_palette_file(file.as_ref()) {
+            Ok(palette) => Some(palette),
+            Err(e) => fail!("{e}"),
+        },
+        _ => None,
+    };
+
+    let plane = match matches.value_of("wall").map(str::to_lowercase).as_deref() {
+        None => BuildPlane::Ground,
+        Some("x") => BuildPlane::WallX,
+        Some("y") => BuildPlane::WallY,
+        Some(other) => fail!("unknown --wall '{other}' (x, y)"),
+    };
+
     // `--size` counts STUDS, which are 5 units of half extent each. With
     // `--micro` it counts micro units. Use `checked_mul`: the half extent is a
     // u16, thus `--size 20000` overflowed and rendered a map at an incorrect
@@ -1544,7 +2510,7 @@ fn run_heightmap(
         micro: micro_size,
         stud: matches.is_present("stud") && blocks,
         snap: matches.is_present("snap"),
-        img: matches.is_present("img") && blocks,
+        img: flat_image,
         glow: matches.is_present("glow"),
         hdmap: matches.is_present("hdmap"),
         nocollide: matches.is_present("nocollide"),
@@ -1553,55 +2519,175 @@ fn run_heightmap(
         surface,
     };
 
+    let cloud = cloud_options.map(|cloud_options| {
+        info!("Reading point clouds");
+        match PointCloudMap::open(heightmap_files, &cloud_options) {
+            Ok(cloud) => {
+                let (w, h) = Heightmap::size(&cloud);
+                info!(
+                    "Gridded into {} x {} cells of {}, {} height levels{}",
+                    commas(w as u64),
+                    commas(h as u64),
+                    cloud_options.cell_size,
+                    commas(cloud.max_level() as u64 + 1),
+                    if cloud.has_rgb() { ", with colour" } else { "" }
+                );
+                cloud
+            }
+            Err(e) => fail!("Error reading point cloud: {e}"),
+        }
+    });
+
     info!("Reading image files");
 
-    // colormap file parsing
-    let colormap = match file_ext(&colormap_file)
-        .map(|s| s.to_lowercase())
-        .as_deref()
-    {
-        Some("png") | Some("jpg") | Some("jpeg") => match ColormapPNG::new(&colormap_file) {
-            Ok(map) => map,
-            Err(err) => {
-                fail!("Error reading colormap: {:?}", err);
+    // colormap file parsing. A point cloud without `-c` is its own colormap:
+    // the points' colours, or a grey ramp of the heights when they have none.
+    let colormap = match (&cloud, matches.is_present("colormap")) {
+        (Some(cloud), false) => ColormapPNG::from_image(cloud.color_image()),
+        _ => match file_ext(&colormap_file)
+            .map(|s| s.to_lowercase())
+            .as_deref()
+        {
+            Some("png") | Some("jpg") | Some("jpeg") => match ColormapPNG::new(&colormap_file) {
+                Ok(map) => map,
+                Err(err) => {
+                    fail!("Error reading colormap: {:?}", err);
+                }
+            },
+            Some(ext) => {
+                fail!("Unsupported colormap format '{}'", ext);
+            }
+            None => {
+                fail!("Missing colormap format for '{}'", colormap_file.display());
             }
         },
-        Some(ext) => {
-            fail!("Unsupported colormap format '{}'", ext);
-        }
-        None => {
-            fail!("Missing colormap format for '{}'", colormap_file.display());
+    };
+
+    // The reduced picture replaces the colormap. What it saved is estimated
+    // from the quadtree's leaves on both pictures, not a second render: the
+    // photos this is for are the ones too big to build twice.
+    let colormap = if flat_image && reduce {
+        let (mut palette, source) = match fixed_palette {
+            Some(palette) => (palette, "the --palette file"),
+            None => (Palette::build(std::slice::from_ref(colormap.image()), colors, 1), "median cut"),
+        };
+        info!(
+            "Reducing the picture to {} colours ({source}, {})",
+            palette.len(),
+            match dither {
+                Dither::None => "no dithering",
+                Dither::Ordered => "ordered dithering",
+                Dither::FloydSteinberg => "Floyd-Steinberg dithering",
+                Dither::Atkinson => "Atkinson dithering",
+            }
+        );
+        let reduced = ColormapPNG::from_image(quantize(colormap.image(), &mut palette, dither));
+        let full = quad_leaf_count(colormap.image(), &options);
+        let cut = quad_leaf_count(reduced.image(), &options);
+        // Dithering a smooth picture can cost MORE than its exact colours
+        // did, and the log says so rather than "-40% fewer".
+        if cut <= full {
+            info!(
+                "Palette reduction: about {} bricks instead of about {} ({}% fewer)",
+                commas(cut as u64),
+                commas(full as u64),
+                (100. - cut as f64 / full.max(1) as f64 * 100.).floor()
+            );
+        } else {
+            warn!(
+                "Palette reduction: about {} bricks instead of about {} -- MORE than the exact \
+                 colours cost. Dithering breaks up runs a smooth picture already had; try \
+                 --dither none",
+                commas(cut as u64),
+                commas(full as u64),
+            );
         }
+        reduced
+    } else {
+        colormap
     };
 
     // heightmap file parsing
-    let heightmap: Box<dyn Heightmap> = if heightmap_files.iter().all(|f| {
+    if cloud.is_none() && !heightmap_files.iter().all(|f| {
         matches!(
             file_ext(f).map(|s| s.to_lowercase()).as_deref(),
             Some("png") | Some("jpg") | Some("jpeg")
         )
     }) {
-        if options.img {
-            Box::new(HeightmapFlat::new(colormap.size()).unwrap())
-        } else {
-            match HeightmapPNG::new(heightmap_files.iter().collect(), options.hdmap) {
-                Ok(map) => Box::new(map),
-                Err(error) => {
-                    fail!("Error reading heightmap: {:?}", error);
-                }
+        fail!("Unsupported heightmap format");
+    }
+    let read = |files: Vec<&PathBuf>| -> Box<dyn Heightmap> {
+        match HeightmapPNG::new(files, options.hdmap) {
+            Ok(map) => Box::new(map),
+            Err(error) => {
+                fail!("Error reading heightmap: {:?}", error);
             }
         }
+    };
+    // `--layers` reads the inputs as bottom/top PAIRS instead of stacking
+    // them, one `HeightmapLayer` per pair; there is then no single heightmap.
+    let layers: Vec<HeightmapLayer> = if layered {
+        heightmap_files
+            .chunks(2)
+            .map(|pair| HeightmapLayer {
+                bottom: read(vec![&pair[0]]),
+                top: read(vec![&pair[1]]),
+            })
+            .collect()
     } else {
-        fail!("Unsupported heightmap format");
+        vec![]
+    };
+    let max_level = match &cloud {
+        Some(cloud) => cloud.max_level(),
+        None if relief => relief_depth + 1,
+        None => 255,
+    };
+    let heightmap: Option<Box<dyn Heightmap>> = if layered {
+        None
+    } else if relief {
+        // The heights come from the depth map, or else from the input
+        // picture -- which is the colormap already, unless `-c` recoloured it.
+        let source = match (matches.value_of("depthmap"), matches.is_present("colormap")) {
+            (Some(file), _) => ColormapPNG::new(file),
+            (None, true) => ColormapPNG::new(&heightmap_files[0]),
+            (None, false) => Ok(ColormapPNG::from_image(colormap.image().clone())),
+        };
+        let source = match source {
+            Ok(source) => source,
+            Err(e) => fail!("Error reading relief source: {e}"),
+        };
+        if source.size() != colormap.size() {
+            fail!(
+                "the relief source is {:?} but the picture colouring it is {:?}; a depth map \
+                 must be the size of its picture",
+                source.size(),
+                colormap.size()
+            );
+        }
+        let invert = matches.is_present("invert");
+        match HeightmapRelief::from_image(source.image(), relief_depth, invert) {
+            Ok(map) => Some(Box::new(map)),
+            Err(e) => fail!("{e}"),
+        }
+    } else if options.img {
+        Some(Box::new(HeightmapFlat::new(colormap.size()).unwrap()))
+    } else if let Some(cloud) = cloud {
+        Some(Box::new(cloud))
+    } else {
+        Some(read(heightmap_files.iter().collect()))
+    };
+    let map_size = match &heightmap {
+        Some(map) => map.size(),
+        None => layers[0].top.size(),
     };
 
     // The size of the render, before it runs. The GUI shows the same values
     // below its scale sliders.
     let plan = footprint(
-        heightmap.size(),
+        map_size,
         options.size,
         if options.img { 0 } else { options.scale },
-        255,
+        max_level,
     );
     info!("Build size: {}", plan.size_text());
     info!(
@@ -1610,7 +2696,12 @@ fn run_heightmap(
         commas(plan.units.1),
         plan.real_text()
     );
-    if !options.img {
+    if plane != BuildPlane::Ground {
+        info!("  {}", plan.wall_text());
+        if !options.img {
+            info!("  {}", plan.relief_text());
+        }
+    } else if !options.img {
         info!("  {}", plan.height_text());
     }
     if plan.over_brick_limit() {
@@ -1620,11 +2711,50 @@ fn run_heightmap(
     // Not `.expect(...)`: `gen_opt_heightmap` returns a `String` describing a
     // real user-facing condition (an image it cannot use), and a panic trace
     // reads as a crash rather than as the refusal it is.
-    let bricks = match gen_opt_heightmap(&*heightmap, &colormap, options, |_| true) {
+    let generated = match &heightmap {
+        Some(map) => gen_opt_heightmap(&**map, &colormap, options, |_| true),
+        None => gen_layered_heightmap(&layers, &colormap, options, |_| true),
+    };
+    let bricks = match generated {
         Ok(b) => b,
         Err(e) => fail!("{e}"),
     };
+    // Turned as one piece after the render, so no generator needs to know.
+    let mut bricks = bricks;
+    stand_upright(&mut bricks, plane);
+    // Every span empty is what a pair given top first looks like, and a
+    // save with nothing in it would otherwise report "Done!" in silence.
+    if layered && bricks.is_empty() {
+        warn!(
+            "--layers built no bricks: no column's top is above its bottom. The inputs are \
+             read as bottom/top pairs -- check they are not given top first"
+        );
+    }
+
+    write_brick_save(
+        matches,
+        bricks,
+        "heightmap file",
+        colormap.image(),
+        &colormap_file.display().to_string(),
+        out_file,
+    );
+}
 
+/// The end of every brick render: the chunk-limit check, the save preview,
+/// the bundle name and `--prefab`, then the write. Split out of
+/// `run_heightmap` so the voxel branch cannot drift from it. `source` names
+/// the input in the save's description; `preview` is the top-down picture the
+/// save browser shows, and `preview_name` is what the log calls it.
+#[cfg(not(target_arch = "wasm32"))]
+fn write_brick_save(
+    matches: &clap::ArgMatches,
+    bricks: Vec<brdb::Brick>,
+    source: &str,
+    preview: &image::RgbaImage,
+    preview_name: &str,
+    out_file: &str,
+) {
     // Do the check BEFORE the write. The game cannot load a save above the
     // chunk limit, and a failure in the game wastes the full render.
     match check_chunk_limit(&bricks) {
@@ -1637,19 +2767,20 @@ fn run_heightmap(
     }
 
     info!("Writing Save to {}", out_file);
-    let mut data = bricks_to_save(bricks);
+    let mut data = bricks_to_save_from(bricks, source);
 
     // Each save gets a preview, world or prefab. The game shows a grid of
-    // pictures, and a generated save has no screenshot of itself. The source
-    // map gives a view from above. Use the COLORMAP, which shows the colours
-    // of the build. Without `-c`, `colormap_file` is already the heightmap.
+    // pictures, and a generated save has no screenshot of itself. The caller
+    // passes a view from above: a heightmap's COLORMAP, which shows the
+    // colours of the build (without `-c` that is the heightmap itself), or a
+    // voxel model's top view.
     //
     // An encode failure only writes a warning, because it must not stop the
     // render.
-    match save_screenshot(colormap.image()) {
+    match save_screenshot(preview) {
         Ok(screenshot) => {
             data.meta.screenshot = Some(screenshot);
-            info!("Save preview: {}", colormap_file.display());
+            info!("Save preview: {preview_name}");
         }
         Err(e) => warn!("no save preview embedded: {e}"),
     }
@@ -1667,6 +2798,18 @@ fn run_heightmap(
     // the completed world and does not send this option to each generator.
     if matches.is_present("prefab") {
         data.make_prefab();
+        // `make_prefab` measures each brick as authored, ignoring its
+        // rotation. Bricks turned off `Z_Positive` -- the sloped pieces of a
+        // `--wall` -- are measured again with their rotated extents so the
+        // prefab's bounds follow the axis they stand on.
+        if data
+            .bricks
+            .iter()
+            .any(|b| !matches!(b.direction, brdb::Direction::ZPositive))
+            && let Some((min, max)) = rotated_bounds(&data.bricks)
+        {
+            data.meta.prefab = Some(brdb::PrefabJson::from_bounds(min, max));
+        }
     }
     if let Err(e) = write_world(&data, &out_file) {
         fail!("{e}");
@@ -1675,18 +2818,243 @@ fn run_heightmap(
     info!("Done!");
 }
 
+/// The bounds of `bricks` with each brick's extents ROTATED by its direction
+/// and roll, which `World::brick_bounds` does not do. Non-procedural bricks
+/// keep `brdb`'s own measure.
+#[cfg(not(target_arch = "wasm32"))]
+fn rotated_bounds(bricks: &[brdb::Brick]) -> Option<(brdb::Position, brdb::Position)> {
+    bricks
+        .iter()
+        .map(|b| match &b.asset {
+            brdb::BrickType::Procedural { size, .. } => {
+                let authored = brdb::IntVector {
+                    x: size.x as i32,
+                    y: size.y as i32,
+                    z: size.z as i32,
+                };
+                let half = layout::rotated_half(authored, b.direction, b.rotation);
+                let half = brdb::Position::new(half.x, half.y, half.z);
+                (b.position - half, b.position + half)
+            }
+            _ => b.local_bounds(),
+        })
+        .reduce(|(lo, hi), (b_lo, b_hi)| {
+            (
+                brdb::Position::new(lo.x.min(b_lo.x), lo.y.min(b_lo.y), lo.z.min(b_lo.z)),
+                brdb::Position::new(hi.x.max(b_hi.x), hi.y.max(b_hi.y), hi.z.max(b_hi.z)),
+            )
+        })
+}
+
+/// The `--vox` and `--mesh` render branch: reads a MagicaVoxel model, or
+/// voxelizes a triangle mesh, greedy meshes the voxels into bricks and writes
+/// the result through the same tail as `run_heightmap`.
+#[cfg(not(target_arch = "wasm32"))]
+fn run_voxel(matches: &clap::ArgMatches, heightmap_files: &[PathBuf], out_file: &str) {
+    // Two readers for one input. Refused rather than guessed between, the
+    // same as the surface renderers.
+    if matches.is_present("vox") && matches.is_present("mesh") {
+        fail(
+            "--vox and --mesh read the input two different ways and cannot be combined: \
+             --vox reads a MagicaVoxel model, --mesh voxelizes an OBJ or STL. Pass exactly one",
+        );
+    }
+    let is_mesh = matches.is_present("mesh");
+    let mode = if is_mesh { "--mesh" } else { "--vox" };
+
+    if heightmap_files.len() > 1 {
+        warn!(
+            "{mode} uses only the first input file; ignoring {} extra input(s)",
+            heightmap_files.len() - 1
+        );
+    }
+    // Named one at a time, like the sloped renderers name the flags they
+    // cannot use: each of these shapes a HEIGHT FIELD, and a voxel model
+    // carries its own heights and colours.
+    for (flag, name) in [
+        ("--terrain", "terrain"),
+        ("--rampify", "rampify"),
+        ("--wedge", "wedge"),
+        ("--layers", "layers"),
+        ("--relief", "relief"),
+        ("--wall", "wall"),
+        ("--img", "img"),
+        ("--colormap", "colormap"),
+        ("--vertical", "vertical"),
+        ("--hdmap", "hdmap"),
+        ("--cull", "cull"),
+        ("--snap", "snap"),
+        ("--cell-size", "cellsize"),
+        ("--cell-z", "cellz"),
+        ("--gap-fill", "gapfill"),
+    ] {
+        if matches.is_present(name) {
+            warn!(
+                "{mode} ignores {flag}: a voxel model carries its own heights and colours, and \
+                 every voxel is a cube of --size"
+            );
+        }
+    }
+    if matches.is_present("greedy") {
+        warn!("--greedy has no effect with {mode}: voxels are always greedy meshed");
+    }
+    if !is_mesh {
+        for (flag, name) in [
+            ("--resolution", "meshresolution"),
+            ("--fill", "meshfill"),
+            ("--up", "meshup"),
+        ] {
+            if matches.is_present(name) {
+                warn!(
+                    "--vox ignores {flag}: it voxelizes a --mesh, and a .vox file is voxels \
+                     already"
+                );
+            }
+        }
+    }
+
+    // Counted exactly as `run_heightmap` counts it: studs, or micro units
+    // with `--micro`.
+    let size = match parse_arg(matches, "size", "--size", "an integer", 1u16) {
+        Ok(0) => fail!("--size must be at least 1 (it is the width of one voxel in studs)"),
+        Ok(v) => v,
+        Err(e) => fail(e),
+    };
+    let micro = matches.is_present("micro");
+    let Some(half_extent) = size.checked_mul(if micro { 1 } else { 5 }) else {
+        fail!(
+            "--size {size} is too large. One voxel would be more than {} units across, which \
+             is more than one brick can be. The maximum brick size is {} units",
+            u16::MAX,
+            MAX_BRICK_HALF_EXTENT * 2
+        );
+    };
+
+    let options = GenOptions {
+        size: half_extent,
+        scale: 1,
+        cull: false,
+        asset: if micro {
+            PB_DEFAULT_MICRO_BRICK
+        } else if matches.is_present("tile") {
+            PB_DEFAULT_TILE
+        } else if matches.is_present("smooth") {
+            PB_DEFAULT_SMOOTH_TILE
+        } else if matches.is_present("stud") {
+            PB_DEFAULT_STUDDED
+        } else {
+            PB_DEFAULT_BRICK
+        },
+        micro,
+        stud: matches.is_present("stud"),
+        snap: false,
+        img: false,
+        glow: matches.is_present("glow"),
+        hdmap: false,
+        nocollide: matches.is_present("nocollide"),
+        quadtree: false,
+        greedy: true,
+        surface: SurfaceMode::Blocks,
+    };
+
+    let input = &heightmap_files[0];
+    let grid = if is_mesh {
+        let resolution = match parse_arg(
+            matches,
+            "meshresolution",
+            "--resolution",
+            "an integer",
+            mesh::VoxelizeOptions::default().resolution,
+        ) {
+            Ok(0) => fail!("--resolution must be at least 1 (it is a count of voxels)"),
+            Ok(v) => v,
+            Err(e) => fail(e),
+        };
+        let fill = match matches.value_of("meshfill").map(|s| s.to_lowercase()).as_deref() {
+            None | Some("solid") => mesh::MeshFill::Solid,
+            Some("shell") => mesh::MeshFill::Shell,
+            Some(other) => fail!("unknown --fill '{other}' (solid, shell)"),
+        };
+        let up = match matches.value_of("meshup").map(|s| s.to_lowercase()).as_deref() {
+            None => None,
+            Some("y") => Some(mesh::UpAxis::Y),
+            Some("z") => Some(mesh::UpAxis::Z),
+            Some(other) => fail!("unknown --up '{other}' (y, z)"),
+        };
+
+        info!("Reading mesh {}", input.display());
+        let mut model = match mesh::Mesh::open(input) {
+            Ok(m) => m,
+            Err(e) => fail!("{e}"),
+        };
+        if let Some(up) = up {
+            model.up = up;
+        }
+        info!(
+            "Read {} triangles, {} materials; voxelizing at {resolution} along the longest axis",
+            commas(model.triangles.len() as u64),
+            model.materials.len()
+        );
+        match mesh::voxelize(&model, &mesh::VoxelizeOptions { resolution, fill }) {
+            Ok(g) => g,
+            Err(e) => fail!("{e}"),
+        }
+    } else {
+        info!("Reading voxel model {}", input.display());
+        match voxel::VoxelGrid::open(input) {
+            Ok(g) => g,
+            Err(e) => fail!("{e}"),
+        }
+    };
+    let (w, h, d) = grid.size();
+    info!(
+        "{} voxels in a {w} x {h} x {d} grid, {} colours",
+        commas(grid.count() as u64),
+        grid.palette.len() - 1
+    );
+
+    // A voxel is as tall as it is wide, so the "shade of grey" height is one
+    // voxel and the brightest shade is the top layer.
+    let plan = footprint((w, h), options.size, options.size as u32 * 2, d);
+    info!("Build size: {}", plan.size_text());
+    info!(
+        "  {} x {} units, {} at 1 unit = 1 inch",
+        commas(plan.units.0),
+        commas(plan.units.1),
+        plan.real_text()
+    );
+    info!("  {}", plan.height_text());
+    if plan.over_brick_limit() {
+        warn!("--size {size}: {}", plan.brick_limit_text().replace("pixel", "voxel"));
+    }
+
+    let bricks = match gen_voxel_bricks(&grid, options, |_| true) {
+        Ok(b) => b,
+        Err(e) => fail!("{e}"),
+    };
+
+    write_brick_save(
+        matches,
+        bricks,
+        if is_mesh { "mesh" } else { "voxel model" },
+        &grid.top_view(),
+        &format!("top view of {}", input.display()),
+        out_file,
+    );
+}
+
 /// Print the pre-render cost readout for whichever mode was chosen. The
-/// three modes report different fields -- hex mode's chunk/character counts
+/// modes report different fields -- hex mode's chunk/character counts
 /// don't apply to colour-array, which tiles nothing and writes no strings.
 /// `width`/`height`/`char_repeat` are only used in text mode, for the
 /// per-band character bound computed in the `AnimMode::Text` arm.
 #[cfg(not(target_arch = "wasm32"))]
-fn log_cost(mode: AnimMode, cost: &cost::Cost, width: u32, height: u32, char_repeat: usize) {
+fn log_cost(mode: AnimMode, cost: &cost::Cost, width: u32, height: u32, opts: &AnimOptions) {
+    let text = &opts.text;
     info!(
         "Estimated cost ({}): {} pixel(s), {} gate(s), {} wire(s), {} brick(s), {} bank(s), {} frame(s)",
         match mode {
-            AnimMode::Brick(AnimEncoding::Hex) => "hex",
-            AnimMode::Brick(AnimEncoding::ColorArray) => "color-array",
+            AnimMode::Brick(enc) => enc.name(),
             AnimMode::Text => "text",
         },
         cost.pixels,
@@ -1698,13 +3066,40 @@ fn log_cost(mode: AnimMode, cost: &cost::Cost, width: u32, height: u32, char_rep
     );
     match mode {
         AnimMode::Brick(AnimEncoding::Hex) => {
-            info!("  {} chunk(s), {} character(s)", cost.chunks, cost.chars)
+            info!("  {} chunk(s), {} character(s)", cost.chunks, cost.chars);
+            let chips = cost::estimate_chips(width, height, cost.frames, opts);
+            if chips.len() > 1 {
+                let gates: Vec<String> = chips.iter().map(|c| c.gates.to_string()).collect();
+                info!(
+                    "  {} chip(s), gate(s) per chip: {} (heaviest {})",
+                    chips.len(),
+                    gates.join(", "),
+                    chips.iter().map(|c| c.gates).max().unwrap_or(0)
+                );
+            }
         }
         // `Cost::chars` is 0 in text mode by design (see `cost::estimate_text`):
         // real length is content-dependent, so this prints the closed-form
         // worst-case bound the band layout was derived from instead.
+        AnimMode::Text if text.mode != PixelMode::Color => {
+            match text_layout::plan_text_bands(width as usize, height as usize, text) {
+                Ok(plan) if !plan.is_empty() => {
+                    let rows = plan[0].rows;
+                    let per_band = text_layout::band_char_bound(width as usize, rows, text);
+                    info!(
+                        "  {} band(s) of {rows} row(s); at most {per_band} character(s) per band \
+                         per frame ({} {} glyph(s) per line, no colour tags)",
+                        plan.len(),
+                        text_layout::mono_line_chars(width as usize, text.mode),
+                        text.mode.name().to_lowercase(),
+                    );
+                }
+                // As below: the render fails with the layout's own message.
+                _ => {}
+            }
+        }
         AnimMode::Text => {
-            let repeat = char_repeat.max(1);
+            let repeat = text.char_repeat.max(1);
             match text_layout::plan_bands(width as usize, height as usize, repeat) {
                 Ok(plan) if !plan.is_empty() => {
                     let row = text_layout::worst_case_row_chars(width as usize, repeat);
@@ -1736,6 +3131,25 @@ fn log_cost(mode: AnimMode, cost: &cost::Cost, width: u32, height: u32, char_rep
                 bytes as f64 / (1024.0 * 1024.0)
             );
         }
+        // What delta mode stores depends on how much of the picture moves,
+        // so the only honest pre-render figure is the ceiling; the renderer
+        // logs the real count once every frame is packed.
+        AnimMode::Brick(AnimEncoding::Delta) => {
+            info!(
+                "  at most {} color element(s) -- one per pixel per frame only if every pixel \
+                 changes every frame; a still region stores one",
+                color_bricks::array_elements(cost.pixels, cost.frames)
+            );
+        }
+        AnimMode::Brick(AnimEncoding::Palette) => {
+            let hex = cost.pixels * cost.frames * HEX_STRIDE;
+            info!(
+                "  {} chunk(s), {} character(s) ({:.0}x fewer than hex mode's {hex})",
+                cost.chunks,
+                cost.chars,
+                hex as f64 / cost.chars.max(1) as f64
+            );
+        }
     }
 }
 
@@ -1816,6 +3230,173 @@ fn load_subtitles(
     Ok(Some(std::sync::Arc::new(track)))
 }
 
+/// Resolve `--skip-seconds`, `--chapters` and `--seek-bar` into the seek
+/// buttons a render gets; all off when none was passed.
+///
+/// `--chapters` is a list of times, `subtitles` (every cue start of the track
+/// `load_subtitles` already read) or `container` (`input`'s own chapters).
+/// Like an empty subtitle track, an empty chapter list is warned about: the
+/// render is fine, just without the two buttons that were asked for.
+#[cfg(not(target_arch = "wasm32"))]
+fn load_seek_controls(
+    matches: &clap::ArgMatches,
+    input: &std::path::Path,
+    subtitles: Option<&Subtitles>,
+    consent: DownloadConsent,
+) -> Result<SeekControls, String> {
+    let skip_s = parse_arg(matches, "animskip", "--skip-seconds", "a number", 0.0f32)?;
+    if !skip_s.is_finite() || skip_s < 0.0 {
+        return Err(format!("--skip-seconds must be a number of seconds, 0 or more (got {skip_s})"));
+    }
+    let bar = parse_arg(matches, "animseekbar", "--seek-bar", "an integer", 0usize)?;
+    let chapters = match matches.value_of("animchapters") {
+        None => Vec::new(),
+        Some("subtitles") => subtitles
+            .ok_or("--chapters subtitles needs --subtitles or --subtitle-track")?
+            .starts(),
+        Some("container") => {
+            if !is_video_path(input) {
+                return Err(format!(
+                    "--chapters container needs a video file, and {} is not one",
+                    input.display()
+                ));
+            }
+            ensure_ffmpeg(consent)?;
+            heightmap::video::ffmpeg::probe_chapters(input)?
+        }
+        Some(list) => seek::parse_times(list).map_err(|e| format!("--chapters: {e}"))?,
+    };
+    if matches.is_present("animchapters") && chapters.is_empty() {
+        warn!("--chapters found no chapters at all -- the render has no chapter buttons");
+    }
+    Ok(SeekControls { skip_s, chapters, bar, playlist: None })
+}
+
+/// The [`Adjustments`] `--crop`, `--rotate`, `--flip` and the grading flags
+/// ask for. The identity when none was given.
+#[cfg(not(target_arch = "wasm32"))]
+fn adjustments(matches: &clap::ArgMatches) -> Result<Adjustments, String> {
+    if matches.is_present("crop") && matches.is_present("autocrop") {
+        return Err("--crop and --auto-crop are mutually exclusive".to_string());
+    }
+    let crop = match matches.value_of("crop") {
+        Some(s) => Some(Crop::parse(s).ok_or_else(|| {
+            format!("--crop '{s}' is not WxH+X+Y with both sides at least 1 (e.g. 1280x536+0+92)")
+        })?),
+        None => None,
+    };
+    let rotation = match matches.value_of("rotate") {
+        Some(s) => Rotation::parse(s)
+            .ok_or_else(|| format!("--rotate '{s}' is not 90, 180 or 270 degrees"))?,
+        None => Rotation::None,
+    };
+    let flip = match matches.value_of("flip") {
+        Some(s) => Flip::parse(s).ok_or_else(|| format!("--flip '{s}' is not h, v or hv"))?,
+        None => Flip::default(),
+    };
+    let d = Grade::default();
+    let grade = Grade {
+        brightness: parse_arg(matches, "brightness", "--brightness", "a number", d.brightness)?,
+        contrast: parse_arg(matches, "contrast", "--contrast", "a number", d.contrast)?,
+        saturation: parse_arg(matches, "saturation", "--saturation", "a number", d.saturation)?,
+        gamma: parse_arg(matches, "gamma", "--gamma", "a number", d.gamma)?,
+    };
+    if !(-1.0..=1.0).contains(&grade.brightness) {
+        return Err(format!("--brightness must be between -1 and 1 (got {})", grade.brightness));
+    }
+    for (flag, v) in [("--contrast", grade.contrast), ("--saturation", grade.saturation)] {
+        if !v.is_finite() || v < 0.0 {
+            return Err(format!("{flag} must be 0 or more (got {v})"));
+        }
+    }
+    if !grade.gamma.is_finite() || grade.gamma <= 0.0 {
+        return Err(format!("--gamma must be above 0 (got {})", grade.gamma));
+    }
+    Ok(Adjustments { crop, rotation, flip, grade })
+}
+
+/// `adjust` for one source: with `--auto-crop`, the bars it finds become the
+/// crop. Checked against the source here, so a `--crop` that runs off the
+/// frame is refused before anything is printed rather than on frame one.
+#[cfg(not(target_arch = "wasm32"))]
+fn adjust_source(
+    adjust: Adjustments,
+    auto_crop: bool,
+    source: &dyn FrameSource,
+    start: f32,
+) -> Result<Adjustments, String> {
+    let info = source.info();
+    let adjust = if auto_crop {
+        match detect_letterbox(source, start)? {
+            Some(crop) => {
+                info!("Cropping bars: keeping {crop} of {}x{}", info.width, info.height);
+                Adjustments { crop: Some(crop), ..adjust }
+            }
+            None => {
+                info!("--auto-crop found no bars to crop");
+                adjust
+            }
+        }
+    } else {
+        adjust
+    };
+    adjust.check(info.width, info.height)?;
+    Ok(adjust)
+}
+
+/// The [`Keying`] `--chroma-key` and `--mask` ask for, or `None` when
+/// neither was given. The tuning flags are refused without a key rather than
+/// ignored, as is a mask that doesn't open.
+#[cfg(not(target_arch = "wasm32"))]
+fn keying(matches: &clap::ArgMatches) -> Result<Option<Keying>, String> {
+    let chroma = match matches.value_of("chromakey") {
+        Some(s) => {
+            let color = ChromaKey::parse_color(s).ok_or_else(|| {
+                format!("--chroma-key '{s}' is not a colour (RRGGBB, green or blue)")
+            })?;
+            let key = ChromaKey::new(color);
+            let band = |name, flag: &str, default: f32| -> Result<f32, String> {
+                let v = parse_arg(matches, name, flag, "a number", default)?;
+                if !v.is_finite() || v < 0.0 {
+                    return Err(format!("{flag} must be 0 or more (got {v})"));
+                }
+                Ok(v)
+            };
+            let tolerance = band("keytolerance", "--key-tolerance", key.tolerance)?;
+            let softness = band("keysoftness", "--key-softness", key.softness)?;
+            let spill = band("keyspill", "--key-spill", key.spill)?;
+            if spill > 1.0 {
+                return Err(format!("--key-spill must be between 0 and 1 (got {spill})"));
+            }
+            Some(ChromaKey { tolerance, softness, spill, ..key })
+        }
+        None => {
+            for (flag, name) in [
+                ("--key-tolerance", "keytolerance"),
+                ("--key-softness", "keysoftness"),
+                ("--key-spill", "keyspill"),
+            ] {
+                if matches.is_present(name) {
+                    return Err(format!("{flag} needs --chroma-key"));
+                }
+            }
+            None
+        }
+    };
+    let mask = match matches.value_of("mask") {
+        Some(path) => Some(
+            image::open(path)
+                .map_err(|e| format!("Error reading --mask {path}: {e}"))?
+                .to_rgba8(),
+        ),
+        None => None,
+    };
+    if chroma.is_none() && mask.is_none() {
+        return Ok(None);
+    }
+    Ok(Some(Keying { chroma, mask }))
+}
+
 // The next few option builders (`target_size`, `anim_options`,
 // `text_options`, `audio_options`) each cover flags that more than one
 // `main` branch needs, in one function instead of one copy per branch: two
@@ -1847,8 +3428,155 @@ fn target_size(
     Ok(Some((target_w, target_h)))
 }
 
+/// The [`Budget`] `--max-gates`, `--max-save-size` and `--max-ram` set, or
+/// `None` when none was given and the render is sized by its flags alone.
+#[cfg(not(target_arch = "wasm32"))]
+fn budget_options(matches: &clap::ArgMatches) -> Result<Option<Budget>, String> {
+    let bytes = |name: &str, flag: &str| match matches.value_of(name) {
+        Some(s) => budget::parse_bytes(s).map(Some).ok_or_else(|| {
+            format!("{flag} must be a size such as 512K, 200M or 4G (got '{s}')")
+        }),
+        None => Ok(None),
+    };
+    let budget = Budget {
+        gates: parse_opt_arg(matches, "maxgates", "--max-gates", "an integer")?,
+        save_bytes: bytes("maxsave", "--max-save-size")?,
+        host_bytes: bytes("maxram", "--max-ram")?,
+    };
+    Ok((!budget.is_empty()).then_some(budget))
+}
+
+/// The best render under `budget`, from `size` and `fps` down (see
+/// [`budget::fit`]), logged for the user. The encoding is searched only when
+/// the user left it to us: an explicit `--anim-encoding` is kept, and
+/// `--panels` and text mode have only the one.
+#[cfg(not(target_arch = "wasm32"))]
+fn fit_anim(
+    matches: &clap::ArgMatches,
+    budget: &Budget,
+    mode: AnimMode,
+    size: (u32, u32),
+    fps: f32,
+    opts: &AnimOptions,
+    frames_at: &dyn Fn(f32) -> usize,
+) -> Result<budget::Fit, String> {
+    let fixed = mode == AnimMode::Text
+        || matches.is_present("animencoding")
+        || matches.is_present("animpanels");
+    let mut modes = vec![mode];
+    if !fixed {
+        for enc in [AnimEncoding::ColorArray, AnimEncoding::Delta, AnimEncoding::Palette] {
+            // Delta is only a candidate where it could be built at all.
+            if enc == AnimEncoding::Delta && delta_bricks::check_options(opts).is_err() {
+                continue;
+            }
+            if mode != AnimMode::Brick(enc) {
+                modes.push(AnimMode::Brick(enc));
+            }
+        }
+    }
+    let space = budget::Space {
+        size,
+        fps,
+        vary_size: true,
+        vary_fps: true,
+        modes,
+        vary_colors: true,
+    };
+    let fitted = budget::fit(&space, opts, budget, frames_at)?;
+    info!(
+        "Fitted to the budget: {}x{} at {} fps, {}{}; {} gate(s), a save of up to {}, up to {} \
+         of memory",
+        fitted.width,
+        fitted.height,
+        fitted.fps,
+        match fitted.mode {
+            AnimMode::Brick(enc) => enc.name(),
+            AnimMode::Text => "text",
+        },
+        if fitted.colors != opts.colors {
+            format!(" with {} colours", fitted.colors)
+        } else {
+            String::new()
+        },
+        fitted.cost.gates,
+        budget::show_bytes(fitted.save_bytes),
+        budget::show_bytes(fitted.host_bytes),
+    );
+    Ok(fitted)
+}
+
+/// `--preview`: replay what `mode` would build from `source` (see
+/// [`replay`]) into the path given, in place of the save. `false` when the
+/// flag is absent, for the caller to go on and build.
+#[cfg(not(target_arch = "wasm32"))]
+fn write_preview(
+    matches: &clap::ArgMatches,
+    mode: AnimMode,
+    source: &dyn FrameSource,
+    opts: &AnimOptions,
+) -> bool {
+    let Some(path) = matches.value_of("animpreview") else {
+        return false;
+    };
+    let scale = match parse_opt_arg(matches, "animpreviewscale", "--preview-scale", "an integer") {
+        Ok(Some(0)) => fail("--preview-scale must be at least 1"),
+        Ok(Some(n)) => n,
+        Ok(None) => replay::auto_scale(source.info().width),
+        Err(e) => fail(e),
+    };
+    let replayed =
+        match replay::replay(mode, source, opts, &mut progress_cli::CliProgress::new()) {
+            Ok(r) => r,
+            Err(e) => fail!("{e}"),
+        };
+    match replay::write(&replayed, std::path::Path::new(path), scale) {
+        Ok(n) => info!(
+            "Wrote a {n}-frame preview of the {}x{} screen to {path}, {scale}x; no save written",
+            replayed.width, replayed.height
+        ),
+        Err(e) => fail!("{e}"),
+    }
+    true
+}
+
+/// The [`Playback`] `--ping-pong`, `--loop-range` and `--repeat` name, shared by
+/// the video, audio and MIDI branches. `--no-loop` stays the options' own
+/// `loop_playback`; it only has to be kept apart from `--repeat`, which would
+/// otherwise quietly win.
+#[cfg(not(target_arch = "wasm32"))]
+fn playback_options(matches: &clap::ArgMatches) -> Result<Playback, String> {
+    let order = match (matches.is_present("pingpong"), matches.value_of("looprange")) {
+        (true, Some(_)) => return Err("--ping-pong cannot take --loop-range".to_string()),
+        (true, None) => PlayOrder::PingPong,
+        (false, None) => PlayOrder::Forward,
+        (false, Some(list)) => {
+            match seek::parse_times(list).map_err(|e| format!("--loop-range: {e}"))?[..] {
+                [start_s, end_s] => PlayOrder::Range { start_s, end_s },
+                _ => {
+                    return Err(format!("--loop-range '{list}' is not START,END (e.g. 0:05,0:20)"));
+                }
+            }
+        }
+    };
+    let plays = match matches.value_of("repeat") {
+        None => None,
+        Some(_) if matches.is_present("noloop") => {
+            return Err("--repeat cannot take --no-loop; --repeat 1 plays once".to_string());
+        }
+        Some(_) => match parse_arg(matches, "repeat", "--repeat", "an integer", 1u32)? {
+            0 => return Err("--repeat must be at least 1".to_string()),
+            n => Some(n),
+        },
+    };
+    let playback = Playback { order, plays };
+    playback.check().map_err(|e| format!("--loop-range: {e}"))?;
+    Ok(playback)
+}
+
 /// The `AnimOptions` both `--anim-mode` branches share: display flags
-/// (`--brick-style`, `--pixel-extent`, `--glow`, `--srgb-to-linear`), clock
+/// (`--brick-style`, `--screen-orientation`, `--pixel-extent`, `--glow`,
+/// `--srgb-to-linear`), clock
 /// flags (`--external-clock`, `--no-loop`), and what the caller already
 /// parsed for its own use.
 #[cfg(not(target_arch = "wasm32"))]
@@ -1861,6 +3589,18 @@ fn anim_options(
     subtitle_lift: f32,
     start: f32,
 ) -> Result<AnimOptions, String> {
+    let panels = match matches.value_of("animpanels") {
+        None => PanelGrid::default(),
+        Some(s) => PanelGrid::parse(s).ok_or_else(|| {
+            format!("--panels '{s}' is not COLSxROWS with both at least 1 (e.g. 2x2)")
+        })?,
+    };
+    let orientation = match matches.value_of("animorientation") {
+        None => ScreenOrientation::Floor,
+        Some(s) => ScreenOrientation::parse(s).ok_or_else(|| {
+            format!("unknown screen orientation '{s}' ({})", ScreenOrientation::NAMES)
+        })?,
+    };
     let brick_style = match matches
         .value_of("animbrickstyle")
         .map(|s| s.to_lowercase())
@@ -1887,10 +3627,13 @@ fn anim_options(
         // the default so that every command line that predates the flag renders
         // exactly what it did before.
         loop_playback: !matches.is_present("noloop"),
+        playback: playback_options(matches)?,
         // NEGATED, and default-on: `--no-control-buttons` is an off-switch, so
         // an unchanged command line ships the buttons.
         control_buttons: !matches.is_present("nocontrolbuttons"),
         brick_style,
+        orientation,
+        panels,
         pixel_extent,
         glow: matches.is_present("glow"),
         srgb_to_linear: matches.is_present("srgb2lin"),
@@ -1913,22 +3656,53 @@ fn anim_options(
     })
 }
 
-/// The `TextOptions` every text-rendering path shares: `--font`,
-/// `--fill-char`, `--empty-char`, `--char-repeat`, `--alpha-threshold`,
-/// `--line-height-world`, `--braille`/`--blocks`, `--luma-threshold`,
-/// `--invert`, `--material`. Numeric values go through [`parse_arg`], so a
-/// mistyped `--char-repeat two` is a CLI error naming the flag.
+/// `--dither`, shared by `--img` palette reduction and the monochrome text
+/// modes.
+#[cfg(not(target_arch = "wasm32"))]
+fn dither_arg(matches: &clap::ArgMatches) -> Result<Dither, String> {
+    match matches.value_of("dither") {
+        None => Ok(Dither::None),
+        Some(s) => Dither::from_name(&s.to_lowercase()).ok_or_else(|| {
+            format!("unknown --dither '{s}' (none, ordered, floyd-steinberg, atkinson)")
+        }),
+    }
+}
+
+/// The `TextOptions` every text-rendering path shares: `--font` (plus any
+/// `--font-file` presets), `--fill-char`, `--empty-char`, `--char-repeat`,
+/// `--alpha-threshold`, `--line-height-world`, the glyph modes (`--braille`,
+/// `--blocks`, `--sextants`, `--octants`), `--luma-threshold`, `--invert`,
+/// `--dither`, `--material`. Numeric values go through [`parse_arg`], so a mistyped
+/// `--char-repeat two` is a CLI error naming the flag.
 #[cfg(not(target_arch = "wasm32"))]
 fn text_options(matches: &clap::ArgMatches) -> Result<TextOptions, String> {
-    let preset = match matches.value_of("font").map(|s| s.to_lowercase()).as_deref() {
-        None | Some("monaspace") | Some("argon") => FontPreset::MonaspaceArgon,
-        Some("iosevka") => FontPreset::IosevkaTerm,
-        Some("orbitron") => FontPreset::Orbitron,
-        Some(other) => {
-            return Err(format!(
-                "unknown font preset '{other}' (monaspace, iosevka, orbitron)"
-            ));
+    text_options_sized(matches, 1.0)
+}
+
+/// [`text_options`] with a different default for `--line-height-world`: a
+/// sign's "pixel" is a whole line of text, and one world unit is unreadable.
+#[cfg(not(target_arch = "wasm32"))]
+fn text_options_sized(
+    matches: &clap::ArgMatches,
+    default_pixel_size: f32,
+) -> Result<TextOptions, String> {
+    let custom = match matches.value_of("fontfile") {
+        Some(path) => {
+            let src = std::fs::read_to_string(path)
+                .map_err(|e| format!("--font-file: could not read {path}: {e}"))?;
+            parse_font_presets(&src).map_err(|e| format!("--font-file {path}: {e}"))?
         }
+        None => Vec::new(),
+    };
+    let preset = match matches.value_of("font") {
+        None => FontChoice::Builtin(FontPreset::MonaspaceArgon),
+        Some(name) => FontChoice::resolve(name, &custom).ok_or_else(|| {
+            let names: Vec<&str> = ["monaspace", "iosevka", "orbitron"]
+                .into_iter()
+                .chain(custom.iter().map(|c| c.name.as_str()))
+                .collect();
+            format!("unknown font preset '{name}' ({})", names.join(", "))
+        })?,
     };
     let material = match matches.value_of("material").map(|s| s.to_lowercase()).as_deref() {
         None | Some("unlit") => TextMaterial::Unlit,
@@ -1950,7 +3724,7 @@ fn text_options(matches: &clap::ArgMatches) -> Result<TextOptions, String> {
         "lineheight",
         "--line-height-world",
         "a number",
-        1.0f32,
+        default_pixel_size,
     )?;
     let d = preset.options(pixel_size);
     let text_opts = TextOptions {
@@ -1974,9 +3748,14 @@ fn text_options(matches: &clap::ArgMatches) -> Result<TextOptions, String> {
             PixelMode::Braille
         } else if matches.is_present("blocks") {
             PixelMode::Blocks
+        } else if matches.is_present("sextants") {
+            PixelMode::Sextants
+        } else if matches.is_present("octants") {
+            PixelMode::Octants
         } else {
             PixelMode::Color
         },
+        dither: dither_arg(matches)?,
         luma_threshold: parse_arg(
             matches,
             "lumathreshold",
@@ -2064,6 +3843,47 @@ where
     }
 }
 
+/// The `--with-audio` half of a video render: opens `input`'s audio track on
+/// the same flags `run_audio` reads and analyses it at the picture's `fps`, at
+/// most `max_frames` frames, so one frame index can drive both.
+#[cfg(not(target_arch = "wasm32"))]
+fn analyze_soundtrack(
+    matches: &clap::ArgMatches,
+    input: &std::path::Path,
+    mode: AudioMode,
+    fps: f32,
+    max_frames: usize,
+    consent: DownloadConsent,
+) -> Result<Soundtrack, String> {
+    let backend = matches.value_of("backend").unwrap_or("auto").parse::<AudioBackend>()?;
+    let opts = AudioOptions { fps, max_frames, ..audio_options(matches)? };
+    audio_cost::check(mode, &opts)?;
+    let track = match matches.value_of("audiotrack") {
+        Some(s) => s
+            .parse::<usize>()
+            .map_err(|e| format!("--audio-track must be a non-negative integer: {e}"))?,
+        None => 0,
+    };
+    info!("Opening audio {}", input.display());
+    let source = open_audio_track(input, backend, consent, track)?;
+    let mut progress = progress_cli::CliProgress::new();
+    let soundtrack = match mode {
+        AudioMode::Bank => Soundtrack::Bank(analyze(source.as_ref(), &opts, &mut progress)?),
+        AudioMode::Voice => {
+            Soundtrack::Voice(analyze_voices(source.as_ref(), &opts, &mut progress)?)
+        }
+    };
+    let cost = audio_cost::estimate(mode, soundtrack.frame_count(), &opts)?;
+    info!(
+        "Soundtrack: {} frame(s) at {fps} fps, {} speaker(s) ({}), {} gates on their own chip",
+        soundtrack.frame_count(),
+        cost.speakers,
+        mode.name(),
+        cost.gates,
+    );
+    Ok(soundtrack)
+}
+
 /// Every `--audio-mode` numeric flag, or its default. Returns `Result` so one
 /// `?` per flag reports the first bad value. Range/consistency checks live in
 /// the modules that own them (`BandPlan`, `analyze`) rather than duplicated
@@ -2169,6 +3989,7 @@ fn audio_options(matches: &clap::ArgMatches) -> Result<AudioOptions, String> {
         // clock exactly as the video path does. Negated and defaulting to
         // looping, same as the video path's `AnimOptions`.
         loop_playback: !matches.is_present("noloop"),
+        playback: playback_options(matches)?,
         // A plain boolean flag, off by default: the beside-the-chip placement
         // is unchanged for every existing render. `build_speaker_world` /
         // `build_voice_world` read this to put the speakers on the chip's inner
@@ -2524,6 +4345,25 @@ mod tests {
         assert!(AnimOptions::default().loop_playback);
     }
 
+    /// A sloped piece stood on a `--wall` faces `Y_Positive`, and the prefab
+    /// bounds must measure it that way rather than as authored.
+    #[test]
+    fn prefab_bounds_measure_rotated_bricks_with_their_rotated_extents() {
+        let wedge = brdb::Brick {
+            asset: brdb::BrickType::Procedural {
+                asset: brdb::assets::bricks::PB_DEFAULT_MICRO_WEDGE,
+                size: brdb::BrickSize::new(1, 2, 3),
+            },
+            position: brdb::Position::new(10, 20, 30),
+            direction: brdb::Direction::YPositive,
+            ..Default::default()
+        };
+        let (min, max) = rotated_bounds(&[wedge]).unwrap();
+        assert_eq!(min, brdb::Position::new(9, 17, 28));
+        assert_eq!(max, brdb::Position::new(11, 23, 32));
+        assert_eq!(rotated_bounds(&[]), None);
+    }
+
     #[test]
     fn parse_arg_returns_the_default_only_when_the_flag_is_absent() {
         let m = args(&[]);
@@ -2553,6 +4393,33 @@ mod tests {
         );
     }
 
+    /// `--ping-pong`, `--loop-range` and `--repeat` reach every options struct
+    /// alike, and the combinations with no single meaning are refused.
+    #[test]
+    fn the_playback_flags_reach_every_branch() {
+        let flags = ["--loop-range", "0:01,2.5", "--repeat", "3"];
+        let want = Playback {
+            order: PlayOrder::Range { start_s: 1.0, end_s: 2.5 },
+            plays: Some(3),
+        };
+        assert_eq!(anim_opts(&flags).expect("valid").playback, want);
+        assert_eq!(audio_options(&args(&flags)).expect("valid").playback, want);
+        assert_eq!(midi_options(&args(&flags)).expect("valid").playback, want);
+        assert_eq!(anim_opts(&["--ping-pong"]).unwrap().playback.order, PlayOrder::PingPong);
+        assert_eq!(anim_opts(&[]).unwrap().playback, Playback::default());
+
+        for (bad, why) in [
+            (&["--ping-pong", "--loop-range", "1,2"][..], "cannot take --loop-range"),
+            (&["--loop-range", "1"], "is not START,END"),
+            (&["--loop-range", "3,2"], "end after it starts"),
+            (&["--repeat", "0"], "at least 1"),
+            (&["--repeat", "2", "--no-loop"], "--repeat 1 plays once"),
+        ] {
+            let err = playback_options(&args(bad)).expect_err("must be refused");
+            assert!(err.contains(why), "{bad:?}: {err}");
+        }
+    }
+
     /// The display flags this builder owns must each reach their own field --
     /// and `--pixel-extent` must be a CLI error rather than the panic the
     /// image path's copy produced.
@@ -2583,6 +4450,28 @@ mod tests {
         assert!(err.contains("brick style"), "{err}");
     }
 
+    #[test]
+    fn panels_default_to_one_chip_and_a_bad_grid_is_refused() {
+        assert_eq!(anim_opts(&[]).expect("valid").panels, PanelGrid::default());
+        let o = anim_opts(&["--panels", "3x2"]).expect("valid");
+        assert_eq!(o.panels, PanelGrid { cols: 3, rows: 2 });
+        for bad in ["0x2", "3", "axb"] {
+            let err = anim_opts(&["--panels", bad]).err().expect("a bad grid must be refused");
+            assert!(err.contains("--panels"), "{bad}: {err}");
+        }
+    }
+
+    #[test]
+    fn screen_orientation_defaults_to_the_floor_and_names_its_spellings() {
+        assert_eq!(anim_opts(&[]).expect("valid").orientation, ScreenOrientation::Floor);
+        let o = anim_opts(&["--screen-orientation", "wall-y"]).expect("valid");
+        assert_eq!(o.orientation, ScreenOrientation::WallYNegative);
+        let err = anim_opts(&["--screen-orientation", "ceiling"])
+            .err()
+            .expect("an unknown orientation must be refused");
+        assert!(err.contains(ScreenOrientation::NAMES), "{err}");
+    }
+
     /// `--width 0` is refused rather than rendered as a screen with no pixels
     /// on it, and the source's own size stands when neither flag is passed.
     #[test]
@@ -2625,4 +4514,20 @@ mod tests {
         assert_eq!(o.fill_char, '#');
         assert_eq!(o.empty_char, '▒');
     }
+
+    /// `--font` finds a `--font-file` preset by name, and an unknown name
+    /// lists the loaded ones alongside the built-ins.
+    #[test]
+    fn font_file_presets_are_selectable_by_name() {
+        let path = std::env::temp_dir().join(format!("h2b_fonts_{}.toml", std::process::id()));
+        std::fs::write(&path, "[[font]]\nname = \"Noto Mono\"\nasset = \"NotoSansMono\"\n")
+            .unwrap();
+        let file = path.to_str().unwrap();
+        let o = text_options(&args(&["--font-file", file, "--font", "Noto Mono"])).expect("valid");
+        assert_eq!(o.font, "NotoSansMono");
+        let err = text_options(&args(&["--font-file", file, "--font", "noto"]))
+            .expect_err("no such preset");
+        assert!(err.contains("Noto Mono") && err.contains("orbitron"), "{err}");
+        std::fs::remove_file(&path).ok();
+    }
 }
@@ -16,6 +16,15 @@ pub trait Colormap {
     fn size(&self) -> (u32, u32);
 }
 
+/// One span of a layered heightmap: each column is solid from `bottom` up to
+/// `top`, and empty wherever `top` is not above `bottom`. Several of them
+/// describe what one surface per column cannot -- arches, cave ceilings,
+/// floating islands.
+pub struct HeightmapLayer {
+    pub bottom: Box<dyn Heightmap>,
+    pub top: Box<dyn Heightmap>,
+}
+
 // PNG based heightmaps
 pub struct HeightmapPNG {
     maps: Vec<RgbaImage>,
@@ -100,6 +109,62 @@ impl HeightmapFlat {
     }
 }
 
+/// A relief carved from a picture's brightness: the mode between a flat
+/// `--img` and a true heightmap. Bright pixels stand proud and dark ones sink
+/// (the other way round when inverted), so a portrait becomes a sculpted
+/// floor coloured by the same picture. A depth map (MiDaS and the like, near
+/// = bright) works the same way in place of the picture's own luminance.
+pub struct HeightmapRelief {
+    width: u32,
+    height: u32,
+    levels: Vec<u32>,
+}
+
+impl Heightmap for HeightmapRelief {
+    fn at(&self, x: u32, y: u32) -> u32 {
+        self.levels[(x + y * self.width) as usize]
+    }
+
+    fn size(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+}
+
+impl HeightmapRelief {
+    /// Deepest relief carved, in height levels. Far past anything a picture's
+    /// 256 shades can fill, and low enough that `depth + 1` never wraps.
+    pub const MAX_DEPTH: u32 = 65_535;
+
+    /// Heights from `source`'s Rec. 709 luma, scaled to `1..=depth + 1`.
+    /// Never below 1: like `HeightmapFlat`, the darkest pixel is still a
+    /// brick, so the relief has no holes in it.
+    pub fn from_image(source: &RgbaImage, depth: u32, invert: bool) -> Result<Self, String> {
+        if depth == 0 {
+            return Err("a relief needs a depth of at least 1 height level".to_string());
+        }
+        if depth > Self::MAX_DEPTH {
+            return Err(format!(
+                "a relief is at most {} height levels deep, got {depth}",
+                Self::MAX_DEPTH
+            ));
+        }
+        let levels = source
+            .pixels()
+            .map(|p| {
+                let [r, g, b, _] = p.0;
+                let luma = 0.2126 * r as f64 + 0.7152 * g as f64 + 0.0722 * b as f64;
+                let level = (luma / 255.0 * depth as f64).round() as u32;
+                1 + if invert { depth - level } else { level }
+            })
+            .collect();
+        Ok(HeightmapRelief {
+            width: source.width(),
+            height: source.height(),
+            levels,
+        })
+    }
+}
+
 // PNG based colormap
 pub struct ColormapPNG {
     source: RgbaImage,
@@ -179,4 +244,22 @@ mod tests {
             "the colormap must not use the linear transfer, which gives {converted:?}"
         );
     }
+
+    #[test]
+    fn a_relief_raises_bright_pixels_and_inverts_on_request() {
+        let mut img = RgbaImage::new(3, 1);
+        img.put_pixel(0, 0, image::Rgba([0, 0, 0, 255]));
+        img.put_pixel(1, 0, image::Rgba([255, 255, 255, 255]));
+        // Luma weighs green heavily: 0.7152 of the full depth rounds to 7.
+        img.put_pixel(2, 0, image::Rgba([0, 255, 0, 255]));
+        let relief = HeightmapRelief::from_image(&img, 10, false).unwrap();
+        assert_eq!(relief.at(0, 0), 1, "black is the floor, still one level");
+        assert_eq!(relief.at(1, 0), 11);
+        assert_eq!(relief.at(2, 0), 8);
+
+        let inverted = HeightmapRelief::from_image(&img, 10, true).unwrap();
+        assert_eq!(inverted.at(0, 0), 11);
+        assert_eq!(inverted.at(1, 0), 1);
+        assert!(HeightmapRelief::from_image(&img, 0, false).is_err());
+    }
 }
@@ -0,0 +1,702 @@
+//! Triangle meshes to voxels: OBJ (with its MTL colours and textures) and
+//! STL readers, and a voxelizer that fills a [`VoxelGrid`] from them.
+//!
+//! The grid then goes through the same greedy mesher as a `.vox` model
+//! (`opt::gen_voxel_bricks`), so a mesh is only ever a second way of MAKING
+//! a voxel grid -- nothing past this module knows where the voxels came from.
+//!
+//! Like the `.vox` reader, both parsers are hand-rolled. The subset a brick
+//! build can use is small: positions, faces, and where each face's colour
+//! comes from.
+
+use crate::voxel::VoxelGrid;
+use image::RgbaImage;
+use log::warn;
+use std::{
+    collections::VecDeque,
+    path::{Path, PathBuf},
+};
+
+/// The colour of a face with nothing else to go on: no material, no texture,
+/// no vertex colours. A light grey reads as "unpainted" rather than as a
+/// colour someone chose.
+pub const DEFAULT_MESH_COLOR: [u8; 4] = [204, 204, 204, 255];
+
+/// Which axis of the FILE points up. The voxelizer turns it into Brickadia's
+/// z-up, left-handed frame.
+///
+/// Each format has a habit rather than a rule: Blender exports OBJ as y-up,
+/// and STL comes out of CAD and slicer tools as z-up. Those are the defaults,
+/// and `--up` overrides them for the file that does otherwise.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum UpAxis {
+    Y,
+    Z,
+}
+
+impl UpAxis {
+    /// Move a point from the file's right-handed frame into Brickadia's.
+    ///
+    /// A y-up file's depth axis becomes Brickadia's `y`, and a z-up file has
+    /// its `y` negated; either way the handedness flips exactly once, so the
+    /// model is not built as its own mirror image.
+    pub fn to_brick(self, p: [f32; 3]) -> [f32; 3] {
+        match self {
+            UpAxis::Y => [p[0], p[2], p[1]],
+            UpAxis::Z => [p[0], -p[1], p[2]],
+        }
+    }
+}
+
+/// What the voxelizer fills.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum MeshFill {
+    /// The surface AND everything it encloses. The greedy mesher merges the
+    /// inside into a few large boxes, so a solid prop usually costs FEWER
+    /// bricks than its shell.
+    #[default]
+    Solid,
+    /// The surface only, one voxel thick. For a mesh that is not closed
+    /// (a terrain sheet, a single wall), where "inside" has no meaning, or
+    /// for a hollow build.
+    Shell,
+}
+
+/// A surface colour from an MTL file.
+#[derive(Clone, Debug)]
+pub struct Material {
+    pub name: String,
+    /// `Kd`, with `d` as alpha.
+    pub color: [u8; 4],
+    /// `map_Kd`, sampled by the face's texture coordinates.
+    pub texture: Option<RgbaImage>,
+}
+
+/// One triangle and everything its colour may come from.
+#[derive(Clone, Debug)]
+pub struct Triangle {
+    pub positions: [[f32; 3]; 3],
+    pub uvs: Option<[[f32; 2]; 3]>,
+    /// Per-vertex colours, the `v x y z r g b` extension several exporters
+    /// write.
+    pub colors: Option<[[f32; 3]; 3]>,
+    pub material: Option<usize>,
+}
+
+/// A triangle soup with its materials.
+#[derive(Clone, Debug)]
+pub struct Mesh {
+    pub triangles: Vec<Triangle>,
+    pub materials: Vec<Material>,
+    /// The axis the file treats as up. Set from the format by the readers.
+    pub up: UpAxis,
+}
+
+/// How finely to voxelize, and what to fill.
+#[derive(Clone, Copy, Debug)]
+pub struct VoxelizeOptions {
+    /// Voxels along the mesh's LONGEST axis. The other two follow from the
+    /// mesh's proportions, so voxels stay cubes.
+    pub resolution: u32,
+    pub fill: MeshFill,
+}
+
+impl Default for VoxelizeOptions {
+    fn default() -> Self {
+        VoxelizeOptions {
+            resolution: 64,
+            fill: MeshFill::Solid,
+        }
+    }
+}
+
+impl Mesh {
+    /// Read an `.obj` or `.stl` file, chosen by extension.
+    pub fn open(file: impl AsRef<Path>) -> Result<Self, String> {
+        let file = file.as_ref();
+        let ext = file
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_lowercase());
+        match ext.as_deref() {
+            Some("obj") => read_obj(file),
+            Some("stl") => {
+                let bytes = std::fs::read(file)
+                    .map_err(|e| format!("Could not read mesh {}: {e}", file.display()))?;
+                parse_stl(&bytes).map_err(|e| format!("{}: {e}", file.display()))
+            }
+            Some(ext) => Err(format!(
+                "Unsupported mesh format '{ext}' (expected .obj or .stl)"
+            )),
+            None => Err(format!("Missing mesh format for '{}'", file.display())),
+        }
+    }
+}
+
+/// Read an OBJ file, with the MTL files and textures it names resolved
+/// relative to it.
+///
+/// A missing MTL or texture is warned about and the faces fall back to their
+/// next colour source: Blender writes an `mtllib` line even when the
+/// materials were never exported, and refusing the whole mesh over its paint
+/// would be the wrong trade.
+pub fn read_obj(file: &Path) -> Result<Mesh, String> {
+    let text = std::fs::read_to_string(file)
+        .map_err(|e| format!("Could not read mesh {}: {e}", file.display()))?;
+    let dir = file.parent().map(Path::to_path_buf).unwrap_or_default();
+    parse_obj(&text, |name| {
+        let mtl_path = dir.join(name);
+        let mtl = match std::fs::read_to_string(&mtl_path) {
+            Ok(t) => t,
+            Err(e) => {
+                warn!(
+                    "material library {} could not be read ({e}); its faces use the default colour",
+                    mtl_path.display()
+                );
+                return Ok(vec![]);
+            }
+        };
+        let mtl_dir = mtl_path.parent().map(Path::to_path_buf).unwrap_or_default();
+        parse_mtl(&mtl, |tex| {
+            let tex_path: PathBuf = mtl_dir.join(tex);
+            image::open(&tex_path)
+                .map(|i| i.to_rgba8())
+                .map_err(|e| format!("texture {} could not be read ({e})", tex_path.display()))
+        })
+    })
+    .map_err(|e| format!("{}: {e}", file.display()))
+}
+
+/// Parse an MTL file. `load_texture` resolves a `map_Kd` name to an image;
+/// one that fails is warned about and the material keeps its `Kd`.
+pub fn parse_mtl(
+    text: &str,
+    mut load_texture: impl FnMut(&str) -> Result<RgbaImage, String>,
+) -> Result<Vec<Material>, String> {
+    let mut materials: Vec<Material> = vec![];
+    for (n, line) in text.lines().enumerate() {
+        let line = line.trim();
+        let mut parts = line.split_whitespace();
+        let Some(key) = parts.next() else { continue };
+        let rest: Vec<&str> = parts.collect();
+        let current = || format!("line {}: '{key}' before any 'newmtl'", n + 1);
+        match key {
+            "newmtl" => materials.push(Material {
+                name: rest.join(" "),
+                color: [255, 255, 255, 255],
+                texture: None,
+            }),
+            "Kd" => {
+                let m = materials.last_mut().ok_or_else(current)?;
+                let rgb = floats::<3>(&rest, n, "Kd")?;
+                for (c, v) in m.color.iter_mut().zip(rgb) {
+                    *c = (v.clamp(0.0, 1.0) * 255.0).round() as u8;
+                }
+            }
+            "d" | "Tr" => {
+                let m = materials.last_mut().ok_or_else(current)?;
+                let [v] = floats::<1>(&rest, n, key)?;
+                // `Tr` is transparency, `d` ("dissolve") is opacity.
+                let opacity = if key == "Tr" { 1.0 - v } else { v };
+                m.color[3] = (opacity.clamp(0.0, 1.0) * 255.0).round() as u8;
+            }
+            "map_Kd" => {
+                let m = materials.last_mut().ok_or_else(current)?;
+                // Options (`-s 1 1 1`, ...) come before the file name, which
+                // is the last token.
+                let Some(name) = rest.last() else { continue };
+                match load_texture(name) {
+                    Ok(t) => m.texture = Some(t),
+                    Err(e) => warn!("{e}; material '{}' uses its Kd colour", m.name),
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(materials)
+}
+
+fn floats<const N: usize>(parts: &[&str], line: usize, what: &str) -> Result<[f32; N], String> {
+    let mut out = [0.0; N];
+    if parts.len() < N {
+        return Err(format!(
+            "line {}: '{what}' needs {N} number(s), got {}",
+            line + 1,
+            parts.len()
+        ));
+    }
+    for (o, p) in out.iter_mut().zip(parts) {
+        *o = p
+            .parse()
+            .map_err(|_| format!("line {}: '{what}' has a bad number '{p}'", line + 1))?;
+    }
+    Ok(out)
+}
+
+/// Resolve an OBJ index (1-based, or negative counting back from the end)
+/// into a 0-based one.
+fn obj_index(token: &str, len: usize, line: usize) -> Result<usize, String> {
+    let i: i64 = token
+        .parse()
+        .map_err(|_| format!("line {}: bad face index '{token}'", line + 1))?;
+    let resolved = if i < 0 { len as i64 + i } else { i - 1 };
+    if resolved < 0 || resolved >= len as i64 {
+        return Err(format!(
+            "line {}: face index {i} is out of range ({len} defined so far)",
+            line + 1
+        ));
+    }
+    Ok(resolved as usize)
+}
+
+/// Parse OBJ text. `load_mtl` resolves an `mtllib` name to its materials.
+///
+/// Polygons are fanned into triangles from their first vertex, which is
+/// exact for the convex faces exporters write.
+pub fn parse_obj(
+    text: &str,
+    mut load_mtl: impl FnMut(&str) -> Result<Vec<Material>, String>,
+) -> Result<Mesh, String> {
+    let mut positions: Vec<[f32; 3]> = vec![];
+    let mut colors: Vec<Option<[f32; 3]>> = vec![];
+    let mut uvs: Vec<[f32; 2]> = vec![];
+    let mut materials: Vec<Material> = vec![];
+    let mut current: Option<usize> = None;
+    let mut triangles = vec![];
+
+    for (n, line) in text.lines().enumerate() {
+        let line = line.trim();
+        let mut parts = line.split_whitespace();
+        let Some(key) = parts.next() else { continue };
+        let rest: Vec<&str> = parts.collect();
+        match key {
+            "v" => {
+                positions.push(floats::<3>(&rest, n, "v")?);
+                colors.push(if rest.len() >= 6 {
+                    Some(floats::<3>(&rest[3..], n, "v")?)
+                } else {
+                    None
+                });
+            }
+            "vt" => {
+                let [u, v] = floats::<2>(&rest, n, "vt")?;
+                uvs.push([u, v]);
+            }
+            "mtllib" => {
+                for name in rest {
+                    materials.extend(load_mtl(name)?);
+                }
+            }
+            "usemtl" => {
+                let name = rest.join(" ");
+                current = materials.iter().position(|m| m.name == name);
+                if current.is_none() {
+                    warn!("line {}: material '{name}' is not defined; using the default colour", n + 1);
+                }
+            }
+            "f" => {
+                let mut corners = vec![];
+                for token in &rest {
+                    let mut refs = token.split('/');
+                    let v = obj_index(refs.next().unwrap_or(""), positions.len(), n)?;
+                    let vt = match refs.next() {
+                        Some(t) if !t.is_empty() => Some(obj_index(t, uvs.len(), n)?),
+                        _ => None,
+                    };
+                    corners.push((v, vt));
+                }
+                if corners.len() < 3 {
+                    return Err(format!(
+                        "line {}: a face needs at least 3 vertices, got {}",
+                        n + 1,
+                        corners.len()
+                    ));
+                }
+                for i in 1..corners.len() - 1 {
+                    let tri = [corners[0], corners[i], corners[i + 1]];
+                    let has_uv = tri.iter().all(|c| c.1.is_some());
+                    let has_color = tri.iter().all(|c| colors[c.0].is_some());
+                    triangles.push(Triangle {
+                        positions: tri.map(|c| positions[c.0]),
+                        uvs: has_uv.then(|| tri.map(|c| uvs[c.1.unwrap()])),
+                        colors: has_color.then(|| tri.map(|c| colors[c.0].unwrap())),
+                        material: current,
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if triangles.is_empty() {
+        return Err("the file has no faces in it".to_string());
+    }
+    Ok(Mesh {
+        triangles,
+        materials,
+        up: UpAxis::Y,
+    })
+}
+
+/// Parse an STL file, binary or ASCII.
+///
+/// Binary is recognised by its size -- an 80-byte header, a count, and 50
+/// bytes per facet -- rather than by the header text, because plenty of
+/// binary writers start the header with "solid" too.
+pub fn parse_stl(bytes: &[u8]) -> Result<Mesh, String> {
+    let binary_count = (bytes.len() >= 84)
+        .then(|| u32::from_le_bytes([bytes[80], bytes[81], bytes[82], bytes[83]]) as usize)
+        .filter(|&n| n.checked_mul(50).and_then(|b| b.checked_add(84)) == Some(bytes.len()));
+
+    let mut triangles = vec![];
+    if let Some(count) = binary_count {
+        for facet in bytes[84..].chunks_exact(50).take(count) {
+            let f = |o: usize| {
+                f32::from_le_bytes([facet[o], facet[o + 1], facet[o + 2], facet[o + 3]])
+            };
+            triangles.push(Triangle {
+                positions: [0, 1, 2].map(|v| {
+                    let o = 12 + v * 12;
+                    [f(o), f(o + 4), f(o + 8)]
+                }),
+                uvs: None,
+                colors: None,
+                material: None,
+            });
+        }
+    } else if bytes.starts_with(b"solid") {
+        let text = String::from_utf8_lossy(bytes);
+        let mut corners = vec![];
+        for (n, line) in text.lines().enumerate() {
+            let mut parts = line.split_whitespace();
+            if parts.next() != Some("vertex") {
+                continue;
+            }
+            let rest: Vec<&str> = parts.collect();
+            corners.push(floats::<3>(&rest, n, "vertex")?);
+            if corners.len() == 3 {
+                triangles.push(Triangle {
+                    positions: [corners[0], corners[1], corners[2]],
+                    uvs: None,
+                    colors: None,
+                    material: None,
+                });
+                corners.clear();
+            }
+        }
+    } else {
+        return Err(
+            "not an STL file (neither an ASCII 'solid' nor a binary file of the size its \
+             facet count gives)"
+                .to_string(),
+        );
+    }
+
+    if triangles.is_empty() {
+        return Err("the file has no facets in it".to_string());
+    }
+    Ok(Mesh {
+        triangles,
+        materials: vec![],
+        up: UpAxis::Z,
+    })
+}
+
+impl Triangle {
+    /// The colour at barycentric weights `w`: the material's texture if it has
+    /// one and the face has coordinates, then vertex colours, then the
+    /// material's `Kd`, then [`DEFAULT_MESH_COLOR`].
+    fn color_at(&self, w: [f32; 3], materials: &[Material]) -> [u8; 4] {
+        let material = self.material.and_then(|m| materials.get(m));
+        if let (Some(tex), Some(uv)) = (material.and_then(|m| m.texture.as_ref()), self.uvs) {
+            let u = w[0] * uv[0][0] + w[1] * uv[1][0] + w[2] * uv[2][0];
+            let v = w[0] * uv[0][1] + w[1] * uv[1][1] + w[2] * uv[2][1];
+            // Texture coordinates repeat, and v runs UP the image.
+            let x = (u.rem_euclid(1.0) * tex.width() as f32) as u32;
+            let y = ((1.0 - v.rem_euclid(1.0)) * tex.height() as f32) as u32;
+            return tex
+                .get_pixel(x.min(tex.width() - 1), y.min(tex.height() - 1))
+                .0;
+        }
+        if let Some(c) = self.colors {
+            let [r, g, b] = [0, 1, 2].map(|ch| {
+                let v = w[0] * c[0][ch] + w[1] * c[1][ch] + w[2] * c[2][ch];
+                (v.clamp(0.0, 1.0) * 255.0).round() as u8
+            });
+            return [r, g, b, 255];
+        }
+        material.map(|m| m.color).unwrap_or(DEFAULT_MESH_COLOR)
+    }
+}
+
+/// Voxelize a mesh into a grid `options.resolution` voxels along its longest
+/// axis.
+///
+/// The SURFACE is found by sampling every triangle at under half a voxel's
+/// spacing, so no voxel a face crosses is stepped over, and each voxel takes
+/// the colour of the first sample to land in it. The INSIDE, for
+/// [`MeshFill::Solid`], is every empty voxel a flood fill from the grid's
+/// border cannot reach; it takes the colour of the surface voxel below it.
+/// A mesh with a hole in it therefore comes out hollow rather than wrong --
+/// the flood leaks in through the hole, exactly as water would.
+pub fn voxelize(mesh: &Mesh, options: &VoxelizeOptions) -> Result<VoxelGrid, String> {
+    if options.resolution == 0 {
+        return Err("the voxel resolution must be at least 1".to_string());
+    }
+    let triangles: Vec<[[f32; 3]; 3]> = mesh
+        .triangles
+        .iter()
+        .map(|t| t.positions.map(|p| mesh.up.to_brick(p)))
+        .collect();
+
+    let mut min = [f32::INFINITY; 3];
+    let mut max = [f32::NEG_INFINITY; 3];
+    for p in triangles.iter().flatten() {
+        for a in 0..3 {
+            if !p[a].is_finite() {
+                return Err("the mesh has a vertex that is not a finite number".to_string());
+            }
+            min[a] = min[a].min(p[a]);
+            max[a] = max[a].max(p[a]);
+        }
+    }
+    let longest = (0..3).map(|a| max[a] - min[a]).fold(0.0, f32::max);
+    if longest <= 0.0 {
+        return Err("the mesh has no extent: every vertex is at the same point".to_string());
+    }
+    let voxel = longest / options.resolution as f32;
+    let dims = [0, 1, 2].map(|a| (((max[a] - min[a]) / voxel).ceil() as u32).max(1));
+    let mut grid = VoxelGrid::new((dims[0], dims[1], dims[2]))?;
+
+    let cell = |p: [f32; 3]| {
+        [0, 1, 2].map(|a| (((p[a] - min[a]) / voxel).floor().max(0.0) as u32).min(dims[a] - 1))
+    };
+
+    for (tri, source) in triangles.iter().zip(&mesh.triangles) {
+        let edge = |a: usize, b: usize| {
+            (0..3)
+                .map(|k| (tri[a][k] - tri[b][k]).powi(2))
+                .sum::<f32>()
+                .sqrt()
+        };
+        let longest_edge = edge(0, 1).max(edge(1, 2)).max(edge(2, 0));
+        let steps = ((longest_edge / (voxel * 0.5)).ceil() as u32).max(1);
+        for i in 0..=steps {
+            for j in 0..=steps - i {
+                let w = [
+                    i as f32 / steps as f32,
+                    j as f32 / steps as f32,
+                    (steps - i - j) as f32 / steps as f32,
+                ];
+                let p = [0, 1, 2].map(|k| w[0] * tri[0][k] + w[1] * tri[1][k] + w[2] * tri[2][k]);
+                let [x, y, z] = cell(p);
+                if grid.entry(x, y, z) == 0 {
+                    grid.set(x, y, z, source.color_at(w, &mesh.materials));
+                }
+            }
+        }
+    }
+
+    if options.fill == MeshFill::Solid {
+        fill_interior(&mut grid);
+    }
+    Ok(grid)
+}
+
+/// Fill every empty cell that is enclosed by filled ones. See [`voxelize`].
+fn fill_interior(grid: &mut VoxelGrid) {
+    let (w, h, d) = grid.size();
+    let index = |x: u32, y: u32, z: u32| (x + y * w) as usize + z as usize * (w * h) as usize;
+    let mut outside = vec![false; (w * h) as usize * d as usize];
+    let mut queue = VecDeque::new();
+
+    for z in 0..d {
+        for y in 0..h {
+            for x in 0..w {
+                let border = x == 0 || y == 0 || z == 0 || x == w - 1 || y == h - 1 || z == d - 1;
+                if border && grid.entry(x, y, z) == 0 {
+                    outside[index(x, y, z)] = true;
+                    queue.push_back((x, y, z));
+                }
+            }
+        }
+    }
+    while let Some((x, y, z)) = queue.pop_front() {
+        let neighbours = [
+            (x.wrapping_sub(1), y, z),
+            (x + 1, y, z),
+            (x, y.wrapping_sub(1), z),
+            (x, y + 1, z),
+            (x, y, z.wrapping_sub(1)),
+            (x, y, z + 1),
+        ];
+        for (nx, ny, nz) in neighbours {
+            if nx >= w || ny >= h || nz >= d {
+                continue;
+            }
+            let i = index(nx, ny, nz);
+            if !outside[i] && grid.entry(nx, ny, nz) == 0 {
+                outside[i] = true;
+                queue.push_back((nx, ny, nz));
+            }
+        }
+    }
+
+    for y in 0..h {
+        for x in 0..w {
+            let mut below = None;
+            for z in 0..d {
+                match grid.get(x, y, z) {
+                    Some(c) => below = Some(c),
+                    None if !outside[index(x, y, z)] => {
+                        grid.set(x, y, z, below.unwrap_or(DEFAULT_MESH_COLOR));
+                    }
+                    None => {}
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A unit cube, y-up, one quad per side.
+    const CUBE: &str = "\
+v 0 0 0
+v 1 0 0
+v 1 1 0
+v 0 1 0
+v 0 0 1
+v 1 0 1
+v 1 1 1
+v 0 1 1
+f 1 4 3 2
+f 5 6 7 8
+f 1 2 6 5
+f 2 3 7 6
+f 3 4 8 7
+f 4 1 5 8
+";
+
+    fn no_mtl(_: &str) -> Result<Vec<Material>, String> {
+        Ok(vec![])
+    }
+
+    fn cube(fill: MeshFill) -> VoxelGrid {
+        let mesh = parse_obj(CUBE, no_mtl).unwrap();
+        voxelize(&mesh, &VoxelizeOptions { resolution: 4, fill }).unwrap()
+    }
+
+    #[test]
+    fn quads_fan_into_two_triangles_each() {
+        let mesh = parse_obj(CUBE, no_mtl).unwrap();
+        assert_eq!(mesh.triangles.len(), 12);
+        assert_eq!(mesh.up, UpAxis::Y);
+    }
+
+    #[test]
+    fn a_closed_cube_fills_solid_and_shells_hollow() {
+        let solid = cube(MeshFill::Solid);
+        assert_eq!(solid.size(), (4, 4, 4));
+        assert_eq!(solid.count(), 64);
+
+        let shell = cube(MeshFill::Shell);
+        assert_eq!(shell.count(), 64 - 8, "the 2x2x2 inside stays empty");
+        assert_eq!(shell.get(1, 1, 1), None);
+        assert_eq!(shell.get(0, 0, 0), Some(DEFAULT_MESH_COLOR));
+    }
+
+    #[test]
+    fn resolution_sets_the_longest_axis_and_keeps_proportions() {
+        let obj = "v 0 0 0\nv 4 0 0\nv 0 2 0\nv 0 0 1\nf 1 2 3\nf 1 2 4\n";
+        let mesh = parse_obj(obj, no_mtl).unwrap();
+        let grid = voxelize(
+            &mesh,
+            &VoxelizeOptions {
+                resolution: 8,
+                fill: MeshFill::Shell,
+            },
+        )
+        .unwrap();
+        // y-up: the file's y (2 long) is Brickadia's z, its z (1) is y.
+        assert_eq!(grid.size(), (8, 2, 4));
+    }
+
+    #[test]
+    fn material_colours_and_negative_indices_are_read() {
+        let obj = "mtllib a.mtl\nv 0 0 0\nv 1 0 0\nv 0 1 0\nusemtl red\nf -3 -2 -1\n";
+        let mesh = parse_obj(obj, |name| {
+            assert_eq!(name, "a.mtl");
+            parse_mtl("newmtl red\nKd 1 0 0\nd 0.5\n", |_| unreachable!())
+        })
+        .unwrap();
+        assert_eq!(mesh.materials[0].color, [255, 0, 0, 128]);
+        let grid = voxelize(&mesh, &VoxelizeOptions::default()).unwrap();
+        assert!(grid.palette[1..].iter().all(|&c| c == [255, 0, 0, 128]));
+    }
+
+    #[test]
+    fn textures_are_sampled_by_uv() {
+        // Left half red, right half blue; the triangle sits in the right half.
+        let tex = RgbaImage::from_fn(2, 1, |x, _| {
+            image::Rgba(if x == 0 { [255, 0, 0, 255] } else { [0, 0, 255, 255] })
+        });
+        let obj = "mtllib t.mtl\nv 0 0 0\nv 1 0 0\nv 0 1 0\nvt 0.6 0.5\nvt 0.9 0.5\nvt 0.6 0.9\n\
+                   usemtl tex\nf 1/1 2/2 3/3\n";
+        let mesh = parse_obj(obj, |_| {
+            parse_mtl("newmtl tex\nKd 1 1 1\nmap_Kd -s 1 1 1 t.png\n", |name| {
+                assert_eq!(name, "t.png", "options before the name are skipped");
+                Ok(tex.clone())
+            })
+        })
+        .unwrap();
+        let grid = voxelize(&mesh, &VoxelizeOptions::default()).unwrap();
+        assert_eq!(&grid.palette[1..], &[[0, 0, 255, 255]]);
+    }
+
+    #[test]
+    fn vertex_colours_are_interpolated() {
+        let obj = "v 0 0 0 0 1 0\nv 1 0 0 0 1 0\nv 0 1 0 0 1 0\nf 1 2 3\n";
+        let mesh = parse_obj(obj, no_mtl).unwrap();
+        let grid = voxelize(&mesh, &VoxelizeOptions::default()).unwrap();
+        assert_eq!(&grid.palette[1..], &[[0, 255, 0, 255]]);
+    }
+
+    #[test]
+    fn binary_and_ascii_stl_read_the_same_triangle() {
+        let ascii = b"solid t\nfacet normal 0 0 1\nouter loop\nvertex 0 0 0\nvertex 1 0 0\n\
+                      vertex 0 1 0\nendloop\nendfacet\nendsolid t\n";
+        let mut binary = vec![0u8; 80];
+        binary.extend(1u32.to_le_bytes());
+        for v in [0f32, 0., 1., 0., 0., 0., 1., 0., 0., 0., 1., 0.] {
+            binary.extend(v.to_le_bytes());
+        }
+        binary.extend([0, 0]);
+
+        let a = parse_stl(ascii).unwrap();
+        let b = parse_stl(&binary).unwrap();
+        assert_eq!(a.up, UpAxis::Z);
+        assert_eq!(a.triangles[0].positions, b.triangles[0].positions);
+    }
+
+    #[test]
+    fn broken_meshes_are_refused_by_name() {
+        assert!(parse_stl(b"garbage").unwrap_err().contains("not an STL"));
+        assert!(parse_obj("v 0 0 0\n", no_mtl).unwrap_err().contains("no faces"));
+        assert!(
+            parse_obj("v 0 0 0\nf 1 2 3\n", no_mtl)
+                .unwrap_err()
+                .contains("out of range")
+        );
+        let flat = parse_obj("v 0 0 0\nv 0 0 0\nv 0 0 0\nf 1 2 3\n", no_mtl).unwrap();
+        assert!(voxelize(&flat, &VoxelizeOptions::default()).unwrap_err().contains("extent"));
+        let mesh = parse_obj(CUBE, no_mtl).unwrap();
+        let zero = VoxelizeOptions {
+            resolution: 0,
+            fill: MeshFill::Solid,
+        };
+        assert!(voxelize(&mesh, &zero).is_err());
+    }
+}
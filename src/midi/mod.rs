@@ -19,14 +19,16 @@
 //!
 //! Playback is EVENT-BASED, not frame-based: each speaker stores its notes as
 //! `(start, end, pitch, volume)` spans, and an in-chip circuit steps a runtime
-//! "playhead" index through them by comparing the clock's `Time` against the
-//! note ends (see `crate::audio::speakers::build_midi_event_world`). That is
+//! "playhead" index through them by comparing the playback time, the clock's
+//! phase in seconds of the piece, against the note ends (see
+//! `crate::audio::speakers::build_midi_event_world` and [`playhead`]). That is
 //! tick-accurate and stores note count rather than a per-tick buffer, verified
 //! in game via the playhead probe.
 use crate::audio::track::SynthWave;
 
 pub mod drums;
 pub mod parse;
+pub mod playhead;
 pub mod preview;
 pub mod schedule;
 pub mod timbre;
@@ -158,6 +160,14 @@ pub struct MidiOptions {
     pub control_buttons: bool,
     /// Loop the piece forever (`true`) or play once and stop (`false`).
     pub loop_playback: bool,
+    /// How many times the piece plays before it stops (`None` = as
+    /// `loop_playback` says) and in what order; a loop range is in seconds
+    /// of the piece.
+    pub playback: crate::anim::clock::Playback,
+    /// Seek and skip buttons beside the control buttons, in seconds of the
+    /// piece, with the skips on marks ([`crate::anim::seek::SeekPlan::on_marks`]).
+    /// Empty by default; needs a first-to-last `playback`, and no playlist.
+    pub seek: crate::anim::seek::SeekControls,
     /// Place the speaker cluster inside the microchip's own inner grid.
     pub speakers_in_chip: bool,
     /// Multiplier on each note's velocity-derived volume, clamped to 1.0.
@@ -196,6 +206,8 @@ impl Default for MidiOptions {
             max_distance: DEFAULT_MAX_DISTANCE,
             control_buttons: true,
             loop_playback: true,
+            playback: crate::anim::clock::Playback::default(),
+            seek: crate::anim::seek::SeekControls::default(),
             speakers_in_chip: false,
             gain: 1.0,
             instrument_volumes: Vec::new(),
@@ -0,0 +1,296 @@
+//! Where a voice's notes and a percussion lane's strikes sit along the clock
+//! their playheads follow, under every [`Playback`] order.
+//!
+//! `crate::audio::speakers::build_midi_event_world` reads the piece off a
+//! render clock counting [`TICKS_PER_SECOND`] frames to a second of the
+//! piece: the playback time is the clock's [`phase`](crate::anim::clock::Clock::phase)
+//! over that. Each playhead steps forward through its events and has to be
+//! put back where the time is whenever it steps back, which is what this
+//! module lays out for it:
+//!
+//! * First to last, the events are the piece's own, and a restart or a wrap
+//!   steps back to 0, where a plain reset puts every playhead.
+//! * A ping-pong's phase counts up through the way back too, so the events
+//!   are laid out there and back ([`EventLayout::notes`],
+//!   [`EventLayout::hits`]): the way back plays the piece's mirror image, and
+//!   the one step back is still the wrap to 0.
+//! * A loop range steps back to its start as well, a second
+//!   [landing](crate::anim::landing). Each playhead stores where it stands
+//!   on each landing -- its keys -- and takes that as its index there.
+//! * A seek button jumps to a landing of its own: a chapter start, a bar
+//!   button's frame, or a mark its skips go to ([`EventLayout::with_seeks`]).
+//!
+//! [`play_notes`] and [`play_hits`] are the circuit's step, written out in
+//! Rust for the tests.
+use super::NoteSpan;
+use crate::anim::clock::{PlayOrder, Playback};
+use crate::anim::landing::Landings;
+use crate::anim::seek::SeekPlan;
+
+/// Frames a second of the piece the render clock counts, at a playback rate
+/// of 1. The rate scales the clock's fps, never this.
+pub const TICKS_PER_SECOND: f64 = 60.0;
+
+/// One render's layout of events along its clock. See the module doc.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EventLayout {
+    /// The piece time a ping-pong turns round at, its last frame's; `None`
+    /// under every other order.
+    turn_s: Option<f64>,
+    landings: Landings,
+}
+
+impl EventLayout {
+    /// The layout for a clock built with `playback` at `fps` over
+    /// `frame_count` frames, each [`TICKS_PER_SECOND`]th of a second of the
+    /// piece.
+    pub fn of(playback: &Playback, fps: f32, frame_count: usize) -> EventLayout {
+        let turn_s = (playback.order == PlayOrder::PingPong)
+            .then(|| frame_count.saturating_sub(1) as f64 / TICKS_PER_SECOND);
+        EventLayout { turn_s, landings: Landings::of(playback, fps, frame_count) }
+    }
+
+    /// This layout with every frame `plan`'s buttons jump to as a landing too
+    /// ([`Landings::with_seeks`]). A seek needs a first-to-last order, so the
+    /// events stay as they are.
+    pub fn with_seeks(self, plan: &SeekPlan, frame_count: usize) -> EventLayout {
+        EventLayout { landings: self.landings.with_seeks(plan, frame_count), ..self }
+    }
+
+    pub fn landings(&self) -> &Landings {
+        &self.landings
+    }
+
+    /// A voice's notes as its playhead stores them: as they are, or there
+    /// and back round a ping-pong's turn. Past the turn the clock never
+    /// plays, so a note is cut at it, and a note still sounding there is one
+    /// note with its own mirror image rather than two.
+    pub fn notes(&self, notes: &[NoteSpan]) -> Vec<NoteSpan> {
+        let Some(turn) = self.turn_s else {
+            return notes.to_vec();
+        };
+        let out: Vec<NoteSpan> = notes
+            .iter()
+            .filter(|n| n.start_s <= turn)
+            .map(|n| NoteSpan { end_s: n.end_s.min(turn), ..*n })
+            .collect();
+        let mut back: Vec<NoteSpan> = out
+            .iter()
+            .rev()
+            .map(|n| NoteSpan { start_s: 2.0 * turn - n.end_s, end_s: 2.0 * turn - n.start_s, ..*n })
+            .collect();
+        let mut there = out;
+        if there.last().is_some_and(|n| n.end_s >= turn) {
+            let held = back.remove(0);
+            there.last_mut().expect("checked").end_s = held.end_s;
+        }
+        there.extend(back);
+        there
+    }
+
+    /// A lane's strike times as its playhead stores them: as they are, or
+    /// there and back round a ping-pong's turn, a strike on the turn struck
+    /// once.
+    pub fn hits(&self, hits: &[f64]) -> Vec<f64> {
+        let Some(turn) = self.turn_s else {
+            return hits.to_vec();
+        };
+        let there: Vec<f64> = hits.iter().copied().filter(|&h| h <= turn).collect();
+        let back = there.iter().rev().filter(|&&h| h < turn).map(|&h| 2.0 * turn - h);
+        there.iter().copied().chain(back).collect()
+    }
+
+    /// A voice's keys over its laid-out `events`: on each landing, the first
+    /// note not yet over -- where the playhead stands once it has kept up.
+    pub fn note_keys(&self, events: &[NoteSpan]) -> Vec<i64> {
+        self.landings.keys(|frame| {
+            let t = frame as f64 / TICKS_PER_SECOND;
+            events.partition_point(|n| n.end_s < t) as i64
+        })
+    }
+
+    /// A lane's keys over its laid-out `hits`: on each landing, the first
+    /// strike not yet due before it, so one on the landing itself still
+    /// strikes.
+    pub fn hit_keys(&self, hits: &[f64]) -> Vec<i64> {
+        self.landings.keys(|frame| {
+            let t = frame as f64 / TICKS_PER_SECOND;
+            hits.partition_point(|&h| h < t) as i64
+        })
+    }
+}
+
+/// Whether a playhead resyncs on `phase` after `prev`, and to what slot of
+/// its keys: `Some(None)` for the plain reset to 0.
+fn resync(landings: &Landings, phase: usize, prev: usize) -> Option<Option<usize>> {
+    if landings.is_plain() {
+        (phase < prev).then_some(None)
+    } else {
+        landings.slot_of(phase).map(Some)
+    }
+}
+
+/// The volume a voice's playhead sets each time the phase changes, over a
+/// sequence of phases: `build_midi_event_world`'s step, gate for gate. The
+/// event is read before the index moves, and an index past the end holds the
+/// last event read. `keys` is ignored on a plain set of landings.
+pub fn play_notes(
+    events: &[NoteSpan],
+    keys: &[i64],
+    landings: &Landings,
+    phases: impl IntoIterator<Item = usize>,
+) -> Vec<f64> {
+    let (mut idx, mut prev, mut held) = (0usize, 0usize, events.first().copied());
+    phases
+        .into_iter()
+        .map(|phase| {
+            let t = phase as f64 / TICKS_PER_SECOND;
+            let in_bounds = idx < events.len();
+            if in_bounds {
+                held = Some(events[idx]);
+            }
+            let volume = match held {
+                Some(n) if n.start_s <= t && t <= n.end_s => n.volume,
+                _ => 0.0,
+            };
+            match resync(landings, phase, prev) {
+                Some(slot) => idx = slot.map_or(0, |s| keys[s] as usize),
+                None if in_bounds && held.is_some_and(|n| t > n.end_s) => idx += 1,
+                None => {}
+            }
+            prev = phase;
+            volume
+        })
+        .collect()
+}
+
+/// Whether a lane's playhead strikes each time the phase changes, over a
+/// sequence of phases. A resync never strikes.
+pub fn play_hits(
+    hits: &[f64],
+    keys: &[i64],
+    landings: &Landings,
+    phases: impl IntoIterator<Item = usize>,
+) -> Vec<bool> {
+    let (mut idx, mut prev) = (0usize, 0usize);
+    phases
+        .into_iter()
+        .map(|phase| {
+            let t = phase as f64 / TICKS_PER_SECOND;
+            let struck = match resync(landings, phase, prev) {
+                Some(slot) => {
+                    idx = slot.map_or(0, |s| keys[s] as usize);
+                    false
+                }
+                None if hits.get(idx).is_some_and(|&h| t >= h) => {
+                    idx += 1;
+                    true
+                }
+                None => false,
+            };
+            prev = phase;
+            struck
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn note(start_s: f64, end_s: f64, volume: f64) -> NoteSpan {
+        NoteSpan { start_s, end_s, pitch: 1.0, volume }
+    }
+
+    /// Notes a few ticks apart over a one-second piece: three short ones
+    /// early, so a playhead reset to the first is still catching up when a
+    /// loop range's start plays, and the last held past the final frame. No
+    /// note starts or ends on a frame, so no float rounding decides a compare.
+    fn notes() -> Vec<NoteSpan> {
+        vec![
+            note(0.02, 0.04, 0.1),
+            note(0.06, 0.08, 0.2),
+            note(0.11, 0.26, 0.3),
+            note(0.31, 0.37, 0.4),
+            note(0.41, 0.56, 0.6),
+            note(0.71, 1.0, 0.9),
+        ]
+    }
+
+    /// The volume the piece itself has at `t`.
+    fn volume_at(notes: &[NoteSpan], t: f64) -> f64 {
+        notes.iter().find(|n| n.start_s <= t && t <= n.end_s).map_or(0.0, |n| n.volume)
+    }
+
+    /// What each order's clock steps through, `ticks` frames in, with the
+    /// piece time each phase stands for.
+    fn walk(playback: &Playback, ticks: i64) -> (Vec<usize>, Vec<f64>) {
+        let pass = playback.unfold(TICKS_PER_SECOND as f32, 60);
+        let phases: Vec<usize> = (0..ticks)
+            .map(|t| match playback.order {
+                PlayOrder::PingPong => t as usize % pass.len(),
+                _ => playback.frame_at(t, TICKS_PER_SECOND as f32, 60),
+            })
+            .collect();
+        let times = (0..ticks)
+            .map(|t| playback.frame_at(t, TICKS_PER_SECOND as f32, 60) as f64 / TICKS_PER_SECOND)
+            .collect();
+        (phases, times)
+    }
+
+    #[test]
+    fn every_order_plays_every_note_where_the_piece_has_it() {
+        let range = PlayOrder::Range { start_s: 0.3, end_s: 0.9 };
+        for order in [PlayOrder::Forward, PlayOrder::PingPong, range] {
+            let playback = Playback { order, plays: None };
+            let layout = EventLayout::of(&playback, TICKS_PER_SECOND as f32, 60);
+            let events = layout.notes(&notes());
+            let keys = layout.note_keys(&events);
+            let (phases, times) = walk(&playback, 300);
+            let got = play_notes(&events, &keys, layout.landings(), phases);
+            let want: Vec<f64> = times.iter().map(|&t| volume_at(&notes(), t)).collect();
+            assert_eq!(got, want, "{order:?}");
+        }
+    }
+
+    #[test]
+    fn a_ping_pong_holds_the_note_over_its_turn_as_one() {
+        let playback = Playback { order: PlayOrder::PingPong, plays: None };
+        let layout = EventLayout::of(&playback, TICKS_PER_SECOND as f32, 60);
+        let events = layout.notes(&notes());
+        let turn = 59.0 / TICKS_PER_SECOND;
+        assert_eq!(events.len(), 11, "five there, the held one, five back");
+        assert_eq!((events[5].start_s, events[5].end_s), (0.71, 2.0 * turn - 0.71));
+        assert!(events.windows(2).all(|w| w[0].end_s < w[1].start_s), "{events:?}");
+    }
+
+    /// Strikes land on the tick the piece time passes them, whichever way it
+    /// is going, and never on a resync.
+    #[test]
+    fn every_order_strikes_every_hit_as_the_time_passes_it() {
+        let hits = [0.21, 0.36, 0.51, 0.81];
+        let range = PlayOrder::Range { start_s: 0.3, end_s: 0.9 };
+        for order in [PlayOrder::Forward, PlayOrder::PingPong, range] {
+            let playback = Playback { order, plays: None };
+            let layout = EventLayout::of(&playback, TICKS_PER_SECOND as f32, 60);
+            let laid = layout.hits(&hits);
+            let keys = layout.hit_keys(&laid);
+            let (phases, times) = walk(&playback, 300);
+            let got = play_hits(&laid, &keys, layout.landings(), phases.iter().copied());
+            let mut prev_time = 0.0f64;
+            let want: Vec<bool> = times
+                .iter()
+                .zip(&phases)
+                .map(|(&t, &phase)| {
+                    let (lo, hi) = (prev_time.min(t), prev_time.max(t));
+                    let passed = phase != 0
+                        && !layout.landings().contains(phase)
+                        && hits.iter().any(|&h| if t >= prev_time { lo < h && h <= hi } else { lo <= h && h < hi });
+                    prev_time = t;
+                    passed
+                })
+                .collect();
+            assert_eq!(got, want, "{order:?}");
+        }
+    }
+}
@@ -64,12 +64,120 @@ pub fn gen_opt_heightmap<F: Fn(f32) -> bool>(
     gen_quad_heightmap(heightmap, colormap, options, progress_f)
 }
 
+/// Generate a LAYERED heightmap: each layer's columns are filled only between
+/// its bottom and top maps, so the build can have gaps under solid ground.
+///
+/// Each layer goes through the quadtree or greedy path on its own, and the
+/// bricks are pooled; layers may overlap, which costs bricks but is not an
+/// error. Only the flat-topped Blocks renderers can do this. The sloped
+/// renderers shape the top of ONE surface per column against its
+/// neighbours, and a column with a ceiling over a gap has no single surface
+/// to shape, so they are refused by name rather than run on the top maps
+/// alone.
+pub fn gen_layered_heightmap<F: Fn(f32) -> bool>(
+    layers: &[HeightmapLayer],
+    colormap: &dyn Colormap,
+    options: GenOptions,
+    progress_f: F,
+) -> Result<Vec<Brick>, String> {
+    if options.surface != SurfaceMode::Blocks {
+        return Err(format!(
+            "the {:?} surface renderer cannot build a layered heightmap: it slopes the top of \
+             one surface per column, and a layered column has a gap between its layers. Use \
+             the flat-topped blocks renderer (quadtree or greedy) for layers",
+            options.surface
+        ));
+    }
+    if options.img {
+        return Err(
+            "a layered heightmap cannot be rendered as a flat image: the layers ARE heights"
+                .to_string(),
+        );
+    }
+    if layers.is_empty() {
+        return Err("a layered heightmap needs at least one bottom/top pair".to_string());
+    }
+    for layer in layers {
+        if layer.bottom.size() != colormap.size() || layer.top.size() != colormap.size() {
+            return Err("Layer heightmaps and colormap must have same dimensions".to_string());
+        }
+    }
+
+    // A span thinner than one brick cannot be built without reaching past one
+    // of its surfaces, so it is refused rather than filled wrong.
+    let step = if options.stud { 5 } else { 2 };
+    for (i, layer) in layers.iter().enumerate() {
+        let (width, height) = layer.top.size();
+        for (x, y) in (0..height).flat_map(|y| (0..width).map(move |x| (x, y))) {
+            let (b, t) = (layer.bottom.at(x, y), layer.top.at(x, y));
+            if options.cull && colormap.at(x, y)[3] == 0 {
+                continue;
+            }
+            if t > b && options.layer_half_height(b, t) == 0 {
+                return Err(format!(
+                    "layer {} is {} level(s) thick at pixel ({x}, {y}), which at --vertical {} is \
+                     thinner than the thinnest brick ({} units); raise --vertical or thicken \
+                     the layer",
+                    i + 1,
+                    t - b,
+                    options.scale,
+                    2 * step
+                ));
+            }
+        }
+    }
+
+    let count = layers.len();
+    let mut bricks = vec![];
+    for (i, layer) in layers.iter().enumerate() {
+        info!("Building layer {} of {}", i + 1, count);
+        let progress = |p: f32| progress_f((i as f32 + p) / count as f32);
+        let (bottom, top) = (&*layer.bottom, &*layer.top);
+        bricks.extend(if options.greedy {
+            let cull = options.cull;
+            gen_greedy_bricks(
+                top.size(),
+                |x, y| {
+                    let (b, t, c) = (bottom.at(x, y), top.at(x, y), colormap.at(x, y));
+                    (t > b && (!cull || c[3] > 0)).then_some((t, Some(b), c))
+                },
+                options.clone(),
+                progress,
+            )?
+        } else {
+            gen_quad_bricks(
+                || QuadTree::new_layer(bottom, top, colormap),
+                top.size(),
+                options.clone(),
+                progress,
+            )?
+        });
+    }
+    Ok(bricks)
+}
+
 /// Generate a heightmap using quadtree optimization
 pub fn gen_quad_heightmap<F: Fn(f32) -> bool>(
     heightmap: &dyn Heightmap,
     colormap: &dyn Colormap,
     options: GenOptions,
     progress_f: F,
+) -> Result<Vec<Brick>, String> {
+    gen_quad_bricks(
+        || QuadTree::new(heightmap, colormap),
+        heightmap.size(),
+        options,
+        progress_f,
+    )
+}
+
+/// The quadtree path from whichever grid `build` makes: a heightmap's
+/// surface, or one layer of a layered heightmap.
+fn gen_quad_bricks<F: Fn(f32) -> bool>(
+    build: impl FnOnce() -> Result<QuadTree, String>,
+    (width, height): (u32, u32),
+    options: GenOptions,
+    progress_f: F,
 ) -> Result<Vec<Brick>, String> {
     macro_rules! progress {
         ($e:expr) => {
@@ -82,9 +190,8 @@ pub fn gen_quad_heightmap<F: Fn(f32) -> bool>(
 
     info!("Building initial quadtree");
     let quadtree_build_start = Instant::now();
-    let (width, height) = heightmap.size();
     let area = width * height;
-    let mut quad = QuadTree::new(heightmap, colormap)?;
+    let mut quad = build()?;
     let quadtree_build_duration = quadtree_build_start.elapsed();
     info!(
         "Built quadtree in {:.2}s",
@@ -179,6 +286,34 @@ pub fn gen_greedy_heightmap<F: Fn(f32) -> bool>(
     colormap: &dyn Colormap,
     options: GenOptions,
     progress_f: F,
+) -> Result<Vec<Brick>, String> {
+    if colormap.size() != heightmap.size() {
+        return Err("Heightmap and colormap must have same dimensions".to_string());
+    }
+
+    let cull = options.cull;
+    gen_greedy_bricks(
+        heightmap.size(),
+        |x, y| {
+            let h = heightmap.at(x, y);
+            let c = colormap.at(x, y);
+            // Only add non-transparent pixels (or all if not culling)
+            (!cull || (h > 0 && c[3] > 0)).then_some((h, None, c))
+        },
+        options,
+        progress_f,
+    )
+}
+
+/// The greedy path over any grid of `(top, bottom, color)` cells. A `None`
+/// bottom is a heightmap surface, a slab at `top`; a `Some` bottom is one
+/// span of a layered heightmap, filled from `bottom` up to `top`. Cells that
+/// `cell` returns `None` for are left empty.
+fn gen_greedy_bricks<F: Fn(f32) -> bool>(
+    (width, height): (u32, u32),
+    cell: impl Fn(u32, u32) -> Option<(u32, Option<u32>, [u8; 4])>,
+    options: GenOptions,
+    progress_f: F,
 ) -> Result<Vec<Brick>, String> {
     macro_rules! progress {
         ($e:expr) => {
@@ -190,21 +325,13 @@ pub fn gen_greedy_heightmap<F: Fn(f32) -> bool>(
     progress!(0.0);
 
     info!("Building greedy mesh planes");
-    let (width, height) = heightmap.size();
-
-    if colormap.size() != heightmap.size() {
-        return Err("Heightmap and colormap must have same dimensions".to_string());
-    }
 
     // Find all unique (height, color) combinations
     let mut height_color_pairs = std::collections::BTreeSet::new();
     for x in 0..width {
         for y in 0..height {
-            let h = heightmap.at(x, y);
-            let c = colormap.at(x, y);
-            // Only add non-transparent pixels (or all if not culling)
-            if !options.cull || (h > 0 && c[3] > 0) {
-                height_color_pairs.insert((h, c));
+            if let Some(key) = cell(x, y) {
+                height_color_pairs.insert(key);
             }
         }
     }
@@ -219,7 +346,8 @@ pub fn gen_greedy_heightmap<F: Fn(f32) -> bool>(
     let plane_build_start = Instant::now();
 
     // Create a map from (h, color) to plane index
-    let mut plane_map: HashMap<(u32, [u8; 4]), usize> = HashMap::with_capacity(pairs_vec.len());
+    let mut plane_map: HashMap<(u32, Option<u32>, [u8; 4]), usize> =
+        HashMap::with_capacity(pairs_vec.len());
     let mut all_planes: Vec<Vec<BitMask>> = Vec::with_capacity(pairs_vec.len());
 
     for (idx, &pair) in pairs_vec.iter().enumerate() {
@@ -234,11 +362,8 @@ pub fn gen_greedy_heightmap<F: Fn(f32) -> bool>(
     // Single pass over the image to populate all planes at once
     for x in 0..width {
         for y in 0..height {
-            let h = heightmap.at(x, y);
-            let c = colormap.at(x, y);
-
-            if !options.cull || (h > 0 && c[3] > 0) {
-                if let Some(&plane_idx) = plane_map.get(&(h, c)) {
+            if let Some(key) = cell(x, y) {
+                if let Some(&plane_idx) = plane_map.get(&key) {
                     all_planes[plane_idx][x as usize].set_bit(y);
                 }
             }
@@ -249,7 +374,7 @@ pub fn gen_greedy_heightmap<F: Fn(f32) -> bool>(
     let planes_with_metadata: Vec<_> = all_planes
         .into_iter()
         .zip(pairs_vec.into_iter())
-        .map(|(plane, (h, color))| (plane, h, color))
+        .map(|(plane, (h, bottom, color))| (plane, (h, bottom), color))
         .collect();
 
     let plane_build_duration = plane_build_start.elapsed();
@@ -307,8 +432,18 @@ pub fn gen_greedy_heightmap<F: Fn(f32) -> bool>(
         let w = quad.w;
         let h_brick = quad.h;
 
+        let (h, bottom) = h;
         let mut z = (options.scale * h) as i32;
-        let mut desired_height = (options.scale * 2) as i32;
+        let mut desired_height = match bottom {
+            // a layer span is stacked up from its bottom surface, in whole
+            // steps, so no brick reaches into the gap below it
+            Some(b) => {
+                let half = options.layer_half_height(b, h);
+                z = (options.scale * b) as i32 + 2 * half;
+                half
+            }
+            None => (options.scale * 2) as i32,
+        };
 
         // Create vertical bricks if needed
         while desired_height > 0 {
@@ -383,3 +518,163 @@ pub fn gen_greedy_heightmap<F: Fn(f32) -> bool>(
     progress!(1.0);
     Ok(all_bricks)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use brdb::assets::bricks::PB_DEFAULT_BRICK;
+    use image::{Rgba, RgbaImage};
+
+    fn options(greedy: bool) -> GenOptions {
+        GenOptions {
+            size: 5,
+            scale: 2,
+            asset: PB_DEFAULT_BRICK,
+            cull: false,
+            micro: false,
+            stud: false,
+            snap: false,
+            img: false,
+            glow: false,
+            hdmap: false,
+            nocollide: false,
+            quadtree: true,
+            greedy,
+            surface: SurfaceMode::Blocks,
+        }
+    }
+
+    /// A one-row grey heightmap.
+    fn row(levels: &[u8]) -> Box<dyn Heightmap> {
+        let img = RgbaImage::from_fn(levels.len() as u32, 1, |x, _| {
+            let v = levels[x as usize];
+            Rgba([v, v, v, 255])
+        });
+        Box::new(HeightmapPNG::from_images(vec![img], false).unwrap())
+    }
+
+    fn colormap(width: u32) -> ColormapPNG {
+        ColormapPNG::from_image(RgbaImage::from_pixel(width, 1, Rgba([9, 9, 9, 255])))
+    }
+
+    /// `(x, bottom z, top z)` of every brick, sorted.
+    fn spans(bricks: &[Brick]) -> Vec<(i32, i32, i32)> {
+        let mut out: Vec<_> = bricks
+            .iter()
+            .map(|b| {
+                let BrickType::Procedural { size, .. } = &b.asset else {
+                    panic!("expected a procedural brick");
+                };
+                let z = size.z as i32;
+                (b.position.x, b.position.z - z, b.position.z + z)
+            })
+            .collect();
+        out.sort();
+        out
+    }
+
+    fn arch() -> Vec<HeightmapLayer> {
+        vec![HeightmapLayer {
+            bottom: row(&[0, 4, 0]),
+            top: row(&[6, 6, 6]),
+        }]
+    }
+
+    #[test]
+    fn a_layer_is_filled_only_between_its_bottom_and_top() {
+        for greedy in [false, true] {
+            let bricks = gen_layered_heightmap(&arch(), &colormap(3), options(greedy), |_| true)
+                .unwrap();
+            let spans = spans(&bricks);
+            let column = |x: i32| spans.iter().find(|s| s.0 == x).copied().unwrap();
+            let (pier, lintel) = (column(-10), column(0));
+            assert_eq!(pier.2, lintel.2, "greedy={greedy}: the tops line up");
+            // The lintel starts 4 levels of `scale` 2 above the piers: the
+            // gap under the arch.
+            assert_eq!(lintel.1 - pier.1, 8, "greedy={greedy}: {spans:?}");
+        }
+    }
+
+    #[test]
+    fn an_empty_span_builds_nothing() {
+        let layers = vec![HeightmapLayer {
+            bottom: row(&[5, 5]),
+            top: row(&[5, 2]),
+        }];
+        for greedy in [false, true] {
+            let bricks =
+                gen_layered_heightmap(&layers, &colormap(2), options(greedy), |_| true).unwrap();
+            assert!(bricks.is_empty(), "greedy={greedy}");
+        }
+    }
+
+    #[test]
+    fn every_layer_builds_its_own_span() {
+        // A floor and an island floating above it, in one column.
+        let layers = vec![
+            HeightmapLayer {
+                bottom: row(&[0]),
+                top: row(&[2]),
+            },
+            HeightmapLayer {
+                bottom: row(&[10]),
+                top: row(&[12]),
+            },
+        ];
+        let bricks = gen_layered_heightmap(&layers, &colormap(1), options(false), |_| true)
+            .unwrap();
+        let spans = spans(&bricks);
+        assert_eq!(spans.len(), 2);
+        assert_eq!(spans[1].1 - spans[0].2, 16, "8 levels of air between them");
+    }
+
+    /// A one-level overhang at `--vertical 4` is exactly one plate: it sits on
+    /// its bottom surface and reaches its top. At `--vertical 6` the three
+    /// levels of half height round down to one plate, still on the bottom.
+    /// At `--vertical 1` it is thinner than any brick and refused.
+    #[test]
+    fn a_one_level_overhang_starts_on_its_bottom() {
+        let overhang = || {
+            vec![HeightmapLayer {
+                bottom: row(&[4]),
+                top: row(&[5]),
+            }]
+        };
+        for greedy in [false, true] {
+            for (scale, top) in [(4, 20), (6, 28)] {
+                let opts = GenOptions { scale, ..options(greedy) };
+                let base = opts.base_height() - 5;
+                let bricks =
+                    gen_layered_heightmap(&overhang(), &colormap(1), opts, |_| true).unwrap();
+                let spans = spans(&bricks);
+                assert_eq!(spans.len(), 1, "greedy={greedy} scale={scale}");
+                assert_eq!(spans[0].1 - base, 4 * scale as i32, "starts on the bottom");
+                assert_eq!(spans[0].2 - base, top, "greedy={greedy} scale={scale}");
+            }
+            let thin = GenOptions { scale: 1, ..options(greedy) };
+            let err = gen_layered_heightmap(&overhang(), &colormap(1), thin, |_| true)
+                .err()
+                .expect("refused");
+            assert!(err.contains("thinner than the thinnest brick"), "{err}");
+        }
+    }
+
+    #[test]
+    fn the_sloped_renderers_and_img_are_refused_by_name() {
+        let mut terrain = options(false);
+        terrain.surface = SurfaceMode::Terrain;
+        let err = gen_layered_heightmap(&arch(), &colormap(3), terrain, |_| true)
+            .err()
+            .unwrap();
+        assert!(err.contains("Terrain") && err.contains("layered"), "{err}");
+
+        let mut img = options(false);
+        img.img = true;
+        assert!(gen_layered_heightmap(&arch(), &colormap(3), img, |_| true).is_err());
+
+        let err = gen_layered_heightmap(&arch(), &colormap(4), options(false), |_| true)
+            .err()
+            .unwrap();
+        assert!(err.contains("same dimensions"), "{err}");
+    }
+}
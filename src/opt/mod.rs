@@ -3,6 +3,8 @@ mod greedy;
 mod quad;
 mod rampify;
 mod terrain;
+mod voxel;
+mod wall;
 mod wedge;
 
 pub use generate::*;
@@ -10,4 +12,6 @@ pub use greedy::*;
 pub use quad::*;
 pub use rampify::*;
 pub use terrain::*;
+pub use voxel::*;
+pub use wall::*;
 pub use wedge::*;
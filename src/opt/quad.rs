@@ -16,6 +16,10 @@ struct Tile {
     size: (u32, u32),
     color: [u8; 4],
     height: u32,
+    // the bottom of this column's span in a layered heightmap, where the
+    // column is filled from here up to `height` rather than down to its
+    // lowest neighbor
+    bottom: Option<u32>,
     neighbors: HashSet<u32>,
     parent: Option<usize>,
 }
@@ -32,6 +36,7 @@ impl Tile {
         self.size == other.size
             && self.color == other.color
             && self.height == other.height
+            && self.bottom == other.bottom
             && self.parent.is_none()
             && other.parent.is_none()
     }
@@ -44,6 +49,7 @@ impl Tile {
         (is_vertical && self.size.0 == other.size.0 || is_horizontal && self.size.1 == other.size.1)
             && self.color == other.color
             && self.height == other.height
+            && self.bottom == other.bottom
             && self.parent.is_none()
             && other.parent.is_none()
     }
@@ -101,6 +107,43 @@ impl QuadTree {
                     size: (1, 1),
                     color: colormap.at(x as u32, y as u32),
                     height: heightmap.at(x as u32, y as u32),
+                    bottom: None,
+                    parent: None,
+                })
+            }
+        }
+
+        Ok(QuadTree {
+            tiles: tiles.into_boxed_slice(),
+            width,
+            height,
+        })
+    }
+
+    // create a grid for one layer of a layered heightmap: every column is
+    // the span from `bottom` up to `top`, so neighbors play no part
+    pub fn new_layer(
+        bottom: &dyn Heightmap,
+        top: &dyn Heightmap,
+        colormap: &dyn Colormap,
+    ) -> Result<Self, String> {
+        let (width, height) = top.size();
+
+        if colormap.size() != top.size() || bottom.size() != top.size() {
+            return Err("Layer heightmaps and colormap must have same dimensions".to_string());
+        }
+
+        let mut tiles = Vec::with_capacity((width * height) as usize);
+        for x in 0..width {
+            for y in 0..height {
+                tiles.push(Tile {
+                    index: (x + y * height) as usize,
+                    center: (x, y),
+                    neighbors: HashSet::new(),
+                    size: (1, 1),
+                    color: colormap.at(x, y),
+                    height: top.at(x, y),
+                    bottom: Some(bottom.at(x, y)),
                     parent: None,
                 })
             }
@@ -267,18 +310,35 @@ impl QuadTree {
                 if t.parent.is_some() || options.cull && (t.height == 0 || t.color[3] == 0) {
                     return vec![];
                 }
+                // an empty span in a layered heightmap is a gap, not a brick
+                if t.bottom.is_some_and(|b| t.height <= b) {
+                    return vec![];
+                }
 
                 let mut z = (options.scale * t.height) as i32;
 
-                // determine the height of this brick (difference of self and smallest neighbor)
-                let raw_height = max(
-                    t.height as i32 - t.neighbors.iter().cloned().min().unwrap_or(0) as i32 + 1,
-                    2,
-                );
-                let mut desired_height = max(raw_height * options.scale as i32 / 2, 2);
+                let mut desired_height = match t.bottom {
+                    // a layer is stacked up from its bottom surface, in whole
+                    // steps, so no brick reaches into the gap below it
+                    Some(b) => {
+                        let half = options.layer_half_height(b, t.height);
+                        z = (options.scale * b) as i32 + 2 * half;
+                        half
+                    }
+                    None => {
+                        // determine the height of this brick (difference of self and smallest neighbor)
+                        let raw_height = max(
+                            t.height as i32
+                                - t.neighbors.iter().cloned().min().unwrap_or(0) as i32
+                                + 1,
+                            2,
+                        );
+                        max(raw_height * options.scale as i32 / 2, 2)
+                    }
+                };
 
-                // snap bricks to grid
-                if options.snap {
+                // snap bricks to grid; a layer stays on its bottom surface
+                if options.snap && t.bottom.is_none() {
                     z += 4 - z % 4;
                     desired_height += 4 - desired_height % 4;
                 }
@@ -341,3 +401,97 @@ impl QuadTree {
             .collect()
     }
 }
+
+/// The tiles [`QuadTree::quad_optimize_level`] leaves of a flat picture --
+/// same-colour squares, merged level by level while `options` would -- with
+/// the culled ones left out. A cheap stand-in for a brick count when
+/// comparing two versions of one picture: one byte-sized grid per level
+/// rather than a tile per pixel, and no bricks built. The line merge after
+/// it only merges further, so it counts high, and by the same amount on
+/// pictures alike.
+pub fn quad_leaf_count(colors: &image::RgbaImage, options: &GenOptions) -> usize {
+    let (mut w, mut h) = (colors.width() as usize, colors.height() as usize);
+    let mut grid: Vec<Option<[u8; 4]>> = Vec::with_capacity(w * h);
+    for y in 0..h {
+        grid.extend((0..w).map(|x| Some(colors.get_pixel(x as u32, y as u32).0)));
+    }
+    let counted = |c: &[u8; 4]| !(options.cull && c[3] == 0);
+    let mut leaves = 0;
+    let mut scale = 0;
+    loop {
+        // The same stop as `gen_quad_heightmap`: no wider than it merges.
+        let merging = options.quadtree && 2_i32.pow(scale + 1) * (options.size as i32) < 500;
+        let (pw, ph) = if merging { (w / 2, h / 2) } else { (0, 0) };
+        let mut parent = vec![None; pw * ph];
+        for (i, p) in parent.iter_mut().enumerate() {
+            let (x, y) = (2 * (i % pw), 2 * (i / pw));
+            let c = grid[y * w + x];
+            if c.is_some()
+                && c == grid[y * w + x + 1]
+                && c == grid[(y + 1) * w + x]
+                && c == grid[(y + 1) * w + x + 1]
+            {
+                *p = c;
+            }
+        }
+        for (i, c) in grid.iter().enumerate() {
+            let (x, y) = (i % w / 2, i / w / 2);
+            let merged = x < pw && y < ph && parent[y * pw + x].is_some();
+            if c.as_ref().is_some_and(|c| !merged && counted(c)) {
+                leaves += 1;
+            }
+        }
+        if parent.iter().all(Option::is_none) {
+            return leaves;
+        }
+        (grid, w, h, scale) = (parent, pw, ph, scale + 1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use brdb::assets::bricks::PB_DEFAULT_BRICK;
+    use image::{Rgba, RgbaImage};
+
+    fn options() -> GenOptions {
+        GenOptions {
+            size: 5,
+            scale: 2,
+            asset: PB_DEFAULT_BRICK,
+            cull: false,
+            micro: false,
+            stud: false,
+            snap: false,
+            img: true,
+            glow: false,
+            hdmap: false,
+            nocollide: false,
+            quadtree: true,
+            greedy: false,
+            surface: SurfaceMode::Blocks,
+        }
+    }
+
+    /// A 4x4 of one colour is one tile, a checkerboard sixteen, and a clear
+    /// quarter is one tile that `cull` leaves out. The odd column past a
+    /// whole square never merges.
+    #[test]
+    fn same_colour_squares_are_one_leaf_each() {
+        let options = options();
+        let (red, blue) = (Rgba([255, 0, 0, 255]), Rgba([0, 0, 255, 255]));
+        assert_eq!(quad_leaf_count(&RgbaImage::from_pixel(4, 4, red), &options), 1);
+        let checker = RgbaImage::from_fn(4, 4, |x, y| if (x + y) % 2 == 0 { red } else { blue });
+        assert_eq!(quad_leaf_count(&checker, &options), 16);
+        assert_eq!(quad_leaf_count(&RgbaImage::from_pixel(5, 4, red), &options), 1 + 4);
+
+        let clear = Rgba([0, 0, 0, 0]);
+        let quarter = RgbaImage::from_fn(4, 4, |x, y| if x < 2 && y < 2 { clear } else { red });
+        assert_eq!(quad_leaf_count(&quarter, &options), 4);
+        let cull = GenOptions { cull: true, ..options.clone() };
+        assert_eq!(quad_leaf_count(&quarter, &cull), 3);
+
+        let no_merge = GenOptions { quadtree: false, ..options };
+        assert_eq!(quad_leaf_count(&RgbaImage::from_pixel(4, 4, red), &no_merge), 16);
+    }
+}
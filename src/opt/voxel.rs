@@ -0,0 +1,294 @@
+//! Voxel models to bricks: the greedy mesher, one layer at a time.
+//!
+//! A heightmap's greedy path meshes one plane per `(height, colour)` pair. A
+//! voxel grid is the same problem stood on its side: every z layer is a
+//! plane, split by colour, and `greedy_mesh_binary_plane` turns each one into
+//! rectangles. Rectangles that repeat EXACTLY in the layer above -- same
+//! position, same size, same colour -- then stack into one taller brick, so a
+//! solid block of one colour comes out as a single box rather than one slab
+//! per layer.
+//!
+//! Every voxel is a cube of `2 * GenOptions::size` units. The model is
+//! centred on the origin across the ground, like a heightmap, and rests on
+//! `z = 0`.
+
+use super::{BitMask, GreedyQuad, greedy_mesh_binary_plane};
+use crate::util::*;
+use crate::voxel::VoxelGrid;
+use brdb::{
+    Brick, BrickSize, BrickType, Color, Position,
+    assets::materials::{GLOW, PLASTIC},
+};
+use log::info;
+#[cfg(not(target_arch = "wasm32"))]
+use rayon::prelude::*;
+use std::collections::HashMap;
+
+/// Mesh one z layer: a plane per palette entry present in it, each greedy
+/// meshed. Returns `(quad, palette entry)` pairs.
+fn mesh_layer(grid: &VoxelGrid, z: u32, max_size: u32) -> Vec<(GreedyQuad, u32)> {
+    let (w, h, _) = grid.size();
+    let mut planes: HashMap<u32, Vec<BitMask>> = HashMap::new();
+    for x in 0..w {
+        for y in 0..h {
+            let entry = grid.entry(x, y, z);
+            if entry == 0 {
+                continue;
+            }
+            planes
+                .entry(entry)
+                .or_insert_with(|| vec![BitMask::new(); w as usize])[x as usize]
+                .set_bit(y);
+        }
+    }
+
+    let mut quads = vec![];
+    for (entry, plane) in planes {
+        quads.extend(
+            greedy_mesh_binary_plane(plane, w, h, max_size)
+                .into_iter()
+                .map(|q| (q, entry)),
+        );
+    }
+    quads
+}
+
+/// Convert a voxel grid to bricks.
+///
+/// Honours `size` (the half extent of one voxel, in units), `asset`, `glow`
+/// and `nocollide`. The height fields -- `scale`, `img`, `cull`, `snap`,
+/// `surface` -- describe a heightmap and have no meaning here; a voxel's
+/// height IS its size.
+pub fn gen_voxel_bricks<F: Fn(f32) -> bool>(
+    grid: &VoxelGrid,
+    options: GenOptions,
+    progress_f: F,
+) -> Result<Vec<Brick>, String> {
+    macro_rules! progress {
+        ($e:expr) => {
+            if !progress_f($e) {
+                return Err("Stopped by user".to_string());
+            }
+        };
+    }
+    progress!(0.0);
+
+    let (width, height, depth) = grid.size();
+    // Positions run to twice a side's voxel count times `size`. A grid under
+    // `MAX_VOXEL_CELLS` can still be long and thin enough to push that past
+    // `i32`, which would wrap in a release build and panic in a debug one.
+    let span = 2 * width.max(height).max(depth) as i64 * options.size as i64;
+    if span > i32::MAX as i64 {
+        return Err(format!(
+            "a voxel grid of {width} x {height} x {depth} at {} units per voxel reaches \
+             {span} units, past the furthest a brick can be placed; lower --size or move the \
+             models closer together",
+            2 * options.size as u32
+        ));
+    }
+    // A brick may not be larger than `MAX_BRICK_HALF_EXTENT` on any axis, so
+    // neither a quad nor a stack of them may span more voxels than that.
+    let max_size = (MAX_BRICK_HALF_EXTENT / options.size.max(1)).max(1) as u32;
+
+    info!("Meshing {} voxel layers", depth);
+    #[cfg(not(target_arch = "wasm32"))]
+    let layers: Vec<_> = (0..depth)
+        .into_par_iter()
+        .map(|z| mesh_layer(grid, z, max_size))
+        .collect();
+    #[cfg(target_arch = "wasm32")]
+    let layers: Vec<_> = (0..depth).map(|z| mesh_layer(grid, z, max_size)).collect();
+    progress!(0.6);
+
+    // Stack identical quads upward. `open` holds the boxes that reached the
+    // previous layer, keyed by footprint and colour; anything not continued
+    // by this layer is closed for good.
+    let mut boxes: Vec<(GreedyQuad, u32, u32, u32)> = vec![];
+    let mut open: HashMap<(u32, u32, u32, u32, u32), usize> = HashMap::new();
+    for (z, quads) in layers.into_iter().enumerate() {
+        let z = z as u32;
+        let mut next = HashMap::with_capacity(quads.len());
+        for (q, entry) in quads {
+            let key = (q.x, q.y, q.w, q.h, entry);
+            match open.get(&key) {
+                Some(&i) if boxes[i].3 < max_size => {
+                    boxes[i].3 += 1;
+                    next.insert(key, i);
+                }
+                _ => {
+                    next.insert(key, boxes.len());
+                    boxes.push((q, entry, z, 1));
+                }
+            }
+        }
+        open = next;
+    }
+    progress!(0.8);
+
+    let size = options.size as i32;
+    let offset_x = -(width as i32 * size);
+    let offset_y = -(height as i32 * size);
+    let bricks: Vec<Brick> = boxes
+        .into_iter()
+        .map(|(q, entry, z, d)| {
+            let color = grid.palette[entry as usize];
+            Brick {
+                asset: BrickType::Procedural {
+                    asset: options.asset.clone(),
+                    size: BrickSize::new(
+                        q.w as u16 * options.size,
+                        q.h as u16 * options.size,
+                        d as u16 * options.size,
+                    ),
+                },
+                position: Position::new(
+                    (q.x * 2 + q.w) as i32 * size + offset_x,
+                    (q.y * 2 + q.h) as i32 * size + offset_y,
+                    (z * 2 + d) as i32 * size,
+                ),
+                collision: options.collision(),
+                color: Color {
+                    r: color[0],
+                    g: color[1],
+                    b: color[2],
+                },
+                owner_index: None,
+                material_intensity: if options.glow { 0 } else { 5 },
+                material: if options.glow { GLOW } else { PLASTIC },
+                ..Default::default()
+            }
+        })
+        .collect();
+
+    let voxels = grid.count();
+    info!(
+        "Reduced {} voxels to {} bricks ({}%)",
+        commas(voxels as u64),
+        commas(bricks.len() as u64),
+        (100. - bricks.len() as f64 / voxels.max(1) as f64 * 100.).floor()
+    );
+
+    progress!(1.0);
+    Ok(bricks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use brdb::assets::bricks::PB_DEFAULT_BRICK;
+
+    fn options(size: u16) -> GenOptions {
+        GenOptions {
+            size,
+            scale: 1,
+            asset: PB_DEFAULT_BRICK,
+            cull: false,
+            micro: false,
+            stud: false,
+            snap: false,
+            img: false,
+            glow: false,
+            hdmap: false,
+            nocollide: false,
+            quadtree: false,
+            greedy: true,
+            surface: SurfaceMode::Blocks,
+        }
+    }
+
+    fn half_extents(b: &Brick) -> (u16, u16, u16) {
+        match &b.asset {
+            BrickType::Procedural { size, .. } => (size.x, size.y, size.z),
+            other => panic!("expected a procedural brick, got {other:?}"),
+        }
+    }
+
+    fn fill(grid: &mut VoxelGrid, to: (u32, u32, u32), color: [u8; 4]) {
+        for x in 0..to.0 {
+            for y in 0..to.1 {
+                for z in 0..to.2 {
+                    grid.set(x, y, z, color);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn a_solid_block_of_one_colour_is_one_brick() {
+        let mut grid = VoxelGrid::new((4, 3, 5)).unwrap();
+        fill(&mut grid, (4, 3, 5), [200, 10, 10, 255]);
+
+        let bricks = gen_voxel_bricks(&grid, options(5), |_| true).unwrap();
+        assert_eq!(bricks.len(), 1);
+        assert_eq!(half_extents(&bricks[0]), (20, 15, 25));
+        // Centred across the ground, resting on z = 0.
+        assert_eq!(bricks[0].position, Position::new(0, 0, 25));
+        assert_eq!(bricks[0].color, Color { r: 200, g: 10, b: 10 });
+    }
+
+    #[test]
+    fn colours_never_share_a_brick() {
+        let mut grid = VoxelGrid::new((2, 1, 2)).unwrap();
+        fill(&mut grid, (2, 1, 2), [1, 1, 1, 255]);
+        grid.set(1, 0, 1, [2, 2, 2, 255]);
+
+        let bricks = gen_voxel_bricks(&grid, options(1), |_| true).unwrap();
+        let volume: u32 = bricks
+            .iter()
+            .map(|b| {
+                let (x, y, z) = half_extents(b);
+                x as u32 * y as u32 * z as u32
+            })
+            .sum();
+        assert_eq!(volume, 4, "every voxel covered exactly once");
+        assert!(bricks.iter().any(|b| b.color == Color { r: 2, g: 2, b: 2 }));
+        assert_eq!(bricks.len(), 3);
+    }
+
+    #[test]
+    fn no_brick_grows_past_the_engine_limit() {
+        let mut grid = VoxelGrid::new((3, 1, 3)).unwrap();
+        fill(&mut grid, (3, 1, 3), [5, 5, 5, 255]);
+
+        // 250 units per voxel: two voxels already reach the 500 limit.
+        let bricks = gen_voxel_bricks(&grid, options(250), |_| true).unwrap();
+        for b in &bricks {
+            let (x, y, z) = half_extents(b);
+            assert!(x.max(y).max(z) <= MAX_BRICK_HALF_EXTENT, "{:?}", (x, y, z));
+        }
+        assert_eq!(bricks.len(), 4);
+    }
+
+    #[test]
+    fn glow_and_nocollide_reach_every_brick() {
+        let mut grid = VoxelGrid::new((1, 1, 1)).unwrap();
+        grid.set(0, 0, 0, [9, 9, 9, 255]);
+        let mut opts = options(5);
+        opts.glow = true;
+        opts.nocollide = true;
+
+        let bricks = gen_voxel_bricks(&grid, opts, |_| true).unwrap();
+        assert_eq!(bricks[0].material, GLOW);
+        assert!(!bricks[0].collision.player);
+        assert!(!bricks[0].collision.weapon);
+    }
+
+    #[test]
+    fn a_grid_placed_past_i32_is_refused_not_wrapped() {
+        let grid = VoxelGrid::new((20_000, 1, 1)).unwrap();
+        let err = gen_voxel_bricks(&grid, options(u16::MAX), |_| true).err().unwrap();
+        assert_eq!(
+            err,
+            "a voxel grid of 20000 x 1 x 1 at 131070 units per voxel reaches 2621400000 units, \
+             past the furthest a brick can be placed; lower --size or move the models closer \
+             together"
+        );
+        assert!(gen_voxel_bricks(&grid, options(5), |_| true).is_ok());
+    }
+
+    #[test]
+    fn a_stopped_render_returns_an_error() {
+        let grid = VoxelGrid::new((1, 1, 1)).unwrap();
+        assert!(gen_voxel_bricks(&grid, options(5), |_| false).is_err());
+    }
+}
@@ -0,0 +1,425 @@
+//! Standing a finished render upright, for murals, billboards and relief
+//! walls.
+//!
+//! Every generator builds lying on the ground: image columns along +x, image
+//! rows along +y, heights up +z. Rather than teach each of them a second
+//! frame, the finished bricks are turned as one rigid body -- a quarter turn
+//! that takes the image's rows down the wall and its heights out of the wall's
+//! face -- and then set down so the wall stands on `z = 0`.
+//!
+//! Box assets (the flat-topped styles, and the foundations the sloped
+//! renderers lay) are turned by permuting their size, so they stay unrotated:
+//! that is exact, keeps brick tops facing up, and keeps `brdb`'s bounds
+//! exact. The sloped pieces cannot be turned that way: a ramp's slope has a
+//! direction, so the piece itself must turn. Each orientation is read as the
+//! world axes the brick's own x, y and z point along ([`basis`]), that frame
+//! is turned with the bricks, and the piece takes whichever of the 24
+//! orientations has the turned frame. Its slope then runs where the turn
+//! takes it: a ramp falling toward the later image rows falls down the wall.
+//!
+//! [`basis`] is measured where it can be and a convention where it cannot.
+//! Facing `Z_Positive`, a roll is a quarter turn counterclockwise about +z
+//! per step, with the slope falling toward the brick's own +x -- what
+//! `rampify`'s ramps and `terrain`'s table rest on. For the other facings,
+//! which of the two remaining world axes the brick's x lands on is the
+//! convention `anim::layout::rotated_half` fixes; the frame follows it, so
+//! the extents read back through `rotated_half` agree with it.
+
+use brdb::{
+    BString, Brick, BrickSize, BrickType, Direction, Position, Rotation,
+    assets::bricks::{
+        PB_DEFAULT_BRICK, PB_DEFAULT_MICRO_BRICK, PB_DEFAULT_SMOOTH_TILE, PB_DEFAULT_STUDDED,
+        PB_DEFAULT_TILE,
+    },
+};
+
+/// Which plane a heightmap or image render is built in.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum BuildPlane {
+    /// Lying on the ground, heights up. Every render before walls.
+    #[default]
+    Ground,
+    /// Standing along the X axis: the picture spans x and z, and heights
+    /// stand out of the wall toward +Y.
+    WallX,
+    /// Standing along the Y axis: the picture spans y and z, and heights
+    /// stand out of the wall toward -X.
+    WallY,
+}
+
+impl BuildPlane {
+    /// Turn a lying-frame vector into this plane: image columns stay
+    /// across, image rows run DOWN the wall, heights come out of it.
+    fn turn(self, (x, y, z): (i32, i32, i32)) -> (i32, i32, i32) {
+        match self {
+            BuildPlane::Ground => (x, y, z),
+            BuildPlane::WallX => (x, z, -y),
+            BuildPlane::WallY => (-z, x, -y),
+        }
+    }
+
+    /// The same turn for half extents, which have no sign.
+    fn turn_half(self, (x, y, z): (u16, u16, u16)) -> (u16, u16, u16) {
+        match self {
+            BuildPlane::Ground => (x, y, z),
+            BuildPlane::WallX => (x, z, y),
+            BuildPlane::WallY => (z, x, y),
+        }
+    }
+}
+
+/// A brick's own x, y and z axes, as the world axes they point along.
+pub(crate) type Basis = [(i32, i32, i32); 3];
+
+pub(crate) const DIRECTIONS: [Direction; 6] = [
+    Direction::XPositive,
+    Direction::XNegative,
+    Direction::YPositive,
+    Direction::YNegative,
+    Direction::ZPositive,
+    Direction::ZNegative,
+];
+
+pub(crate) const ROTATIONS: [Rotation; 4] = [
+    Rotation::Deg0,
+    Rotation::Deg90,
+    Rotation::Deg180,
+    Rotation::Deg270,
+];
+
+/// Where a brick facing `direction` and rolled by `rotation` points its own
+/// axes (see the module doc). The roll turns x and y about the brick's z;
+/// the facing then carries z onto `direction`, and x onto the first of the
+/// two world axes left, in `rotated_half`'s order.
+pub(crate) fn basis(direction: Direction, rotation: Rotation) -> Basis {
+    let (x, y) = match rotation {
+        Rotation::Deg0 => ((1, 0, 0), (0, 1, 0)),
+        Rotation::Deg90 => ((0, 1, 0), (-1, 0, 0)),
+        Rotation::Deg180 => ((-1, 0, 0), (0, -1, 0)),
+        Rotation::Deg270 => ((0, -1, 0), (1, 0, 0)),
+    };
+    let face = |(a, b, c): (i32, i32, i32)| match direction {
+        Direction::XPositive => (c, a, b),
+        Direction::XNegative => (-c, a, -b),
+        Direction::YPositive => (a, c, -b),
+        Direction::YNegative => (a, -c, b),
+        Direction::ZNegative => (a, -b, -c),
+        Direction::ZPositive | Direction::MAX => (a, b, c),
+    };
+    [face(x), face(y), face((0, 0, 1))]
+}
+
+/// The orientation whose [`basis`] is `frame`. A quarter turn of any
+/// orientation is another of the 24, so a turned frame always has one.
+fn orientation_of(frame: Basis) -> (Direction, Rotation) {
+    DIRECTIONS
+        .iter()
+        .flat_map(|&d| ROTATIONS.iter().map(move |&r| (d, r)))
+        .find(|&(d, r)| basis(d, r) == frame)
+        .expect("a quarter turn of an orientation is an orientation")
+}
+
+/// The world half extents of a brick of authored half `size` whose axes
+/// point along `frame`: each authored extent lands on the world axis its
+/// own axis points along.
+fn world_half(frame: Basis, size: (u16, u16, u16)) -> (u16, u16, u16) {
+    let mut world = [0; 3];
+    for (axis, extent) in frame.into_iter().zip([size.0, size.1, size.2]) {
+        let on = [axis.0, axis.1, axis.2];
+        world[on.iter().position(|&c| c != 0).unwrap_or(2)] = extent;
+    }
+    (world[0], world[1], world[2])
+}
+
+/// The assets whose shape is a plain box. A box turned on its side is the
+/// same box with its size permuted, so these never need a rotation.
+fn is_box(asset: &BString) -> bool {
+    [
+        PB_DEFAULT_BRICK,
+        PB_DEFAULT_TILE,
+        PB_DEFAULT_SMOOTH_TILE,
+        PB_DEFAULT_STUDDED,
+        PB_DEFAULT_MICRO_BRICK,
+    ]
+    .iter()
+    .any(|b| b == asset)
+}
+
+/// Stand a lying render upright in `plane`, in place, resting on `z = 0`.
+/// `Ground` leaves the bricks untouched.
+pub fn stand_upright(bricks: &mut [Brick], plane: BuildPlane) {
+    if plane == BuildPlane::Ground || bricks.is_empty() {
+        return;
+    }
+    let mut floor = i32::MAX;
+    for brick in bricks.iter_mut() {
+        let p = brick.position;
+        let (x, y, z) = plane.turn((p.x, p.y, p.z));
+        brick.position = Position::new(x, y, z);
+
+        let BrickType::Procedural { asset, size } = &mut brick.asset else {
+            continue;
+        };
+        let half_z = if is_box(asset) {
+            // A roll about z swaps a box's x and y extents before the turn.
+            let half = match brick.rotation {
+                Rotation::Deg0 | Rotation::Deg180 => (size.x, size.y, size.z),
+                Rotation::Deg90 | Rotation::Deg270 => (size.y, size.x, size.z),
+            };
+            let world = plane.turn_half(half);
+            *size = BrickSize::new(world.0, world.1, world.2);
+            brick.rotation = Rotation::Deg0;
+            world.2 as i32
+        } else {
+            let frame = basis(brick.direction, brick.rotation).map(|axis| plane.turn(axis));
+            let (direction, rotation) = orientation_of(frame);
+            brick.direction = direction;
+            brick.rotation = rotation;
+            world_half(basis(direction, rotation), (size.x, size.y, size.z)).2 as i32
+        };
+        floor = floor.min(z - half_z);
+    }
+    if floor == i32::MAX {
+        return;
+    }
+    for brick in bricks.iter_mut() {
+        brick.position.z -= floor;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use brdb::assets::bricks::{PB_DEFAULT_MICRO_RAMP, PB_DEFAULT_MICRO_WEDGE};
+
+    fn brick(
+        asset: BString,
+        size: (u16, u16, u16),
+        at: (i32, i32, i32),
+        rotation: Rotation,
+    ) -> Brick {
+        Brick {
+            asset: BrickType::Procedural {
+                asset,
+                size: BrickSize::new(size.0, size.1, size.2),
+            },
+            position: Position::new(at.0, at.1, at.2),
+            rotation,
+            ..Default::default()
+        }
+    }
+
+    fn half(b: &Brick) -> (u16, u16, u16) {
+        match &b.asset {
+            BrickType::Procedural { size, .. } => (size.x, size.y, size.z),
+            other => panic!("expected a procedural brick, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn boxes_stand_up_by_permuting_their_size() {
+        // Two image rows, one above the other in the picture (row 0 at -y).
+        let mut bricks = vec![
+            brick(PB_DEFAULT_TILE, (5, 5, 2), (0, -5, 2), Rotation::Deg0),
+            brick(PB_DEFAULT_TILE, (5, 5, 4), (0, 5, 4), Rotation::Deg0),
+        ];
+        stand_upright(&mut bricks, BuildPlane::WallX);
+        assert_eq!(half(&bricks[0]), (5, 2, 5));
+        // Row 0 is the TOP of the wall, and the wall stands on the ground.
+        assert_eq!(bricks[0].position, Position::new(0, 2, 15));
+        assert_eq!(bricks[1].position, Position::new(0, 4, 5));
+        assert!(matches!(bricks[0].direction, Direction::ZPositive));
+
+        let mut rolled = vec![brick(
+            PB_DEFAULT_BRICK,
+            (10, 5, 6),
+            (0, 0, 6),
+            Rotation::Deg90,
+        )];
+        stand_upright(&mut rolled, BuildPlane::WallY);
+        assert_eq!(
+            half(&rolled[0]),
+            (6, 5, 10),
+            "the roll's swap is applied first"
+        );
+        assert!(matches!(rolled[0].rotation, Rotation::Deg0));
+        assert_eq!(rolled[0].position, Position::new(-6, 0, 10));
+    }
+
+    /// Every (direction, rotation) is a distinct right-handed frame, so a
+    /// turned frame names exactly one orientation.
+    #[test]
+    fn every_orientation_is_its_own_right_handed_frame() {
+        let mut seen = Vec::new();
+        for d in DIRECTIONS {
+            for r in ROTATIONS {
+                let [x, y, z] = basis(d, r);
+                let cross = (x.1 * y.2 - x.2 * y.1, x.2 * y.0 - x.0 * y.2, x.0 * y.1 - x.1 * y.0);
+                assert_eq!(cross, z, "{d:?} {r:?} is right-handed");
+                assert!(!seen.contains(&[x, y, z]), "{d:?} {r:?} repeats a frame");
+                seen.push([x, y, z]);
+                let (d2, r2) = orientation_of([x, y, z]);
+                assert_eq!(basis(d2, r2), [x, y, z], "{d:?} {r:?} is found again");
+            }
+        }
+    }
+
+    /// A ramp lying on the ground falls toward its own +x: +x at `Deg0`,
+    /// then a quarter turn counterclockwise per step (as `rampify` lays
+    /// them). Stood up, it must fall the way the turn takes that, and rest
+    /// on the ground with the turned footprint.
+    #[test]
+    fn a_stood_up_ramp_slopes_and_sits_where_the_turn_takes_it() {
+        let falls = [
+            (Rotation::Deg0, (1, 0, 0)),
+            (Rotation::Deg90, (0, 1, 0)),
+            (Rotation::Deg180, (-1, 0, 0)),
+            (Rotation::Deg270, (0, -1, 0)),
+        ];
+        for plane in [BuildPlane::WallX, BuildPlane::WallY] {
+            for (rotation, fall) in falls {
+                let odd = matches!(rotation, Rotation::Deg90 | Rotation::Deg270);
+                let footprint = if odd { (2, 3, 1) } else { (3, 2, 1) };
+                let mut bricks = vec![brick(PB_DEFAULT_MICRO_RAMP, (3, 2, 1), (0, 0, 1), rotation)];
+                stand_upright(&mut bricks, plane);
+                let b = &bricks[0];
+                let tag = format!("{plane:?} {rotation:?}");
+
+                assert_eq!(basis(b.direction, b.rotation)[0], plane.turn(fall), "{tag}: slope");
+                // Heights come out of the wall: the top faces outward.
+                assert_eq!(basis(b.direction, b.rotation)[2], plane.turn((0, 0, 1)), "{tag}");
+
+                let (hx, hy, hz) = plane.turn_half(footprint);
+                let world = world_half(basis(b.direction, b.rotation), (3, 2, 1));
+                assert_eq!(world, (hx, hy, hz), "{tag}: world bounds");
+                assert_eq!(b.position.z, hz as i32, "{tag}: rests on the ground");
+                assert_eq!(half(b), (3, 2, 1), "{tag}: authored size is kept");
+            }
+        }
+    }
+
+    /// On the X wall a ramp falling toward the later image rows (+y on the
+    /// ground) falls DOWN the wall; on the Y wall one falling toward +x
+    /// falls along +y, the wall's own across.
+    #[test]
+    fn the_slope_follows_the_picture() {
+        let mut bricks = vec![brick(
+            PB_DEFAULT_MICRO_WEDGE,
+            (1, 1, 1),
+            (0, 0, 1),
+            Rotation::Deg90,
+        )];
+        stand_upright(&mut bricks, BuildPlane::WallX);
+        assert_eq!(basis(bricks[0].direction, bricks[0].rotation)[0], (0, 0, -1));
+        assert!(matches!(bricks[0].direction, Direction::YPositive));
+
+        let mut bricks = vec![brick(
+            PB_DEFAULT_MICRO_WEDGE,
+            (1, 1, 1),
+            (0, 0, 1),
+            Rotation::Deg0,
+        )];
+        stand_upright(&mut bricks, BuildPlane::WallY);
+        assert_eq!(basis(bricks[0].direction, bricks[0].rotation)[0], (0, 1, 0));
+        assert!(matches!(bricks[0].direction, Direction::XNegative));
+    }
+
+    /// Every sloped renderer, end to end: a raised corner block gives each
+    /// of them ramps and corners in several rolls. Stood up, every sloped
+    /// piece faces out of the wall, keeps the roll it was laid with -- the
+    /// facing alone carries it upright -- and slopes where the turn takes
+    /// its ground slope.
+    #[test]
+    fn every_sloped_renderer_faces_out_of_the_wall_with_its_roll() {
+        use crate::map::{ColormapPNG, HeightmapPNG};
+        use crate::opt::gen_opt_heightmap;
+        use crate::util::{GenOptions, SurfaceMode};
+        use image::{Rgba, RgbaImage};
+
+        let img = RgbaImage::from_fn(4, 4, |x, y| {
+            let v = if x >= 2 && y >= 2 { 40 } else { 0 };
+            Rgba([v, v, v, 255])
+        });
+        let map = HeightmapPNG::from_images(vec![img], false).unwrap();
+        let colormap = ColormapPNG::from_image(RgbaImage::from_pixel(4, 4, Rgba([9, 9, 9, 255])));
+        let sloped =
+            |b: &&Brick| matches!(&b.asset, BrickType::Procedural { asset, .. } if !is_box(asset));
+
+        for surface in [
+            SurfaceMode::Terrain,
+            SurfaceMode::Rampify,
+            SurfaceMode::Wedge,
+        ] {
+            let options = GenOptions {
+                size: 5,
+                scale: 1,
+                asset: PB_DEFAULT_MICRO_BRICK,
+                cull: false,
+                micro: true,
+                stud: false,
+                snap: false,
+                img: false,
+                glow: false,
+                hdmap: false,
+                nocollide: false,
+                quadtree: true,
+                greedy: false,
+                surface,
+            };
+            let ground = gen_opt_heightmap(&map, &colormap, options, |_| true).unwrap();
+            for (plane, facing) in [
+                (BuildPlane::WallX, Direction::YPositive),
+                (BuildPlane::WallY, Direction::XNegative),
+            ] {
+                let mut upright = ground.clone();
+                stand_upright(&mut upright, plane);
+                let pairs: Vec<_> = ground
+                    .iter()
+                    .filter(sloped)
+                    .zip(upright.iter().filter(sloped))
+                    .collect();
+                assert!(!pairs.is_empty(), "{surface:?} lays sloped pieces");
+                for (g, u) in pairs {
+                    let tag = format!("{surface:?} {plane:?} {:?} {:?}", g.direction, g.rotation);
+                    assert!(
+                        matches!(g.direction, Direction::ZPositive),
+                        "{tag}: laid flat"
+                    );
+                    // `brdb`'s orientation enums have no `PartialEq`.
+                    assert_eq!(
+                        format!("{:?}", u.direction),
+                        format!("{facing:?}"),
+                        "{tag}: faces out"
+                    );
+                    assert_eq!(
+                        format!("{:?}", u.rotation),
+                        format!("{:?}", g.rotation),
+                        "{tag}: roll"
+                    );
+                    let (was, now) = (
+                        basis(g.direction, g.rotation),
+                        basis(u.direction, u.rotation),
+                    );
+                    assert_eq!(
+                        now,
+                        was.map(|axis| plane.turn(axis)),
+                        "{tag}: slope follows the turn"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn the_ground_plane_changes_nothing() {
+        let mut bricks = vec![brick(
+            PB_DEFAULT_TILE,
+            (5, 5, 2),
+            (3, -7, 2),
+            Rotation::Deg90,
+        )];
+        stand_upright(&mut bricks, BuildPlane::Ground);
+        assert_eq!(bricks[0].position, Position::new(3, -7, 2));
+        assert_eq!(half(&bricks[0]), (5, 5, 2));
+    }
+}
@@ -0,0 +1,749 @@
+//! Point clouds as a heightmap source: `.xyz`/`.csv` text and uncompressed
+//! `.las`, binned into a grid of cells.
+//!
+//! A LIDAR export is a scatter of points, not a grid. Each point falls into
+//! one cell of `cell_size` (in the file's own units, usually metres), every
+//! cell reduces its points' heights to one -- the highest, the mean or the
+//! lowest -- and the cells no point landed in are filled from their
+//! neighbours by inverse-distance weighting. The result implements
+//! [`Heightmap`] and [`Colormap`], so everything downstream of the image
+//! readers takes it unchanged.
+//!
+//! The grid is north-up like a map image: row 0 is the largest `y`.
+
+use crate::map::{Colormap, Heightmap};
+use image::RgbaImage;
+use std::path::Path;
+
+/// Height levels per cell width. With one level per unit (`--vertical 1`)
+/// and one cell per stud (`--size 1`, 10 units) this keeps the terrain at
+/// its true proportions: a rise of one cell width is one stud tall.
+pub const LEVELS_PER_CELL: f64 = 10.0;
+
+/// The most cells a cloud may be binned into. Past this the cell size is
+/// almost certainly in the wrong unit (centimetres for metres), and the grid
+/// is refused with that hint rather than allocated.
+pub const MAX_CLOUD_CELLS: u64 = 1 << 26;
+
+/// One point: position, and colour if the file has one.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Point {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    pub rgb: Option<[u8; 3]>,
+}
+
+/// How a cell's points become one height.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum CellReduce {
+    /// The highest point: the canopy and rooftops of a first-return scan.
+    #[default]
+    Max,
+    Mean,
+    /// The lowest point: the closest to bare ground under vegetation.
+    Min,
+}
+
+/// How to grid a cloud.
+#[derive(Clone, Copy, Debug)]
+pub struct PointCloudOptions {
+    /// The width of one cell, in the points' own units.
+    pub cell_size: f64,
+    pub reduce: CellReduce,
+    /// How far, in cells, an empty cell looks for filled ones to interpolate
+    /// from. 0 leaves every empty cell empty.
+    pub fill_radius: u32,
+}
+
+impl Default for PointCloudOptions {
+    fn default() -> Self {
+        PointCloudOptions {
+            cell_size: 1.0,
+            reduce: CellReduce::Max,
+            fill_radius: 8,
+        }
+    }
+}
+
+/// A gridded point cloud.
+pub struct PointCloudMap {
+    width: u32,
+    height: u32,
+    /// Height level per cell; `None` where no point landed and no gap fill
+    /// reached.
+    levels: Vec<Option<u32>>,
+    colors: Option<Vec<[u8; 3]>>,
+    max_level: u32,
+}
+
+impl Heightmap for PointCloudMap {
+    fn at(&self, x: u32, y: u32) -> u32 {
+        self.levels[(x + y * self.width) as usize].unwrap_or(0)
+    }
+
+    fn size(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+}
+
+/// The points' own colours when they carry RGB, and a grey ramp of the
+/// height otherwise -- what a heightmap image used as its own colormap shows.
+/// Empty cells are transparent, so `--cull` drops them.
+impl Colormap for PointCloudMap {
+    fn at(&self, x: u32, y: u32) -> [u8; 4] {
+        let i = (x + y * self.width) as usize;
+        let Some(level) = self.levels[i] else {
+            return [0; 4];
+        };
+        match &self.colors {
+            Some(colors) => {
+                let [r, g, b] = colors[i];
+                [r, g, b, 255]
+            }
+            None => {
+                let v = (level as u64 * 255 / self.max_level.max(1) as u64) as u8;
+                [v, v, v, 255]
+            }
+        }
+    }
+
+    fn size(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+}
+
+impl PointCloudMap {
+    /// Whether the points carried colour. Without it the colormap is a grey
+    /// ramp of the heights.
+    pub fn has_rgb(&self) -> bool {
+        self.colors.is_some()
+    }
+
+    /// The highest level in the grid, for the footprint readout.
+    pub fn max_level(&self) -> u32 {
+        self.max_level
+    }
+
+    /// The colormap as an image, for the save preview and for
+    /// `ColormapPNG::from_image`.
+    pub fn color_image(&self) -> RgbaImage {
+        RgbaImage::from_fn(self.width, self.height, |x, y| {
+            image::Rgba(Colormap::at(self, x, y))
+        })
+    }
+
+    /// Bin `points` into a grid. See the module docs.
+    pub fn from_points(points: &[Point], options: &PointCloudOptions) -> Result<Self, String> {
+        if !(options.cell_size > 0.0 && options.cell_size.is_finite()) {
+            return Err(format!(
+                "the cell size must be a positive number, got {}",
+                options.cell_size
+            ));
+        }
+        let points: Vec<&Point> = points
+            .iter()
+            .filter(|p| p.x.is_finite() && p.y.is_finite() && p.z.is_finite())
+            .collect();
+        if points.is_empty() {
+            return Err("the point cloud has no points in it".to_string());
+        }
+
+        let (mut min, mut max) = ([f64::INFINITY; 3], [f64::NEG_INFINITY; 3]);
+        for p in &points {
+            for (a, v) in [p.x, p.y, p.z].into_iter().enumerate() {
+                min[a] = min[a].min(v);
+                max[a] = max[a].max(v);
+            }
+        }
+        let cell = options.cell_size;
+        // The casts saturate at u64::MAX for a tiny cell, so neither the +1
+        // nor the product may wrap: an overflow is simply over the limit.
+        let width = (((max[0] - min[0]) / cell).floor() as u64).saturating_add(1);
+        let height = (((max[1] - min[1]) / cell).floor() as u64).saturating_add(1);
+        if width.checked_mul(height).is_none_or(|n| n > MAX_CLOUD_CELLS) {
+            return Err(format!(
+                "a cell size of {cell} makes a {width} x {height} grid, more than the \
+                 {MAX_CLOUD_CELLS} cells this converter will build. The points span {:.1} x \
+                 {:.1} in their own units; raise the cell size (is it in the same unit as the \
+                 points?)",
+                max[0] - min[0],
+                max[1] - min[1],
+            ));
+        }
+        let (width, height) = (width as u32, height as u32);
+        let cells = (width * height) as usize;
+
+        // Accumulate per cell: count, sum, min, max, and colour sums.
+        let mut count = vec![0u32; cells];
+        let mut sum = vec![0f64; cells];
+        let mut low = vec![f64::INFINITY; cells];
+        let mut high = vec![f64::NEG_INFINITY; cells];
+        let has_rgb = points.iter().any(|p| p.rgb.is_some());
+        let mut rgb_sum = vec![[0u64; 3]; if has_rgb { cells } else { 0 }];
+        let mut rgb_count = vec![0u32; if has_rgb { cells } else { 0 }];
+        for p in &points {
+            let x = (((p.x - min[0]) / cell).floor() as u32).min(width - 1);
+            let y = (((max[1] - p.y) / cell).floor() as u32).min(height - 1);
+            let i = (x + y * width) as usize;
+            count[i] += 1;
+            sum[i] += p.z;
+            low[i] = low[i].min(p.z);
+            high[i] = high[i].max(p.z);
+            if let Some(rgb) = p.rgb {
+                for c in 0..3 {
+                    rgb_sum[i][c] += rgb[c] as u64;
+                }
+                rgb_count[i] += 1;
+            }
+        }
+
+        let mut z: Vec<Option<f64>> = (0..cells)
+            .map(|i| {
+                (count[i] > 0).then(|| match options.reduce {
+                    CellReduce::Max => high[i],
+                    CellReduce::Mean => sum[i] / count[i] as f64,
+                    CellReduce::Min => low[i],
+                })
+            })
+            .collect();
+        let mut colors: Option<Vec<Option<[f64; 3]>>> = has_rgb.then(|| {
+            (0..cells)
+                .map(|i| {
+                    (rgb_count[i] > 0).then(|| rgb_sum[i].map(|s| s as f64 / rgb_count[i] as f64))
+                })
+                .collect()
+        });
+
+        fill_gaps(width, height, options.fill_radius, &mut z, colors.as_mut());
+
+        let z_min = z.iter().flatten().cloned().fold(f64::INFINITY, f64::min);
+        let levels: Vec<Option<u32>> = z
+            .iter()
+            .map(|z| z.map(|z| ((z - z_min) / cell * LEVELS_PER_CELL).round() as u32))
+            .collect();
+        let max_level = levels.iter().flatten().cloned().max().unwrap_or(0);
+        Ok(PointCloudMap {
+            width,
+            height,
+            levels,
+            colors: colors.map(|colors| {
+                colors
+                    .into_iter()
+                    .map(|c| c.map_or([0; 3], |c| c.map(|v| v.round().clamp(0.0, 255.0) as u8)))
+                    .collect()
+            }),
+            max_level,
+        })
+    }
+
+    /// Read and grid one or more point files, all into one cloud.
+    pub fn open(files: &[impl AsRef<Path>], options: &PointCloudOptions) -> Result<Self, String> {
+        let mut points = vec![];
+        for file in files {
+            points.extend(read_points(file.as_ref())?);
+        }
+        Self::from_points(&points, options)
+    }
+}
+
+/// The point cloud formats, by extension.
+pub fn is_point_cloud(file: &Path) -> bool {
+    matches!(
+        file.extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_lowercase())
+            .as_deref(),
+        Some("xyz") | Some("csv") | Some("las") | Some("laz")
+    )
+}
+
+/// Read a point file, chosen by extension.
+pub fn read_points(file: &Path) -> Result<Vec<Point>, String> {
+    let ext = file
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase());
+    // Refused by name before reading: there is no decoder to read it with.
+    if ext.as_deref() == Some("laz") {
+        return Err(format!(
+            "{}: compressed LAZ is not supported; decompress it to .las first (e.g. with \
+             laszip or PDAL)",
+            file.display()
+        ));
+    }
+    let bytes = std::fs::read(file)
+        .map_err(|e| format!("Could not read points {}: {e}", file.display()))?;
+    let points = match ext.as_deref() {
+        Some("las") => parse_las(&bytes),
+        _ => parse_xyz(&String::from_utf8_lossy(&bytes)),
+    };
+    points.map_err(|e| format!("{}: {e}", file.display()))
+}
+
+/// Parse whitespace-, comma- or semicolon-separated points, `x y z` with an
+/// optional `r g b` after them.
+///
+/// A first line that is not numbers is a header, and its column names are
+/// used when it has them (`x`, `y`, `z`, and `r`/`red` etc.), so a CSV whose
+/// colour sits in columns 7-9 still reads. Colour channels are 0-255, or
+/// 16-bit if any exceeds 255, or 0-1 if none exceeds 1.
+pub fn parse_xyz(text: &str) -> Result<Vec<Point>, String> {
+    let split = |line: &str| -> Vec<String> {
+        line.split(|c: char| c == ',' || c == ';' || c.is_whitespace())
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+            .collect()
+    };
+    let mut columns = [0, 1, 2];
+    let mut rgb_columns: Option<[usize; 3]> = None;
+    let mut header_done = false;
+    let mut rows: Vec<(usize, Vec<f64>)> = vec![];
+
+    for (n, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with("//") {
+            continue;
+        }
+        let fields = split(line);
+        let numbers: Result<Vec<f64>, _> = fields.iter().map(|f| f.parse::<f64>()).collect();
+        match numbers {
+            Ok(values) => {
+                header_done = true;
+                rows.push((n, values));
+            }
+            Err(_) if !header_done => {
+                header_done = true;
+                let names: Vec<String> = fields.iter().map(|f| f.to_lowercase()).collect();
+                let find = |options: &[&str]| {
+                    names
+                        .iter()
+                        .position(|n| options.contains(&n.trim_matches('"')))
+                };
+                if let (Some(x), Some(y), Some(z)) = (find(&["x"]), find(&["y"]), find(&["z"])) {
+                    columns = [x, y, z];
+                }
+                if let (Some(r), Some(g), Some(b)) = (
+                    find(&["r", "red"]),
+                    find(&["g", "green"]),
+                    find(&["b", "blue"]),
+                ) {
+                    rgb_columns = Some([r, g, b]);
+                }
+            }
+            Err(_) => {
+                return Err(format!("line {}: '{line}' is not a row of numbers", n + 1));
+            }
+        }
+    }
+    if rows.is_empty() {
+        return Err("the file has no points in it".to_string());
+    }
+
+    // Positional colour when the header did not name it: three more
+    // columns after x, y and z.
+    let rgb_columns =
+        rgb_columns.or_else(|| rows.iter().all(|(_, v)| v.len() >= 6).then_some([3, 4, 5]));
+    let channel_max = rgb_columns.map_or(0.0, |cols| {
+        rows.iter()
+            .flat_map(|(_, v)| cols.iter().filter_map(|&c| v.get(c)))
+            .cloned()
+            .fold(0.0, f64::max)
+    });
+    let channel_scale = if channel_max > 255.0 {
+        255.0 / 65535.0
+    } else if channel_max <= 1.0 {
+        255.0
+    } else {
+        1.0
+    };
+
+    rows.into_iter()
+        .map(|(n, v)| {
+            let get = |c: usize| {
+                v.get(c).cloned().ok_or_else(|| {
+                    format!(
+                        "line {}: expected at least {} columns, got {}",
+                        n + 1,
+                        c + 1,
+                        v.len()
+                    )
+                })
+            };
+            let rgb = match rgb_columns {
+                Some(cols) => {
+                    let mut rgb = [0u8; 3];
+                    for (out, c) in rgb.iter_mut().zip(cols) {
+                        *out = (get(c)? * channel_scale).round().clamp(0.0, 255.0) as u8;
+                    }
+                    Some(rgb)
+                }
+                None => None,
+            };
+            Ok(Point {
+                x: get(columns[0])?,
+                y: get(columns[1])?,
+                z: get(columns[2])?,
+                rgb,
+            })
+        })
+        .collect()
+}
+
+/// Parse an uncompressed LAS file (versions 1.0-1.4, point formats 0-10).
+pub fn parse_las(bytes: &[u8]) -> Result<Vec<Point>, String> {
+    let short = || "the file ends inside its LAS header".to_string();
+    let u8_at = |o: usize| bytes.get(o).cloned().ok_or_else(short);
+    let u16_at = |o: usize| -> Result<u16, String> {
+        Ok(u16::from_le_bytes(
+            bytes.get(o..o + 2).ok_or_else(short)?.try_into().unwrap(),
+        ))
+    };
+    let u32_at = |o: usize| -> Result<u32, String> {
+        Ok(u32::from_le_bytes(
+            bytes.get(o..o + 4).ok_or_else(short)?.try_into().unwrap(),
+        ))
+    };
+    let f64_at = |o: usize| -> Result<f64, String> {
+        Ok(f64::from_le_bytes(
+            bytes.get(o..o + 8).ok_or_else(short)?.try_into().unwrap(),
+        ))
+    };
+
+    if !bytes.starts_with(b"LASF") {
+        return Err("not a LAS file (it does not start with 'LASF')".to_string());
+    }
+    let minor = u8_at(25)?;
+    let offset = u32_at(96)? as usize;
+    let raw_format = u8_at(104)?;
+    // Bits 6-7 mark a LAZ-compressed file written with a .las name.
+    if raw_format & 0xc0 != 0 {
+        return Err(
+            "this LAS file is LAZ-compressed; decompress it first (e.g. with laszip or PDAL)"
+                .to_string(),
+        );
+    }
+    let format = raw_format & 0x3f;
+    let record = u16_at(105)? as usize;
+    let mut count = u32_at(107)? as u64;
+    if count == 0 && minor >= 4 {
+        let b = bytes.get(247..255).ok_or_else(short)?;
+        count = u64::from_le_bytes(b.try_into().unwrap());
+    }
+    let scale = [f64_at(131)?, f64_at(139)?, f64_at(147)?];
+    let origin = [f64_at(155)?, f64_at(163)?, f64_at(171)?];
+
+    let rgb_offset = match format {
+        2 => Some(20),
+        3 | 5 => Some(28),
+        7 | 8 | 10 => Some(30),
+        0 | 1 | 4 | 6 | 9 => None,
+        other => return Err(format!("unknown LAS point format {other}")),
+    };
+    if record < rgb_offset.map_or(12, |o| o + 6) {
+        return Err(format!(
+            "point records of {record} bytes are too short for point format {format}"
+        ));
+    }
+    let needed = (count as usize)
+        .checked_mul(record)
+        .and_then(|n| n.checked_add(offset))
+        .ok_or("the point count overflows")?;
+    if needed > bytes.len() {
+        return Err(format!(
+            "the header promises {count} points, but the file ends after {}",
+            bytes.len().saturating_sub(offset) / record.max(1)
+        ));
+    }
+
+    let records = bytes[offset..needed].chunks_exact(record);
+    let channel = |r: &[u8], o: usize| u16::from_le_bytes(r[o..o + 2].try_into().unwrap());
+    // LAS colour is 16-bit, but many writers store 8-bit values in it. As
+    // PDAL and CloudCompare do, a cloud whose every channel fits in a byte is
+    // read as 8-bit rather than shifted down to near black.
+    let shift = match rgb_offset {
+        Some(o) => {
+            let max = records.clone().flat_map(|r| [0, 2, 4].map(|c| channel(r, o + c))).max();
+            if max.unwrap_or(0) > 255 { 8 } else { 0 }
+        }
+        None => 8,
+    };
+
+    Ok(records
+        .map(|r| {
+            let i32_at = |o: usize| i32::from_le_bytes(r[o..o + 4].try_into().unwrap());
+            Point {
+                x: i32_at(0) as f64 * scale[0] + origin[0],
+                y: i32_at(4) as f64 * scale[1] + origin[1],
+                z: i32_at(8) as f64 * scale[2] + origin[2],
+                rgb: rgb_offset.map(|o| [0, 2, 4].map(|c| (channel(r, o + c) >> shift) as u8)),
+            }
+        })
+        .collect())
+}
+
+/// Fill empty cells by inverse-distance weighting over the filled cells
+/// within `radius`. Searches outward ring by ring and stops once it has a
+/// handful of samples, so a dense area is interpolated from its nearest
+/// neighbours rather than everything in range. Reads only the ORIGINAL
+/// samples, never a cell filled earlier in the same pass, so the result does
+/// not depend on scan order.
+fn fill_gaps(
+    width: u32,
+    height: u32,
+    radius: u32,
+    z: &mut [Option<f64>],
+    mut colors: Option<&mut Vec<Option<[f64; 3]>>>,
+) {
+    const ENOUGH: usize = 8;
+    if radius == 0 {
+        return;
+    }
+    let source_z = z.to_vec();
+    let source_colors = colors.as_ref().map(|c| c.to_vec());
+
+    for y in 0..height as i64 {
+        for x in 0..width as i64 {
+            let i = (x + y * width as i64) as usize;
+            if source_z[i].is_some() {
+                continue;
+            }
+            let mut samples: Vec<(f64, usize)> = vec![];
+            for r in 1..=radius as i64 {
+                for dy in -r..=r {
+                    for dx in -r..=r {
+                        if dx.abs() != r && dy.abs() != r {
+                            continue;
+                        }
+                        let (sx, sy) = (x + dx, y + dy);
+                        if sx < 0 || sy < 0 || sx >= width as i64 || sy >= height as i64 {
+                            continue;
+                        }
+                        let j = (sx + sy * width as i64) as usize;
+                        if source_z[j].is_some() {
+                            samples.push((1.0 / (dx * dx + dy * dy) as f64, j));
+                        }
+                    }
+                }
+                if samples.len() >= ENOUGH {
+                    break;
+                }
+            }
+            if samples.is_empty() {
+                continue;
+            }
+            let total: f64 = samples.iter().map(|s| s.0).sum();
+            z[i] = Some(
+                samples
+                    .iter()
+                    .map(|&(w, j)| w * source_z[j].unwrap())
+                    .sum::<f64>()
+                    / total,
+            );
+            if let (Some(colors), Some(source)) = (colors.as_mut(), &source_colors) {
+                let mut rgb = [0.0; 3];
+                let mut weight = 0.0;
+                for &(w, j) in &samples {
+                    if let Some(c) = source[j] {
+                        for k in 0..3 {
+                            rgb[k] += w * c[k];
+                        }
+                        weight += w;
+                    }
+                }
+                if weight > 0.0 {
+                    colors[i] = Some(rgb.map(|v| v / weight));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn p(x: f64, y: f64, z: f64) -> Point {
+        Point { x, y, z, rgb: None }
+    }
+
+    fn grid(points: &[Point], reduce: CellReduce, fill_radius: u32) -> PointCloudMap {
+        PointCloudMap::from_points(
+            points,
+            &PointCloudOptions {
+                cell_size: 1.0,
+                reduce,
+                fill_radius,
+            },
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn each_reduction_picks_its_own_height() {
+        // Two points in cell (0, 0), one alone in cell (1, 0) at z = 0.
+        let points = [p(0.2, 0.5, 1.0), p(0.7, 0.5, 3.0), p(1.5, 0.5, 0.0)];
+        let level = |reduce| Heightmap::at(&grid(&points, reduce, 0), 0, 0);
+        assert_eq!(level(CellReduce::Max), 30);
+        assert_eq!(level(CellReduce::Mean), 20);
+        assert_eq!(level(CellReduce::Min), 10);
+    }
+
+    #[test]
+    fn the_grid_is_north_up() {
+        let map = grid(&[p(0.0, 0.0, 0.0), p(0.0, 2.0, 5.0)], CellReduce::Max, 0);
+        assert_eq!(Heightmap::size(&map), (1, 3));
+        assert_eq!(Heightmap::at(&map, 0, 0), 50, "the largest y is row 0");
+        assert_eq!(Heightmap::at(&map, 0, 2), 0);
+    }
+
+    #[test]
+    fn gaps_are_filled_by_inverse_distance_and_only_within_reach() {
+        let points = [p(0.5, 0.5, 0.0), p(2.5, 0.5, 2.0), p(9.5, 0.5, 0.0)];
+        let filled = grid(&points, CellReduce::Max, 1);
+        // Cell 1 sits between z = 0 and z = 2, equally far from both.
+        assert_eq!(Heightmap::at(&filled, 1, 0), 10);
+        assert_eq!(Colormap::at(&filled, 1, 0)[3], 255);
+        // Cell 5 is 3 cells from any sample: out of reach, so transparent.
+        assert_eq!(Colormap::at(&filled, 5, 0), [0; 4]);
+
+        let unfilled = grid(&points, CellReduce::Max, 0);
+        assert_eq!(Colormap::at(&unfilled, 1, 0), [0; 4]);
+    }
+
+    #[test]
+    fn rgb_points_make_a_colormap_and_plain_ones_a_grey_ramp() {
+        let coloured = [
+            Point {
+                rgb: Some([255, 0, 0]),
+                ..p(0.0, 0.0, 0.0)
+            },
+            Point {
+                rgb: Some([0, 0, 255]),
+                ..p(1.0, 0.0, 1.0)
+            },
+        ];
+        let map = grid(&coloured, CellReduce::Max, 0);
+        assert!(map.has_rgb());
+        assert_eq!(Colormap::at(&map, 0, 0), [255, 0, 0, 255]);
+
+        let plain = grid(&[p(0.0, 0.0, 0.0), p(1.0, 0.0, 1.0)], CellReduce::Max, 0);
+        assert!(!plain.has_rgb());
+        assert_eq!(Colormap::at(&plain, 0, 0), [0, 0, 0, 255]);
+        assert_eq!(Colormap::at(&plain, 1, 0), [255, 255, 255, 255]);
+    }
+
+    #[test]
+    fn xyz_reads_positional_and_named_columns() {
+        let plain = parse_xyz("# comment\n1 2 3\n4,5,6\n").unwrap();
+        assert_eq!(plain, vec![p(1.0, 2.0, 3.0), p(4.0, 5.0, 6.0)]);
+
+        let named = parse_xyz("id,Z,X,Y,Red,Green,Blue\n7,3,1,2,65535,0,32896\n").unwrap();
+        assert_eq!(named[0].x, 1.0);
+        assert_eq!(named[0].z, 3.0);
+        assert_eq!(
+            named[0].rgb,
+            Some([255, 0, 128]),
+            "16-bit colour is scaled down"
+        );
+
+        let unit = parse_xyz("0 0 0 1 0.5 0\n").unwrap();
+        assert_eq!(unit[0].rgb, Some([255, 128, 0]), "0-1 colour is scaled up");
+
+        assert!(
+            parse_xyz("1 2 3\nfoo bar\n")
+                .unwrap_err()
+                .contains("line 2")
+        );
+        assert!(parse_xyz("x y\n1 2\n").unwrap_err().contains("columns"));
+    }
+
+    /// A minimal LAS 1.2 file, point format 2, with the given points.
+    fn las(points: &[([i32; 3], [u16; 3])]) -> Vec<u8> {
+        let mut header = vec![0u8; 227];
+        header[0..4].copy_from_slice(b"LASF");
+        header[24] = 1;
+        header[25] = 2;
+        header[94..96].copy_from_slice(&227u16.to_le_bytes());
+        header[96..100].copy_from_slice(&227u32.to_le_bytes());
+        header[104] = 2;
+        header[105..107].copy_from_slice(&26u16.to_le_bytes());
+        header[107..111].copy_from_slice(&(points.len() as u32).to_le_bytes());
+        for (o, v) in [
+            (131, 0.01f64),
+            (139, 0.01),
+            (147, 0.01),
+            (155, 100.0),
+            (163, 0.0),
+            (171, 0.0),
+        ] {
+            header[o..o + 8].copy_from_slice(&v.to_le_bytes());
+        }
+        for (pos, rgb) in points {
+            let mut record = vec![0u8; 26];
+            for (a, v) in pos.iter().enumerate() {
+                record[a * 4..a * 4 + 4].copy_from_slice(&v.to_le_bytes());
+            }
+            for (c, v) in rgb.iter().enumerate() {
+                record[20 + c * 2..22 + c * 2].copy_from_slice(&v.to_le_bytes());
+            }
+            header.extend(record);
+        }
+        header
+    }
+
+    #[test]
+    fn las_points_are_scaled_offset_and_coloured() {
+        let points = parse_las(&las(&[([150, -200, 1234], [65535, 0, 256])])).unwrap();
+        assert_eq!(points.len(), 1);
+        assert!((points[0].x - 101.5).abs() < 1e-9);
+        assert!((points[0].y + 2.0).abs() < 1e-9);
+        assert!((points[0].z - 12.34).abs() < 1e-9);
+        assert_eq!(points[0].rgb, Some([255, 0, 1]));
+    }
+
+    /// A cloud written with 8-bit colour in the 16-bit fields is read as
+    /// 8-bit, not shifted down to black.
+    #[test]
+    fn an_8_bit_coloured_las_keeps_its_colour() {
+        let points = parse_las(&las(&[
+            ([0, 0, 0], [255, 128, 0]),
+            ([1, 1, 1], [12, 34, 56]),
+        ]))
+        .unwrap();
+        assert_eq!(points[0].rgb, Some([255, 128, 0]));
+        assert_eq!(points[1].rgb, Some([12, 34, 56]));
+    }
+
+    #[test]
+    fn broken_las_files_are_refused_by_name() {
+        assert!(parse_las(b"PK zip").unwrap_err().contains("not a LAS"));
+        let mut cut = las(&[([0, 0, 0], [0, 0, 0])]);
+        cut.truncate(cut.len() - 1);
+        assert!(parse_las(&cut).unwrap_err().contains("promises 1 points"));
+        let mut laz = las(&[]);
+        laz[104] |= 0x80;
+        assert!(parse_las(&laz).unwrap_err().contains("LAZ"));
+    }
+
+    #[test]
+    fn a_cell_size_in_the_wrong_unit_is_refused() {
+        let points = [p(0.0, 0.0, 0.0), p(100_000.0, 100_000.0, 0.0)];
+        let err = PointCloudMap::from_points(&points, &PointCloudOptions::default())
+            .err()
+            .unwrap();
+        assert!(err.contains("raise the cell size"), "{err}");
+        let zero = PointCloudOptions {
+            cell_size: 0.0,
+            ..Default::default()
+        };
+        assert!(PointCloudMap::from_points(&points, &zero).is_err());
+        // Small enough that both sides saturate: refused, not an overflow.
+        let tiny = PointCloudOptions {
+            cell_size: f64::MIN_POSITIVE,
+            ..Default::default()
+        };
+        let err = PointCloudMap::from_points(&points, &tiny).err().unwrap();
+        assert!(err.contains("raise the cell size"), "{err}");
+    }
+}
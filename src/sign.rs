@@ -0,0 +1,400 @@
+//! Sign maker: plain text -- a string or a text file -- laid out as one
+//! `Component_TextDisplay`, with an optional backing plate behind it.
+//!
+//! Text mode draws PICTURES out of glyphs; this draws words. The whole sign is
+//! one component on one anchor cube ([`add_text_block_styled`]), so inline
+//! `<color="RRGGBB">` runs carry across line breaks exactly as the game draws
+//! them, and the alignment is the component's own anchor rather than padding
+//! with spaces -- which would only line up in a monospace font.
+//!
+//! Word wrap counts characters, not rendered width: the game measures glyphs,
+//! this crate cannot. The plate is sized the same way, from the font preset's
+//! calibrated advance (one fill glyph is `1 / char_repeat` of a square pixel),
+//! so it fits a monospace font and is an estimate for a proportional one.
+
+use crate::text::{
+    ANCHOR_CUBE_HALF, DEFAULT_OUTLINE_WIDTH, FACE_X_POSITIVE, MAX_COMPONENT_CHARS, OUTLINE_NONE,
+    OUTLINE_OUTLINED, TextOptions, Vector2f, add_text_block_styled,
+};
+use crate::util::MAX_BRICK_HALF_EXTENT;
+use brdb::{
+    Brick, BrickSize, BrickType, Color, Position, PrefabJson, Vector3f, World,
+    assets::bricks::PB_DEFAULT_MICRO_BRICK,
+};
+
+/// Default height of one line of text in world units -- one stud, where text
+/// mode's default of one unit per pixel row would be unreadably small.
+pub const DEFAULT_SIGN_LINE: f32 = 10.0;
+
+/// Horizontal alignment of the sign's lines.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum SignAlign {
+    #[default]
+    Left,
+    Centre,
+    Right,
+}
+
+impl SignAlign {
+    /// The component `Anchor` for this alignment: the cube names the block's
+    /// top-left, top-centre or top-right point, so lines hang down from it.
+    pub fn anchor(&self) -> Vector2f {
+        let x = match self {
+            SignAlign::Left => 0.0,
+            SignAlign::Centre => 0.5,
+            SignAlign::Right => 1.0,
+        };
+        Vector2f { x, y: 0.0 }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SignOptions {
+    /// Font, geometry and material. `line_world_height` is the height of one
+    /// line of text in world units.
+    pub text: TextOptions,
+    /// Colour of any text outside an inline `<color>` run.
+    pub color: [u8; 3],
+    pub align: SignAlign,
+    /// Wrap lines longer than this many visible characters at word breaks
+    /// (None = only the input's own line breaks).
+    pub wrap: Option<usize>,
+    /// Colour of a backing plate behind the text (None = no plate).
+    pub plate: Option<[u8; 3]>,
+    /// Draw a black outline around the glyphs.
+    pub outline: bool,
+}
+
+/// A `RRGGBB` or `#RRGGBB` hex colour.
+pub fn parse_hex_color(s: &str) -> Option<[u8; 3]> {
+    let hex = s.strip_prefix('#').unwrap_or(s);
+    if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let v = u32::from_str_radix(hex, 16).ok()?;
+    Some([(v >> 16) as u8, (v >> 8) as u8, v as u8])
+}
+
+/// Characters of `s` the game draws: everything outside `<...>` rich-text
+/// tags.
+pub fn visible_len(s: &str) -> usize {
+    let mut n = 0;
+    let mut in_tag = false;
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        if in_tag {
+            in_tag = c != '>';
+        } else if c == '<'
+            && chars
+                .peek()
+                .is_some_and(|&d| d == '/' || d.is_ascii_alphabetic())
+        {
+            in_tag = true;
+        } else {
+            n += 1;
+        }
+    }
+    n
+}
+
+/// Split `text` into the sign's lines: the input's own line breaks, then each
+/// line word-wrapped to `wrap` visible characters. A word longer than the
+/// whole width is broken mid-word rather than overflowing. Tags stay with the
+/// word they are written against and cost nothing.
+pub fn wrap_lines(text: &str, wrap: Option<usize>) -> Vec<String> {
+    let mut out = Vec::new();
+    for line in text.lines() {
+        let line = line.trim_end();
+        let Some(width) = wrap.filter(|&w| w > 0) else {
+            out.push(line.to_string());
+            continue;
+        };
+        let mut cur = String::new();
+        let mut cur_len = 0;
+        for word in line.split_whitespace() {
+            let mut word = word.to_string();
+            let mut len = visible_len(&word);
+            let gap = usize::from(cur_len > 0);
+            if cur_len + gap + len <= width {
+                if gap == 1 {
+                    cur.push(' ');
+                }
+                cur.push_str(&word);
+                cur_len += gap + len;
+                continue;
+            }
+            if cur_len > 0 {
+                out.push(std::mem::take(&mut cur));
+            }
+            while len > width {
+                let (head, tail) = split_visible(&word, width);
+                out.push(head);
+                word = tail;
+                len = visible_len(&word);
+            }
+            cur = word;
+            cur_len = len;
+        }
+        out.push(cur);
+    }
+    out
+}
+
+/// `s` split after `n` visible characters (tags before the split point stay
+/// in the head).
+fn split_visible(s: &str, n: usize) -> (String, String) {
+    let at = s
+        .char_indices()
+        .map(|(i, _)| i)
+        .find(|&i| i > 0 && visible_len(&s[..i]) == n)
+        .unwrap_or(s.len());
+    (s[..at].to_string(), s[at..].to_string())
+}
+
+/// The component text for `lines`: the base colour as one opening tag, so
+/// inline `<color>` runs inside override it and the rest falls back to it.
+pub fn sign_text(lines: &[String], color: [u8; 3]) -> String {
+    format!(
+        "<color=\"{}{}{}\">{}",
+        crate::util::hex_pair(color[0]),
+        crate::util::hex_pair(color[1]),
+        crate::util::hex_pair(color[2]),
+        lines.join("\n")
+    )
+}
+
+/// Build the sign into `world`, returning its size in (columns, lines), and
+/// mark the world a prefab bounded by the plate and the text's own extent.
+///
+/// The text stands upright facing world +X like every text render, hanging
+/// down from an anchor cube at its top edge. Coordinates stay non-negative
+/// (see [`crate::text::add_text_tiles`]): the plate, when present, sits at
+/// the origin and the cube rests on its front face. A plate wider or taller
+/// than one procedural brick can be is tiled.
+pub fn build_sign(world: &mut World, text: &str, opts: &SignOptions) -> Result<(u32, u32), String> {
+    let lines = wrap_lines(text, opts.wrap);
+    if lines.iter().all(|l| visible_len(l) == 0) {
+        return Err("the sign has no text".to_string());
+    }
+    let body = sign_text(&lines, opts.color);
+    let chars = body.chars().count();
+    if chars > MAX_COMPONENT_CHARS {
+        return Err(format!(
+            "the sign is {chars} characters, more than the {MAX_COMPONENT_CHARS} one \
+             TextDisplay holds"
+        ));
+    }
+
+    let t = &opts.text;
+    let cols = lines.iter().map(|l| visible_len(l)).max().unwrap_or(0);
+    let char_w = t.line_world_height * t.pitch_x / t.char_repeat.max(1) as f32;
+    let width = (cols as f32 * char_w).ceil() as i32;
+    let height = (lines.len() as f32 * t.line_world_height * t.pitch_y).ceil() as i32;
+    // half a line of margin around the text, at least a micro unit
+    let pad = ((t.line_world_height * t.pitch_y) / 2.0).ceil().max(1.0) as i32;
+
+    // plate half-extents; the text is centred on the plate either way, so
+    // the same coordinates serve a sign without one
+    let hy = (width + 2 * pad + 1) / 2;
+    let hz = (height + 2 * pad + 1) / 2;
+    // the text's right is world -Y: the left edge is the high-Y side
+    let left_y = hy + (width + 1) / 2;
+    let anchor_y = match opts.align {
+        SignAlign::Left => left_y,
+        SignAlign::Centre => hy,
+        SignAlign::Right => left_y - width,
+    };
+    let top_z = hz + (height + 1) / 2;
+    // plate 2 units thick at x 0..2; the cube rests on its front face
+    let cube_x = if opts.plate.is_some() {
+        2 + ANCHOR_CUBE_HALF
+    } else {
+        ANCHOR_CUBE_HALF
+    };
+
+    add_text_block_styled(
+        world,
+        body,
+        Position::new(cube_x, anchor_y, top_z),
+        t.line_height,
+        t.kerning,
+        Vector3f {
+            x: t.offset_x,
+            y: t.offset_y,
+            z: t.offset_z,
+        },
+        false,
+        FACE_X_POSITIVE,
+        opts.align.anchor(),
+        if opts.outline {
+            OUTLINE_OUTLINED
+        } else {
+            OUTLINE_NONE
+        },
+        DEFAULT_OUTLINE_WIDTH,
+        t,
+    );
+    if let Some(rgb) = opts.plate {
+        for &(y, half_y) in &plate_spans(2 * hy) {
+            for &(z, half_z) in &plate_spans(2 * hz) {
+                world.add_brick(Brick {
+                    asset: BrickType::Procedural {
+                        asset: PB_DEFAULT_MICRO_BRICK,
+                        size: BrickSize::new(1, half_y, half_z),
+                    },
+                    position: Position::new(1, y + i32::from(half_y), z + i32::from(half_z)),
+                    color: Color {
+                        r: rgb[0],
+                        g: rgb[1],
+                        b: rgb[2],
+                    },
+                    ..Default::default()
+                });
+            }
+        }
+    }
+    world.register_used_components();
+
+    // the bricks, and the text where it overhangs them: without a plate
+    // the only brick is the cube at the text's top edge
+    let (bmin, bmax) = world
+        .brick_bounds()
+        .unwrap_or((Position::ZERO, Position::ZERO));
+    let min = Position::new(bmin.x, bmin.y.min(left_y - width), bmin.z.min(top_z - height));
+    let max = Position::new(bmax.x, bmax.y.max(left_y), bmax.z.max(top_z));
+    world.meta.bundle.level_type = "Prefab".to_string();
+    world.meta.prefab = Some(PrefabJson::from_bounds(min, max));
+    Ok((cols as u32, lines.len() as u32))
+}
+
+/// `0..len` (even) cut into spans no procedural brick is too big for, as
+/// `(start, half extent)`.
+fn plate_spans(len: i32) -> Vec<(i32, u16)> {
+    let most = 2 * i32::from(MAX_BRICK_HALF_EXTENT);
+    (0..len)
+        .step_by(most as usize)
+        .map(|at| (at, ((len - at).min(most) / 2) as u16))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::text::FontPreset;
+
+    #[test]
+    fn tags_cost_nothing_and_a_lone_angle_bracket_is_text() {
+        assert_eq!(visible_len("<color=\"FF0000\">red</>"), 3);
+        assert_eq!(visible_len("a < b"), 5);
+    }
+
+    #[test]
+    fn lines_wrap_at_word_breaks_and_overlong_words_break() {
+        assert_eq!(
+            wrap_lines("the quick brown fox\nend", Some(10)),
+            ["the quick", "brown fox", "end"]
+        );
+        assert_eq!(wrap_lines("abcdefghij", Some(4)), ["abcd", "efgh", "ij"]);
+        assert_eq!(
+            wrap_lines("<color=\"FF0000\">hot</> stuff", Some(5)),
+            ["<color=\"FF0000\">hot</>", "stuff"],
+            "a tag does not count toward the width"
+        );
+        assert_eq!(wrap_lines("kept  as is ", None), ["kept  as is"]);
+    }
+
+    #[test]
+    fn a_sign_is_one_component_with_a_plate_behind_it() {
+        let opts = SignOptions {
+            text: FontPreset::MonaspaceArgon.options(10.0),
+            color: [255, 255, 0],
+            align: SignAlign::Centre,
+            wrap: Some(8),
+            plate: Some([40, 40, 40]),
+            outline: false,
+        };
+        let mut world = World::new();
+        let (cols, lines) = build_sign(&mut world, "hello sign world", &opts).unwrap();
+        assert_eq!((cols, lines), (5, 3));
+        assert_eq!(world.bricks.len(), 2, "anchor cube + plate");
+        let (min, _) = world.brick_bounds().unwrap();
+        assert!(min.x >= 0 && min.y >= 0 && min.z >= 0, "{min:?}");
+    }
+
+    /// A sign wider than one procedural brick can be gets a tiled plate:
+    /// every tile within the limit, and together exactly the plate's span.
+    #[test]
+    fn a_long_sign_tiles_its_plate() {
+        let opts = SignOptions {
+            text: FontPreset::MonaspaceArgon.options(40.0),
+            color: [255; 3],
+            align: SignAlign::Left,
+            wrap: None,
+            plate: Some([40, 40, 40]),
+            outline: false,
+        };
+        let mut world = World::new();
+        build_sign(&mut world, &"x".repeat(400), &opts).unwrap();
+        // (centre y, half y, half z) of every plate tile, in order along y
+        let mut tiles: Vec<(i32, u16, u16)> = world
+            .bricks
+            .iter()
+            .filter_map(|b| match b.asset {
+                // the plate is at x 0..2; the anchor cube rests in front
+                BrickType::Procedural { size, .. } if b.position.x == 1 => {
+                    Some((b.position.y, size.y, size.z))
+                }
+                _ => None,
+            })
+            .collect();
+        tiles.sort();
+        assert!(tiles.len() > 1, "{tiles:?}");
+        let mut edge = 0;
+        for &(y, half_y, half_z) in &tiles {
+            assert!(half_y <= MAX_BRICK_HALF_EXTENT && half_z <= MAX_BRICK_HALF_EXTENT);
+            assert_eq!(y - i32::from(half_y), edge, "edge to edge from y = 0: {tiles:?}");
+            edge = y + i32::from(half_y);
+        }
+    }
+
+    /// The prefab's bounds are the text's own extent: without a plate, no
+    /// wider than the text itself and the cube it hangs from.
+    #[test]
+    fn a_plateless_sign_is_bounded_by_its_text() {
+        let opts = SignOptions {
+            text: FontPreset::MonaspaceArgon.options(10.0),
+            color: [255; 3],
+            align: SignAlign::Centre,
+            wrap: None,
+            plate: None,
+            outline: false,
+        };
+        let mut world = World::new();
+        let (cols, lines) = build_sign(&mut world, "ab\ncd", &opts).unwrap();
+        let t = &opts.text;
+        let width = cols as f64 * f64::from(t.line_world_height * t.pitch_x) / t.char_repeat as f64;
+        let height = lines as f64 * f64::from(t.line_world_height * t.pitch_y);
+        let pivot = world.meta.prefab.expect("a prefab").pivots.bounds_pivot.half_extent;
+        let cube = f64::from(ANCHOR_CUBE_HALF);
+        let within = |half: f64, extent: f64| {
+            half >= extent.floor() / 2.0 && half <= extent.ceil() / 2.0 + cube
+        };
+        assert!(within(pivot.y, width) && within(pivot.z, height), "{pivot:?}");
+    }
+
+    #[test]
+    fn an_empty_or_oversized_sign_is_an_error() {
+        let opts = SignOptions {
+            text: FontPreset::MonaspaceArgon.options(10.0),
+            color: [255; 3],
+            align: SignAlign::Left,
+            wrap: None,
+            plate: None,
+            outline: false,
+        };
+        assert!(build_sign(&mut World::new(), " \n ", &opts).is_err());
+        let long = "x".repeat(MAX_COMPONENT_CHARS + 1);
+        let err = build_sign(&mut World::new(), &long, &opts).unwrap_err();
+        assert!(err.contains("characters"), "{err}");
+    }
+}
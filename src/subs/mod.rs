@@ -103,6 +103,12 @@ impl Subtitles {
         self.cues.is_empty()
     }
 
+    /// Every cue's start time, ascending -- the chapter list
+    /// `--chapters subtitles` seeks between.
+    pub fn starts(&self) -> Vec<f64> {
+        self.cues.iter().map(|c| c.start_s).collect()
+    }
+
     /// The subtitle text active at time `t_s`, or `""` if none covers it.
     ///
     /// `start_s <= t_s < end_s` (end exclusive, so two adjacent cues sharing
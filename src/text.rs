@@ -1,3 +1,4 @@
+use crate::dither::{self, Dither};
 use brdb::{
     AsBrdbValue, BrdbSchemaError, Brick, BrickSize, BrickType, Collision, Color, Direction,
     Position, PrefabJson, SavedBrickColor, Vector3f, WirePort, World,
@@ -50,12 +51,26 @@ impl FontPreset {
         }
     }
 
+    /// The preset a `--font` keyword names (case-insensitive): the short
+    /// keywords or the display name.
+    pub fn from_keyword(s: &str) -> Option<FontPreset> {
+        let s = s.to_lowercase();
+        match s.as_str() {
+            "monaspace" | "argon" => Some(FontPreset::MonaspaceArgon),
+            "iosevka" => Some(FontPreset::IosevkaTerm),
+            "orbitron" => Some(FontPreset::Orbitron),
+            _ => FontPreset::ALL
+                .into_iter()
+                .find(|p| p.name().to_lowercase() == s),
+        }
+    }
+
     /// Calibrated options for this font at the given pixel size (world units
     /// per pixel row). Geometry values may need manual re-calibration; UIs
     /// expose them for tweaking after the preset seeds them.
     pub fn options(&self, pixel_size: f32) -> TextOptions {
         let base = TextOptions {
-            font: self.font_asset(),
+            font: self.font_asset().to_string(),
             fill_char: '█',
             empty_char: ' ',
             char_repeat: 2,
@@ -74,6 +89,7 @@ impl FontPreset {
             mode: PixelMode::Color,
             luma_threshold: 128,
             invert: false,
+            dither: Dither::None,
             tile_override: None,
             // material defaults from the calibrated reference clipboards
             // (they also match the game's own field defaults)
@@ -112,6 +128,288 @@ impl FontPreset {
     }
 }
 
+/// A font preset read from a presets file (see [`parse_font_presets`]) rather
+/// than compiled in -- for in-game fonts the built-in [`FontPreset`]s do not
+/// cover. Geometry is stored at pixel size 1 and scales the way the built-ins'
+/// does: `line_height`, `kerning` and the offsets multiply by the pixel size;
+/// `line_offset` and the pitches do not.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CustomFontPreset {
+    /// Display name; also what `--font` matches (case-insensitive).
+    pub name: String,
+    /// BrickFontDescriptor asset name.
+    pub font_asset: String,
+    pub fill_char: char,
+    pub empty_char: char,
+    pub char_repeat: usize,
+    pub line_height: f32,
+    pub line_offset: f32,
+    pub kerning: f32,
+    pub offset_x: f32,
+    pub offset_y: f32,
+    pub offset_z: f32,
+    pub pitch_x: f32,
+    pub pitch_y: f32,
+}
+
+impl CustomFontPreset {
+    /// A preset for `font_asset` with every other field at the plain
+    /// monospace base (Iosevka Term's values): a file only has to list what
+    /// differs.
+    pub fn new(name: &str, font_asset: &str) -> Self {
+        let base = FontPreset::IosevkaTerm.options(1.0);
+        Self {
+            name: name.to_string(),
+            font_asset: font_asset.to_string(),
+            fill_char: base.fill_char,
+            empty_char: base.empty_char,
+            char_repeat: base.char_repeat,
+            line_height: base.line_height,
+            line_offset: base.line_offset,
+            kerning: base.kerning,
+            offset_x: base.offset_x,
+            offset_y: base.offset_y,
+            offset_z: base.offset_z,
+            pitch_x: base.pitch_x,
+            pitch_y: base.pitch_y,
+        }
+    }
+
+    /// Options for this font at the given pixel size, on the same non-geometry
+    /// defaults (material, thresholds, mode) as the built-in presets.
+    pub fn options(&self, pixel_size: f32) -> TextOptions {
+        TextOptions {
+            font: self.font_asset.clone(),
+            fill_char: self.fill_char,
+            empty_char: self.empty_char,
+            char_repeat: self.char_repeat,
+            line_height: self.line_height * pixel_size,
+            line_offset: self.line_offset,
+            kerning: self.kerning * pixel_size,
+            offset_x: self.offset_x * pixel_size,
+            offset_y: self.offset_y * pixel_size,
+            offset_z: self.offset_z * pixel_size,
+            pitch_x: self.pitch_x,
+            pitch_y: self.pitch_y,
+            ..FontPreset::IosevkaTerm.options(pixel_size)
+        }
+    }
+}
+
+/// Either kind of font preset, for the places (`--font`, the GUI's font
+/// list) that offer both.
+#[derive(Clone, Debug, PartialEq)]
+pub enum FontChoice {
+    Builtin(FontPreset),
+    Custom(CustomFontPreset),
+}
+
+impl FontChoice {
+    /// Display name for UIs.
+    pub fn name(&self) -> &str {
+        match self {
+            FontChoice::Builtin(p) => p.name(),
+            FontChoice::Custom(c) => &c.name,
+        }
+    }
+
+    /// Options for this preset at the given pixel size.
+    pub fn options(&self, pixel_size: f32) -> TextOptions {
+        match self {
+            FontChoice::Builtin(p) => p.options(pixel_size),
+            FontChoice::Custom(c) => c.options(pixel_size),
+        }
+    }
+
+    /// Resolve a `--font` value: the built-in keywords first, then the
+    /// custom presets by name (case-insensitive).
+    pub fn resolve(s: &str, custom: &[CustomFontPreset]) -> Option<FontChoice> {
+        if let Some(p) = FontPreset::from_keyword(s) {
+            return Some(FontChoice::Builtin(p));
+        }
+        let s = s.to_lowercase();
+        custom
+            .iter()
+            .find(|c| c.name.to_lowercase() == s)
+            .map(|c| FontChoice::Custom(c.clone()))
+    }
+}
+
+/// Parse a font presets file: a small TOML subset of `[[font]]` tables, one
+/// per preset, holding `key = value` lines.
+///
+/// ```toml
+/// [[font]]
+/// name = "Noto Mono"          # required; what --font matches
+/// asset = "NotoSansMono"      # required; the BrickFontDescriptor asset
+/// fill_char = "█"
+/// empty_char = " "
+/// char_repeat = 2
+/// line_height = 0.61          # the rest at pixel size 1
+/// line_offset = 0
+/// kerning = 0
+/// offset_x = 0
+/// offset_y = -0.2
+/// offset_z = 0
+/// pitch = 0.9375              # sets pitch_x and pitch_y together
+/// ```
+///
+/// Omitted keys keep [`CustomFontPreset::new`]'s values. Unknown keys, a
+/// duplicate name, and a name a built-in keyword already claims are errors
+/// naming the line, so a typo cannot silently fall back to a default.
+pub fn parse_font_presets(src: &str) -> Result<Vec<CustomFontPreset>, String> {
+    // (preset, line of its `[[font]]`, whether name/asset were given)
+    let mut out: Vec<(CustomFontPreset, usize, bool, bool)> = Vec::new();
+    for (i, raw) in src.lines().enumerate() {
+        let ln = i + 1;
+        let line = strip_toml_comment(raw).trim();
+        if line.is_empty() {
+            continue;
+        }
+        if line.starts_with('[') {
+            if line != "[[font]]" {
+                return Err(format!("line {ln}: expected [[font]], got {line}"));
+            }
+            out.push((CustomFontPreset::new("", ""), ln, false, false));
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            return Err(format!("line {ln}: expected key = value"));
+        };
+        let (key, value) = (key.trim(), value.trim());
+        let Some((preset, _, has_name, has_asset)) = out.last_mut() else {
+            return Err(format!("line {ln}: '{key}' before the first [[font]]"));
+        };
+        let num = || -> Result<f32, String> {
+            value
+                .parse::<f32>()
+                .ok()
+                .filter(|v| v.is_finite())
+                .ok_or_else(|| format!("line {ln}: {key} must be a number, got {value}"))
+        };
+        let string = || {
+            toml_string(value)
+                .ok_or_else(|| format!("line {ln}: {key} must be a quoted string, got {value}"))
+        };
+        let glyph = || -> Result<char, String> {
+            let s = string()?;
+            let mut chars = s.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => Ok(c),
+                _ => Err(format!("line {ln}: {key} must be exactly one character")),
+            }
+        };
+        match key {
+            "name" => {
+                preset.name = string()?;
+                *has_name = true;
+            }
+            "asset" => {
+                preset.font_asset = string()?;
+                *has_asset = true;
+            }
+            "fill_char" => preset.fill_char = glyph()?,
+            "empty_char" => preset.empty_char = glyph()?,
+            "char_repeat" => {
+                preset.char_repeat =
+                    value
+                        .parse::<usize>()
+                        .ok()
+                        .filter(|&n| n >= 1)
+                        .ok_or_else(|| {
+                            format!("line {ln}: char_repeat must be an integer >= 1, got {value}")
+                        })?
+            }
+            "line_height" => preset.line_height = num()?,
+            "line_offset" => preset.line_offset = num()?,
+            "kerning" => preset.kerning = num()?,
+            "offset_x" => preset.offset_x = num()?,
+            "offset_y" => preset.offset_y = num()?,
+            "offset_z" => preset.offset_z = num()?,
+            "pitch" => {
+                preset.pitch_x = num()?;
+                preset.pitch_y = preset.pitch_x;
+            }
+            "pitch_x" => preset.pitch_x = num()?,
+            "pitch_y" => preset.pitch_y = num()?,
+            _ => return Err(format!("line {ln}: unknown key '{key}'")),
+        }
+    }
+    let mut presets: Vec<CustomFontPreset> = Vec::with_capacity(out.len());
+    for (preset, ln, has_name, has_asset) in out {
+        if !has_name || preset.name.trim().is_empty() {
+            return Err(format!("line {ln}: [[font]] needs a name"));
+        }
+        if !has_asset || preset.font_asset.trim().is_empty() {
+            return Err(format!("line {ln}: font '{}' needs an asset", preset.name));
+        }
+        if FontPreset::from_keyword(&preset.name).is_some() {
+            return Err(format!(
+                "line {ln}: '{}' is already a built-in font preset",
+                preset.name
+            ));
+        }
+        if presets
+            .iter()
+            .any(|p| p.name.to_lowercase() == preset.name.to_lowercase())
+        {
+            return Err(format!("line {ln}: duplicate font '{}'", preset.name));
+        }
+        presets.push(preset);
+    }
+    Ok(presets)
+}
+
+/// `line` up to a `#` that is not inside a string.
+fn strip_toml_comment(line: &str) -> &str {
+    let mut quote = None;
+    let mut escaped = false;
+    for (i, c) in line.char_indices() {
+        match quote {
+            Some('"') if escaped => escaped = false,
+            Some('"') if c == '\\' => escaped = true,
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if c == '"' || c == '\'' => quote = Some(c),
+            None if c == '#' => return &line[..i],
+            None => {}
+        }
+    }
+    line
+}
+
+/// A TOML basic (`"..."`, with `\\ \" \n \t \uXXXX` escapes) or literal
+/// (`'...'`) string's contents.
+fn toml_string(v: &str) -> Option<String> {
+    if let Some(lit) = v.strip_prefix('\'').and_then(|r| r.strip_suffix('\'')) {
+        return (!lit.contains('\'')).then(|| lit.to_string());
+    }
+    let body = v.strip_prefix('"')?.strip_suffix('"')?;
+    let mut out = String::new();
+    let mut chars = body.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => return None,
+            '\\' => out.push(match chars.next()? {
+                '\\' => '\\',
+                '"' => '"',
+                'n' => '\n',
+                't' => '\t',
+                'u' => {
+                    let hex: String = chars.by_ref().take(4).collect();
+                    if hex.len() != 4 {
+                        return None;
+                    }
+                    char::from_u32(u32::from_str_radix(&hex, 16).ok()?)?
+                }
+                _ => return None,
+            }),
+            c => out.push(c),
+        }
+    }
+    Some(out)
+}
+
 /// TextDisplay material (`EBRTextMaterial` -- declaration order matches the
 /// game's enum values). Unlit and Graffiti have no shading settings; only
 /// Graffiti projects onto nearby bricks (depth/angle limit, priority).
@@ -214,8 +512,9 @@ impl TextShading {
 
 #[derive(Clone, Debug)]
 pub struct TextOptions {
-    /// BrickFontDescriptor asset name.
-    pub font: &'static str,
+    /// BrickFontDescriptor asset name. Owned, since a [`CustomFontPreset`]
+    /// names fonts only known at runtime.
+    pub font: String,
     pub fill_char: char,
     pub empty_char: char,
     pub char_repeat: usize,
@@ -248,6 +547,9 @@ pub struct TextOptions {
     pub luma_threshold: u8,
     /// Monochrome modes: draw dark pixels instead of bright ones.
     pub invert: bool,
+    /// Monochrome modes: how the dots spread the shading the luma cut
+    /// throws away (None = a hard cut).
+    pub dither: Dither,
     /// Override the mode's tile edge in pixels (None = mode default). The
     /// calibration grid uses this to force seams in mono modes.
     pub tile_override: Option<u32>,
@@ -285,10 +587,20 @@ impl TextOptions {
 /// LineOffset of -8 (gap X from the font preset); blocks wants font size
 /// 1.08, gap X 0.41, and matches the normal mode's zeroes. Both use gap Y
 /// 0.8125.
+///
+/// Sextants and octants are provisional, NOT measured. They take blocks'
+/// geometry scaled to their taller cells: font size grows with the cell
+/// height (1.08 per two rows), and gap X grows with the font size, since a
+/// glyph widens as it grows. Both front ends offer them and say so in their
+/// help; re-seed them in-game with the calibration grid to make them exact.
 pub fn mono_geometry(mode: PixelMode, pixel_size: f32) -> (f32, f32, f32, Option<f32>, f32) {
     match mode {
         PixelMode::Braille => (2.7 * pixel_size, -4.0 * pixel_size, -8.0, None, 0.8125),
-        _ => (1.08 * pixel_size, 0.0, 0.0, Some(0.41), 0.8125),
+        _ => {
+            // blocks' measured values are for a two-row cell
+            let scale = mode.cell().1.max(2) as f32 / 2.0;
+            (1.08 * scale * pixel_size, 0.0, 0.0, Some(0.41 * scale), 0.8125)
+        }
     }
 }
 
@@ -401,6 +713,28 @@ const BRAILLE_SPRITES: &str = "\
 /// in the char's 2×2 pixel cell.
 const BLOCK_SPRITES: &str = " ▘▝▀▖▌▞▛▗▚▐▜▄▙▟█";
 
+/// Sextant sprite sheet (Unicode 13, U+1FB00..): index bit `y*2 + x` ⇒
+/// sixth at (x, y) in the char's 2×3 pixel cell. The four patterns Unicode
+/// left out of the sextant run (empty, full, left and right column) are the
+/// existing space, █, ▌ and ▐.
+const SEXTANT_SPRITES: &str = " 🬀🬁🬂🬃🬄🬅🬆🬇🬈🬉🬊🬋🬌🬍🬎🬏🬐🬑🬒🬓▌🬔🬕🬖🬗🬘🬙🬚🬛🬜🬝🬞🬟🬠🬡🬢🬣🬤🬥🬦🬧▐🬨🬩🬪🬫🬬🬭🬮🬯🬰🬱🬲🬳🬴🬵🬶🬷🬸🬹🬺🬻█";
+
+/// Octant sprite sheet (Unicode 16, U+1CD00..U+1CDE5): index bit `y*2 + x`
+/// ⇒ eighth at (x, y) in the char's 2×4 pixel cell. The 26 patterns already
+/// encoded elsewhere -- the quadrant blocks, the quarter and three-quarter
+/// bars, the middle-left/right quarters and the four corner eighths -- use
+/// those characters instead, which is why the run skips them. (The leading
+/// space is escaped: a line continuation swallows a literal one.)
+const OCTANT_SPRITES: &str = "\
+\x20𜺨𜺫🮂𜴀▘𜴁𜴂𜴃𜴄▝𜴅𜴆𜴇𜴈▀𜴉𜴊𜴋𜴌🯦𜴍𜴎𜴏𜴐𜴑𜴒𜴓𜴔𜴕𜴖𜴗\
+𜴘𜴙𜴚𜴛𜴜𜴝𜴞𜴟🯧𜴠𜴡𜴢𜴣𜴤𜴥𜴦𜴧𜴨𜴩𜴪𜴫𜴬𜴭𜴮𜴯𜴰𜴱𜴲𜴳𜴴𜴵🮅\
+𜺣𜴶𜴷𜴸𜴹𜴺𜴻𜴼𜴽𜴾𜴿𜵀𜵁𜵂𜵃𜵄▖𜵅𜵆𜵇𜵈▌𜵉𜵊𜵋𜵌▞𜵍𜵎𜵏𜵐▛\
+𜵑𜵒𜵓𜵔𜵕𜵖𜵗𜵘𜵙𜵚𜵛𜵜𜵝𜵞𜵟𜵠𜵡𜵢𜵣𜵤𜵥𜵦𜵧𜵨𜵩𜵪𜵫𜵬𜵭𜵮𜵯𜵰\
+𜺠𜵱𜵲𜵳𜵴𜵵𜵶𜵷𜵸𜵹𜵺𜵻𜵼𜵽𜵾𜵿𜶀𜶁𜶂𜶃𜶄𜶅𜶆𜶇𜶈𜶉𜶊𜶋𜶌𜶍𜶎𜶏\
+▗𜶐𜶑𜶒𜶓▚𜶔𜶕𜶖𜶗▐𜶘𜶙𜶚𜶛▜𜶜𜶝𜶞𜶟𜶠𜶡𜶢𜶣𜶤𜶥𜶦𜶧𜶨𜶩𜶪𜶫\
+▂𜶬𜶭𜶮𜶯𜶰𜶱𜶲𜶳𜶴𜶵𜶶𜶷𜶸𜶹𜶺𜶻𜶼𜶽𜶾𜶿𜷀𜷁𜷂𜷃𜷄𜷅𜷆𜷇𜷈𜷉𜷊\
+𜷋𜷌𜷍𜷎𜷏𜷐𜷑𜷒𜷓𜷔𜷕𜷖𜷗𜷘𜷙𜷚▄𜷛𜷜𜷝𜷞▙𜷟𜷠𜷡𜷢▟𜷣▆𜷤𜷥█";
+
 /// How pixels become glyphs.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum PixelMode {
@@ -411,16 +745,30 @@ pub enum PixelMode {
     Braille,
     /// Monochrome quadrant blocks: each char is a 2×2 pixel cell.
     Blocks,
+    /// Monochrome sextants: each char is a 2×3 pixel cell, filled solidly
+    /// like blocks rather than dotted like braille.
+    Sextants,
+    /// Monochrome octants: each char is a 2×4 pixel cell -- braille's
+    /// density with solid cells. Needs a font with Unicode 16 coverage.
+    Octants,
 }
 
 impl PixelMode {
-    pub const ALL: [PixelMode; 3] = [PixelMode::Color, PixelMode::Braille, PixelMode::Blocks];
+    pub const ALL: [PixelMode; 5] = [
+        PixelMode::Color,
+        PixelMode::Braille,
+        PixelMode::Blocks,
+        PixelMode::Sextants,
+        PixelMode::Octants,
+    ];
 
     pub fn name(&self) -> &'static str {
         match self {
             PixelMode::Color => "Color",
             PixelMode::Braille => "Braille",
             PixelMode::Blocks => "Blocks",
+            PixelMode::Sextants => "Sextants",
+            PixelMode::Octants => "Octants",
         }
     }
 
@@ -430,44 +778,87 @@ impl PixelMode {
             PixelMode::Color => (1, 1),
             PixelMode::Braille => (2, 4),
             PixelMode::Blocks => (2, 2),
+            PixelMode::Sextants => (2, 3),
+            PixelMode::Octants => (2, 4),
         }
     }
 
     /// Tile edge in pixels. Mono modes pack 4-8 pixels per char, so their
-    /// tiles are larger for the same char budget (fewer anchor bricks).
+    /// tiles are larger for the same char budget (fewer anchor bricks). The
+    /// edge must be a whole number of cells, or every tile seam cuts a row of
+    /// cells in half: sextants' 3-pixel cells take 132 rather than 128.
     pub fn tile_px(&self) -> u32 {
         match self {
             PixelMode::Color => TILE_PX,
-            PixelMode::Braille | PixelMode::Blocks => 128,
+            PixelMode::Braille | PixelMode::Blocks | PixelMode::Octants => 128,
+            PixelMode::Sextants => 132,
         }
     }
 
+    /// The dots `glyph` draws in this mode's cell, as the sprite index
+    /// [`encode_mono_tile`] built it from (bit `dy * cell.0 + dx`), or `None`
+    /// for a character that is not one of this mode's sprites.
+    pub fn sprite_bits(&self, glyph: char) -> Option<usize> {
+        self.sprites().chars().position(|c| c == glyph)
+    }
+
     fn sprites(&self) -> &'static str {
         match self {
             PixelMode::Color => "",
             PixelMode::Braille => BRAILLE_SPRITES,
             PixelMode::Blocks => BLOCK_SPRITES,
+            PixelMode::Sextants => SEXTANT_SPRITES,
+            PixelMode::Octants => OCTANT_SPRITES,
         }
     }
 }
 
-/// Whether a pixel's dot is drawn in a monochrome mode.
-fn mono_on(p: [u8; 4], opts: &TextOptions) -> bool {
-    if p[3] < opts.alpha_threshold {
-        return false;
+/// Which pixels' dots are drawn in a monochrome mode, row-major over the
+/// whole image -- dithered in one pass, so diffused error crosses tile
+/// seams instead of restarting at every tile.
+pub fn mono_mask(img: &RgbaImage, opts: &TextOptions) -> Vec<bool> {
+    let levels: Vec<Option<u8>> = img
+        .pixels()
+        .map(|p| {
+            let p = p.0;
+            (p[3] >= opts.alpha_threshold).then(|| {
+                // Rec.601 luma; inverted, the dark end is the drawn end
+                let luma = (p[0] as u32 * 299 + p[1] as u32 * 587 + p[2] as u32 * 114) / 1000;
+                if opts.invert {
+                    255 - luma as u8
+                } else {
+                    luma as u8
+                }
+            })
+        })
+        .collect();
+    // inverting the levels mirrors the cut too: `luma < cut` becomes
+    // `255 - luma > 255 - cut`
+    let cut = if opts.invert {
+        (256 - opts.luma_threshold as u32).min(255) as u8
+    } else {
+        opts.luma_threshold
+    };
+    let mut on = dither::threshold(&levels, img.width() as usize, cut, opts.dither);
+    if opts.invert && opts.luma_threshold == 0 {
+        // a cut of 0 draws every visible pixel, so inverted it draws none
+        on.fill(false);
     }
-    // Rec.601 luma
-    let luma = (p[0] as u32 * 299 + p[1] as u32 * 587 + p[2] as u32 * 114) / 1000;
-    (luma as u8 >= opts.luma_threshold) != opts.invert
+    on
 }
 
-/// Encode one tile as monochrome sprite characters (braille or quadrant
-/// blocks). Returns the text and its char count; edge cells pad with OFF
-/// pixels like the reference implementation.
-fn encode_mono_tile(img: &RgbaImage, opts: &TextOptions) -> (String, usize, bool) {
+/// Encode one tile -- `w`×`h` pixels from (`tx`, `ty`) of an image `width`
+/// wide, as [`mono_mask`] cut it -- as monochrome sprite characters.
+/// Returns the text, its char count and whether any dot is on; edge cells
+/// pad with OFF pixels like the reference implementation.
+pub fn encode_mono_tile(
+    mask: &[bool],
+    width: u32,
+    (tx, ty, w, h): (u32, u32, u32, u32),
+    opts: &TextOptions,
+) -> (String, usize, bool) {
     let (cw, ch) = opts.mode.cell();
     let table: Vec<char> = opts.mode.sprites().chars().collect();
-    let (w, h) = img.dimensions();
     let (cols, lines) = (w.div_ceil(cw), h.div_ceil(ch));
     let mut out = String::new();
     let mut chars = 0usize;
@@ -482,7 +873,7 @@ fn encode_mono_tile(img: &RgbaImage, opts: &TextOptions) -> (String, usize, bool
             for dy in 0..ch {
                 for dx in 0..cw {
                     let (x, y) = (cx * cw + dx, cy * ch + dy);
-                    if x < w && y < h && mono_on(img.get_pixel(x, y).0, opts) {
+                    if x < w && y < h && mask[((ty + y) * width + tx + x) as usize] {
                         idx |= 1 << (dy * cw + dx);
                     }
                 }
@@ -514,6 +905,10 @@ pub struct TextTile {
 pub fn encode_tiles(img: &RgbaImage, opts: &TextOptions) -> Result<Vec<TextTile>, String> {
     let (w, h) = img.dimensions();
     let tile_px = opts.tile_px();
+    let mask = match opts.mode {
+        PixelMode::Color => vec![],
+        _ => mono_mask(img, opts),
+    };
     let mut tiles = Vec::new();
     let mut ty = 0u32;
     while ty < h {
@@ -521,15 +916,18 @@ pub fn encode_tiles(img: &RgbaImage, opts: &TextOptions) -> Result<Vec<TextTile>
         let mut tx = 0u32;
         while tx < w {
             let tw = tile_px.min(w - tx);
-            let sub = image::imageops::crop_imm(img, tx, ty, tw, th).to_image();
             let (bands, visible) = match opts.mode {
                 PixelMode::Color => {
+                    let sub = image::imageops::crop_imm(img, tx, ty, tw, th).to_image();
                     let bands = encode_bands(&sub, opts)?;
                     let visible = bands.iter().any(|b| b.text.chars().any(|c| c != '\n'));
                     (bands, visible)
                 }
-                PixelMode::Braille | PixelMode::Blocks => {
-                    let (text, chars, any_on) = encode_mono_tile(&sub, opts);
+                PixelMode::Braille
+                | PixelMode::Blocks
+                | PixelMode::Sextants
+                | PixelMode::Octants => {
+                    let (text, chars, any_on) = encode_mono_tile(&mask, w, (tx, ty, tw, th), opts);
                     let band = TextBand {
                         start_row: 0,
                         rows: th as usize,
@@ -553,6 +951,15 @@ pub fn encode_tiles(img: &RgbaImage, opts: &TextOptions) -> Result<Vec<TextTile>
     Ok(tiles)
 }
 
+/// What `tiles` cost in the save: (characters, TextDisplay components) --
+/// one component, on its own anchor cube, per band.
+pub fn tiles_cost(tiles: &[TextTile]) -> (usize, usize) {
+    tiles
+        .iter()
+        .flat_map(|t| &t.bands)
+        .fold((0, 0), |(chars, n), b| (chars + b.chars, n + 1))
+}
+
 fn row_too_wide(y: u32, chars: usize) -> String {
     format!(
         "row {y} encodes to {chars} chars, over the {MAX_COMPONENT_CHARS}-char \
@@ -566,8 +973,8 @@ fn row_too_wide(y: u32, chars: usize) -> String {
 /// and the text it counts cannot drift apart. All three parts are ASCII, so
 /// `len()` (bytes) equals the char count, which is what makes
 /// `COLOR_TAG_CHARS` a constant instead of a `chars().count()` per tag.
-const COLOR_TAG_OPEN: &str = "<color=\"";
-const COLOR_TAG_CLOSE: &str = "\">";
+pub(crate) const COLOR_TAG_OPEN: &str = "<color=\"";
+pub(crate) const COLOR_TAG_CLOSE: &str = "\">";
 const COLOR_TAG_CHARS: usize = COLOR_TAG_OPEN.len() + 6 + COLOR_TAG_CLOSE.len();
 
 /// Encode one image row. Updates `last_color` with the final emitted tag so
@@ -714,8 +1121,12 @@ fn anchor_cube(position: Position, visible: bool) -> Brick {
 ///
 /// `FACE_X_POSITIVE` is what every text render in this crate has used (the
 /// glyph wall faces world +X); `FACE_Z_POSITIVE` is the upward face, for
-/// text meant to lie flat in the ground plane.
+/// text meant to lie flat in the ground plane. The other three side faces
+/// are for a caption over a brick-mode wall facing that way.
 pub const FACE_X_POSITIVE: u8 = 0;
+pub const FACE_X_NEGATIVE: u8 = 1;
+pub const FACE_Y_POSITIVE: u8 = 2;
+pub const FACE_Y_NEGATIVE: u8 = 3;
 pub const FACE_Z_POSITIVE: u8 = 4;
 
 /// Add a TextDisplay block with explicit geometry (LineHeight/Kerning/Offset)
@@ -756,7 +1167,7 @@ pub fn add_text_block(
 /// [`add_text_block`] with the anchor point ([`ANCHOR_TOP_LEFT`] /
 /// [`ANCHOR_BOTTOM_CENTRE`]) and outline ([`OUTLINE_NONE`] /
 /// [`OUTLINE_OUTLINED`]) made explicit, for a caller that draws on a
-/// different face too (`face` is [`FACE_X_POSITIVE`] / [`FACE_Z_POSITIVE`]).
+/// different face too (`face` is any of the `FACE_*` constants).
 #[allow(clippy::too_many_arguments)]
 pub fn add_text_block_styled(
     world: &mut World,
@@ -1075,14 +1486,17 @@ pub fn build_calibration_world(opts: &TextOptions, cube_spacing: f32) -> World {
 }
 
 /// Mark the world as a prefab whose pivots/bounds cover the full rendered
-/// image. The anchor-cube grid already spans most of the image; each
-/// outermost tile's content extends up to one tile beyond its cube toward
-/// the text's right (world -Y) and downward (-Z), so the bounds pad those
-/// two edges -- a ground placement then rests the image (not the cubes) on
-/// the ground.
-pub fn make_text_prefab(world: &mut World, _img_w: u32, _img_h: u32, opts: &TextOptions) {
-    let span_x = (TILE_PX as f32 * opts.line_world_height * opts.pitch_x).ceil() as i32;
-    let span_z = (TILE_PX as f32 * opts.line_world_height * opts.pitch_y).ceil() as i32;
+/// `img_w` x `img_h` image. The anchor-cube grid already spans most of the
+/// image; each outermost tile's content extends beyond its cube toward the
+/// text's right (world -Y) and downward (-Z) by that tile's own pixels -- a
+/// whole tile's at most, and only what the image has left over in the last
+/// tile column and row -- so the bounds pad those two edges by exactly that.
+/// A ground placement then rests the image (not the cubes) on the ground.
+pub fn make_text_prefab(world: &mut World, img_w: u32, img_h: u32, opts: &TextOptions) {
+    let tile_px = opts.tile_px().max(1);
+    let last_tile = |px: u32| (px.max(1) - 1) % tile_px + 1;
+    let span_x = (last_tile(img_w) as f32 * opts.line_world_height * opts.pitch_x).ceil() as i32;
+    let span_z = (last_tile(img_h) as f32 * opts.line_world_height * opts.pitch_y).ceil() as i32;
     let (bmin, bmax) = world
         .brick_bounds()
         .unwrap_or((Position::ZERO, Position::ZERO));
@@ -1387,6 +1801,78 @@ mod tests {
         bands.into_iter().next().unwrap().text
     }
 
+    #[test]
+    fn a_font_presets_file_parses_with_omitted_keys_at_their_defaults() {
+        let src = r#"
+# two fonts
+[[font]]
+name = "Noto Mono"   # a comment
+asset = "NotoSansMono"
+fill_char = "\u2593"
+line_height = 0.5
+kerning = -0.1
+pitch = 0.9
+
+[[font]]
+name = 'Hash #1'
+asset = "Orbitron"
+char_repeat = 1
+"#;
+        let presets = parse_font_presets(src).expect("valid");
+        assert_eq!(presets.len(), 2);
+        let noto = &presets[0];
+        assert_eq!(noto.fill_char, '▓');
+        assert_eq!(noto.empty_char, ' ', "omitted keys keep the base");
+        assert_eq!((noto.pitch_x, noto.pitch_y), (0.9, 0.9));
+        assert_eq!(
+            presets[1].name, "Hash #1",
+            "a # inside a string is not a comment"
+        );
+
+        // geometry scales with pixel size; line offset and pitch do not
+        let o = noto.options(2.0);
+        assert_eq!(o.font, "NotoSansMono");
+        assert_eq!(o.line_height, 1.0);
+        assert_eq!(o.kerning, -0.2);
+        assert_eq!(o.pitch_x, 0.9);
+        assert_eq!(o.line_world_height, 2.0);
+
+        let choice = FontChoice::resolve("noto mono", &presets).expect("by name");
+        assert_eq!(choice.name(), "Noto Mono");
+        assert_eq!(
+            FontChoice::resolve("orbitron", &presets),
+            Some(FontChoice::Builtin(FontPreset::Orbitron))
+        );
+    }
+
+    #[test]
+    fn a_bad_font_presets_file_is_an_error_naming_the_line() {
+        for (src, want) in [
+            (
+                "[[font]]\nname = \"A\"\nasset = \"B\"\nkernign = 1",
+                "line 4",
+            ),
+            ("[[font]]\nname = \"A\"", "needs an asset"),
+            (
+                "[[font]]\nname = \"A\"\nasset = \"B\"\nline_height = tall",
+                "line 4",
+            ),
+            (
+                "[[font]]\nname = \"A\"\nasset = \"B\"\nfill_char = \"ab\"",
+                "one character",
+            ),
+            ("[[font]]\nname = \"Orbitron\"\nasset = \"B\"", "built-in"),
+            ("name = \"A\"", "before the first"),
+            (
+                "[[font]]\nname = \"A\"\nasset = \"B\"\n[[font]]\nname = \"a\"\nasset = \"C\"",
+                "duplicate",
+            ),
+        ] {
+            let err = parse_font_presets(src).expect_err(src);
+            assert!(err.contains(want), "{src:?}: {err}");
+        }
+    }
+
     #[test]
     fn single_opaque_pixel() {
         assert_eq!(text(&img(&[&[RED]])), "<color=\"FF0000\">██");
@@ -1596,6 +2082,66 @@ mod tests {
         assert_eq!(encode_tiles(&dark, &inv).unwrap()[0].bands[0].text, "⣿");
     }
 
+    /// Every sheet holds one distinct glyph per cell pattern, and the two
+    /// new ones agree with blocks wherever their cells can draw a quadrant.
+    #[test]
+    fn sprite_sheets_cover_every_pattern_once() {
+        for mode in PixelMode::ALL
+            .into_iter()
+            .filter(|m| *m != PixelMode::Color)
+        {
+            let (cw, ch) = mode.cell();
+            let table: Vec<char> = mode.sprites().chars().collect();
+            assert_eq!(table.len(), 1 << (cw * ch), "{}", mode.name());
+            let distinct: std::collections::HashSet<_> = table.iter().collect();
+            assert_eq!(
+                distinct.len(),
+                table.len(),
+                "{} repeats a glyph",
+                mode.name()
+            );
+            assert_eq!(mode.tile_px() % ch, 0, "{} tiles cut cells", mode.name());
+        }
+        let oct: Vec<char> = OCTANT_SPRITES.chars().collect();
+        // a quadrant is two stacked eighths: top-left = bits 0 and 2
+        assert_eq!(oct[0b0000_0101], '▘');
+        assert_eq!(oct[0b1010_0000], '▗');
+        assert_eq!(oct[0b1111_1111], '█');
+        assert_eq!(oct[0b0000_0100], '\u{1CD00}', "the run starts at octant 3");
+        let sex: Vec<char> = SEXTANT_SPRITES.chars().collect();
+        assert_eq!(sex[0b01_0101], '▌');
+        assert_eq!(sex[1], '\u{1FB00}');
+    }
+
+    #[test]
+    fn sextant_and_octant_cells_encode_solid_patterns() {
+        let white = Rgba([255, 255, 255, 255]);
+        // 2×3: top row on ⇒ sextant 1+2 (🬂); all on ⇒ █
+        let mut i = RgbaImage::new(2, 3);
+        i.put_pixel(0, 0, white);
+        i.put_pixel(1, 0, white);
+        let opts = TextOptions {
+            mode: PixelMode::Sextants,
+            ..Default::default()
+        };
+        assert_eq!(encode_tiles(&i, &opts).unwrap()[0].bands[0].text, "🬂");
+        let full = RgbaImage::from_pixel(2, 3, white);
+        assert_eq!(encode_tiles(&full, &opts).unwrap()[0].bands[0].text, "█");
+
+        // 2×4: bottom row on ⇒ ▂; left column ⇒ ▌
+        let opts = TextOptions {
+            mode: PixelMode::Octants,
+            ..Default::default()
+        };
+        let mut i = RgbaImage::new(4, 4);
+        i.put_pixel(0, 3, white);
+        i.put_pixel(1, 3, white);
+        for y in 0..4 {
+            i.put_pixel(2, y, white);
+        }
+        assert_eq!(encode_tiles(&i, &opts).unwrap()[0].bands[0].text, "▂▌");
+    }
+
     #[test]
     fn block_cells_encode_quadrants() {
         let opts = TextOptions {
@@ -1618,6 +2164,59 @@ mod tests {
         assert_eq!(tiles[0].bands[0].chars, 3);
     }
 
+    /// A smooth gradient tags nearly every pixel; snapped to a few colours
+    /// it runs long, and costs a fraction of the characters.
+    #[test]
+    fn a_quantized_picture_encodes_in_fewer_characters() {
+        let mut img = RgbaImage::new(32, 32);
+        for (x, y, p) in img.enumerate_pixels_mut() {
+            *p = Rgba([(x * 8) as u8, (y * 8) as u8, 0x40, 0xFF]);
+        }
+        let opts = TextOptions::default();
+        let (full, _) = tiles_cost(&encode_tiles(&img, &opts).unwrap());
+        let mut palette = crate::anim::palette::Palette::build(std::slice::from_ref(&img), 4, 1);
+        let reduced = dither::quantize(&img, &mut palette, Dither::None);
+        let (fewer, components) = tiles_cost(&encode_tiles(&reduced, &opts).unwrap());
+        assert!(fewer * 3 < full, "{fewer} vs {full}");
+        assert!(components >= 1);
+    }
+
+    /// A flat grey below the cut draws nothing plain and about its share of
+    /// dots dithered -- across tile seams, and mirrored by `--invert`.
+    #[test]
+    fn dithered_mono_modes_keep_a_grey() {
+        // 40%: a midtone, where Atkinson's dropped error does not show
+        let grey = RgbaImage::from_pixel(64, 64, Rgba([102, 102, 102, 255]));
+        let dots = |opts: &TextOptions| -> usize {
+            encode_tiles(&grey, opts)
+                .unwrap()
+                .iter()
+                .flat_map(|t| t.bands[0].text.chars())
+                .filter(|&c| c != '\n')
+                .map(|c| (c as u32 - 0x2800).count_ones() as usize)
+                .sum()
+        };
+        let plain = TextOptions {
+            mode: PixelMode::Braille,
+            ..Default::default()
+        };
+        assert_eq!(dots(&plain), 0);
+        for dither in [Dither::Ordered, Dither::FloydSteinberg, Dither::Atkinson] {
+            let opts = TextOptions {
+                dither,
+                ..plain.clone()
+            };
+            let share = dots(&opts) as f32 / (64.0 * 64.0);
+            assert!((share - 0.4).abs() < 0.05, "{dither:?}: {share}");
+            let inv = TextOptions {
+                invert: true,
+                ..opts
+            };
+            let share = dots(&inv) as f32 / (64.0 * 64.0);
+            assert!((share - 0.6).abs() < 0.05, "{dither:?} inverted: {share}");
+        }
+    }
+
     #[test]
     fn tiny_pixel_sizes_never_overlap_cubes() {
         // 0.02 units/px: a 32px tile renders ~0.6 units wide, far below the
@@ -1787,6 +2386,23 @@ mod tests {
         assert_eq!(world.wires.len() / 16, 5, "Offset Z variable dropped");
     }
 
+    /// An image narrower and shorter than one tile pads the bounds by its
+    /// own size, not by a whole tile's.
+    #[test]
+    fn prefab_bounds_follow_the_image_not_the_tile() {
+        use brdb::World;
+        let i = RgbaImage::from_pixel(6, 4, RED);
+        let opts = TextOptions::default();
+        let mut world = World::new();
+        add_text_bricks(&mut world, encode_bands(&i, &opts).unwrap(), &opts);
+        let (bmin, bmax) = world.brick_bounds().unwrap();
+        make_text_prefab(&mut world, 6, 4, &opts);
+        let half = world.meta.prefab.unwrap().pivots.bounds_pivot.half_extent;
+        let span = |px: u32, pitch: f32| (px as f32 * opts.line_world_height * pitch).ceil() as i32;
+        assert_eq!(half.y * 2.0, f64::from(bmax.y - bmin.y + span(6, opts.pitch_x)));
+        assert_eq!(half.z * 2.0, f64::from(bmax.z - bmin.z + span(4, opts.pitch_y)));
+    }
+
     #[test]
     fn bricks_stack_one_per_band() {
         use brdb::{Position, World};
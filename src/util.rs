@@ -31,6 +31,7 @@ pub enum SurfaceMode {
     Wedge,
 }
 
+#[derive(Clone)]
 pub struct GenOptions {
     pub size: u16,
     pub scale: u32,
@@ -62,6 +63,17 @@ impl GenOptions {
         }
     }
 
+    /// The half height of the brick stack that fills a layered column from
+    /// level `bottom` up to level `top`. Every box height is rounded to a
+    /// step (a plate's 2, or a stud brick's 5), so the span is rounded DOWN
+    /// to whole steps: the stack starts exactly on `bottom`, and never
+    /// reaches into the gap below it. 0 for a span thinner than one step.
+    pub fn layer_half_height(&self, bottom: u32, top: u32) -> i32 {
+        let step = if self.stud { 5 } else { 2 };
+        let half = (top.saturating_sub(bottom) * self.scale / 2) as i32;
+        half - half % step
+    }
+
     /// The collision values that each renderer makes from `--nocollide`. They
     /// are in one function, so the sloped modes always agree with the other
     /// modes.
@@ -217,6 +229,29 @@ impl Footprint {
         )
     }
 
+    /// `stands 512 studs tall (43 ft)`
+    ///
+    /// For a `--wall`, whose picture rows run up the wall: the vertical size
+    /// of the picture, in the same two units.
+    pub fn wall_text(&self) -> String {
+        format!(
+            "stands {} studs tall ({})",
+            studs_text(self.studs.1),
+            length_text(self.units.1),
+        )
+    }
+
+    /// `up to 25 studs out of the wall (21 ft)`
+    ///
+    /// A wall's heights stand out of its face rather than up.
+    pub fn relief_text(&self) -> String {
+        format!(
+            "up to {} studs out of the wall ({})",
+            studs_text(self.max_height_units as f64 / UNITS_PER_STUD),
+            length_text(self.max_height_units),
+        )
+    }
+
     /// The real-world width and length in miles, at one inch per unit.
     pub fn miles(&self) -> (f64, f64) {
         (
@@ -514,11 +549,17 @@ pub fn save_screenshot(source: &image::RgbaImage) -> Result<Vec<u8>, String> {
 /// to the preview. The count tells the user if the build is loadable, and only
 /// the render knows it.
 pub fn bricks_to_save(bricks: Vec<Brick>) -> World {
+    bricks_to_save_from(bricks, "heightmap file")
+}
+
+/// [`bricks_to_save`] for a save that was not made from a heightmap.
+/// `source` names what it was made from, e.g. "voxel model".
+pub fn bricks_to_save_from(bricks: Vec<Brick>, source: &str) -> World {
     let mut world = World::new();
     let count = bricks.len();
     world.add_bricks(bricks);
     world.meta.bundle.description = format!(
-        "Save generated from heightmap file\n{} bricks",
+        "Save generated from {source}\n{} bricks",
         commas(count as u64)
     );
     world
@@ -0,0 +1,446 @@
+//! Crop, rotate, mirror and colour-grade a clip on its way in.
+//!
+//! Each of these used to be an ffmpeg pre-pass: crop the black bars off, turn
+//! the phone footage upright, lift the saturation, then render the result.
+//! Here each is a [`FrameOp`], and [`OpStream`] layers any one of them over a
+//! [`FrameStream`], so they compose the way `scale::ResizeStream` and
+//! `key::KeyStream` do. `stream::AdaptedSource` applies a whole
+//! [`Adjustments`] in a fixed order (see its `open`) over the raw decoded
+//! frames, which is what makes them identical on the builtin and ffmpeg
+//! backends.
+
+use image::{RgbaImage, imageops};
+
+use super::stream::{FrameSource, FrameStream};
+
+/// One per-frame transform.
+pub trait FrameOp {
+    /// Transform one frame. An error is fatal to the render, as any
+    /// [`FrameStream`] error is.
+    fn apply(&self, frame: RgbaImage) -> Result<RgbaImage, String>;
+
+    /// The size a `width` x `height` frame comes out at.
+    fn size(&self, width: u32, height: u32) -> (u32, u32) {
+        (width, height)
+    }
+}
+
+/// A [`FrameOp`] applied to every frame of the stream under it.
+pub struct OpStream<'a, O> {
+    inner: Box<dyn FrameStream + 'a>,
+    op: O,
+}
+
+impl<'a, O: FrameOp> OpStream<'a, O> {
+    pub fn new(inner: Box<dyn FrameStream + 'a>, op: O) -> Self {
+        Self { inner, op }
+    }
+}
+
+impl<O: FrameOp> FrameStream for OpStream<'_, O> {
+    fn next(&mut self) -> Result<Option<RgbaImage>, String> {
+        self.inner.next()?.map(|f| self.op.apply(f)).transpose()
+    }
+
+    /// Forwarded, so only the frame `advance` keeps is transformed -- the
+    /// same saving `ResizeStream::advance` makes, and it has to be kept at
+    /// every layer or one plain `next` loop in the middle undoes it.
+    fn advance(&mut self, n: usize) -> Result<(usize, Option<RgbaImage>), String> {
+        let (got, last) = self.inner.advance(n)?;
+        Ok((got, last.map(|f| self.op.apply(f)).transpose()?))
+    }
+}
+
+/// A rectangle of the source frame to keep.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Crop {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Crop {
+    /// `WxH+X+Y`, or `WxH` for the top-left corner.
+    pub fn parse(s: &str) -> Option<Self> {
+        let (size, offset) = match s.split_once('+') {
+            Some((size, offset)) => (size, Some(offset)),
+            None => (s, None),
+        };
+        let (w, h) = size.split_once('x')?;
+        let (x, y) = match offset {
+            Some(o) => {
+                let (x, y) = o.split_once('+')?;
+                (x.trim().parse().ok()?, y.trim().parse().ok()?)
+            }
+            None => (0, 0),
+        };
+        let crop = Self { x, y, width: w.trim().parse().ok()?, height: h.trim().parse().ok()? };
+        (crop.width > 0 && crop.height > 0).then_some(crop)
+    }
+
+    /// Refuses a rectangle that runs off a `width` x `height` frame, rather
+    /// than cropping to whatever part of it overlaps.
+    pub fn check(&self, width: u32, height: u32) -> Result<(), String> {
+        let fits = self.width > 0
+            && self.height > 0
+            && u64::from(self.x) + u64::from(self.width) <= u64::from(width)
+            && u64::from(self.y) + u64::from(self.height) <= u64::from(height);
+        if fits {
+            Ok(())
+        } else {
+            Err(format!("crop {self} does not fit inside a {width}x{height} frame"))
+        }
+    }
+}
+
+impl std::fmt::Display for Crop {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}x{}+{}+{}", self.width, self.height, self.x, self.y)
+    }
+}
+
+impl FrameOp for Crop {
+    fn apply(&self, frame: RgbaImage) -> Result<RgbaImage, String> {
+        self.check(frame.width(), frame.height())?;
+        Ok(imageops::crop_imm(&frame, self.x, self.y, self.width, self.height).to_image())
+    }
+
+    fn size(&self, _: u32, _: u32) -> (u32, u32) {
+        (self.width, self.height)
+    }
+}
+
+/// A quarter-turn rotation, clockwise.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Rotation {
+    #[default]
+    None,
+    Cw90,
+    Half,
+    Ccw90,
+}
+
+impl Rotation {
+    /// Degrees clockwise: 0, 90, 180 or 270 (-90 for 270).
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim() {
+            "0" => Some(Rotation::None),
+            "90" => Some(Rotation::Cw90),
+            "180" => Some(Rotation::Half),
+            "270" | "-90" => Some(Rotation::Ccw90),
+            _ => None,
+        }
+    }
+
+    pub fn degrees(self) -> u32 {
+        match self {
+            Rotation::None => 0,
+            Rotation::Cw90 => 90,
+            Rotation::Half => 180,
+            Rotation::Ccw90 => 270,
+        }
+    }
+}
+
+impl FrameOp for Rotation {
+    fn apply(&self, frame: RgbaImage) -> Result<RgbaImage, String> {
+        Ok(match self {
+            Rotation::None => frame,
+            Rotation::Cw90 => imageops::rotate90(&frame),
+            Rotation::Half => imageops::rotate180(&frame),
+            Rotation::Ccw90 => imageops::rotate270(&frame),
+        })
+    }
+
+    fn size(&self, width: u32, height: u32) -> (u32, u32) {
+        match self {
+            Rotation::Cw90 | Rotation::Ccw90 => (height, width),
+            Rotation::None | Rotation::Half => (width, height),
+        }
+    }
+}
+
+/// Mirror left-right, top-bottom, or both.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Flip {
+    pub horizontal: bool,
+    pub vertical: bool,
+}
+
+impl Flip {
+    /// `h`, `v` or `hv` (also `horizontal`, `vertical`, `both`).
+    pub fn parse(s: &str) -> Option<Self> {
+        let (horizontal, vertical) = match s.trim().to_ascii_lowercase().as_str() {
+            "h" | "horizontal" => (true, false),
+            "v" | "vertical" => (false, true),
+            "hv" | "vh" | "both" => (true, true),
+            _ => return None,
+        };
+        Some(Self { horizontal, vertical })
+    }
+}
+
+impl FrameOp for Flip {
+    fn apply(&self, mut frame: RgbaImage) -> Result<RgbaImage, String> {
+        if self.horizontal {
+            imageops::flip_horizontal_in_place(&mut frame);
+        }
+        if self.vertical {
+            imageops::flip_vertical_in_place(&mut frame);
+        }
+        Ok(frame)
+    }
+}
+
+/// Brightness, contrast, saturation and gamma, applied to colour only;
+/// alpha passes through untouched.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Grade {
+    /// Added to every channel, as a fraction of full scale: `-1..=1`, `0`
+    /// leaves it alone.
+    pub brightness: f32,
+    /// Stretch about mid-grey: `1` leaves it alone, `0` is flat grey.
+    pub contrast: f32,
+    /// Distance from the pixel's own grey: `1` leaves it alone, `0` is
+    /// greyscale, `2` twice as vivid.
+    pub saturation: f32,
+    /// Output is `v^(1/gamma)`: above `1` lifts the shadows, below darkens.
+    pub gamma: f32,
+}
+
+impl Default for Grade {
+    fn default() -> Self {
+        Self { brightness: 0.0, contrast: 1.0, saturation: 1.0, gamma: 1.0 }
+    }
+}
+
+impl Grade {
+    pub fn is_identity(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Brightness, contrast and gamma per channel value. Saturation mixes
+    /// channels, so it is the one part a table can't hold.
+    fn table(&self) -> [u8; 256] {
+        std::array::from_fn(|i| {
+            let v = (i as f32 / 255.0 - 0.5) * self.contrast + 0.5 + self.brightness;
+            let v = v.clamp(0.0, 1.0).powf(1.0 / self.gamma);
+            (v * 255.0).round() as u8
+        })
+    }
+}
+
+impl FrameOp for Grade {
+    fn apply(&self, mut frame: RgbaImage) -> Result<RgbaImage, String> {
+        if self.is_identity() {
+            return Ok(frame);
+        }
+        let table = self.table();
+        for p in frame.pixels_mut() {
+            let [r, g, b] = [p.0[0], p.0[1], p.0[2]].map(|c| f32::from(table[usize::from(c)]));
+            let grey = 0.299 * r + 0.587 * g + 0.114 * b;
+            for (c, v) in p.0[..3].iter_mut().zip([r, g, b]) {
+                *c = (grey + (v - grey) * self.saturation).round().clamp(0.0, 255.0) as u8;
+            }
+        }
+        Ok(frame)
+    }
+}
+
+/// Everything this module can do to a clip, in the order
+/// `stream::AdaptedSource` applies it.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Adjustments {
+    pub crop: Option<Crop>,
+    pub rotation: Rotation,
+    pub flip: Flip,
+    pub grade: Grade,
+}
+
+impl Adjustments {
+    /// Whether the frame's shape changes: a crop or a rotation. Only these
+    /// have to see the source at native size (see `open_video`'s target).
+    pub fn reshapes(&self) -> bool {
+        self.crop.is_some() || self.rotation != Rotation::None
+    }
+
+    /// The size a `width` x `height` source comes out at, before any resize.
+    pub fn size(&self, width: u32, height: u32) -> (u32, u32) {
+        let (w, h) = self.crop.map_or((width, height), |c| c.size(width, height));
+        self.rotation.size(w, h)
+    }
+
+    /// Refuses a crop that doesn't fit the source, before a stream is opened
+    /// rather than on its first frame.
+    pub fn check(&self, width: u32, height: u32) -> Result<(), String> {
+        self.crop.map_or(Ok(()), |c| c.check(width, height))
+    }
+
+    /// The crop, rotation and flip over `s`. The grade is left to the
+    /// caller: it is cheapest after the resize, where geometry can't go.
+    pub fn reshape<'a>(&self, mut s: Box<dyn FrameStream + 'a>) -> Box<dyn FrameStream + 'a> {
+        if let Some(crop) = self.crop {
+            s = Box::new(OpStream::new(s, crop));
+        }
+        if self.rotation != Rotation::None {
+            s = Box::new(OpStream::new(s, self.rotation));
+        }
+        if self.flip != Flip::default() {
+            s = Box::new(OpStream::new(s, self.flip));
+        }
+        s
+    }
+}
+
+/// How many frames [`detect_letterbox`] looks at, one per second of source.
+pub const LETTERBOX_SAMPLES: usize = 12;
+
+/// Brightest a bar may be. Encoders leave compression noise in black bars,
+/// so "black" is anything this dark.
+const BAR_LUMA: u32 = 24;
+
+/// The rectangle inside a clip's black bars, or `None` when it has none.
+///
+/// Looks at [`LETTERBOX_SAMPLES`] frames a second apart from `start_s` and
+/// keeps everything any of them shows, so one dark scene can't crop away
+/// picture the next one uses; frames that are black throughout (fades, a
+/// title card's gaps) are skipped. A row or column belongs to the picture
+/// once more than 1/32 of it is brighter than a bar, so a stray bright
+/// pixel of noise doesn't.
+pub fn detect_letterbox(source: &dyn FrameSource, start_s: f32) -> Result<Option<Crop>, String> {
+    let info = source.info();
+    let (w, h) = (info.width, info.height);
+    let mut stream = source.open()?;
+    let step = (info.fps.round() as usize).max(1);
+    let skip = (start_s.max(0.0) * info.fps) as usize;
+    let mut found: Option<(u32, u32, u32, u32)> = None;
+    let mut next = skip + 1;
+    for _ in 0..LETTERBOX_SAMPLES {
+        let (_, frame) = stream.advance(next)?;
+        next = step;
+        let Some(frame) = frame else {
+            break;
+        };
+        if let Some((x0, y0, x1, y1)) = picture_bounds(&frame) {
+            found = Some(match found {
+                None => (x0, y0, x1, y1),
+                Some((a0, b0, a1, b1)) => (a0.min(x0), b0.min(y0), a1.max(x1), b1.max(y1)),
+            });
+        }
+    }
+    Ok(found.and_then(|(x0, y0, x1, y1)| {
+        let crop = Crop { x: x0, y: y0, width: x1 - x0, height: y1 - y0 };
+        (crop != Crop { x: 0, y: 0, width: w, height: h }).then_some(crop)
+    }))
+}
+
+/// `(x0, y0, x1, y1)`, exclusive, of what isn't bar in one frame.
+fn picture_bounds(frame: &RgbaImage) -> Option<(u32, u32, u32, u32)> {
+    let (w, h) = frame.dimensions();
+    let lit = |x, y| {
+        let [r, g, b, a] = frame.get_pixel(x, y).0;
+        a > 0 && (299 * u32::from(r) + 587 * u32::from(g) + 114 * u32::from(b)) / 1000 > BAR_LUMA
+    };
+    let row = |y| (0..w).filter(|&x| lit(x, y)).count() as u32 > w / 32;
+    let column = |x| (0..h).filter(|&y| lit(x, y)).count() as u32 > h / 32;
+    let y0 = (0..h).find(|&y| row(y))?;
+    let y1 = (0..h).rev().find(|&y| row(y))? + 1;
+    let x0 = (0..w).find(|&x| column(x))?;
+    let x1 = (0..w).rev().find(|&x| column(x))? + 1;
+    Some((x0, y0, x1, y1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::video::Clip;
+    use image::Rgba;
+
+    /// A 4x2 frame whose every pixel is unique: red is x, green is y.
+    fn numbered() -> RgbaImage {
+        RgbaImage::from_fn(4, 2, |x, y| Rgba([x as u8, y as u8, 0, 255]))
+    }
+
+    #[test]
+    fn geometry_ops_move_pixels_where_they_say() {
+        let crop = Crop::parse("2x1+1+1").expect("parse");
+        let out = crop.apply(numbered()).expect("crop");
+        assert_eq!(out.dimensions(), (2, 1));
+        assert_eq!(out.get_pixel(0, 0).0[..2], [1, 1]);
+        assert!(Crop::parse("4x2+1+0").unwrap().apply(numbered()).is_err());
+
+        let turned = Rotation::Cw90.apply(numbered()).expect("rotate");
+        assert_eq!(turned.dimensions(), Rotation::Cw90.size(4, 2));
+        // Clockwise: the bottom-left corner comes up to the top-left.
+        assert_eq!(turned.get_pixel(0, 0).0[..2], [0, 1]);
+
+        let flipped = Flip::parse("hv").unwrap().apply(numbered()).expect("flip");
+        assert_eq!(flipped.get_pixel(0, 0).0[..2], [3, 1]);
+    }
+
+    #[test]
+    fn grade_identity_is_exact_and_saturation_zero_is_grey() {
+        let f = numbered();
+        assert_eq!(Grade::default().apply(f.clone()).unwrap(), f);
+
+        let grey = Grade { saturation: 0.0, ..Grade::default() };
+        let p = grey.apply(RgbaImage::from_pixel(1, 1, Rgba([200, 40, 60, 90]))).unwrap();
+        let [r, g, b, a] = p.get_pixel(0, 0).0;
+        assert!(r == g && g == b, "{r} {g} {b}");
+        assert_eq!(a, 90, "alpha is never graded");
+
+        let bright = Grade { brightness: 0.5, ..Grade::default() };
+        let p = bright.apply(RgbaImage::from_pixel(1, 1, Rgba([0, 128, 255, 255]))).unwrap();
+        assert_eq!(p.get_pixel(0, 0).0[..3], [128, 255, 255]);
+    }
+
+    #[test]
+    fn adjustments_report_the_size_their_stream_emits() {
+        let clip = Clip { width: 4, height: 2, fps: 1.0, frames: vec![numbered(); 3] };
+        let adjust = Adjustments {
+            crop: Crop::parse("3x2+1+0"),
+            rotation: Rotation::Ccw90,
+            flip: Flip { horizontal: true, vertical: false },
+            grade: Grade::default(),
+        };
+        let mut s = adjust.reshape(clip.open().expect("open"));
+        let (got, last) = s.advance(2).expect("advance");
+        assert_eq!(got, 2);
+        assert_eq!(last.unwrap().dimensions(), adjust.size(4, 2));
+        assert_eq!(adjust.size(4, 2), (2, 3));
+    }
+
+    #[test]
+    fn letterbox_detection_finds_the_picture_inside_the_bars() {
+        // 16x12 with four rows of bar above and below, and a fade to black
+        // that must not narrow anything.
+        let boxed = RgbaImage::from_fn(16, 12, |x, y| {
+            if (4..8).contains(&y) {
+                Rgba([100, (x * 10) as u8, 50, 255])
+            } else {
+                Rgba([8, 8, 8, 255])
+            }
+        });
+        let black = RgbaImage::from_pixel(16, 12, Rgba([0, 0, 0, 255]));
+        let frames = vec![boxed.clone(), black, boxed];
+        let clip = Clip { width: 16, height: 12, fps: 1.0, frames };
+        let crop = detect_letterbox(&clip, 0.0).expect("detect");
+        assert_eq!(crop, Some(Crop { x: 0, y: 4, width: 16, height: 4 }));
+
+        let grey = RgbaImage::from_pixel(4, 2, Rgba([128, 128, 128, 255]));
+        let full = Clip { width: 4, height: 2, fps: 1.0, frames: vec![grey] };
+        assert_eq!(detect_letterbox(&full, 0.0).expect("detect"), None);
+    }
+
+    #[test]
+    fn crops_parse_and_refuse_what_does_not_fit() {
+        assert_eq!(Crop::parse("1280x536"), Some(Crop { x: 0, y: 0, width: 1280, height: 536 }));
+        let crop = Crop::parse("1280x536+0+92").expect("parse");
+        assert_eq!(crop.to_string(), "1280x536+0+92");
+        assert_eq!(Crop::parse("0x10"), None);
+        assert_eq!(Crop::parse("10x10+5"), None);
+        assert!(Crop::parse("10x10+5+5").unwrap().check(15, 15).is_ok());
+        assert!(Crop::parse("10x10+6+5").unwrap().check(15, 15).is_err());
+    }
+}
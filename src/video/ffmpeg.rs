@@ -800,6 +800,34 @@ fn probe_metadata(path: &Path) -> Result<ProbedInfo, String> {
     Ok(ProbedInfo { width, height, fps, nb_frames, duration, is_vfr })
 }
 
+/// The start of every chapter `path`'s container lists, in seconds, for
+/// `--chapters container`. Empty, not an error, for a file with none.
+pub fn probe_chapters(path: &Path) -> Result<Vec<f64>, String> {
+    let ffprobe_bin = ffmpeg_sidecar::ffprobe::ffprobe_path();
+    let mut cmd = std::process::Command::new(&ffprobe_bin);
+    // No console window: see `hide_console`.
+    hide_console(&mut cmd);
+    let output = cmd
+        .args(["-v", "error", "-show_entries", "chapter=start_time", "-of", "csv=p=0"])
+        .arg(path)
+        .output()
+        .map_err(|e| format!("failed to run ffprobe on {}: {e}", path.display()))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(if stderr.trim().is_empty() {
+            format!("ffprobe exited with {} for {}", output.status, path.display())
+        } else {
+            format!("ffprobe failed for {}: {}", path.display(), stderr.trim())
+        });
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| line.trim().trim_end_matches(',').parse::<f64>().ok())
+        .filter(|t| t.is_finite())
+        .collect())
+}
+
 /// Whether ffprobe's `avg_frame_rate` and `r_frame_rate` disagree -- the
 /// standard signal that a stream is genuinely variable frame rate, chosen
 /// over comparing `nb_frames` against `duration * avg_frame_rate` because
@@ -0,0 +1,255 @@
+//! Transparency from the picture itself: a chroma key and a static mask.
+//!
+//! Until this, a render's only transparency was source alpha under
+//! `alpha_threshold`, and only GIF/WebP/APNG carry any -- a green-screen MP4
+//! arrives fully opaque. [`KeyStream`] cuts alpha out of each frame instead,
+//! so a keyed pixel is culled exactly as a transparent source pixel would
+//! be. It is a [`FrameStream`] adapter like `scale::ResizeStream`, layered by
+//! `stream::AdaptedSource`, so the builtin and ffmpeg backends both get it.
+
+use image::{RgbaImage, imageops};
+
+use super::stream::FrameStream;
+
+/// Remove pixels near one colour, the way a green screen is removed.
+///
+/// Distance is measured in the Cb/Cr plane only (BT.601, in byte units):
+/// how coloured a pixel is and toward which hue, not how bright. A grey or
+/// white foreground therefore sits far from any saturated key, however much
+/// light the screen behind it gets.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ChromaKey {
+    pub color: [u8; 3],
+    /// Chroma distance at or under which a pixel is fully removed.
+    pub tolerance: f32,
+    /// Width of the band past `tolerance` across which alpha ramps back up
+    /// to opaque. `0` is a hard edge.
+    pub softness: f32,
+    /// How much of the key colour's cast to take back out of the pixels
+    /// that stay, `0..=1`. Green light bounced onto hair and edges survives
+    /// the key and reads as a green fringe; `1` clamps the key's channel to
+    /// the larger of the other two, which removes it.
+    pub spill: f32,
+}
+
+impl ChromaKey {
+    pub const DEFAULT_TOLERANCE: f32 = 48.0;
+    pub const DEFAULT_SOFTNESS: f32 = 32.0;
+    pub const DEFAULT_SPILL: f32 = 1.0;
+
+    pub fn new(color: [u8; 3]) -> Self {
+        Self {
+            color,
+            tolerance: Self::DEFAULT_TOLERANCE,
+            softness: Self::DEFAULT_SOFTNESS,
+            spill: Self::DEFAULT_SPILL,
+        }
+    }
+
+    /// A key colour: `RRGGBB` (with or without `#`), or `green`/`blue` for
+    /// the pure primaries.
+    pub fn parse_color(s: &str) -> Option<[u8; 3]> {
+        match s.to_ascii_lowercase().as_str() {
+            "green" => Some([0, 255, 0]),
+            "blue" => Some([0, 0, 255]),
+            _ => crate::sign::parse_hex_color(s),
+        }
+    }
+
+    /// How much of a pixel survives, `0.0` (keyed out) to `1.0`.
+    fn coverage(&self, rgb: [u8; 3]) -> f32 {
+        let (cb, cr) = chroma(rgb);
+        let (kb, kr) = chroma(self.color);
+        let d = ((cb - kb).powi(2) + (cr - kr).powi(2)).sqrt();
+        if d <= self.tolerance {
+            0.0
+        } else if d >= self.tolerance + self.softness {
+            1.0
+        } else {
+            (d - self.tolerance) / self.softness
+        }
+    }
+
+    /// Pull the key's dominant channel down toward the other two.
+    fn despill(&self, rgb: &mut [u8; 3]) {
+        let k = dominant(self.color);
+        let others = (0..3).filter(|&c| c != k).map(|c| rgb[c]).max().unwrap_or(0);
+        if rgb[k] > others {
+            let excess = f32::from(rgb[k] - others);
+            rgb[k] -= (excess * self.spill).round() as u8;
+        }
+    }
+}
+
+/// BT.601 Cb/Cr, centred on zero.
+fn chroma([r, g, b]: [u8; 3]) -> (f32, f32) {
+    let (r, g, b) = (f32::from(r), f32::from(g), f32::from(b));
+    let cb = -0.168_736 * r - 0.331_264 * g + 0.5 * b;
+    let cr = 0.5 * r - 0.418_688 * g - 0.081_312 * b;
+    (cb, cr)
+}
+
+/// The channel a key colour is mostly made of; ties go to green, the usual
+/// screen.
+fn dominant(rgb: [u8; 3]) -> usize {
+    [1, 0, 2].into_iter().max_by_key(|&c| (rgb[c], c == 1)).unwrap_or(1)
+}
+
+/// Everything [`KeyStream`] applies: a chroma key, a mask, or both.
+#[derive(Clone, Debug, Default)]
+pub struct Keying {
+    pub chroma: Option<ChromaKey>,
+    /// Static per-pixel cover, stretched over every frame: white keeps,
+    /// black cuts, and the mask's own alpha cuts too. Grey is partial, so a
+    /// soft-edged mask fades rather than steps.
+    pub mask: Option<RgbaImage>,
+}
+
+impl Keying {
+    pub fn is_empty(&self) -> bool {
+        self.chroma.is_none() && self.mask.is_none()
+    }
+
+    /// Key one frame in place. `mask` must already be the frame's size.
+    fn apply(&self, frame: &mut RgbaImage, mask: Option<&RgbaImage>) {
+        for (x, y, p) in frame.enumerate_pixels_mut() {
+            let mut keep = 1.0;
+            if let Some(key) = &self.chroma {
+                let mut rgb = [p.0[0], p.0[1], p.0[2]];
+                keep = key.coverage(rgb);
+                key.despill(&mut rgb);
+                p.0[..3].copy_from_slice(&rgb);
+            }
+            if let Some(m) = mask {
+                let [r, g, b, a] = m.get_pixel(x, y).0;
+                let luma = 0.299 * f32::from(r) + 0.587 * f32::from(g) + 0.114 * f32::from(b);
+                keep *= luma / 255.0 * f32::from(a) / 255.0;
+            }
+            p.0[3] = (f32::from(p.0[3]) * keep).round() as u8;
+        }
+    }
+}
+
+/// Applies a [`Keying`] to every frame of the stream under it.
+pub struct KeyStream<'a> {
+    inner: Box<dyn FrameStream + 'a>,
+    keying: &'a Keying,
+    /// The mask at frame size, made on the first frame. Every frame of a
+    /// stream is one size (see `SourceInfo`), so it is stretched only once.
+    fitted: Option<RgbaImage>,
+}
+
+impl<'a> KeyStream<'a> {
+    pub fn new(inner: Box<dyn FrameStream + 'a>, keying: &'a Keying) -> Self {
+        Self { inner, keying, fitted: None }
+    }
+
+    fn key(&mut self, mut f: RgbaImage) -> RgbaImage {
+        if let Some(mask) = &self.keying.mask {
+            let stale = self.fitted.as_ref().is_none_or(|m| m.dimensions() != f.dimensions());
+            if stale {
+                self.fitted = Some(if mask.dimensions() == f.dimensions() {
+                    mask.clone()
+                } else {
+                    imageops::resize(mask, f.width(), f.height(), imageops::FilterType::Triangle)
+                });
+            }
+        }
+        self.keying.apply(&mut f, self.fitted.as_ref());
+        f
+    }
+}
+
+impl FrameStream for KeyStream<'_> {
+    fn next(&mut self) -> Result<Option<RgbaImage>, String> {
+        Ok(self.inner.next()?.map(|f| self.key(f)))
+    }
+
+    /// Keys only the frame `advance` keeps, as `ResizeStream` resizes only
+    /// that one: the frames passed over are never shown.
+    fn advance(&mut self, n: usize) -> Result<(usize, Option<RgbaImage>), String> {
+        let (got, last) = self.inner.advance(n)?;
+        Ok((got, last.map(|f| self.key(f))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::video::Clip;
+    use crate::video::stream::FrameSource;
+    use image::Rgba;
+
+    fn keyed(keying: &Keying, frame: RgbaImage) -> RgbaImage {
+        let (width, height) = frame.dimensions();
+        let clip = Clip { width, height, fps: 1.0, frames: vec![frame] };
+        let mut s = KeyStream::new(clip.open().expect("open"), keying);
+        s.next().expect("next").expect("a frame")
+    }
+
+    #[test]
+    fn the_key_colour_is_cut_and_far_colours_are_kept() {
+        let keying = Keying { chroma: Some(ChromaKey::new([0, 255, 0])), mask: None };
+        let frame = RgbaImage::from_fn(3, 1, |x, _| match x {
+            0 => Rgba([0, 255, 0, 255]),
+            // A real screen's green rather than the pure primary.
+            1 => Rgba([30, 230, 50, 255]),
+            _ => Rgba([200, 40, 60, 255]),
+        });
+        let out = keyed(&keying, frame);
+        assert_eq!(out.get_pixel(0, 0).0[3], 0);
+        assert_eq!(out.get_pixel(1, 0).0[3], 0);
+        assert_eq!(out.get_pixel(2, 0), &Rgba([200, 40, 60, 255]));
+    }
+
+    #[test]
+    fn softness_ramps_and_spill_is_pulled_down() {
+        let mut key = ChromaKey::new([0, 255, 0]);
+        key.tolerance = 0.0;
+        key.softness = 400.0;
+        let keying = Keying { chroma: Some(key), mask: None };
+        // Greenish grey: kept in part, and its green cast removed.
+        let out = keyed(&keying, RgbaImage::from_pixel(1, 1, Rgba([100, 140, 90, 255])));
+        let p = out.get_pixel(0, 0).0;
+        assert!(p[3] > 0 && p[3] < 255, "alpha {}", p[3]);
+        assert_eq!(p[1], 100, "green clamped to the larger of red and blue");
+
+        key.spill = 0.0;
+        let keying = Keying { chroma: Some(key), mask: None };
+        let out = keyed(&keying, RgbaImage::from_pixel(1, 1, Rgba([100, 140, 90, 255])));
+        assert_eq!(out.get_pixel(0, 0).0[1], 140);
+    }
+
+    #[test]
+    fn a_mask_is_stretched_to_the_frame_and_multiplies_alpha() {
+        // Left half black, right half white, at half the frame's size.
+        let mask = RgbaImage::from_fn(4, 1, |x, _| {
+            if x < 2 { Rgba([0, 0, 0, 255]) } else { Rgba([255, 255, 255, 255]) }
+        });
+        let keying = Keying { chroma: None, mask: Some(mask) };
+        let frame = RgbaImage::from_pixel(8, 2, Rgba([10, 20, 30, 200]));
+        let out = keyed(&keying, frame);
+        for y in 0..2 {
+            assert_eq!(out.get_pixel(0, y).0, [10, 20, 30, 0]);
+            assert_eq!(out.get_pixel(7, y).0, [10, 20, 30, 200]);
+        }
+    }
+
+    #[test]
+    fn advance_keys_the_frame_it_keeps() {
+        let frames = (0..4).map(|i| RgbaImage::from_pixel(1, 1, Rgba([0, 255, 0, i]))).collect();
+        let clip = Clip { width: 1, height: 1, fps: 1.0, frames };
+        let keying = Keying { chroma: Some(ChromaKey::new([0, 255, 0])), mask: None };
+        let mut s = KeyStream::new(clip.open().expect("open"), &keying);
+        let (got, last) = s.advance(3).expect("advance");
+        assert_eq!(got, 3);
+        assert_eq!(last.expect("a frame").get_pixel(0, 0).0[3], 0);
+    }
+
+    #[test]
+    fn key_colours_parse_by_name_or_hex() {
+        assert_eq!(ChromaKey::parse_color("Green"), Some([0, 255, 0]));
+        assert_eq!(ChromaKey::parse_color("#00b140"), Some([0, 0xb1, 0x40]));
+        assert_eq!(ChromaKey::parse_color("teal"), None);
+    }
+}
@@ -1,11 +1,13 @@
 use image::RgbaImage;
 
+pub mod adjust;
 pub mod animated;
 pub mod backend;
 pub mod demux;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod ffmpeg;
 pub mod builtin;
+pub mod key;
 pub mod scale;
 pub mod sequence;
 pub mod source;
@@ -122,9 +122,12 @@ impl FrameSource for crate::video::Clip {
     }
 }
 
+use super::adjust::{Adjustments, OpStream};
+use super::key::{KeyStream, Keying};
 use super::scale::{FitMode, Filter, FpsStream, ResizeStream, estimated_frame_count};
 
-/// A source with resize and fps conversion layered over it.
+/// A source with crop/rotate/flip, resize, keying, colour grading and fps
+/// conversion layered over it, in that order.
 pub struct AdaptedSource<'a> {
     pub inner: &'a dyn FrameSource,
     pub size: Option<(u32, u32)>,
@@ -134,12 +137,19 @@ pub struct AdaptedSource<'a> {
     pub start_s: f32,
     pub duration_s: Option<f32>,
     pub max_frames: usize,
+    /// Chroma key and mask (see `video::key`); `None` leaves alpha alone.
+    pub key: Option<&'a Keying>,
+    /// Crop, rotation, mirror and grade (see `video::adjust`). `size` is the
+    /// size AFTER these, so a crop is never stretched back out to the
+    /// source's aspect.
+    pub adjust: Adjustments,
 }
 
 impl FrameSource for AdaptedSource<'_> {
     fn info(&self) -> SourceInfo {
         let base = self.inner.info();
-        let (width, height) = self.size.unwrap_or((base.width, base.height));
+        let (width, height) =
+            self.size.unwrap_or_else(|| self.adjust.size(base.width, base.height));
         SourceInfo {
             width,
             height,
@@ -194,10 +204,27 @@ impl FrameSource for AdaptedSource<'_> {
 
     fn open(&self) -> Result<Box<dyn FrameStream + '_>, String> {
         let base = self.inner.info();
-        let mut s = self.inner.open()?;
+        self.adjust.check(base.width, base.height)?;
+        // Reshaped first, at the raw frames' own size, since a crop
+        // rectangle is in source pixels -- which is also why a caller must
+        // not hand `open_video` a target size when `adjust.reshapes()`.
+        let mut s = self.adjust.reshape(self.inner.open()?);
         if let Some((w, h)) = self.size {
             s = Box::new(ResizeStream::new(s, w, h, self.fit, self.filter));
         }
+        // Keyed after the resize, so on both backends it sees target-sized
+        // frames (ffmpeg scales in its own filtergraph, the builtin one in
+        // `ResizeStream`): the mask is stretched to the same size either way,
+        // and each pixel is keyed once rather than once per source pixel.
+        // Under `FpsStream`, whose `advance` means only kept frames are keyed.
+        if let Some(key) = self.key.filter(|k| !k.is_empty()) {
+            s = Box::new(KeyStream::new(s, key));
+        }
+        // Graded after the key, so the key matches the colour as shot --
+        // the one the user picked off the source -- rather than as boosted.
+        if !self.adjust.grade.is_identity() {
+            s = Box::new(OpStream::new(s, self.adjust.grade));
+        }
         Ok(Box::new(FpsStream::new(
             s,
             base.fps,
@@ -317,6 +344,8 @@ mod tests {
             start_s: 0.0,
             duration_s: None,
             max_frames: 1000,
+            key: None,
+            adjust: Adjustments::default(),
         };
         let info = adapted.info();
         assert_eq!((info.width, info.height), (6, 4), "info must report the ADAPTED size");
@@ -339,6 +368,40 @@ mod tests {
         assert_eq!(info.frame_count_hint, Some(n), "the hint must match what was actually emitted");
     }
 
+    /// With no resize, the size `info` promises is the cropped, turned one
+    /// -- the `SourceInfo` contract holds through the reshape.
+    #[test]
+    fn an_adapted_source_reports_the_reshaped_size() {
+        let frames = vec![RgbaImage::from_pixel(6, 4, Rgba([9, 9, 9, 255])); 3];
+        let base = crate::video::Clip { width: 6, height: 4, fps: 10.0, frames };
+        let adjust = Adjustments {
+            crop: crate::video::adjust::Crop::parse("5x3+1+1"),
+            rotation: crate::video::adjust::Rotation::Cw90,
+            ..Adjustments::default()
+        };
+        let adapted = AdaptedSource {
+            inner: &base,
+            size: None,
+            fit: crate::video::scale::FitMode::Exact,
+            filter: crate::video::scale::Filter::Nearest,
+            target_fps: 10.0,
+            start_s: 0.0,
+            duration_s: None,
+            max_frames: 1000,
+            key: None,
+            adjust,
+        };
+        let info = adapted.info();
+        assert_eq!((info.width, info.height), (3, 5));
+        let mut s = adapted.open().expect("open");
+        while let Some(f) = s.next().expect("next") {
+            assert_eq!(f.dimensions(), (3, 5));
+        }
+
+        let off = Adjustments { crop: crate::video::adjust::Crop::parse("6x4+1+0"), ..adjust };
+        assert!(AdaptedSource { adjust: off, ..adapted }.open().is_err(), "a crop off the frame");
+    }
+
     /// The bug this fixes, reproduced directly: a `--duration` window used to
     /// be ignored entirely because the old check only looked at whether
     /// `target_fps == base.fps`, which is true for every frame sequence
@@ -355,6 +418,8 @@ mod tests {
             start_s: 0.0,
             duration_s: Some(1.0), // 10 frames' worth at 10fps
             max_frames: 1000,
+            key: None,
+            adjust: Adjustments::default(),
         };
         assert_eq!(
             adapted.info().frame_count_hint,
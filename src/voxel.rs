@@ -0,0 +1,757 @@
+//! Voxel models: a dense colour grid and a reader for MagicaVoxel `.vox`
+//! files.
+//!
+//! The grid is in BRICKADIA's axes: `x` and `y` across the ground, `z` up,
+//! left-handed. MagicaVoxel is z-up as well but right-handed, so the reader
+//! mirrors `y` on the way in -- without that every model comes out as its own
+//! mirror image, which is invisible on a sphere and very visible on text or a
+//! character holding something in one hand.
+//!
+//! The reader is hand-rolled rather than a dependency, the same call the
+//! subtitle parsers made: the format is a handful of fixed-layout chunks, and
+//! the parts a brick build can use (sizes, voxels, palette, scene placement)
+//! are a small fraction of what a full loader carries.
+
+use image::RgbaImage;
+use std::{collections::HashMap, path::Path};
+
+/// The most cells a grid may hold. Each cell is 4 bytes, so this is 512 MiB
+/// of grid -- far past any single MagicaVoxel model (256 on a side at most),
+/// but a SCENE can scatter models across thousands of cells, and its bounding
+/// box is what gets allocated. Refused by name rather than left to the
+/// allocator, which would abort the process with no message at all.
+pub const MAX_VOXEL_CELLS: u64 = 1 << 27;
+
+/// The most scene-graph nodes [`read_vox`] will visit. The depth cap stops a
+/// cycle, but not a graph of groups that list the same children again and
+/// again: that is finite, and exponential. A real scene visits each node
+/// once per instance, so this is far past any file the editor writes.
+const MAX_SCENE_VISITS: usize = 1 << 20;
+
+/// A dense grid of coloured voxels.
+///
+/// Cells hold an index into [`VoxelGrid::palette`], 0 meaning empty, rather
+/// than a colour each: the mesher splits every layer by colour, and comparing
+/// one integer per cell is what keeps that split cheap. The palette grows as
+/// colours are set, so a mesh sampled from a texture is not limited to the
+/// 255 colours a `.vox` can name.
+#[derive(Debug)]
+pub struct VoxelGrid {
+    size: (u32, u32, u32),
+    cells: Vec<u32>,
+    /// Colour of each palette index. Entry 0 is the empty cell and is never
+    /// drawn.
+    pub palette: Vec<[u8; 4]>,
+    lookup: HashMap<[u8; 4], u32>,
+}
+
+impl VoxelGrid {
+    /// An empty grid of `size` cells.
+    pub fn new(size: (u32, u32, u32)) -> Result<Self, String> {
+        // In `u128`: three `u32` sides can overflow a `u64`.
+        let cells = size.0 as u128 * size.1 as u128 * size.2 as u128;
+        if cells == 0 {
+            return Err(format!(
+                "a voxel grid of {} x {} x {} has no cells to fill",
+                size.0, size.1, size.2
+            ));
+        }
+        if cells > MAX_VOXEL_CELLS as u128 {
+            return Err(format!(
+                "a voxel grid of {} x {} x {} is {cells} cells, more than the {MAX_VOXEL_CELLS} \
+                 this converter will allocate. For a .vox scene this is the bounding box of \
+                 every model in it, so models placed far apart cost the space between them as \
+                 well; move them together or export one model at a time",
+                size.0, size.1, size.2
+            ));
+        }
+        Ok(VoxelGrid {
+            size,
+            cells: vec![0; cells as usize],
+            palette: vec![[0; 4]],
+            lookup: HashMap::new(),
+        })
+    }
+
+    /// The grid's size in cells, `(x, y, z)`.
+    pub fn size(&self) -> (u32, u32, u32) {
+        self.size
+    }
+
+    fn index(&self, x: u32, y: u32, z: u32) -> usize {
+        (x as usize + y as usize * self.size.0 as usize)
+            + z as usize * self.size.0 as usize * self.size.1 as usize
+    }
+
+    /// Fill a cell with `color`, adding it to the palette if it is new.
+    pub fn set(&mut self, x: u32, y: u32, z: u32, color: [u8; 4]) {
+        let entry = match self.lookup.get(&color) {
+            Some(&i) => i,
+            None => {
+                let i = self.palette.len() as u32;
+                self.palette.push(color);
+                self.lookup.insert(color, i);
+                i
+            }
+        };
+        let i = self.index(x, y, z);
+        self.cells[i] = entry;
+    }
+
+    /// The palette index of a cell, 0 if it is empty.
+    pub fn entry(&self, x: u32, y: u32, z: u32) -> u32 {
+        self.cells[self.index(x, y, z)]
+    }
+
+    /// The colour of a cell, `None` if it is empty.
+    pub fn get(&self, x: u32, y: u32, z: u32) -> Option<[u8; 4]> {
+        match self.entry(x, y, z) {
+            0 => None,
+            i => Some(self.palette[i as usize]),
+        }
+    }
+
+    /// How many cells are filled.
+    pub fn count(&self) -> usize {
+        self.cells.iter().filter(|&&c| c != 0).count()
+    }
+
+    /// The model seen from above: each pixel is the colour of the highest
+    /// filled cell in its column, transparent where the column is empty.
+    ///
+    /// This is the save preview. A heightmap render uses its colormap for
+    /// the same purpose, and this is the same view of a voxel build.
+    pub fn top_view(&self) -> RgbaImage {
+        let (w, h, d) = self.size;
+        RgbaImage::from_fn(w, h, |x, y| {
+            let top = (0..d).rev().find_map(|z| self.get(x, y, z));
+            image::Rgba(top.unwrap_or([0; 4]))
+        })
+    }
+
+    /// Read a MagicaVoxel `.vox` file.
+    pub fn open(file: impl AsRef<Path>) -> Result<Self, String> {
+        let file = file.as_ref();
+        let bytes = std::fs::read(file)
+            .map_err(|e| format!("Could not read voxel model {}: {e}", file.display()))?;
+        read_vox(&bytes).map_err(|e| format!("{}: {e}", file.display()))
+    }
+}
+
+/// MagicaVoxel's built-in palette, used when a file has no `RGBA` chunk.
+///
+/// Entry `i` is colour index `i + 1`, the same shift the `RGBA` chunk uses.
+/// It is a 6x6x6 colour cube without black, then ten-step red, green, blue
+/// and grey ramps, and a transparent last entry -- generated rather than
+/// pasted as 1 KiB of literals, because that is what it is.
+pub fn default_palette() -> [[u8; 4]; 256] {
+    const CUBE: [u8; 6] = [0xff, 0xcc, 0x99, 0x66, 0x33, 0x00];
+    const RAMP: [u8; 10] = [0xee, 0xdd, 0xbb, 0xaa, 0x88, 0x77, 0x55, 0x44, 0x22, 0x11];
+
+    let mut palette = [[0u8; 4]; 256];
+    let mut i = 0;
+    for r in CUBE {
+        for g in CUBE {
+            for b in CUBE {
+                if (r, g, b) != (0, 0, 0) {
+                    palette[i] = [r, g, b, 255];
+                    i += 1;
+                }
+            }
+        }
+    }
+    for ramp in [
+        |v| [v, 0, 0, 255],
+        |v| [0, v, 0, 255],
+        |v| [0, 0, v, 255],
+        |v| [v, v, v, 255],
+    ] {
+        for v in RAMP {
+            palette[i] = ramp(v);
+            i += 1;
+        }
+    }
+    palette
+}
+
+/// A model as the file stores it: its size, then `(x, y, z, colour index)`
+/// per voxel, in the model's own right-handed frame.
+struct VoxModel {
+    size: [i32; 3],
+    voxels: Vec<[u8; 4]>,
+}
+
+/// A rotation and translation taking a model's cells into the scene.
+type Placement = ([[i32; 3]; 3], [i32; 3]);
+
+/// One node of the scene graph MagicaVoxel 0.99+ writes. Older files have
+/// none, and every model sits at the origin.
+enum SceneNode {
+    Transform {
+        child: i32,
+        rotation: [[i32; 3]; 3],
+        translation: [i32; 3],
+    },
+    Group(Vec<i32>),
+    Shape(Vec<i32>),
+}
+
+/// Bounds-checked little-endian reads over a chunk's bytes. Every read names
+/// what it was reading, so a truncated file says WHERE it was cut short.
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize, what: &str) -> Result<&'a [u8], String> {
+        let end = self
+            .pos
+            .checked_add(n)
+            .filter(|&end| end <= self.bytes.len())
+            .ok_or_else(|| format!("the file ends in the middle of {what}"))?;
+        let out = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(out)
+    }
+
+    fn i32(&mut self, what: &str) -> Result<i32, String> {
+        let b = self.take(4, what)?;
+        Ok(i32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    fn len(&mut self, what: &str) -> Result<usize, String> {
+        let n = self.i32(what)?;
+        usize::try_from(n).map_err(|_| format!("{what} has a negative length ({n})"))
+    }
+
+    fn string(&mut self, what: &str) -> Result<String, String> {
+        let n = self.len(what)?;
+        Ok(String::from_utf8_lossy(self.take(n, what)?).into_owned())
+    }
+
+    fn dict(&mut self, what: &str) -> Result<HashMap<String, String>, String> {
+        let n = self.len(what)?;
+        let mut dict = HashMap::new();
+        for _ in 0..n {
+            let key = self.string(what)?;
+            let value = self.string(what)?;
+            dict.insert(key, value);
+        }
+        Ok(dict)
+    }
+}
+
+/// Decode MagicaVoxel's packed rotation byte into a matrix.
+///
+/// Bits 0-1 are the column of the first row's non-zero entry, bits 2-3 the
+/// second row's, and the third row takes the column left over; bits 4-6 are
+/// the three rows' signs.
+fn decode_rotation(r: u8) -> Result<[[i32; 3]; 3], String> {
+    let first = (r & 3) as usize;
+    let second = ((r >> 2) & 3) as usize;
+    if first > 2 || second > 2 || first == second {
+        return Err(format!("a scene transform has an invalid rotation ({r})"));
+    }
+    let third = 3 - first - second;
+    let mut m = [[0; 3]; 3];
+    for (row, col) in [first, second, third].into_iter().enumerate() {
+        m[row][col] = if r & (16 << row) != 0 { -1 } else { 1 };
+    }
+    Ok(m)
+}
+
+fn mul(a: [[i32; 3]; 3], b: [[i32; 3]; 3]) -> [[i32; 3]; 3] {
+    let mut m = [[0; 3]; 3];
+    for (r, row) in m.iter_mut().enumerate() {
+        for (c, cell) in row.iter_mut().enumerate() {
+            *cell = (0..3).map(|k| a[r][k] * b[k][c]).sum();
+        }
+    }
+    m
+}
+
+/// `m` applied to `v`, or `None` if a coordinate leaves `i32`. `m` is a
+/// signed permutation, but its `-1` still overflows on `i32::MIN`.
+fn apply(m: [[i32; 3]; 3], v: [i32; 3]) -> Option<[i32; 3]> {
+    let row = |r: usize| {
+        (0..3).try_fold(0i32, |sum, k| sum.checked_add(m[r][k].checked_mul(v[k])?))
+    };
+    Some([row(0)?, row(1)?, row(2)?])
+}
+
+/// `a + b`, or `None` if a coordinate leaves `i32`.
+fn add(a: [i32; 3], b: [i32; 3]) -> Option<[i32; 3]> {
+    Some([a[0].checked_add(b[0])?, a[1].checked_add(b[1])?, a[2].checked_add(b[2])?])
+}
+
+/// Parse a `.vox` file's bytes into a grid.
+///
+/// Every model is placed where the file's scene graph puts it -- translation
+/// and the 90-degree rotations MagicaVoxel allows -- and the grid is the
+/// bounding box of all of them, so a multi-model scene converts as the one
+/// build it is in the editor. A file without a scene graph (written before
+/// MagicaVoxel 0.99) stacks its models at the origin, which is also how that
+/// editor shows them.
+pub fn read_vox(bytes: &[u8]) -> Result<VoxelGrid, String> {
+    let mut r = Reader { bytes, pos: 0 };
+    if r.take(4, "the header")? != b"VOX " {
+        return Err("not a MagicaVoxel file (it does not start with 'VOX ')".to_string());
+    }
+    r.i32("the header")?;
+
+    if r.take(4, "the MAIN chunk")? != b"MAIN" {
+        return Err("the file has no MAIN chunk after its header".to_string());
+    }
+    let main_content = r.len("the MAIN chunk")?;
+    let main_children = r.len("the MAIN chunk")?;
+    r.take(main_content, "the MAIN chunk")?;
+    let end = r
+        .pos
+        .checked_add(main_children)
+        .filter(|&end| end <= bytes.len())
+        .ok_or("the file ends in the middle of the MAIN chunk")?;
+
+    let mut models: Vec<VoxModel> = vec![];
+    let mut pending_size: Option<[i32; 3]> = None;
+    let mut palette: Option<[[u8; 4]; 256]> = None;
+    let mut nodes: HashMap<i32, SceneNode> = HashMap::new();
+
+    while r.pos < end {
+        let id: [u8; 4] = r.take(4, "a chunk header")?.try_into().unwrap();
+        let content = r.len("a chunk header")?;
+        let children = r.len("a chunk header")?;
+        let name = String::from_utf8_lossy(&id).into_owned();
+        let what = format!("the {name} chunk");
+        let mut c = Reader {
+            bytes: r.take(content, &what)?,
+            pos: 0,
+        };
+        r.take(children, &what)?;
+
+        match &id {
+            b"SIZE" => {
+                let size = [c.i32(&what)?, c.i32(&what)?, c.i32(&what)?];
+                if size.iter().any(|&s| s <= 0) {
+                    return Err(format!(
+                        "a model has an invalid size {} x {} x {}",
+                        size[0], size[1], size[2]
+                    ));
+                }
+                pending_size = Some(size);
+            }
+            b"XYZI" => {
+                let size = pending_size.take().ok_or(
+                    "the file has voxel data (XYZI) with no model size (SIZE) before it",
+                )?;
+                let n = c.len(&what)?;
+                let data = c.take(n.checked_mul(4).ok_or("XYZI count overflows")?, &what)?;
+                models.push(VoxModel {
+                    size,
+                    voxels: data
+                        .chunks_exact(4)
+                        .map(|v| [v[0], v[1], v[2], v[3]])
+                        .collect(),
+                });
+            }
+            b"RGBA" => {
+                let data = c.take(256 * 4, &what)?;
+                let mut p = [[0u8; 4]; 256];
+                for (entry, rgba) in p.iter_mut().zip(data.chunks_exact(4)) {
+                    *entry = [rgba[0], rgba[1], rgba[2], rgba[3]];
+                }
+                palette = Some(p);
+            }
+            b"nTRN" => {
+                let node = c.i32(&what)?;
+                c.dict(&what)?;
+                let child = c.i32(&what)?;
+                c.i32(&what)?; // reserved id
+                c.i32(&what)?; // layer
+                let frames = c.len(&what)?;
+                // Animation frames are keyframes; the first is the pose the
+                // editor opens on, and a brick build has one pose.
+                let mut rotation = [[1, 0, 0], [0, 1, 0], [0, 0, 1]];
+                let mut translation = [0; 3];
+                for frame in 0..frames {
+                    let attrs = c.dict(&what)?;
+                    if frame > 0 {
+                        continue;
+                    }
+                    if let Some(rot) = attrs.get("_r") {
+                        let byte = rot
+                            .trim()
+                            .parse::<u8>()
+                            .map_err(|_| format!("a scene transform has a bad rotation '{rot}'"))?;
+                        rotation = decode_rotation(byte)?;
+                    }
+                    if let Some(t) = attrs.get("_t") {
+                        let parts = t
+                            .split_whitespace()
+                            .map(|p| p.parse::<i32>())
+                            .collect::<Result<Vec<_>, _>>()
+                            .ok()
+                            .filter(|p| p.len() == 3)
+                            .ok_or_else(|| {
+                                format!("a scene transform has a bad translation '{t}'")
+                            })?;
+                        translation = [parts[0], parts[1], parts[2]];
+                    }
+                }
+                nodes.insert(
+                    node,
+                    SceneNode::Transform {
+                        child,
+                        rotation,
+                        translation,
+                    },
+                );
+            }
+            b"nGRP" => {
+                let node = c.i32(&what)?;
+                c.dict(&what)?;
+                let n = c.len(&what)?;
+                let children = (0..n)
+                    .map(|_| c.i32(&what))
+                    .collect::<Result<Vec<_>, _>>()?;
+                nodes.insert(node, SceneNode::Group(children));
+            }
+            b"nSHP" => {
+                let node = c.i32(&what)?;
+                c.dict(&what)?;
+                let n = c.len(&what)?;
+                let mut shape = vec![];
+                for _ in 0..n {
+                    shape.push(c.i32(&what)?);
+                    c.dict(&what)?;
+                }
+                nodes.insert(node, SceneNode::Shape(shape));
+            }
+            // PACK, materials, layers, cameras, notes: nothing a brick can
+            // show.
+            _ => {}
+        }
+    }
+
+    if models.is_empty() {
+        return Err("the file has no models in it".to_string());
+    }
+
+    // Each placed model: which one, and the rotation/translation taking its
+    // cells into the scene. `None` rotation means "as stored, no pivot".
+    let mut placed: Vec<(usize, Option<Placement>)> = vec![];
+    if nodes.contains_key(&0) {
+        let identity = [[1, 0, 0], [0, 1, 0], [0, 0, 1]];
+        let mut stack = vec![(0, identity, [0; 3], 0usize)];
+        let mut visits = 0usize;
+        while let Some((id, rot, t, depth)) = stack.pop() {
+            // A cycle in a malformed graph would otherwise never end.
+            if depth > 1024 {
+                return Err("the scene graph is nested too deeply (is it cyclic?)".to_string());
+            }
+            visits += 1;
+            if visits > MAX_SCENE_VISITS {
+                return Err(format!(
+                    "the scene graph expands to over {MAX_SCENE_VISITS} nodes (does it list \
+                     the same children over and over?)"
+                ));
+            }
+            match nodes.get(&id) {
+                Some(SceneNode::Transform {
+                    child,
+                    rotation,
+                    translation,
+                }) => {
+                    let placed = apply(rot, *translation)
+                        .and_then(|inner| add(t, inner))
+                        .ok_or("a scene translation is out of range")?;
+                    stack.push((*child, mul(rot, *rotation), placed, depth + 1));
+                }
+                Some(SceneNode::Group(children)) => {
+                    for &child in children {
+                        stack.push((child, rot, t, depth + 1));
+                    }
+                }
+                Some(SceneNode::Shape(shape)) => {
+                    for &model in shape {
+                        let model = usize::try_from(model)
+                            .ok()
+                            .filter(|&m| m < models.len())
+                            .ok_or_else(|| {
+                                format!("the scene places model {model}, which does not exist")
+                            })?;
+                        placed.push((model, Some((rot, t))));
+                    }
+                }
+                None => return Err(format!("the scene refers to missing node {id}")),
+            }
+        }
+    } else {
+        placed.extend((0..models.len()).map(|m| (m, None)));
+    }
+
+    // Every voxel's scene position, in a first pass for the bounding box and
+    // a second to fill the grid. Resolved twice rather than collected once:
+    // instancing can place far more voxels than the grid has cells, so the
+    // grid's size check has to run before anything sized by the voxel count
+    // is allocated.
+    let resolve = |model: &VoxModel, transform: Option<Placement>, v: &[u8; 4]| {
+        let local = [v[0] as i32, v[1] as i32, v[2] as i32];
+        match transform {
+            // MagicaVoxel pivots a model about its centre cell, rounded
+            // down, before rotating and translating it.
+            Some((rot, t)) => {
+                let centred = [0, 1, 2].map(|a| local[a] - model.size[a] / 2);
+                apply(rot, centred)
+                    .and_then(|p| add(p, t))
+                    .ok_or("a scene translation is out of range")
+            }
+            None => Ok(local),
+        }
+    };
+
+    let mut min = [i32::MAX; 3];
+    let mut max = [i32::MIN; 3];
+    for &(model, transform) in &placed {
+        let model = &models[model];
+        for v in model.voxels.iter().filter(|v| v[3] != 0) {
+            let p = resolve(model, transform, v)?;
+            for a in 0..3 {
+                min[a] = min[a].min(p[a]);
+                max[a] = max[a].max(p[a]);
+            }
+        }
+    }
+    if min[0] > max[0] {
+        return Err("every model in the file is empty".to_string());
+    }
+
+    // Across the whole of `i32` an axis spans 2^32 cells, one more than a
+    // `u32` holds; that is refused by `VoxelGrid::new` all the same, so it
+    // saturates rather than wrapping to an empty axis.
+    let size = [0, 1, 2].map(|a| {
+        u32::try_from(max[a] as i64 - min[a] as i64 + 1).unwrap_or(u32::MAX)
+    });
+    let mut grid = VoxelGrid::new((size[0], size[1], size[2]))?;
+
+    let palette = palette.unwrap_or_else(default_palette);
+    for (model, transform) in placed {
+        let model = &models[model];
+        for v in model.voxels.iter().filter(|v| v[3] != 0) {
+            let p = resolve(model, transform, v)?;
+            // Mirror y: see the module docs.
+            grid.set(
+                (p[0] - min[0]) as u32,
+                (max[1] - p[1]) as u32,
+                (p[2] - min[2]) as u32,
+                palette[v[3] as usize - 1],
+            );
+        }
+    }
+    Ok(grid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunk(id: &[u8; 4], content: &[u8]) -> Vec<u8> {
+        let mut out = id.to_vec();
+        out.extend((content.len() as i32).to_le_bytes());
+        out.extend(0i32.to_le_bytes());
+        out.extend(content);
+        out
+    }
+
+    fn ints(values: &[i32]) -> Vec<u8> {
+        values.iter().flat_map(|v| v.to_le_bytes()).collect()
+    }
+
+    fn string(s: &str) -> Vec<u8> {
+        let mut out = ints(&[s.len() as i32]);
+        out.extend(s.as_bytes());
+        out
+    }
+
+    /// A `.vox` file holding `children` inside MAIN.
+    fn vox(children: &[Vec<u8>]) -> Vec<u8> {
+        let body: Vec<u8> = children.concat();
+        let mut out = b"VOX ".to_vec();
+        out.extend(150i32.to_le_bytes());
+        out.extend(b"MAIN");
+        out.extend(0i32.to_le_bytes());
+        out.extend((body.len() as i32).to_le_bytes());
+        out.extend(body);
+        out
+    }
+
+    fn model(size: [i32; 3], voxels: &[[u8; 4]]) -> Vec<Vec<u8>> {
+        let mut xyzi = ints(&[voxels.len() as i32]);
+        xyzi.extend(voxels.iter().flatten());
+        vec![chunk(b"SIZE", &ints(&size)), chunk(b"XYZI", &xyzi)]
+    }
+
+    #[test]
+    fn the_default_palette_is_the_editors() {
+        let p = default_palette();
+        assert_eq!(p[0], [255, 255, 255, 255], "index 1 is white");
+        assert_eq!(p[1], [255, 255, 204, 255], "blue varies fastest");
+        assert_eq!(p[214], [0, 0, 51, 255], "the cube ends just before black");
+        assert_eq!(p[215], [238, 0, 0, 255], "then the red ramp");
+        assert_eq!(p[254], [17, 17, 17, 255], "the grey ramp ends the colours");
+        assert_eq!(p[255], [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn a_single_model_reads_with_its_palette_and_mirrored_y() {
+        let mut rgba = vec![0u8; 1024];
+        // Colour index 1 is the FIRST palette entry, index 2 the second.
+        rgba[0..4].copy_from_slice(&[10, 20, 30, 255]);
+        rgba[4..8].copy_from_slice(&[40, 50, 60, 255]);
+        let mut children = model([2, 3, 1], &[[0, 0, 0, 1], [1, 2, 0, 2]]);
+        children.push(chunk(b"RGBA", &rgba));
+
+        let grid = read_vox(&vox(&children)).unwrap();
+        assert_eq!(grid.size(), (2, 3, 1));
+        assert_eq!(grid.count(), 2);
+        // y = 0 in the file is the LAST row here.
+        assert_eq!(grid.get(0, 2, 0), Some([10, 20, 30, 255]));
+        assert_eq!(grid.get(1, 0, 0), Some([40, 50, 60, 255]));
+        assert_eq!(grid.get(0, 0, 0), None);
+    }
+
+    #[test]
+    fn a_file_without_a_palette_uses_the_default_one() {
+        let grid = read_vox(&vox(&model([1, 1, 1], &[[0, 0, 0, 216]]))).unwrap();
+        assert_eq!(grid.get(0, 0, 0), Some([238, 0, 0, 255]));
+    }
+
+    #[test]
+    fn scene_translations_place_models_apart() {
+        let mut children = model([1, 1, 1], &[[0, 0, 0, 1]]);
+        children.extend(model([1, 1, 1], &[[0, 0, 0, 2]]));
+
+        let trn = |node: i32, child: i32, t: &str| {
+            let mut c = ints(&[node, 0, child, -1, 0, 1]);
+            c.extend(ints(&[1]));
+            c.extend(string("_t"));
+            c.extend(string(t));
+            chunk(b"nTRN", &c)
+        };
+        let shp = |node: i32, model: i32| chunk(b"nSHP", &ints(&[node, 0, 1, model, 0]));
+        children.push(trn(0, 1, "0 0 0"));
+        children.push(chunk(b"nGRP", &ints(&[1, 0, 2, 2, 4])));
+        children.push(trn(2, 3, "0 0 0"));
+        children.push(shp(3, 0));
+        children.push(trn(4, 5, "4 0 2"));
+        children.push(shp(5, 1));
+
+        let grid = read_vox(&vox(&children)).unwrap();
+        assert_eq!(grid.size(), (5, 1, 3));
+        assert_eq!(grid.get(0, 0, 0), Some(default_palette()[0]));
+        assert_eq!(grid.get(4, 0, 2), Some(default_palette()[1]));
+        assert_eq!(grid.count(), 2);
+    }
+
+    /// A transform that a crafted file pushes past `i32`, nested so the sum
+    /// overflows, and one negated by a rotation at `i32::MIN`.
+    #[test]
+    fn a_translation_out_of_range_is_refused_not_wrapped() {
+        let trn = |node: i32, child: i32, attrs: &[(&str, &str)]| {
+            let mut c = ints(&[node, 0, child, -1, 0, 1, attrs.len() as i32]);
+            for (k, v) in attrs {
+                c.extend(string(k));
+                c.extend(string(v));
+            }
+            chunk(b"nTRN", &c)
+        };
+        let shp = |node: i32| chunk(b"nSHP", &ints(&[node, 0, 1, 0, 0]));
+        let max = i32::MAX.to_string();
+        let min = format!("{} 0 0", i32::MIN);
+
+        let mut nested = model([1, 1, 1], &[[0, 0, 0, 1]]);
+        nested.push(trn(0, 1, &[("_t", &format!("{max} 0 0"))]));
+        nested.push(trn(1, 2, &[("_t", &format!("{max} 0 0"))]));
+        nested.push(shp(2));
+        assert!(read_vox(&vox(&nested)).unwrap_err().contains("out of range"));
+
+        // Rotation 4 | 16 is the identity with x negated.
+        let mut negated = model([1, 1, 1], &[[0, 0, 0, 1]]);
+        negated.push(trn(0, 1, &[("_r", "20")]));
+        negated.push(trn(1, 2, &[("_t", &min)]));
+        negated.push(shp(2));
+        assert!(read_vox(&vox(&negated)).unwrap_err().contains("out of range"));
+    }
+
+    /// Two models at opposite ends of `i32`: every translation is in range,
+    /// but the bounding box spans 2^32 cells on x, one more than a `u32`.
+    #[test]
+    fn a_scene_spanning_all_of_i32_is_refused_by_its_size() {
+        let mut children = model([1, 1, 1], &[[0, 0, 0, 1]]);
+        let trn = |node: i32, child: i32, t: &str| {
+            let mut c = ints(&[node, 0, child, -1, 0, 1, 1]);
+            c.extend(string("_t"));
+            c.extend(string(t));
+            chunk(b"nTRN", &c)
+        };
+        let shp = |node: i32| chunk(b"nSHP", &ints(&[node, 0, 1, 0, 0]));
+        children.push(trn(0, 1, "0 0 0"));
+        children.push(chunk(b"nGRP", &ints(&[1, 0, 2, 2, 4])));
+        children.push(trn(2, 3, &format!("{} 0 0", i32::MIN)));
+        children.push(shp(3));
+        children.push(trn(4, 5, &format!("{} 0 0", i32::MAX)));
+        children.push(shp(5));
+        assert!(read_vox(&vox(&children)).unwrap_err().contains("more than"));
+    }
+
+    /// Groups that each list the next one twice: 2^40 paths, under the depth
+    /// cap, refused after a bounded walk instead of hanging.
+    #[test]
+    fn a_scene_graph_that_expands_exponentially_is_refused() {
+        let mut children = model([1, 1, 1], &[[0, 0, 0, 1]]);
+        for level in 0..40 {
+            children.push(chunk(b"nGRP", &ints(&[level, 0, 2, level + 1, level + 1])));
+        }
+        children.push(chunk(b"nSHP", &ints(&[40, 0, 1, 0, 0])));
+        assert!(read_vox(&vox(&children)).unwrap_err().contains("expands to over"));
+    }
+
+    #[test]
+    fn rotations_decode_to_signed_permutations() {
+        // 4 = first row x, second row y, third z, all positive: identity.
+        assert_eq!(decode_rotation(4).unwrap(), [[1, 0, 0], [0, 1, 0], [0, 0, 1]]);
+        // 1 | (0 << 2) | sign on row 0: [0,-1,0],[1,0,0],[0,0,1].
+        assert_eq!(
+            decode_rotation(1 | 16).unwrap(),
+            [[0, -1, 0], [1, 0, 0], [0, 0, 1]]
+        );
+        assert!(decode_rotation(0).is_err(), "two rows on one axis");
+    }
+
+    #[test]
+    fn broken_files_are_refused_by_name() {
+        assert!(read_vox(b"PNG junk").unwrap_err().contains("not a MagicaVoxel"));
+        let mut cut = vox(&model([2, 2, 2], &[[0, 0, 0, 1]]));
+        cut.truncate(cut.len() - 3);
+        assert!(read_vox(&cut).unwrap_err().contains("ends in the middle"));
+        assert!(read_vox(&vox(&[])).unwrap_err().contains("no models"));
+        let orphan = chunk(b"XYZI", &ints(&[0]));
+        assert!(read_vox(&vox(&[orphan])).unwrap_err().contains("SIZE"));
+    }
+
+    #[test]
+    fn a_grid_too_large_to_allocate_is_refused() {
+        assert!(VoxelGrid::new((1 << 10, 1 << 10, 1 << 10)).is_err());
+        assert!(VoxelGrid::new((0, 4, 4)).is_err());
+    }
+
+    #[test]
+    fn the_top_view_shows_the_highest_cell_of_each_column() {
+        let mut grid = VoxelGrid::new((2, 1, 3)).unwrap();
+        grid.set(0, 0, 0, [1, 1, 1, 255]);
+        grid.set(0, 0, 2, [9, 9, 9, 255]);
+        let view = grid.top_view();
+        assert_eq!(view.get_pixel(0, 0).0, [9, 9, 9, 255]);
+        assert_eq!(view.get_pixel(1, 0).0, [0, 0, 0, 0]);
+    }
+}
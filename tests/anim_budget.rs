@@ -0,0 +1,91 @@
+//! `anim::budget`'s figures against real renders.
+//!
+//! The search is only as good as its bounds: a save bound that a real save
+//! can exceed would pick a render over the user's limit. Noise is the worst
+//! case for every encoding -- no runs for text mode, a change on every frame
+//! for delta mode, nothing for the compressor -- so the bound is checked on
+//! noise.
+use heightmap::anim::bricks::AnimOptions;
+use heightmap::anim::budget::{self, Budget, Space};
+use heightmap::anim::{AnimEncoding, AnimMode};
+use heightmap::progress::NoProgress;
+use heightmap::video::Clip;
+use image::{Rgba, RgbaImage};
+
+fn noise_clip(w: u32, h: u32, n: usize) -> Clip {
+    let mut state = 0x2545_f491_u32;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 17;
+        state ^= state << 5;
+        state
+    };
+    let frames = (0..n)
+        .map(|_| {
+            RgbaImage::from_fn(w, h, |_, _| {
+                let v = next().to_le_bytes();
+                Rgba([v[0], v[1], v[2], 255])
+            })
+        })
+        .collect();
+    Clip { width: w, height: h, fps: 10.0, frames }
+}
+
+const MODES: [AnimMode; 5] = [
+    AnimMode::Brick(AnimEncoding::Hex),
+    AnimMode::Brick(AnimEncoding::ColorArray),
+    AnimMode::Brick(AnimEncoding::Delta),
+    AnimMode::Brick(AnimEncoding::Palette),
+    AnimMode::Text,
+];
+
+#[test]
+fn a_written_save_never_exceeds_its_bound() {
+    for mode in MODES {
+        for (w, h, n) in [(4, 3, 2), (16, 9, 20), (32, 18, 40)] {
+            let opts = AnimOptions { colors: 1000, ..AnimOptions::default() };
+            let world = mode.build(&noise_clip(w, h, n), &opts, &mut NoProgress).expect("build");
+            let real = world.to_brz_vec().expect("encode").len();
+            let cost = mode.estimate(w, h, n, &opts).expect("estimate");
+            let bound = budget::save_bytes(mode, w, h, &cost, &opts);
+            assert!(real <= bound, "{mode:?} {w}x{h}x{n}: {real} bytes over a bound of {bound}");
+        }
+    }
+}
+
+/// What the search picks is what gets built: the fitted options render a
+/// world whose gates are the estimate's, under the budget.
+#[test]
+fn a_fitted_render_stays_under_its_gate_budget() {
+    let clip = noise_clip(32, 18, 10);
+    let space = Space {
+        size: (32, 18),
+        fps: 10.0,
+        vary_size: true,
+        vary_fps: false,
+        modes: vec![AnimMode::Brick(AnimEncoding::Hex)],
+        vary_colors: false,
+    };
+    let budget = Budget { gates: Some(400), ..Budget::default() };
+    let opts = AnimOptions::default();
+    let fit = budget::fit(&space, &opts, &budget, &|_| 10).expect("fit");
+    assert!(fit.width < 32);
+
+    let source = heightmap::video::stream::AdaptedSource {
+        inner: &clip,
+        size: Some((fit.width, fit.height)),
+        fit: heightmap::video::scale::FitMode::Exact,
+        filter: heightmap::video::scale::Filter::Nearest,
+        target_fps: fit.fps,
+        start_s: 0.0,
+        duration_s: None,
+        max_frames: 1000,
+        key: None,
+        adjust: heightmap::video::adjust::Adjustments::default(),
+    };
+    let world = fit.mode.build(&source, &opts, &mut NoProgress).expect("build");
+    // Less the chip's seven I/O pins, as `anim_playback` counts.
+    let gates = world.grids[0].1.len() - 7;
+    assert_eq!(gates, fit.cost.gates);
+    assert!(gates <= 400);
+}
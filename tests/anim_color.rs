@@ -6,10 +6,13 @@
 //! equal to the frame count) plus the two properties that are specific to this
 //! design: the detector's exec output FANS OUT to every pixel's `Get`, and no
 //! exec input ever gains a second source.
+#[path = "anim_save.rs"]
+mod anim_save;
 #[path = "wire_integrity.rs"]
 mod wire_integrity;
 
-use brdb::{IntVector, IntoReader, Position, schema::WireArrayVariant};
+use anim_save::{count_component, saved_arrays, write_and_open};
+use brdb::{IntVector, Position, schema::WireArrayVariant};
 use heightmap::anim::bricks::{
     ARRAY_GET, ARRAY_VAR, AnimOptions, BRANCH, CHANGE_DETECTOR, COMPARE_GE, DisplayBrickStyle,
     PROP_CHANGER, SELECT, build_brick_world,
@@ -40,60 +43,15 @@ fn source_color(x: u32, y: u32, f: u32) -> [u8; 4] {
     [(x * 17 + f) as u8, (y * 53 + f * 7) as u8, (x * 31 + y * 11 + f * 3) as u8, 255]
 }
 
-/// Write a world out and reopen it, returning the reader plus the chip's
-/// persistent grid id. The id is assigned at WRITE time and differs from the
-/// placeholder `Chip::entity_id`, so it has to be discovered by reading
-/// entities back -- the same thing every test in `anim_world.rs` does.
-fn write_and_open(world: &brdb::World, tag: &str) -> (std::path::PathBuf, brdb::BrReader<impl brdb::BrFsReader>, usize) {
-    let path = std::env::temp_dir().join(format!(
-        "h2b_color_{tag}_{}_{:?}.brz",
-        std::process::id(),
-        std::thread::current().id()
-    ));
-    std::fs::write(&path, world.to_brz_vec().expect("encode")).expect("write");
-    let db = brdb::Brz::open(&path).expect("reopen").into_reader();
-    let mut chip_grid_id = None;
-    for index in db.entity_chunk_index().expect("entity chunk index") {
-        for e in db.entity_chunk(index).expect("entity chunk") {
-            if e.is_microchip_grid() {
-                chip_grid_id = e.id;
-            }
-        }
-    }
-    let id = chip_grid_id.expect("the renderer must publish exactly one microchip grid");
-    (path, db, id)
-}
-
 /// Every `LinearColorArray` persisted inside the chip, in save order.
 fn saved_color_arrays(db: &brdb::BrReader<impl brdb::BrFsReader>, gid: usize) -> Vec<Vec<(f32, f32, f32, f32)>> {
-    let mut arrays = Vec::new();
-    for chunk in &db.brick_chunk_index(gid).expect("chunk index") {
-        let (_soa, structs) = db.component_chunk(gid, chunk.index).expect("components");
-        for s in &structs {
-            if s.get_name() == "BrickComponentData_WireGraphPseudo_ArrayVar"
-                && let Some(value) = s.get("Value")
-            {
-                let variant: WireArrayVariant =
-                    value.try_into().expect("ArrayVar Value must decode");
-                if let WireArrayVariant::LinearColorArray(v) = variant {
-                    arrays.push(v);
-                }
-            }
-        }
-    }
-    arrays
-}
-
-fn count_component(world: &brdb::World, class: &str) -> usize {
-    world.grids[0]
-        .1
-        .iter()
-        .filter(|b| {
-            b.components
-                .iter()
-                .any(|c| c.component_type().is_some_and(|t| t.to_string() == class))
+    saved_arrays(db, gid)
+        .into_iter()
+        .filter_map(|a| match a {
+            WireArrayVariant::LinearColorArray(v) => Some(v),
+            _ => None,
         })
-        .count()
+        .collect()
 }
 
 // --- shape ------------------------------------------------------------------
@@ -307,7 +265,7 @@ fn each_pixels_saved_array_holds_its_own_linearized_colours() {
     let (w, h, n) = (4u32, 3u32, 5u32);
     let clip = distinct_clip(w, h, n as usize);
     let world = build_color_array_world(&clip, &AnimOptions::default(), &mut NoProgress).unwrap();
-    let (path, db, gid) = write_and_open(&world, "values");
+    let (path, db, gid) = write_and_open(&world, "color_values");
 
     let arrays = saved_color_arrays(&db, gid);
     assert_eq!(arrays.len(), (w * h) as usize, "one saved array per pixel");
@@ -352,7 +310,7 @@ fn saved_colours_are_linear_not_raw_srgb() {
     let img = RgbaImage::from_pixel(1, 1, Rgba([128, 128, 128, 255]));
     let clip = Clip { width: 1, height: 1, fps: 10.0, frames: vec![img] };
     let world = build_color_array_world(&clip, &AnimOptions::default(), &mut NoProgress).unwrap();
-    let (path, db, gid) = write_and_open(&world, "linear");
+    let (path, db, gid) = write_and_open(&world, "color_linear");
 
     let arrays = saved_color_arrays(&db, gid);
     assert_eq!(arrays.len(), 1);
@@ -374,7 +332,7 @@ fn a_frame_where_a_pixel_is_culled_stores_transparent_black() {
     let clear = RgbaImage::from_pixel(1, 1, Rgba([255, 255, 255, 0]));
     let clip = Clip { width: 1, height: 1, fps: 10.0, frames: vec![opaque, clear] };
     let world = build_color_array_world(&clip, &AnimOptions::default(), &mut NoProgress).unwrap();
-    let (path, db, gid) = write_and_open(&world, "culled");
+    let (path, db, gid) = write_and_open(&world, "color_culled");
 
     let arrays = saved_color_arrays(&db, gid);
     assert_eq!(arrays[0], vec![(1.0, 1.0, 1.0, 1.0), (0.0, 0.0, 0.0, 0.0)]);
@@ -389,7 +347,7 @@ fn a_render_encodes_and_every_wire_resolves() {
         let clip = distinct_clip(5, 4, 5);
         let opts = AnimOptions { bank_size, ..AnimOptions::default() };
         let world = build_color_array_world(&clip, &opts, &mut NoProgress).expect("build");
-        let (path, _db, _gid) = write_and_open(&world, tag);
+        let (path, _db, _gid) = write_and_open(&world, &format!("color_{tag}"));
         wire_integrity::assert_wires_valid(&path);
         let _ = std::fs::remove_file(&path);
     }
@@ -467,7 +425,7 @@ fn the_chip_plane_contains_every_brick_of_a_multi_bank_render() {
         world.grids[0].1.iter().map(|b| b.position + Position::CHUNK_HALF).collect();
     assert!(!centers.is_empty());
 
-    let (path, db, _gid) = write_and_open(&world, "plane");
+    let (path, db, _gid) = write_and_open(&world, "color_plane");
     let mut extent: Option<IntVector> = None;
     let mut center: Option<IntVector> = None;
     for index in db.entity_chunk_index().expect("entity chunk index") {
@@ -530,7 +488,7 @@ fn banking_splits_each_pixels_colours_at_the_right_seam() {
     let clip = distinct_clip(w, h, n as usize);
     let opts = AnimOptions { bank_size: bank, ..AnimOptions::default() };
     let world = build_color_array_world(&clip, &opts, &mut NoProgress).expect("build");
-    let (path, db, gid) = write_and_open(&world, "seam");
+    let (path, db, gid) = write_and_open(&world, "color_seam");
 
     let arrays = saved_color_arrays(&db, gid);
     // 4 pixels x 3 banks (3 + 3 + 1).
@@ -699,7 +657,7 @@ fn boundary_constants_are_the_real_multiples_of_the_bank_size() {
     let clip = distinct_clip(2, 2, 7);
     let opts = AnimOptions { bank_size: 3, ..AnimOptions::default() };
     let world = build_color_array_world(&clip, &opts, &mut NoProgress).expect("build");
-    let (path, db, gid) = write_and_open(&world, "bounds");
+    let (path, db, gid) = write_and_open(&world, "color_bounds");
 
     let mut bounds = Vec::new();
     for chunk in &db.brick_chunk_index(gid).expect("chunk index") {
@@ -867,7 +825,7 @@ fn the_encoding_enum_dispatches_to_the_right_renderer() {
 fn temp_saves_do_not_leak() {
     let clip = distinct_clip(2, 2, 2);
     let world = build_color_array_world(&clip, &AnimOptions::default(), &mut NoProgress).unwrap();
-    let (path, _db, _gid) = write_and_open(&world, "leak");
+    let (path, _db, _gid) = write_and_open(&world, "color_leak");
     assert!(path.exists());
     let _ = std::fs::remove_file(&path);
     assert!(!path.exists());
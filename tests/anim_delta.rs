@@ -0,0 +1,113 @@
+//! Delta mode (`anim::delta_bricks`), end to end.
+//!
+//! The structural invariants every encoding keeps (`tests/anim_save.rs`:
+//! every wire resolving, no exec fan-in, the estimate matching a real
+//! render), plus the one this encoding exists for: a still pixel saves one
+//! colour however long the clip.
+#[path = "anim_save.rs"]
+mod anim_save;
+#[path = "wire_integrity.rs"]
+mod wire_integrity;
+
+use anim_save::{
+    assert_estimate_matches, assert_no_exec_fan_in, count_component, saved_arrays, write_and_open,
+};
+use brdb::schema::WireArrayVariant;
+use heightmap::anim::bricks::{ARRAY_VAR, AnimOptions};
+use heightmap::anim::delta_bricks::{GATES_PER_PIXEL, VAR, build_delta_world};
+use heightmap::anim::{AnimEncoding, cost};
+use heightmap::progress::NoProgress;
+use heightmap::video::Clip;
+use image::{Rgba, RgbaImage};
+
+/// A `w x h` clip that is one flat colour except pixel (0, 0), which steps
+/// to a new grey every `period` frames.
+fn mostly_still_clip(w: u32, h: u32, n: usize, period: usize) -> Clip {
+    let frames = (0..n)
+        .map(|f| {
+            RgbaImage::from_fn(w, h, |x, y| {
+                if (x, y) == (0, 0) {
+                    let v = (f / period * 40 % 250) as u8;
+                    Rgba([v, v, v, 255])
+                } else {
+                    Rgba([30, 120, 200, 255])
+                }
+            })
+        })
+        .collect();
+    Clip { width: w, height: h, fps: 10.0, frames }
+}
+
+/// The point of the encoding, read back out of a real save: eleven still
+/// pixels store one colour and one start each, and the moving one stores a
+/// colour per step -- where colour-array mode would store 12 x 30.
+#[test]
+fn a_still_pixel_saves_one_colour_however_long_the_clip() {
+    let clip = mostly_still_clip(4, 3, 30, 10);
+    let world = build_delta_world(&clip, &AnimOptions::default(), &mut NoProgress).expect("build");
+    let (path, db, gid) = write_and_open(&world, "delta_still");
+
+    let arrays = saved_arrays(&db, gid);
+    assert_eq!(arrays.len(), 2 * 12, "a start list and a colour list per pixel");
+    let mut colour_lengths = Vec::new();
+    let mut start_lists = Vec::new();
+    for a in arrays {
+        match a {
+            WireArrayVariant::LinearColorArray(v) => colour_lengths.push(v.len()),
+            WireArrayVariant::Int64Array(v) => start_lists.push(v),
+            other => panic!("unexpected array {other:?}"),
+        }
+    }
+    colour_lengths.sort();
+    assert_eq!(colour_lengths, [vec![1; 11], vec![3]].concat());
+    start_lists.sort();
+    assert_eq!(start_lists, [vec![vec![10, 20, 30]], vec![vec![30]; 11]].concat());
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn a_render_encodes_and_every_wire_resolves() {
+    for (bank_size, tag) in [(usize::MAX, "single"), (4usize, "multi")] {
+        let clip = mostly_still_clip(5, 4, 9, 3);
+        let opts = AnimOptions { bank_size, ..AnimOptions::default() };
+        let world = build_delta_world(&clip, &opts, &mut NoProgress).expect("build");
+        let (path, _db, _gid) = write_and_open(&world, &format!("delta_{tag}"));
+        wire_integrity::assert_wires_valid(&path);
+        let _ = std::fs::remove_file(&path);
+    }
+}
+
+/// Each pixel's exec is one chain, and exec fan-in is never produced -- the
+/// same rule the colour-array renderer keeps.
+#[test]
+fn no_exec_input_ever_has_two_sources() {
+    let clip = mostly_still_clip(6, 4, 7, 2);
+    let world = build_delta_world(&clip, &AnimOptions::default(), &mut NoProgress).expect("build");
+    assert_no_exec_fan_in(&world, "delta");
+}
+
+#[test]
+fn the_cost_estimate_matches_a_real_render() {
+    for (w, h, n, bank) in [(4u32, 3u32, 5usize, usize::MAX), (2, 2, 9, 3), (3, 3, 12, 4)] {
+        let clip = mostly_still_clip(w, h, n, 4);
+        let opts = AnimOptions { bank_size: bank, ..AnimOptions::default() };
+        let world = build_delta_world(&clip, &opts, &mut NoProgress).expect("build");
+        let est = cost::estimate_delta(w, h, n, &opts);
+        assert_estimate_matches(&world, &est, &format!("{w}x{h}x{n} bank {bank}"));
+    }
+}
+
+#[test]
+fn the_encoding_enum_dispatches_to_the_delta_renderer() {
+    assert_eq!(AnimEncoding::parse("delta"), Some(AnimEncoding::Delta));
+    let clip = mostly_still_clip(3, 2, 4, 2);
+    let opts = AnimOptions::default();
+    let world = AnimEncoding::Delta.build(&clip, &opts, &mut NoProgress).expect("delta");
+    assert_eq!(count_component(&world, VAR), 6, "one playhead index per pixel");
+    assert_eq!(count_component(&world, ARRAY_VAR), 12, "two arrays per pixel");
+    assert_eq!(
+        AnimEncoding::Delta.estimate(64, 36, 300, &opts),
+        cost::estimate_delta(64, 36, 300, &opts)
+    );
+    assert_eq!(cost::estimate_delta(64, 36, 300, &opts).gates, 64 * 36 * GATES_PER_PIXEL + 9);
+}
@@ -362,6 +362,22 @@ fn a_culled_pixel_still_reserves_its_slot() {
     assert_eq!(visible, vec![false; 3]);
 }
 
+/// Only a pixel culled in one frame and kept in another is partly culled:
+/// one culled throughout has no brick, and one never culled has no culled
+/// slot to show.
+#[test]
+fn partly_culled_needs_a_pixel_both_culled_and_kept() {
+    let (clear, opaque) = (image::Rgba([0, 0, 0, 0]), image::Rgba([9, 9, 9, 255]));
+    let mut p = Packer::new(2, 1, 128, HEX_STRIDE);
+    p.push_frame(&image::RgbaImage::from_fn(2, 1, |x, _| if x == 0 { clear } else { opaque }))
+        .expect("push");
+    p.push_frame(&image::RgbaImage::from_fn(2, 1, |x, _| if x == 0 { clear } else { opaque }))
+        .expect("push");
+    assert!(!p.partly_culled(), "pixel 0 culled throughout, pixel 1 never");
+    p.push_frame(&image::RgbaImage::from_pixel(2, 1, clear)).expect("push");
+    assert!(p.partly_culled(), "pixel 1 culled in the last frame");
+}
+
 #[test]
 fn pushing_past_the_frame_cap_errors() {
     let img = image::RgbaImage::from_pixel(1, 1, image::Rgba([1, 2, 3, 255]));
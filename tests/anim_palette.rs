@@ -0,0 +1,160 @@
+//! Palette mode (`anim::palette_bricks`), end to end.
+//!
+//! The structural invariants every encoding keeps (`tests/anim_save.rs`:
+//! every wire resolving, no exec fan-in, the estimate matching a real
+//! render), plus the one this encoding exists for: a frame string of one
+//! character per pixel that decodes, through the same lookup the chip does,
+//! to the picture.
+#[path = "anim_save.rs"]
+mod anim_save;
+#[path = "wire_integrity.rs"]
+mod wire_integrity;
+
+use anim_save::{
+    assert_estimate_matches, assert_no_exec_fan_in, count_component, saved_arrays, write_and_open,
+};
+use brdb::schema::WireArrayVariant;
+use heightmap::anim::bricks::{ARRAY_VAR, AnimOptions};
+use heightmap::anim::palette::{index_record, index_table};
+use heightmap::anim::palette_bricks::{GATES_PER_PIXEL, STRING_FIND, build_palette_world};
+use heightmap::anim::{AnimEncoding, cost};
+use heightmap::progress::NoProgress;
+use heightmap::video::Clip;
+use image::{Rgba, RgbaImage};
+
+const COLOURS: [[u8; 3]; 4] = [[255, 0, 0], [0, 255, 0], [0, 0, 255], [255, 255, 255]];
+
+/// A `w x h` clip of four flat colours, shifting one pixel per frame.
+fn four_colour_clip(w: u32, h: u32, n: usize) -> Clip {
+    let frames = (0..n)
+        .map(|f| {
+            RgbaImage::from_fn(w, h, |x, y| {
+                let [r, g, b] = COLOURS[(x as usize + y as usize + f) % 4];
+                Rgba([r, g, b, 255])
+            })
+        })
+        .collect();
+    Clip { width: w, height: h, fps: 10.0, frames }
+}
+
+/// Read back out of a real save: one character per pixel per frame, and each
+/// one, looked up the way the chip's `Find` and `Get` do, is exactly the
+/// source colour -- four colours fit a four-entry palette with no rounding.
+#[test]
+fn a_frame_string_is_one_character_per_pixel_and_decodes_to_the_picture() {
+    let (w, h, n) = (5u32, 3u32, 6usize);
+    let clip = four_colour_clip(w, h, n);
+    let opts = AnimOptions { colors: 4, ..AnimOptions::default() };
+    let world = build_palette_world(&clip, &opts, &mut NoProgress).expect("build");
+    let (path, db, gid) = write_and_open(&world, "palette_decode");
+
+    let mut frames = None;
+    let mut palette = None;
+    for a in saved_arrays(&db, gid) {
+        match a {
+            WireArrayVariant::StringArray(v) => assert!(frames.replace(v).is_none(), "one chunk"),
+            WireArrayVariant::LinearColorArray(v) => {
+                assert!(palette.replace(v).is_none(), "one shared palette")
+            }
+            other => panic!("unexpected array {other:?}"),
+        }
+    }
+    let (frames, palette) = (frames.expect("frame strings"), palette.expect("palette"));
+    assert_eq!(frames.len(), n);
+    assert_eq!(palette.len(), 4);
+
+    let table = index_table(palette.len(), 1);
+    let lut = heightmap::util::srgb_to_linear_f32_table();
+    for (f, s) in frames.iter().enumerate() {
+        assert_eq!(s.len(), (w * h) as usize, "frame {f}: one character per pixel");
+        for (i, code) in s.chars().enumerate() {
+            let offset = table.find(code).expect("every code is in the table");
+            let p = clip.frames[f].get_pixel(i as u32 % w, i as u32 / w).0;
+            let want = (lut[p[0] as usize], lut[p[1] as usize], lut[p[2] as usize], 1.0);
+            assert_eq!(palette[offset / index_record(1)], want, "frame {f} pixel {i}");
+        }
+    }
+    let _ = std::fs::remove_file(&path);
+}
+
+/// A pixel culled in only some frames shows entry 0 there, and the saved
+/// palette keeps that entry black -- whether the sample saw the cull (frame
+/// 0) or only the packer did (frame 1 of 240, which the sample skips).
+#[test]
+fn a_partly_culled_pixel_reads_a_black_entry_0() {
+    for (culled_frame, tag) in [(0usize, "sampled"), (1, "unsampled")] {
+        let mut clip = four_colour_clip(3, 2, 240);
+        clip.frames[culled_frame].get_pixel_mut(2, 1).0[3] = 0;
+        let opts = AnimOptions { colors: 5, ..AnimOptions::default() };
+        let world = build_palette_world(&clip, &opts, &mut NoProgress).expect("build");
+        let (path, db, gid) = write_and_open(&world, &format!("palette_black_{tag}"));
+        let palette = saved_arrays(&db, gid).into_iter().find_map(|a| match a {
+            WireArrayVariant::LinearColorArray(v) => Some(v),
+            _ => None,
+        });
+        let palette = palette.expect("palette");
+        assert_eq!(palette[0], (0.0, 0.0, 0.0, 1.0), "{tag}");
+        assert_eq!(palette.len(), 5, "{tag}: four colours and the black");
+        let _ = std::fs::remove_file(&path);
+    }
+}
+
+#[test]
+fn a_render_encodes_and_every_wire_resolves() {
+    for (bank_size, colors, tag) in [(usize::MAX, 0, "single"), (4usize, 100, "multi")] {
+        let clip = four_colour_clip(5, 4, 9);
+        let opts = AnimOptions { bank_size, colors, ..AnimOptions::default() };
+        let world = build_palette_world(&clip, &opts, &mut NoProgress).expect("build");
+        let (path, _db, _gid) = write_and_open(&world, &format!("palette_{tag}"));
+        wire_integrity::assert_wires_valid(&path);
+        let _ = std::fs::remove_file(&path);
+    }
+}
+
+/// Each pixel's `Get` runs off its chunk's detector, and exec fan-in is never
+/// produced -- the same rule every other renderer keeps.
+#[test]
+fn no_exec_input_ever_has_two_sources() {
+    let clip = four_colour_clip(6, 4, 7);
+    let opts = AnimOptions { bank_size: 3, ..AnimOptions::default() };
+    let world = build_palette_world(&clip, &opts, &mut NoProgress).expect("build");
+    assert_no_exec_fan_in(&world, "palette");
+}
+
+#[test]
+fn the_cost_estimate_matches_a_real_render() {
+    for (w, h, n, bank, colors) in
+        [(4u32, 3u32, 5usize, usize::MAX, 0usize), (2, 2, 9, 3, 8), (3, 3, 12, 4, 200)]
+    {
+        let clip = four_colour_clip(w, h, n);
+        let opts = AnimOptions { bank_size: bank, colors, ..AnimOptions::default() };
+        let world = build_palette_world(&clip, &opts, &mut NoProgress).expect("build");
+        let est = cost::estimate_palette(w, h, n, &opts);
+        assert_estimate_matches(&world, &est, &format!("{w}x{h}x{n} bank {bank} colors {colors}"));
+    }
+}
+
+#[test]
+fn the_encoding_enum_dispatches_to_the_palette_renderer() {
+    assert_eq!(AnimEncoding::parse("palette"), Some(AnimEncoding::Palette));
+    let clip = four_colour_clip(3, 2, 4);
+    let opts = AnimOptions::default();
+    let world = AnimEncoding::Palette.build(&clip, &opts, &mut NoProgress).expect("palette");
+    assert_eq!(count_component(&world, STRING_FIND), 6, "one lookup per pixel");
+    assert_eq!(count_component(&world, ARRAY_VAR), 2, "one chunk array and the palette");
+    assert_eq!(
+        AnimEncoding::Palette.estimate(64, 36, 300, &opts),
+        cost::estimate_palette(64, 36, 300, &opts)
+    );
+
+    // The saving the encoding is for: a sixth of hex mode's characters at the
+    // default 64 colours, a third past them.
+    let hex = cost::estimate(192, 108, 300, &opts).chars;
+    assert_eq!(cost::estimate_palette(192, 108, 300, &opts).chars * 6, hex);
+    let wide = AnimOptions { colors: 256, ..AnimOptions::default() };
+    assert_eq!(cost::estimate_palette(192, 108, 300, &wide).chars * 3, hex);
+    assert_eq!(
+        cost::estimate_palette(64, 36, 300, &opts).gates,
+        64 * 36 * GATES_PER_PIXEL + 2 + 1 + 8
+    );
+}
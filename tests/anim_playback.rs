@@ -0,0 +1,100 @@
+//! Playback orders (`AnimOptions::playback`), end to end in every mode.
+//!
+//! The clock is shared, so what a renderer can get wrong is only what it
+//! wraps around it: the order's extra gates colliding with its own, or an
+//! estimate that left them out.
+#[path = "gradient_clip.rs"]
+mod gradient_clip;
+#[path = "wire_integrity.rs"]
+mod wire_integrity;
+
+use gradient_clip::gradient_clip;
+use heightmap::anim::bricks::AnimOptions;
+use heightmap::anim::clock::{PlayOrder, Playback};
+use heightmap::anim::seek::SeekControls;
+use heightmap::anim::{AnimEncoding, AnimMode};
+use heightmap::progress::NoProgress;
+
+const MODES: [AnimMode; 5] = [
+    AnimMode::Brick(AnimEncoding::Hex),
+    AnimMode::Brick(AnimEncoding::ColorArray),
+    AnimMode::Brick(AnimEncoding::Delta),
+    AnimMode::Brick(AnimEncoding::Palette),
+    AnimMode::Text,
+];
+
+const ORDERS: [PlayOrder; 3] =
+    [PlayOrder::Forward, PlayOrder::PingPong, PlayOrder::Range { start_s: 0.2, end_s: 0.6 }];
+
+#[test]
+fn the_estimate_matches_a_render_in_every_order_and_mode() {
+    for mode in MODES {
+        let (w, h) = if mode == AnimMode::Text { (32, 16) } else { (4, 3) };
+        for order in ORDERS {
+            for plays in [None, Some(3)] {
+                let playback = Playback { order, plays };
+                let opts = AnimOptions { bank_size: 16, playback, ..AnimOptions::default() };
+                let tag = format!("{mode:?} {playback:?}");
+                let world =
+                    mode.build(&gradient_clip(w, h, 8), &opts, &mut NoProgress).expect("build");
+                let est = mode.estimate(w, h, 8, &opts).expect("estimate");
+
+                assert_eq!(world.grids[0].1.len() - 7, est.gates, "{tag}: gate count");
+                assert_eq!(world.wires.len(), est.wires, "{tag}: wire count");
+                assert_eq!(world.bricks.len(), est.bricks, "{tag}: brick count");
+            }
+        }
+    }
+}
+
+/// Hex for the clock's own gates; delta for the playheads' landing chain and
+/// keys.
+#[test]
+fn every_order_passes_wire_integrity() {
+    for (enc, order) in [AnimEncoding::Hex, AnimEncoding::Delta]
+        .into_iter()
+        .flat_map(|enc| ORDERS.map(|order| (enc, order)))
+    {
+        let opts = AnimOptions {
+            playback: Playback { order, plays: Some(2) },
+            ..AnimOptions::default()
+        };
+        let world = AnimMode::Brick(enc)
+            .build(&gradient_clip(4, 3, 8), &opts, &mut NoProgress)
+            .expect("build");
+        let path = std::env::temp_dir().join(format!(
+            "h2b_playback_{}_{order:?}_{}.brz",
+            enc.name(),
+            std::process::id()
+        ));
+        std::fs::write(&path, world.to_brz_vec().expect("encode")).expect("write");
+        let out = std::panic::catch_unwind(|| wire_integrity::assert_wires_valid(&path));
+        let _ = std::fs::remove_file(&path);
+        if let Err(e) = out {
+            std::panic::resume_unwind(e);
+        }
+    }
+}
+
+/// A seek button lands on a frame of the clip; under ping-pong or a range
+/// that frame is not a place in the order, so the two are refused together.
+#[test]
+fn seek_buttons_refuse_an_order_other_than_forward() {
+    let opts = AnimOptions {
+        seek: SeekControls { skip_s: 1.0, ..SeekControls::default() },
+        playback: Playback { order: PlayOrder::PingPong, plays: None },
+        ..AnimOptions::default()
+    };
+    let Err(err) =
+        AnimMode::Brick(AnimEncoding::Hex).build(&gradient_clip(4, 3, 8), &opts, &mut NoProgress)
+    else {
+        panic!("a seekable ping-pong render must be refused");
+    };
+    assert!(err.contains("ping-pong"), "{err}");
+
+    let zero = AnimOptions { playback: Playback { plays: Some(0), ..Playback::default() }, ..opts };
+    let Err(err) = AnimMode::Text.build(&gradient_clip(32, 16, 8), &zero, &mut NoProgress) else {
+        panic!("zero plays must be refused");
+    };
+    assert!(err.contains("at least once"), "{err}");
+}
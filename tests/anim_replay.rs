@@ -0,0 +1,237 @@
+//! `anim::replay`: a render's encoded arrays decoded back into frames.
+//!
+//! The replay is only worth looking at if it shows the encoding's losses and
+//! nothing else, so an encoding that loses nothing must replay its source
+//! exactly, and each loss -- culling, `--srgb-to-linear`, glyph cells -- must
+//! show where the encoding puts it.
+use heightmap::anim::bricks::AnimOptions;
+use heightmap::anim::clock::{PlayOrder, Playback};
+use heightmap::anim::delta_pack::DeltaPacker;
+use heightmap::anim::replay::{self, Replay};
+use heightmap::anim::{AnimEncoding, AnimMode};
+use heightmap::progress::NoProgress;
+use heightmap::subs::{Cue, Subtitles};
+use heightmap::text::PixelMode;
+use heightmap::video::Clip;
+use image::{Rgba, RgbaImage};
+use std::sync::Arc;
+
+/// Eight colours at most, so a palette of eight holds them all.
+fn blocks_clip(w: u32, h: u32, n: usize) -> Clip {
+    let frames = (0..n)
+        .map(|f| {
+            RgbaImage::from_fn(w, h, |x, y| {
+                let k = (x / 2 + y + f as u32) % 8;
+                Rgba([(k * 36) as u8, 255 - (k * 30) as u8, (k * 17) as u8, 255])
+            })
+        })
+        .collect();
+    Clip { width: w, height: h, fps: 10.0, frames }
+}
+
+fn replayed(mode: AnimMode, clip: &Clip, opts: &AnimOptions) -> Replay {
+    replay::replay(mode, clip, opts, &mut NoProgress).expect("replay")
+}
+
+#[test]
+fn a_lossless_encoding_replays_its_source_exactly() {
+    let clip = blocks_clip(6, 4, 5);
+    let palette = AnimOptions { colors: 8, ..AnimOptions::default() };
+    for (mode, opts) in [
+        (AnimMode::Brick(AnimEncoding::Hex), AnimOptions::default()),
+        (AnimMode::Brick(AnimEncoding::ColorArray), AnimOptions::default()),
+        (AnimMode::Brick(AnimEncoding::Delta), AnimOptions::default()),
+        (AnimMode::Brick(AnimEncoding::Palette), palette),
+        (AnimMode::Text, AnimOptions::default()),
+    ] {
+        let r = replayed(mode, &clip, &opts);
+        assert_eq!(r.frames, 5);
+        for (f, source) in clip.frames.iter().enumerate() {
+            assert_eq!(&r.screen(f), source, "{mode:?} frame {f}");
+        }
+    }
+}
+
+/// A pixel culled everywhere has no brick; one culled in a single frame
+/// shows what its encoding writes there.
+#[test]
+fn culled_pixels_replay_as_each_encoding_writes_them() {
+    let mut clip = blocks_clip(4, 2, 3);
+    for frame in &mut clip.frames {
+        frame.get_pixel_mut(0, 0).0[3] = 0;
+    }
+    clip.frames[1].get_pixel_mut(3, 1).0[3] = 0;
+    let (clear, black) = (Rgba([0, 0, 0, 0]), Rgba([0, 0, 0, 255]));
+    // Palette mode's all-'0' slot is entry 0, which a culling clip keeps
+    // black: the clip has no black of its own, so it is reserved.
+    let palette = AnimOptions { colors: 8, ..AnimOptions::default() };
+    for (enc, culled, opts) in [
+        (AnimEncoding::Hex, black, AnimOptions::default()),
+        (AnimEncoding::ColorArray, clear, AnimOptions::default()),
+        (AnimEncoding::Delta, clear, AnimOptions::default()),
+        (AnimEncoding::Palette, black, palette),
+    ] {
+        let r = replayed(AnimMode::Brick(enc), &clip, &opts);
+        for f in 0..3 {
+            assert_eq!(r.screen(f).get_pixel(0, 0), &clear, "{enc:?}: no brick, nothing shown");
+        }
+        assert_eq!(r.screen(1).get_pixel(3, 1), &culled, "{enc:?}");
+        assert_eq!(r.screen(2).get_pixel(3, 1), clip.frames[2].get_pixel(3, 1), "{enc:?}");
+    }
+}
+
+/// A pixel culled in a frame the palette sample skips -- every other frame
+/// of 240 -- is caught by the packer, and the clip is packed again with
+/// black kept for it.
+#[test]
+fn a_pixel_culled_between_palette_samples_still_shows_black() {
+    let mut clip = blocks_clip(4, 2, 240);
+    clip.frames[1].get_pixel_mut(3, 1).0[3] = 0;
+    // Nine: the clip's eight colours and the black it has to give up one for.
+    let opts = AnimOptions { colors: 9, ..AnimOptions::default() };
+    let r = replayed(AnimMode::Brick(AnimEncoding::Palette), &clip, &opts);
+    assert_eq!(r.screen(1).get_pixel(3, 1), &Rgba([0, 0, 0, 255]));
+    assert_eq!(r.screen(2).get_pixel(3, 1), clip.frames[2].get_pixel(3, 1));
+}
+
+/// The replay reads a delta pixel straight off its change list; the chip
+/// steps a playhead one change per frame change. Over every tick of a
+/// counted forward playback -- three passes, two wraps -- they agree.
+#[test]
+fn delta_replays_what_its_playhead_shows() {
+    let clip = blocks_clip(4, 2, 5);
+    let playback = Playback { order: PlayOrder::Forward, plays: Some(3) };
+    let opts = AnimOptions { playback, ..AnimOptions::default() };
+    let r = replayed(AnimMode::Brick(AnimEncoding::Delta), &clip, &opts);
+    let (order, _) = r.play_order();
+    assert_eq!(order.len(), 15);
+
+    let mut packer = DeltaPacker::new(4, 2, 0);
+    for frame in &clip.frames {
+        packer.push_frame(frame).unwrap();
+    }
+    let (tracks, _) = packer.finish();
+    for (i, track) in tracks.iter().enumerate() {
+        let played = track.play(order.iter().copied());
+        let read: Vec<_> = order.iter().map(|&f| track.color_at(f)).collect();
+        assert_eq!(played, read, "pixel {i}");
+    }
+}
+
+/// The same agreement under the other two orders: a ping-pong's playhead
+/// reading the clock's phase over its track laid out there and back, a loop
+/// range's resyncing on the range start.
+#[test]
+fn delta_replays_what_its_playhead_shows_in_every_order() {
+    use heightmap::anim::landing::Landings;
+    let clip = blocks_clip(4, 2, 5);
+    let mut packer = DeltaPacker::new(4, 2, 0);
+    for frame in &clip.frames {
+        packer.push_frame(frame).unwrap();
+    }
+    let (tracks, _) = packer.finish();
+    let range = PlayOrder::Range { start_s: 0.2, end_s: 0.4 };
+    for order in [PlayOrder::PingPong, range] {
+        let playback = Playback { order, plays: Some(3) };
+        let opts = AnimOptions { playback, ..AnimOptions::default() };
+        let r = replayed(AnimMode::Brick(AnimEncoding::Delta), &clip, &opts);
+        let (frames, _) = r.play_order();
+        let pass = playback.unfold(clip.fps, 5);
+        let landings = Landings::of(&playback, clip.fps, 5);
+        for (i, track) in tracks.iter().enumerate() {
+            let played = match order {
+                PlayOrder::PingPong => {
+                    track.along(&pass).play((0..frames.len()).map(|t| t % pass.len()))
+                }
+                _ => track.play_landing(frames.iter().copied(), &landings),
+            };
+            let read: Vec<_> = frames.iter().map(|&f| track.color_at(f)).collect();
+            assert_eq!(played, read, "{order:?} pixel {i}");
+        }
+    }
+}
+
+#[test]
+fn srgb_to_linear_shows_the_bytes_it_writes() {
+    let clip = blocks_clip(4, 2, 2);
+    let opts = AnimOptions { srgb_to_linear: true, ..AnimOptions::default() };
+    let r = replayed(AnimMode::Brick(AnimEncoding::Hex), &clip, &opts);
+    let shown = r.screen(0);
+    for (got, src) in shown.pixels().zip(clip.frames[0].pixels()) {
+        for c in 0..3 {
+            assert_eq!(got.0[c], heightmap::util::to_linear_gamma(src.0[c]));
+        }
+    }
+}
+
+/// A glyph mode draws its dots white wherever the source is bright, and
+/// nothing elsewhere.
+#[test]
+fn a_glyph_mode_replays_its_dots() {
+    let frame = RgbaImage::from_fn(8, 8, |x, _| {
+        if x < 4 { Rgba([255, 255, 255, 255]) } else { Rgba([0, 0, 0, 255]) }
+    });
+    let clip = Clip { width: 8, height: 8, fps: 10.0, frames: vec![frame] };
+    for mode in [PixelMode::Braille, PixelMode::Blocks, PixelMode::Sextants] {
+        let base = AnimOptions::default();
+        let opts = AnimOptions { text: heightmap::text::TextOptions { mode, ..base.text }, ..base };
+        let shown = replayed(AnimMode::Text, &clip, &opts).screen(0);
+        for (x, _, p) in shown.enumerate_pixels() {
+            let want = if x < 4 { 255 } else { 0 };
+            assert_eq!(p.0[3], want, "{mode:?} column {x}");
+        }
+    }
+}
+
+#[test]
+fn subtitles_are_drawn_on_their_own_frames() {
+    let clip = blocks_clip(16, 9, 10);
+    let track = Subtitles::new(vec![Cue { start_s: 0.3, end_s: 0.6, text: "Hi".to_string() }]);
+    let opts = AnimOptions { subtitles: Some(Arc::new(track)), ..AnimOptions::default() };
+    let r = replayed(AnimMode::Brick(AnimEncoding::Hex), &clip, &opts);
+    let scale = replay::auto_scale(16);
+    for f in 0..10 {
+        let plain = r.screen(f);
+        let shown = r.frame(f, scale);
+        let differs = shown
+            .enumerate_pixels()
+            .any(|(x, y, p)| p != plain.get_pixel(x / scale, y / scale));
+        assert_eq!(differs, (3..6).contains(&f), "frame {f}: {:?}", r.subtitle(f));
+    }
+}
+
+#[test]
+fn every_format_writes_the_play_order() {
+    let clip = blocks_clip(4, 2, 4);
+    let opts = AnimOptions {
+        playback: Playback { order: PlayOrder::PingPong, plays: None },
+        ..AnimOptions::default()
+    };
+    let r = replayed(AnimMode::Brick(AnimEncoding::Hex), &clip, &opts);
+    let (order, forever) = r.play_order();
+    assert_eq!(order, vec![0, 1, 2, 3, 2, 1]);
+    assert!(forever);
+
+    let dir = std::env::temp_dir().join(format!("h2b_replay_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    assert_eq!(replay::write(&r, &dir.join("frames"), 2).expect("pngs"), 6);
+    let fifth = image::open(dir.join("frames/frame_00004.png")).expect("open").to_rgba8();
+    assert_eq!(fifth.dimensions(), (8, 4));
+    assert_eq!(fifth.get_pixel(0, 0), clip.frames[2].get_pixel(0, 0));
+
+    let apng = dir.join("preview.png");
+    replay::write(&r, &apng, 1).expect("apng");
+    let file = std::io::BufReader::new(std::fs::File::open(&apng).expect("open"));
+    let decoder = image::codecs::png::PngDecoder::new(file).expect("png");
+    let frames = image::AnimationDecoder::into_frames(decoder.apng().unwrap())
+        .collect_frames()
+        .expect("decode");
+    assert_eq!(frames.len(), 6);
+    assert_eq!(frames[3].buffer(), &clip.frames[3]);
+
+    let gif = dir.join("preview.gif");
+    replay::write(&r, &gif, 1).expect("gif");
+    assert!(std::fs::metadata(&gif).unwrap().len() > 0);
+    assert!(replay::write(&r, &dir.join("preview.mp4"), 1).is_err());
+    let _ = std::fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,95 @@
+//! Shared helpers for the brick-mode encodings' end-to-end tests: write a
+//! world to a real save and read back what its chip persisted, count a
+//! component, and the structural checks every encoding keeps (no exec
+//! fan-in, an estimate that matches the render exactly).
+//!
+//! Declared as a module (not a crate) by consumers via
+//! `#[path = "anim_save.rs"] mod anim_save;`.
+#![allow(dead_code)]
+
+use brdb::{IntoReader, schema::WireArrayVariant};
+use heightmap::anim::cost::Cost;
+
+/// Write a world out and reopen it, returning the reader plus the chip's
+/// persistent grid id. The id is assigned at WRITE time and differs from the
+/// placeholder `Chip::entity_id`, so it has to be discovered by reading
+/// entities back -- the same thing every test in `anim_world.rs` does.
+pub fn write_and_open(
+    world: &brdb::World,
+    tag: &str,
+) -> (std::path::PathBuf, brdb::BrReader<impl brdb::BrFsReader + use<>>, usize) {
+    let path = std::env::temp_dir().join(format!(
+        "h2b_{tag}_{}_{:?}.brz",
+        std::process::id(),
+        std::thread::current().id()
+    ));
+    std::fs::write(&path, world.to_brz_vec().expect("encode")).expect("write");
+    let db = brdb::Brz::open(&path).expect("reopen").into_reader();
+    let mut chip_grid_id = None;
+    for index in db.entity_chunk_index().expect("entity chunk index") {
+        for e in db.entity_chunk(index).expect("entity chunk") {
+            if e.is_microchip_grid() {
+                chip_grid_id = e.id;
+            }
+        }
+    }
+    let id = chip_grid_id.expect("the renderer must publish exactly one microchip grid");
+    (path, db, id)
+}
+
+/// Every array persisted inside the chip, in save order.
+pub fn saved_arrays(
+    db: &brdb::BrReader<impl brdb::BrFsReader>,
+    gid: usize,
+) -> Vec<WireArrayVariant> {
+    let mut arrays = Vec::new();
+    for chunk in &db.brick_chunk_index(gid).expect("chunk index") {
+        let (_soa, structs) = db.component_chunk(gid, chunk.index).expect("components");
+        for s in &structs {
+            if s.get_name() == "BrickComponentData_WireGraphPseudo_ArrayVar"
+                && let Some(value) = s.get("Value")
+            {
+                arrays.push(value.try_into().expect("ArrayVar Value must decode"));
+            }
+        }
+    }
+    arrays
+}
+
+/// Inner-grid bricks carrying a component of `class`.
+pub fn count_component(world: &brdb::World, class: &str) -> usize {
+    world.grids[0]
+        .1
+        .iter()
+        .filter(|b| {
+            b.components
+                .iter()
+                .any(|c| c.component_type().is_some_and(|t| t.to_string() == class))
+        })
+        .count()
+}
+
+/// Exec fan-in is the unverified case, so no encoding may produce it: no
+/// exec input has two sources.
+pub fn assert_no_exec_fan_in(world: &brdb::World, tag: &str) {
+    let mut seen = std::collections::HashSet::new();
+    for wire in &world.wires {
+        let t = &wire.target;
+        let port = t.port_name.to_string();
+        if port.contains("Exec") && !port.contains("Out") {
+            assert!(
+                seen.insert((t.brick_id, port.clone())),
+                "{tag}: exec input {}.{port} has more than one source",
+                t.brick_id
+            );
+        }
+    }
+}
+
+/// The estimate is exact: gates are every inner-grid brick but the chip's
+/// seven I/O pins, and the wires and main-grid bricks match one for one.
+pub fn assert_estimate_matches(world: &brdb::World, est: &Cost, tag: &str) {
+    assert_eq!(world.grids[0].1.len() - 7, est.gates, "{tag}: gate count");
+    assert_eq!(world.wires.len(), est.wires, "{tag}: wire count");
+    assert_eq!(world.bricks.len(), est.bricks, "{tag}: brick count");
+}
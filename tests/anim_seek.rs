@@ -0,0 +1,206 @@
+//! Seek controls (`AnimOptions::seek`), end to end in every mode.
+//!
+//! The seek graph is the one piece of chip logic every renderer shares, built
+//! after each has placed its own gates, so what can break is per renderer:
+//! the rows it picks colliding with something already there, a wire into the
+//! clock's offset that does not resolve, or an estimate that forgot it.
+#[path = "gradient_clip.rs"]
+mod gradient_clip;
+#[path = "wire_integrity.rs"]
+mod wire_integrity;
+
+use gradient_clip::gradient_clip;
+use heightmap::anim::bricks::AnimOptions;
+use heightmap::anim::clock::ADD;
+use heightmap::anim::playlist::PlaylistSource;
+use heightmap::anim::seek::{self, SeekControls};
+use heightmap::anim::{AnimEncoding, AnimMode};
+use heightmap::progress::NoProgress;
+use heightmap::video::stream::FrameSource;
+
+/// Every mode that takes seek buttons. Delta's take them on marks, its
+/// pixels landing on every frame a button jumps to.
+const MODES: [AnimMode; 5] = [
+    AnimMode::Brick(AnimEncoding::Hex),
+    AnimMode::Brick(AnimEncoding::ColorArray),
+    AnimMode::Brick(AnimEncoding::Delta),
+    AnimMode::Brick(AnimEncoding::Palette),
+    AnimMode::Text,
+];
+
+/// [`MODES`] that take a playlist: delta's pixels cannot land on the frame a
+/// clip's loop point goes back to (see `delta_refuses_a_playlist`).
+const PLAYLIST_MODES: [AnimMode; 4] = [
+    AnimMode::Brick(AnimEncoding::Hex),
+    AnimMode::Brick(AnimEncoding::ColorArray),
+    AnimMode::Brick(AnimEncoding::Palette),
+    AnimMode::Text,
+];
+
+/// Every kind of button at once, with both chapters inside the 8-frame clip
+/// (frames 2 and 5 at 10 fps), which is what makes the estimate exact.
+fn every_button() -> SeekControls {
+    SeekControls { skip_s: 10.0, chapters: vec![0.2, 0.5], bar: 4, playlist: None }
+}
+
+fn count(bricks: &[brdb::Brick], class: &str) -> usize {
+    bricks
+        .iter()
+        .filter(|b| {
+            b.components.iter().any(|c| c.component_type().is_some_and(|t| t.to_string() == class))
+        })
+        .count()
+}
+
+#[test]
+fn the_estimate_matches_a_seekable_render_in_every_mode() {
+    for mode in MODES {
+        let skips = SeekControls { skip_s: 0.2, ..SeekControls::default() };
+        for seek in [every_button(), SeekControls { bar: 3, ..SeekControls::default() }, skips] {
+            let (w, h, n) = if mode == AnimMode::Text { (32, 16, 8) } else { (4, 3, 8) };
+            let opts = AnimOptions { bank_size: 16, seek: seek.clone(), ..AnimOptions::default() };
+            let world =
+                mode.build(&gradient_clip(w, h, n), &opts, &mut NoProgress).expect("build");
+            let est = mode.estimate(w, h, n, &opts).expect("estimate");
+            let tag = format!("{mode:?} {seek:?}");
+
+            // Pins are the clock's seven and one per seek button.
+            let pins = 7 + seek::button_count(&seek);
+            assert_eq!(world.grids[0].1.len() - pins, est.gates, "{tag}: gate count");
+            assert_eq!(world.wires.len(), est.wires, "{tag}: wire count");
+            assert_eq!(world.bricks.len(), est.bricks, "{tag}: brick count");
+        }
+    }
+}
+
+#[test]
+fn a_seekable_render_passes_wire_integrity_in_every_mode() {
+    for mode in MODES {
+        let (w, h) = if mode == AnimMode::Text { (32, 16) } else { (4, 3) };
+        let opts = AnimOptions { bank_size: 16, seek: every_button(), ..AnimOptions::default() };
+        let world = mode.build(&gradient_clip(w, h, 8), &opts, &mut NoProgress).expect("build");
+        with_written_save(&world, &format!("{mode:?}"), |path| {
+            wire_integrity::assert_wires_valid(path)
+        });
+    }
+}
+
+fn with_written_save(world: &brdb::World, tag: &str, check: impl FnOnce(&std::path::Path)) {
+    let path = std::env::temp_dir().join(format!(
+        "h2b_seek_{tag}_{}_{:?}.brz",
+        std::process::id(),
+        std::thread::current().id()
+    ));
+    std::fs::write(&path, world.to_brz_vec().expect("encode")).expect("write");
+    let out = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| check(&path)));
+    let _ = std::fs::remove_file(&path);
+    if let Err(e) = out {
+        std::panic::resume_unwind(e);
+    }
+}
+
+/// A playlist is one timeline to every renderer, so its buttons and (with
+/// auto-advance off) its loop check are all the estimate has to add.
+#[test]
+fn the_estimate_matches_a_playlist_render_in_every_mode() {
+    for mode in PLAYLIST_MODES {
+        let (w, h) = if mode == AnimMode::Text { (32, 16) } else { (4, 3) };
+        let (a, b, c) = (gradient_clip(w, h, 3), gradient_clip(w, h, 4), gradient_clip(w, h, 2));
+        for auto_advance in [true, false] {
+            let clips: Vec<(String, &dyn FrameSource)> =
+                vec![("a".into(), &a), ("b".into(), &b), ("c".into(), &c)];
+            let source = PlaylistSource::new(clips, auto_advance).expect("playlist");
+            let seek = SeekControls {
+                skip_s: 1.0,
+                playlist: Some(source.playlist().clone()),
+                ..SeekControls::default()
+            };
+            let opts = AnimOptions { bank_size: 16, seek: seek.clone(), ..AnimOptions::default() };
+            let world = mode.build(&source, &opts, &mut NoProgress).expect("build");
+            let n = source.playlist().frame_count();
+            assert_eq!(n, if auto_advance { 9 } else { 12 });
+            let est = mode.estimate(w, h, n, &opts).expect("estimate");
+            let tag = format!("{mode:?} auto-advance {auto_advance}");
+
+            // Prev/Next Clip, a button per clip, and the two skips.
+            assert_eq!(seek::button_count(&seek), 7, "{tag}");
+            let pins = 7 + 7;
+            assert_eq!(world.grids[0].1.len() - pins, est.gates, "{tag}: gate count");
+            assert_eq!(world.wires.len(), est.wires, "{tag}: wire count");
+            assert_eq!(world.bricks.len(), est.bricks, "{tag}: brick count");
+            if mode == AnimMode::Text {
+                continue;
+            }
+            with_written_save(&world, &format!("playlist_{auto_advance}"), |path| {
+                wire_integrity::assert_wires_valid(path)
+            });
+        }
+    }
+}
+
+/// A clip's loop point sends the clock back to the clip's start from a frame
+/// no button pressed, so a delta playlist is refused before a frame is read;
+/// the same clips as a seekable single clip build.
+#[test]
+fn delta_refuses_a_playlist() {
+    let delta = AnimMode::Brick(AnimEncoding::Delta);
+    let (a, b) = (gradient_clip(4, 3, 3), gradient_clip(4, 3, 4));
+    let clips: Vec<(String, &dyn FrameSource)> = vec![("a".into(), &a), ("b".into(), &b)];
+    let source = PlaylistSource::new(clips, true).expect("playlist");
+    let playlist = Some(source.playlist().clone());
+    let seek = SeekControls { playlist, ..SeekControls::default() };
+    let opts = AnimOptions { seek, ..AnimOptions::default() };
+    let Err(err) = delta.build(&source, &opts, &mut NoProgress) else {
+        panic!("a delta playlist must be refused");
+    };
+    assert!(err.contains("--playlist"), "{err}");
+    let opts = AnimOptions { seek: every_button(), ..AnimOptions::default() };
+    delta.build(&a, &opts, &mut NoProgress).expect("seek buttons, no refusal");
+}
+
+/// On marks, a delta render's skips jump to a frame like a bar button does
+/// -- a `Subtract` each, where a plain skip inlines its step -- and its
+/// pixels read their keys off a landing graph instead of the reset pair.
+#[test]
+fn delta_skips_jump_to_marks() {
+    let skips = SeekControls { skip_s: 0.2, ..SeekControls::default() };
+    let opts = AnimOptions { seek: skips, control_buttons: false, ..AnimOptions::default() };
+    let clip = gradient_clip(4, 3, 8);
+    let subtracts = |mode: AnimMode| {
+        let world = mode.build(&clip, &opts, &mut NoProgress).expect("build");
+        count(&world.grids[0].1, heightmap::anim::bricks::SUBTRACT)
+    };
+    assert_eq!(subtracts(AnimMode::Brick(AnimEncoding::Hex)), 0);
+    assert_eq!(subtracts(AnimMode::Brick(AnimEncoding::Delta)), 2);
+}
+
+/// No seek buttons, no offset: a render without them is the render it was
+/// before seeking existed, down to the clock having no `Add`.
+#[test]
+fn a_render_without_seek_buttons_has_no_offset() {
+    let world = AnimMode::Brick(AnimEncoding::Hex)
+        .build(&gradient_clip(3, 2, 5), &AnimOptions::default(), &mut NoProgress)
+        .expect("build");
+    assert_eq!(count(&world.grids[0].1, ADD), 0);
+
+    let opts = AnimOptions { seek: every_button(), ..AnimOptions::default() };
+    let world = AnimMode::Brick(AnimEncoding::Hex)
+        .build(&gradient_clip(3, 2, 5), &opts, &mut NoProgress)
+        .expect("build");
+    assert_eq!(count(&world.grids[0].1, ADD), 1, "the clock's offset");
+}
+
+/// Chapters outside the clip are dropped, so the render has fewer gates than
+/// an estimate that counted them -- never more.
+#[test]
+fn chapters_past_the_clip_only_shrink_the_render() {
+    let clip = gradient_clip(3, 2, 8);
+    let opts = AnimOptions {
+        seek: SeekControls { chapters: vec![0.3, 5.0, 9.0], ..SeekControls::default() },
+        ..AnimOptions::default()
+    };
+    let world = AnimMode::Brick(AnimEncoding::Hex).build(&clip, &opts, &mut NoProgress).unwrap();
+    let est = heightmap::anim::cost::estimate(3, 2, 8, &opts);
+    assert!(world.grids[0].1.len() - 9 < est.gates, "two chapters' chains were never built");
+    assert!(world.wires.len() < est.wires);
+}
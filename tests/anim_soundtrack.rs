@@ -0,0 +1,184 @@
+//! A screen with its soundtrack (`anim::soundtrack`), end to end.
+//!
+//! The point of building the two into one world is the single clock: one
+//! timer, its frame index reaching every chip that reads one, and a save that
+//! still encodes with every wire resolving -- for each renderer the screen can
+//! come from, and either audio mode.
+#[path = "gradient_clip.rs"]
+mod gradient_clip;
+#[path = "wire_integrity.rs"]
+mod wire_integrity;
+
+use gradient_clip::gradient_clip;
+use heightmap::anim::bricks::{AnimOptions, PanelGrid};
+use heightmap::anim::chip::{MICROCHIP_INPUT, MICROCHIP_OUTPUT};
+use heightmap::anim::clock::TIMER;
+use heightmap::anim::soundtrack::build_with_soundtrack;
+use heightmap::anim::{AnimEncoding, AnimMode};
+use heightmap::audio::speakers::{AUDIO_EMITTER, Soundtrack};
+use heightmap::audio::track::{AudioOptions, VoiceTrack, band_plan};
+use heightmap::audio::voices::{VoiceStats, VoiceStreams};
+use heightmap::progress::NoProgress;
+
+fn bank(frames: usize, fps: f32) -> Soundtrack {
+    let opts = AudioOptions { bands: Some(6), ..AudioOptions::default() };
+    let plan = band_plan(&opts).expect("plan");
+    let n = plan.len();
+    let volumes = vec![vec![0.5; frames]; n];
+    Soundtrack::Bank(VoiceTrack { plan, volumes, fps, frame_count: frames })
+}
+
+fn voices(frames: usize, fps: f32) -> Soundtrack {
+    Soundtrack::Voice(VoiceStreams {
+        pitches: vec![vec![1.0; frames]; 3],
+        volumes: vec![vec![0.5; frames]; 3],
+        fps,
+        frame_count: frames,
+        stats: VoiceStats::default(),
+    })
+}
+
+fn has(brick: &brdb::Brick, class: &str) -> bool {
+    brick.components.iter().any(|c| c.component_type().is_some_and(|t| t.to_string() == class))
+}
+
+fn all_bricks(world: &brdb::World) -> impl Iterator<Item = &brdb::Brick> {
+    world.bricks.iter().chain(world.grids.iter().flat_map(|(_, b)| b))
+}
+
+/// One timer for the whole world, and every chip input pin not fed from the
+/// main grid (where the control buttons are) fed by another chip's output pin
+/// -- the screen's `Frame` pins, reading the speakers' `Frame Index`.
+fn assert_one_clock(world: &brdb::World, tag: &str) {
+    assert_eq!(all_bricks(world).filter(|b| has(b, TIMER)).count(), 1, "{tag}: one timer");
+    let main: Vec<usize> = world.bricks.iter().filter_map(|b| b.id).collect();
+    let outputs: Vec<usize> =
+        all_bricks(world).filter(|b| has(b, MICROCHIP_OUTPUT)).filter_map(|b| b.id).collect();
+    let inputs: Vec<usize> =
+        all_bricks(world).filter(|b| has(b, MICROCHIP_INPUT)).filter_map(|b| b.id).collect();
+    let mut frame_pins = 0;
+    for id in inputs {
+        let feeds: Vec<_> = world
+            .wires
+            .iter()
+            .filter(|w| w.target.brick_id == id && !main.contains(&w.source.brick_id))
+            .collect();
+        assert!(feeds.len() <= 1, "{tag}: input pin {id} has one source at most");
+        if let [feed] = feeds.as_slice() {
+            assert!(outputs.contains(&feed.source.brick_id), "{tag}: pin {id} fed by a pin");
+            frame_pins += 1;
+        }
+    }
+    assert!(frame_pins >= 1, "{tag}: the screen reads the speakers' index");
+}
+
+fn encode_and_check(world: &brdb::World, tag: &str) {
+    let path = std::env::temp_dir().join(format!(
+        "h2b_soundtrack_{tag}_{}_{:?}.brz",
+        std::process::id(),
+        std::thread::current().id()
+    ));
+    std::fs::write(&path, world.to_brz_vec().expect("encode")).expect("write");
+    wire_integrity::assert_wires_valid(&path);
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn every_screen_mode_shares_the_speakers_clock() {
+    let modes = [
+        AnimMode::Brick(AnimEncoding::Hex),
+        AnimMode::Brick(AnimEncoding::ColorArray),
+        AnimMode::Brick(AnimEncoding::Delta),
+        AnimMode::Brick(AnimEncoding::Palette),
+        AnimMode::Text,
+    ];
+    let clip = gradient_clip(6, 4, 5);
+    for mode in modes {
+        for (track, kind) in [(bank(5, 10.0), "bank"), (voices(5, 10.0), "voice")] {
+            let tag = format!("{mode:?}_{kind}").replace(['(', ')'], "");
+            let opts = AnimOptions::default();
+            let world = build_with_soundtrack(
+                &mode,
+                &clip,
+                &opts,
+                track,
+                &AudioOptions::default(),
+                &mut NoProgress,
+            )
+            .unwrap_or_else(|e| panic!("{tag}: {e}"));
+            assert!(
+                all_bricks(&world).any(|b| has(b, AUDIO_EMITTER)),
+                "{tag}: the speakers are in the world"
+            );
+            assert_one_clock(&world, &tag);
+            encode_and_check(&world, &tag);
+        }
+    }
+}
+
+/// A track longer or shorter than the picture still builds: it is cut or
+/// padded to the frame count the screen actually rendered.
+#[test]
+fn a_track_of_the_wrong_length_is_fitted_to_the_picture() {
+    let clip = gradient_clip(4, 3, 6);
+    for frames in [2, 40] {
+        let world = build_with_soundtrack(
+            &AnimMode::Brick(AnimEncoding::Hex),
+            &clip,
+            &AnimOptions::default(),
+            bank(frames, 10.0),
+            &AudioOptions::default(),
+            &mut NoProgress,
+        )
+        .expect("build");
+        assert_one_clock(&world, &format!("{frames} frames"));
+    }
+}
+
+/// A split screen's first panel reads the speakers' index; the rest read it
+/// from the first, as they would from its own clock.
+#[test]
+fn a_video_wall_plays_from_the_speakers_clock() {
+    let opts = AnimOptions { panels: PanelGrid { cols: 2, rows: 2 }, ..AnimOptions::default() };
+    let world = build_with_soundtrack(
+        &AnimMode::Brick(AnimEncoding::Hex),
+        &gradient_clip(6, 4, 5),
+        &opts,
+        voices(5, 10.0),
+        &AudioOptions::default(),
+        &mut NoProgress,
+    )
+    .expect("build");
+    assert_one_clock(&world, "wall");
+    encode_and_check(&world, "wall");
+}
+
+#[test]
+fn an_external_clock_or_a_mismatched_rate_is_refused() {
+    let clip = gradient_clip(4, 3, 3);
+    let hex = AnimMode::Brick(AnimEncoding::Hex);
+    let external = AnimOptions { external_clock: true, ..AnimOptions::default() };
+    let Err(err) = build_with_soundtrack(
+        &hex,
+        &clip,
+        &external,
+        bank(3, 10.0),
+        &AudioOptions::default(),
+        &mut NoProgress,
+    ) else {
+        panic!("an external clock is refused");
+    };
+    assert!(err.contains("external clock"), "{err}");
+
+    let Err(err) = build_with_soundtrack(
+        &hex,
+        &clip,
+        &AnimOptions::default(),
+        bank(3, 30.0),
+        &AudioOptions::default(),
+        &mut NoProgress,
+    ) else {
+        panic!("30 fps audio under a 10 fps picture is refused");
+    };
+    assert!(err.contains("fps"), "{err}");
+}
@@ -374,6 +374,25 @@ fn the_text_cost_estimate_matches_a_real_render() {
     }
 }
 
+/// The same in a monochrome glyph mode, whose layout bands whole glyph lines
+/// rather than worst-case colour rows.
+#[test]
+fn the_text_cost_estimate_matches_a_glyph_mode_render() {
+    use heightmap::anim::cost;
+    use heightmap::text::PixelMode;
+    for mode in [PixelMode::Braille, PixelMode::Blocks, PixelMode::Octants] {
+        let mut opts = AnimOptions { bank_size: 3, ..AnimOptions::default() };
+        opts.text.mode = mode;
+        let world = AnimMode::Text
+            .build(&support::clip(48, 30, 7), &opts, &mut NoProgress)
+            .expect("build");
+        let est = cost::estimate_text(48, 30, 7, &opts).expect("a legal geometry must estimate");
+        assert_eq!(world.grids[0].1.len() - 7, est.gates, "{mode:?}: gate count");
+        assert_eq!(world.wires.len(), est.wires, "{mode:?}: wire count");
+        assert_eq!(world.bricks.len(), est.bricks, "{mode:?}: brick count");
+    }
+}
+
 /// The same with a subtitle track, so `cost::subtitle_cost`'s contribution to
 /// the text estimate is measured against a render too.
 #[test]
@@ -0,0 +1,135 @@
+//! Multi-chip video walls (`AnimOptions::panels`), end to end.
+//!
+//! A split hex render keeps every invariant a one-chip render has (every
+//! wire resolving, the estimate matching the render) per chip, and adds the
+//! one a wall exists for: one clock, on the first chip, feeding every other
+//! chip's frame index.
+#[path = "gradient_clip.rs"]
+mod gradient_clip;
+#[path = "wire_integrity.rs"]
+mod wire_integrity;
+
+use gradient_clip::gradient_clip;
+use heightmap::anim::bricks::{
+    AnimOptions, MAKE_COLOR_HEX, PROP_CHANGER, PanelGrid, build_brick_world,
+};
+use heightmap::anim::chip::{MICROCHIP_INPUT, MICROCHIP_OUTPUT};
+use heightmap::anim::clock::TIMER;
+use heightmap::anim::cost;
+use heightmap::progress::NoProgress;
+
+fn has(brick: &brdb::Brick, class: &str) -> bool {
+    brick.components.iter().any(|c| c.component_type().is_some_and(|t| t.to_string() == class))
+}
+
+fn count(bricks: &[brdb::Brick], class: &str) -> usize {
+    bricks.iter().filter(|b| has(b, class)).count()
+}
+
+fn wall(cols: u32, rows: u32, bank_size: usize) -> AnimOptions {
+    AnimOptions { panels: PanelGrid { cols, rows }, bank_size, ..AnimOptions::default() }
+}
+
+/// One chip per panel, each carrying exactly the gates `estimate_chips` says
+/// it does, and the whole render what `estimate` says.
+#[test]
+fn every_panel_gets_its_own_chip_and_the_estimate_matches_each() {
+    for (w, h, n, cols, rows, bank) in
+        [(5u32, 4u32, 7usize, 2u32, 2u32, 3usize), (9, 2, 4, 3, 1, usize::MAX), (4, 6, 5, 1, 3, 2)]
+    {
+        let clip = gradient_clip(w, h, n);
+        let opts = wall(cols, rows, bank);
+        let world = build_brick_world(&clip, &opts, &mut NoProgress).expect("build");
+        let chips = cost::estimate_chips(w, h, n, &opts);
+        let tag = format!("{w}x{h}x{n} as {cols}x{rows} bank {bank}");
+        assert_eq!(world.grids.len(), (cols * rows) as usize, "{tag}: one chip per panel");
+        assert_eq!(chips.len(), world.grids.len(), "{tag}: one estimate per chip");
+
+        for (i, ((_, bricks), est)) in world.grids.iter().zip(&chips).enumerate() {
+            // The first chip's pins are the clock's seven and its `Frame
+            // Index` output; every other chip's is its one `Frame` input.
+            let pins = count(bricks, MICROCHIP_INPUT) + count(bricks, MICROCHIP_OUTPUT);
+            assert_eq!(pins, if i == 0 { 8 } else { 1 }, "{tag}: chip {i} pins");
+            assert_eq!(bricks.len() - pins, est.gates, "{tag}: chip {i} gate count");
+        }
+        let total = cost::estimate(w, h, n, &opts);
+        assert_eq!(world.wires.len(), total.wires, "{tag}: wire count");
+        assert_eq!(world.bricks.len(), total.bricks, "{tag}: brick count");
+        assert_eq!(total.pixels, (w * h) as usize);
+    }
+}
+
+/// The first chip's timer is the only one, and every other chip's `Frame`
+/// pin is fed straight from one output pin on the first chip.
+#[test]
+fn one_clock_drives_every_panel() {
+    let opts = wall(3, 2, usize::MAX);
+    let world = build_brick_world(&gradient_clip(6, 4, 5), &opts, &mut NoProgress).expect("build");
+    let timers: Vec<usize> = world.grids.iter().map(|(_, b)| count(b, TIMER)).collect();
+    assert_eq!(timers, [1, 0, 0, 0, 0, 0]);
+
+    let outputs: Vec<usize> = world.grids[0]
+        .1
+        .iter()
+        .filter(|b| has(b, MICROCHIP_OUTPUT))
+        .filter_map(|b| b.id)
+        .collect();
+    let mut sources = std::collections::HashSet::new();
+    for (i, (_, bricks)) in world.grids.iter().enumerate().skip(1) {
+        let input = bricks.iter().find(|b| has(b, MICROCHIP_INPUT)).and_then(|b| b.id);
+        let input = input.expect("every other chip has a Frame pin");
+        let feeds: Vec<_> = world.wires.iter().filter(|w| w.target.brick_id == input).collect();
+        assert_eq!(feeds.len(), 1, "chip {i}: one source");
+        assert!(outputs.contains(&feeds[0].source.brick_id), "chip {i}: fed by the first chip");
+        sources.insert(feeds[0].source.brick_id);
+    }
+    assert_eq!(sources.len(), 1, "every panel reads the same pin");
+}
+
+/// Splitting moves gates between chips without losing a pixel: every display
+/// brick is still coloured by exactly one `MakeColorHex`.
+#[test]
+fn every_pixel_is_driven_exactly_once_across_the_panels() {
+    let (w, h) = (7u32, 5u32);
+    let opts = wall(3, 2, usize::MAX);
+    let world = build_brick_world(&gradient_clip(w, h, 3), &opts, &mut NoProgress).expect("build");
+    let displays: Vec<usize> =
+        world.bricks.iter().filter(|b| has(b, PROP_CHANGER)).filter_map(|b| b.id).collect();
+    assert_eq!(displays.len(), (w * h) as usize);
+    for id in displays {
+        let drivers = world
+            .wires
+            .iter()
+            .filter(|wire| wire.target.brick_id == id)
+            .filter(|wire| wire.source.component_type.to_string() == MAKE_COLOR_HEX)
+            .count();
+        assert_eq!(drivers, 1, "display brick {id}");
+    }
+}
+
+#[test]
+fn a_split_render_encodes_and_every_wire_resolves() {
+    for (external_clock, tag) in [(false, "clock"), (true, "external")] {
+        let opts = AnimOptions { external_clock, ..wall(2, 2, 2) };
+        let world =
+            build_brick_world(&gradient_clip(5, 4, 5), &opts, &mut NoProgress).expect("build");
+        let path = std::env::temp_dir().join(format!(
+            "h2b_wall_{tag}_{}_{:?}.brz",
+            std::process::id(),
+            std::thread::current().id()
+        ));
+        std::fs::write(&path, world.to_brz_vec().expect("encode")).expect("write");
+        wire_integrity::assert_wires_valid(&path);
+        let _ = std::fs::remove_file(&path);
+    }
+}
+
+/// More panels than pixels along an axis collapses to one per pixel, in the
+/// render and the estimate alike.
+#[test]
+fn a_grid_finer_than_the_picture_gives_one_panel_per_pixel() {
+    let opts = wall(8, 1, usize::MAX);
+    let world = build_brick_world(&gradient_clip(3, 2, 2), &opts, &mut NoProgress).expect("build");
+    assert_eq!(world.grids.len(), 3);
+    assert_eq!(cost::estimate_chips(3, 2, 2, &opts).len(), 3);
+}
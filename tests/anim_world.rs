@@ -11,7 +11,7 @@ use brdb::{
     schema::WireArrayVariant,
 };
 use heightmap::anim::chip;
-use heightmap::anim::clock::{build_clock, gate};
+use heightmap::anim::clock::{Playback, build_clock, gate};
 use heightmap::anim::layout::{CELL, GATE_HALF, lattice_pos};
 
 /// Stand-in for the `gate` helper Task 5 adds: emit an arbitrary brick into
@@ -141,7 +141,8 @@ fn clock_emits_four_gates_and_three_pins_without_collision() {
         15.0,
         90,
         // LOOPING, the default: this test pins the free-running Limit.
-        true,
+        Playback::default(),
+        false,
         Position { x: 5, y: 5, z: 2 },
     );
     assert_eq!(
@@ -227,7 +228,8 @@ fn written_clock(fps: f32, frames: usize, loop_playback: bool, tag: &str) -> (f6
         &mut c,
         fps,
         frames,
-        loop_playback,
+        Playback::default().with_loop(loop_playback),
+        false,
         Position { x: 5, y: 5, z: 2 },
     );
     let placed = c.placed().len() - before;
@@ -403,7 +405,8 @@ fn build_spike_world() -> (World, usize) {
     );
     let service = |col: i32, row: i32| lattice_pos(col, row, 2, h, GATE_HALF);
 
-    let clock = build_clock(&mut world, &mut c, 2.0, frames.len(), true, service(0, -2));
+    let looping = Playback::default();
+    let clock = build_clock(&mut world, &mut c, 2.0, frames.len(), looping, false, service(0, -2));
     let detector = gate(
         &mut c,
         "B_1x1_Gate_Expr_ChangeDetectorExec",
@@ -2352,3 +2352,136 @@ fn the_control_buttons_appear_on_an_audio_render_and_vanish_when_disabled() {
         }
     }
 }
+
+/// Seek buttons on a song (`build_*_world_with_seek`): the same graph as a
+/// video's, hung off the speakers' clock, so a bank and a voice render each
+/// gain exactly what [`seek::graph_cost`] and [`seek::button_count`] say, and
+/// the save's wires resolve. A ping-pong is refused, as for video.
+#[test]
+fn seek_buttons_on_a_song_add_the_seek_graph_to_the_speakers_clock() {
+    use heightmap::anim::clock::{PlayOrder, Playback};
+    use heightmap::anim::seek::{self, SeekControls};
+    use heightmap::audio::speakers::{build_speaker_world_with_seek, build_voice_world_with_seek};
+
+    // Both chapters inside the one-second track, so every button is built.
+    let seek = SeekControls { skip_s: 0.25, chapters: vec![0.2, 0.5], bar: 4, playlist: None };
+    let (gates, wires) = seek::graph_cost(&seek);
+    let buttons = seek::button_count(&seek);
+
+    let bank_opts = AudioOptions { bands: Some(6), ..AudioOptions::default() };
+    let track = tone_track(1.0, &bank_opts);
+    let voice = voice_opts(3);
+    let streams = voice_streams(1.0, &voice);
+    let renders = [
+        (
+            "bank",
+            build_speaker_world(&track, &bank_opts).expect("build"),
+            build_speaker_world_with_seek(&track, &bank_opts, &seek).expect("build"),
+        ),
+        (
+            "voice",
+            build_voice_world(&streams, &voice).expect("build"),
+            build_voice_world_with_seek(&streams, &voice, &seek).expect("build"),
+        ),
+    ];
+    for (tag, plain, seekable) in &renders {
+        // A pin per button in the chip, a button and its wire on the main grid.
+        assert_eq!(
+            seekable.grids[0].1.len() - plain.grids[0].1.len(),
+            gates + buttons,
+            "{tag}: chip bricks"
+        );
+        assert_eq!(seekable.wires.len() - plain.wires.len(), wires + buttons, "{tag}: wires");
+        assert_eq!(seekable.bricks.len() - plain.bricks.len(), buttons, "{tag}: buttons");
+
+        let path = std::env::temp_dir()
+            .join(format!("h2b_audio_seek_{tag}_{}.brz", std::process::id()));
+        std::fs::write(&path, seekable.to_brz_vec().expect("encode")).expect("write");
+        let result = std::panic::catch_unwind(|| wire_integrity::assert_wires_valid(&path));
+        let _ = std::fs::remove_file(&path);
+        if let Err(e) = result {
+            std::panic::resume_unwind(e);
+        }
+    }
+
+    let ping_pong = AudioOptions {
+        playback: Playback { order: PlayOrder::PingPong, plays: None },
+        ..bank_opts
+    };
+    let Err(err) = build_speaker_world_with_seek(&track, &ping_pong, &seek) else {
+        panic!("a seekable ping-pong must be refused");
+    };
+    assert!(err.contains("seek buttons"), "{err}");
+}
+
+/// A playlist of songs (`audio::playlist`): the analyses laid end to end on
+/// the same speakers, with a button per song plus Previous/Next on the
+/// speakers' clock, for a bank and a voice render, auto-advance on and off.
+#[test]
+fn a_playlist_of_songs_plays_them_back_to_back_on_one_set_of_speakers() {
+    use heightmap::anim::seek::{self, SeekControls};
+    use heightmap::audio::playlist::{join_streams, join_tracks};
+    use heightmap::audio::speakers::{build_speaker_world_with_seek, build_voice_world_with_seek};
+
+    let bank_opts = AudioOptions { bands: Some(6), ..AudioOptions::default() };
+    let voice = voice_opts(3);
+    for auto_advance in [true, false] {
+        let songs = vec![
+            ("intro".to_string(), tone_track(0.5, &bank_opts)),
+            ("theme".to_string(), tone_track(1.0, &bank_opts)),
+        ];
+        let lengths: Vec<usize> = songs.iter().map(|(_, t)| t.frame_count).collect();
+        let (track, playlist) = join_tracks(songs, auto_advance).expect("join");
+        let copies = if auto_advance { 0 } else { 2 };
+        assert_eq!(track.frame_count, lengths.iter().sum::<usize>() + copies);
+        assert!(track.volumes.iter().all(|row| row.len() == track.frame_count));
+        let bank_seek = SeekControls { playlist: Some(playlist), ..SeekControls::default() };
+
+        let songs = vec![
+            ("intro".to_string(), voice_streams(0.5, &voice)),
+            ("theme".to_string(), voice_streams(1.0, &voice)),
+        ];
+        let (streams, playlist) = join_streams(songs, auto_advance).expect("join");
+        assert!(streams.pitches.iter().all(|row| row.len() == streams.frame_count));
+        let voice_seek = SeekControls { playlist: Some(playlist), ..SeekControls::default() };
+
+        let renders = [
+            (
+                "bank",
+                build_speaker_world(&track, &bank_opts).expect("build"),
+                build_speaker_world_with_seek(&track, &bank_opts, &bank_seek).expect("build"),
+                &bank_seek,
+            ),
+            (
+                "voice",
+                build_voice_world(&streams, &voice).expect("build"),
+                build_voice_world_with_seek(&streams, &voice, &voice_seek).expect("build"),
+                &voice_seek,
+            ),
+        ];
+        for (tag, plain, playlist, seek) in &renders {
+            let tag = format!("{tag} auto-advance {auto_advance}");
+            // Previous/Next Clip and one button per song.
+            assert_eq!(seek::button_count(seek), 4, "{tag}");
+            assert_eq!(playlist.bricks.len() - plain.bricks.len(), 4, "{tag}: buttons");
+            let (gates, _) = seek::graph_cost(seek);
+            assert_eq!(
+                playlist.grids[0].1.len() - plain.grids[0].1.len(),
+                gates + 4,
+                "{tag}: chip bricks"
+            );
+
+            let path = std::env::temp_dir().join(format!(
+                "h2b_audio_playlist_{}_{}.brz",
+                tag.replace(' ', "_"),
+                std::process::id()
+            ));
+            std::fs::write(&path, playlist.to_brz_vec().expect("encode")).expect("write");
+            let result = std::panic::catch_unwind(|| wire_integrity::assert_wires_valid(&path));
+            let _ = std::fs::remove_file(&path);
+            if let Err(e) = result {
+                std::panic::resume_unwind(e);
+            }
+        }
+    }
+}
@@ -194,6 +194,43 @@ fn the_audio_branch_exits_nonzero() {
             "10",
         ],
     );
+    // A budget flag is parsed before the file is opened, too.
+    assert_fails(
+        "audio_max_ram",
+        "--max-ram",
+        &[wav, "--audio-mode", "bank", "--max-ram", "plenty"],
+    );
+    // A playlist of songs needs two of them, and no one song's chapters; a
+    // list of MIDI files is refused outright.
+    assert_fails("audio_playlist_one", "--playlist", &[wav, "--audio-mode", "bank", "--playlist"]);
+    assert_fails(
+        "audio_playlist_chapters",
+        "--chapters",
+        &[wav, wav, "--audio-mode", "bank", "--playlist", "--chapters", "0,5"],
+    );
+    assert_fails(
+        "midi_playlist",
+        "--playlist",
+        &["nonexistent_song.mid", "nonexistent_song.mid", "--midi", "--playlist"],
+    );
+    // A piece's seek buttons need a first-to-last order too; refused from
+    // the flags, so the file is never read.
+    assert_fails(
+        "midi_seek_loop_range",
+        "--loop-range",
+        &["nonexistent_song.mid", "--midi", "--skip-seconds", "5", "--loop-range", "1,2"],
+    );
+    // A song's seek buttons need a first-to-last order and no budget fit.
+    assert_fails(
+        "audio_seek_ping_pong",
+        "--ping-pong",
+        &[wav, "--audio-mode", "bank", "--seek-bar", "4", "--ping-pong"],
+    );
+    assert_fails(
+        "audio_seek_budget",
+        "--max-gates",
+        &[wav, "--audio-mode", "voice", "--chapters", "0,10", "--max-gates", "50000"],
+    );
     // ...and a source that cannot be opened at all.
     assert_fails("audio_missing", "nonexistent_song.wav", &[wav, "--audio-mode", "bank"]);
 }
@@ -231,6 +268,97 @@ fn the_anim_branch_exits_nonzero() {
         "subtitle",
         &with(&["--subtitles", "nonexistent.srt"]),
     );
+    assert_fails("anim_with_audio", "--audio-mode", &with(&["--with-audio", "choir"]));
+    assert_fails("anim_with_audio_image", "--with-audio", &with(&["--with-audio", "bank"]));
+    assert_fails(
+        "anim_with_audio_clock",
+        "--external-clock",
+        &with(&["--with-audio", "voice", "--external-clock"]),
+    );
+    assert_fails("anim_skip", "--skip-seconds", &with(&["--skip-seconds", "abc"]));
+    assert_fails("anim_seek_bar", "--seek-bar", &with(&["--seek-bar", "-1"]));
+    assert_fails("anim_chapters", "--chapters", &with(&["--chapters", "1:xx"]));
+    assert_fails("anim_chapters_subs", "--subtitle", &with(&["--chapters", "subtitles"]));
+    assert_fails("anim_chapters_container", "video file", &with(&["--chapters", "container"]));
+    assert_fails(
+        "anim_seek_clock",
+        "--external-clock",
+        &with(&["--seek-bar", "4", "--external-clock"]),
+    );
+    assert_fails("anim_playlist_one", "at least two", &with(&["--playlist"]));
+    let args = [png, png, "--anim-mode", "brick", "--playlist"];
+    assert_fails("anim_playlist_image", "video files only", &args);
+    assert_fails(
+        "anim_playlist_subs",
+        "--subtitles",
+        &with(&["--playlist", "--subtitles", "nonexistent.srt"]),
+    );
+    assert_fails(
+        "anim_playlist_no_loop",
+        "--no-loop",
+        &with(&["--playlist", "--no-auto-advance", "--no-loop"]),
+    );
+    assert_fails(
+        "anim_playlist_ping_pong",
+        "--ping-pong",
+        &with(&["--playlist", "--ping-pong"]),
+    );
+    assert_fails(
+        "anim_playlist_delta",
+        "--anim-encoding delta",
+        &with(&["--playlist", "--anim-encoding", "delta"]),
+    );
+    assert_fails(
+        "anim_playlist_repeat",
+        "--repeat",
+        &with(&["--playlist", "--no-auto-advance", "--repeat", "2"]),
+    );
+    assert_fails(
+        "anim_seek_range",
+        "--loop-range",
+        &with(&["--seek-bar", "4", "--loop-range", "1,2"]),
+    );
+    assert_fails("anim_range_shape", "START,END", &with(&["--loop-range", "1,2,3"]));
+    assert_fails("anim_repeat_zero", "--repeat", &with(&["--repeat", "0"]));
+    assert_fails("anim_max_gates", "--max-gates", &with(&["--max-gates", "many"]));
+    assert_fails("anim_max_save", "--max-save-size", &with(&["--max-save-size", "1.5G"]));
+    assert_fails(
+        "anim_budget_playlist",
+        "--playlist",
+        &with(&["--playlist", "--max-gates", "1000"]),
+    );
+    assert_fails(
+        "anim_preview_scale",
+        "--preview-scale",
+        &with(&["--preview", "preview.gif", "--preview-scale", "0"]),
+    );
+    assert_fails("anim_preview_format", ".mp4", &with(&["--preview", "preview.mp4"]));
+    assert_fails("anim_chroma_key", "--chroma-key", &with(&["--chroma-key", "teal"]));
+    assert_fails(
+        "anim_key_spill",
+        "--key-spill",
+        &with(&["--chroma-key", "green", "--key-spill", "2"]),
+    );
+    assert_fails("anim_key_unkeyed", "needs --chroma-key", &with(&["--key-tolerance", "10"]));
+    assert_fails("anim_crop_parse", "--crop", &with(&["--crop", "10x"]));
+    // tiny_png() is 8x8.
+    assert_fails("anim_crop_off", "does not fit", &with(&["--crop", "8x8+1+0"]));
+    assert_fails(
+        "anim_crop_auto",
+        "mutually exclusive",
+        &with(&["--crop", "2x2", "--auto-crop"]),
+    );
+    assert_fails("anim_rotate", "--rotate", &with(&["--rotate", "45"]));
+    assert_fails("anim_flip", "--flip", &with(&["--flip", "x"]));
+    assert_fails("anim_gamma", "--gamma", &with(&["--gamma", "0"]));
+    assert_fails("anim_brightness", "--brightness", &with(&["--brightness", "2"]));
+    assert_fails(
+        "anim_mask_missing",
+        "nonexistent_mask.png",
+        &with(&["--mask", "nonexistent_mask.png"]),
+    );
+    // Under the clock's own gates: no screen at all fits.
+    assert_fails("anim_budget_unmet", "nothing fits", &with(&["--max-gates", "2"]));
     assert_fails(
         "anim_missing",
         "nonexistent_clip.png",
@@ -238,6 +366,15 @@ fn the_anim_branch_exits_nonzero() {
     );
 }
 
+/// The `--sign` branch.
+#[test]
+fn the_sign_branch_exits_nonzero() {
+    assert_fails("sign_align", "--align", &["--sign-text", "hi", "--align", "middle"]);
+    assert_fails("sign_plate", "--plate", &["--sign-text", "hi", "--plate", "grey"]);
+    assert_fails("sign_wrap", "--wrap", &["--sign-text", "hi", "--wrap", "0"]);
+    assert_fails("sign_missing", "nonexistent_sign.txt", &["nonexistent_sign.txt", "--sign"]);
+}
+
 /// The `--text` branch.
 #[test]
 fn the_text_branch_exits_nonzero() {
@@ -245,7 +382,22 @@ fn the_text_branch_exits_nonzero() {
     let png = png.as_str();
 
     assert_fails("text_font", "font", &[png, "--text", "--font", "bogus"]);
+    assert_fails(
+        "text_font_file",
+        "--font-file",
+        &[png, "--text", "--font-file", "nonexistent_fonts.toml"],
+    );
     assert_fails("text_material", "material", &[png, "--text", "--material", "nope"]);
+    assert_fails(
+        "text_dither",
+        "--dither",
+        &[png, "--text", "--braille", "--dither", "sideways"],
+    );
+    assert_fails(
+        "text_palette",
+        "nonexistent_palette.hex",
+        &[png, "--text", "--palette", "nonexistent_palette.hex"],
+    );
     assert_fails(
         "text_char_repeat",
         "--char-repeat",
@@ -271,6 +423,38 @@ fn the_heightmap_branch_exits_nonzero() {
         &[png, "--vertical", "abc"],
     );
     assert_fails("hm_missing", "nonexistent_map.png", &["nonexistent_map.png", "--img"]);
+    // `--layers` needs whole bottom/top pairs and a flat-topped renderer.
+    assert_fails("hm_layers_odd", "PAIRS", &[png, "--layers"]);
+    assert_fails("hm_layers_terrain", "--terrain", &[png, png, "--layers", "--terrain"]);
+    assert_fails("hm_layers_img", "--img", &[png, png, "--layers", "--img"]);
+    // A relief carves the picture; it has no place for a second height source.
+    assert_fails("hm_relief_img", "--img", &[png, "--relief", "--img"]);
+    assert_fails("hm_relief_layers", "--layers", &[png, png, "--relief", "--layers"]);
+    assert_fails("hm_relief_depth", "--relief-depth", &[png, "--relief", "--relief-depth", "0"]);
+    assert_fails(
+        "hm_relief_depth_max",
+        "--relief-depth",
+        &[png, "--relief", "--relief-depth", "4294967295"],
+    );
+    assert_fails(
+        "hm_relief_depth_map",
+        "nonexistent_depth.png",
+        &[png, "--relief", "--depth-map", "nonexistent_depth.png"],
+    );
+    assert_fails("hm_wall", "--wall", &[png, "--wall", "z"]);
+    // Palette reduction: one palette source, a known dither, a real file.
+    assert_fails("hm_colors", "--colors", &[png, "--img", "--colors", "many"]);
+    assert_fails(
+        "hm_colors_palette",
+        "--palette",
+        &[png, "--img", "--colors", "8", "--palette", png],
+    );
+    assert_fails("hm_dither", "--dither", &[png, "--img", "--colors", "8", "--dither", "x"]);
+    assert_fails(
+        "hm_palette_missing",
+        "nonexistent_palette.hex",
+        &[png, "--img", "--palette", "nonexistent_palette.hex"],
+    );
     // An input the heightmap path has no decoder for.
     let txt = std::env::temp_dir().join(format!("h2b_exit_codes_{}.txt", std::process::id()));
     std::fs::write(&txt, b"not an image").expect("write the stub");
@@ -279,6 +463,61 @@ fn the_heightmap_branch_exits_nonzero() {
     let _ = std::fs::remove_file(&txt);
 }
 
+/// The heightmap branch with point cloud inputs.
+#[test]
+fn the_point_cloud_branch_exits_nonzero() {
+    let xyz = std::env::temp_dir().join(format!("h2b_exit_codes_{}.xyz", std::process::id()));
+    std::fs::write(&xyz, b"0 0 0\n1 1 1\n").expect("write the stub");
+    let xyz_s = xyz.to_string_lossy().to_string();
+    let xyz = xyz_s.as_str();
+    let png = tiny_png().to_string_lossy().to_string();
+
+    assert_fails("pc_cell_size", "--cell-size", &[xyz, "--cell-size", "abc"]);
+    assert_fails("pc_zero_cell_size", "--cell-size", &[xyz, "--cell-size", "0"]);
+    assert_fails("pc_cell_z", "--cell-z", &[xyz, "--cell-z", "median"]);
+    assert_fails("pc_gap_fill", "--gap-fill", &[xyz, "--gap-fill", "-1"]);
+    assert_fails("pc_mixed", "mix point clouds", &[xyz, png.as_str()]);
+    assert_fails("pc_layers", "--layers", &[xyz, xyz, "--layers"]);
+    assert_fails("pc_missing", "nonexistent_cloud.las", &["nonexistent_cloud.las"]);
+    assert_fails("pc_laz", "LAZ", &["nonexistent_cloud.laz"]);
+    let _ = std::fs::remove_file(&xyz_s);
+}
+
+/// The `--vox` branch.
+#[test]
+fn the_voxel_branch_exits_nonzero() {
+    assert_fails(
+        "vox_size_value",
+        "--size",
+        &["model.vox", "--vox", "--size", "abc"],
+    );
+    assert_fails("vox_zero_size", "--size", &["model.vox", "--vox", "--size", "0"]);
+    assert_fails("vox_missing", "nonexistent_model.vox", &["nonexistent_model.vox", "--vox"]);
+    // A file that is not a MagicaVoxel model at all.
+    let png = tiny_png().to_string_lossy().to_string();
+    assert_fails("vox_format", "not a MagicaVoxel", &[png.as_str(), "--vox"]);
+}
+
+/// The `--mesh` branch.
+#[test]
+fn the_mesh_branch_exits_nonzero() {
+    let mesh = ["model.obj", "--mesh"];
+    let with = |extra: &[&'static str]| -> Vec<&str> {
+        let mut v = mesh.to_vec();
+        v.extend_from_slice(extra);
+        v
+    };
+
+    assert_fails("mesh_and_vox", "--vox", &with(&["--vox"]));
+    assert_fails("mesh_resolution", "--resolution", &with(&["--resolution", "abc"]));
+    assert_fails("mesh_zero_resolution", "--resolution", &with(&["--resolution", "0"]));
+    assert_fails("mesh_fill", "--fill", &with(&["--fill", "hollow"]));
+    assert_fails("mesh_up", "--up", &with(&["--up", "x"]));
+    assert_fails("mesh_missing", "nonexistent_mesh.stl", &["nonexistent_mesh.stl", "--mesh"]);
+    let png = tiny_png().to_string_lossy().to_string();
+    assert_fails("mesh_format", "Unsupported mesh format", &[png.as_str(), "--mesh"]);
+}
+
 // A zero-pixel render is refused, not "Done!": `--width`/`--height` in brick
 // mode and `--size`/`--vertical` on the heightmap path, same shape.
 #[test]
@@ -305,26 +544,6 @@ fn a_zero_sized_render_is_refused_everywhere() {
     assert_fails("zero_vertical", "--vertical", &[png, "--vertical", "0"]);
 }
 
-// The two disagree by construction: `text_options` lays the geometry out for
-// braille, while the animated text encoder never consults `opts.mode` and
-// emits colour runs regardless.
-#[test]
-fn braille_under_anim_text_is_refused_rather_than_rendered_wrong() {
-    let png = tiny_png().to_string_lossy().to_string();
-    let png = png.as_str();
-
-    assert_fails(
-        "anim_text_braille",
-        "--braille",
-        &[png, "--anim-mode", "text", "--braille"],
-    );
-    assert_fails(
-        "anim_text_blocks",
-        "--blocks",
-        &[png, "--anim-mode", "text", "--blocks"],
-    );
-}
-
 /// The complementary case for every refusal above: a legal command line still
 /// renders and still exits 0.
 ///
@@ -355,3 +574,71 @@ fn a_good_command_line_still_exits_zero_and_writes_its_save() {
     assert!(out.exists(), "a valid render must write its save:\n{log}");
     let _ = std::fs::remove_file(&out);
 }
+
+/// `--preview` writes the replay and exits 0 without a save.
+#[test]
+fn a_preview_is_written_in_place_of_the_save() {
+    let png = tiny_png().to_string_lossy().to_string();
+    let out = out_path("preview");
+    let preview = out.with_extension("gif");
+
+    let result = heightmap()
+        .args([
+            png.as_str(),
+            "--anim-mode",
+            "brick",
+            "--preview",
+            preview.to_str().unwrap(),
+            "-o",
+            out.to_str().unwrap(),
+        ])
+        .output()
+        .expect("spawn the CLI");
+    let log = String::from_utf8_lossy(&result.stdout).into_owned()
+        + &String::from_utf8_lossy(&result.stderr);
+
+    assert!(result.status.success(), "a preview must exit 0:\n{log}");
+    assert!(preview.exists(), "the preview must be written:\n{log}");
+    assert!(!out.exists(), "a preview writes no save:\n{log}");
+    let _ = std::fs::remove_file(&preview);
+}
+
+/// `--crop` and `--rotate` reach the frames the render packs, not only the
+/// size it reports: the preview of a cropped, turned 8x8 is the turned crop.
+#[test]
+fn crop_and_rotate_shape_the_rendered_frames() {
+    let png = tiny_png().to_string_lossy().to_string();
+    let out = out_path("crop_rotate");
+    // No extension: numbered PNGs in a directory.
+    let dir = out.with_extension("");
+    let _ = std::fs::remove_dir_all(&dir);
+
+    let result = heightmap()
+        .args([
+            png.as_str(),
+            "--anim-mode",
+            "brick",
+            "--crop",
+            "6x2+1+3",
+            "--rotate",
+            "90",
+            "--preview",
+            dir.to_str().unwrap(),
+            "--preview-scale",
+            "1",
+            "-o",
+            out.to_str().unwrap(),
+        ])
+        .output()
+        .expect("spawn the CLI");
+    let log = String::from_utf8_lossy(&result.stdout).into_owned()
+        + &String::from_utf8_lossy(&result.stderr);
+
+    assert!(result.status.success(), "a crop and rotate must exit 0:\n{log}");
+    let frame = image::open(dir.join("frame_00000.png")).expect("open").to_rgba8();
+    assert_eq!(frame.dimensions(), (2, 6), "{log}");
+    // Turned clockwise, the crop's bottom-left source pixel (1, 4) is the
+    // top-left; tiny_png() writes x * 32 into red and y * 32 into green.
+    assert_eq!(frame.get_pixel(0, 0).0[..2], [32, 128]);
+    let _ = std::fs::remove_dir_all(&dir);
+}
@@ -86,3 +86,118 @@ fn text_mode_renders_a_save_through_the_cli() {
     let _ = std::fs::remove_file(&png);
     let _ = std::fs::remove_file(&brz);
 }
+
+/// `--sign-text` needs no input file at all -- clap must let it through
+/// without INPUT -- and writes a save.
+#[test]
+fn a_sign_renders_without_an_input_file() {
+    let brz = std::env::temp_dir().join(format!("h2b_cli_sign_{}.brz", std::process::id()));
+    let out = heightmap()
+        .args([
+            "--sign-text",
+            "OPEN\n<color=\"FF0000\">24</> hours",
+            "--align",
+            "centre",
+            "--plate",
+            "202020",
+            "-o",
+            brz.to_str().unwrap(),
+        ])
+        .output()
+        .unwrap();
+    let log = String::from_utf8_lossy(&out.stdout).into_owned() + &String::from_utf8_lossy(&out.stderr);
+    assert!(out.status.success(), "sign render must succeed: {log}");
+    assert!(brz.exists(), "no save written: {log}");
+    assert!(log.contains("2 line(s)"), "{log}");
+    let _ = std::fs::remove_file(&brz);
+}
+
+/// The sextant and octant glyph modes are reachable from the CLI next to
+/// `--braille`/`--blocks` and render a still picture.
+#[test]
+fn sextant_and_octant_modes_render_through_the_cli() {
+    let dir = std::env::temp_dir();
+    let png = dir.join(format!("h2b_cli_cells_{}.png", std::process::id()));
+    let mut img = image::RgbaImage::new(12, 12);
+    for (x, _, p) in img.enumerate_pixels_mut() {
+        let v = if x < 6 { 0xFF } else { 0 };
+        *p = image::Rgba([v, v, v, 0xFF]);
+    }
+    img.save(&png).unwrap();
+    for mode in ["--sextants", "--octants"] {
+        let brz = dir.join(format!("h2b_cli_cells_{}{mode}.brz", std::process::id()));
+        let out = heightmap()
+            .args([png.to_str().unwrap(), "-i", "--text", mode, "-o", brz.to_str().unwrap()])
+            .output()
+            .unwrap();
+        let log = String::from_utf8_lossy(&out.stdout).into_owned() + &String::from_utf8_lossy(&out.stderr);
+        assert!(out.status.success(), "{mode} render must succeed: {log}");
+        assert!(brz.exists(), "no save written for {mode}: {log}");
+        let _ = std::fs::remove_file(&brz);
+    }
+    let _ = std::fs::remove_file(&png);
+}
+
+/// `--colors` on a still colour render reduces the picture first and the
+/// log reports what that saved.
+#[test]
+fn a_still_colour_render_reports_its_palette_savings() {
+    let dir = std::env::temp_dir();
+    let png = dir.join(format!("h2b_cli_text_colors_{}.png", std::process::id()));
+    let brz = dir.join(format!("h2b_cli_text_colors_{}.brz", std::process::id()));
+    let mut img = image::RgbaImage::new(32, 32);
+    for (x, y, p) in img.enumerate_pixels_mut() {
+        *p = image::Rgba([(x * 8) as u8, (y * 8) as u8, 0x40, 0xFF]);
+    }
+    img.save(&png).unwrap();
+
+    let out = heightmap()
+        .args([png.to_str().unwrap(), "--text", "--colors", "4", "-o", brz.to_str().unwrap()])
+        .output()
+        .unwrap();
+    let log = String::from_utf8_lossy(&out.stdout).into_owned() + &String::from_utf8_lossy(&out.stderr);
+    assert!(out.status.success(), "reduced text render must succeed: {log}");
+    assert!(brz.exists(), "no save written: {log}");
+    assert!(log.contains("Palette reduction"), "{log}");
+    assert!(log.contains("fewer characters"), "{log}");
+
+    let _ = std::fs::remove_file(&png);
+    let _ = std::fs::remove_file(&brz);
+}
+
+/// The glyph modes carry through `--anim-mode text`: the save is written and
+/// the cost readout describes glyph lines, not colour runs.
+#[test]
+fn anim_text_renders_in_a_glyph_mode() {
+    let dir = std::env::temp_dir();
+    let png = dir.join(format!("h2b_cli_anim_braille_{}.png", std::process::id()));
+    let brz = dir.join(format!("h2b_cli_anim_braille_{}.brz", std::process::id()));
+    let mut img = image::RgbaImage::new(32, 16);
+    for (x, y, p) in img.enumerate_pixels_mut() {
+        let v = ((x + y) * 8) as u8;
+        *p = image::Rgba([v, v, v, 0xFF]);
+    }
+    img.save(&png).unwrap();
+
+    for mode in ["--braille", "--blocks"] {
+        let out = heightmap()
+            .args([
+                png.to_str().unwrap(),
+                "--anim-mode",
+                "text",
+                mode,
+                "--dither",
+                "ordered",
+                "-o",
+                brz.to_str().unwrap(),
+            ])
+            .output()
+            .unwrap();
+        let log = String::from_utf8_lossy(&out.stdout).into_owned() + &String::from_utf8_lossy(&out.stderr);
+        assert!(out.status.success(), "{mode} anim render must succeed: {log}");
+        assert!(brz.exists(), "no save written for {mode}: {log}");
+        assert!(log.contains("no colour tags"), "{mode}: {log}");
+        let _ = std::fs::remove_file(&brz);
+    }
+    let _ = std::fs::remove_file(&png);
+}
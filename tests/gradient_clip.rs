@@ -0,0 +1,21 @@
+//! Shared fixture: a small 10 fps clip whose every pixel differs from its
+//! neighbours and from the same pixel one frame on, so no encoding can fold
+//! two frames or two pixels together by accident.
+//!
+//! Declared as a module (not a crate) by consumers via
+//! `#[path = "gradient_clip.rs"] mod gradient_clip;`.
+#![allow(dead_code)]
+
+use heightmap::video::Clip;
+use image::{Rgba, RgbaImage};
+
+pub fn gradient_clip(w: u32, h: u32, n: usize) -> Clip {
+    let frames = (0..n)
+        .map(|f| {
+            RgbaImage::from_fn(w, h, |x, y| {
+                Rgba([(x * 40) as u8, (y * 40) as u8, (f * 20) as u8, 255])
+            })
+        })
+        .collect();
+    Clip { width: w, height: h, fps: 10.0, frames }
+}
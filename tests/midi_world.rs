@@ -187,3 +187,92 @@ fn a_uniform_tone_gives_every_speaker_the_same_wave() {
     assert!(score.voices.iter().all(|s| s.synth == SynthWave::Triangle));
     build_midi_event_world(&score, &opts).expect("build");
 }
+
+/// Every order builds, counted or not, and its save's wires resolve: the
+/// playheads read the clock's phase, so its order and stop reach them. Only a
+/// loop range starting past 0 s needs the landing chain.
+#[test]
+fn every_order_builds_and_only_a_loop_range_adds_a_landing() {
+    use heightmap::anim::clock::{PlayOrder, Playback};
+    use heightmap::anim::landing::COMPARE_EQ;
+    use heightmap::audio::percussion::PALETTE_ROLES;
+    use heightmap::midi::PercussionLane;
+    let bytes = two_instrument_smf();
+    let range = PlayOrder::Range { start_s: 0.25, end_s: 0.5 };
+    for order in [PlayOrder::Forward, PlayOrder::PingPong, range] {
+        for plays in [None, Some(2)] {
+            let opts = MidiOptions { playback: Playback { order, plays }, ..MidiOptions::default() };
+            let mut score = analyze_midi(&bytes, &opts).expect("analyze");
+            // A drum lane too, for its strike counter.
+            score.percussion_lanes.push(PercussionLane {
+                sound: PALETTE_ROLES[0].sound,
+                hits: vec![0.1, 0.3],
+            });
+            let world = build_midi_event_world(&score, &opts).expect("build");
+            let landings = world.grids[0]
+                .1
+                .iter()
+                .filter(|b| b.components.iter().any(|c| {
+                    c.component_type().is_some_and(|t| t.to_string() == COMPARE_EQ)
+                }))
+                .count();
+            assert_eq!(landings, usize::from(order == range), "{order:?} {plays:?}");
+
+            let path = std::env::temp_dir()
+                .join(format!("h2b_midi_order_{}.brz", std::process::id()));
+            std::fs::write(&path, world.to_brz_vec().expect("encode")).expect("write");
+            let result = std::panic::catch_unwind(|| wire_integrity::assert_wires_valid(&path));
+            let _ = std::fs::remove_file(&path);
+            if let Err(e) = result {
+                std::panic::resume_unwind(e);
+            }
+        }
+    }
+}
+
+/// Seek buttons on a piece: the song's seek graph, each skip jumping to a
+/// mark, and every playhead resyncing on the marks and the other seeks'
+/// frames, so its landing chain is there even under a plain first-to-last
+/// order. The save's wires resolve; a ping-pong is refused.
+#[test]
+fn seek_buttons_build_with_a_landing_on_every_playhead() {
+    use heightmap::anim::clock::{PlayOrder, Playback};
+    use heightmap::anim::landing::COMPARE_EQ;
+    use heightmap::anim::seek::{self, SeekControls};
+    let bytes = two_instrument_smf();
+    let seek = SeekControls { skip_s: 0.25, chapters: vec![0.2], bar: 4, playlist: None };
+    let plain_opts = MidiOptions::default();
+    let score = analyze_midi(&bytes, &plain_opts).expect("analyze");
+    let plain = build_midi_event_world(&score, &plain_opts).expect("build");
+    let opts = MidiOptions { seek: seek.clone(), ..MidiOptions::default() };
+    let world = build_midi_event_world(&score, &opts).expect("build");
+
+    assert_eq!(world.bricks.len() - plain.bricks.len(), seek::button_count(&seek), "buttons");
+    let (gates, _) = seek::marked_graph_cost(&seek);
+    assert!(world.grids[0].1.len() - plain.grids[0].1.len() > gates, "seek graph and landings");
+    let landings = world.grids[0]
+        .1
+        .iter()
+        .filter(|b| b.components.iter().any(|c| {
+            c.component_type().is_some_and(|t| t.to_string() == COMPARE_EQ)
+        }))
+        .count();
+    assert!(landings >= score.voices.len(), "a landing per playhead, got {landings}");
+
+    let path = std::env::temp_dir().join(format!("h2b_midi_seek_{}.brz", std::process::id()));
+    std::fs::write(&path, world.to_brz_vec().expect("encode")).expect("write");
+    let result = std::panic::catch_unwind(|| wire_integrity::assert_wires_valid(&path));
+    let _ = std::fs::remove_file(&path);
+    if let Err(e) = result {
+        std::panic::resume_unwind(e);
+    }
+
+    let ping_pong = MidiOptions {
+        playback: Playback { order: PlayOrder::PingPong, plays: None },
+        ..opts
+    };
+    let Err(err) = build_midi_event_world(&score, &ping_pong) else {
+        panic!("a seekable ping-pong must be refused");
+    };
+    assert!(err.contains("seek buttons"), "{err}");
+}